@@ -0,0 +1,266 @@
+//! In-memory conversion throughput between WKB, GeoJSON, FlatGeobuf and geo-types.
+//!
+//! Unlike `geobench`, these benchmarks need no database or web server.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use flatgeobuf::*;
+use geozero::geojson::{GeoJson, GeoJsonWriter};
+use geozero::wkb::{WkbDialect, WkbWriter};
+use geozero::wkt::WktStr;
+use geozero::{CoordDimensions, GeozeroDatasource, ProcessorSink, ToGeo, ToJson, ToWkb, ToWkt};
+use std::io::Cursor;
+
+/// Geometries of a dataset in all benchmarked representations
+struct Dataset {
+    name: &'static str,
+    wkb: Vec<Vec<u8>>,
+    geojson: Vec<String>,
+    geo: Vec<geo_types::Geometry<f64>>,
+}
+
+impl Dataset {
+    fn from_wkb(name: &'static str, wkb: Vec<Vec<u8>>) -> Self {
+        let geojson = wkb
+            .iter()
+            .map(|wkb| geozero::wkb::Wkb(wkb.clone()).to_json().unwrap())
+            .collect();
+        let geo = wkb
+            .iter()
+            .map(|wkb| geozero::wkb::Wkb(wkb.clone()).to_geo().unwrap())
+            .collect();
+        Dataset {
+            name,
+            wkb,
+            geojson,
+            geo,
+        }
+    }
+}
+
+fn points() -> Dataset {
+    let wkb = (0..10_000)
+        .map(|i| {
+            let pt = geo_types::Point::new(i as f64 * 0.001, 47.0 + i as f64 * 0.0001);
+            geo_types::Geometry::Point(pt)
+                .to_wkb(CoordDimensions::default())
+                .unwrap()
+        })
+        .collect();
+    Dataset::from_wkb("points", wkb)
+}
+
+fn countries_fgb() -> Vec<u8> {
+    std::fs::read("../geozero/tests/data/countries.fgb").unwrap()
+}
+
+/// Dense (multi)polygons of world countries
+fn polygons() -> Dataset {
+    let data = countries_fgb();
+    let mut reader = Cursor::new(data);
+    let mut fgb = FgbReader::open(&mut reader).unwrap().select_all().unwrap();
+    let mut wkb = Vec::new();
+    while let Some(feature) = fgb.next().unwrap() {
+        wkb.push(feature.to_wkb(CoordDimensions::default()).unwrap());
+    }
+    Dataset::from_wkb("polygons", wkb)
+}
+
+/// Curve geometries without GeoJSON and geo-types representation
+fn curves() -> Vec<Vec<u8>> {
+    let wkt = [
+        "CIRCULARSTRING(0 0,1 1,2 0)",
+        "COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0))",
+        "CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0,3 -1,0 -1,0 0)))",
+        "MULTICURVE((0 0,5 5),CIRCULARSTRING(4 0,4 4,8 4))",
+    ];
+    (0..2_500)
+        .flat_map(|_| wkt.iter())
+        .map(|wkt| WktStr(*wkt).to_wkb(CoordDimensions::default()).unwrap())
+        .collect()
+}
+
+fn wkb_to_geojson(wkb: &[Vec<u8>]) {
+    let mut out: Vec<u8> = Vec::new();
+    for geom in wkb {
+        let mut writer = GeoJsonWriter::new(&mut out);
+        geozero::wkb::process_wkb_geom(&mut geom.as_slice(), &mut writer).unwrap();
+    }
+}
+
+fn geojson_to_wkb(geojson: &[String]) {
+    let mut out: Vec<u8> = Vec::new();
+    let mut writer = WkbWriter::new(&mut out, WkbDialect::Wkb);
+    for geom in geojson {
+        geozero::geojson::read_geojson_geom(&mut geom.as_bytes(), &mut writer).unwrap();
+    }
+}
+
+fn geometry_benchmark(c: &mut Criterion) {
+    for dataset in &[points(), polygons()] {
+        let mut group = c.benchmark_group(format!("convert_{}", dataset.name));
+        group.throughput(Throughput::Elements(dataset.wkb.len() as u64));
+        group.bench_function("wkb_to_geojson", |b| {
+            b.iter(|| wkb_to_geojson(&dataset.wkb))
+        });
+        group.bench_function("wkb_to_geo", |b| {
+            b.iter(|| {
+                for wkb in &dataset.wkb {
+                    geozero::wkb::Wkb(wkb.clone()).to_geo().unwrap();
+                }
+            })
+        });
+        group.bench_function("geojson_to_wkb", |b| {
+            b.iter(|| geojson_to_wkb(&dataset.geojson))
+        });
+        group.bench_function("geojson_to_geo", |b| {
+            b.iter(|| {
+                for geojson in &dataset.geojson {
+                    GeoJson(geojson.as_str()).to_geo().unwrap();
+                }
+            })
+        });
+        group.bench_function("geo_to_wkb", |b| {
+            b.iter(|| {
+                for geom in &dataset.geo {
+                    geom.to_wkb(CoordDimensions::default()).unwrap();
+                }
+            })
+        });
+        group.bench_function("geo_to_geojson", |b| {
+            b.iter(|| {
+                for geom in &dataset.geo {
+                    geom.to_json().unwrap();
+                }
+            })
+        });
+        group.finish();
+    }
+}
+
+fn curves_benchmark(c: &mut Criterion) {
+    let wkb = curves();
+    let wkt: Vec<String> = wkb
+        .iter()
+        .map(|wkb| geozero::wkb::Wkb(wkb.clone()).to_wkt().unwrap())
+        .collect();
+    let mut group = c.benchmark_group("convert_curves");
+    group.throughput(Throughput::Elements(wkb.len() as u64));
+    group.bench_function("wkb_to_wkt", |b| {
+        b.iter(|| {
+            for wkb in &wkb {
+                geozero::wkb::Wkb(wkb.clone()).to_wkt().unwrap();
+            }
+        })
+    });
+    group.bench_function("wkt_to_wkb", |b| {
+        b.iter(|| {
+            for wkt in &wkt {
+                WktStr(wkt.as_str())
+                    .to_wkb(CoordDimensions::default())
+                    .unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn fgb_benchmark(c: &mut Criterion) {
+    let data = countries_fgb();
+    let mut geojson: Vec<u8> = Vec::new();
+    {
+        let mut reader = Cursor::new(&data);
+        let mut fgb = FgbReader::open(&mut reader).unwrap().select_all().unwrap();
+        fgb.process_features(&mut GeoJsonWriter::new(&mut geojson))
+            .unwrap();
+    }
+    let geojson = String::from_utf8(geojson).unwrap();
+
+    let mut group = c.benchmark_group("convert_fgb");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    for target in &["geojson", "wkb"] {
+        group.bench_with_input(BenchmarkId::new("fgb_to", target), target, |b, target| {
+            b.iter(|| {
+                let mut reader = Cursor::new(&data);
+                let mut fgb = FgbReader::open(&mut reader).unwrap().select_all().unwrap();
+                let mut out: Vec<u8> = Vec::new();
+                let result = if *target == "geojson" {
+                    fgb.process_features(&mut GeoJsonWriter::new(&mut out))
+                } else {
+                    fgb.process_features(&mut WkbWriter::new(&mut out, WkbDialect::Wkb))
+                };
+                result.unwrap();
+            })
+        });
+    }
+    group.bench_function("fgb_to_geo", |b| {
+        b.iter(|| {
+            let mut reader = Cursor::new(&data);
+            let mut fgb = FgbReader::open(&mut reader).unwrap().select_all().unwrap();
+            while let Some(feature) = fgb.next().unwrap() {
+                feature.to_geo().unwrap();
+            }
+        })
+    });
+    group.bench_function("geojson_to_fgb", |b| {
+        b.iter(|| {
+            let mut fgb = FgbWriter::create("countries", GeometryType::MultiPolygon).unwrap();
+            GeoJson(&geojson).process(&mut fgb).unwrap();
+            let mut out: Vec<u8> = Vec::new();
+            fgb.write(&mut out).unwrap();
+        })
+    });
+    group.finish();
+}
+
+/// GeoJSON features with many properties of mixed types
+fn attribute_heavy_geojson() -> String {
+    let features: Vec<String> = (0..2_000)
+        .map(|i| {
+            let properties: Vec<String> = (0..20)
+                .map(|p| match p % 5 {
+                    0 => format!(r#""name{}": "feature {} property {}""#, p, i, p),
+                    1 => format!(r#""count{}": {}"#, p, i * p),
+                    2 => format!(r#""value{}": {}.5"#, p, i),
+                    3 => format!(r#""tags{}": ["a", "b", {{"k": {}}}]"#, p, i),
+                    _ => format!(r#""empty{}": null"#, p),
+                })
+                .collect();
+            format!(
+                r#"{{"type": "Feature", "properties": {{{}}}, "geometry": {{"type": "Point", "coordinates": [{}, 47.0]}}}}"#,
+                properties.join(", "),
+                i as f64 * 0.001
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+        features.join(",\n")
+    )
+}
+
+fn properties_benchmark(c: &mut Criterion) {
+    let geojson = attribute_heavy_geojson();
+    let mut group = c.benchmark_group("properties");
+    group.throughput(Throughput::Elements(2_000 * 20));
+    group.bench_function("geojson_read", |b| {
+        b.iter(|| GeoJson(&geojson).process(&mut ProcessorSink).unwrap())
+    });
+    group.bench_function("geojson_to_geojson", |b| {
+        b.iter(|| {
+            let mut out: Vec<u8> = Vec::new();
+            GeoJson(&geojson)
+                .process(&mut GeoJsonWriter::new(&mut out))
+                .unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    geometry_benchmark,
+    curves_benchmark,
+    fgb_benchmark,
+    properties_benchmark
+);
+criterion_main!(benches);
@@ -254,6 +254,62 @@ mod gdal {
     }
 }
 
+mod writer {
+    use flatgeobuf::*;
+    use geozero::error::Result;
+    use geozero::geojson::GeoJsonWriter;
+    use geozero::wkb::{WkbDialect, WkbWriter};
+    use geozero::{FeatureProcessor, WriteCounter};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn fgb_process<P: FeatureProcessor>(processor: &mut P) -> Result<()> {
+        let mut filein = BufReader::new(File::open("tests/data/countries.fgb")?);
+        let mut fgb = FgbReader::open(&mut filein)?.select_all()?;
+        fgb.process_features(processor)
+    }
+
+    fn output(ext: &str) -> Result<File> {
+        let path = std::env::temp_dir().join(format!("geozero-bench.{}", ext));
+        Ok(File::create(path)?)
+    }
+
+    pub(super) fn fgb_to_geojson(buffered: bool) -> Result<()> {
+        let mut file = output("json")?;
+        if buffered {
+            let mut out = WriteCounter::new(&mut file);
+            fgb_process(&mut GeoJsonWriter::new(&mut out))?;
+            out.into_inner()?;
+        } else {
+            fgb_process(&mut GeoJsonWriter::new(&mut file))?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn fgb_to_wkb(buffered: bool) -> Result<()> {
+        let mut file = output("wkb")?;
+        if buffered {
+            let mut out = WriteCounter::new(&mut file);
+            fgb_process(&mut WkbWriter::new(&mut out, WkbDialect::Wkb))?;
+            out.into_inner()?;
+        } else {
+            fgb_process(&mut WkbWriter::new(&mut file, WkbDialect::Wkb))?;
+        }
+        Ok(())
+    }
+}
+
+fn writer_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("writer");
+    group.bench_function("1-geojson", |b| b.iter(|| writer::fgb_to_geojson(false)));
+    group.bench_function("2-geojson_buffered", |b| {
+        b.iter(|| writer::fgb_to_geojson(true))
+    });
+    group.bench_function("3-wkb", |b| b.iter(|| writer::fgb_to_wkb(false)));
+    group.bench_function("4-wkb_buffered", |b| b.iter(|| writer::fgb_to_wkb(true)));
+    group.finish()
+}
+
 fn countries_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("countries");
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -548,5 +604,5 @@ fn buildings_bbox_benchmark(c: &mut Criterion) {
 }
 
 criterion_group!(name=benches; config=Criterion::default().sample_size(10);
-                 targets=countries_benchmark,countries_bbox_benchmark,buildings_bbox_benchmark,buildings_benchmark);
+                 targets=countries_benchmark,countries_bbox_benchmark,buildings_bbox_benchmark,buildings_benchmark,writer_benchmark);
 criterion_main!(benches);
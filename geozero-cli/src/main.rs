@@ -1,9 +1,12 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use flatgeobuf::*;
 use geozero::csv::{CsvReader, CsvWriter};
-use geozero::error::Result;
+use geozero::error::{GeozeroError, Result};
 use geozero::geojson::{GeoJsonReader, GeoJsonWriter};
+use geozero::processing::BboxFilter;
 use geozero::svg::SvgWriter;
+#[cfg(feature = "proj")]
+use geozero::transform::ProjTransformer;
 use geozero::wkt::{WktReader, WktWriter};
 use geozero::{FeatureProcessor, GeozeroDatasource};
 use std::env;
@@ -11,18 +14,37 @@ use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::num::ParseFloatError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
+#[clap(version, about)]
 struct Cli {
-    /// The path or URL to the FlatGeobuf file to read
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert between formats, optionally filtering and reprojecting features
+    Convert(ConvertArgs),
+}
+
+#[derive(Args)]
+struct ConvertArgs {
+    /// The path or URL (FlatGeobuf only) of the file to read
     input: String,
-    /// Geometries within extent
-    #[clap(short, long, parse(try_from_str = parse_extent))]
-    extent: Option<Extent>,
     /// The path to the file to write
     #[clap(parse(from_os_str))]
-    dest: std::path::PathBuf,
+    dest: PathBuf,
+    /// Geometries within bounding box minx,miny,maxx,maxy (in source SRS)
+    #[clap(short, long, alias = "extent", parse(try_from_str = parse_extent))]
+    bbox: Option<Extent>,
+    /// Source SRS for reprojection
+    #[clap(long = "s_srs", default_value = "EPSG:4326")]
+    s_srs: String,
+    /// Reproject to SRS, e.g. EPSG:3857 (requires feature `proj`)
+    #[clap(long = "t_srs")]
+    t_srs: Option<String>,
 
     /// When processing CSV, the name of the column holding a WKT geometry.
     #[clap(long)]
@@ -53,15 +75,21 @@ fn parse_extent(src: &str) -> std::result::Result<Extent, ParseFloatError> {
     })
 }
 
-fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<()> {
+fn input_extension(args: &ConvertArgs) -> Option<&str> {
+    Path::new(&args.input).extension().and_then(OsStr::to_str)
+}
+
+/// Read input features into `processor`
+fn read<P: FeatureProcessor>(args: &ConvertArgs, processor: &mut P) -> Result<()> {
     let pathin = Path::new(&args.input);
     let mut filein = BufReader::new(File::open(pathin)?);
-    match pathin.extension().and_then(OsStr::to_str) {
+    match input_extension(args) {
         Some("csv") => {
             let geometry_column_name = args
                 .csv_geometry_column
+                .as_deref()
                 .expect("must specify --csv-geometry-column=<column name> when parsing CSV");
-            let mut ds = CsvReader::new(&geometry_column_name, &mut filein);
+            let mut ds = CsvReader::new(geometry_column_name, &mut filein);
             GeozeroDatasource::process(&mut ds, processor)?;
         }
         Some("json") | Some("geojson") => {
@@ -70,7 +98,7 @@ fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<()> {
         }
         Some("fgb") => {
             let ds = FgbReader::open(&mut filein)?;
-            let mut ds = if let Some(bbox) = &args.extent {
+            let mut ds = if let Some(bbox) = &args.bbox {
                 ds.select_bbox(bbox.minx, bbox.miny, bbox.maxx, bbox.maxy)?
             } else {
                 ds.select_all()?
@@ -86,42 +114,73 @@ fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<()> {
     Ok(())
 }
 
-fn process(args: Cli) -> Result<()> {
+/// Read input features into `processor`, skipping features outside of bbox.
+fn filter<P: FeatureProcessor>(args: &ConvertArgs, mut processor: P) -> Result<P> {
+    match args.bbox {
+        // FlatGeobuf reader selects features with spatial index
+        Some(bbox) if input_extension(args) != Some("fgb") => {
+            let bbox = [bbox.minx, bbox.miny, bbox.maxx, bbox.maxy];
+            let mut filter = BboxFilter::new(bbox, processor);
+            read(args, &mut filter)?;
+            Ok(filter.into_inner())
+        }
+        _ => {
+            read(args, &mut processor)?;
+            Ok(processor)
+        }
+    }
+}
+
+/// Read, filter and reproject input features into `processor`.
+fn convert<P: FeatureProcessor>(args: &ConvertArgs, processor: P) -> Result<P> {
+    match &args.t_srs {
+        #[cfg(feature = "proj")]
+        Some(t_srs) => {
+            let transformer = ProjTransformer::new(&args.s_srs, t_srs, processor)?;
+            Ok(filter(args, transformer)?.into_inner())
+        }
+        #[cfg(not(feature = "proj"))]
+        Some(_) => Err(GeozeroError::Dataset(
+            "reprojection requires geozero-cli feature `proj`".to_string(),
+        )),
+        None => filter(args, processor),
+    }
+}
+
+fn svg_writer<'a>(
+    args: &ConvertArgs,
+    out: &'a mut BufWriter<File>,
+) -> SvgWriter<'a, BufWriter<File>> {
+    let mut processor = SvgWriter::new(out, true);
+    if let Some(extent) = args.bbox {
+        processor.set_dimensions(extent.minx, extent.miny, extent.maxx, extent.maxy, 800, 600);
+    } else {
+        // TODO: get image size as opts and full extent from data
+        processor.set_dimensions(-180.0, -90.0, 180.0, 90.0, 800, 600);
+    }
+    processor
+}
+
+fn process(args: ConvertArgs) -> Result<()> {
     let mut fout = BufWriter::new(File::create(&args.dest)?);
     match args.dest.extension().and_then(OsStr::to_str) {
         Some("csv") => {
-            let mut processor = CsvWriter::new(&mut fout);
-            transform(args, &mut processor)?;
+            convert(&args, CsvWriter::new(&mut fout))?;
         }
         Some("fgb") => {
-            let mut fgb = FgbWriter::create("fgb", GeometryType::Unknown)?;
-            transform(args, &mut fgb)?;
+            let fgb = FgbWriter::create("fgb", GeometryType::Unknown)?;
+            let fgb = convert(&args, fgb)?;
             fgb.write(&mut fout)?;
         }
         Some("json") | Some("geojson") => {
-            let mut processor = GeoJsonWriter::new(&mut fout);
-            transform(args, &mut processor)?;
+            convert(&args, GeoJsonWriter::new(&mut fout))?;
         }
         Some("svg") => {
-            let mut processor = SvgWriter::new(&mut fout, true);
-            if let Some(extent) = args.extent {
-                processor.set_dimensions(
-                    extent.minx,
-                    extent.miny,
-                    extent.maxx,
-                    extent.maxy,
-                    800,
-                    600,
-                );
-            } else {
-                // TODO: get image size as opts and full extent from data
-                processor.set_dimensions(-180.0, -90.0, 180.0, 90.0, 800, 600);
-            }
-            transform(args, &mut processor)?;
+            let processor = svg_writer(&args, &mut fout);
+            convert(&args, processor)?;
         }
         Some("wkt") => {
-            let mut processor = WktWriter::new(&mut fout);
-            transform(args, &mut processor)?;
+            convert(&args, WktWriter::new(&mut fout))?;
         }
         _ => panic!("Unkown output file extension"),
     }
@@ -129,9 +188,14 @@ fn process(args: Cli) -> Result<()> {
 }
 
 #[tokio::main]
-async fn process_url(args: Cli) -> Result<()> {
+async fn process_url(args: ConvertArgs) -> Result<()> {
+    if args.t_srs.is_some() {
+        return Err(GeozeroError::Dataset(
+            "reprojection of remote files is not supported".to_string(),
+        ));
+    }
     let ds = HttpFgbReader::open(&args.input).await?;
-    let mut ds = if let Some(bbox) = &args.extent {
+    let mut ds = if let Some(bbox) = &args.bbox {
         ds.select_bbox(bbox.minx, bbox.miny, bbox.maxx, bbox.maxy)
             .await?
     } else {
@@ -145,19 +209,7 @@ async fn process_url(args: Cli) -> Result<()> {
             ds.process_features(&mut processor).await?;
         }
         Some("svg") => {
-            let mut processor = SvgWriter::new(&mut fout, true);
-            if let Some(extent) = args.extent {
-                processor.set_dimensions(
-                    extent.minx,
-                    extent.miny,
-                    extent.maxx,
-                    extent.maxy,
-                    800,
-                    600,
-                );
-            } else {
-                processor.set_dimensions(-180.0, -90.0, 180.0, 90.0, 800, 600);
-            }
+            let mut processor = svg_writer(&args, &mut fout);
             ds.process_features(&mut processor).await?;
         }
         _ => panic!("Unkown output format"),
@@ -166,17 +218,21 @@ async fn process_url(args: Cli) -> Result<()> {
 }
 
 fn main() {
-    let args = Cli::parse();
+    let cli = Cli::parse();
 
     if env::var("RUST_LOG").is_err() {
         env::set_var("RUST_LOG", "info");
     }
     env_logger::init();
 
-    let result = if args.input.starts_with("http") {
-        process_url(args).map_err(|e| e.to_string())
-    } else {
-        process(args).map_err(|e| e.to_string())
+    let result = match cli.command {
+        Command::Convert(args) => {
+            if args.input.starts_with("http") {
+                process_url(args).map_err(|e| e.to_string())
+            } else {
+                process(args).map_err(|e| e.to_string())
+            }
+        }
     };
     if let Err(msg) = result {
         println!("Processing failed: {}", msg);
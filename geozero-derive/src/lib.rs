@@ -0,0 +1,146 @@
+//! Derive macros for the [GeoZero](https://github.com/georust/geozero) API.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta};
+
+/// Derive `FeatureProperties`, `GeozeroGeometry` and `GeozeroDatasource`.
+///
+/// Fields are processed as properties, the field marked with `#[geozero(geometry)]`
+/// as feature geometry.
+///
+/// Field attributes:
+/// * `#[geozero(geometry)]`: Feature geometry, implementing `GeozeroGeometry`
+/// * `#[geozero(rename = "name")]`: Property name
+/// * `#[geozero(skip)]`: Field is not processed
+#[proc_macro_derive(GeozeroFeature, attributes(geozero))]
+pub fn derive_geozero_feature(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Field configuration from `#[geozero(...)]` attributes.
+#[derive(Default)]
+struct FieldAttrs {
+    geometry: bool,
+    skip: bool,
+    rename: Option<String>,
+}
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("geozero")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected #[geozero(...)]")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("geometry") => {
+                    attrs.geometry = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => attrs.skip = true,
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                    match nv.lit {
+                        Lit::Str(name) => attrs.rename = Some(name.value()),
+                        lit => return Err(Error::new_spanned(lit, "expected string literal")),
+                    }
+                }
+                other => return Err(Error::new_spanned(other, "unknown geozero attribute")),
+            }
+        }
+    }
+    Ok(attrs)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    name,
+                    "GeozeroFeature requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "GeozeroFeature can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut geometry = None;
+    let mut properties = Vec::new();
+    for field in fields {
+        let attrs = field_attrs(field)?;
+        let ident = field.ident.as_ref().expect("named field");
+        if attrs.geometry {
+            if geometry.is_some() {
+                return Err(Error::new_spanned(field, "duplicate geometry field"));
+            }
+            geometry = Some(ident);
+        } else if !attrs.skip {
+            let prop_name = attrs.rename.unwrap_or_else(|| ident.to_string());
+            properties.push(quote! {
+                if let Some(value) = ::geozero::PropertyWriteType::column_value(&self.#ident) {
+                    if processor.property(idx, #prop_name, &value)? {
+                        return Ok(true);
+                    }
+                    idx += 1;
+                }
+            });
+        }
+    }
+    let geometry = geometry.ok_or_else(|| {
+        Error::new_spanned(name, "missing field with #[geozero(geometry)] attribute")
+    })?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::geozero::FeatureProperties for #name #ty_generics #where_clause {
+            #[allow(unused_mut, unused_assignments, unused_variables)]
+            fn process_properties<P: ::geozero::PropertyProcessor>(
+                &self,
+                processor: &mut P,
+            ) -> ::geozero::error::Result<bool> {
+                let mut idx = 0;
+                #(#properties)*
+                Ok(false)
+            }
+        }
+
+        impl #impl_generics ::geozero::GeozeroGeometry for #name #ty_generics #where_clause {
+            fn process_geom<P: ::geozero::GeomProcessor>(
+                &self,
+                processor: &mut P,
+            ) -> ::geozero::error::Result<()> {
+                ::geozero::GeozeroGeometry::process_geom(&self.#geometry, processor)
+            }
+        }
+
+        impl #impl_generics ::geozero::GeozeroDatasource for #name #ty_generics #where_clause {
+            fn process<P: ::geozero::FeatureProcessor>(
+                &mut self,
+                processor: &mut P,
+            ) -> ::geozero::error::Result<()> {
+                processor.dataset_begin(None)?;
+                processor.feature_begin(0)?;
+                processor.properties_begin()?;
+                ::geozero::FeatureProperties::process_properties(&*self, processor)?;
+                processor.properties_end()?;
+                processor.geometry_begin()?;
+                ::geozero::GeozeroGeometry::process_geom(&*self, processor)?;
+                processor.geometry_end()?;
+                processor.feature_end(0)?;
+                processor.dataset_end()
+            }
+        }
+    })
+}
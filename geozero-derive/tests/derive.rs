@@ -0,0 +1,47 @@
+use geo_types::{point, Geometry};
+use geozero::error::Result;
+use geozero::{FeatureProperties, ProcessToJson};
+use geozero_derive::GeozeroFeature;
+
+#[derive(GeozeroFeature)]
+struct City {
+    name: String,
+    #[geozero(rename = "pop")]
+    population: Option<u64>,
+    #[geozero(skip)]
+    #[allow(dead_code)]
+    internal_id: u32,
+    capital: bool,
+    #[geozero(geometry)]
+    location: Geometry<f64>,
+}
+
+fn zurich(population: Option<u64>) -> City {
+    City {
+        name: "Zurich".to_string(),
+        population,
+        internal_id: 7,
+        capital: false,
+        location: Geometry::Point(point!(x: 8.54, y: 47.37)),
+    }
+}
+
+#[test]
+fn to_json() -> Result<()> {
+    assert_eq!(
+        zurich(Some(421878)).to_json()?,
+        r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "Zurich", "pop": 421878, "capital": false}, "geometry": {"type": "Point", "coordinates": [8.54,47.37]}}]}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn properties() -> Result<()> {
+    let city = zurich(None);
+    assert_eq!(city.property::<String>("name")?, "Zurich");
+    assert!(city.property::<u64>("pop").is_err());
+    assert_eq!(city.property_n::<bool>(1)?, false);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+#ifndef GEOZERO_H
+#define GEOZERO_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define GEOZERO_OK 0
+#define GEOZERO_ERROR 1
+#define GEOZERO_INVALID_ARGUMENT 2
+
+/* Message of the last error in the current thread or NULL */
+const char *geozero_last_error(void);
+
+/* Release strings and buffers returned by geozero */
+void geozero_string_free(char *s);
+void geozero_buffer_free(uint8_t *buf, size_t len);
+
+/* Geometry conversions */
+int geozero_wkb_to_geojson(const uint8_t *buf, size_t len, char **out);
+int geozero_wkb_to_wkt(const uint8_t *buf, size_t len, char **out);
+int geozero_geojson_to_wkb(const char *json, uint8_t **out, size_t *out_len);
+int geozero_geojson_to_wkt(const char *json, char **out);
+int geozero_wkt_to_wkb(const char *wkt, uint8_t **out, size_t *out_len);
+int geozero_wkt_to_geojson(const char *wkt, char **out);
+
+/*
+ * Processor implemented with callbacks.
+ *
+ * Every callback receives `user_data` as first argument and returns 0 on success.
+ * Any other return value aborts processing with an error. Callbacks may be NULL.
+ * Geometry types are passed as OGC WKB type codes (1 = Point, 2 = LineString, ...).
+ * Missing Z and M values are passed as NaN.
+ */
+typedef struct GeozeroProcessor {
+    void *user_data;
+    bool dims_z;
+    bool dims_m;
+    int (*dataset_begin)(void *user_data, const char *name);
+    int (*dataset_end)(void *user_data);
+    int (*feature_begin)(void *user_data, uint64_t idx);
+    int (*feature_end)(void *user_data, uint64_t idx);
+    int (*property)(void *user_data, size_t idx, const char *name, const char *value);
+    int (*geometry_begin)(void *user_data, uint32_t geometry_type, size_t size, size_t idx);
+    int (*geometry_end)(void *user_data, uint32_t geometry_type, size_t idx);
+    int (*coordinate)(void *user_data, double x, double y, double z, double m, size_t idx);
+} GeozeroProcessor;
+
+int geozero_process_geojson(const char *json, const GeozeroProcessor *processor);
+int geozero_process_wkt(const char *wkt, const GeozeroProcessor *processor);
+int geozero_process_wkb(const uint8_t *buf, size_t len, const GeozeroProcessor *processor);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GEOZERO_H */
@@ -0,0 +1,210 @@
+//! C API for GeoZero conversions and processing.
+//!
+//! All functions return a status code ([GEOZERO_OK] on success). The message of the
+//! last error of the calling thread is available with [geozero_last_error].
+//! Strings and buffers returned by geozero have to be released with
+//! [geozero_string_free] and [geozero_buffer_free].
+//!
+//! Custom processors are implemented in C by filling a [GeozeroProcessor] struct with
+//! callbacks and passing it to one of the `geozero_process_*` functions.
+//!
+//! See `include/geozero.h` for the C declarations.
+
+#![allow(clippy::missing_safety_doc)]
+
+mod processor;
+
+pub use processor::*;
+
+use geozero::error::{GeozeroError, Result};
+use geozero::geojson::GeoJson;
+use geozero::wkb::{Wkb, WkbDialect, WkbWriter};
+use geozero::wkt::WktStr;
+use geozero::{GeozeroGeometry, ToJson, ToWkt};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+/// Success
+pub const GEOZERO_OK: c_int = 0;
+/// Conversion or processing error
+pub const GEOZERO_ERROR: c_int = 1;
+/// Invalid argument, e.g. a NULL pointer or a string which is not valid UTF-8
+pub const GEOZERO_INVALID_ARGUMENT: c_int = 2;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(msg: String) {
+    let msg = CString::new(msg.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+}
+
+fn status<T>(result: Result<T>, out: impl FnOnce(T)) -> c_int {
+    match result {
+        Ok(v) => {
+            out(v);
+            GEOZERO_OK
+        }
+        Err(e) => {
+            set_last_error(e.to_string());
+            GEOZERO_ERROR
+        }
+    }
+}
+
+fn invalid_argument(msg: &str) -> c_int {
+    set_last_error(msg.to_string());
+    GEOZERO_INVALID_ARGUMENT
+}
+
+unsafe fn str_arg<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+unsafe fn buf_arg<'a>(buf: *const u8, len: usize) -> Option<&'a [u8]> {
+    if buf.is_null() {
+        return None;
+    }
+    Some(slice::from_raw_parts(buf, len))
+}
+
+unsafe fn return_string(s: String, out: *mut *mut c_char) {
+    *out = CString::new(s).map_or(ptr::null_mut(), CString::into_raw);
+}
+
+unsafe fn return_buffer(buf: Vec<u8>, out: *mut *mut u8, out_len: *mut usize) {
+    let buf = buf.into_boxed_slice();
+    *out_len = buf.len();
+    *out = Box::into_raw(buf) as *mut u8;
+}
+
+fn to_wkb<G: GeozeroGeometry>(geom: &G) -> Result<Vec<u8>> {
+    let mut wkb: Vec<u8> = Vec::new();
+    {
+        let mut writer = WkbWriter::new(&mut wkb, WkbDialect::Wkb);
+        writer.auto_dims = true;
+        geom.process_geom(&mut writer)?;
+    }
+    Ok(wkb)
+}
+
+/// Message of the last error in the current thread or NULL.
+///
+/// The returned string is owned by geozero and valid until the next call of a geozero function.
+#[no_mangle]
+pub extern "C" fn geozero_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |msg| msg.as_ptr()))
+}
+
+/// Release a string returned by geozero.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Release a buffer returned by geozero.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_buffer_free(buf: *mut u8, len: usize) {
+    if !buf.is_null() {
+        drop(Box::from_raw(slice::from_raw_parts_mut(buf, len)));
+    }
+}
+
+/// Convert a WKB geometry to GeoJSON.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_wkb_to_geojson(
+    buf: *const u8,
+    len: usize,
+    out: *mut *mut c_char,
+) -> c_int {
+    match buf_arg(buf, len) {
+        Some(wkb) if !out.is_null() => {
+            status(Wkb(wkb.to_vec()).to_json(), |s| return_string(s, out))
+        }
+        _ => invalid_argument("invalid WKB buffer or output pointer"),
+    }
+}
+
+/// Convert a WKB geometry to WKT.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_wkb_to_wkt(
+    buf: *const u8,
+    len: usize,
+    out: *mut *mut c_char,
+) -> c_int {
+    match buf_arg(buf, len) {
+        Some(wkb) if !out.is_null() => {
+            status(Wkb(wkb.to_vec()).to_wkt(), |s| return_string(s, out))
+        }
+        _ => invalid_argument("invalid WKB buffer or output pointer"),
+    }
+}
+
+/// Convert a GeoJSON geometry to WKB.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_geojson_to_wkb(
+    json: *const c_char,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> c_int {
+    match str_arg(json) {
+        Some(json) if !out.is_null() && !out_len.is_null() => {
+            status(to_wkb(&GeoJson(json)), |buf| {
+                return_buffer(buf, out, out_len)
+            })
+        }
+        _ => invalid_argument("invalid GeoJSON string or output pointer"),
+    }
+}
+
+/// Convert a GeoJSON geometry to WKT.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_geojson_to_wkt(
+    json: *const c_char,
+    out: *mut *mut c_char,
+) -> c_int {
+    match str_arg(json) {
+        Some(json) if !out.is_null() => status(GeoJson(json).to_wkt(), |s| return_string(s, out)),
+        _ => invalid_argument("invalid GeoJSON string or output pointer"),
+    }
+}
+
+/// Convert a WKT geometry to WKB.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_wkt_to_wkb(
+    wkt: *const c_char,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> c_int {
+    match str_arg(wkt) {
+        Some(wkt) if !out.is_null() && !out_len.is_null() => {
+            status(to_wkb(&WktStr(wkt)), |buf| return_buffer(buf, out, out_len))
+        }
+        _ => invalid_argument("invalid WKT string or output pointer"),
+    }
+}
+
+/// Convert a WKT geometry to GeoJSON.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_wkt_to_geojson(
+    wkt: *const c_char,
+    out: *mut *mut c_char,
+) -> c_int {
+    match str_arg(wkt) {
+        Some(wkt) if !out.is_null() => status(WktStr(wkt).to_json(), |s| return_string(s, out)),
+        _ => invalid_argument("invalid WKT string or output pointer"),
+    }
+}
+
+pub(crate) fn callback_error(callback: &str, code: c_int) -> GeozeroError {
+    GeozeroError::Feature(format!("callback `{}` returned {}", callback, code))
+}
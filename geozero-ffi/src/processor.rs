@@ -0,0 +1,276 @@
+use crate::{buf_arg, callback_error, invalid_argument, status, str_arg};
+use geozero::error::Result;
+use geozero::geojson::GeoJson;
+use geozero::wkb::{WKBGeometryType, Wkb};
+use geozero::wkt::WktStr;
+use geozero::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource,
+    GeozeroGeometry, PropertyProcessor,
+};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+/// Processor implemented with C callbacks.
+///
+/// Every callback receives `user_data` as first argument and returns 0 on success.
+/// Any other return value aborts processing with an error. Callbacks may be NULL.
+/// Geometry types are passed as OGC WKB type codes (1 = Point, 2 = LineString, ...).
+#[repr(C)]
+pub struct GeozeroProcessor {
+    /// Pointer passed to all callbacks
+    pub user_data: *mut c_void,
+    /// Request Z values in `coordinate` callbacks
+    pub dims_z: bool,
+    /// Request M values in `coordinate` callbacks
+    pub dims_m: bool,
+    /// Begin of dataset processing with optional dataset name
+    pub dataset_begin: Option<unsafe extern "C" fn(*mut c_void, name: *const c_char) -> c_int>,
+    /// End of dataset processing
+    pub dataset_end: Option<unsafe extern "C" fn(*mut c_void) -> c_int>,
+    /// Begin of feature processing
+    pub feature_begin: Option<unsafe extern "C" fn(*mut c_void, idx: u64) -> c_int>,
+    /// End of feature processing
+    pub feature_end: Option<unsafe extern "C" fn(*mut c_void, idx: u64) -> c_int>,
+    /// Feature property with value formatted as string
+    pub property: Option<
+        unsafe extern "C" fn(
+            *mut c_void,
+            idx: usize,
+            name: *const c_char,
+            value: *const c_char,
+        ) -> c_int,
+    >,
+    /// Begin of a geometry with WKB geometry type and number of members or coordinates
+    pub geometry_begin: Option<
+        unsafe extern "C" fn(*mut c_void, geometry_type: u32, size: usize, idx: usize) -> c_int,
+    >,
+    /// End of a geometry
+    pub geometry_end:
+        Option<unsafe extern "C" fn(*mut c_void, geometry_type: u32, idx: usize) -> c_int>,
+    /// Coordinate with NaN for missing Z and M values
+    pub coordinate: Option<
+        unsafe extern "C" fn(*mut c_void, x: f64, y: f64, z: f64, m: f64, idx: usize) -> c_int,
+    >,
+}
+
+macro_rules! callback {
+    ($p:expr, $name:ident $(, $arg:expr)*) => {
+        match $p.$name {
+            Some(f) => match unsafe { f($p.user_data $(, $arg)*) } {
+                0 => Ok(()),
+                code => Err(callback_error(stringify!($name), code)),
+            },
+            None => Ok(()),
+        }
+    };
+}
+
+/// Adapter from [GeozeroProcessor] callbacks to [FeatureProcessor].
+pub struct CallbackProcessor<'a>(pub &'a GeozeroProcessor);
+
+impl CallbackProcessor<'_> {
+    fn begin(&self, geometry_type: WKBGeometryType, size: usize, idx: usize) -> Result<()> {
+        callback!(self.0, geometry_begin, geometry_type as u32, size, idx)
+    }
+    fn end(&self, geometry_type: WKBGeometryType, idx: usize) -> Result<()> {
+        callback!(self.0, geometry_end, geometry_type as u32, idx)
+    }
+}
+
+impl GeomProcessor for CallbackProcessor<'_> {
+    fn dimensions(&self) -> CoordDimensions {
+        let mut dims = CoordDimensions::xy();
+        dims.z = self.0.dims_z;
+        dims.m = self.0.dims_m;
+        dims
+    }
+    fn multi_dim(&self) -> bool {
+        self.0.dims_z || self.0.dims_m
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        callback!(self.0, coordinate, x, y, f64::NAN, f64::NAN, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let z = z.unwrap_or(f64::NAN);
+        let m = m.unwrap_or(f64::NAN);
+        callback!(self.0, coordinate, x, y, z, m, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::Point, 0, idx)?;
+        self.end(WKBGeometryType::Point, idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::Point, 1, idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::Point, idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::MultiPoint, size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::MultiPoint, idx)
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::LineString, size, idx)
+    }
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::LineString, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::MultiLineString, size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::MultiLineString, idx)
+    }
+    fn polygon_begin(&mut self, _tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::Polygon, size, idx)
+    }
+    fn polygon_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::Polygon, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::MultiPolygon, size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::MultiPolygon, idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::GeometryCollection, size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::GeometryCollection, idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::CircularString, size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::CircularString, idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::CompoundCurve, size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::CompoundCurve, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::CurvePolygon, size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::CurvePolygon, idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::MultiCurve, size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::MultiCurve, idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::MultiSurface, size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::MultiSurface, idx)
+    }
+    fn triangle_begin(&mut self, _tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::Triangle, size, idx)
+    }
+    fn triangle_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::Triangle, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::PolyhedralSurface, size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::PolyhedralSurface, idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(WKBGeometryType::Tin, size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end(WKBGeometryType::Tin, idx)
+    }
+}
+
+impl PropertyProcessor for CallbackProcessor<'_> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        // Strings with NUL characters are truncated
+        let name = CString::new(name.split('\0').next().unwrap_or_default()).unwrap_or_default();
+        let value = value.to_string();
+        let value = CString::new(value.split('\0').next().unwrap_or_default()).unwrap_or_default();
+        callback!(self.0, property, idx, name.as_ptr(), value.as_ptr())?;
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for CallbackProcessor<'_> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        let name = name.and_then(|name| CString::new(name).ok());
+        let name_ptr = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+        callback!(self.0, dataset_begin, name_ptr)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        callback!(self.0, dataset_end)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        callback!(self.0, feature_begin, idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        callback!(self.0, feature_end, idx)
+    }
+}
+
+/// Process a GeoJSON geometry, feature or feature collection with callbacks.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_process_geojson(
+    json: *const c_char,
+    processor: *const GeozeroProcessor,
+) -> c_int {
+    match (str_arg(json), processor.as_ref()) {
+        (Some(json), Some(processor)) => {
+            let mut processor = CallbackProcessor(processor);
+            status(GeoJson(json).process(&mut processor), |_| {})
+        }
+        _ => invalid_argument("invalid GeoJSON string or processor"),
+    }
+}
+
+/// Process a WKT geometry with callbacks.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_process_wkt(
+    wkt: *const c_char,
+    processor: *const GeozeroProcessor,
+) -> c_int {
+    match (str_arg(wkt), processor.as_ref()) {
+        (Some(wkt), Some(processor)) => {
+            let mut processor = CallbackProcessor(processor);
+            status(WktStr(wkt).process_geom(&mut processor), |_| {})
+        }
+        _ => invalid_argument("invalid WKT string or processor"),
+    }
+}
+
+/// Process a WKB geometry with callbacks.
+#[no_mangle]
+pub unsafe extern "C" fn geozero_process_wkb(
+    buf: *const u8,
+    len: usize,
+    processor: *const GeozeroProcessor,
+) -> c_int {
+    match (buf_arg(buf, len), processor.as_ref()) {
+        (Some(wkb), Some(processor)) => {
+            let mut processor = CallbackProcessor(processor);
+            status(Wkb(wkb.to_vec()).process_geom(&mut processor), |_| {})
+        }
+        _ => invalid_argument("invalid WKB buffer or processor"),
+    }
+}
@@ -0,0 +1,156 @@
+use geozero_ffi::*;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+unsafe fn take_string(s: *mut c_char) -> String {
+    let result = CStr::from_ptr(s).to_str().unwrap().to_string();
+    geozero_string_free(s);
+    result
+}
+
+#[test]
+fn conversions() {
+    unsafe {
+        let wkt = CString::new("POINT(10 -20)").unwrap();
+        let mut wkb = ptr::null_mut();
+        let mut len = 0;
+        assert_eq!(
+            geozero_wkt_to_wkb(wkt.as_ptr(), &mut wkb, &mut len),
+            GEOZERO_OK
+        );
+        assert_eq!(len, 21);
+
+        let mut json = ptr::null_mut();
+        assert_eq!(geozero_wkb_to_geojson(wkb, len, &mut json), GEOZERO_OK);
+        assert_eq!(
+            take_string(json),
+            r#"{"type": "Point", "coordinates": [10,-20]}"#
+        );
+        geozero_buffer_free(wkb, len);
+
+        let json = CString::new(r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#).unwrap();
+        let mut wkt = ptr::null_mut();
+        assert_eq!(geozero_geojson_to_wkt(json.as_ptr(), &mut wkt), GEOZERO_OK);
+        assert_eq!(take_string(wkt), "LINESTRING(1 2,3 4)");
+    }
+}
+
+#[test]
+fn errors() {
+    unsafe {
+        let wkt = CString::new("POINT(10").unwrap();
+        let mut json = ptr::null_mut();
+        assert_eq!(
+            geozero_wkt_to_geojson(wkt.as_ptr(), &mut json),
+            GEOZERO_ERROR
+        );
+        assert!(!geozero_last_error().is_null());
+
+        assert_eq!(
+            geozero_wkt_to_geojson(ptr::null(), &mut json),
+            GEOZERO_INVALID_ARGUMENT
+        );
+        let msg = CStr::from_ptr(geozero_last_error()).to_str().unwrap();
+        assert_eq!(msg, "invalid WKT string or output pointer");
+    }
+}
+
+unsafe extern "C" fn feature_begin(user_data: *mut c_void, idx: u64) -> c_int {
+    let events = &mut *(user_data as *mut Vec<String>);
+    events.push(format!("feature {}", idx));
+    0
+}
+
+unsafe extern "C" fn property(
+    user_data: *mut c_void,
+    _idx: usize,
+    name: *const c_char,
+    value: *const c_char,
+) -> c_int {
+    let events = &mut *(user_data as *mut Vec<String>);
+    let name = CStr::from_ptr(name).to_str().unwrap();
+    let value = CStr::from_ptr(value).to_str().unwrap();
+    events.push(format!("{}={}", name, value));
+    0
+}
+
+unsafe extern "C" fn geometry_begin(
+    user_data: *mut c_void,
+    geometry_type: u32,
+    size: usize,
+    _idx: usize,
+) -> c_int {
+    let events = &mut *(user_data as *mut Vec<String>);
+    events.push(format!("geometry {} ({})", geometry_type, size));
+    0
+}
+
+unsafe extern "C" fn coordinate(
+    user_data: *mut c_void,
+    x: f64,
+    y: f64,
+    z: f64,
+    _m: f64,
+    _idx: usize,
+) -> c_int {
+    let events = &mut *(user_data as *mut Vec<String>);
+    if z.is_nan() {
+        events.push(format!("{} {}", x, y));
+    } else {
+        events.push(format!("{} {} {}", x, y, z));
+    }
+    0
+}
+
+unsafe extern "C" fn failing_feature_end(_user_data: *mut c_void, _idx: u64) -> c_int {
+    42
+}
+
+fn recorder(events: &mut Vec<String>) -> GeozeroProcessor {
+    GeozeroProcessor {
+        user_data: events as *mut Vec<String> as *mut c_void,
+        dims_z: true,
+        dims_m: false,
+        dataset_begin: None,
+        dataset_end: None,
+        feature_begin: Some(feature_begin),
+        feature_end: None,
+        property: Some(property),
+        geometry_begin: Some(geometry_begin),
+        geometry_end: None,
+        coordinate: Some(coordinate),
+    }
+}
+
+#[test]
+fn callback_processor() {
+    let json = CString::new(
+        r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "LineString", "coordinates": [[1,2,3],[4,5,6]]}}"#,
+    )
+    .unwrap();
+    let mut events = Vec::new();
+    let processor = recorder(&mut events);
+    unsafe {
+        assert_eq!(
+            geozero_process_geojson(json.as_ptr(), &processor),
+            GEOZERO_OK
+        );
+    }
+    assert_eq!(
+        events,
+        ["feature 0", "name=a", "geometry 2 (2)", "1 2 3", "4 5 6"]
+    );
+
+    let mut events = Vec::new();
+    let mut processor = recorder(&mut events);
+    processor.feature_end = Some(failing_feature_end);
+    unsafe {
+        assert_eq!(
+            geozero_process_geojson(json.as_ptr(), &processor),
+            GEOZERO_ERROR
+        );
+        let msg = CStr::from_ptr(geozero_last_error()).to_str().unwrap();
+        assert!(msg.contains("callback `feature_end` returned 42"));
+    }
+}
@@ -0,0 +1,310 @@
+//! Python bindings for GeoZero readers and processors.
+//!
+//! ```python
+//! import geozero_py
+//!
+//! for wkb, properties in geozero_py.read("countries.fgb", bbox=(8.8, 47.2, 9.5, 55.3)):
+//!     print(properties["name"], len(wkb))
+//!
+//! geozero_py.process("places.json", lambda wkb, properties: print(properties))
+//! ```
+
+use flatgeobuf::FgbReader;
+use geozero::csv::CsvReader;
+use geozero::error::{GeozeroError, Result};
+use geozero::geo_types::GeoWriter;
+use geozero::geojson::GeoJsonReader;
+use geozero::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource,
+    PropertyProcessor, ToWkb,
+};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Read options
+struct ReadArgs<'a> {
+    bbox: Option<(f64, f64, f64, f64)>,
+    geometry_column: Option<&'a str>,
+}
+
+/// Read dataset from file into `processor`, choosing the reader by file extension.
+fn read_file<P: FeatureProcessor>(path: &str, args: &ReadArgs, processor: &mut P) -> Result<()> {
+    let path = Path::new(path);
+    let mut file = BufReader::new(File::open(path)?);
+    match path.extension().and_then(OsStr::to_str) {
+        Some("csv") => {
+            let geometry_column = args.geometry_column.ok_or_else(|| {
+                GeozeroError::Dataset("geometry_column required for CSV files".to_string())
+            })?;
+            CsvReader::new(geometry_column, &mut file).process(processor)
+        }
+        Some("json") | Some("geojson") => GeoJsonReader(&mut file).process(processor),
+        Some("fgb") => {
+            let fgb = FgbReader::open(&mut file)?;
+            let mut fgb = if let Some((minx, miny, maxx, maxy)) = args.bbox {
+                fgb.select_bbox(minx, miny, maxx, maxy)?
+            } else {
+                fgb.select_all()?
+            };
+            fgb.process_features(processor)?;
+            Ok(())
+        }
+        _ => Err(GeozeroError::Dataset(format!(
+            "unsupported file extension of `{}`",
+            path.display()
+        ))),
+    }
+}
+
+/// Destination of converted features
+enum Sink {
+    Collect(Vec<PyObject>),
+    Callback(PyObject),
+}
+
+/// Processor converting features to `(wkb, properties)` pairs.
+struct PyFeatureProcessor<'py> {
+    py: Python<'py>,
+    geom: GeoWriter,
+    properties: &'py PyDict,
+    sink: Sink,
+    /// Python exception raised in callback
+    error: Option<PyErr>,
+}
+
+impl<'py> PyFeatureProcessor<'py> {
+    fn new(py: Python<'py>, sink: Sink) -> Self {
+        PyFeatureProcessor {
+            py,
+            geom: GeoWriter::new(),
+            properties: PyDict::new(py),
+            sink,
+            error: None,
+        }
+    }
+
+    fn py_result<T>(&mut self, result: PyResult<T>) -> Result<T> {
+        result.map_err(|e| {
+            let msg = e.to_string();
+            self.error = Some(e);
+            GeozeroError::Feature(msg)
+        })
+    }
+
+    fn column_value(&self, value: &ColumnValue) -> PyObject {
+        let py = self.py;
+        match value {
+            ColumnValue::Byte(v) => (*v).into_py(py),
+            ColumnValue::UByte(v) => (*v).into_py(py),
+            ColumnValue::Bool(v) => (*v).into_py(py),
+            ColumnValue::Short(v) => (*v).into_py(py),
+            ColumnValue::UShort(v) => (*v).into_py(py),
+            ColumnValue::Int(v) => (*v).into_py(py),
+            ColumnValue::UInt(v) => (*v).into_py(py),
+            ColumnValue::Long(v) => (*v).into_py(py),
+            ColumnValue::ULong(v) => (*v).into_py(py),
+            ColumnValue::Float(v) => (*v).into_py(py),
+            ColumnValue::Double(v) => (*v).into_py(py),
+            ColumnValue::String(v) => (*v).into_py(py),
+            ColumnValue::Json(v) => (*v).into_py(py),
+            ColumnValue::DateTime(v) => (*v).into_py(py),
+            ColumnValue::Binary(v) => PyBytes::new(py, *v).into_py(py),
+        }
+    }
+}
+
+fn to_pyerr(e: GeozeroError) -> PyErr {
+    match e {
+        GeozeroError::IoError(e) => PyIOError::new_err(e.to_string()),
+        e => PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// Process features of a file with a `PyFeatureProcessor`.
+fn process_file(py: Python, path: &str, args: ReadArgs, sink: Sink) -> PyResult<Sink> {
+    let mut processor = PyFeatureProcessor::new(py, sink);
+    let result = read_file(path, &args, &mut processor);
+    match processor.error.take() {
+        Some(e) => Err(e),
+        None => result.map(|_| processor.sink).map_err(to_pyerr),
+    }
+}
+
+impl GeomProcessor for PyFeatureProcessor<'_> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.geom.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.geom.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.geometrycollection_end(idx)
+    }
+}
+
+impl PropertyProcessor for PyFeatureProcessor<'_> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let value = self.column_value(value);
+        let result = self.properties.set_item(name, value);
+        self.py_result(result)?;
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for PyFeatureProcessor<'_> {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.properties = PyDict::new(self.py);
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let py = self.py;
+        let wkb: PyObject = match self.geom.take_geometry() {
+            Some(geom) => PyBytes::new(py, &geom.to_wkb(CoordDimensions::xy())?).into_py(py),
+            None => py.None(),
+        };
+        let properties = self.properties.into_py(py);
+        let result = match &mut self.sink {
+            Sink::Collect(features) => {
+                features.push((wkb, properties).into_py(py));
+                Ok(())
+            }
+            Sink::Callback(callback) => callback.call1(py, (wkb, properties)).map(|_| ()),
+        };
+        self.py_result(result)
+    }
+}
+
+/// Iterator over `(wkb, properties)` tuples of a dataset.
+#[pyclass]
+struct FeatureIter {
+    features: std::vec::IntoIter<PyObject>,
+}
+
+#[pymethods]
+impl FeatureIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<PyObject> {
+        slf.features.next()
+    }
+}
+
+/// Read features of a GeoJSON, FlatGeobuf or CSV file.
+///
+/// Returns an iterator of `(wkb, properties)` tuples with 2D WKB geometries (or `None`)
+/// and a dict of feature properties.
+/// `bbox` `(minx, miny, maxx, maxy)` selects FlatGeobuf features by spatial index,
+/// `geometry_column` is the name of the WKT column of CSV files.
+#[pyfunction]
+#[pyo3(text_signature = "(path, bbox=None, geometry_column=None)")]
+fn read(
+    py: Python,
+    path: &str,
+    bbox: Option<(f64, f64, f64, f64)>,
+    geometry_column: Option<&str>,
+) -> PyResult<FeatureIter> {
+    let args = ReadArgs {
+        bbox,
+        geometry_column,
+    };
+    match process_file(py, path, args, Sink::Collect(Vec::new()))? {
+        Sink::Collect(features) => Ok(FeatureIter {
+            features: features.into_iter(),
+        }),
+        Sink::Callback(_) => unreachable!(),
+    }
+}
+
+/// Call `processor(wkb, properties)` for each feature of a GeoJSON, FlatGeobuf or CSV file.
+///
+/// Exceptions raised by `processor` abort processing and are propagated.
+#[pyfunction]
+#[pyo3(text_signature = "(path, processor, bbox=None, geometry_column=None)")]
+fn process(
+    py: Python,
+    path: &str,
+    processor: PyObject,
+    bbox: Option<(f64, f64, f64, f64)>,
+    geometry_column: Option<&str>,
+) -> PyResult<()> {
+    let args = ReadArgs {
+        bbox,
+        geometry_column,
+    };
+    process_file(py, path, args, Sink::Callback(processor))?;
+    Ok(())
+}
+
+#[pymodule]
+fn geozero_py(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(read, m)?)?;
+    m.add_function(wrap_pyfunction!(process, m)?)?;
+    m.add_class::<FeatureIter>()?;
+    Ok(())
+}
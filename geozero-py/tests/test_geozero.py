@@ -0,0 +1,40 @@
+import pytest
+
+import geozero_py
+
+COUNTRIES = "../geozero/tests/data/countries.fgb"
+PLACES = "../geozero/tests/data/places.json"
+
+
+def test_read_fgb_bbox():
+    features = list(geozero_py.read(COUNTRIES, bbox=(8.8, 47.2, 9.5, 55.3)))
+    names = [properties["name"] for _, properties in features]
+    assert "Denmark" in names
+    assert "Brazil" not in names
+    wkb, _ = features[0]
+    assert wkb[0] == 1  # little endian WKB
+
+
+def test_process_geojson():
+    count = 0
+
+    def count_features(wkb, properties):
+        nonlocal count
+        count += 1
+        assert isinstance(properties, dict)
+
+    geozero_py.process(PLACES, count_features)
+    assert count == 1249
+
+
+def test_callback_error():
+    def fail(wkb, properties):
+        raise RuntimeError("stop")
+
+    with pytest.raises(RuntimeError, match="stop"):
+        geozero_py.process(PLACES, fail)
+
+
+def test_unsupported_format():
+    with pytest.raises(ValueError):
+        geozero_py.read("data.xyz")
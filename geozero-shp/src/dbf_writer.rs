@@ -0,0 +1,256 @@
+use crate::Error;
+use byteorder::{LittleEndian, WriteBytesExt};
+use geozero::ColumnValue;
+use std::io::Write;
+
+const MAX_NAME_LEN: usize = 10;
+const MAX_CHAR_LEN: usize = 254;
+const MAX_NUMERIC_LEN: usize = 20;
+const MAX_DECIMALS: usize = 15;
+
+/// Property value buffered until the .dbf is written
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FieldValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl FieldValue {
+    pub fn from_column_value(value: &ColumnValue) -> Option<FieldValue> {
+        let value = match value {
+            ColumnValue::Byte(v) => FieldValue::Int(*v as i64),
+            ColumnValue::UByte(v) => FieldValue::Int(*v as i64),
+            ColumnValue::Bool(v) => FieldValue::Bool(*v),
+            ColumnValue::Short(v) => FieldValue::Int(*v as i64),
+            ColumnValue::UShort(v) => FieldValue::Int(*v as i64),
+            ColumnValue::Int(v) => FieldValue::Int(*v as i64),
+            ColumnValue::UInt(v) => FieldValue::Int(*v as i64),
+            ColumnValue::Long(v) => FieldValue::Int(*v),
+            ColumnValue::ULong(v) if *v <= i64::MAX as u64 => FieldValue::Int(*v as i64),
+            ColumnValue::ULong(v) => FieldValue::Float(*v as f64),
+            ColumnValue::Float(v) => FieldValue::Float(*v as f64),
+            ColumnValue::Double(v) => FieldValue::Float(*v),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                FieldValue::Str(v.to_string())
+            }
+            ColumnValue::Binary(_) => return None,
+        };
+        Some(value)
+    }
+}
+
+/// DBF field definition
+#[derive(Debug, PartialEq)]
+pub(crate) struct DbfField {
+    pub name: String,
+    pub field_type: u8,
+    pub length: usize,
+    pub decimals: usize,
+}
+
+impl DbfField {
+    /// Infer field type, width and decimal count from all column values
+    pub fn infer<'a>(name: String, values: impl Iterator<Item = &'a FieldValue>) -> DbfField {
+        let mut field_type = None;
+        let (mut int_len, mut frac_len, mut char_len) = (1, 0, 1);
+        for value in values {
+            let value_type = match value {
+                FieldValue::Bool(_) => b'L',
+                FieldValue::Int(_) | FieldValue::Float(_) => b'N',
+                FieldValue::Str(_) => b'C',
+            };
+            field_type = match field_type {
+                None => Some(value_type),
+                Some(t) if t == value_type => Some(t),
+                Some(_) => Some(b'C'),
+            };
+            let text = value_to_string(value);
+            char_len = char_len.max(text.len());
+            let (int_part, frac_part) = match text.find('.') {
+                Some(pos) => (pos, text.len() - pos - 1),
+                None => (text.len(), 0),
+            };
+            int_len = int_len.max(int_part);
+            frac_len = frac_len.max(frac_part);
+        }
+        match field_type.unwrap_or(b'C') {
+            b'L' => DbfField {
+                name,
+                field_type: b'L',
+                length: 1,
+                decimals: 0,
+            },
+            b'N' if int_len < MAX_NUMERIC_LEN => {
+                let decimals = frac_len
+                    .min(MAX_DECIMALS)
+                    .min(MAX_NUMERIC_LEN - int_len - 1);
+                let length = if decimals > 0 {
+                    int_len + 1 + decimals
+                } else {
+                    int_len
+                };
+                DbfField {
+                    name,
+                    field_type: b'N',
+                    length,
+                    decimals,
+                }
+            }
+            _ => DbfField {
+                name,
+                field_type: b'C',
+                length: char_len.min(MAX_CHAR_LEN),
+                decimals: 0,
+            },
+        }
+    }
+
+    fn write_value<W: Write>(&self, out: &mut W, value: Option<&FieldValue>) -> Result<(), Error> {
+        let text = match (self.field_type, value) {
+            (_, None) => String::new(),
+            (b'L', Some(FieldValue::Bool(v))) => if *v { "T" } else { "F" }.to_string(),
+            (b'N', Some(FieldValue::Float(v))) => {
+                format!(
+                    "{:>width$.prec$}",
+                    v,
+                    width = self.length,
+                    prec = self.decimals
+                )
+            }
+            (b'N', Some(FieldValue::Int(v))) if self.decimals > 0 => format!(
+                "{:>width$.prec$}",
+                *v as f64,
+                width = self.length,
+                prec = self.decimals
+            ),
+            (b'N', Some(v)) => format!("{:>width$}", value_to_string(v), width = self.length),
+            (_, Some(v)) => value_to_string(v),
+        };
+        let mut bytes = text.as_bytes();
+        if bytes.len() > self.length {
+            let mut end = self.length;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            bytes = &bytes[..end];
+        }
+        out.write_all(bytes)?;
+        for _ in bytes.len()..self.length {
+            out.write_u8(b' ')?;
+        }
+        Ok(())
+    }
+}
+
+fn value_to_string(value: &FieldValue) -> String {
+    match value {
+        FieldValue::Bool(v) => v.to_string(),
+        FieldValue::Int(v) => v.to_string(),
+        FieldValue::Float(v) => v.to_string(),
+        FieldValue::Str(v) => v.clone(),
+    }
+}
+
+/// Make column names valid and unique DBF field names
+pub(crate) fn field_names(columns: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(columns.len());
+    for column in columns {
+        let base: String = column
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(MAX_NAME_LEN)
+            .collect();
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            let suffix_str = suffix.to_string();
+            let len = base.len().min(MAX_NAME_LEN - suffix_str.len());
+            name = format!("{}{}", &base[..len], suffix_str);
+            suffix += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// Write .dbf content
+pub(crate) fn write_dbf<W: Write>(
+    out: &mut W,
+    fields: &[DbfField],
+    records: &[Vec<Option<FieldValue>>],
+) -> Result<(), Error> {
+    let header_size = 32 + 32 * fields.len() + 1;
+    let record_size = 1 + fields.iter().map(|f| f.length).sum::<usize>();
+
+    out.write_u8(0x03)?; // dBase III without memo
+    out.write_all(&[0, 1, 1])?; // last update (YYMMDD)
+    out.write_u32::<LittleEndian>(records.len() as u32)?;
+    out.write_u16::<LittleEndian>(header_size as u16)?;
+    out.write_u16::<LittleEndian>(record_size as u16)?;
+    out.write_all(&[0; 20])?;
+
+    for field in fields {
+        let mut name = [0u8; 11];
+        name[..field.name.len()].copy_from_slice(field.name.as_bytes());
+        out.write_all(&name)?;
+        out.write_u8(field.field_type)?;
+        out.write_all(&[0; 4])?;
+        out.write_u8(field.length as u8)?;
+        out.write_u8(field.decimals as u8)?;
+        out.write_all(&[0; 14])?;
+    }
+    out.write_u8(0x0D)?;
+
+    for record in records {
+        out.write_u8(b' ')?; // not deleted
+        for (i, field) in fields.iter().enumerate() {
+            field.write_value(out, record.get(i).and_then(|v| v.as_ref()))?;
+        }
+    }
+    out.write_u8(0x1A)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_fields() {
+        let values = vec![FieldValue::Int(12), FieldValue::Int(-345)];
+        let field = DbfField::infer("int".to_string(), values.iter());
+        assert_eq!(
+            (field.field_type, field.length, field.decimals),
+            (b'N', 4, 0)
+        );
+
+        let values = vec![FieldValue::Int(12), FieldValue::Float(1.25)];
+        let field = DbfField::infer("float".to_string(), values.iter());
+        assert_eq!(
+            (field.field_type, field.length, field.decimals),
+            (b'N', 5, 2)
+        );
+
+        let values = vec![FieldValue::Int(12), FieldValue::Str("abc".to_string())];
+        let field = DbfField::infer("mixed".to_string(), values.iter());
+        assert_eq!(
+            (field.field_type, field.length, field.decimals),
+            (b'C', 3, 0)
+        );
+    }
+
+    #[test]
+    fn unique_names() {
+        let columns = vec![
+            "population".to_string(),
+            "population_2020".to_string(),
+            "name".to_string(),
+        ];
+        assert_eq!(
+            field_names(&columns),
+            vec!["population", "populatio1", "name"]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::point_z::BBoxZ;
 use crate::Error;
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::fmt;
-use std::io::Read;
+use std::io::{Read, Write};
 
 pub(crate) const HEADER_SIZE: i32 = 100;
 const FILE_CODE: i32 = 9994;
@@ -69,6 +69,25 @@ impl Header {
 
         Ok(hdr)
     }
+
+    pub fn write_to<T: Write>(&self, dest: &mut T) -> Result<(), Error> {
+        dest.write_i32::<BigEndian>(FILE_CODE)?;
+        dest.write_all(&[0; SIZE_OF_SKIP])?;
+        dest.write_i32::<BigEndian>(self.file_length)?;
+        dest.write_i32::<LittleEndian>(self.version)?;
+        dest.write_i32::<LittleEndian>(self.shape_type as i32)?;
+
+        dest.write_f64::<LittleEndian>(self.bbox.min.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.m)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.m)?;
+
+        Ok(())
+    }
 }
 
 /// The enum for the ShapeType as defined in the
@@ -168,6 +187,38 @@ impl ShapeType {
     }
 }
 
+/// Type of a Multipatch part
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum PatchType {
+    TriangleStrip = 0,
+    TriangleFan = 1,
+    OuterRing = 2,
+    InnerRing = 3,
+    FirstRing = 4,
+    Ring = 5,
+}
+
+impl PatchType {
+    pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<PatchType, Error> {
+        let code = source.read_i32::<LittleEndian>()?;
+        Self::from(code).ok_or_else(|| Error::InvalidPatchType(code))
+    }
+
+    /// Returns the PatchType corresponding to the input code
+    /// if the code is valid
+    pub fn from(code: i32) -> Option<PatchType> {
+        match code {
+            0 => Some(PatchType::TriangleStrip),
+            1 => Some(PatchType::TriangleFan),
+            2 => Some(PatchType::OuterRing),
+            3 => Some(PatchType::InnerRing),
+            4 => Some(PatchType::FirstRing),
+            5 => Some(PatchType::Ring),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for ShapeType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -193,7 +244,6 @@ impl fmt::Display for ShapeType {
 mod tests {
     use super::*;
 
-    use byteorder::WriteBytesExt;
     use std::io::{Seek, SeekFrom};
 
     #[test]
@@ -206,4 +256,19 @@ mod tests {
         src.seek(SeekFrom::Start(0)).unwrap();
         assert!(Header::read_from(&mut src).is_err());
     }
+
+    #[test]
+    fn write_read_header() {
+        let mut hdr = Header::default();
+        hdr.shape_type = ShapeType::PolygonZ;
+        hdr.file_length = 128;
+        hdr.bbox.min.x = -1.0;
+        hdr.bbox.max.y = 2.0;
+        let mut buf = Vec::new();
+        hdr.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), HEADER_SIZE as usize);
+
+        let read = Header::read_from(&mut buf.as_slice()).unwrap();
+        assert!(read == hdr);
+    }
 }
@@ -1,16 +1,20 @@
+mod dbf_writer;
 mod header;
 mod point_z;
 mod property_processor;
 pub mod reader;
 mod shp_reader;
+mod shp_writer;
 mod shx_reader;
+pub mod writer;
 
 use thiserror::Error;
 
-pub use header::ShapeType;
+pub use header::{PatchType, ShapeType};
 pub use property_processor::*;
 pub use reader::Reader;
 pub use shp_reader::NO_DATA;
+pub use writer::ShpWriter;
 
 /// All Errors that can happen when using this library
 #[derive(Error, Debug)]
@@ -47,6 +51,14 @@ pub enum Error {
     MissingIndexFile,
     #[error("Geozero error")]
     GeozeroError(#[from] geozero::error::GeozeroError),
+    /// Error when reading a zipped shapefile
+    #[cfg(feature = "with-zip")]
+    #[error("Zip error")]
+    ZipError(#[from] zip::result::ZipError),
+    /// The zip archive contains no matching `.shp` file
+    #[cfg(feature = "with-zip")]
+    #[error("No shapefile found in zip archive")]
+    MissingShapefile,
 }
 
 impl From<dbase::Error> for Error {
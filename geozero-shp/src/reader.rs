@@ -219,6 +219,62 @@ impl Reader<BufReader<File>> {
     }
 }
 
+#[cfg(feature = "with-zip")]
+impl Reader<std::io::Cursor<Vec<u8>>> {
+    /// Creates a reader from a zipped shapefile
+    ///
+    /// Reads the member `name` (e.g. `data/roads.shp`) or the first `.shp` member of the
+    /// archive, together with the `.shx` and `.dbf` members with the same base name.
+    /// Members are read into memory, since zip entries are not seekable.
+    pub fn from_zip<R: Read + Seek>(archive: R, name: Option<&str>) -> Result<Self, Error> {
+        let mut archive = zip::ZipArchive::new(archive)?;
+        let shp_name = archive
+            .file_names()
+            .filter(|member| !member.starts_with("__MACOSX/"))
+            .find(|member| match name {
+                Some(name) => *member == name,
+                None => member.to_lowercase().ends_with(".shp"),
+            })
+            .ok_or(Error::MissingShapefile)?
+            .to_string();
+        let base = &shp_name[..shp_name.len() - 4];
+        let member_with_extension = |archive: &zip::ZipArchive<R>, ext: &str| {
+            archive
+                .file_names()
+                .find(|member| {
+                    member.len() == shp_name.len()
+                        && member.starts_with(base)
+                        && member[base.len()..].eq_ignore_ascii_case(ext)
+                })
+                .map(|member| member.to_string())
+        };
+        let shx_name = member_with_extension(&archive, ".shx");
+        let dbf_name = member_with_extension(&archive, ".dbf");
+
+        let mut read_member = |name: &str| -> Result<std::io::Cursor<Vec<u8>>, Error> {
+            let mut member = archive.by_name(name)?;
+            let mut data = Vec::with_capacity(member.size() as usize);
+            member.read_to_end(&mut data)?;
+            Ok(std::io::Cursor::new(data))
+        };
+        let mut reader = Self::new(read_member(&shp_name)?)?;
+        if let Some(shx_name) = shx_name {
+            reader.add_index_source(read_member(&shx_name)?)?;
+        }
+        if let Some(dbf_name) = dbf_name {
+            reader.add_dbf_source(read_member(&dbf_name)?)?;
+        }
+        Ok(reader)
+    }
+
+    /// Creates a reader from a path to a zipped shapefile
+    ///
+    /// (see [from_zip()](struct.Reader.html#method.from_zip))
+    pub fn from_zip_path<P: AsRef<Path>>(path: P, name: Option<&str>) -> Result<Self, Error> {
+        Self::from_zip(BufReader::new(File::open(path)?), name)
+    }
+}
+
 // Does not work, because iter_features requires P instead of &mut P
 // impl<T: Read> GeozeroDatasource for Reader<T> {
 //     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> geozero::error::Result<()> {
@@ -1,4 +1,4 @@
-use crate::{Error, ShapeType};
+use crate::{Error, PatchType, ShapeType};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use geozero::GeomProcessor;
 use std::io::Read;
@@ -199,13 +199,12 @@ fn read_polygon<P: GeomProcessor, T: Read>(
 }
 
 fn read_multipatch_shape_content<P: GeomProcessor, T: Read>(
-    _processor: &mut P,
+    processor: &mut P,
     source: &mut T,
     record_size: usize,
 ) -> Result<(), Error> {
-    // TODO
-    let mut buffer = vec![0; record_size];
-    source.read_exact(&mut buffer)?;
+    let multipatch = MultiPartShape::read_multipatch(source, record_size)?;
+    multipatch.process_multipatch(processor)?;
     Ok(())
 }
 
@@ -213,6 +212,7 @@ fn read_multipatch_shape_content<P: GeomProcessor, T: Read>(
 
 struct MultiPartShape {
     parts_index: Vec<usize>,
+    part_types: Vec<PatchType>,
     coords: Vec<Coord>,
     z_values: Vec<f64>,
     m_values: Vec<f64>,
@@ -228,11 +228,30 @@ impl MultiPartShape {
         source: &mut R,
         record_size: usize,
         has_z: bool,
+    ) -> Result<MultiPartShape, Error> {
+        Self::read_parts(source, record_size, has_z, false)
+    }
+
+    fn read_multipatch<R: Read>(
+        source: &mut R,
+        record_size: usize,
+    ) -> Result<MultiPartShape, Error> {
+        Self::read_parts(source, record_size, true, true)
+    }
+
+    fn read_parts<R: Read>(
+        source: &mut R,
+        record_size: usize,
+        has_z: bool,
+        has_part_types: bool,
     ) -> Result<MultiPartShape, Error> {
         let _bbox = read_bbox(source, 2)?;
         let num_parts = source.read_i32::<LittleEndian>()? as usize;
         let num_points = source.read_i32::<LittleEndian>()? as usize;
         let mut rec_size = multipart_record_size(num_points, num_parts);
+        if has_part_types {
+            rec_size += size_of::<i32>() * num_parts;
+        }
         if has_z {
             rec_size += multipart_dim_value_size(num_points);
         }
@@ -246,9 +265,21 @@ impl MultiPartShape {
             parts_index.push(source.read_i32::<LittleEndian>()? as usize);
         }
         parts_index.push(num_points); // add last index to simplify iteration
+        if parts_index.windows(2).any(|w| w[0] > w[1]) {
+            return Err(Error::InvalidShapeRecordSize);
+        }
+
+        let mut part_types = Vec::new();
+        if has_part_types {
+            part_types.reserve(num_parts);
+            for _ in 0..num_parts {
+                part_types.push(PatchType::read_from(source)?);
+            }
+        }
 
         let mut multipart = MultiPartShape {
             parts_index,
+            part_types,
             coords: Vec::new(),
             z_values: Vec::new(),
             m_values: Vec::new(),
@@ -268,81 +299,159 @@ impl MultiPartShape {
         self.parts_index.len() - 1
     }
 
-    fn detect_polys(&self) -> Vec<usize> {
-        let mut polys = Vec::with_capacity(self.parts_index.len());
-        for idx in 0..self.parts_index.len() - 1 {
-            let (start_index, end_index) = (self.parts_index[idx], self.parts_index[idx + 1]);
-            if idx == 0
-                || ring_type_from_points_ordering(&self.coords[start_index..end_index])
-                    == RingType::OuterRing
-            {
-                polys.push(idx);
+    fn part_coords(&self, part: usize) -> &[Coord] {
+        &self.coords[self.parts_index[part]..self.parts_index[part + 1]]
+    }
+
+    /// Group parts into polygons.
+    ///
+    /// Each outer ring (clockwise) starts a new polygon. Inner rings are assigned to the
+    /// last outer ring containing them, or to the preceding outer ring if none does.
+    fn detect_polys(&self) -> Vec<Vec<usize>> {
+        let mut polys: Vec<Vec<usize>> = Vec::new();
+        for part in 0..self.num_parts() {
+            let coords = self.part_coords(part);
+            if polys.is_empty() || ring_type_from_points_ordering(coords) == RingType::OuterRing {
+                polys.push(vec![part]);
+                continue;
             }
+            let container = coords.first().and_then(|pt| {
+                polys
+                    .iter()
+                    .rposition(|rings| point_in_ring(pt, self.part_coords(rings[0])))
+            });
+            let poly_idx = container.unwrap_or(polys.len() - 1);
+            polys[poly_idx].push(part);
         }
-        polys.push(self.parts_index.len() - 1);
         polys
     }
 
     fn process<P: GeomProcessor>(&self, processor: &mut P, as_poly: bool) -> Result<(), Error> {
         let tagged = false;
-        let multi_dim = processor.dimensions().z || processor.dimensions().m;
-        let get_z = processor.dimensions().z && self.z_values.len() > 0;
-        let get_m = processor.dimensions().m && self.m_values.len() > 0;
-
-        let geom_parts_indices = if as_poly {
-            self.detect_polys()
-        } else {
-            vec![0, self.parts_index.len() - 1]
-        };
 
         if as_poly {
-            processor.multipolygon_begin(geom_parts_indices.len() - 1, 0)?;
+            let polys = self.detect_polys();
+            processor.multipolygon_begin(polys.len(), 0)?;
+            for (geom_idx, rings) in polys.iter().enumerate() {
+                processor.polygon_begin(tagged, rings.len(), geom_idx)?;
+                for (ring_idx, part) in rings.iter().enumerate() {
+                    let (start, end) = (self.parts_index[*part], self.parts_index[*part + 1]);
+                    self.process_linestring(processor, start..end, ring_idx)?;
+                }
+                processor.polygon_end(tagged, geom_idx)?;
+            }
+            processor.multipolygon_end(0)?;
         } else {
             processor.multilinestring_begin(self.num_parts(), 0)?;
-        }
-        for (geom_idx, geom_start_end) in geom_parts_indices.windows(2).enumerate() {
-            let (geom_start, geom_end) = (geom_start_end[0], geom_start_end[1]);
-            let num_rings = geom_end - geom_start;
-            if as_poly {
-                processor.polygon_begin(tagged, num_rings, geom_idx)?;
+            for (line_idx, start_end) in self.parts_index.windows(2).enumerate() {
+                self.process_linestring(processor, start_end[0]..start_end[1], line_idx)?;
             }
-            let mut ring_idx = 0;
-            for start_end in self.parts_index[geom_start..=geom_end].windows(2) {
-                let (start_index, end_index) = (start_end[0], start_end[1]);
-                let num_points_in_part = end_index - start_index;
-                processor.linestring_begin(tagged, num_points_in_part, ring_idx)?;
-                for ofs in start_index..end_index {
-                    let coord_idx = ofs - start_index;
-                    let coord = &self.coords[ofs];
-                    if !multi_dim {
-                        processor.xy(coord.x, coord.y, coord_idx)?;
+            processor.multilinestring_end(0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Process Multipatch as MultiPolygon.
+    ///
+    /// Triangle strips and fans are split into triangular polygons.
+    fn process_multipatch<P: GeomProcessor>(&self, processor: &mut P) -> Result<(), Error> {
+        let tagged = false;
+        // polygons with rings of coordinate offsets
+        let mut polys: Vec<Vec<Vec<usize>>> = Vec::new();
+        let mut in_first_ring = false;
+        for part in 0..self.num_parts() {
+            let (start, end) = (self.parts_index[part], self.parts_index[part + 1]);
+            match self.part_types[part] {
+                PatchType::TriangleStrip => {
+                    for i in start..end.saturating_sub(2) {
+                        polys.push(vec![vec![i, i + 1, i + 2, i]]);
+                    }
+                    in_first_ring = false;
+                }
+                PatchType::TriangleFan => {
+                    for i in start + 1..end.saturating_sub(1) {
+                        polys.push(vec![vec![start, i, i + 1, start]]);
+                    }
+                    in_first_ring = false;
+                }
+                PatchType::OuterRing => {
+                    polys.push(vec![(start..end).collect()]);
+                    in_first_ring = false;
+                }
+                PatchType::FirstRing => {
+                    polys.push(vec![(start..end).collect()]);
+                    in_first_ring = true;
+                }
+                PatchType::InnerRing | PatchType::Ring => {
+                    let is_hole = match self.part_types[part] {
+                        PatchType::InnerRing => !polys.is_empty(),
+                        _ => in_first_ring,
+                    };
+                    if is_hole {
+                        polys.last_mut().unwrap().push((start..end).collect());
                     } else {
-                        let z = if get_z {
-                            Some(self.z_values[ofs])
-                        } else {
-                            None
-                        };
-                        let m = if get_m {
-                            Some(self.m_values[ofs])
-                        } else {
-                            None
-                        };
-                        processor.coordinate(coord.x, coord.y, z, m, None, None, coord_idx)?;
+                        polys.push(vec![(start..end).collect()]);
                     }
                 }
-                processor.linestring_end(tagged, ring_idx)?;
-                ring_idx += 1;
             }
-            if as_poly {
-                processor.polygon_end(tagged, geom_idx)?;
+        }
+
+        processor.multipolygon_begin(polys.len(), 0)?;
+        for (geom_idx, rings) in polys.iter().enumerate() {
+            processor.polygon_begin(tagged, rings.len(), geom_idx)?;
+            for (ring_idx, ring) in rings.iter().enumerate() {
+                processor.linestring_begin(tagged, ring.len(), ring_idx)?;
+                for (coord_idx, ofs) in ring.iter().enumerate() {
+                    self.process_coord(processor, *ofs, coord_idx)?;
+                }
+                processor.linestring_end(tagged, ring_idx)?;
             }
+            processor.polygon_end(tagged, geom_idx)?;
         }
-        if as_poly {
-            processor.multipolygon_end(0)?;
-        } else {
-            processor.multilinestring_end(0)?;
+        processor.multipolygon_end(0)?;
+        Ok(())
+    }
+
+    fn process_linestring<P: GeomProcessor>(
+        &self,
+        processor: &mut P,
+        range: std::ops::Range<usize>,
+        idx: usize,
+    ) -> Result<(), Error> {
+        let tagged = false;
+        let start_index = range.start;
+        processor.linestring_begin(tagged, range.len(), idx)?;
+        for ofs in range {
+            self.process_coord(processor, ofs, ofs - start_index)?;
         }
+        processor.linestring_end(tagged, idx)?;
+        Ok(())
+    }
 
+    fn process_coord<P: GeomProcessor>(
+        &self,
+        processor: &mut P,
+        ofs: usize,
+        idx: usize,
+    ) -> Result<(), Error> {
+        let coord = &self.coords[ofs];
+        let dims = processor.dimensions();
+        if !(dims.z || dims.m) {
+            processor.xy(coord.x, coord.y, idx)?;
+        } else {
+            let z = if dims.z {
+                self.z_values.get(ofs).cloned()
+            } else {
+                None
+            };
+            let m = if dims.m {
+                self.m_values.get(ofs).cloned()
+            } else {
+                None
+            };
+            processor.coordinate(coord.x, coord.y, z, m, None, None, idx)?;
+        }
         Ok(())
     }
 }
@@ -424,3 +533,15 @@ fn ring_type_from_points_ordering(points: &[Coord]) -> RingType {
         RingType::OuterRing
     }
 }
+
+/// Point in ring test (crossing number)
+fn point_in_ring(pt: &Coord, ring: &[Coord]) -> bool {
+    let mut inside = false;
+    for seg in ring.windows(2) {
+        let (a, b) = (&seg[0], &seg[1]);
+        if (a.y > pt.y) != (b.y > pt.y) && pt.x < (b.x - a.x) * (pt.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
@@ -0,0 +1,270 @@
+use crate::header::{Header, HEADER_SIZE};
+use crate::point_z::{BBoxZ, PointZ};
+use crate::shp_reader::{is_no_data, RecordHeader};
+use crate::{Error, ShapeType};
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use std::io::Write;
+use std::mem::size_of;
+
+/// Geometry type of a buffered shape
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum ShapeKind {
+    Null,
+    Point,
+    Multipoint,
+    Polyline,
+    Polygon,
+}
+
+/// Shape buffered until the shapefile is written
+pub(crate) struct Shape {
+    pub kind: ShapeKind,
+    pub parts: Vec<Vec<PointZ>>,
+}
+
+impl Shape {
+    pub fn null() -> Self {
+        Shape {
+            kind: ShapeKind::Null,
+            parts: Vec::new(),
+        }
+    }
+
+    fn points(&self) -> impl Iterator<Item = &PointZ> {
+        self.parts.iter().flatten()
+    }
+
+    fn num_points(&self) -> usize {
+        self.parts.iter().map(|part| part.len()).sum()
+    }
+
+    fn bbox(&self) -> Option<BBoxZ> {
+        let mut points = self.points();
+        let first = points.next()?;
+        let mut bbox = BBoxZ {
+            min: *first,
+            max: *first,
+        };
+        for pt in points {
+            bbox_extend(&mut bbox, pt);
+        }
+        Some(bbox)
+    }
+
+    /// Size of the record content in bytes
+    fn content_size(&self, format: ShapeFormat) -> usize {
+        let num_points = self.num_points();
+        let dim_values = |n: usize| 2 * size_of::<f64>() + n * size_of::<f64>();
+        let mut size = size_of::<i32>(); // shape type
+        match self.kind {
+            ShapeKind::Null => return size,
+            ShapeKind::Point => {
+                size += 2 * size_of::<f64>();
+                if format.has_z() {
+                    size += size_of::<f64>();
+                }
+                if format.has_m {
+                    size += size_of::<f64>();
+                }
+                return size;
+            }
+            ShapeKind::Multipoint => {
+                size += 4 * size_of::<f64>(); // BBOX
+                size += size_of::<i32>(); // num points
+            }
+            ShapeKind::Polyline | ShapeKind::Polygon => {
+                size += 4 * size_of::<f64>(); // BBOX
+                size += 2 * size_of::<i32>(); // num parts, num points
+                size += self.parts.len() * size_of::<i32>();
+            }
+        }
+        size += num_points * 2 * size_of::<f64>();
+        if format.has_z() {
+            size += dim_values(num_points);
+        }
+        if format.has_m {
+            size += dim_values(num_points);
+        }
+        size
+    }
+
+    fn write_content<W: Write>(&self, out: &mut W, format: ShapeFormat) -> Result<(), Error> {
+        if self.kind == ShapeKind::Null {
+            out.write_i32::<LittleEndian>(ShapeType::NullShape as i32)?;
+            return Ok(());
+        }
+        out.write_i32::<LittleEndian>(format.shape_type as i32)?;
+        if self.kind == ShapeKind::Point {
+            let pt = self.points().next().ok_or(Error::InvalidShapeRecordSize)?;
+            out.write_f64::<LittleEndian>(pt.x)?;
+            out.write_f64::<LittleEndian>(pt.y)?;
+            if format.has_z() {
+                out.write_f64::<LittleEndian>(pt.z)?;
+            }
+            if format.has_m {
+                out.write_f64::<LittleEndian>(pt.m)?;
+            }
+            return Ok(());
+        }
+
+        let bbox = self.bbox().unwrap_or_default();
+        out.write_f64::<LittleEndian>(bbox.min.x)?;
+        out.write_f64::<LittleEndian>(bbox.min.y)?;
+        out.write_f64::<LittleEndian>(bbox.max.x)?;
+        out.write_f64::<LittleEndian>(bbox.max.y)?;
+        if self.kind != ShapeKind::Multipoint {
+            out.write_i32::<LittleEndian>(self.parts.len() as i32)?;
+        }
+        out.write_i32::<LittleEndian>(self.num_points() as i32)?;
+        if self.kind != ShapeKind::Multipoint {
+            let mut start = 0;
+            for part in &self.parts {
+                out.write_i32::<LittleEndian>(start as i32)?;
+                start += part.len();
+            }
+        }
+        for pt in self.points() {
+            out.write_f64::<LittleEndian>(pt.x)?;
+            out.write_f64::<LittleEndian>(pt.y)?;
+        }
+        if format.has_z() {
+            out.write_f64::<LittleEndian>(bbox.min.z)?;
+            out.write_f64::<LittleEndian>(bbox.max.z)?;
+            for pt in self.points() {
+                out.write_f64::<LittleEndian>(pt.z)?;
+            }
+        }
+        if format.has_m {
+            out.write_f64::<LittleEndian>(bbox.min.m)?;
+            out.write_f64::<LittleEndian>(bbox.max.m)?;
+            for pt in self.points() {
+                out.write_f64::<LittleEndian>(pt.m)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shape type of the written file
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ShapeFormat {
+    pub shape_type: ShapeType,
+    /// Measures are optional for Z types
+    pub has_m: bool,
+}
+
+impl ShapeFormat {
+    pub fn new(kind: ShapeKind, has_z: bool, has_m: bool) -> Self {
+        use ShapeType::*;
+        let types = match kind {
+            ShapeKind::Null => [NullShape, NullShape, NullShape],
+            ShapeKind::Point => [Point, PointZ, PointM],
+            ShapeKind::Multipoint => [Multipoint, MultipointZ, MultipointM],
+            ShapeKind::Polyline => [Polyline, PolylineZ, PolylineM],
+            ShapeKind::Polygon => [Polygon, PolygonZ, PolygonM],
+        };
+        let shape_type = if has_z {
+            types[1]
+        } else if has_m {
+            types[2]
+        } else {
+            types[0]
+        };
+        ShapeFormat {
+            shape_type,
+            has_m: has_m && kind != ShapeKind::Null,
+        }
+    }
+
+    fn has_z(&self) -> bool {
+        self.shape_type.has_z()
+    }
+}
+
+/// Write .shp and .shx content
+pub(crate) fn write_shapes<W: Write>(
+    shp: &mut W,
+    shx: &mut W,
+    shapes: &[Shape],
+    format: ShapeFormat,
+) -> Result<(), Error> {
+    let content_sizes: Vec<usize> = shapes.iter().map(|s| s.content_size(format)).collect();
+    let shp_size = HEADER_SIZE as usize
+        + content_sizes
+            .iter()
+            .map(|size| RecordHeader::SIZE + size)
+            .sum::<usize>();
+    let shx_size = HEADER_SIZE as usize + shapes.len() * RecordHeader::SIZE;
+
+    let mut header = Header::default();
+    header.shape_type = format.shape_type;
+    let mut bboxes = shapes.iter().filter_map(|shape| shape.bbox());
+    if let Some(mut bbox) = bboxes.next() {
+        for other in bboxes {
+            bbox_extend(&mut bbox, &other.min);
+            bbox_extend(&mut bbox, &other.max);
+        }
+        if !format.has_z() {
+            bbox.min.z = 0.0;
+            bbox.max.z = 0.0;
+        }
+        if !format.has_m || is_no_data(bbox.min.m) {
+            bbox.min.m = 0.0;
+            bbox.max.m = 0.0;
+        }
+        header.bbox = bbox;
+    } else {
+        header.bbox.min.m = 0.0;
+        header.bbox.max.m = 0.0;
+    }
+    header.file_length = (shp_size / 2) as i32;
+    header.write_to(shp)?;
+    header.file_length = (shx_size / 2) as i32;
+    header.write_to(shx)?;
+
+    let mut offset = HEADER_SIZE as usize;
+    for (i, (shape, size)) in shapes.iter().zip(content_sizes).enumerate() {
+        shp.write_i32::<BigEndian>(i as i32 + 1)?;
+        shp.write_i32::<BigEndian>((size / 2) as i32)?;
+        shape.write_content(shp, format)?;
+
+        shx.write_i32::<BigEndian>((offset / 2) as i32)?;
+        shx.write_i32::<BigEndian>((size / 2) as i32)?;
+        offset += RecordHeader::SIZE + size;
+    }
+    Ok(())
+}
+
+/// Close ring and orient it clockwise for outer rings and counterclockwise for holes
+pub(crate) fn orient_ring(ring: &mut Vec<PointZ>, outer: bool) {
+    if let (Some(first), Some(last)) = (ring.first(), ring.last()) {
+        if first.x != last.x || first.y != last.y {
+            ring.push(*first);
+        }
+    }
+    // positive for clockwise rings (see `ring_type_from_points_ordering`)
+    let area = ring
+        .windows(2)
+        .map(|pts| (pts[1].x - pts[0].x) * (pts[1].y + pts[0].y))
+        .sum::<f64>();
+    if (outer && area < 0.0) || (!outer && area > 0.0) {
+        ring.reverse();
+    }
+}
+
+fn bbox_extend(bbox: &mut BBoxZ, pt: &PointZ) {
+    bbox.min.x = bbox.min.x.min(pt.x);
+    bbox.min.y = bbox.min.y.min(pt.y);
+    bbox.min.z = bbox.min.z.min(pt.z);
+    bbox.max.x = bbox.max.x.max(pt.x);
+    bbox.max.y = bbox.max.y.max(pt.y);
+    bbox.max.z = bbox.max.z.max(pt.z);
+    if !is_no_data(pt.m) {
+        if is_no_data(bbox.min.m) || pt.m < bbox.min.m {
+            bbox.min.m = pt.m;
+        }
+        if is_no_data(bbox.max.m) || pt.m > bbox.max.m {
+            bbox.max.m = pt.m;
+        }
+    }
+}
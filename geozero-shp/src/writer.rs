@@ -0,0 +1,331 @@
+use crate::dbf_writer::{field_names, write_dbf, DbfField, FieldValue};
+use crate::point_z::PointZ;
+use crate::shp_reader::NO_DATA;
+use crate::shp_writer::{orient_ring, write_shapes, Shape, ShapeFormat, ShapeKind};
+use crate::Error;
+use geozero::error::{GeozeroError, Result};
+use geozero::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Shapefile writer
+///
+/// Shapes and attributes are buffered and written to the .shp, .shx and .dbf outputs
+/// at the end of the dataset, or when calling [finish()](struct.ShpWriter.html#method.finish).
+///
+/// # Usage example:
+///
+/// ```rust,no_run
+/// use geozero::geojson::GeoJson;
+/// use geozero::GeozeroDatasource;
+///
+/// let mut writer = geozero_shp::ShpWriter::from_path("points.shp")?;
+/// let mut geojson = GeoJson(r#"{"type": "FeatureCollection", "features": []}"#);
+/// geojson.process(&mut writer)?;
+/// # Ok::<(), geozero_shp::Error>(())
+/// ```
+pub struct ShpWriter<W: Write> {
+    /// Write Z and/or M shape types
+    pub dims: CoordDimensions,
+    /// Write all points as Multipoint if points and multipoints are mixed,
+    /// instead of returning an error.
+    pub promote_to_multi: bool,
+    shp: W,
+    shx: W,
+    dbf: W,
+    shapes: Vec<Shape>,
+    columns: Vec<String>,
+    records: Vec<Vec<Option<FieldValue>>>,
+    current: Option<Shape>,
+    depth: usize,
+    in_polygon: bool,
+    in_feature_geometry: bool,
+    finished: bool,
+}
+
+impl<W: Write> ShpWriter<W> {
+    /// Creates a writer with outputs for the .shp, .shx and .dbf content
+    pub fn new(shp: W, shx: W, dbf: W) -> Self {
+        ShpWriter {
+            dims: CoordDimensions::default(),
+            promote_to_multi: false,
+            shp,
+            shx,
+            dbf,
+            shapes: Vec::new(),
+            columns: Vec::new(),
+            records: Vec::new(),
+            current: None,
+            depth: 0,
+            in_polygon: false,
+            in_feature_geometry: false,
+            finished: false,
+        }
+    }
+
+    /// Write buffered shapes and records
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shapes can't be written with a single shape type.
+    pub fn finish(&mut self) -> std::result::Result<(), Error> {
+        if self.finished {
+            return Ok(());
+        }
+        let kind = self.shape_kind()?;
+        if kind == ShapeKind::Multipoint {
+            for shape in &mut self.shapes {
+                if shape.kind == ShapeKind::Point {
+                    shape.kind = ShapeKind::Multipoint;
+                }
+            }
+        }
+        let format = ShapeFormat::new(kind, self.dims.z, self.dims.m);
+        write_shapes(&mut self.shp, &mut self.shx, &self.shapes, format)?;
+
+        // features without properties
+        self.records.resize(self.shapes.len(), Vec::new());
+        let fields: Vec<DbfField> = field_names(&self.columns)
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values = self
+                    .records
+                    .iter()
+                    .filter_map(move |record| record.get(i).and_then(|v| v.as_ref()));
+                DbfField::infer(name, values)
+            })
+            .collect();
+        write_dbf(&mut self.dbf, &fields, &self.records)?;
+
+        self.shp.flush()?;
+        self.shx.flush()?;
+        self.dbf.flush()?;
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Common shape type of all shapes
+    fn shape_kind(&self) -> std::result::Result<ShapeKind, Error> {
+        let mut kind = ShapeKind::Null;
+        for shape in &self.shapes {
+            kind = match (kind, shape.kind) {
+                (k, ShapeKind::Null) => k,
+                (ShapeKind::Null, k) => k,
+                (k1, k2) if k1 == k2 => k1,
+                (ShapeKind::Point, ShapeKind::Multipoint)
+                | (ShapeKind::Multipoint, ShapeKind::Point)
+                    if self.promote_to_multi =>
+                {
+                    ShapeKind::Multipoint
+                }
+                (k1, k2) => {
+                    return Err(Error::GeozeroError(GeozeroError::Geometry(format!(
+                        "Mixed geometry types {:?} and {:?} not supported in Shapefile",
+                        k1, k2
+                    ))))
+                }
+            };
+        }
+        Ok(kind)
+    }
+
+    fn begin_shape(&mut self, kind: ShapeKind) {
+        if self.depth == 0 {
+            let shape = self.current.get_or_insert_with(Shape::null);
+            shape.kind = kind;
+        }
+        self.depth += 1;
+    }
+
+    fn end_shape(&mut self) {
+        self.depth -= 1;
+        if self.depth == 0 && !self.in_feature_geometry {
+            self.shapes
+                .push(self.current.take().unwrap_or_else(Shape::null));
+        }
+    }
+
+    fn begin_part(&mut self, size: usize) -> Result<()> {
+        let shape = self
+            .current
+            .as_mut()
+            .ok_or_else(|| GeozeroError::Geometry("Missing geometry begin".to_string()))?;
+        shape.parts.push(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn add_point(&mut self, pt: PointZ) -> Result<()> {
+        let part = self
+            .current
+            .as_mut()
+            .and_then(|shape| shape.parts.last_mut())
+            .ok_or_else(|| GeozeroError::Geometry("Missing geometry begin".to_string()))?;
+        part.push(pt);
+        Ok(())
+    }
+}
+
+impl ShpWriter<BufWriter<File>> {
+    /// Creates a writer for the given .shp path
+    ///
+    /// The .shx and .dbf files are created next to it.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> std::result::Result<Self, Error> {
+        let shape_path = path.as_ref().to_path_buf();
+        let shp = BufWriter::new(File::create(&shape_path)?);
+        let shx = BufWriter::new(File::create(shape_path.with_extension("shx"))?);
+        let dbf = BufWriter::new(File::create(shape_path.with_extension("dbf"))?);
+        Ok(Self::new(shp, shx, dbf))
+    }
+}
+
+impl<W: Write> GeomProcessor for ShpWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_point(PointZ::new(x, y, 0.0, NO_DATA))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_point(PointZ::new(x, y, z.unwrap_or(0.0), m.unwrap_or(NO_DATA)))
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Null);
+        self.end_shape();
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Point);
+        if self.depth == 1 {
+            self.begin_part(1)?;
+        }
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape();
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Multipoint);
+        self.begin_part(size)
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape();
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if !self.in_polygon {
+            self.begin_shape(ShapeKind::Polyline);
+        } else {
+            self.depth += 1;
+        }
+        self.begin_part(size)
+    }
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        if self.in_polygon {
+            if let Some(ring) = self
+                .current
+                .as_mut()
+                .and_then(|shape| shape.parts.last_mut())
+            {
+                orient_ring(ring, idx == 0);
+            }
+            self.depth -= 1;
+        } else {
+            self.end_shape();
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Polyline);
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape();
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Polygon);
+        self.in_polygon = true;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.in_polygon = false;
+        self.end_shape();
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeKind::Polygon);
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape();
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(GeozeroError::Geometry(
+            "GeometryCollection not supported in Shapefile".to_string(),
+        ))
+    }
+}
+
+impl<W: Write> PropertyProcessor for ShpWriter<W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let col = match self.columns.iter().position(|col| col == name) {
+            Some(col) => col,
+            None => {
+                self.columns.push(name.to_string());
+                self.columns.len() - 1
+            }
+        };
+        if let Some(record) = self.records.last_mut() {
+            if record.len() <= col {
+                record.resize(col + 1, None);
+            }
+            record[col] = FieldValue::from_column_value(value);
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write> FeatureProcessor for ShpWriter<W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        self.finish().map_err(|e| match e {
+            Error::GeozeroError(e) => e,
+            Error::IoError(e) => GeozeroError::IoError(e),
+            e => GeozeroError::Dataset(e.to_string()),
+        })
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.records.push(Vec::new());
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        // feature without geometry
+        if self.shapes.len() < self.records.len() {
+            self.shapes.push(Shape::null());
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.in_feature_geometry = true;
+        self.current = Some(Shape::null());
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.in_feature_geometry = false;
+        self.shapes
+            .push(self.current.take().unwrap_or_else(Shape::null));
+        Ok(())
+    }
+}
@@ -330,3 +330,66 @@ fn polygonzm() -> Result<(), geozero_shp::Error> {
 
     Ok(())
 }
+
+#[test]
+fn polygon_ring_nesting() -> Result<(), geozero_shp::Error> {
+    // hole of the first polygon is stored after the second polygon
+    let reader = geozero_shp::Reader::from_path("./tests/data/polygon_nested.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.iter_geometries(WktWriter::new(&mut wkt_data)).next();
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2)),((20 0,20 10,30 10,30 0,20 0)))"
+    );
+    Ok(())
+}
+
+#[test]
+fn multipatch() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/multipatch.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::new(&mut wkt_data);
+    writer.dims.z = true;
+    reader.iter_geometries(writer).next();
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOLYGON(((0 0 0,0 0 3,5 0 0,0 0 0)),((0 0 3,5 0 0,5 0 3,0 0 3)),((5 0 0,5 0 3,5 5 0,5 0 0)),((5 0 3,5 5 0,5 5 3,5 0 3)),((5 5 0,5 5 3,0 5 0,5 5 0)),((5 5 3,0 5 0,0 5 3,5 5 3)),((0 5 0,0 5 3,0 0 0,0 5 0)),((0 5 3,0 0 0,0 0 3,0 5 3)),((2.5 2.5 5,0 0 3,5 0 3,2.5 2.5 5)),((2.5 2.5 5,5 0 3,5 5 3,2.5 2.5 5)),((2.5 2.5 5,5 5 3,0 5 3,2.5 2.5 5)),((2.5 2.5 5,0 5 3,0 0 3,2.5 2.5 5)))"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "with-zip")]
+#[test]
+fn zipped() -> Result<(), geozero_shp::Error> {
+    use std::io::{Cursor, Write};
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    zip.add_directory("__MACOSX/", options)?;
+    for (name, path) in [
+        ("data/poly.shp", "./tests/data/poly.shp"),
+        ("data/poly.shx", "./tests/data/poly.shx"),
+        ("data/poly.DBF", "./tests/data/poly.dbf"),
+    ] {
+        zip.start_file(name, options)?;
+        zip.write_all(&std::fs::read(path)?)?;
+    }
+    let archive = zip.finish()?;
+
+    let reader = geozero_shp::Reader::from_zip(archive, None)?;
+    assert_eq!(reader.header().shape_type, geozero_shp::ShapeType::Polygon);
+    let mut cnt = 0;
+    for feat in reader.iter_features(ProcessorSink::new())? {
+        assert!(feat?.property::<f64>("EAS_ID").unwrap() > 100.0);
+        cnt += 1;
+    }
+    assert_eq!(cnt, 10);
+
+    let archive = Cursor::new(Vec::new());
+    assert!(geozero_shp::Reader::from_zip(
+        zip::ZipWriter::new(archive).finish()?,
+        Some("data/poly.shp")
+    )
+    .is_err());
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use geozero::geojson::{GeoJson, GeoJsonWriter};
+use geozero::wkt::WktWriter;
+use geozero::{FeatureProperties, GeozeroDatasource, ProcessorSink};
+use geozero_shp::{Reader, ShapeType, ShpWriter};
+use std::path::PathBuf;
+use std::str::from_utf8;
+
+fn temp_shp(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("geozero-shp-{}.shp", name))
+}
+
+#[test]
+fn write_points() -> Result<(), geozero_shp::Error> {
+    let path = temp_shp("points");
+    let mut geojson = GeoJson(
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a", "count": 12, "value": 1.5, "ok": true}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "bcd", "count": -345, "value": 2}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#,
+    );
+    let mut writer = ShpWriter::from_path(&path)?;
+    geojson.process(&mut writer)?;
+    drop(writer);
+
+    let reader = Reader::from_path(&path)?;
+    assert_eq!(reader.header().shape_type, ShapeType::Point);
+    assert_eq!(reader.header().bbox.x_range(), [1.0, 3.0]);
+    let fields = reader
+        .dbf_fields()?
+        .iter()
+        .map(|f| format!("{}:{}", f.name(), f.length()))
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["name:3", "count:4", "value:3", "ok:1"]);
+
+    let mut cnt = 0;
+    for feat in reader.iter_features(ProcessorSink::new())? {
+        let feat = feat?;
+        if cnt == 0 {
+            assert_eq!(feat.property::<String>("name").unwrap(), "a");
+            assert_eq!(feat.property::<f64>("count").unwrap(), 12.0);
+            assert_eq!(feat.property::<f64>("value").unwrap(), 1.5);
+        } else {
+            assert_eq!(feat.property::<f64>("count").unwrap(), -345.0);
+            assert_eq!(feat.property::<f64>("value").unwrap(), 2.0);
+        }
+        cnt += 1;
+    }
+    assert_eq!(cnt, 2);
+
+    let reader = Reader::from_path(&path)?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader
+        .iter_geometries(WktWriter::new(&mut wkt_data))
+        .count();
+    assert_eq!(from_utf8(&wkt_data).unwrap(), "POINT(1 2)POINT(3 4)");
+    Ok(())
+}
+
+#[test]
+fn write_polygons() -> Result<(), geozero_shp::Error> {
+    let path = temp_shp("polygons");
+    // counterclockwise exterior ring and clockwise hole
+    let mut geojson = GeoJson(
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[10,0],[10,10],[0,10],[0,0]],[[2,2],[2,4],[4,4],[4,2],[2,2]]]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[20,0],[30,0],[30,10],[20,0]]]]}}
+        ]}"#,
+    );
+    let mut writer = ShpWriter::from_path(&path)?;
+    geojson.process(&mut writer)?;
+    drop(writer);
+
+    let reader = Reader::from_path(&path)?;
+    assert_eq!(reader.header().shape_type, ShapeType::Polygon);
+    let mut json: Vec<u8> = Vec::new();
+    let cnt = reader.iter_features(GeoJsonWriter::new(&mut json))?.count();
+    assert_eq!(cnt, 2);
+
+    let reader = Reader::from_path(&path)?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader
+        .iter_geometries(WktWriter::new(&mut wkt_data))
+        .count();
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2)))MULTIPOLYGON(((20 0,30 10,30 0,20 0)))"
+    );
+    Ok(())
+}
+
+#[test]
+fn mixed_types() -> Result<(), geozero_shp::Error> {
+    let features = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "MultiPoint", "coordinates": [[3, 4], [5, 6]]}}
+        ]}"#;
+
+    let path = temp_shp("mixed");
+    let mut writer = ShpWriter::from_path(&path)?;
+    assert!(GeoJson(features).process(&mut writer).is_err());
+    // failed writer isn't marked as finished
+    assert!(writer.finish().is_err());
+
+    let mut writer = ShpWriter::from_path(&path)?;
+    writer.promote_to_multi = true;
+    GeoJson(features).process(&mut writer)?;
+    drop(writer);
+
+    let reader = Reader::from_path(&path)?;
+    assert_eq!(reader.header().shape_type, ShapeType::Multipoint);
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader
+        .iter_geometries(WktWriter::new(&mut wkt_data))
+        .count();
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOINT(1 2)MULTIPOINT(3 4,5 6)"
+    );
+    Ok(())
+}
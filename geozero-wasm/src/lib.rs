@@ -0,0 +1,128 @@
+//! WebAssembly bindings for GeoZero conversions.
+//!
+//! Exposes the streaming converters of GeoZero to JavaScript via
+//! [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/):
+//!
+//! ```js
+//! import { wktToGeoJson, fgbToGeoJson } from "geozero-wasm";
+//!
+//! wktToGeoJson("POINT(10 -20)"); // '{"type": "Point", "coordinates": [10,-20]}'
+//! const bytes = new Uint8Array(await (await fetch("countries.fgb")).arrayBuffer());
+//! fgbToGeoJson(bytes, [8.8, 47.2, 9.5, 55.3]);
+//! ```
+//!
+//! The conversion functions are also available as plain Rust functions in [convert].
+
+use wasm_bindgen::prelude::*;
+
+/// Conversion functions with GeoZero errors.
+pub mod convert {
+    use flatgeobuf::FgbReader;
+    use geozero::error::{GeozeroError, Result};
+    use geozero::geojson::GeoJson;
+    use geozero::wkb::{Wkb, WkbDialect, WkbWriter};
+    use geozero::wkt::WktStr;
+    use geozero::{GeozeroGeometry, ProcessToJson, ToJson, ToWkt};
+    use std::io::Cursor;
+
+    fn to_wkb<G: GeozeroGeometry>(geom: &G) -> Result<Vec<u8>> {
+        let mut wkb: Vec<u8> = Vec::new();
+        {
+            let mut writer = WkbWriter::new(&mut wkb, WkbDialect::Wkb);
+            writer.auto_dims = true;
+            geom.process_geom(&mut writer)?;
+        }
+        Ok(wkb)
+    }
+
+    /// Convert a WKB geometry to GeoJSON.
+    pub fn wkb_to_geojson(wkb: &[u8]) -> Result<String> {
+        Wkb(wkb.to_vec()).to_json()
+    }
+
+    /// Convert a WKB geometry to WKT.
+    pub fn wkb_to_wkt(wkb: &[u8]) -> Result<String> {
+        Wkb(wkb.to_vec()).to_wkt()
+    }
+
+    /// Convert a GeoJSON geometry to WKB, keeping Z and M coordinates.
+    pub fn geojson_to_wkb(json: &str) -> Result<Vec<u8>> {
+        to_wkb(&GeoJson(json))
+    }
+
+    /// Convert a GeoJSON geometry to WKT.
+    pub fn geojson_to_wkt(json: &str) -> Result<String> {
+        GeoJson(json).to_wkt()
+    }
+
+    /// Convert a WKT geometry to WKB, keeping Z and M coordinates.
+    pub fn wkt_to_wkb(wkt: &str) -> Result<Vec<u8>> {
+        to_wkb(&WktStr(wkt))
+    }
+
+    /// Convert a WKT geometry to GeoJSON.
+    pub fn wkt_to_geojson(wkt: &str) -> Result<String> {
+        WktStr(wkt).to_json()
+    }
+
+    /// Convert a FlatGeobuf file to a GeoJSON FeatureCollection.
+    ///
+    /// With `bbox` `[minx, miny, maxx, maxy]`, only features intersecting the bounding box
+    /// are read, using the spatial index of the file.
+    pub fn fgb_to_geojson(fgb: &[u8], bbox: Option<&[f64]>) -> Result<String> {
+        let mut reader = Cursor::new(fgb);
+        let fgb = FgbReader::open(&mut reader)?;
+        let mut fgb = match bbox {
+            Some(&[minx, miny, maxx, maxy]) => fgb.select_bbox(minx, miny, maxx, maxy)?,
+            Some(_) => return Err(GeozeroError::Dataset("bbox must have 4 values".to_string())),
+            None => fgb.select_all()?,
+        };
+        fgb.to_json()
+    }
+}
+
+fn js_error(e: geozero::error::GeozeroError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Convert a WKB geometry to GeoJSON.
+#[wasm_bindgen(js_name = wkbToGeoJson)]
+pub fn wkb_to_geojson(wkb: &[u8]) -> Result<String, JsError> {
+    convert::wkb_to_geojson(wkb).map_err(js_error)
+}
+
+/// Convert a WKB geometry to WKT.
+#[wasm_bindgen(js_name = wkbToWkt)]
+pub fn wkb_to_wkt(wkb: &[u8]) -> Result<String, JsError> {
+    convert::wkb_to_wkt(wkb).map_err(js_error)
+}
+
+/// Convert a GeoJSON geometry to WKB.
+#[wasm_bindgen(js_name = geoJsonToWkb)]
+pub fn geojson_to_wkb(json: &str) -> Result<Vec<u8>, JsError> {
+    convert::geojson_to_wkb(json).map_err(js_error)
+}
+
+/// Convert a GeoJSON geometry to WKT.
+#[wasm_bindgen(js_name = geoJsonToWkt)]
+pub fn geojson_to_wkt(json: &str) -> Result<String, JsError> {
+    convert::geojson_to_wkt(json).map_err(js_error)
+}
+
+/// Convert a WKT geometry to WKB.
+#[wasm_bindgen(js_name = wktToWkb)]
+pub fn wkt_to_wkb(wkt: &str) -> Result<Vec<u8>, JsError> {
+    convert::wkt_to_wkb(wkt).map_err(js_error)
+}
+
+/// Convert a WKT geometry to GeoJSON.
+#[wasm_bindgen(js_name = wktToGeoJson)]
+pub fn wkt_to_geojson(wkt: &str) -> Result<String, JsError> {
+    convert::wkt_to_geojson(wkt).map_err(js_error)
+}
+
+/// Convert a FlatGeobuf file to a GeoJSON FeatureCollection, optionally filtered by bbox.
+#[wasm_bindgen(js_name = fgbToGeoJson)]
+pub fn fgb_to_geojson(fgb: &[u8], bbox: Option<Vec<f64>>) -> Result<String, JsError> {
+    convert::fgb_to_geojson(fgb, bbox.as_deref()).map_err(js_error)
+}
@@ -0,0 +1,39 @@
+use geozero::error::Result;
+use geozero_wasm::convert::*;
+
+#[test]
+fn geometry_conversions() -> Result<()> {
+    let wkb = wkt_to_wkb("POINT(10 -20)")?;
+    assert_eq!(
+        wkb,
+        [1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 36, 64, 0, 0, 0, 0, 0, 0, 52, 192]
+    );
+    assert_eq!(wkb_to_wkt(&wkb)?, "POINT(10 -20)");
+    assert_eq!(
+        wkb_to_geojson(&wkb)?,
+        r#"{"type": "Point", "coordinates": [10,-20]}"#
+    );
+
+    let json = r#"{"type": "LineString", "coordinates": [[1,2,3],[4,5,6]]}"#;
+    assert_eq!(geojson_to_wkt(json)?, "LINESTRING(1 2 3,4 5 6)");
+    let wkb = geojson_to_wkb(json)?;
+    assert_eq!(wkb_to_wkt(&wkb)?, "LINESTRING(1 2 3,4 5 6)");
+    assert_eq!(
+        wkt_to_geojson("LINESTRING(1 2,3 4)")?,
+        r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn fgb_with_bbox() -> Result<()> {
+    let fgb = std::fs::read("../geozero/tests/data/countries.fgb")?;
+    let json = fgb_to_geojson(&fgb, Some(&[8.8, 47.2, 9.5, 55.3]))?;
+    assert!(json.contains(r#""name": "Denmark""#));
+    assert!(!json.contains(r#""name": "Brazil""#));
+    assert!(fgb_to_geojson(&fgb, Some(&[8.8, 47.2])).is_err());
+
+    let json = fgb_to_geojson(&fgb, None)?;
+    assert!(json.contains(r#""name": "Brazil""#));
+    Ok(())
+}
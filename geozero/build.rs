@@ -1,4 +1,4 @@
-#[cfg(feature = "with-mvt")]
+#[cfg(any(feature = "with-mvt", feature = "with-geobuf"))]
 use std::{
     env,
     fs::OpenOptions,
@@ -6,30 +6,30 @@ use std::{
     path::Path,
 };
 
-#[cfg(feature = "with-mvt")]
-fn compile_protos() -> Result<(), Box<dyn std::error::Error>> {
+#[cfg(any(feature = "with-mvt", feature = "with-geobuf"))]
+fn compile_protos(dir: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
     // override the build location, in order to check in the changes to proto files
-    env::set_var("OUT_DIR", "src/mvt");
+    env::set_var("OUT_DIR", dir);
 
     // The current working directory can vary depending on how the project is being
     // built or released so we build an absolute path to the proto file
-    let path = Path::new("src/mvt/vector_tile.proto");
-    if path.exists() && cfg!(feature = "with-mvt") && std::env::var("DOCS_RS").is_err() {
+    let proto = format!("{}/{}.proto", dir, name);
+    let generated = format!("{}/{}.rs", dir, name);
+    let path = Path::new(&proto);
+    if path.exists() && std::env::var("DOCS_RS").is_err() {
         // avoid rerunning build if the file has not changed
-        println!("cargo:rerun-if-changed=src/mvt/vector_tile.proto");
+        println!("cargo:rerun-if-changed={}", proto);
 
-        prost_build::compile_protos(&["src/mvt/vector_tile.proto"], &["src/mvt/"])?;
+        prost_build::compile_protos(&[&proto], &[dir])?;
         // read file contents to string
-        let mut file = OpenOptions::new()
-            .read(true)
-            .open("src/mvt/vector_tile.rs")?;
+        let mut file = OpenOptions::new().read(true).open(&generated)?;
         let mut buffer = String::new();
         file.read_to_string(&mut buffer)?;
         // append warning that file was auto-generate
         let mut file = OpenOptions::new()
             .write(true)
             .truncate(true)
-            .open("src/mvt/vector_tile.rs")?;
+            .open(&generated)?;
         file.write_all("// This file was automatically generated through the build.rs script, and should not be edited.\n\n".as_bytes())?;
         file.write_all(buffer.as_bytes())?;
     }
@@ -40,7 +40,9 @@ fn compile_protos() -> Result<(), Box<dyn std::error::Error>> {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(feature = "with-mvt")]
-    compile_protos()?;
+    compile_protos("src/mvt", "vector_tile")?;
+    #[cfg(feature = "with-geobuf")]
+    compile_protos("src/geobuf", "geobuf")?;
 
     Ok(())
 }
@@ -0,0 +1,11 @@
+#![no_main]
+use geozero::wkb::Ewkb;
+use geozero::{GeozeroGeometry, ToGeo, ToWkt};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let geom = Ewkb(data.to_vec());
+    let _ = geom.dims();
+    let _ = geom.to_wkt();
+    let _ = geom.to_geo();
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use geozero::wkb::process_ewkb_geom;
+use geozero::wkt::WktWriter;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Element counts can't be checked against the input length of streams
+    let mut stream = std::io::Cursor::new(data);
+    let _ = process_ewkb_geom(&mut stream, &mut WktWriter::new(&mut Vec::new()));
+});
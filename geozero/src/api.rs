@@ -6,10 +6,14 @@
 
 use crate::error::{GeozeroError, Result};
 use crate::feature_processor::FeatureProcessor;
+use crate::processing::FeaturePage;
 use crate::property_processor::{
     PropertyProcessor, PropertyReadType, PropertyReader, PropertyReaderIdx,
 };
+use crate::schema::{DatasetSchema, SchemaProcessor};
+use crate::serde_properties::SerdeProperties;
 use crate::{CoordDimensions, GeomProcessor};
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 
 /// Geometry processing trait.
@@ -37,6 +41,120 @@ pub trait GeozeroDatasource {
         let mut geom_processor = DatasourceGeomProcessor(processor);
         self.process(&mut geom_processor)
     }
+    /// Column names and types, geometry type and dimensions of all selected features.
+    ///
+    /// The default implementation scans all features, which consumes datasources reading
+    /// from a stream. Datasources with a header describing the schema return it without scanning.
+    fn schema(&mut self) -> Result<DatasetSchema> {
+        let mut processor = SchemaProcessor::new();
+        self.process(&mut processor)?;
+        Ok(processor.into_schema())
+    }
+    /// Process features until a processor returns [GeozeroError::Stop].
+    ///
+    /// Processors can return `GeozeroError::Stop` from `feature_end` to end processing
+    /// gracefully, e.g. after N features or when a search condition is met. The dataset
+    /// is then finished with `dataset_end`. Returns `true`, if processing was stopped.
+    fn process_until_stop<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<bool> {
+        match self.process(processor) {
+            Ok(()) => Ok(false),
+            Err(e) if matches!(e.kind(), GeozeroError::Stop) => {
+                processor.dataset_end()?;
+                Ok(true)
+            }
+            Err(e) => Err(e),
+        }
+    }
+    /// Process at most `take` features after skipping the first `skip` features.
+    ///
+    /// Feature indices passed to the processor start at 0. The default implementation
+    /// reads and discards skipped features and stops reading after the last feature.
+    /// Indexed datasources override it to seek to the first feature.
+    fn process_range<P: FeatureProcessor>(
+        &mut self,
+        processor: &mut P,
+        skip: u64,
+        take: Option<u64>,
+    ) -> Result<()> {
+        let mut page = FeaturePage::new(skip, take, processor);
+        self.process_until_stop(&mut page).map(|_| ())
+    }
+    /// Datasource skipping the first `n` features.
+    ///
+    /// # Usage example:
+    ///
+    /// ```
+    /// use geozero::geojson::GeoJson;
+    /// use geozero::processing::StatsProcessor;
+    /// use geozero::GeozeroDatasource;
+    ///
+    /// let geojson = r#"{"type": "FeatureCollection", "features": [
+    ///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+    ///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [2, 2]}},
+    ///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 3]}}
+    /// ]}"#;
+    /// let mut stats = StatsProcessor::new();
+    /// GeoJson(geojson).skip(1).take(1).process(&mut stats).unwrap();
+    /// assert_eq!(stats.stats().features, 1);
+    /// ```
+    fn skip(&mut self, n: u64) -> FeatureRange<'_, Self>
+    where
+        Self: Sized,
+    {
+        FeatureRange {
+            source: self,
+            skip: n,
+            take: None,
+        }
+    }
+    /// Datasource processing at most `n` features.
+    fn take(&mut self, n: u64) -> FeatureRange<'_, Self>
+    where
+        Self: Sized,
+    {
+        FeatureRange {
+            source: self,
+            skip: 0,
+            take: Some(n),
+        }
+    }
+}
+
+/// Range of features of a datasource, e.g. a page of a large file.
+///
+/// Created with [GeozeroDatasource::skip] and [GeozeroDatasource::take].
+pub struct FeatureRange<'a, D> {
+    source: &'a mut D,
+    skip: u64,
+    take: Option<u64>,
+}
+
+impl<D: GeozeroDatasource> GeozeroDatasource for FeatureRange<'_, D> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        self.source.process_range(processor, self.skip, self.take)
+    }
+    fn process_range<P: FeatureProcessor>(
+        &mut self,
+        processor: &mut P,
+        skip: u64,
+        take: Option<u64>,
+    ) -> Result<()> {
+        let take = match (self.take, take) {
+            (Some(own), Some(take)) => Some(own.saturating_sub(skip).min(take)),
+            (Some(own), None) => Some(own.saturating_sub(skip)),
+            (None, take) => take,
+        };
+        self.source
+            .process_range(processor, self.skip.saturating_add(skip), take)
+    }
+}
+
+/// Datasource which can be processed again from the first feature.
+///
+/// Required for multi-pass processing like [convert_with_schema](crate::processing::convert_with_schema).
+pub trait RewindableDatasource: GeozeroDatasource {
+    /// Reset datasource to the first feature.
+    fn rewind(&mut self) -> Result<()>;
 }
 
 /// Feature processing API
@@ -91,6 +209,12 @@ pub trait FeatureProperties {
         let _ = self.process_properties(&mut properties)?;
         Ok(properties)
     }
+    /// Deserialize properties into a struct
+    fn properties_as<T: DeserializeOwned>(&self) -> Result<T> {
+        let mut properties = SerdeProperties::new();
+        let _ = self.process_properties(&mut properties)?;
+        properties.deserialize()
+    }
 }
 
 // Newtype for GeomProcessor impl for adding no-op PropertyProcessor/FeatureProcessor impl
@@ -201,3 +325,94 @@ impl<P: GeomProcessor> GeomProcessor for DatasourceGeomProcessor<'_, P> {
 
 impl<P: GeomProcessor> PropertyProcessor for DatasourceGeomProcessor<'_, P> {}
 impl<P: GeomProcessor> FeatureProcessor for DatasourceGeomProcessor<'_, P> {}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+
+    /// Stop after `limit` features
+    struct FirstN {
+        limit: u64,
+        events: Vec<String>,
+    }
+
+    impl GeomProcessor for FirstN {}
+    impl PropertyProcessor for FirstN {}
+    impl FeatureProcessor for FirstN {
+        fn feature_end(&mut self, idx: u64) -> Result<()> {
+            self.events.push(format!("f{}", idx));
+            if idx + 1 == self.limit {
+                return Err(GeozeroError::Stop);
+            }
+            Ok(())
+        }
+        fn dataset_end(&mut self) -> Result<()> {
+            self.events.push("end".to_string());
+            Ok(())
+        }
+    }
+
+    const GEOJSON: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [2, 2]}},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 3]}}
+    ]}"#;
+
+    #[test]
+    fn process_until_stop() -> Result<()> {
+        let mut processor = FirstN {
+            limit: 2,
+            events: Vec::new(),
+        };
+        assert!(GeoJson(GEOJSON).process_until_stop(&mut processor)?);
+        assert_eq!(processor.events, ["f0", "f1", "end"]);
+
+        let mut processor = FirstN {
+            limit: 5,
+            events: Vec::new(),
+        };
+        assert!(!GeoJson(GEOJSON).process_until_stop(&mut processor)?);
+        assert_eq!(processor.events, ["f0", "f1", "f2", "end"]);
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl GeomProcessor for Events {
+        fn xy(&mut self, x: f64, _y: f64, _idx: usize) -> Result<()> {
+            self.0.push(format!("x={}", x));
+            Ok(())
+        }
+    }
+    impl PropertyProcessor for Events {}
+    impl FeatureProcessor for Events {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(format!("f{}", idx));
+            Ok(())
+        }
+        fn dataset_end(&mut self) -> Result<()> {
+            self.0.push("end".to_string());
+            Ok(())
+        }
+    }
+
+    fn events<D: GeozeroDatasource>(mut source: D) -> Result<String> {
+        let mut processor = Events::default();
+        source.process(&mut processor)?;
+        Ok(processor.0.join(" "))
+    }
+
+    #[test]
+    fn feature_range() -> Result<()> {
+        assert_eq!(events(GeoJson(GEOJSON).skip(1))?, "f0 x=2 f1 x=3 end");
+        assert_eq!(events(GeoJson(GEOJSON).take(1))?, "f0 x=1 end");
+        assert_eq!(events(GeoJson(GEOJSON).skip(1).take(1))?, "f0 x=2 end");
+        assert_eq!(events(GeoJson(GEOJSON).take(2).skip(1))?, "f0 x=2 end");
+        assert_eq!(events(GeoJson(GEOJSON).take(0))?, "end");
+        assert_eq!(events(GeoJson(GEOJSON).skip(5))?, "end");
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
 use crate::wkb::wkb_reader::{process_wkb_geom_n, read_wkb_header};
-use crate::{GeomProcessor, GeozeroGeometry};
-use arrow2::array::{BinaryArray, Offset};
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource,
+    GeozeroGeometry, PropertyProcessor,
+};
+use arrow2::array::{
+    Array, BinaryArray, BooleanArray, FixedSizeListArray, ListArray, Offset, PrimitiveArray,
+    StructArray, Utf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::types::NativeType;
 
 impl GeozeroGeometry for BinaryArray<i32> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
@@ -33,6 +42,462 @@ pub fn process_geoarrow_wkb_geom<T: Offset>(
     Ok(())
 }
 
+/// GeoArrow geometry encoding of an Arrow column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeoArrowType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    Wkb,
+}
+
+impl GeoArrowType {
+    /// Geometry encoding from the `ARROW:extension:name` of a field.
+    ///
+    /// Binary fields without extension name are assumed to contain WKB.
+    pub fn from_field(field: &Field) -> Option<Self> {
+        GeoArrowType::from_extension(field).or_else(|| match field.data_type.to_logical_type() {
+            DataType::Binary | DataType::LargeBinary => Some(GeoArrowType::Wkb),
+            _ => None,
+        })
+    }
+
+    /// Geometry encoding of fields with a GeoArrow extension name.
+    fn from_extension(field: &Field) -> Option<Self> {
+        let name = match &field.data_type {
+            DataType::Extension(name, _, _) => Some(name.as_str()),
+            _ => field
+                .metadata
+                .get("ARROW:extension:name")
+                .map(|name| name.as_str()),
+        };
+        match name {
+            Some("geoarrow.point") => Some(GeoArrowType::Point),
+            Some("geoarrow.linestring") => Some(GeoArrowType::LineString),
+            Some("geoarrow.polygon") => Some(GeoArrowType::Polygon),
+            Some("geoarrow.multipoint") => Some(GeoArrowType::MultiPoint),
+            Some("geoarrow.multilinestring") => Some(GeoArrowType::MultiLineString),
+            Some("geoarrow.multipolygon") => Some(GeoArrowType::MultiPolygon),
+            Some("geoarrow.wkb") => Some(GeoArrowType::Wkb),
+            _ => None,
+        }
+    }
+}
+
+/// Reader for Arrow record batches with a GeoArrow geometry column.
+///
+/// Geometries are read from nested list coordinate arrays (interleaved or struct
+/// coordinates) or WKB columns. Further columns with a GeoArrow extension name are
+/// processed as additional geometries (see
+/// [multiple_geometries](crate::FeatureProcessor::multiple_geometries)).
+/// All other columns are processed as properties.
+pub struct GeoArrowReader<'a> {
+    schema: &'a Schema,
+    chunks: &'a [Chunk<Box<dyn Array>>],
+    geometry_idx: usize,
+    geometry_type: GeoArrowType,
+    /// Column index and encoding of additional geometry columns
+    additional_geometries: Vec<(usize, GeoArrowType)>,
+}
+
+impl<'a> GeoArrowReader<'a> {
+    pub fn new(
+        schema: &'a Schema,
+        chunks: &'a [Chunk<Box<dyn Array>>],
+        geometry_column: &str,
+    ) -> Result<Self> {
+        let geometry_idx = schema
+            .fields
+            .iter()
+            .position(|f| f.name == geometry_column)
+            .ok_or(GeozeroError::ColumnNotFound)?;
+        let geometry_type = GeoArrowType::from_field(&schema.fields[geometry_idx])
+            .ok_or_else(|| GeozeroError::Geometry("No GeoArrow geometry column".to_string()))?;
+        let additional_geometries = schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != geometry_idx)
+            .filter_map(|(i, field)| GeoArrowType::from_extension(field).map(|t| (i, t)))
+            .collect();
+        Ok(GeoArrowReader {
+            schema,
+            chunks,
+            geometry_idx,
+            geometry_type,
+            additional_geometries,
+        })
+    }
+}
+
+impl GeoArrowReader<'_> {
+    fn is_geometry(&self, col: usize) -> bool {
+        col == self.geometry_idx || self.additional_geometries.iter().any(|(i, _)| *i == col)
+    }
+
+    fn process_row<P: FeatureProcessor>(
+        &self,
+        chunk: &Chunk<Box<dyn Array>>,
+        row: usize,
+        feature_idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geometries = &chunk.arrays()[self.geometry_idx];
+        processor.feature_begin(feature_idx)?;
+        processor.properties_begin()?;
+        process_properties(
+            self.schema,
+            chunk,
+            &|col| self.is_geometry(col),
+            row,
+            processor,
+        )?;
+        processor.properties_end()?;
+        if geometries.is_valid(row) {
+            processor.geometry_begin()?;
+            process_geoarrow_geom_n(geometries.as_ref(), self.geometry_type, row, 0, processor)?;
+            processor.geometry_end()?;
+        } else {
+            processor.null_geometry()?;
+        }
+        if processor.multiple_geometries() {
+            for (i, (col, geometry_type)) in self.additional_geometries.iter().enumerate() {
+                let geometries = &chunk.arrays()[*col];
+                if !geometries.is_valid(row) {
+                    continue;
+                }
+                let name = &self.schema.fields[*col].name;
+                processor.geometry_column_begin(i + 1, name)?;
+                process_geoarrow_geom_n(geometries.as_ref(), *geometry_type, row, 0, processor)?;
+                processor.geometry_column_end(i + 1, name)?;
+            }
+        }
+        processor.feature_end(feature_idx)
+    }
+}
+
+impl GeozeroDatasource for GeoArrowReader<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        processor.dataset_begin(None)?;
+        let mut feature_idx = 0;
+        for chunk in self.chunks {
+            for row in 0..chunk.len() {
+                self.process_row(chunk, row, feature_idx, processor)?;
+                feature_idx += 1;
+            }
+        }
+        processor.dataset_end()
+    }
+    fn process_range<P: FeatureProcessor>(
+        &mut self,
+        processor: &mut P,
+        skip: u64,
+        take: Option<u64>,
+    ) -> Result<()> {
+        processor.dataset_begin(None)?;
+        let end = take.map_or(u64::MAX, |take| skip.saturating_add(take));
+        let mut offset = 0;
+        for chunk in self.chunks {
+            let len = chunk.len() as u64;
+            for row in skip.max(offset)..end.min(offset + len) {
+                self.process_row(chunk, (row - offset) as usize, row - skip, processor)?;
+            }
+            offset += len;
+            if offset >= end {
+                break;
+            }
+        }
+        processor.dataset_end()
+    }
+}
+
+#[cfg(feature = "with-rayon")]
+impl crate::ParallelDatasource for GeoArrowReader<'_> {
+    fn feature_count(&self) -> u64 {
+        self.chunks.iter().map(|chunk| chunk.len() as u64).sum()
+    }
+    fn process_feature<P: FeatureProcessor>(&self, idx: u64, processor: &mut P) -> Result<()> {
+        let mut row = idx as usize;
+        for chunk in self.chunks {
+            if row < chunk.len() {
+                return self.process_row(chunk, row, idx, processor);
+            }
+            row -= chunk.len();
+        }
+        Err(GeozeroError::Feature(format!("index {} out of range", idx)))
+    }
+}
+
+fn process_properties<P: PropertyProcessor>(
+    schema: &Schema,
+    chunk: &Chunk<Box<dyn Array>>,
+    is_geometry: &dyn Fn(usize) -> bool,
+    row: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let mut i = 0;
+    for (col, (field, array)) in schema.fields.iter().zip(chunk.arrays()).enumerate() {
+        if is_geometry(col) || array.is_null(row) {
+            continue;
+        }
+        let array = array.as_ref();
+        let value = match field.data_type.to_logical_type() {
+            DataType::Boolean => ColumnValue::Bool(downcast::<BooleanArray>(array)?.value(row)),
+            DataType::Int8 => ColumnValue::Byte(primitive_value(array, row)?),
+            DataType::UInt8 => ColumnValue::UByte(primitive_value(array, row)?),
+            DataType::Int16 => ColumnValue::Short(primitive_value(array, row)?),
+            DataType::UInt16 => ColumnValue::UShort(primitive_value(array, row)?),
+            DataType::Int32 => ColumnValue::Int(primitive_value(array, row)?),
+            DataType::UInt32 => ColumnValue::UInt(primitive_value(array, row)?),
+            DataType::Int64 => ColumnValue::Long(primitive_value(array, row)?),
+            DataType::UInt64 => ColumnValue::ULong(primitive_value(array, row)?),
+            DataType::Float32 => ColumnValue::Float(primitive_value(array, row)?),
+            DataType::Float64 => ColumnValue::Double(primitive_value(array, row)?),
+            DataType::Utf8 => ColumnValue::String(downcast::<Utf8Array<i32>>(array)?.value(row)),
+            DataType::LargeUtf8 => {
+                ColumnValue::String(downcast::<Utf8Array<i64>>(array)?.value(row))
+            }
+            DataType::Binary => {
+                ColumnValue::Binary(downcast::<BinaryArray<i32>>(array)?.value(row))
+            }
+            DataType::LargeBinary => {
+                ColumnValue::Binary(downcast::<BinaryArray<i64>>(array)?.value(row))
+            }
+            // nested and temporal types are not supported
+            _ => continue,
+        };
+        if processor.property(i, &field.name, &value)? {
+            break;
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> Result<&T> {
+    array
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| GeozeroError::Dataset("Unexpected Arrow array type".to_string()))
+}
+
+fn primitive_value<T: NativeType>(array: &dyn Array, row: usize) -> Result<T> {
+    Ok(downcast::<PrimitiveArray<T>>(array)?.value(row))
+}
+
+/// Process geometry at position `row` of a GeoArrow array.
+pub fn process_geoarrow_geom_n<P: GeomProcessor>(
+    array: &dyn Array,
+    geometry_type: GeoArrowType,
+    row: usize,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match geometry_type {
+        GeoArrowType::Point => {
+            let coords = Coords::new(array)?;
+            processor.point_begin(idx)?;
+            coords.process(row, 0, processor)?;
+            processor.point_end(idx)
+        }
+        GeoArrowType::LineString => {
+            let lines = List::new(array)?;
+            let coords = Coords::new(lines.values)?;
+            process_linestring(&coords, lines.range(row), true, idx, processor)
+        }
+        GeoArrowType::MultiPoint => {
+            let points = List::new(array)?;
+            let coords = Coords::new(points.values)?;
+            let range = points.range(row);
+            processor.multipoint_begin(range.len(), idx)?;
+            for (idxc, pos) in range.enumerate() {
+                processor.point_begin(idxc)?;
+                coords.process(pos, 0, processor)?;
+                processor.point_end(idxc)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        GeoArrowType::Polygon => {
+            let polygons = List::new(array)?;
+            let rings = List::new(polygons.values)?;
+            let coords = Coords::new(rings.values)?;
+            process_polygon(&rings, &coords, polygons.range(row), true, idx, processor)
+        }
+        GeoArrowType::MultiLineString => {
+            let multilines = List::new(array)?;
+            let lines = List::new(multilines.values)?;
+            let coords = Coords::new(lines.values)?;
+            let range = multilines.range(row);
+            processor.multilinestring_begin(range.len(), idx)?;
+            for (idxl, line) in range.enumerate() {
+                process_linestring(&coords, lines.range(line), false, idxl, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        GeoArrowType::MultiPolygon => {
+            let multipolygons = List::new(array)?;
+            let polygons = List::new(multipolygons.values)?;
+            let rings = List::new(polygons.values)?;
+            let coords = Coords::new(rings.values)?;
+            let range = multipolygons.range(row);
+            processor.multipolygon_begin(range.len(), idx)?;
+            for (idxp, polygon) in range.enumerate() {
+                process_polygon(
+                    &rings,
+                    &coords,
+                    polygons.range(polygon),
+                    false,
+                    idxp,
+                    processor,
+                )?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        GeoArrowType::Wkb => {
+            let raw = &mut match array.data_type().to_logical_type() {
+                DataType::LargeBinary => downcast::<BinaryArray<i64>>(array)?.value(row),
+                _ => downcast::<BinaryArray<i32>>(array)?.value(row),
+            };
+            let info = read_wkb_header(raw)?;
+            process_wkb_geom_n(raw, &info, read_wkb_header, idx, processor)
+        }
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &Coords,
+    range: std::ops::Range<usize>,
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, range.len(), idx)?;
+    coords.process_range(range, processor)?;
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &List,
+    coords: &Coords,
+    range: std::ops::Range<usize>,
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, range.len(), idx)?;
+    for (idxr, ring) in range.enumerate() {
+        process_linestring(coords, rings.range(ring), false, idxr, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+/// Nested list level of a geometry array
+struct List<'a> {
+    offsets: &'a [i32],
+    values: &'a dyn Array,
+}
+
+impl<'a> List<'a> {
+    fn new(array: &'a dyn Array) -> Result<Self> {
+        let list = array
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .ok_or_else(|| GeozeroError::Geometry("GeoArrow list array expected".to_string()))?;
+        Ok(List {
+            offsets: list.offsets().as_slice(),
+            values: list.values().as_ref(),
+        })
+    }
+
+    fn range(&self, i: usize) -> std::ops::Range<usize> {
+        self.offsets[i] as usize..self.offsets[i + 1] as usize
+    }
+}
+
+/// Coordinate array, either interleaved (`FixedSizeList`) or separated (`Struct`)
+enum Coords<'a> {
+    Interleaved(&'a [f64], usize),
+    Separated(&'a [f64], &'a [f64], Option<&'a [f64]>),
+}
+
+impl<'a> Coords<'a> {
+    fn new(array: &'a dyn Array) -> Result<Self> {
+        let f64_values = |array: &'a dyn Array| -> Result<&'a [f64]> {
+            array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .map(|values| values.values().as_slice())
+                .ok_or_else(|| GeozeroError::Geometry("GeoArrow f64 values expected".to_string()))
+        };
+        if let Some(list) = array.as_any().downcast_ref::<FixedSizeListArray>() {
+            Ok(Coords::Interleaved(
+                f64_values(list.values().as_ref())?,
+                list.size(),
+            ))
+        } else if let Some(coords) = array.as_any().downcast_ref::<StructArray>() {
+            let values = coords.values();
+            if values.len() < 2 {
+                return Err(GeozeroError::Coord);
+            }
+            Ok(Coords::Separated(
+                f64_values(values[0].as_ref())?,
+                f64_values(values[1].as_ref())?,
+                values.get(2).map(|z| f64_values(z.as_ref())).transpose()?,
+            ))
+        } else {
+            Err(GeozeroError::Geometry(
+                "GeoArrow coordinate array expected".to_string(),
+            ))
+        }
+    }
+
+    /// Process coordinates at positions `range`, interleaved XY(Z) values at once
+    fn process_range<P: GeomProcessor>(
+        &self,
+        range: std::ops::Range<usize>,
+        processor: &mut P,
+    ) -> Result<()> {
+        match self {
+            Coords::Interleaved(values, size) if (2..=3).contains(size) => {
+                let coords = values
+                    .get(range.start * size..range.end * size)
+                    .ok_or(GeozeroError::Coord)?;
+                let dims = CoordDimensions {
+                    z: *size == 3,
+                    ..Default::default()
+                };
+                processor.coords(coords, dims, 0)
+            }
+            _ => {
+                for (idxc, pos) in range.enumerate() {
+                    self.process(pos, idxc, processor)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn process<P: GeomProcessor>(&self, pos: usize, idx: usize, processor: &mut P) -> Result<()> {
+        let (x, y, z) = match self {
+            Coords::Interleaved(values, size) => {
+                let coord = values
+                    .get(pos * size..(pos + 1) * size)
+                    .ok_or(GeozeroError::Coord)?;
+                (coord[0], coord[1], coord.get(2).copied())
+            }
+            Coords::Separated(x, y, z) => (x[pos], y[pos], z.map(|z| z[pos])),
+        };
+        if processor.multi_dim() {
+            processor.coordinate(x, y, z, None, None, None, idx)
+        } else {
+            processor.xy(x, y, idx)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -61,4 +526,230 @@ mod test {
         );
         Ok(())
     }
+
+    #[cfg(feature = "with-geojson")]
+    mod layout {
+        use super::*;
+        use crate::ProcessToJson;
+        use arrow2::buffer::Buffer;
+
+        fn coords(values: Vec<f64>) -> Box<dyn Array> {
+            Box::new(FixedSizeListArray::new(
+                FixedSizeListArray::default_datatype(DataType::Float64, 2),
+                Box::new(PrimitiveArray::from_vec(values)),
+                None,
+            ))
+        }
+
+        fn list(offsets: Vec<i32>, values: Box<dyn Array>) -> Box<dyn Array> {
+            let data_type = ListArray::<i32>::default_datatype(values.data_type().clone());
+            Box::new(ListArray::<i32>::new(
+                data_type,
+                Buffer::from(offsets),
+                values,
+                None,
+            ))
+        }
+
+        fn field(name: &str, extension: &str, array: &dyn Array) -> Field {
+            Field::new(
+                name,
+                DataType::Extension(
+                    extension.to_string(),
+                    Box::new(array.data_type().clone()),
+                    None,
+                ),
+                true,
+            )
+        }
+
+        #[test]
+        fn linestrings() -> Result<()> {
+            let names: Box<dyn Array> = Box::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+            let lines = list(
+                vec![0, 2, 5],
+                coords(vec![1., 2., 3., 4., 5., 6., 7., 8., 9., 10.]),
+            );
+            let schema = Schema::from(vec![
+                Field::new("name", DataType::Utf8, false),
+                field("geometry", "geoarrow.linestring", lines.as_ref()),
+            ]);
+            let chunks = vec![Chunk::new(vec![names, lines])];
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geometry")?;
+            assert_eq!(
+                reader.to_json()?,
+                r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,4]]}},
+{"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "LineString", "coordinates": [[5,6],[7,8],[9,10]]}}]}"#
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn geometry_columns() -> Result<()> {
+            use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+            #[derive(Default)]
+            struct GeometryColumns(Vec<String>);
+            impl GeomProcessor for GeometryColumns {}
+            impl PropertyProcessor for GeometryColumns {}
+            impl FeatureProcessor for GeometryColumns {
+                fn multiple_geometries(&self) -> bool {
+                    true
+                }
+                fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+                    self.0.push(format!("{}:{}", idx, name));
+                    Ok(())
+                }
+            }
+
+            let lines = list(vec![0, 2], coords(vec![1., 2., 3., 4.]));
+            let points = coords(vec![2., 3.]);
+            let schema = Schema::from(vec![
+                field("geometry", "geoarrow.linestring", lines.as_ref()),
+                field("centroid", "geoarrow.point", points.as_ref()),
+            ]);
+            let chunks = vec![Chunk::new(vec![lines, points])];
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geometry")?;
+            assert_eq!(
+                reader.to_json()?,
+                r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,4]]}}]}"#
+            );
+            let mut processor = GeometryColumns::default();
+            reader.process(&mut processor)?;
+            assert_eq!(processor.0, ["1:centroid"]);
+            Ok(())
+        }
+
+        #[test]
+        #[cfg(feature = "with-geoparquet")]
+        fn transform_geometry_columns() -> Result<()> {
+            use crate::arrow::GeoParquetWriter;
+            use crate::transform::TransformProcessor;
+            use arrow2::io::parquet::read as parquet;
+            use std::io::Cursor;
+
+            let lines = list(vec![0, 2], coords(vec![1., 2., 3., 4.]));
+            let points = coords(vec![2., 3.]);
+            let schema = Schema::from(vec![
+                field("geometry", "geoarrow.linestring", lines.as_ref()),
+                field("centroid", "geoarrow.point", points.as_ref()),
+            ]);
+            let chunks = vec![Chunk::new(vec![lines, points])];
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geometry")?;
+            let mut out: Vec<u8> = Vec::new();
+            let mut transformer = TransformProcessor::with_transform(
+                |x: f64, y: f64, z: Option<f64>| -> Result<_> { Ok((x + 10.0, y, z)) },
+                GeoParquetWriter::new(&mut out),
+            );
+            reader.process(&mut transformer)?;
+
+            let mut reader = Cursor::new(out);
+            let metadata = parquet::read_metadata(&mut reader)?;
+            let schema = parquet::infer_schema(&metadata)?;
+            let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, vec!["geometry", "centroid"]);
+            let mut chunks =
+                parquet::FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+            let chunk = chunks.next().unwrap()?;
+            let wkt = |i: usize| {
+                chunk.arrays()[i]
+                    .as_any()
+                    .downcast_ref::<BinaryArray<i32>>()
+                    .unwrap()
+                    .to_wkt()
+            };
+            assert_eq!(wkt(0)?, "GEOMETRYCOLLECTION(LINESTRING(11 2,13 4))");
+            assert_eq!(wkt(1)?, "GEOMETRYCOLLECTION(POINT(12 3))");
+            Ok(())
+        }
+
+        #[test]
+        fn multipolygons() -> Result<()> {
+            let ring = vec![0., 0., 1., 0., 1., 1., 0., 0.];
+            let mut values = ring.clone();
+            values.extend(ring.iter().map(|v| v + 10.));
+            let rings = list(vec![0, 4, 8], coords(values));
+            let polygons = list(vec![0, 1, 2], rings);
+            let multipolygons = list(vec![0, 2], polygons);
+            let schema = Schema::from(vec![field(
+                "geom",
+                "geoarrow.multipolygon",
+                multipolygons.as_ref(),
+            )]);
+            let chunks = vec![Chunk::new(vec![multipolygons])];
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geom")?;
+            assert_eq!(
+                reader.to_json()?,
+                r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[0,0],[1,0],[1,1],[0,0]]],[[[10,10],[11,10],[11,11],[10,10]]]]}}]}"#
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn feature_range() -> Result<()> {
+            let chunk = |first: f64| {
+                let names: Box<dyn Array> = Box::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+                let values = (0..8).map(|v| first + v as f64).collect();
+                let lines = list(vec![0, 2, 4], coords(values));
+                Chunk::new(vec![names, lines])
+            };
+            let chunks = vec![chunk(0.), chunk(10.)];
+            let lines = chunks[0].arrays()[1].as_ref();
+            let schema = Schema::from(vec![
+                Field::new("name", DataType::Utf8, false),
+                field("geometry", "geoarrow.linestring", lines),
+            ]);
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geometry")?;
+            assert_eq!(
+                reader.skip(1).take(2).to_json()?,
+                r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "LineString", "coordinates": [[4,5],[6,7]]}},
+{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "LineString", "coordinates": [[10,11],[12,13]]}}]}"#
+            );
+            assert_eq!(
+                reader
+                    .skip(3)
+                    .take(2)
+                    .to_json()?
+                    .matches("Feature\"")
+                    .count(),
+                1
+            );
+            assert_eq!(reader.skip(5).to_json()?.matches("Feature\"").count(), 0);
+            Ok(())
+        }
+
+        #[test]
+        #[cfg(feature = "with-rayon")]
+        fn par_process() -> Result<()> {
+            use crate::geojson::GeoJsonWriter;
+            use crate::ParallelDatasource;
+
+            let chunk = |first: f64| {
+                let names: Box<dyn Array> = Box::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+                let values = (0..8).map(|v| first + v as f64).collect();
+                let lines = list(vec![0, 2, 4], coords(values));
+                Chunk::new(vec![names, lines])
+            };
+            let chunks = vec![chunk(0.), chunk(10.)];
+            let lines = chunks[0].arrays()[1].as_ref();
+            let schema = Schema::from(vec![
+                Field::new("name", DataType::Utf8, false),
+                field("geometry", "geoarrow.linestring", lines),
+            ]);
+            let mut reader = GeoArrowReader::new(&schema, &chunks, "geometry")?;
+            assert_eq!(reader.feature_count(), 4);
+            let mut out: Vec<u8> = Vec::new();
+            reader.par_process(&mut GeoJsonWriter::new(&mut out))?;
+            assert_eq!(std::str::from_utf8(&out).unwrap(), reader.to_json()?);
+            Ok(())
+        }
+    }
 }
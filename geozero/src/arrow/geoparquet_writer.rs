@@ -0,0 +1,577 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{WkbDialect, WkbWriter};
+use crate::wkt::buffering_wkt_writer::BufferingWktWriter;
+use crate::wkt::WktStr;
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroGeometry,
+    PropertyProcessor,
+};
+use arrow2::array::{
+    Array, MutableArray, MutableBinaryArray, MutableBooleanArray, MutablePrimitiveArray,
+    MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{Field, Schema};
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, KeyValue, RowGroupIterator, Version,
+    WriteOptions,
+};
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// GeoParquet writer.
+///
+/// Features are buffered into Arrow arrays and written as a single row group
+/// at the end of the dataset. Properties are stored in typed columns, the geometry
+/// as WKB binary column with GeoParquet metadata. Additional geometries are written
+/// into further geometry columns named after the source column.
+pub struct GeoParquetWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// Name of geometry column
+    pub geometry_column: String,
+    pub compression: CompressionOptions,
+    out: &'a mut W,
+    columns: Vec<(String, ColumnBuilder)>,
+    primary: GeometryColumnBuilder,
+    additional_geometries: Vec<(String, GeometryColumnBuilder)>,
+    /// Index of the additional geometry column being processed
+    current_column: Option<usize>,
+    /// WKB of the primary geometry, if converted before additional geometries
+    primary_wkb: Option<Option<Vec<u8>>>,
+    wkt_writer: BufferingWktWriter,
+    num_rows: usize,
+}
+
+/// WKB geometry column with GeoParquet column metadata
+#[derive(Default)]
+struct GeometryColumnBuilder {
+    geometries: MutableBinaryArray<i32>,
+    geometry_types: BTreeSet<&'static str>,
+    bbox: Option<[f64; 4]>,
+}
+
+impl<'a, W: Write> GeoParquetWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> GeoParquetWriter<'a, W> {
+        GeoParquetWriter {
+            dims: CoordDimensions::default(),
+            geometry_column: "geometry".to_string(),
+            compression: CompressionOptions::Uncompressed,
+            out,
+            columns: Vec::new(),
+            primary: GeometryColumnBuilder::default(),
+            additional_geometries: Vec::new(),
+            current_column: None,
+            primary_wkb: None,
+            wkt_writer: BufferingWktWriter::new(),
+            num_rows: 0,
+        }
+    }
+
+    fn has_started_writing_geometry(&self) -> bool {
+        !self.wkt_writer.bytes().is_empty()
+    }
+
+    fn offset_geom_idx(&self, input_idx: usize) -> usize {
+        if self.has_started_writing_geometry() {
+            input_idx
+        } else {
+            // avoid prefixing subsequent feature geometry with a comma
+            0
+        }
+    }
+
+    /// Geometry column of the geometry being processed
+    fn geometry_column(&mut self) -> &mut GeometryColumnBuilder {
+        match self.current_column {
+            Some(i) => &mut self.additional_geometries[i].1,
+            None => &mut self.primary,
+        }
+    }
+
+    fn geometry_type(&mut self, geometry_type: &'static str) {
+        if !self.has_started_writing_geometry() {
+            self.geometry_column().geometry_types.insert(geometry_type);
+        }
+    }
+
+    fn extend_bbox(&mut self, x: f64, y: f64) {
+        let bbox = self.geometry_column().bbox.get_or_insert([x, y, x, y]);
+        bbox[0] = bbox[0].min(x);
+        bbox[1] = bbox[1].min(y);
+        bbox[2] = bbox[2].max(x);
+        bbox[3] = bbox[3].max(y);
+    }
+
+    /// Convert buffered geometry to WKB
+    fn wkb(&self) -> Result<Option<Vec<u8>>> {
+        let wkt = self.wkt_writer.bytes();
+        if wkt.is_empty() {
+            return Ok(None);
+        }
+        let wkt = std::str::from_utf8(wkt)
+            .map_err(|_| GeozeroError::Geometry("Invalid UTF-8 encoding".to_string()))?;
+        let mut wkb: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb, WkbDialect::Wkb);
+        writer.dims = self.dims;
+        WktStr(wkt).process_geom(&mut writer)?;
+        Ok(Some(wkb))
+    }
+
+    /// GeoParquet column metadata
+    fn column_metadata(&self, column: &GeometryColumnBuilder) -> serde_json::Value {
+        let suffix = if self.dims.z { " Z" } else { "" };
+        let geometry_types: Vec<String> = column
+            .geometry_types
+            .iter()
+            .map(|t| format!("{}{}", t, suffix))
+            .collect();
+        let mut metadata = serde_json::json!({
+            "encoding": "WKB",
+            "geometry_types": geometry_types,
+        });
+        if let Some(bbox) = column.bbox {
+            metadata["bbox"] = serde_json::json!(bbox);
+        }
+        metadata
+    }
+
+    /// GeoParquet file metadata
+    fn geo_metadata(&self) -> String {
+        let mut columns = serde_json::Map::new();
+        columns.insert(
+            self.geometry_column.clone(),
+            self.column_metadata(&self.primary),
+        );
+        for (name, column) in &self.additional_geometries {
+            columns.insert(name.clone(), self.column_metadata(column));
+        }
+        serde_json::json!({
+            "version": "1.0.0-beta.1",
+            "primary_column": self.geometry_column,
+            "columns": columns,
+        })
+        .to_string()
+    }
+}
+
+impl<W: Write> FeatureProcessor for GeoParquetWriter<'_, W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        let mut fields = Vec::with_capacity(self.columns.len() + 1);
+        let mut arrays = Vec::with_capacity(self.columns.len() + 1);
+        for (name, column) in &mut self.columns {
+            let array = column.as_mut_array().as_box();
+            fields.push(Field::new(name.as_str(), array.data_type().clone(), true));
+            arrays.push(array);
+        }
+        let geometries = self.primary.geometries.as_box();
+        fields.push(Field::new(
+            self.geometry_column.as_str(),
+            geometries.data_type().clone(),
+            true,
+        ));
+        arrays.push(geometries);
+        for (name, column) in &mut self.additional_geometries {
+            let geometries = column.geometries.as_box();
+            fields.push(Field::new(
+                name.as_str(),
+                geometries.data_type().clone(),
+                true,
+            ));
+            arrays.push(geometries);
+        }
+        let schema = Schema::from(fields);
+
+        let options = WriteOptions {
+            write_statistics: true,
+            compression: self.compression,
+            version: Version::V2,
+        };
+        let encodings = schema
+            .fields
+            .iter()
+            .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+            .collect();
+        let chunks = vec![Ok(Chunk::new(arrays))];
+        let row_groups =
+            RowGroupIterator::try_new(chunks.into_iter(), &schema, options, encodings)?;
+        let metadata = self.geo_metadata();
+        let mut writer = FileWriter::try_new(&mut self.out, schema, options)?;
+        for group in row_groups {
+            writer.write(group?)?;
+        }
+        writer.end(Some(vec![KeyValue {
+            key: "geo".to_string(),
+            value: Some(metadata),
+        }]))?;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let wkb = match self.primary_wkb.take() {
+            Some(wkb) => wkb,
+            None => self.wkb()?,
+        };
+        self.primary.geometries.push(wkb);
+        self.wkt_writer.clear();
+        self.num_rows += 1;
+        // fill missing properties
+        for (_, column) in &mut self.columns {
+            let array = column.as_mut_array();
+            if array.len() < self.num_rows {
+                array.push_null();
+            }
+        }
+        for (_, column) in &mut self.additional_geometries {
+            if column.geometries.len() < self.num_rows {
+                column.geometries.push_null();
+            }
+        }
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        true
+    }
+    fn geometry_column_begin(&mut self, _idx: usize, name: &str) -> Result<()> {
+        if self.primary_wkb.is_none() {
+            self.primary_wkb = Some(self.wkb()?);
+            self.wkt_writer.clear();
+        }
+        let i = match self
+            .additional_geometries
+            .iter()
+            .position(|(col, _)| col == name)
+        {
+            Some(i) => i,
+            None => {
+                let mut column = GeometryColumnBuilder::default();
+                for _ in 0..self.num_rows {
+                    column.geometries.push_null();
+                }
+                self.additional_geometries.push((name.to_string(), column));
+                self.additional_geometries.len() - 1
+            }
+        };
+        self.current_column = Some(i);
+        Ok(())
+    }
+    fn geometry_column_end(&mut self, _idx: usize, _name: &str) -> Result<()> {
+        let wkb = self.wkb()?;
+        self.wkt_writer.clear();
+        self.geometry_column().geometries.push(wkb);
+        self.current_column = None;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for GeoParquetWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let column = match self.columns.iter().position(|(col, _)| col == name) {
+            Some(i) => &mut self.columns[i].1,
+            None => {
+                let mut column = ColumnBuilder::new(value);
+                for _ in 0..self.num_rows {
+                    column.as_mut_array().push_null();
+                }
+                self.columns.push((name.to_string(), column));
+                &mut self.columns.last_mut().unwrap().1
+            }
+        };
+        column.push(name, value)?;
+        Ok(false)
+    }
+}
+
+/// Arrow array builder of a property column
+enum ColumnBuilder {
+    Boolean(MutableBooleanArray),
+    Int8(MutablePrimitiveArray<i8>),
+    UInt8(MutablePrimitiveArray<u8>),
+    Int16(MutablePrimitiveArray<i16>),
+    UInt16(MutablePrimitiveArray<u16>),
+    Int32(MutablePrimitiveArray<i32>),
+    UInt32(MutablePrimitiveArray<u32>),
+    Int64(MutablePrimitiveArray<i64>),
+    UInt64(MutablePrimitiveArray<u64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Utf8(MutableUtf8Array<i32>),
+    Binary(MutableBinaryArray<i32>),
+}
+
+impl ColumnBuilder {
+    fn new(value: &ColumnValue) -> Self {
+        match value {
+            ColumnValue::Bool(_) => ColumnBuilder::Boolean(MutableBooleanArray::new()),
+            ColumnValue::Byte(_) => ColumnBuilder::Int8(MutablePrimitiveArray::new()),
+            ColumnValue::UByte(_) => ColumnBuilder::UInt8(MutablePrimitiveArray::new()),
+            ColumnValue::Short(_) => ColumnBuilder::Int16(MutablePrimitiveArray::new()),
+            ColumnValue::UShort(_) => ColumnBuilder::UInt16(MutablePrimitiveArray::new()),
+            ColumnValue::Int(_) => ColumnBuilder::Int32(MutablePrimitiveArray::new()),
+            ColumnValue::UInt(_) => ColumnBuilder::UInt32(MutablePrimitiveArray::new()),
+            ColumnValue::Long(_) => ColumnBuilder::Int64(MutablePrimitiveArray::new()),
+            ColumnValue::ULong(_) => ColumnBuilder::UInt64(MutablePrimitiveArray::new()),
+            ColumnValue::Float(_) => ColumnBuilder::Float32(MutablePrimitiveArray::new()),
+            ColumnValue::Double(_) => ColumnBuilder::Float64(MutablePrimitiveArray::new()),
+            ColumnValue::String(_) | ColumnValue::Json(_) | ColumnValue::DateTime(_) => {
+                ColumnBuilder::Utf8(MutableUtf8Array::new())
+            }
+            ColumnValue::Binary(_) => ColumnBuilder::Binary(MutableBinaryArray::new()),
+        }
+    }
+
+    fn as_mut_array(&mut self) -> &mut dyn MutableArray {
+        match self {
+            ColumnBuilder::Boolean(a) => a,
+            ColumnBuilder::Int8(a) => a,
+            ColumnBuilder::UInt8(a) => a,
+            ColumnBuilder::Int16(a) => a,
+            ColumnBuilder::UInt16(a) => a,
+            ColumnBuilder::Int32(a) => a,
+            ColumnBuilder::UInt32(a) => a,
+            ColumnBuilder::Int64(a) => a,
+            ColumnBuilder::UInt64(a) => a,
+            ColumnBuilder::Float32(a) => a,
+            ColumnBuilder::Float64(a) => a,
+            ColumnBuilder::Utf8(a) => a,
+            ColumnBuilder::Binary(a) => a,
+        }
+    }
+
+    fn push(&mut self, name: &str, value: &ColumnValue) -> Result<()> {
+        match (self, value) {
+            (ColumnBuilder::Boolean(a), ColumnValue::Bool(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Int8(a), ColumnValue::Byte(v)) => a.push(Some(*v)),
+            (ColumnBuilder::UInt8(a), ColumnValue::UByte(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Int16(a), ColumnValue::Short(v)) => a.push(Some(*v)),
+            (ColumnBuilder::UInt16(a), ColumnValue::UShort(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Int32(a), ColumnValue::Int(v)) => a.push(Some(*v)),
+            (ColumnBuilder::UInt32(a), ColumnValue::UInt(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Int64(a), ColumnValue::Long(v)) => a.push(Some(*v)),
+            (ColumnBuilder::UInt64(a), ColumnValue::ULong(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Float32(a), ColumnValue::Float(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Float64(a), ColumnValue::Double(v)) => a.push(Some(*v)),
+            // integers in floating point columns, e.g. from GeoJSON numbers
+            (ColumnBuilder::Float64(a), ColumnValue::Long(v)) => a.push(Some(*v as f64)),
+            (ColumnBuilder::Float64(a), ColumnValue::ULong(v)) => a.push(Some(*v as f64)),
+            (ColumnBuilder::Utf8(a), ColumnValue::String(v))
+            | (ColumnBuilder::Utf8(a), ColumnValue::Json(v))
+            | (ColumnBuilder::Utf8(a), ColumnValue::DateTime(v)) => a.push(Some(*v)),
+            (ColumnBuilder::Binary(a), ColumnValue::Binary(v)) => a.push(Some(*v)),
+            _ => {
+                return Err(GeozeroError::Property(format!(
+                    "column `{}` has mixed value types",
+                    name
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> GeomProcessor for GeoParquetWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.extend_bbox(x, y);
+        self.wkt_writer.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.extend_bbox(x, y);
+        self.wkt_writer.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geometry_type("Point");
+        self.wkt_writer.empty_point(self.offset_geom_idx(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geometry_type("Point");
+        self.wkt_writer.point_begin(self.offset_geom_idx(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.wkt_writer.point_end(self.offset_geom_idx(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("MultiPoint");
+        self.wkt_writer
+            .multipoint_begin(size, self.offset_geom_idx(idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.wkt_writer.multipoint_end(self.offset_geom_idx(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("LineString");
+        self.wkt_writer
+            .linestring_begin(tagged, size, self.offset_geom_idx(idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkt_writer
+            .linestring_end(tagged, self.offset_geom_idx(idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("MultiLineString");
+        self.wkt_writer
+            .multilinestring_begin(size, self.offset_geom_idx(idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkt_writer
+            .multilinestring_end(self.offset_geom_idx(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("Polygon");
+        self.wkt_writer
+            .polygon_begin(tagged, size, self.offset_geom_idx(idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkt_writer
+            .polygon_end(tagged, self.offset_geom_idx(idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("MultiPolygon");
+        self.wkt_writer
+            .multipolygon_begin(size, self.offset_geom_idx(idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkt_writer.multipolygon_end(self.offset_geom_idx(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry_type("GeometryCollection");
+        self.wkt_writer
+            .geometrycollection_begin(size, self.offset_geom_idx(idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.wkt_writer
+            .geometrycollection_end(self.offset_geom_idx(idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::wkt::conversion::ToWkt;
+    use crate::GeozeroDatasource;
+    use arrow2::array::{BinaryArray, PrimitiveArray, Utf8Array};
+    use arrow2::io::parquet::read;
+    use std::io::Cursor;
+
+    #[test]
+    fn geojson_to_geoparquet() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "a", "count": 3}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "LineString", "coordinates": [[3, 4], [-5, 6]]}}
+            ]}"#,
+        );
+        let mut out: Vec<u8> = Vec::new();
+        geojson.process(&mut GeoParquetWriter::new(&mut out))?;
+
+        let mut reader = Cursor::new(out);
+        let metadata = read::read_metadata(&mut reader)?;
+        let geo = metadata
+            .key_value_metadata()
+            .as_ref()
+            .and_then(|kv| kv.iter().find(|kv| kv.key == "geo"))
+            .and_then(|kv| kv.value.clone())
+            .unwrap();
+        assert_eq!(
+            geo,
+            r#"{"columns":{"geometry":{"bbox":[-5.0,2.0,3.0,6.0],"encoding":"WKB","geometry_types":["LineString","Point"]}},"primary_column":"geometry","version":"1.0.0-beta.1"}"#
+        );
+
+        let schema = read::infer_schema(&metadata)?;
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["count", "name", "geometry"]);
+        let mut chunks =
+            read::FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+        let chunk = chunks.next().unwrap()?;
+        let arrays = chunk.arrays();
+
+        let count = arrays[0]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .unwrap();
+        assert_eq!(count.iter().collect::<Vec<_>>(), vec![Some(&3), None]);
+        let name = arrays[1].as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(name.iter().collect::<Vec<_>>(), vec![Some("a"), Some("b")]);
+        let geometry = arrays[2]
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert_eq!(
+            geometry.to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(3 4,-5 6))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn geometry_columns() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoParquetWriter::new(&mut out);
+        writer.dataset_begin(None)?;
+        for (idx, (geometry, centroid)) in [
+            ("POINT(1 2)", Some("POINT(3 4)")),
+            ("LINESTRING(0 0,1 1)", None),
+        ]
+        .iter()
+        .enumerate()
+        {
+            writer.feature_begin(idx as u64)?;
+            writer.geometry_begin()?;
+            WktStr(*geometry).process_geom(&mut writer)?;
+            writer.geometry_end()?;
+            if let Some(centroid) = centroid {
+                writer.geometry_column_begin(1, "centroid")?;
+                WktStr(*centroid).process_geom(&mut writer)?;
+                writer.geometry_column_end(1, "centroid")?;
+            }
+            writer.feature_end(idx as u64)?;
+        }
+        writer.dataset_end()?;
+
+        let mut reader = Cursor::new(out);
+        let metadata = read::read_metadata(&mut reader)?;
+        let geo = metadata
+            .key_value_metadata()
+            .as_ref()
+            .and_then(|kv| kv.iter().find(|kv| kv.key == "geo"))
+            .and_then(|kv| kv.value.clone())
+            .unwrap();
+        assert_eq!(
+            geo,
+            r#"{"columns":{"centroid":{"bbox":[3.0,4.0,3.0,4.0],"encoding":"WKB","geometry_types":["Point"]},"geometry":{"bbox":[0.0,0.0,1.0,2.0],"encoding":"WKB","geometry_types":["LineString","Point"]}},"primary_column":"geometry","version":"1.0.0-beta.1"}"#
+        );
+        let schema = read::infer_schema(&metadata)?;
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["geometry", "centroid"]);
+        let mut chunks =
+            read::FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+        let chunk = chunks.next().unwrap()?;
+        let geometry = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert_eq!(
+            geometry.to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 1))"
+        );
+        let centroid = chunk.arrays()[1]
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert_eq!(centroid.len(), 2);
+        assert!(centroid.is_valid(0));
+        assert!(centroid.is_null(1));
+        Ok(())
+    }
+}
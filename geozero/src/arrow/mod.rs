@@ -1,5 +1,15 @@
 //! GeoArrow conversions.
 //!
 pub(crate) mod geoarrow_reader;
+#[cfg(feature = "with-geoparquet")]
+mod geoparquet_writer;
 
 pub use geoarrow_reader::*;
+#[cfg(feature = "with-geoparquet")]
+pub use geoparquet_writer::*;
+
+impl From<arrow2::error::Error> for crate::error::GeozeroError {
+    fn from(error: arrow2::error::Error) -> Self {
+        crate::error::GeozeroError::Dataset(error.to_string())
+    }
+}
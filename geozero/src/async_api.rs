@@ -0,0 +1,348 @@
+//! Asynchronous feature processing.
+//!
+//! Synchronous readers are executed on a blocking thread and hand over complete features
+//! to an [AsyncFeatureProcessor], so that readers and processors waiting for I/O don't block
+//! the executor. Features are streamed through a bounded channel, the payload is never
+//! buffered as a whole.
+
+use crate::error::{GeozeroError, Result};
+use crate::processing::record::FeatureRecorder;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    GeozeroDatasource, PropertyProcessor,
+};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+/// Number of features read ahead of the processor.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Asynchronous feature processing trait.
+///
+/// Features are passed as a whole. Their properties and geometry can be processed
+/// with any synchronous processor like a datasource.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait AsyncFeatureProcessor: Send {
+    /// Dimensions recorded for feature geometries
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xy()
+    }
+    /// Begin of dataset processing
+    async fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+    /// SRID of dataset geometries
+    async fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        Ok(())
+    }
+    /// Descriptive metadata of dataset
+    async fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        Ok(())
+    }
+    /// End of dataset processing
+    async fn dataset_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    /// Begin of a batch of at most `size` features
+    async fn batch_begin(&mut self, size: usize) -> Result<()> {
+        Ok(())
+    }
+    /// End of a batch of features
+    async fn batch_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    /// Process feature
+    async fn feature(&mut self, idx: u64, feature: RecordedFeature) -> Result<()>;
+}
+
+/// Feature read by a synchronous reader.
+///
+/// Processing emits the recorded feature events including `feature_begin` and `feature_end`.
+#[derive(Default)]
+pub struct RecordedFeature(FeatureRecorder);
+
+impl GeozeroDatasource for RecordedFeature {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        self.0.replay(processor)
+    }
+}
+
+enum Message {
+    DatasetBegin(Option<String>),
+    DatasetSrid(Option<i32>),
+    DatasetMetadata(Box<DatasetMetadata>),
+    BatchBegin(usize),
+    BatchEnd,
+    DatasetEnd,
+    Feature(u64, RecordedFeature),
+}
+
+/// Synchronous processor sending features to an [AsyncFeatureProcessor].
+///
+/// Geometries outside of features are ignored.
+pub struct FeatureSender {
+    sender: mpsc::Sender<Message>,
+    dims: CoordDimensions,
+    feature: Option<RecordedFeature>,
+}
+
+impl FeatureSender {
+    fn send(&self, message: Message) -> Result<()> {
+        self.sender
+            .blocking_send(message)
+            .map_err(|_| GeozeroError::Dataset("async processor stopped".to_string()))
+    }
+}
+
+/// Forward events of current feature to recorder.
+macro_rules! record_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                match &mut self.feature {
+                    Some(feature) => feature.0.$name($($arg),*),
+                    None => Ok(()),
+                }
+            }
+        )*
+    };
+}
+
+impl GeomProcessor for FeatureSender {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    record_events!(
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        coordinate(x: f64, y: f64, z: Option<f64>, m: Option<f64>, t: Option<f64>, tm: Option<u64>, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl PropertyProcessor for FeatureSender {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match &mut self.feature {
+            Some(feature) => feature.0.property(idx, name, value),
+            None => Ok(false),
+        }
+    }
+}
+
+impl FeatureProcessor for FeatureSender {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.send(Message::DatasetBegin(name.map(String::from)))
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.send(Message::DatasetSrid(srid))
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.send(Message::DatasetMetadata(Box::new(metadata.clone())))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.send(Message::DatasetEnd)
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.send(Message::BatchBegin(size))
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.send(Message::BatchEnd)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        let mut feature = RecordedFeature::default();
+        feature.0.feature_begin(idx)?;
+        self.feature = Some(feature);
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if let Some(mut feature) = self.feature.take() {
+            feature.0.feature_end(idx)?;
+            self.send(Message::Feature(idx, feature))?;
+        }
+        Ok(())
+    }
+    record_events!(
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+/// Run synchronous reader on a blocking thread and process features asynchronously.
+///
+/// Errors of the processor take precedence over reader errors.
+pub async fn process_async<F, P>(read: F, processor: &mut P) -> Result<()>
+where
+    F: FnOnce(&mut FeatureSender) -> Result<()> + Send + 'static,
+    P: AsyncFeatureProcessor,
+{
+    let (sender, mut receiver) = mpsc::channel(CHANNEL_CAPACITY);
+    let mut feature_sender = FeatureSender {
+        sender,
+        dims: processor.dimensions(),
+        feature: None,
+    };
+    let reader = tokio::task::spawn_blocking(move || read(&mut feature_sender));
+    let mut result = Ok(());
+    while let Some(message) = receiver.recv().await {
+        result = match message {
+            Message::DatasetBegin(name) => processor.dataset_begin(name.as_deref()).await,
+            Message::DatasetSrid(srid) => processor.dataset_srid(srid).await,
+            Message::DatasetMetadata(metadata) => processor.dataset_metadata(&metadata).await,
+            Message::DatasetEnd => processor.dataset_end().await,
+            Message::BatchBegin(size) => processor.batch_begin(size).await,
+            Message::BatchEnd => processor.batch_end().await,
+            Message::Feature(idx, feature) => processor.feature(idx, feature).await,
+        };
+        if result.is_err() {
+            break;
+        }
+    }
+    // Stop reader when processing failed
+    drop(receiver);
+    let read_result = reader
+        .await
+        .map_err(|e| GeozeroError::Dataset(e.to_string()))?;
+    result.and(read_result)
+}
+
+/// Process datasource on a blocking thread with an asynchronous processor.
+pub async fn process_datasource_async<D, P>(mut datasource: D, processor: &mut P) -> Result<()>
+where
+    D: GeozeroDatasource + Send + 'static,
+    P: AsyncFeatureProcessor,
+{
+    process_async(move |sender| datasource.process(sender), processor).await
+}
+
+/// Read GeoJSON feature collection from asynchronous reader.
+#[cfg(feature = "with-geojson")]
+pub async fn read_geojson_fc_async<R, P>(reader: R, processor: &mut P) -> Result<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    P: AsyncFeatureProcessor,
+{
+    process_async(
+        move |sender| {
+            crate::geojson::read_geojson_fc(tokio_util::io::SyncIoBridge::new(reader), sender)
+        },
+        processor,
+    )
+    .await
+}
+
+/// Read GeoJSON from asynchronous reader.
+///
+/// The input is parsed as a whole, use [read_geojson_fc_async] for streaming feature collections.
+#[cfg(feature = "with-geojson")]
+pub async fn read_geojson_async<R, P>(reader: R, processor: &mut P) -> Result<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    P: AsyncFeatureProcessor,
+{
+    process_async(
+        move |sender| {
+            crate::geojson::read_geojson(tokio_util::io::SyncIoBridge::new(reader), sender)
+        },
+        processor,
+    )
+    .await
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use std::io::Cursor;
+
+    #[derive(Default)]
+    struct WktCollector {
+        datasets: usize,
+        features: Vec<(u64, String)>,
+    }
+
+    #[async_trait]
+    impl AsyncFeatureProcessor for WktCollector {
+        async fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+            self.datasets += 1;
+            Ok(())
+        }
+        async fn feature(&mut self, idx: u64, mut feature: RecordedFeature) -> Result<()> {
+            let mut wkt_data: Vec<u8> = Vec::new();
+            feature.process_geom(&mut WktWriter::new(&mut wkt_data))?;
+            self.features
+                .push((idx, String::from_utf8(wkt_data).unwrap()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn geojson() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}, {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,4]]}}]}"#;
+        let mut collector = WktCollector::default();
+        read_geojson_async(Cursor::new(geojson.as_bytes().to_vec()), &mut collector).await?;
+        assert_eq!(collector.datasets, 1);
+        assert_eq!(
+            collector.features,
+            vec![
+                (0, "POINT(1 2)".to_string()),
+                // GeoJSON reader passes feature index as geometry index
+                (1, ",LINESTRING(1 2,3 4)".to_string())
+            ]
+        );
+        Ok(())
+    }
+
+    struct Failing;
+
+    #[async_trait]
+    impl AsyncFeatureProcessor for Failing {
+        async fn feature(&mut self, idx: u64, _feature: RecordedFeature) -> Result<()> {
+            Err(GeozeroError::Feature(format!("failing at {}", idx)))
+        }
+    }
+
+    #[tokio::test]
+    async fn processor_error() {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#;
+        let err = read_geojson_async(Cursor::new(geojson.as_bytes().to_vec()), &mut Failing)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "processing feature: `failing at 0`");
+    }
+}
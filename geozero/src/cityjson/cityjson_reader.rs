@@ -0,0 +1,444 @@
+use crate::error::{GeozeroError, Result};
+use crate::property_processor::process_json_property;
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    PropertyProcessor,
+};
+use serde_json::map::Map;
+use serde_json::value::Value as JsonValue;
+use std::io::Read;
+
+/// CityJSON String slice.
+pub struct CityJson<'a>(pub &'a str);
+
+impl GeozeroGeometry for CityJson<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_cityjson_geom(self.0.as_bytes(), processor)
+    }
+}
+
+impl GeozeroDatasource for CityJson<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_cityjson(self.0.as_bytes(), processor)
+    }
+}
+
+/// CityJSON Reader.
+pub struct CityJsonReader<'a, R: Read>(pub &'a mut R);
+
+impl<'a, R: Read> GeozeroDatasource for CityJsonReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_cityjson(&mut self.0, processor)
+    }
+}
+
+/// Read and process CityJSON.
+///
+/// Each city object is processed as feature with its `id` as foreign member.
+/// The city object type is passed as `type` property, followed by the object attributes.
+///
+/// Geometries are converted as follows:
+/// * MultiPoint and MultiLineString as the corresponding simple feature types
+/// * MultiSurface and CompositeSurface as MultiPolygon
+/// * Solid as PolyhedralSurface with the surfaces of all shells
+/// * MultiSolid and CompositeSolid as GeometryCollection of PolyhedralSurfaces
+///
+/// City objects with several geometries (e.g. multiple LoDs) have a GeometryCollection
+/// geometry. Geometry templates and semantic surfaces are not supported.
+pub fn read_cityjson<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let model = CityModel::from_reader(reader)?;
+    processor.dataset_begin(None)?;
+    if model.srid.is_some() {
+        processor.dataset_srid(model.srid)?;
+    }
+    for (idx, (id, object)) in model.objects.iter().enumerate() {
+        let idx = idx as u64;
+        processor.feature_begin(idx)?;
+        processor.foreign_member("id", &JsonValue::from(id.as_str()).to_string())?;
+        processor.properties_begin()?;
+        process_properties(object, processor).map_err(|e| e.in_feature(idx))?;
+        processor.properties_end()?;
+        let geometries = object_geometries(object);
+        if !geometries.is_empty() {
+            processor.geometry_begin()?;
+            model
+                .process_geometries(&geometries, idx as usize, processor)
+                .map_err(|e| e.in_feature(idx))?;
+            processor.geometry_end()?;
+        }
+        processor.feature_end(idx)?;
+    }
+    processor.dataset_end()
+}
+
+/// Read and process CityJSON geometries as geometry collection.
+pub fn read_cityjson_geom<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let model = CityModel::from_reader(reader)?;
+    let geometries: Vec<&JsonValue> = model.objects.values().flat_map(object_geometries).collect();
+    processor.geometrycollection_begin(geometries.len(), 0)?;
+    for (idx, geometry) in geometries.into_iter().enumerate() {
+        model.process_geometry(geometry, idx, processor)?;
+    }
+    processor.geometrycollection_end(0)
+}
+
+fn object_geometries(object: &JsonValue) -> Vec<&JsonValue> {
+    match object.get("geometry") {
+        Some(JsonValue::Array(geometries)) => geometries.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn json_error(e: serde_json::Error) -> GeozeroError {
+    GeozeroError::Dataset(format!("Invalid CityJSON: {}", e))
+}
+
+fn invalid(msg: &str) -> GeozeroError {
+    GeozeroError::Geometry(format!("Invalid CityJSON: {}", msg))
+}
+
+fn array<'a>(value: &'a JsonValue, msg: &str) -> Result<&'a Vec<JsonValue>> {
+    value.as_array().ok_or_else(|| invalid(msg))
+}
+
+/// City model with transformed vertices
+struct CityModel {
+    vertices: Vec<[f64; 3]>,
+    objects: Map<String, JsonValue>,
+    srid: Option<i32>,
+}
+
+impl CityModel {
+    fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let json: JsonValue = serde_json::from_reader(reader).map_err(json_error)?;
+        let mut json = match json {
+            JsonValue::Object(json) => json,
+            _ => return Err(invalid("expected CityJSON object")),
+        };
+        if json.get("type").and_then(|t| t.as_str()) != Some("CityJSON") {
+            return Err(invalid("expected CityJSON object"));
+        }
+        let (scale, translate) = match json.get("transform") {
+            Some(transform) => (
+                parse_triple(transform.get("scale"))?,
+                parse_triple(transform.get("translate"))?,
+            ),
+            None => ([1.0; 3], [0.0; 3]),
+        };
+        let vertices = match json.get("vertices") {
+            Some(JsonValue::Array(vertices)) => vertices
+                .iter()
+                .map(|v| {
+                    let v = parse_triple(Some(v))?;
+                    Ok([
+                        v[0] * scale[0] + translate[0],
+                        v[1] * scale[1] + translate[1],
+                        v[2] * scale[2] + translate[2],
+                    ])
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => return Err(invalid("missing vertices")),
+        };
+        let srid = json
+            .get("metadata")
+            .and_then(|metadata| metadata.get("referenceSystem"))
+            .and_then(|crs| crs.as_str())
+            .and_then(crate::epsg_code);
+        let objects = match json.remove("CityObjects") {
+            Some(JsonValue::Object(objects)) => objects,
+            _ => return Err(invalid("missing CityObjects")),
+        };
+        Ok(CityModel {
+            vertices,
+            objects,
+            srid,
+        })
+    }
+
+    fn process_geometries<P: GeomProcessor>(
+        &self,
+        geometries: &[&JsonValue],
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        if let [geometry] = geometries {
+            return self.process_geometry(geometry, idx, processor);
+        }
+        processor.geometrycollection_begin(geometries.len(), idx)?;
+        for (idxg, geometry) in geometries.iter().enumerate() {
+            self.process_geometry(geometry, idxg, processor)?;
+        }
+        processor.geometrycollection_end(idx)
+    }
+
+    fn process_geometry<P: GeomProcessor>(
+        &self,
+        geometry: &JsonValue,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geom_type = geometry
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| invalid("geometry type"))?;
+        let boundaries = geometry
+            .get("boundaries")
+            .ok_or_else(|| invalid("missing boundaries"));
+        match geom_type {
+            "MultiPoint" => {
+                let points = array(boundaries?, "boundaries")?;
+                let multi_dim = processor.multi_dim();
+                processor.multipoint_begin(points.len(), idx)?;
+                for (idxc, point) in points.iter().enumerate() {
+                    self.process_vertex(point, multi_dim, idxc, processor)?;
+                }
+                processor.multipoint_end(idx)
+            }
+            "MultiLineString" => {
+                let lines = array(boundaries?, "boundaries")?;
+                processor.multilinestring_begin(lines.len(), idx)?;
+                for (idxl, line) in lines.iter().enumerate() {
+                    self.process_linestring(line, false, idxl, processor)?;
+                }
+                processor.multilinestring_end(idx)
+            }
+            "MultiSurface" | "CompositeSurface" => {
+                let surfaces = array(boundaries?, "boundaries")?;
+                processor.multipolygon_begin(surfaces.len(), idx)?;
+                for (idxp, surface) in surfaces.iter().enumerate() {
+                    self.process_surface(surface, idxp, processor)?;
+                }
+                processor.multipolygon_end(idx)
+            }
+            "Solid" => self.process_solid(boundaries?, idx, processor),
+            "MultiSolid" | "CompositeSolid" => {
+                let solids = array(boundaries?, "boundaries")?;
+                processor.geometrycollection_begin(solids.len(), idx)?;
+                for (idxs, solid) in solids.iter().enumerate() {
+                    self.process_solid(solid, idxs, processor)?;
+                }
+                processor.geometrycollection_end(idx)
+            }
+            other => Err(GeozeroError::Geometry(format!(
+                "Unsupported CityJSON geometry type `{}`",
+                other
+            ))),
+        }
+    }
+
+    fn process_vertex<P: GeomProcessor>(
+        &self,
+        index: &JsonValue,
+        multi_dim: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let [x, y, z] = index
+            .as_u64()
+            .and_then(|i| self.vertices.get(i as usize))
+            .ok_or_else(|| invalid("vertex index out of range"))?;
+        if multi_dim {
+            processor.coordinate(*x, *y, Some(*z), None, None, None, idx)
+        } else {
+            processor.xy(*x, *y, idx)
+        }
+    }
+
+    fn process_linestring<P: GeomProcessor>(
+        &self,
+        line: &JsonValue,
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let indices = array(line, "vertex indices")?;
+        let multi_dim = processor.multi_dim();
+        processor.linestring_begin(tagged, indices.len(), idx)?;
+        for (idxc, index) in indices.iter().enumerate() {
+            self.process_vertex(index, multi_dim, idxc, processor)?;
+        }
+        processor.linestring_end(tagged, idx)
+    }
+
+    /// Process ring, closed by repeating the first vertex
+    fn process_ring<P: GeomProcessor>(
+        &self,
+        ring: &JsonValue,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let indices = array(ring, "vertex indices")?;
+        let multi_dim = processor.multi_dim();
+        let closing = indices
+            .first()
+            .filter(|&first| indices.last() != Some(first));
+        let size = indices.len() + closing.iter().count();
+        processor.linestring_begin(false, size, idx)?;
+        for (idxc, index) in indices.iter().chain(closing).enumerate() {
+            self.process_vertex(index, multi_dim, idxc, processor)?;
+        }
+        processor.linestring_end(false, idx)
+    }
+
+    fn process_surface<P: GeomProcessor>(
+        &self,
+        surface: &JsonValue,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let rings = array(surface, "surface")?;
+        processor.polygon_begin(false, rings.len(), idx)?;
+        for (idxr, ring) in rings.iter().enumerate() {
+            self.process_ring(ring, idxr, processor)?;
+        }
+        processor.polygon_end(false, idx)
+    }
+
+    fn process_solid<P: GeomProcessor>(
+        &self,
+        shells: &JsonValue,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let surfaces = array(shells, "solid")?
+            .iter()
+            .map(|shell| array(shell, "shell"))
+            .collect::<Result<Vec<_>>>()?;
+        let size = surfaces.iter().map(|shell| shell.len()).sum();
+        processor.polyhedralsurface_begin(size, idx)?;
+        for (idxp, surface) in surfaces.into_iter().flatten().enumerate() {
+            self.process_surface(surface, idxp, processor)?;
+        }
+        processor.polyhedralsurface_end(idx)
+    }
+}
+
+fn parse_triple(value: Option<&JsonValue>) -> Result<[f64; 3]> {
+    let values = value
+        .and_then(|v| v.as_array())
+        .filter(|v| v.len() >= 3)
+        .ok_or_else(|| invalid("vertex"))?;
+    let mut triple = [0.0; 3];
+    for (v, json) in triple.iter_mut().zip(values) {
+        *v = json.as_f64().ok_or_else(|| invalid("vertex"))?;
+    }
+    Ok(triple)
+}
+
+/// Process city object type and attributes
+fn process_properties<P: PropertyProcessor>(object: &JsonValue, processor: &mut P) -> Result<()> {
+    if let Some(JsonValue::String(object_type)) = object.get("type") {
+        if processor.property(0, "type", &ColumnValue::String(object_type))? {
+            return Ok(());
+        }
+    }
+    let attributes = match object.get("attributes") {
+        Some(JsonValue::Object(attributes)) => attributes,
+        _ => return Ok(()),
+    };
+    let mut json = String::new();
+    for (i, (key, value)) in attributes.iter().enumerate() {
+        let i = i + 1;
+        let finish = process_json_property(processor, i, key, value, &mut json)?;
+        if finish {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::wkt::WktWriter;
+    use crate::{CoordDimensions, ToWkt};
+
+    const CUBE: &str = r#"{
+      "type": "CityJSON",
+      "version": "1.1",
+      "transform": {"scale": [0.5, 0.5, 0.5], "translate": [100, 200, 0]},
+      "CityObjects": {
+        "building-1": {
+          "type": "Building",
+          "geometry": [{
+            "type": "Solid",
+            "lod": "1",
+            "boundaries": [[
+              [[0, 3, 2, 1]], [[4, 5, 6, 7]], [[0, 1, 5, 4]],
+              [[1, 2, 6, 5]], [[2, 3, 7, 6]], [[3, 0, 4, 7]]
+            ]]
+          }]
+        },
+        "tree-1": {
+          "type": "SolitaryVegetationObject",
+          "geometry": [{"type": "MultiPoint", "lod": "1", "boundaries": [8]}]
+        }
+      },
+      "vertices": [
+        [0, 0, 0], [2, 0, 0], [2, 2, 0], [0, 2, 0],
+        [0, 0, 2], [2, 0, 2], [2, 2, 2], [0, 2, 2],
+        [6, 6, 0]
+      ]
+    }"#;
+
+    #[test]
+    fn geometries() -> Result<()> {
+        let wkt = CityJson(CUBE).to_wkt()?;
+        assert!(wkt.starts_with(
+            "GEOMETRYCOLLECTION(POLYHEDRALSURFACE(((100 200,100 201,101 201,101 200,100 200)),"
+        ));
+        assert!(wkt.ends_with("),MULTIPOINT(103 203))"));
+        assert_eq!(wkt.matches("((").count(), 6);
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        CityJson(CUBE).process_geom(&mut writer)?;
+        assert!(std::str::from_utf8(&out)
+            .unwrap()
+            .contains("((100 200 1,101 200 1,101 201 1,100 201 1,100 200 1))"));
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let cityjson = r#"{
+          "type": "CityJSON",
+          "version": "1.1",
+          "metadata": {"referenceSystem": "https://www.opengis.net/def/crs/EPSG/0/7415"},
+          "CityObjects": {
+            "building-1": {
+              "type": "Building",
+              "attributes": {"roofType": "flat", "storeys": 1},
+              "geometry": [{"type": "MultiSurface", "lod": "0", "boundaries": [[[0, 1, 2, 3]]]}]
+            }
+          },
+          "vertices": [[0, 0, 0], [2, 0, 0], [2, 2, 0], [0, 2, 0]]
+        }"#;
+        let mut out: Vec<u8> = Vec::new();
+        CityJson(cityjson).process(&mut GeoJsonWriter::new(&mut out))?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(
+            r#""crs": {"type": "name", "properties": {"name": "urn:ogc:def:crs:EPSG::7415"}}"#
+        ));
+        assert!(json.contains(r#"{"type": "Feature", "id": "building-1""#));
+        assert!(json
+            .contains(r#""properties": {"type": "Building", "roofType": "flat", "storeys": 1}"#));
+        assert!(json.contains(
+            r#"{"type": "MultiPolygon", "coordinates": [[[[0,0],[2,0],[2,2],[0,2],[0,0]]]]}"#
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_model() {
+        assert!(CityJson(r#"{"type": "Topology", "objects": {}}"#)
+            .to_wkt()
+            .is_err());
+        let cityjson = r#"{"type": "CityJSON", "CityObjects": {"p": {"type": "Bridge",
+          "geometry": [{"type": "MultiPoint", "boundaries": [1]}]}}, "vertices": [[0, 0, 0]]}"#;
+        assert!(CityJson(cityjson).to_wkt().is_err());
+    }
+}
@@ -0,0 +1,4 @@
+//! CityJSON conversions.
+mod cityjson_reader;
+
+pub use cityjson_reader::*;
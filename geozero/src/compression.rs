@@ -0,0 +1,194 @@
+use crate::error::Result;
+use flate2::bufread::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression format of a stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionFormat {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl CompressionFormat {
+    /// Detect compression from the leading bytes of a stream.
+    pub fn detect(magic: &[u8]) -> Self {
+        if magic.starts_with(GZIP_MAGIC) {
+            CompressionFormat::Gzip
+        } else if magic.starts_with(ZSTD_MAGIC) {
+            CompressionFormat::Zstd
+        } else {
+            CompressionFormat::None
+        }
+    }
+
+    /// Compression by file extension (`.gz` or `.zst`).
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => CompressionFormat::Gzip,
+            Some("zst") => CompressionFormat::Zstd,
+            _ => CompressionFormat::None,
+        }
+    }
+}
+
+/// Reader decompressing gzip or zstd compressed input transparently.
+///
+/// The compression is detected from the leading bytes of `reader`, uncompressed input
+/// is passed through. The result can be used with text readers like
+/// [GeoJsonReader](crate::geojson::GeoJsonReader), `CsvReader` or `read_gml`.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{decompress, geojson::GeoJsonReader, GeozeroDatasource};
+/// use geozero::processing::StatsProcessor;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// # fn read() -> geozero::error::Result<()> {
+/// let mut reader = decompress(BufReader::new(File::open("places.geojson.gz")?))?;
+/// let mut stats = StatsProcessor::new();
+/// GeoJsonReader(&mut reader).process(&mut stats)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn decompress<'a, R: BufRead + 'a>(mut reader: R) -> Result<Box<dyn Read + 'a>> {
+    let compression = CompressionFormat::detect(reader.fill_buf()?);
+    Ok(match compression {
+        CompressionFormat::None => Box::new(reader),
+        CompressionFormat::Gzip => Box::new(MultiGzDecoder::new(reader)),
+        CompressionFormat::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+    })
+}
+
+/// Open file `path` with transparent decompression.
+pub fn open_decompressed<P: AsRef<Path>>(path: P) -> Result<Box<dyn Read>> {
+    decompress(BufReader::new(File::open(path)?))
+}
+
+/// Writer with optional gzip or zstd compression.
+///
+/// Compressed streams are completed with [finish](CompressedWriter::finish).
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{decompress, CompressedWriter, CompressionFormat, GeozeroGeometry};
+/// use geozero::{geojson::GeoJsonWriter, wkt::WktStr};
+/// use std::io::Read;
+///
+/// let mut out = CompressedWriter::new(Vec::new(), CompressionFormat::Gzip).unwrap();
+/// WktStr("POINT(1 2)").process_geom(&mut GeoJsonWriter::new(&mut out)).unwrap();
+/// let gz = out.finish().unwrap();
+/// let mut json = String::new();
+/// decompress(gz.as_slice()).unwrap().read_to_string(&mut json).unwrap();
+/// assert_eq!(json, r#"{"type": "Point", "coordinates": [1,2]}"#);
+/// ```
+pub enum CompressedWriter<W: Write> {
+    None(W),
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Writer compressing into `out` with default compression level.
+    pub fn new(out: W, compression: CompressionFormat) -> Result<Self> {
+        Ok(match compression {
+            CompressionFormat::None => CompressedWriter::None(out),
+            CompressionFormat::Gzip => {
+                CompressedWriter::Gzip(GzEncoder::new(out, flate2::Compression::default()))
+            }
+            CompressionFormat::Zstd => CompressedWriter::Zstd(zstd::Encoder::new(out, 0)?),
+        })
+    }
+
+    /// Complete the compressed stream and return the output.
+    pub fn finish(self) -> Result<W> {
+        Ok(match self {
+            CompressedWriter::None(out) => out,
+            CompressedWriter::Gzip(encoder) => encoder.finish()?,
+            CompressedWriter::Zstd(encoder) => encoder.finish()?,
+        })
+    }
+}
+
+impl CompressedWriter<BufWriter<File>> {
+    /// Create file `path` compressed according to its extension (`.gz` or `.zst`).
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let compression = CompressionFormat::from_path(&path);
+        CompressedWriter::new(BufWriter::new(File::create(path)?), compression)
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            CompressedWriter::None(out) => out.write(buf),
+            CompressedWriter::Gzip(encoder) => encoder.write(buf),
+            CompressedWriter::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            CompressedWriter::None(out) => out.flush(),
+            CompressedWriter::Gzip(encoder) => encoder.flush(),
+            CompressedWriter::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonReader;
+    use crate::processing::StatsProcessor;
+    use crate::GeozeroDatasource;
+
+    const GEOJSON: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+    ]}"#;
+
+    fn read_compressed(compression: CompressionFormat) -> Result<u64> {
+        let mut out = CompressedWriter::new(Vec::new(), compression)?;
+        out.write_all(GEOJSON.as_bytes())?;
+        let data = out.finish()?;
+        assert_eq!(CompressionFormat::detect(&data), compression);
+        let mut reader = decompress(data.as_slice())?;
+        let mut stats = StatsProcessor::new();
+        GeoJsonReader(&mut reader).process(&mut stats)?;
+        Ok(stats.stats().features)
+    }
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        assert_eq!(read_compressed(CompressionFormat::None)?, 2);
+        assert_eq!(read_compressed(CompressionFormat::Gzip)?, 2);
+        assert_eq!(read_compressed(CompressionFormat::Zstd)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn extension() {
+        assert_eq!(
+            CompressionFormat::from_path("places.geojson.gz"),
+            CompressionFormat::Gzip
+        );
+        assert_eq!(
+            CompressionFormat::from_path("places.csv.zst"),
+            CompressionFormat::Zstd
+        );
+        assert_eq!(
+            CompressionFormat::from_path("places.geojson"),
+            CompressionFormat::None
+        );
+    }
+}
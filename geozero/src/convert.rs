@@ -0,0 +1,212 @@
+//! Convenience functions for converting single geometries between formats.
+//!
+//! Text outputs keep Z and M values, binary outputs are written in 2D.
+use crate::error::{GeozeroError, Result};
+#[allow(unused_imports)]
+use crate::{CoordDimensions, GeozeroGeometry};
+
+#[cfg(feature = "with-geojson")]
+#[allow(unused_imports)]
+use crate::geojson::{GeoJson, GeoJsonWriter};
+#[cfg(feature = "with-wkb")]
+#[allow(unused_imports)]
+use crate::wkb::{process_wkb_slice, WkbDialect, WkbWriter};
+#[cfg(feature = "with-wkt")]
+#[allow(unused_imports)]
+use crate::wkt::{WktStr, WktWriter};
+
+#[allow(dead_code)]
+fn utf8(out: Vec<u8>) -> Result<String> {
+    String::from_utf8(out).map_err(|_| GeozeroError::Geometry("Invalid UTF-8 encoding".to_string()))
+}
+
+/// Decode a hex encoded byte string, as used by PostGIS for (E)WKB.
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex = hex.trim();
+    if !hex.len().is_multiple_of(2) {
+        return Err(GeozeroError::Geometry(
+            "Invalid hex string length".to_string(),
+        ));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| GeozeroError::Geometry("Invalid hex string".to_string()))
+        })
+        .collect()
+}
+
+/// Encode bytes as upper case hex string.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+#[cfg(all(
+    feature = "with-wkt",
+    any(feature = "with-wkb", feature = "with-geojson")
+))]
+fn to_wkt<F: FnOnce(&mut WktWriter<Vec<u8>>) -> Result<()>>(process: F) -> Result<String> {
+    let mut out: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::new(&mut out);
+    writer.dims = CoordDimensions::xyzm();
+    process(&mut writer)?;
+    utf8(out)
+}
+
+#[cfg(all(
+    feature = "with-geojson",
+    any(feature = "with-wkb", feature = "with-wkt")
+))]
+fn to_geojson<F: FnOnce(&mut GeoJsonWriter<Vec<u8>>) -> Result<()>>(process: F) -> Result<String> {
+    let mut out: Vec<u8> = Vec::new();
+    let mut writer = GeoJsonWriter::new(&mut out);
+    writer.dims = CoordDimensions::xyz();
+    process(&mut writer)?;
+    utf8(out)
+}
+
+#[cfg(all(
+    feature = "with-wkb",
+    any(feature = "with-wkt", feature = "with-geojson")
+))]
+fn to_wkb<G: GeozeroGeometry>(geom: &G, dialect: WkbDialect, srid: Option<i32>) -> Result<Vec<u8>> {
+    let mut out: Vec<u8> = Vec::new();
+    let mut writer = WkbWriter::new(&mut out, dialect);
+    writer.srid = srid;
+    geom.process_geom(&mut writer)?;
+    Ok(out)
+}
+
+/// Convert WKB to WKT.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn wkb_to_wkt(wkb: &[u8]) -> Result<String> {
+    to_wkt(|writer| process_wkb_slice(wkb, WkbDialect::Wkb, writer))
+}
+
+/// Convert EWKB to WKT.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn ewkb_to_wkt(ewkb: &[u8]) -> Result<String> {
+    to_wkt(|writer| process_wkb_slice(ewkb, WkbDialect::Ewkb, writer))
+}
+
+/// Convert hex encoded EWKB (or WKB) to WKT.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn hex_to_wkt(hex: &str) -> Result<String> {
+    ewkb_to_wkt(&decode_hex(hex)?)
+}
+
+/// Convert WKB to GeoJSON.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn wkb_to_geojson(wkb: &[u8]) -> Result<String> {
+    to_geojson(|writer| process_wkb_slice(wkb, WkbDialect::Wkb, writer))
+}
+
+/// Convert EWKB to GeoJSON.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn ewkb_to_geojson(ewkb: &[u8]) -> Result<String> {
+    to_geojson(|writer| process_wkb_slice(ewkb, WkbDialect::Ewkb, writer))
+}
+
+/// Convert hex encoded EWKB (or WKB) to GeoJSON.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn hex_to_geojson(hex: &str) -> Result<String> {
+    ewkb_to_geojson(&decode_hex(hex)?)
+}
+
+/// Convert WKT to 2D WKB.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn wkt_to_wkb(wkt: &str) -> Result<Vec<u8>> {
+    to_wkb(&WktStr(wkt), WkbDialect::Wkb, None)
+}
+
+/// Convert WKT to 2D EWKB.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn wkt_to_ewkb(wkt: &str, srid: Option<i32>) -> Result<Vec<u8>> {
+    to_wkb(&WktStr(wkt), WkbDialect::Ewkb, srid)
+}
+
+/// Convert WKT to 2D hex encoded EWKB.
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+pub fn wkt_to_hex(wkt: &str, srid: Option<i32>) -> Result<String> {
+    Ok(encode_hex(&wkt_to_ewkb(wkt, srid)?))
+}
+
+/// Convert a GeoJSON geometry to 2D WKB.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn geojson_to_wkb(json: &str) -> Result<Vec<u8>> {
+    to_wkb(&GeoJson(json), WkbDialect::Wkb, None)
+}
+
+/// Convert a GeoJSON geometry to 2D EWKB.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn geojson_to_ewkb(json: &str, srid: Option<i32>) -> Result<Vec<u8>> {
+    to_wkb(&GeoJson(json), WkbDialect::Ewkb, srid)
+}
+
+/// Convert a GeoJSON geometry to 2D hex encoded EWKB.
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+pub fn geojson_to_hex(json: &str, srid: Option<i32>) -> Result<String> {
+    Ok(encode_hex(&geojson_to_ewkb(json, srid)?))
+}
+
+/// Convert WKT to GeoJSON.
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+pub fn wkt_to_geojson(wkt: &str) -> Result<String> {
+    to_geojson(|writer| WktStr(wkt).process_geom(writer))
+}
+
+/// Convert a GeoJSON geometry to WKT.
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+pub fn geojson_to_wkt(json: &str) -> Result<String> {
+    to_wkt(|writer| GeoJson(json).process_geom(writer))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        assert_eq!(decode_hex("01ff0A").unwrap(), vec![1, 255, 10]);
+        assert_eq!(encode_hex(&[1, 255, 10]), "01FF0A");
+        assert!(decode_hex("01f").is_err());
+        assert!(decode_hex("0g").is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "with-wkb", feature = "with-wkt", feature = "with-geojson"))]
+    fn conversions() {
+        // SELECT 'SRID=4326;POINT(10 -20 100)'::geometry
+        let hex = "01010000A0E6100000000000000000244000000000000034C00000000000005940";
+        assert_eq!(hex_to_wkt(hex).unwrap(), "POINT(10 -20 100)");
+        assert_eq!(
+            hex_to_geojson(hex).unwrap(),
+            r#"{"type": "Point", "coordinates": [10,-20,100]}"#
+        );
+
+        let wkb = wkt_to_wkb("LINESTRING(1 2,3 4)").unwrap();
+        assert_eq!(wkb_to_wkt(&wkb).unwrap(), "LINESTRING(1 2,3 4)");
+        assert_eq!(
+            wkb_to_geojson(&wkb).unwrap(),
+            r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#
+        );
+
+        let ewkb =
+            geojson_to_ewkb(r#"{"type": "Point", "coordinates": [10,-20]}"#, Some(4326)).unwrap();
+        assert_eq!(
+            encode_hex(&ewkb),
+            "0101000020E6100000000000000000244000000000000034C0"
+        );
+        assert_eq!(ewkb_to_wkt(&ewkb).unwrap(), "POINT(10 -20)");
+        assert_eq!(
+            wkt_to_hex("POINT(10 -20)", Some(4326)).unwrap(),
+            encode_hex(&ewkb)
+        );
+        assert_eq!(
+            geojson_to_wkt(&wkt_to_geojson("POINT(1 2)").unwrap()).unwrap(),
+            "POINT(1 2)"
+        );
+    }
+}
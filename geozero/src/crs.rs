@@ -0,0 +1,75 @@
+//! Coordinate reference system identifiers.
+
+/// EPSG code of a CRS name.
+///
+/// Supported notations:
+/// * `EPSG:4326`
+/// * `urn:ogc:def:crs:EPSG::4326` (also with version, e.g. `urn:ogc:def:crs:EPSG:6.6:4326`)
+/// * `http://www.opengis.net/def/crs/EPSG/0/4326` (also with `https`)
+/// * `http://www.opengis.net/gml/srs/epsg.xml#4326`
+///
+/// ```
+/// assert_eq!(geozero::epsg_code("urn:ogc:def:crs:EPSG::3857"), Some(3857));
+/// assert_eq!(geozero::epsg_code("urn:ogc:def:crs:OGC:1.3:CRS84"), None);
+/// ```
+pub fn epsg_code(crs_name: &str) -> Option<i32> {
+    let name = crs_name.trim();
+    let code = if let Some(code) = strip_prefix_ignore_case(name, "EPSG:") {
+        code
+    } else if let Some(rest) = strip_prefix_ignore_case(name, "urn:ogc:def:crs:EPSG:") {
+        // optional version between the last two colons
+        rest.rsplit(':').next()?
+    } else if let Some(code) = name
+        .strip_prefix("http://www.opengis.net/def/crs/EPSG/")
+        .or_else(|| name.strip_prefix("https://www.opengis.net/def/crs/EPSG/"))
+        .and_then(|rest| rest.rsplit('/').next())
+    {
+        code
+    } else {
+        name.strip_prefix("http://www.opengis.net/gml/srs/epsg.xml#")?
+    };
+    code.parse().ok()
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    if s.len() >= prefix.len() && s.is_char_boundary(prefix.len()) {
+        let (head, tail) = s.split_at(prefix.len());
+        if head.eq_ignore_ascii_case(prefix) {
+            return Some(tail);
+        }
+    }
+    None
+}
+
+/// OGC URN of an EPSG code, as used in legacy GeoJSON `crs` members.
+pub fn epsg_urn(srid: i32) -> String {
+    format!("urn:ogc:def:crs:EPSG::{}", srid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crs_names() {
+        assert_eq!(epsg_code("EPSG:4326"), Some(4326));
+        assert_eq!(epsg_code("epsg:2056"), Some(2056));
+        assert_eq!(epsg_code("urn:ogc:def:crs:EPSG::4326"), Some(4326));
+        assert_eq!(epsg_code("urn:ogc:def:crs:EPSG:6.6:4326"), Some(4326));
+        assert_eq!(
+            epsg_code("http://www.opengis.net/def/crs/EPSG/0/25832"),
+            Some(25832)
+        );
+        assert_eq!(
+            epsg_code("https://www.opengis.net/def/crs/EPSG/0/7415"),
+            Some(7415)
+        );
+        assert_eq!(
+            epsg_code("http://www.opengis.net/gml/srs/epsg.xml#4326"),
+            Some(4326)
+        );
+        assert_eq!(epsg_code("urn:ogc:def:crs:OGC:1.3:CRS84"), None);
+        assert_eq!(epsg_code("EPSG:abc"), None);
+        assert_eq!(epsg_code(&epsg_urn(3857)), Some(3857));
+    }
+}
@@ -1,99 +1,243 @@
 use crate::error::{GeozeroError, Result};
-use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    RewindableDatasource,
+};
+
+use std::io::{Read, Seek, SeekFrom};
+
+/// Columns containing the geometry of a CSV row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvGeometryColumns {
+    /// Single column with WKT geometry
+    Wkt(String),
+    /// Point from longitude and latitude columns
+    LonLat { lon: String, lat: String },
+}
 
-use std::io::Read;
+impl CsvGeometryColumns {
+    fn lon_lat(lon: &str, lat: &str) -> Self {
+        CsvGeometryColumns::LonLat {
+            lon: lon.to_string(),
+            lat: lat.to_string(),
+        }
+    }
+}
 
 pub struct Csv<'a> {
     csv_text: &'a str,
-    geometry_column_name: String,
+    geometry_columns: CsvGeometryColumns,
 }
 
 impl<'a> Csv<'a> {
     pub fn new(geometry_column_name: &str, csv_text: &'a str) -> Self {
         Self {
             csv_text,
-            geometry_column_name: geometry_column_name.to_string(),
+            geometry_columns: CsvGeometryColumns::Wkt(geometry_column_name.to_string()),
+        }
+    }
+
+    /// Read point geometries from longitude and latitude columns.
+    pub fn with_lon_lat(lon_column_name: &str, lat_column_name: &str, csv_text: &'a str) -> Self {
+        Self {
+            csv_text,
+            geometry_columns: CsvGeometryColumns::lon_lat(lon_column_name, lat_column_name),
         }
     }
 }
 
 impl GeozeroDatasource for Csv<'_> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(
-            self.csv_text.as_bytes(),
-            processor,
-            &self.geometry_column_name,
-        )
+        process_csv_features_with(self.csv_text.as_bytes(), processor, &self.geometry_columns)
+    }
+}
+
+impl RewindableDatasource for Csv<'_> {
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
 impl GeozeroGeometry for Csv<'_> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(
-            self.csv_text.as_bytes(),
-            processor,
-            &self.geometry_column_name,
-        )
+        process_csv_geom_with(self.csv_text.as_bytes(), processor, &self.geometry_columns)
     }
 }
 
 pub struct CsvString {
     csv_text: String,
-    geometry_column_name: String,
+    geometry_columns: CsvGeometryColumns,
 }
 
 impl CsvString {
     pub fn new(geometry_column_name: &str, csv_text: String) -> Self {
         Self {
             csv_text,
-            geometry_column_name: geometry_column_name.to_string(),
+            geometry_columns: CsvGeometryColumns::Wkt(geometry_column_name.to_string()),
+        }
+    }
+
+    /// Read point geometries from longitude and latitude columns.
+    pub fn with_lon_lat(lon_column_name: &str, lat_column_name: &str, csv_text: String) -> Self {
+        Self {
+            csv_text,
+            geometry_columns: CsvGeometryColumns::lon_lat(lon_column_name, lat_column_name),
         }
     }
 }
 
 impl GeozeroDatasource for CsvString {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(
-            self.csv_text.as_bytes(),
-            processor,
-            &self.geometry_column_name,
-        )
+        process_csv_features_with(self.csv_text.as_bytes(), processor, &self.geometry_columns)
+    }
+}
+
+impl RewindableDatasource for CsvString {
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
 impl GeozeroGeometry for CsvString {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(
-            self.csv_text.as_bytes(),
-            processor,
-            &self.geometry_column_name,
-        )
+        process_csv_geom_with(self.csv_text.as_bytes(), processor, &self.geometry_columns)
     }
 }
 
+/// CSV reader streaming rows as features.
+///
+/// The geometry is read from a WKT column or from a longitude/latitude column pair.
+/// All other columns are processed as string properties.
 pub struct CsvReader<R: Read> {
     inner: R,
-    geometry_column_name: String,
+    geometry_columns: CsvGeometryColumns,
 }
 
 impl<R: Read> CsvReader<R> {
     pub fn new(geometry_column_name: &str, inner: R) -> Self {
         Self {
             inner,
-            geometry_column_name: geometry_column_name.to_string(),
+            geometry_columns: CsvGeometryColumns::Wkt(geometry_column_name.to_string()),
+        }
+    }
+
+    /// Read point geometries from longitude and latitude columns.
+    pub fn with_lon_lat(lon_column_name: &str, lat_column_name: &str, inner: R) -> Self {
+        Self {
+            inner,
+            geometry_columns: CsvGeometryColumns::lon_lat(lon_column_name, lat_column_name),
         }
     }
 }
 
 impl<R: Read> GeozeroDatasource for CsvReader<R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(&mut self.inner, processor, &self.geometry_column_name)
+        process_csv_features_with(&mut self.inner, processor, &self.geometry_columns)
+    }
+}
+
+impl<R: Read + Seek> RewindableDatasource for CsvReader<R> {
+    fn rewind(&mut self) -> Result<()> {
+        self.inner.seek(SeekFrom::Start(0))?;
+        Ok(())
     }
 }
 
 impl<R: Read + Clone> GeozeroGeometry for CsvReader<R> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(self.inner.clone(), processor, &self.geometry_column_name)
+        process_csv_geom_with(self.inner.clone(), processor, &self.geometry_columns)
+    }
+}
+
+/// Column indices of geometry columns
+enum GeometryIdx {
+    Wkt(usize),
+    LonLat(usize, usize),
+}
+
+impl GeometryIdx {
+    fn new(headers: &csv::StringRecord, columns: &CsvGeometryColumns) -> Result<Self> {
+        let position = |name: &str| {
+            headers
+                .iter()
+                .position(|f| f == name)
+                .ok_or(GeozeroError::ColumnNotFound)
+        };
+        match columns {
+            CsvGeometryColumns::Wkt(name) => Ok(GeometryIdx::Wkt(position(name)?)),
+            CsvGeometryColumns::LonLat { lon, lat } => {
+                Ok(GeometryIdx::LonLat(position(lon)?, position(lat)?))
+            }
+        }
+    }
+
+    fn contains(&self, idx: usize) -> bool {
+        match self {
+            GeometryIdx::Wkt(wkt_idx) => *wkt_idx == idx,
+            GeometryIdx::LonLat(lon_idx, lat_idx) => *lon_idx == idx || *lat_idx == idx,
+        }
+    }
+
+    fn is_empty(&self, record: &csv::StringRecord) -> Result<bool> {
+        let field = |i: usize| record.get(i).ok_or(GeozeroError::ColumnNotFound);
+        match self {
+            GeometryIdx::Wkt(idx) => Ok(field(*idx)?.is_empty()),
+            GeometryIdx::LonLat(lon_idx, lat_idx) => {
+                Ok(field(*lon_idx)?.trim().is_empty() || field(*lat_idx)?.trim().is_empty())
+            }
+        }
+    }
+
+    fn process<P: GeomProcessor>(
+        &self,
+        record: &csv::StringRecord,
+        record_idx: usize,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let field = |i: usize| record.get(i).ok_or(GeozeroError::ColumnNotFound);
+        match self {
+            GeometryIdx::Wkt(wkt_idx) => {
+                let geometry_field = field(*wkt_idx)?;
+                use std::str::FromStr;
+                wkt::Wkt::from_str(geometry_field)
+                    .map_err(|e| GeozeroError::Geometry(e.to_string()))
+                    .and_then(|wkt| {
+                        crate::wkt::wkt_reader::process_wkt_geom_n(&wkt.item, idx, processor)
+                    })
+                    .map_err(|e| {
+                        // +2 to start at line 1 and to account for the header row
+                        let line = record_idx + 2;
+                        log::warn!(
+                            "line {}: invalid WKT: '{}', record: {:?}",
+                            line,
+                            geometry_field,
+                            record
+                        );
+                        e
+                    })?;
+            }
+            GeometryIdx::LonLat(lon_idx, lat_idx) => {
+                let (lon, lat) = (field(*lon_idx)?.trim(), field(*lat_idx)?.trim());
+                let coord = |v: &str| {
+                    v.parse::<f64>().map_err(|_| {
+                        // +2 to start at line 1 and to account for the header row
+                        let line = record_idx + 2;
+                        log::warn!("line {}: invalid coordinate: '{}'", line, v);
+                        GeozeroError::Coord
+                    })
+                };
+                let (x, y) = (coord(lon)?, coord(lat)?);
+                processor.point_begin(idx)?;
+                if processor.multi_dim() {
+                    processor.coordinate(x, y, None, None, None, None, 0)?;
+                } else {
+                    processor.xy(x, y, 0)?;
+                }
+                processor.point_end(idx)?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -101,14 +245,23 @@ pub fn process_csv_geom(
     input: impl Read,
     processor: &mut impl GeomProcessor,
     geometry_column: &str,
+) -> Result<()> {
+    process_csv_geom_with(
+        input,
+        processor,
+        &CsvGeometryColumns::Wkt(geometry_column.to_string()),
+    )
+}
+
+pub fn process_csv_geom_with(
+    input: impl Read,
+    processor: &mut impl GeomProcessor,
+    geometry_columns: &CsvGeometryColumns,
 ) -> Result<()> {
     let mut reader = csv::Reader::from_reader(input);
     let headers = reader.headers()?.clone();
 
-    let geometry_idx = headers
-        .iter()
-        .position(|f| f == geometry_column)
-        .ok_or(GeozeroError::ColumnNotFound)?;
+    let geometry_idx = GeometryIdx::new(&headers, geometry_columns)?;
 
     // REVIEW: We don't know the size of the geometry collection, so we lie and say it's 0.
     // Alternatively we'd have to re-traverse the file an extra time just to count or hold
@@ -118,25 +271,9 @@ pub fn process_csv_geom(
 
     for (record_idx, record) in reader.into_records().enumerate() {
         let record = record?;
-        let geometry_field = record
-            .get(geometry_idx)
-            .ok_or(GeozeroError::ColumnNotFound)?;
-        use std::str::FromStr;
-        let wkt = wkt::Wkt::from_str(&geometry_field)
-            .map_err(|e| GeozeroError::Geometry(e.to_string()))?;
-        crate::wkt::wkt_reader::process_wkt_geom_n(&wkt.item, record_idx, processor).map_err(
-            |e| {
-                // +2 to start at line 1 and to account for the header row
-                let line = record_idx + 2;
-                log::warn!(
-                    "line {}: invalid WKT: '{}', record: {:?}",
-                    line,
-                    geometry_field,
-                    &record
-                );
-                e
-            },
-        )?;
+        if !geometry_idx.is_empty(&record)? {
+            geometry_idx.process(&record, record_idx, record_idx, processor)?;
+        }
     }
 
     processor.geometrycollection_end(0)?;
@@ -148,15 +285,24 @@ pub fn process_csv_features(
     input: impl Read,
     processor: &mut impl FeatureProcessor,
     geometry_column: &str,
+) -> Result<()> {
+    process_csv_features_with(
+        input,
+        processor,
+        &CsvGeometryColumns::Wkt(geometry_column.to_string()),
+    )
+}
+
+pub fn process_csv_features_with(
+    input: impl Read,
+    processor: &mut impl FeatureProcessor,
+    geometry_columns: &CsvGeometryColumns,
 ) -> Result<()> {
     let mut reader = csv::Reader::from_reader(input);
     let headers = reader.headers()?.clone();
     processor.dataset_begin(None)?;
 
-    let geometry_idx = headers
-        .iter()
-        .position(|f| f == geometry_column)
-        .ok_or(GeozeroError::ColumnNotFound)?;
+    let geometry_idx = GeometryIdx::new(&headers, geometry_columns)?;
 
     for (feature_idx, record) in reader.into_records().enumerate() {
         let record = record?;
@@ -168,8 +314,8 @@ pub fn process_csv_features(
             .iter()
             .zip(record.iter())
             .enumerate()
-            // skip the geometry field -  we process it after all the "properties"
-            .filter(|(input_idx, _)| *input_idx != geometry_idx)
+            // skip the geometry fields -  we process them after all the "properties"
+            .filter(|(input_idx, _)| !geometry_idx.contains(*input_idx))
             .map(|(_input_idx, (header, value))| (header, value));
 
         for (output_idx, (header, field)) in properties_iter.enumerate() {
@@ -179,27 +325,13 @@ pub fn process_csv_features(
 
         processor.properties_end()?;
 
-        let geometry_field = record
-            .get(geometry_idx)
-            .ok_or(GeozeroError::ColumnNotFound)?;
-
         // Do all formats allow empty geometries?
-        if !geometry_field.is_empty() {
+        if !geometry_idx.is_empty(&record)? {
             processor.geometry_begin()?;
-            crate::wkt::wkt_reader::read_wkt(&mut geometry_field.as_bytes(), processor).map_err(
-                |e| {
-                    // +2 to start at line 1 and to account for the header row
-                    let line = feature_idx + 2;
-                    log::warn!(
-                        "line {}: invalid WKT: '{}', record: {:?}",
-                        line,
-                        geometry_field,
-                        &record
-                    );
-                    e
-                },
-            )?;
+            geometry_idx.process(&record, feature_idx, 0, processor)?;
             processor.geometry_end()?;
+        } else {
+            processor.null_geometry()?;
         }
 
         processor.feature_end(feature_idx as u64)?;
@@ -416,8 +548,80 @@ mod tests {
 
         let json = csv.to_json().unwrap();
 
-        // ensure the json is parsable. Note it's not parsable back as geojson due to the
-        // missing geometry. Some formats, like FGB, will tolerate this null geometry.
-        serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        // missing geometries are written as null geometry
+        let geojson = json.parse::<::geojson::GeoJson>().unwrap();
+        match geojson {
+            ::geojson::GeoJson::FeatureCollection(fc) => assert!(fc.features[1].geometry.is_none()),
+            _ => panic!("feature collection expected"),
+        }
+    }
+
+    #[test]
+    fn lon_lat_feature_processor() {
+        use crate::geojson::conversion::ProcessToJson;
+
+        let mut csv = CsvReader::with_lon_lat(
+            "lon",
+            "lat",
+            r#"name,lat,lon
+Zürich,47.3769,8.5417
+Nowhere,,
+Bern, 46.948 , 7.4474"#
+                .as_bytes(),
+        );
+        let expected_geojson = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+               {
+                   "type": "Feature",
+                   "geometry": {
+                       "type": "Point",
+                       "coordinates": [8.5417, 47.3769]
+                   },
+                   "properties": {
+                       "name": "Zürich"
+                   }
+               },
+               {
+                   "type": "Feature",
+                   "geometry": null,
+                   "properties": {
+                       "name": "Nowhere"
+                   }
+               },
+               {
+                   "type": "Feature",
+                   "geometry": {
+                       "type": "Point",
+                       "coordinates": [7.4474, 46.948]
+                   },
+                   "properties": {
+                       "name": "Bern"
+                   }
+               }
+            ]
+        });
+
+        let actual_geojson = csv.to_json().unwrap();
+        let actual_geojson: serde_json::Value = serde_json::from_str(&actual_geojson).unwrap();
+
+        assert_eq!(expected_geojson, actual_geojson);
+    }
+
+    #[test]
+    fn lon_lat_geom_processor() {
+        use crate::wkt::conversion::ToWkt;
+
+        let csv = Csv::with_lon_lat("x", "y", "id,x,y\n1,1.5,2\n2,3,-4\n");
+        assert_eq!(
+            csv.to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1.5 2),POINT(3 -4))"
+        );
+
+        let csv = Csv::with_lon_lat("x", "y", "id,x,y\n1,east,2\n");
+        assert!(csv.to_wkt().is_err());
+
+        let csv = Csv::with_lon_lat("lon", "lat", "id,x,y\n1,1.5,2\n");
+        assert!(matches!(csv.to_wkt(), Err(GeozeroError::ColumnNotFound)));
     }
 }
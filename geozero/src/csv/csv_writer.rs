@@ -1,35 +1,80 @@
 use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::wkt::WktWriter;
 use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
-use buffering_wkt_writer::BufferingWktWriter;
 
 use std::io::Write;
 
+/// Serialization of the CSV geometry column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvGeometryFormat {
+    /// Well-known text
+    #[default]
+    Wkt,
+    /// Hex encoded WKB, as accepted by PostGIS
+    #[cfg(feature = "with-wkb")]
+    WkbHex,
+    /// GeoJSON geometry object
+    #[cfg(feature = "with-geojson")]
+    GeoJson,
+}
+
 pub struct CsvWriter<'w, W: Write> {
     csv: csv::Writer<&'w mut W>,
     headers: Vec<String>,
     has_written_first_record: bool,
     current_row_props: Vec<String>,
-    wkt_writer: BufferingWktWriter,
+    dims: CoordDimensions,
+    /// Geometry of current row, written in the configured format at the end of the row
+    geometry: FeatureRecorder,
+    geometry_column: String,
+    geometry_format: CsvGeometryFormat,
 }
 
 impl<'w, W: Write> CsvWriter<'w, W> {
     pub fn new(out: &'w mut W) -> Self {
-        let wkt_writer = BufferingWktWriter::new();
+        Self::with_csv_writer(csv::Writer::from_writer(out))
+    }
+
+    /// Writer with custom field delimiter, e.g. `b'\t'` for TSV.
+    pub fn with_delimiter(out: &'w mut W, delimiter: u8) -> Self {
+        Self::with_csv_writer(
+            csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(out),
+        )
+    }
+
+    fn with_csv_writer(csv: csv::Writer<&'w mut W>) -> Self {
         Self {
-            csv: csv::Writer::from_writer(out),
+            csv,
             headers: vec!["geometry".to_string()],
             has_written_first_record: false,
             current_row_props: vec![],
-            wkt_writer,
+            dims: CoordDimensions::default(),
+            geometry: FeatureRecorder::default(),
+            geometry_column: "geometry".to_string(),
+            geometry_format: CsvGeometryFormat::default(),
         }
     }
 
     pub fn set_dims(&mut self, dims: CoordDimensions) {
-        self.wkt_writer.dims = dims;
+        self.dims = dims;
+    }
+
+    /// Set name of geometry column (default: `geometry`).
+    pub fn set_geometry_column(&mut self, name: &str) {
+        self.geometry_column = name.to_string();
+        self.headers[0] = name.to_string();
+    }
+
+    /// Set serialization of geometry column (default: WKT).
+    pub fn set_geometry_format(&mut self, format: CsvGeometryFormat) {
+        self.geometry_format = format;
     }
 
     fn has_started_writing_geometry_in_this_row(&self) -> bool {
-        !self.wkt_writer.bytes().is_empty()
+        !self.geometry.is_empty()
     }
 
     fn offset_geom_idx(&self, input_idx: usize) -> usize {
@@ -40,15 +85,48 @@ impl<'w, W: Write> CsvWriter<'w, W> {
             0
         }
     }
+
+    /// Geometry field in configured format, written from the recorded geometry.
+    fn geometry_field(&self) -> Result<Vec<u8>> {
+        let mut out: Vec<u8> = Vec::new();
+        if self.geometry.is_empty() {
+            return Ok(out);
+        }
+        match self.geometry_format {
+            CsvGeometryFormat::Wkt => {
+                let mut writer = WktWriter::new(&mut out);
+                writer.dims = self.dims;
+                self.geometry.replay(&mut writer)?;
+            }
+            #[cfg(feature = "with-wkb")]
+            CsvGeometryFormat::WkbHex => {
+                use crate::wkb::{WkbDialect, WkbWriter};
+
+                let mut writer = WkbWriter::new(&mut out, WkbDialect::Wkb);
+                writer.dims = self.dims;
+                writer.hex = true;
+                self.geometry.replay(&mut writer)?;
+            }
+            #[cfg(feature = "with-geojson")]
+            CsvGeometryFormat::GeoJson => {
+                use crate::geojson::GeoJsonWriter;
+
+                let mut writer = GeoJsonWriter::new(&mut out);
+                writer.dims = self.dims;
+                self.geometry.replay(&mut writer)?;
+            }
+        }
+        Ok(out)
+    }
 }
 
 impl<W: Write> FeatureProcessor for CsvWriter<'_, W> {
     fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
-        debug_assert_eq!(self.headers, &["geometry"]);
+        debug_assert_eq!(self.headers, std::slice::from_ref(&self.geometry_column));
         Ok(())
     }
     fn dataset_end(&mut self) -> Result<()> {
-        self.headers = vec!["geometry".to_string()];
+        self.headers = vec![self.geometry_column.clone()];
         Ok(())
     }
     fn feature_begin(&mut self, _idx: u64) -> Result<()> {
@@ -62,9 +140,9 @@ impl<W: Write> FeatureProcessor for CsvWriter<'_, W> {
             self.csv.write_record(self.headers.clone())?;
         }
 
-        let geom = self.wkt_writer.bytes();
+        let geom = self.geometry_field()?;
         self.csv.write_field(geom)?;
-        self.wkt_writer.clear();
+        self.geometry.clear();
 
         for field in &self.current_row_props {
             self.csv.write_field(field)?;
@@ -111,10 +189,10 @@ impl<W: Write> PropertyProcessor for CsvWriter<'_, W> {
 
 impl<W: Write> GeomProcessor for CsvWriter<'_, W> {
     fn dimensions(&self) -> CoordDimensions {
-        self.wkt_writer.dimensions()
+        self.dims
     }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        self.wkt_writer.xy(x, y, idx)
+        self.geometry.xy(x, y, idx)
     }
     fn coordinate(
         &mut self,
@@ -126,265 +204,117 @@ impl<W: Write> GeomProcessor for CsvWriter<'_, W> {
         tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        self.wkt_writer.coordinate(x, y, z, m, t, tm, idx)
+        self.geometry.coordinate(x, y, z, m, t, tm, idx)
     }
     fn point_begin(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.point_begin(self.offset_geom_idx(idx))
+        self.geometry.point_begin(self.offset_geom_idx(idx))
     }
     fn point_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.point_end(self.offset_geom_idx(idx))
+        self.geometry.point_end(self.offset_geom_idx(idx))
     }
     fn empty_point(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.empty_point(self.offset_geom_idx(idx))
+        self.geometry.empty_point(self.offset_geom_idx(idx))
     }
     fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .multipoint_begin(size, self.offset_geom_idx(idx))
     }
     fn multipoint_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.multipoint_end(self.offset_geom_idx(idx))
+        self.geometry.multipoint_end(self.offset_geom_idx(idx))
     }
     fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .linestring_begin(tagged, size, self.offset_geom_idx(idx))
     }
     fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .linestring_end(tagged, self.offset_geom_idx(idx))
     }
     fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .multilinestring_begin(size, self.offset_geom_idx(idx))
     }
     fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer
-            .multilinestring_end(self.offset_geom_idx(idx))
+        self.geometry.multilinestring_end(self.offset_geom_idx(idx))
     }
     fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .polygon_begin(tagged, size, self.offset_geom_idx(idx))
     }
     fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-        self.wkt_writer
-            .polygon_end(tagged, self.offset_geom_idx(idx))
+        self.geometry.polygon_end(tagged, self.offset_geom_idx(idx))
     }
     fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .multipolygon_begin(size, self.offset_geom_idx(idx))
     }
     fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.multipolygon_end(self.offset_geom_idx(idx))
+        self.geometry.multipolygon_end(self.offset_geom_idx(idx))
     }
     fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .geometrycollection_begin(size, self.offset_geom_idx(idx))
     }
     fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .geometrycollection_end(self.offset_geom_idx(idx))
     }
     fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .circularstring_begin(size, self.offset_geom_idx(idx))
     }
     fn circularstring_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer
-            .circularstring_end(self.offset_geom_idx(idx))
+        self.geometry.circularstring_end(self.offset_geom_idx(idx))
     }
     fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .compoundcurve_begin(size, self.offset_geom_idx(idx))
     }
     fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.compoundcurve_end(self.offset_geom_idx(idx))
+        self.geometry.compoundcurve_end(self.offset_geom_idx(idx))
     }
     fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .curvepolygon_begin(size, self.offset_geom_idx(idx))
     }
     fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.curvepolygon_end(self.offset_geom_idx(idx))
+        self.geometry.curvepolygon_end(self.offset_geom_idx(idx))
     }
     fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .multicurve_begin(size, self.offset_geom_idx(idx))
     }
     fn multicurve_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.multicurve_end(self.offset_geom_idx(idx))
+        self.geometry.multicurve_end(self.offset_geom_idx(idx))
     }
     fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .multisurface_begin(size, self.offset_geom_idx(idx))
     }
     fn multisurface_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.multisurface_end(self.offset_geom_idx(idx))
+        self.geometry.multisurface_end(self.offset_geom_idx(idx))
     }
     fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .triangle_begin(tagged, size, self.offset_geom_idx(idx))
     }
     fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .triangle_end(tagged, self.offset_geom_idx(idx))
     }
     fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .polyhedralsurface_begin(size, self.offset_geom_idx(idx))
     }
     fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer
+        self.geometry
             .polyhedralsurface_end(self.offset_geom_idx(idx))
     }
     fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        self.wkt_writer.tin_begin(size, self.offset_geom_idx(idx))
+        self.geometry.tin_begin(size, self.offset_geom_idx(idx))
     }
     fn tin_end(&mut self, idx: usize) -> Result<()> {
-        self.wkt_writer.tin_end(self.offset_geom_idx(idx))
-    }
-}
-
-// Writing Wkt is delegated to the WktWriter, and it's output buffered to Vec, to be eventually
-// incorporated in the output of the CSV writer. Is there a better way?
-mod buffering_wkt_writer {
-    use crate::error::Result;
-    use crate::{wkt::WktWriter, CoordDimensions, GeomProcessor};
-
-    #[derive(Default)]
-    pub(crate) struct BufferingWktWriter {
-        buffer: Vec<u8>,
-        pub(crate) dims: CoordDimensions,
-    }
-
-    impl BufferingWktWriter {
-        pub(crate) fn new() -> Self {
-            Self::default()
-        }
-
-        pub(crate) fn clear(&mut self) {
-            self.buffer.clear()
-        }
-
-        pub(crate) fn bytes(&self) -> &[u8] {
-            &self.buffer
-        }
-
-        fn wkt_writer(&mut self) -> WktWriter<'_, Vec<u8>> {
-            WktWriter::new(&mut self.buffer)
-        }
-    }
-
-    impl GeomProcessor for BufferingWktWriter {
-        fn dimensions(&self) -> CoordDimensions {
-            self.dims
-        }
-        fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-            self.wkt_writer().xy(x, y, idx)
-        }
-        fn coordinate(
-            &mut self,
-            x: f64,
-            y: f64,
-            z: Option<f64>,
-            m: Option<f64>,
-            t: Option<f64>,
-            tm: Option<u64>,
-            idx: usize,
-        ) -> Result<()> {
-            self.wkt_writer().coordinate(x, y, z, m, t, tm, idx)
-        }
-        fn point_begin(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().point_begin(idx)
-        }
-        fn point_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().point_end(idx)
-        }
-        fn empty_point(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().empty_point(idx)
-        }
-        fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().multipoint_begin(size, idx)
-        }
-        fn multipoint_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().multipoint_end(idx)
-        }
-        fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().linestring_begin(tagged, size, idx)
-        }
-        fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-            self.wkt_writer().linestring_end(tagged, idx)
-        }
-        fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().multilinestring_begin(size, idx)
-        }
-        fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().multilinestring_end(idx)
-        }
-        fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().polygon_begin(tagged, size, idx)
-        }
-        fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-            self.wkt_writer().polygon_end(tagged, idx)
-        }
-        fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().multipolygon_begin(size, idx)
-        }
-        fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().multipolygon_end(idx)
-        }
-        fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().geometrycollection_begin(size, idx)
-        }
-        fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().geometrycollection_end(idx)
-        }
-        fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().circularstring_begin(size, idx)
-        }
-        fn circularstring_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().circularstring_end(idx)
-        }
-        fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().compoundcurve_begin(size, idx)
-        }
-        fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().compoundcurve_end(idx)
-        }
-        fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().curvepolygon_begin(size, idx)
-        }
-        fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().curvepolygon_end(idx)
-        }
-        fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().multicurve_begin(size, idx)
-        }
-        fn multicurve_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().multicurve_end(idx)
-        }
-        fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().multisurface_begin(size, idx)
-        }
-        fn multisurface_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().multisurface_end(idx)
-        }
-        fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().triangle_begin(tagged, size, idx)
-        }
-        fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
-            self.wkt_writer().triangle_end(tagged, idx)
-        }
-        fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().polyhedralsurface_begin(size, idx)
-        }
-        fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().polyhedralsurface_end(idx)
-        }
-        fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-            self.wkt_writer().tin_begin(size, idx)
-        }
-        fn tin_end(&mut self, idx: usize) -> Result<()> {
-            self.wkt_writer().tin_end(idx)
-        }
+        self.geometry.tin_end(self.offset_geom_idx(idx))
     }
 }
 
@@ -515,4 +445,93 @@ POINT(1 45),904 7th Av,05/22/2019 12:55:00 PM,F190051945,Car Fire
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    #[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+    fn geometry_formats() {
+        use super::{CsvGeometryFormat, CsvWriter};
+        use crate::GeozeroDatasource;
+
+        let input_geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "properties": { "NAME": "Foo" }, "geometry": { "type": "Point", "coordinates": [1, 2] } },
+                { "type": "Feature", "properties": { "NAME": "Bar \"Baz\"" }, "geometry": null }
+            ]
+        }"#;
+
+        let mut out: Vec<u8> = Vec::new();
+        {
+            let mut writer = CsvWriter::with_delimiter(&mut out, b'\t');
+            writer.set_geometry_column("wkb");
+            writer.set_geometry_format(CsvGeometryFormat::WkbHex);
+            crate::geojson::GeoJson(input_geojson)
+                .process(&mut writer)
+                .unwrap();
+        }
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "wkb\tNAME\n0101000000000000000000F03F0000000000000040\tFoo\n\t\"Bar \"\"Baz\"\"\"\n"
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        {
+            let mut writer = CsvWriter::new(&mut out);
+            writer.set_geometry_format(CsvGeometryFormat::GeoJson);
+            crate::geojson::GeoJson(input_geojson)
+                .process(&mut writer)
+                .unwrap();
+        }
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"geometry,NAME
+"{""type"": ""Point"", ""coordinates"": [1,2]}",Foo
+,"Bar ""Baz"""
+"#
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+    fn geometry_formats_z() {
+        use super::{CsvGeometryFormat, CsvWriter};
+        use crate::{CoordDimensions, GeozeroDatasource};
+
+        let input_geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"NAME": "Foo"}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}}]}"#;
+        let write = |format| {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = CsvWriter::new(&mut out);
+                writer.set_dims(CoordDimensions::xyz());
+                writer.set_geometry_format(format);
+                crate::geojson::GeoJson(input_geojson)
+                    .process(&mut writer)
+                    .unwrap();
+            }
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            write(CsvGeometryFormat::Wkt),
+            "geometry,NAME\nPOINT(1 2 3),Foo\n"
+        );
+        assert_eq!(
+            write(CsvGeometryFormat::WkbHex),
+            "geometry,NAME\n01E9030000000000000000F03F00000000000000400000000000000840,Foo\n"
+        );
+        assert_eq!(
+            write(CsvGeometryFormat::GeoJson),
+            "geometry,NAME\n\"{\"\"type\"\": \"\"Point\"\", \"\"coordinates\"\": [1,2,3]}\",Foo\n"
+        );
+    }
+
+    #[test]
+    fn nested_properties() {
+        let input_geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"tags": ["a", "b"], "meta": {"k": 1}}, "geometry": {"type": "Point", "coordinates": [1, 2]}}]}"#;
+        assert_eq!(
+            crate::geojson::GeoJson(input_geojson).to_csv().unwrap(),
+            r#"geometry,meta,tags
+POINT(1 2),"{""k"":1}","[""a"",""b""]"
+"#
+        );
+    }
 }
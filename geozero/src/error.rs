@@ -1,4 +1,5 @@
 //! Error and Result types.
+use std::fmt;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -35,8 +36,152 @@ pub enum GeozeroError {
     #[error("processing geometry `{0}`")]
     Geometry(String),
     // General
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(String),
+    #[error("processing cancelled")]
+    Cancelled,
+    /// Graceful end of processing requested by a processor, see
+    /// [process_until_stop](crate::GeozeroDatasource::process_until_stop)
+    #[error("processing stopped")]
+    Stop,
     #[error("I/O error")]
     IoError(#[from] std::io::Error),
+    // Error with location in input
+    #[error("{source} ({context})")]
+    Context {
+        context: ErrorContext,
+        source: Box<GeozeroError>,
+    },
+}
+
+/// Location of an error in the processed input.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ErrorContext {
+    /// Number of bytes read when the error occurred (binary readers)
+    pub offset: Option<u64>,
+    /// Line and column, starting at 1 (text readers)
+    pub position: Option<(usize, usize)>,
+    /// Feature index
+    pub feature: Option<u64>,
+    /// Type of top-level geometry
+    pub geometry_type: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(offset) = self.offset {
+            parts.push(format!("at byte {}", offset));
+        }
+        if let Some((line, column)) = self.position {
+            parts.push(format!("at line {} column {}", line, column));
+        }
+        if let Some(feature) = self.feature {
+            parts.push(format!("in feature {}", feature));
+        }
+        if let Some(geometry_type) = &self.geometry_type {
+            parts.push(format!("in {}", geometry_type));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl GeozeroError {
+    /// Location of the error, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            GeozeroError::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+    /// Error without location.
+    pub fn kind(&self) -> &GeozeroError {
+        match self {
+            GeozeroError::Context { source, .. } => source.kind(),
+            other => other,
+        }
+    }
+    #[cfg(any(
+        feature = "with-cityjson",
+        feature = "with-geojson",
+        feature = "with-georss",
+        feature = "with-gpkg",
+        feature = "with-postgis-copy",
+        feature = "with-sql",
+        feature = "with-wkb"
+    ))]
+    fn update_context(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        match self {
+            GeozeroError::Context {
+                mut context,
+                source,
+            } => {
+                update(&mut context);
+                GeozeroError::Context { context, source }
+            }
+            other => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                GeozeroError::Context {
+                    context,
+                    source: Box::new(other),
+                }
+            }
+        }
+    }
+    /// Add byte offset, if not already set.
+    #[cfg(feature = "with-wkb")]
+    pub(crate) fn at_offset(self, offset: u64) -> Self {
+        self.update_context(|context| {
+            context.offset.get_or_insert(offset);
+        })
+    }
+    /// Add line and column, if not already set.
+    #[cfg(feature = "with-geojson")]
+    pub(crate) fn at_position(self, line: usize, column: usize) -> Self {
+        self.update_context(|context| {
+            context.position.get_or_insert((line, column));
+        })
+    }
+    /// Add feature index, if not already set.
+    #[cfg(any(
+        feature = "with-cityjson",
+        feature = "with-geojson",
+        feature = "with-georss",
+        feature = "with-gpkg",
+        feature = "with-postgis-copy",
+        feature = "with-sql"
+    ))]
+    pub(crate) fn in_feature(self, idx: u64) -> Self {
+        self.update_context(|context| {
+            context.feature.get_or_insert(idx);
+        })
+    }
+    /// Add geometry type, if not already set.
+    #[cfg(feature = "with-wkb")]
+    pub(crate) fn in_geometry(self, geometry_type: &str) -> Self {
+        self.update_context(|context| {
+            context
+                .geometry_type
+                .get_or_insert_with(|| geometry_type.to_string());
+        })
+    }
 }
 
 pub type Result<T> = std::result::Result<T, GeozeroError>;
+
+#[test]
+#[cfg(all(feature = "with-wkb", feature = "with-geojson"))]
+fn context() {
+    let err = GeozeroError::GeometryFormat
+        .at_offset(12)
+        .in_feature(3)
+        .in_feature(4)
+        .in_geometry("Polygon");
+    assert_eq!(
+        err.to_string(),
+        "geometry format (at byte 12, in feature 3, in Polygon)"
+    );
+    assert!(matches!(err.kind(), GeozeroError::GeometryFormat));
+    assert_eq!(err.context().unwrap().feature, Some(3));
+}
@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::geometry_processor::GeomProcessor;
 use crate::property_processor::PropertyProcessor;
+use crate::schema::DatasetMetadata;
 
 /// Feature processing trait
 #[allow(unused_variables)]
@@ -9,6 +10,18 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
         Ok(())
     }
+    /// SRID of dataset geometries
+    ///
+    /// Called after `dataset_begin` by readers knowing the dataset CRS.
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        Ok(())
+    }
+    /// Descriptive metadata of dataset
+    ///
+    /// Called after `dataset_begin` by readers of formats with dataset headers.
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        Ok(())
+    }
     /// End of dataset processing
     fn dataset_end(&mut self) -> Result<()> {
         Ok(())
@@ -37,4 +50,51 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn geometry_end(&mut self) -> Result<()> {
         Ok(())
     }
+    /// Feature without geometry
+    ///
+    /// Called instead of `geometry_begin`/`geometry_end` for features with a
+    /// null geometry (e.g. GeoJSON `"geometry": null` or NULL GeoPackage geometries).
+    fn null_geometry(&mut self) -> Result<()> {
+        Ok(())
+    }
+    /// Processing of additional geometry columns
+    ///
+    /// Readers of datasets with more than one geometry column per feature (e.g. PostGIS
+    /// queries or GeoParquet files) emit only the primary geometry, unless the processor
+    /// returns `true`.
+    fn multiple_geometries(&self) -> bool {
+        false
+    }
+    /// Begin of an additional feature geometry
+    ///
+    /// Called for each further geometry column `name` after the primary geometry,
+    /// with `idx` starting at 1.
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        Ok(())
+    }
+    /// End of an additional feature geometry
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Non-standard member of dataset or feature with value as JSON text
+    ///
+    /// Called after `dataset_begin` for dataset members and after `feature_begin`
+    /// for feature members (e.g. GeoJSON foreign members or feature `id`).
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Begin of a batch of at most `size` features
+    ///
+    /// Called before `feature_begin` of the first feature in the batch, e.g. by
+    /// [Batcher](crate::processing::Batcher). Database writers can start a transaction.
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        Ok(())
+    }
+    /// End of a batch of features
+    ///
+    /// Called after `feature_end` of the last feature in the batch.
+    /// Database writers can flush buffered rows and commit.
+    fn batch_end(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
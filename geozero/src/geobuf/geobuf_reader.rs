@@ -0,0 +1,327 @@
+use crate::error::{GeozeroError, Result};
+use crate::geobuf::geobuf::data::{self, geometry::Type, value::ValueType, DataType};
+use crate::geobuf::geobuf::Data;
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    PropertyProcessor,
+};
+use prost::Message;
+use std::io::Read;
+
+/// Geobuf encoded data.
+pub struct Geobuf<'a>(pub &'a [u8]);
+
+impl GeozeroGeometry for Geobuf<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_geobuf_geom(self.0, processor)
+    }
+}
+
+impl GeozeroDatasource for Geobuf<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_geobuf(self.0, processor)
+    }
+}
+
+/// Geobuf Reader.
+pub struct GeobufReader<'a, R: Read>(pub &'a mut R);
+
+impl<'a, R: Read> GeozeroDatasource for GeobufReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_geobuf(&mut self.0, processor)
+    }
+}
+
+fn decode<R: Read>(mut reader: R) -> Result<Data> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    Data::decode(buf.as_slice()).map_err(|e| GeozeroError::Dataset(e.to_string()))
+}
+
+/// Read and process Geobuf features.
+pub fn read_geobuf<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let data = decode(reader)?;
+    let decoder = Decoder::new(&data);
+    processor.dataset_begin(None)?;
+    match &data.data_type {
+        Some(DataType::FeatureCollection(collection)) => {
+            for (idx, feature) in collection.features.iter().enumerate() {
+                decoder.process_feature(feature, idx as u64, processor)?;
+            }
+        }
+        Some(DataType::Feature(feature)) => decoder.process_feature(feature, 0, processor)?,
+        Some(DataType::Geometry(geometry)) => {
+            processor.feature_begin(0)?;
+            processor.geometry_begin()?;
+            decoder.process_geometry(geometry, 0, processor)?;
+            processor.geometry_end()?;
+            processor.feature_end(0)?;
+        }
+        None => {}
+    }
+    processor.dataset_end()
+}
+
+/// Read and process Geobuf geometries.
+///
+/// Geometries of a feature collection are processed as geometry collection.
+pub fn read_geobuf_geom<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let data = decode(reader)?;
+    let decoder = Decoder::new(&data);
+    match &data.data_type {
+        Some(DataType::FeatureCollection(collection)) => {
+            processor.geometrycollection_begin(collection.features.len(), 0)?;
+            for (idx, feature) in collection.features.iter().enumerate() {
+                decoder.process_geometry(&feature.geometry, idx, processor)?;
+            }
+            processor.geometrycollection_end(0)
+        }
+        Some(DataType::Feature(feature)) => {
+            decoder.process_geometry(&feature.geometry, 0, processor)
+        }
+        Some(DataType::Geometry(geometry)) => decoder.process_geometry(geometry, 0, processor),
+        None => Ok(()),
+    }
+}
+
+struct Decoder<'a> {
+    keys: &'a [String],
+    dims: usize,
+    e: f64,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a Data) -> Self {
+        Decoder {
+            keys: &data.keys,
+            dims: data.dimensions().max(2) as usize,
+            e: 10f64.powi(data.precision() as i32),
+        }
+    }
+
+    fn process_feature<P: FeatureProcessor>(
+        &self,
+        feature: &data::Feature,
+        idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.feature_begin(idx)?;
+        if !feature.properties.is_empty() {
+            processor.properties_begin()?;
+            self.process_properties(feature, processor)?;
+            processor.properties_end()?;
+        }
+        processor.geometry_begin()?;
+        self.process_geometry(&feature.geometry, 0, processor)?;
+        processor.geometry_end()?;
+        processor.feature_end(idx)
+    }
+
+    fn process_properties<P: PropertyProcessor>(
+        &self,
+        feature: &data::Feature,
+        processor: &mut P,
+    ) -> Result<()> {
+        for (i, pair) in feature.properties.chunks(2).enumerate() {
+            let (key, value) = match pair {
+                [key, value] => (
+                    self.keys.get(*key as usize),
+                    feature.values.get(*value as usize),
+                ),
+                _ => (None, None),
+            };
+            let (key, value) = match (key, value.and_then(|v| v.value_type.as_ref())) {
+                (Some(key), Some(value)) => (key, value),
+                _ => {
+                    return Err(GeozeroError::Property(
+                        "Invalid Geobuf property index".to_string(),
+                    ))
+                }
+            };
+            let finish = match value {
+                ValueType::StringValue(v) => processor.property(i, key, &ColumnValue::String(v))?,
+                ValueType::DoubleValue(v) => {
+                    processor.property(i, key, &ColumnValue::Double(*v))?
+                }
+                ValueType::PosIntValue(v) => processor.property(i, key, &ColumnValue::ULong(*v))?,
+                ValueType::NegIntValue(v) => {
+                    processor.property(i, key, &ColumnValue::Long(-(*v as i64)))?
+                }
+                ValueType::BoolValue(v) => processor.property(i, key, &ColumnValue::Bool(*v))?,
+                ValueType::JsonValue(v) => processor.property(i, key, &ColumnValue::Json(v))?,
+            };
+            if finish {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn process_geometry<P: GeomProcessor>(
+        &self,
+        geometry: &data::Geometry,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geom_type = Type::from_i32(geometry.r#type).ok_or_else(|| {
+            GeozeroError::Geometry(format!("Unknown Geobuf geometry type {}", geometry.r#type))
+        })?;
+        let coords = &geometry.coords;
+        match geom_type {
+            Type::Point => {
+                processor.point_begin(idx)?;
+                self.process_coord(coords, 0, processor)?;
+                processor.point_end(idx)
+            }
+            Type::Multipoint => {
+                let line = self.line(coords, false);
+                processor.multipoint_begin(line.len() / self.dims, idx)?;
+                self.process_coords(&line, processor)?;
+                processor.multipoint_end(idx)
+            }
+            Type::Linestring => {
+                let line = self.line(coords, false);
+                self.process_linestring(&line, true, idx, processor)
+            }
+            Type::Multilinestring => {
+                let lines = self.lines(&geometry.lengths, coords, false)?;
+                processor.multilinestring_begin(lines.len(), idx)?;
+                for (idxl, line) in lines.iter().enumerate() {
+                    self.process_linestring(line, false, idxl, processor)?;
+                }
+                processor.multilinestring_end(idx)
+            }
+            Type::Polygon => {
+                let rings = self.lines(&geometry.lengths, coords, true)?;
+                self.process_polygon(&rings, true, idx, processor)
+            }
+            Type::Multipolygon => {
+                let polygons = self.polygons(&geometry.lengths, coords)?;
+                processor.multipolygon_begin(polygons.len(), idx)?;
+                for (idxp, rings) in polygons.iter().enumerate() {
+                    self.process_polygon(rings, false, idxp, processor)?;
+                }
+                processor.multipolygon_end(idx)
+            }
+            Type::Geometrycollection => {
+                processor.geometrycollection_begin(geometry.geometries.len(), idx)?;
+                for (idxg, geometry) in geometry.geometries.iter().enumerate() {
+                    self.process_geometry(geometry, idxg, processor)?;
+                }
+                processor.geometrycollection_end(idx)
+            }
+        }
+    }
+
+    /// Delta-decode coordinates, repeating the first position for closed rings
+    fn line(&self, coords: &[i64], closed: bool) -> Vec<i64> {
+        let mut sum = vec![0; self.dims];
+        let mut line = Vec::with_capacity(coords.len() + self.dims);
+        for pos in coords.chunks_exact(self.dims) {
+            for (j, v) in pos.iter().enumerate() {
+                sum[j] += v;
+                line.push(sum[j]);
+            }
+        }
+        if closed && line.len() >= self.dims {
+            line.extend_from_within(..self.dims);
+        }
+        line
+    }
+
+    fn lines(&self, lengths: &[u32], coords: &[i64], closed: bool) -> Result<Vec<Vec<i64>>> {
+        if lengths.is_empty() {
+            return Ok(vec![self.line(coords, closed)]);
+        }
+        let mut start = 0;
+        lengths
+            .iter()
+            .map(|len| {
+                let end = start + *len as usize * self.dims;
+                let line = coords.get(start..end).ok_or_else(invalid_lengths)?;
+                start = end;
+                Ok(self.line(line, closed))
+            })
+            .collect()
+    }
+
+    /// Lengths are encoded as number of polygons, followed by number of rings
+    /// and ring lengths of each polygon.
+    fn polygons(&self, lengths: &[u32], coords: &[i64]) -> Result<Vec<Vec<Vec<i64>>>> {
+        if lengths.is_empty() {
+            return Ok(vec![vec![self.line(coords, true)]]);
+        }
+        let mut lengths = lengths.iter().map(|len| *len as usize);
+        let num_polygons = lengths.next().ok_or_else(invalid_lengths)?;
+        let mut start = 0;
+        let mut polygons = Vec::with_capacity(num_polygons);
+        for _ in 0..num_polygons {
+            let num_rings = lengths.next().ok_or_else(invalid_lengths)?;
+            let mut rings = Vec::with_capacity(num_rings);
+            for _ in 0..num_rings {
+                let end = start + lengths.next().ok_or_else(invalid_lengths)? * self.dims;
+                let ring = coords.get(start..end).ok_or_else(invalid_lengths)?;
+                rings.push(self.line(ring, true));
+                start = end;
+            }
+            polygons.push(rings);
+        }
+        Ok(polygons)
+    }
+
+    fn process_coord<P: GeomProcessor>(
+        &self,
+        pos: &[i64],
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        if pos.len() < 2 {
+            return Err(GeozeroError::Coord);
+        }
+        let x = pos[0] as f64 / self.e;
+        let y = pos[1] as f64 / self.e;
+        if processor.multi_dim() {
+            let z = pos.get(2).map(|z| *z as f64 / self.e);
+            processor.coordinate(x, y, z, None, None, None, idx)
+        } else {
+            processor.xy(x, y, idx)
+        }
+    }
+
+    fn process_coords<P: GeomProcessor>(&self, line: &[i64], processor: &mut P) -> Result<()> {
+        for (idxc, pos) in line.chunks_exact(self.dims).enumerate() {
+            self.process_coord(pos, idxc, processor)?;
+        }
+        Ok(())
+    }
+
+    fn process_linestring<P: GeomProcessor>(
+        &self,
+        line: &[i64],
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.linestring_begin(tagged, line.len() / self.dims, idx)?;
+        self.process_coords(line, processor)?;
+        processor.linestring_end(tagged, idx)
+    }
+
+    fn process_polygon<P: GeomProcessor>(
+        &self,
+        rings: &[Vec<i64>],
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.polygon_begin(tagged, rings.len(), idx)?;
+        for (idxl, ring) in rings.iter().enumerate() {
+            self.process_linestring(ring, false, idxl, processor)?;
+        }
+        processor.polygon_end(tagged, idx)
+    }
+}
+
+fn invalid_lengths() -> GeozeroError {
+    GeozeroError::Geometry("Geobuf lengths don't match coordinates".to_string())
+}
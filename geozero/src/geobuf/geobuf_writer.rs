@@ -0,0 +1,404 @@
+use crate::error::{GeozeroError, Result};
+use crate::geobuf::geobuf::data::{self, geometry::Type, value::ValueType, DataType};
+use crate::geobuf::geobuf::Data;
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use prost::Message;
+use std::io::Write;
+
+/// Geometry under construction
+struct GeometryBuilder {
+    geometry: data::Geometry,
+    /// Line or ring lengths of MultiLineString and Polygon
+    lengths: Vec<u32>,
+    /// Ring lengths of MultiPolygon parts
+    polygons: Vec<Vec<u32>>,
+}
+
+impl GeometryBuilder {
+    fn new(geom_type: Type) -> Self {
+        GeometryBuilder {
+            geometry: data::Geometry {
+                r#type: geom_type as i32,
+                ..Default::default()
+            },
+            lengths: Vec::new(),
+            polygons: Vec::new(),
+        }
+    }
+
+    fn geom_type(&self) -> Option<Type> {
+        Type::from_i32(self.geometry.r#type)
+    }
+
+    fn finish(mut self) -> data::Geometry {
+        match self.geom_type() {
+            Some(Type::Multilinestring) | Some(Type::Polygon) if self.lengths.len() != 1 => {
+                self.geometry.lengths = self.lengths;
+            }
+            Some(Type::Multipolygon) if self.polygons.len() != 1 || self.polygons[0].len() != 1 => {
+                let mut lengths = vec![self.polygons.len() as u32];
+                for rings in self.polygons {
+                    lengths.push(rings.len() as u32);
+                    lengths.extend(rings);
+                }
+                self.geometry.lengths = lengths;
+            }
+            _ => {}
+        }
+        self.geometry
+    }
+}
+
+/// Geobuf writer.
+///
+/// Features are buffered and encoded at the end of the dataset.
+/// Coordinates are stored as integers with `precision` decimal digits.
+pub struct GeobufWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// Number of digits after the decimal point
+    pub precision: u32,
+    out: &'a mut W,
+    keys: Vec<String>,
+    features: Vec<data::Feature>,
+    feature: Option<data::Feature>,
+    stack: Vec<GeometryBuilder>,
+    line: Vec<Vec<f64>>,
+    geometry: Option<data::Geometry>,
+}
+
+impl<'a, W: Write> GeobufWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> GeobufWriter<'a, W> {
+        GeobufWriter {
+            dims: CoordDimensions::default(),
+            precision: 6,
+            out,
+            keys: Vec::new(),
+            features: Vec::new(),
+            feature: None,
+            stack: Vec::new(),
+            line: Vec::new(),
+            geometry: None,
+        }
+    }
+
+    fn num_dims(&self) -> usize {
+        if self.dims.z {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn begin(&mut self, geom_type: Type) {
+        self.stack.push(GeometryBuilder::new(geom_type));
+    }
+
+    fn end(&mut self) -> Result<()> {
+        let builder = self
+            .stack
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("Unbalanced geometry end".to_string()))?;
+        let geometry = builder.finish();
+        match self.stack.last_mut() {
+            Some(parent) => parent.geometry.geometries.push(geometry),
+            None => self.geometry = Some(geometry),
+        }
+        Ok(())
+    }
+
+    fn current(&mut self) -> Result<&mut GeometryBuilder> {
+        self.stack
+            .last_mut()
+            .ok_or_else(|| GeozeroError::Geometry("Missing geometry begin".to_string()))
+    }
+
+    /// Delta-encode buffered positions into current geometry
+    ///
+    /// The last position of closed rings is omitted.
+    fn flush_line(&mut self, closed: bool) -> Result<u32> {
+        let mut line = std::mem::take(&mut self.line);
+        if closed && line.len() > 1 && line.first() == line.last() {
+            line.pop();
+        }
+        let e = 10f64.powi(self.precision as i32);
+        let mut sum = vec![0i64; self.num_dims()];
+        let builder = self.current()?;
+        for pos in &line {
+            for (j, v) in pos.iter().enumerate() {
+                let n = (v * e).round() as i64 - sum[j];
+                builder.geometry.coords.push(n);
+                sum[j] += n;
+            }
+        }
+        Ok(line.len() as u32)
+    }
+
+    fn encode(&mut self, data_type: DataType) -> Result<()> {
+        let data = Data {
+            keys: std::mem::take(&mut self.keys),
+            dimensions: Some(self.num_dims() as u32),
+            precision: Some(self.precision),
+            data_type: Some(data_type),
+        };
+        self.out.write_all(&data.encode_to_vec())?;
+        Ok(())
+    }
+}
+
+impl<W: Write> FeatureProcessor for GeobufWriter<'_, W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        if self.features.is_empty() {
+            if let Some(geometry) = self.geometry.take() {
+                return self.encode(DataType::Geometry(geometry));
+            }
+        }
+        let collection = data::FeatureCollection {
+            features: std::mem::take(&mut self.features),
+            ..Default::default()
+        };
+        self.encode(DataType::FeatureCollection(collection))
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Some(data::Feature::default());
+        self.geometry = None;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if let Some(mut feature) = self.feature.take() {
+            feature.geometry = self
+                .geometry
+                .take()
+                .unwrap_or_else(|| GeometryBuilder::new(Type::Geometrycollection).finish());
+            self.features.push(feature);
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for GeobufWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let value = match value {
+            ColumnValue::Byte(v) => int_value(*v as i64),
+            ColumnValue::UByte(v) => ValueType::PosIntValue(*v as u64),
+            ColumnValue::Bool(v) => ValueType::BoolValue(*v),
+            ColumnValue::Short(v) => int_value(*v as i64),
+            ColumnValue::UShort(v) => ValueType::PosIntValue(*v as u64),
+            ColumnValue::Int(v) => int_value(*v as i64),
+            ColumnValue::UInt(v) => ValueType::PosIntValue(*v as u64),
+            ColumnValue::Long(v) => int_value(*v),
+            ColumnValue::ULong(v) => ValueType::PosIntValue(*v),
+            ColumnValue::Float(v) => ValueType::DoubleValue(*v as f64),
+            ColumnValue::Double(v) => ValueType::DoubleValue(*v),
+            ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+                ValueType::StringValue(v.to_string())
+            }
+            ColumnValue::Json(v) => ValueType::JsonValue(v.to_string()),
+            ColumnValue::Binary(_) => return Ok(false),
+        };
+        let key = match self.keys.iter().position(|key| key == name) {
+            Some(key) => key,
+            None => {
+                self.keys.push(name.to_string());
+                self.keys.len() - 1
+            }
+        };
+        if let Some(feature) = self.feature.as_mut() {
+            feature.properties.push(key as u32);
+            feature.properties.push(feature.values.len() as u32);
+            feature.values.push(data::Value {
+                value_type: Some(value),
+            });
+        }
+        Ok(false)
+    }
+}
+
+fn int_value(v: i64) -> ValueType {
+    if v < 0 {
+        ValueType::NegIntValue(v.unsigned_abs())
+    } else {
+        ValueType::PosIntValue(v as u64)
+    }
+}
+
+impl<W: Write> GeomProcessor for GeobufWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        let mut pos = vec![x, y];
+        pos.resize(self.num_dims(), 0.0);
+        self.line.push(pos);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        let mut pos = vec![x, y];
+        if self.dims.z {
+            pos.push(z.unwrap_or(0.0));
+        }
+        self.line.push(pos);
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Type::Point);
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.flush_line(false)?;
+        self.end()
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Type::Multipoint);
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.flush_line(false)?;
+        self.end()
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        match self.stack.last().and_then(|builder| builder.geom_type()) {
+            Some(Type::Multilinestring) | Some(Type::Polygon) | Some(Type::Multipolygon) => {}
+            _ => self.begin(Type::Linestring),
+        }
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        match self.current()?.geom_type() {
+            Some(Type::Multilinestring) => {
+                let len = self.flush_line(false)?;
+                self.current()?.lengths.push(len);
+            }
+            Some(Type::Polygon) => {
+                let len = self.flush_line(true)?;
+                self.current()?.lengths.push(len);
+            }
+            Some(Type::Multipolygon) => {
+                let len = self.flush_line(true)?;
+                if let Some(rings) = self.current()?.polygons.last_mut() {
+                    rings.push(len);
+                }
+            }
+            _ => {
+                self.flush_line(false)?;
+                self.end()?;
+            }
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Type::Multilinestring);
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        match self.stack.last_mut() {
+            Some(builder) if builder.geom_type() == Some(Type::Multipolygon) => {
+                builder.polygons.push(Vec::new())
+            }
+            _ => self.begin(Type::Polygon),
+        }
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if self.current()?.geom_type() == Some(Type::Polygon) {
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Type::Multipolygon);
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Type::Geometrycollection);
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geobuf::Geobuf;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::wkt::WktStr;
+    use crate::{GeozeroDatasource, ToGeobuf, ToWkt};
+
+    fn roundtrip(wkt: &str) -> Result<String> {
+        let geobuf = WktStr(wkt).to_geobuf()?;
+        Geobuf(&geobuf).to_wkt()
+    }
+
+    #[test]
+    fn geometries() -> Result<()> {
+        for wkt in [
+            "POINT(1.5 -2)",
+            "MULTIPOINT(1 2,3 4)",
+            "LINESTRING(1 2,3 4,-5 6.25)",
+            "MULTILINESTRING((1 2,3 4),(5 6,7 8))",
+            "POLYGON((0 0,10 0,10 10,0 0),(2 2,4 2,4 4,2 2))",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5),(5.2 5.2,5.4 5.2,5.4 5.4,5.2 5.2)))",
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(3 4,5 6))",
+        ] {
+            assert_eq!(roundtrip(wkt)?, wkt);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn encoding() -> Result<()> {
+        // coordinates are delta encoded integers
+        let geobuf = WktStr("LINESTRING(1 2,3 4)").to_geobuf()?;
+        let data = Data::decode(geobuf.as_slice()).unwrap();
+        assert_eq!(data.precision(), 6);
+        match data.data_type {
+            Some(DataType::Geometry(geometry)) => {
+                assert_eq!(geometry.r#type, Type::Linestring as i32);
+                assert_eq!(geometry.coords, vec![1000000, 2000000, 2000000, 2000000]);
+            }
+            _ => panic!("geometry expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "a", "count": -3}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": {"name": "b", "value": 1.5}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+            ]}"#,
+        );
+        let mut out: Vec<u8> = Vec::new();
+        geojson.process(&mut GeobufWriter::new(&mut out))?;
+
+        let mut json: Vec<u8> = Vec::new();
+        Geobuf(&out).process(&mut GeoJsonWriter::new(&mut json))?;
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"count": -3, "name": "a"}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+{"type": "Feature", "properties": {"name": "b", "value": 1.5}, "geometry": {"type": "Point", "coordinates": [3,4]}}]}"#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Geobuf conversions.
+#[rustfmt::skip]
+#[allow(clippy::module_inception, clippy::enum_variant_names)]
+mod geobuf;
+mod geobuf_reader;
+mod geobuf_writer;
+
+pub use geobuf_reader::*;
+pub use geobuf_writer::*;
+
+pub(crate) mod conversion {
+    use super::geobuf_writer::*;
+    use crate::error::Result;
+    use crate::{FeatureProcessor, GeozeroDatasource, GeozeroGeometry};
+
+    /// Convert to Geobuf.
+    pub trait ToGeobuf {
+        /// Convert to Geobuf encoded geometry.
+        fn to_geobuf(&self) -> Result<Vec<u8>>;
+    }
+
+    impl<T: GeozeroGeometry> ToGeobuf for T {
+        fn to_geobuf(&self) -> Result<Vec<u8>> {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = GeobufWriter::new(&mut out);
+                writer.dims = self.dims();
+                self.process_geom(&mut writer)?;
+                writer.dataset_end()?;
+            }
+            Ok(out)
+        }
+    }
+
+    /// Consume features as Geobuf.
+    pub trait ProcessToGeobuf {
+        /// Consume features as Geobuf encoded feature collection.
+        fn to_geobuf(&mut self) -> Result<Vec<u8>>;
+    }
+
+    impl<T: GeozeroDatasource> ProcessToGeobuf for T {
+        fn to_geobuf(&mut self) -> Result<Vec<u8>> {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = GeobufWriter::new(&mut out);
+                self.process(&mut writer)?;
+            }
+            Ok(out)
+        }
+    }
+}
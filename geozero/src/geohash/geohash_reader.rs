@@ -0,0 +1,101 @@
+use super::BASE32;
+use crate::error::{GeozeroError, Result};
+use crate::{GeomProcessor, GeozeroGeometry};
+
+/// Geohash cell, processed as bounding box Polygon or center Point.
+pub struct Geohash<'a> {
+    hash: &'a str,
+    center: bool,
+}
+
+impl<'a> Geohash<'a> {
+    /// Geohash cell as bounding box Polygon.
+    pub fn new(hash: &'a str) -> Self {
+        Geohash {
+            hash,
+            center: false,
+        }
+    }
+
+    /// Geohash cell as center Point.
+    pub fn center(hash: &'a str) -> Self {
+        Geohash { hash, center: true }
+    }
+}
+
+impl GeozeroGeometry for Geohash<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        let [minx, miny, maxx, maxy] = decode_bbox(self.hash)?;
+        if self.center {
+            processor.point_begin(0)?;
+            processor.xy((minx + maxx) / 2.0, (miny + maxy) / 2.0, 0)?;
+            processor.point_end(0)
+        } else {
+            processor.polygon_begin(true, 1, 0)?;
+            processor.linestring_begin(false, 5, 0)?;
+            for (idx, (x, y)) in [
+                (minx, miny),
+                (maxx, miny),
+                (maxx, maxy),
+                (minx, maxy),
+                (minx, miny),
+            ]
+            .iter()
+            .enumerate()
+            {
+                processor.xy(*x, *y, idx)?;
+            }
+            processor.linestring_end(false, 0)?;
+            processor.polygon_end(true, 0)
+        }
+    }
+}
+
+/// Decode geohash into bounding box `[minx, miny, maxx, maxy]`.
+pub fn decode_bbox(hash: &str) -> Result<[f64; 4]> {
+    if hash.is_empty() {
+        return Err(GeozeroError::Geometry("Empty geohash".to_string()));
+    }
+    let mut lon = [-180.0, 180.0];
+    let mut lat = [-90.0, 90.0];
+    let mut even = true;
+    for c in hash.bytes() {
+        let value = BASE32
+            .iter()
+            .position(|b| *b == c.to_ascii_lowercase())
+            .ok_or_else(|| GeozeroError::Geometry(format!("Invalid geohash `{}`", hash)))?;
+        for bit in (0..5).rev() {
+            let range = if even { &mut lon } else { &mut lat };
+            let mid = (range[0] + range[1]) / 2.0;
+            if (value >> bit) & 1 == 1 {
+                range[0] = mid;
+            } else {
+                range[1] = mid;
+            }
+            even = !even;
+        }
+    }
+    Ok([lon[0], lat[0], lon[1], lat[1]])
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::ToWkt;
+
+    #[test]
+    fn bbox() -> Result<()> {
+        assert_eq!(
+            Geohash::new("ezs42").to_wkt()?,
+            "POLYGON((-5.625 42.5830078125,-5.5810546875 42.5830078125,-5.5810546875 42.626953125,-5.625 42.626953125,-5.625 42.5830078125))"
+        );
+        assert_eq!(
+            Geohash::center("ezs42").to_wkt()?,
+            "POINT(-5.60302734375 42.60498046875)"
+        );
+        assert!(Geohash::new("ezs4a").to_wkt().is_err());
+        assert!(Geohash::new("").to_wkt().is_err());
+        Ok(())
+    }
+}
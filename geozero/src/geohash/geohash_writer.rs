@@ -0,0 +1,120 @@
+use super::BASE32;
+use crate::error::{GeozeroError, Result};
+use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// Geohash writer.
+///
+/// Writes the geohash of each point, one per line for features.
+pub struct GeohashWriter<'a, W: Write> {
+    /// Number of geohash characters
+    pub len: usize,
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> GeohashWriter<'a, W> {
+    pub fn new(out: &'a mut W, len: usize) -> GeohashWriter<'a, W> {
+        GeohashWriter { len, out }
+    }
+
+    fn unsupported() -> GeozeroError {
+        GeozeroError::Geometry("Geohash supports only Point geometries".to_string())
+    }
+}
+
+/// Encode position as geohash with `len` characters.
+pub fn encode_geohash(x: f64, y: f64, len: usize) -> Result<String> {
+    if !(-180.0..=180.0).contains(&x) || !(-90.0..=90.0).contains(&y) {
+        return Err(GeozeroError::Geometry(format!(
+            "Invalid geohash position ({} {})",
+            x, y
+        )));
+    }
+    let mut lon = [-180.0, 180.0];
+    let mut lat = [-90.0, 90.0];
+    let mut even = true;
+    let mut hash = String::with_capacity(len);
+    while hash.len() < len {
+        let mut value = 0;
+        for _ in 0..5 {
+            let (range, v) = if even { (&mut lon, x) } else { (&mut lat, y) };
+            let mid = (range[0] + range[1]) / 2.0;
+            value <<= 1;
+            if v >= mid {
+                value |= 1;
+                range[0] = mid;
+            } else {
+                range[1] = mid;
+            }
+            even = !even;
+        }
+        hash.push(BASE32[value] as char);
+    }
+    Ok(hash)
+}
+
+impl<W: Write> FeatureProcessor for GeohashWriter<'_, W> {
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.out.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for GeohashWriter<'_, W> {}
+
+impl<W: Write> GeomProcessor for GeohashWriter<'_, W> {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        let hash = encode_geohash(x, y, self.len)?;
+        self.out.write_all(hash.as_bytes())?;
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode() -> Result<()> {
+        assert_eq!(encode_geohash(10.40744, 57.64911, 11)?, "u4pruydqqvj");
+        assert_eq!(encode_geohash(-5.6, 42.6, 5)?, "ezs42");
+        assert!(encode_geohash(200.0, 0.0, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [10.40744, 57.64911]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [-5.6, 42.6]}}
+            ]}"#,
+        );
+        let mut out: Vec<u8> = Vec::new();
+        geojson.process(&mut GeohashWriter::new(&mut out, 5))?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), "u4pru\nezs42\n");
+        Ok(())
+    }
+}
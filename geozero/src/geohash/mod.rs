@@ -0,0 +1,31 @@
+//! Geohash conversions.
+mod geohash_reader;
+mod geohash_writer;
+
+pub use geohash_reader::*;
+pub use geohash_writer::*;
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+pub(crate) mod conversion {
+    use super::geohash_writer::*;
+    use crate::error::Result;
+    use crate::GeozeroGeometry;
+
+    /// Convert to geohash.
+    pub trait ToGeohash {
+        /// Convert Point to geohash with given number of characters.
+        fn to_geohash(&self, len: usize) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToGeohash for T {
+        fn to_geohash(&self, len: usize) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = GeohashWriter::new(&mut out, len);
+            self.process_geom(&mut writer)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
@@ -1,13 +1,15 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
+use crate::property_processor::process_json_property;
 use crate::{
-    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
-    PropertyProcessor,
+    FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry, PropertyProcessor,
+    RewindableDatasource,
 };
+use geojson::feature::Id;
 use geojson::{Feature, FeatureReader};
 use geojson::{GeoJson as GeoGeoJson, Geometry, Value};
 use serde_json::map::Map;
 use serde_json::value::Value as JsonValue;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 
 /// GeoJSON String.
 #[derive(Debug)]
@@ -25,6 +27,12 @@ impl GeozeroDatasource for GeoJsonString {
     }
 }
 
+impl RewindableDatasource for GeoJsonString {
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// GeoJSON String slice.
 pub struct GeoJson<'a>(pub &'a str);
 
@@ -40,6 +48,12 @@ impl GeozeroDatasource for GeoJson<'_> {
     }
 }
 
+impl RewindableDatasource for GeoJson<'_> {
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// GeoJSON Reader.
 pub struct GeoJsonReader<'a, R: Read>(pub &'a mut R);
 
@@ -49,6 +63,13 @@ impl<'a, R: Read> GeozeroDatasource for GeoJsonReader<'a, R> {
     }
 }
 
+impl<'a, R: Read + Seek> RewindableDatasource for GeoJsonReader<'a, R> {
+    fn rewind(&mut self) -> Result<()> {
+        self.0.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+}
+
 /// Read and process GeoJSON.
 pub fn read_geojson<R: Read, P: FeatureProcessor>(mut reader: R, processor: &mut P) -> Result<()> {
     let mut geojson_str = String::new();
@@ -60,7 +81,8 @@ pub fn read_geojson<R: Read, P: FeatureProcessor>(mut reader: R, processor: &mut
 pub fn read_geojson_fc<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
     let mut idx = 0;
     for feature in FeatureReader::from_reader(reader).features() {
-        process_geojson_feature(&feature?, idx, processor)?;
+        let feature = feature.map_err(|e| GeozeroError::from(e).in_feature(idx as u64))?;
+        process_geojson_feature(&feature, idx, processor)?;
         idx += 1;
     }
     Ok(())
@@ -81,18 +103,43 @@ pub fn read_geojson_geom<R: Read, P: GeomProcessor>(
 fn process_geojson<P: FeatureProcessor>(gj: &GeoGeoJson, processor: &mut P) -> Result<()> {
     match *gj {
         GeoGeoJson::FeatureCollection(ref collection) => {
-            processor.dataset_begin(None)?;
+            let name = collection
+                .foreign_members
+                .as_ref()
+                .and_then(|members| members.get("name"))
+                .and_then(JsonValue::as_str);
+            processor.dataset_begin(name)?;
+            let srid = collection
+                .foreign_members
+                .as_ref()
+                .and_then(|members| members.get("crs"))
+                .and_then(legacy_crs_srid);
+            if srid.is_some() {
+                processor.dataset_srid(srid)?;
+            }
+            if let Some(ref members) = collection.foreign_members {
+                for (key, value) in members {
+                    if (key != "name" || name.is_none()) && (key != "crs" || srid.is_none()) {
+                        processor.foreign_member(key, &value.to_string())?;
+                    }
+                }
+            }
             for (idx, feature) in collection.features.iter().enumerate() {
                 processor.feature_begin(idx as u64)?;
+                process_feature_members(feature, processor)?;
                 if let Some(ref properties) = feature.properties {
                     processor.properties_begin()?;
-                    process_properties(properties, processor)?;
+                    process_properties(properties, processor)
+                        .map_err(|e| e.in_feature(idx as u64))?;
                     processor.properties_end()?;
                 }
                 if let Some(ref geometry) = feature.geometry {
                     processor.geometry_begin()?;
-                    process_geojson_geom_n(geometry, idx, processor)?;
+                    process_geojson_geom_n(geometry, idx, processor)
+                        .map_err(|e| e.in_feature(idx as u64))?;
                     processor.geometry_end()?;
+                } else {
+                    processor.null_geometry()?;
                 }
                 processor.feature_end(idx as u64)?;
             }
@@ -115,15 +162,19 @@ fn process_geojson_feature<P: FeatureProcessor>(
     processor.dataset_begin(None)?;
     if feature.geometry.is_some() || feature.properties.is_some() {
         processor.feature_begin(idx as u64)?;
+        process_feature_members(feature, processor)?;
         if let Some(ref properties) = feature.properties {
             processor.properties_begin()?;
-            process_properties(properties, processor)?;
+            process_properties(properties, processor).map_err(|e| e.in_feature(idx as u64))?;
             processor.properties_end()?;
         }
         if let Some(ref geometry) = feature.geometry {
             processor.geometry_begin()?;
-            process_geojson_geom_n(geometry, idx, processor)?;
+            process_geojson_geom_n(geometry, idx, processor)
+                .map_err(|e| e.in_feature(idx as u64))?;
             processor.geometry_end()?;
+        } else {
+            processor.null_geometry()?;
         }
         processor.feature_end(idx as u64)?;
     }
@@ -131,6 +182,34 @@ fn process_geojson_feature<P: FeatureProcessor>(
     Ok(())
 }
 
+/// EPSG code of legacy named CRS (`{"type": "name", "properties": {"name": "EPSG:4326"}}`)
+fn legacy_crs_srid(crs: &JsonValue) -> Option<i32> {
+    if crs.get("type")?.as_str()? != "name" {
+        return None;
+    }
+    crate::epsg_code(crs.get("properties")?.get("name")?.as_str()?)
+}
+
+/// Process feature `id` and foreign members
+fn process_feature_members<P: FeatureProcessor>(
+    feature: &Feature,
+    processor: &mut P,
+) -> Result<()> {
+    match feature.id {
+        Some(Id::String(ref id)) => {
+            processor.foreign_member("id", &JsonValue::from(id.as_str()).to_string())?
+        }
+        Some(Id::Number(ref id)) => processor.foreign_member("id", &id.to_string())?,
+        None => {}
+    }
+    if let Some(ref members) = feature.foreign_members {
+        for (key, value) in members {
+            processor.foreign_member(key, &value.to_string())?;
+        }
+    }
+    Ok(())
+}
+
 /// Process top-level GeoJSON items (geometry only)
 fn process_geojson_geom<P: GeomProcessor>(gj: &GeoGeoJson, processor: &mut P) -> Result<()> {
     match *gj {
@@ -198,23 +277,10 @@ fn process_properties<P: PropertyProcessor>(
     properties: &Map<String, JsonValue>,
     processor: &mut P,
 ) -> Result<()> {
+    let mut json = String::new();
     for (i, (key, value)) in properties.iter().enumerate() {
         // Could we provide a stable property index?
-        match value {
-            JsonValue::String(v) => processor.property(i, &key, &ColumnValue::String(v))?,
-            JsonValue::Number(v) if v.is_f64() => {
-                processor.property(i, &key, &ColumnValue::Double(v.as_f64().unwrap()))?
-            }
-            JsonValue::Number(v) if v.is_i64() => {
-                processor.property(i, &key, &ColumnValue::Long(v.as_i64().unwrap()))?
-            }
-            JsonValue::Number(v) if v.is_u64() => {
-                processor.property(i, &key, &ColumnValue::ULong(v.as_u64().unwrap()))?
-            }
-            JsonValue::Bool(v) => processor.property(i, &key, &ColumnValue::Bool(*v))?,
-            // Null, Array(Vec<Value>), Object(Map<String, Value>)
-            _ => processor.property(i, &key, &ColumnValue::String(&value.to_string()))?,
-        };
+        process_json_property(processor, i, key, value, &mut json)?;
     }
     Ok(())
 }
@@ -250,6 +316,9 @@ fn process_point<P: GeomProcessor>(
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
+    if point_type.is_empty() {
+        return processor.empty_point(idx);
+    }
     processor.point_begin(idx)?;
     process_coord(point_type, processor.multi_dim(), 0, processor)?;
     processor.point_end(idx)
@@ -371,6 +440,31 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn error_context() {
+        struct NoLines;
+        impl GeomProcessor for NoLines {
+            fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+                Err(GeozeroError::Geometry("no lines".to_string()))
+            }
+        }
+        impl PropertyProcessor for NoLines {}
+        impl FeatureProcessor for NoLines {}
+
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1,2]}}, {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,4]]}}]}"#,
+        );
+        let err = geojson.process(&mut NoLines).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "processing geometry `no lines` (in feature 1)"
+        );
+
+        let mut geojson = GeoJson("{\"type\": \"Point\",\n\"coordinates\": [1,}");
+        let err = geojson.process(&mut NoLines).unwrap_err();
+        assert_eq!(err.context().and_then(|c| c.position).map(|p| p.0), Some(2));
+    }
+
     #[test]
     fn properties() -> Result<()> {
         let mut geojson = GeoJson(
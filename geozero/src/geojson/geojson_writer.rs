@@ -3,17 +3,115 @@ use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, Prope
 use std::fmt::Display;
 use std::io::Write;
 
+/// Output formatting of [GeoJsonWriter].
+#[derive(Clone, Default, Debug)]
+pub struct GeoJsonWriterOptions {
+    /// Write members on separate lines indented by two spaces (default: false)
+    pub pretty: bool,
+    /// Write feature properties ordered by name (default: false)
+    pub sort_properties: bool,
+    /// Break coordinate arrays into lines of at most this number of positions
+    /// (default: 0 for no line-wrapping)
+    pub coordinates_per_line: usize,
+}
+
 /// GeoJSON writer.
+///
+/// Unbuffered outputs should be wrapped in a [WriteCounter](crate::WriteCounter).
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::{GeoJsonWriter, GeoJsonWriterOptions};
+/// use geozero::{wkt::WktStr, GeozeroGeometry};
+///
+/// let options = GeoJsonWriterOptions { pretty: true, ..Default::default() };
+/// let mut json = Vec::new();
+/// let mut writer = GeoJsonWriter::with_options(&mut json, options);
+/// WktStr("POINT(1 2)").process_geom(&mut writer).unwrap();
+/// assert_eq!(
+///     std::str::from_utf8(&json).unwrap(),
+///     "{\n  \"type\": \"Point\",\n  \"coordinates\": [1,2]\n}"
+/// );
+/// ```
 pub struct GeoJsonWriter<'a, W: Write> {
     pub dims: CoordDimensions,
+    pub options: GeoJsonWriterOptions,
     out: &'a mut W,
+    /// Dataset members written, features array not opened yet
+    in_dataset_header: bool,
+    /// Features written in current dataset
+    has_features: bool,
+    /// Indentation level of current geometry object
+    level: usize,
+    /// Nesting level of geometry collections
+    collections: usize,
+    /// Number of properties written in current feature
+    properties: usize,
+    /// Properties buffered for sorting
+    sorted_properties: Vec<(String, Vec<u8>)>,
 }
 
+/// Indentation level of feature members
+const FEATURE_LEVEL: usize = 3;
+
 impl<'a, W: Write> GeoJsonWriter<'a, W> {
     pub fn new(out: &'a mut W) -> GeoJsonWriter<'a, W> {
+        Self::with_options(out, GeoJsonWriterOptions::default())
+    }
+    /// Writer with custom output formatting.
+    pub fn with_options(out: &'a mut W, options: GeoJsonWriterOptions) -> GeoJsonWriter<'a, W> {
         GeoJsonWriter {
             dims: CoordDimensions::default(),
+            options,
             out,
+            in_dataset_header: false,
+            has_features: false,
+            level: 0,
+            collections: 0,
+            properties: 0,
+            sorted_properties: Vec::new(),
+        }
+    }
+    /// Flush and return the output.
+    pub fn into_inner(self) -> Result<&'a mut W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+    fn features_begin(&mut self) -> Result<()> {
+        if self.in_dataset_header {
+            self.in_dataset_header = false;
+            self.out.write_all(b",")?;
+            self.newline(1)?;
+            self.out.write_all(br#""features": ["#)?;
+        }
+        Ok(())
+    }
+    /// Line break, indented when pretty-printing.
+    fn newline(&mut self, level: usize) -> Result<()> {
+        self.out.write_all(b"\n")?;
+        if self.options.pretty {
+            for _ in 0..level {
+                self.out.write_all(b"  ")?;
+            }
+        }
+        Ok(())
+    }
+    /// Line break before the first member of an object when pretty-printing.
+    fn open(&mut self, level: usize) -> Result<()> {
+        if self.options.pretty {
+            self.newline(level)?;
+        }
+        Ok(())
+    }
+    /// Separator between object members.
+    fn separator(&mut self, level: usize) -> Result<()> {
+        if self.options.pretty {
+            self.out.write_all(b",")?;
+            self.newline(level)
+        } else {
+            self.out.write_all(b", ")?;
+            Ok(())
         }
     }
     fn comma(&mut self, idx: usize) -> Result<()> {
@@ -22,51 +120,150 @@ impl<'a, W: Write> GeoJsonWriter<'a, W> {
         }
         Ok(())
     }
+    /// Separator before a coordinate, wrapping lines after `coordinates_per_line` positions.
+    fn coord_separator(&mut self, idx: usize) -> Result<()> {
+        self.comma(idx)?;
+        let per_line = self.options.coordinates_per_line;
+        if per_line > 0 && idx > 0 && idx.is_multiple_of(per_line) {
+            self.newline(self.level + 2)?;
+        }
+        Ok(())
+    }
+    /// Begin of a geometry object with member `member` (`coordinates` or `geometries`).
+    fn object_begin(&mut self, idx: usize, geometry_type: &str, member: &str) -> Result<()> {
+        self.comma(idx)?;
+        if self.collections > 0 {
+            self.open(self.level)?;
+        }
+        self.out.write_all(b"{")?;
+        self.open(self.level + 1)?;
+        write!(self.out, "\"type\": \"{}\"", geometry_type)?;
+        self.separator(self.level + 1)?;
+        write!(self.out, "\"{}\": ", member)?;
+        Ok(())
+    }
+    fn object_end(&mut self) -> Result<()> {
+        self.open(self.level)?;
+        self.out.write_all(b"}")?;
+        Ok(())
+    }
+    fn property_separator(&mut self) -> Result<()> {
+        if self.properties > 0 {
+            self.out.write_all(b",")?;
+            if !self.options.pretty {
+                self.out.write_all(b" ")?;
+            }
+        }
+        self.open(FEATURE_LEVEL + 1)?;
+        self.properties += 1;
+        Ok(())
+    }
 }
 
 impl<W: Write> FeatureProcessor for GeoJsonWriter<'_, W> {
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
-        self.out.write_all(
-            br#"{
-"type": "FeatureCollection""#,
-        )?;
+        self.out.write_all(b"{")?;
+        self.newline(1)?;
+        self.out.write_all(br#""type": "FeatureCollection""#)?;
         if let Some(name) = name {
-            write!(self.out, ",\n\"name\": \"{}\"", name)?;
+            self.out.write_all(b",")?;
+            self.newline(1)?;
+            write!(self.out, "\"name\": \"{}\"", name)?;
+        }
+        self.in_dataset_header = true;
+        self.has_features = false;
+        Ok(())
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if let (Some(srid), true) = (srid, self.in_dataset_header) {
+            self.out.write_all(b",")?;
+            self.newline(1)?;
+            write!(
+                self.out,
+                "\"crs\": {{\"type\": \"name\", \"properties\": {{\"name\": \"{}\"}}}}",
+                crate::epsg_urn(srid)
+            )?;
         }
-        self.out.write_all(
-            br#",
-"features": ["#,
-        )?;
         Ok(())
     }
     fn dataset_end(&mut self) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        self.features_begin()?;
+        if self.has_features {
+            self.open(1)?;
+        }
+        self.out.write_all(b"]")?;
+        self.open(0)?;
+        self.out.write_all(b"}")?;
         Ok(())
     }
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.features_begin()?;
         if idx > 0 {
-            self.out.write_all(b",\n")?;
+            self.out.write_all(b",")?;
+            self.newline(FEATURE_LEVEL - 1)?;
+        } else {
+            self.open(FEATURE_LEVEL - 1)?;
         }
-        self.out.write_all(br#"{"type": "Feature""#)?;
+        self.out.write_all(b"{")?;
+        self.open(FEATURE_LEVEL)?;
+        self.out.write_all(br#""type": "Feature""#)?;
+        self.has_features = true;
         Ok(())
     }
     fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.open(FEATURE_LEVEL - 1)?;
         self.out.write_all(b"}")?;
         Ok(())
     }
     fn properties_begin(&mut self) -> Result<()> {
-        self.out.write_all(br#", "properties": {"#)?;
+        self.separator(FEATURE_LEVEL)?;
+        self.out.write_all(br#""properties": {"#)?;
+        self.properties = 0;
+        self.sorted_properties.clear();
         Ok(())
     }
     fn properties_end(&mut self) -> Result<()> {
+        if self.options.sort_properties {
+            let mut properties = std::mem::take(&mut self.sorted_properties);
+            properties.sort_by(|a, b| a.0.cmp(&b.0));
+            for (name, json) in &properties {
+                self.property_separator()?;
+                write!(self.out, "\"{}\": ", name.replace("\"", "\\\""))?;
+                self.out.write_all(json)?;
+            }
+            properties.clear();
+            self.sorted_properties = properties;
+        }
+        if self.properties > 0 {
+            self.open(FEATURE_LEVEL)?;
+        }
         self.out.write_all(b"}")?;
         Ok(())
     }
     fn geometry_begin(&mut self) -> Result<()> {
-        self.out.write_all(br#", "geometry": "#)?;
+        self.separator(FEATURE_LEVEL)?;
+        self.out.write_all(br#""geometry": "#)?;
+        self.level = FEATURE_LEVEL;
         Ok(())
     }
     fn geometry_end(&mut self) -> Result<()> {
+        self.level = 0;
+        Ok(())
+    }
+    fn null_geometry(&mut self) -> Result<()> {
+        self.separator(FEATURE_LEVEL)?;
+        self.out.write_all(br#""geometry": null"#)?;
+        Ok(())
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        if self.in_dataset_header {
+            self.out.write_all(b",")?;
+            self.newline(1)?;
+        } else {
+            self.separator(FEATURE_LEVEL)?;
+        }
+        // value is JSON text
+        write_num_prop(self.out, name, &value)?;
         Ok(())
     }
 }
@@ -76,7 +273,7 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<'_, W> {
         self.dims
     }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        self.comma(idx)?;
+        self.coord_separator(idx)?;
         self.out.write_all(&format!("[{},{}]", x, y).as_bytes())?;
         Ok(())
     }
@@ -90,7 +287,7 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<'_, W> {
         _tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        self.comma(idx)?;
+        self.coord_separator(idx)?;
         self.out.write_all(&format!("[{},{}", x, y).as_bytes())?;
         if let Some(z) = z {
             self.out.write_all(&format!(",{}", z).as_bytes())?;
@@ -98,91 +295,89 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<'_, W> {
         self.out.write_all(b"]")?;
         Ok(())
     }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.object_begin(idx, "Point", "coordinates")?;
+        self.out.write_all(b"[]")?;
+        self.object_end()
+    }
     fn point_begin(&mut self, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out
-            .write_all(br#"{"type": "Point", "coordinates": "#)?;
-        Ok(())
+        self.object_begin(idx, "Point", "coordinates")
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"}")?;
-        Ok(())
+        self.object_end()
     }
     fn multipoint_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out
-            .write_all(br#"{"type": "MultiPoint", "coordinates": ["#)?;
+        self.object_begin(idx, "MultiPoint", "coordinates")?;
+        self.out.write_all(b"[")?;
         Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
-        Ok(())
+        self.out.write_all(b"]")?;
+        self.object_end()
     }
     fn linestring_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
         if tagged {
-            self.out
-                .write_all(br#"{"type": "LineString", "coordinates": ["#)?;
+            self.object_begin(idx, "LineString", "coordinates")?;
         } else {
-            self.out.write_all(b"[")?;
+            self.comma(idx)?;
         }
+        self.out.write_all(b"[")?;
         Ok(())
     }
     fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.out.write_all(b"]")?;
         if tagged {
-            self.out.write_all(b"]}")?;
-        } else {
-            self.out.write_all(b"]")?;
+            self.object_end()?;
         }
         Ok(())
     }
     fn multilinestring_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out
-            .write_all(br#"{"type": "MultiLineString", "coordinates": ["#)?;
+        self.object_begin(idx, "MultiLineString", "coordinates")?;
+        self.out.write_all(b"[")?;
         Ok(())
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
-        Ok(())
+        self.out.write_all(b"]")?;
+        self.object_end()
     }
     fn polygon_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
         if tagged {
-            self.out
-                .write_all(br#"{"type": "Polygon", "coordinates": ["#)?;
+            self.object_begin(idx, "Polygon", "coordinates")?;
         } else {
-            self.out.write_all(b"[")?;
+            self.comma(idx)?;
         }
+        self.out.write_all(b"[")?;
         Ok(())
     }
     fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.out.write_all(b"]")?;
         if tagged {
-            self.out.write_all(b"]}")?;
-        } else {
-            self.out.write_all(b"]")?;
+            self.object_end()?;
         }
         Ok(())
     }
     fn multipolygon_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out
-            .write_all(br#"{"type": "MultiPolygon", "coordinates": ["#)?;
+        self.object_begin(idx, "MultiPolygon", "coordinates")?;
+        self.out.write_all(b"[")?;
         Ok(())
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
-        Ok(())
+        self.out.write_all(b"]")?;
+        self.object_end()
     }
     fn geometrycollection_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out
-            .write_all(br#"{"type": "GeometryCollection", "geometries": ["#)?;
+        self.object_begin(idx, "GeometryCollection", "geometries")?;
+        self.out.write_all(b"[")?;
+        self.collections += 1;
+        self.level += 2;
         Ok(())
     }
     fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
-        Ok(())
+        self.level -= 2;
+        self.collections -= 1;
+        self.open(self.level + 1)?;
+        self.out.write_all(b"]")?;
+        self.object_end()
     }
 }
 
@@ -191,40 +386,32 @@ fn write_num_prop<'a, W: Write>(out: &'a mut W, colname: &str, v: &dyn Display)
     Ok(())
 }
 
-fn write_str_prop<'a, W: Write>(out: &'a mut W, colname: &str, v: &str) -> Result<()> {
-    out.write_all(
-        &format!(
-            r#""{}": "{}""#,
-            colname.replace("\"", "\\\""),
-            v.replace("\"", "\\\"")
-        )
-        .as_bytes(),
-    )?;
+/// Write property value as JSON. Binary values are not supported.
+fn write_value<W: Write>(out: &mut W, value: &ColumnValue) -> Result<()> {
+    match value {
+        ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+            out.write_all(format!(r#""{}""#, v.replace("\"", "\\\"")).as_bytes())?
+        }
+        // numbers, booleans and JSON text
+        _ => write!(out, "{}", value)?,
+    }
     Ok(())
 }
 
 impl<W: Write> PropertyProcessor for GeoJsonWriter<'_, W> {
-    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
-        if i > 0 {
-            self.out.write_all(b", ")?;
+    fn property(&mut self, _i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        if let ColumnValue::Binary(_) = colval {
+            return Ok(false);
+        }
+        if self.options.sort_properties {
+            let mut json = Vec::new();
+            write_value(&mut json, colval)?;
+            self.sorted_properties.push((colname.to_string(), json));
+        } else {
+            self.property_separator()?;
+            write!(self.out, "\"{}\": ", colname.replace("\"", "\\\""))?;
+            write_value(self.out, colval)?;
         }
-        match colval {
-            ColumnValue::Byte(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::UByte(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Bool(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Short(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::UShort(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Int(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::UInt(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Long(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::ULong(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Float(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::Double(v) => write_num_prop(self.out, colname, &v)?,
-            ColumnValue::String(v) => write_str_prop(self.out, colname, &v)?,
-            ColumnValue::Json(_v) => (),
-            ColumnValue::DateTime(v) => write_str_prop(self.out, colname, &v)?,
-            ColumnValue::Binary(_v) => (),
-        };
         Ok(false)
     }
 }
@@ -297,6 +484,18 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn empty_geometries() -> Result<()> {
+        use crate::wkt::WktStr;
+        assert_eq!(
+            WktStr("GEOMETRYCOLLECTION(POINT EMPTY,LINESTRING EMPTY,MULTIPOLYGON EMPTY)")
+                .to_json()?,
+            r#"{"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": []},{"type": "LineString", "coordinates": []},{"type": "MultiPolygon", "coordinates": []}]}"#
+        );
+        Ok(())
+    }
+
     #[test]
     fn geometry_collection() -> Result<()> {
         let geojson = r#"{"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [100.1,0.1]},{"type": "LineString", "coordinates": [[101.1,0.1],[102.1,1.1]]}]}"#;
@@ -312,11 +511,11 @@ mod test {
         let mut out: Vec<u8> = Vec::new();
         assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
         let jsonout = std::str::from_utf8(&out).unwrap();
-        // TODO: FeatureCollection name missing
         assert_eq!(
             jsonout,
             r#"{
 "type": "FeatureCollection",
+"name": "countries",
 "features": [{"type": "Feature", "properties": {"id": "NZL", "name": "New Zealand"}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[173.020375,-40.919052],[173.247234,-41.331999],[173.958405,-40.926701],[174.247587,-41.349155],[174.248517,-41.770008],[173.876447,-42.233184],[173.22274,-42.970038],[172.711246,-43.372288],[173.080113,-43.853344],[172.308584,-43.865694],[171.452925,-44.242519],[171.185138,-44.897104],[170.616697,-45.908929],[169.831422,-46.355775],[169.332331,-46.641235],[168.411354,-46.619945],[167.763745,-46.290197],[166.676886,-46.219917],[166.509144,-45.852705],[167.046424,-45.110941],[168.303763,-44.123973],[168.949409,-43.935819],[169.667815,-43.555326],[170.52492,-43.031688],[171.12509,-42.512754],[171.569714,-41.767424],[171.948709,-41.514417],[172.097227,-40.956104],[172.79858,-40.493962],[173.020375,-40.919052]]],[[[174.612009,-36.156397],[175.336616,-37.209098],[175.357596,-36.526194],[175.808887,-36.798942],[175.95849,-37.555382],[176.763195,-37.881253],[177.438813,-37.961248],[178.010354,-37.579825],[178.517094,-37.695373],[178.274731,-38.582813],[177.97046,-39.166343],[177.206993,-39.145776],[176.939981,-39.449736],[177.032946,-39.879943],[176.885824,-40.065978],[176.508017,-40.604808],[176.01244,-41.289624],[175.239567,-41.688308],[175.067898,-41.425895],[174.650973,-41.281821],[175.22763,-40.459236],[174.900157,-39.908933],[173.824047,-39.508854],[173.852262,-39.146602],[174.574802,-38.797683],[174.743474,-38.027808],[174.697017,-37.381129],[174.292028,-36.711092],[174.319004,-36.534824],[173.840997,-36.121981],[173.054171,-35.237125],[172.636005,-34.529107],[173.007042,-34.450662],[173.551298,-35.006183],[174.32939,-35.265496],[174.612009,-36.156397]]]]}}]}"#
         );
 
@@ -325,17 +524,169 @@ mod test {
         let mut out: Vec<u8> = Vec::new();
         assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
         let jsonout = std::str::from_utf8(&out).unwrap();
-        // TODO: FeatureCollection name missing
         assert_eq!(
             jsonout,
             r#"{
 "type": "FeatureCollection",
+"name": "countries",
 "features": [{"type": "Feature", "properties": {"\"name\"": "\"New Zealand\"", "id": "NZL"}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[173.020375,-40.919052],[173.247234,-41.331999],[173.958405,-40.926701],[174.247587,-41.349155],[174.248517,-41.770008],[173.876447,-42.233184],[173.22274,-42.970038],[172.711246,-43.372288],[173.080113,-43.853344],[172.308584,-43.865694],[171.452925,-44.242519],[171.185138,-44.897104],[170.616697,-45.908929],[169.831422,-46.355775],[169.332331,-46.641235],[168.411354,-46.619945],[167.763745,-46.290197],[166.676886,-46.219917],[166.509144,-45.852705],[167.046424,-45.110941],[168.303763,-44.123973],[168.949409,-43.935819],[169.667815,-43.555326],[170.52492,-43.031688],[171.12509,-42.512754],[171.569714,-41.767424],[171.948709,-41.514417],[172.097227,-40.956104],[172.79858,-40.493962],[173.020375,-40.919052]]],[[[174.612009,-36.156397],[175.336616,-37.209098],[175.357596,-36.526194],[175.808887,-36.798942],[175.95849,-37.555382],[176.763195,-37.881253],[177.438813,-37.961248],[178.010354,-37.579825],[178.517094,-37.695373],[178.274731,-38.582813],[177.97046,-39.166343],[177.206993,-39.145776],[176.939981,-39.449736],[177.032946,-39.879943],[176.885824,-40.065978],[176.508017,-40.604808],[176.01244,-41.289624],[175.239567,-41.688308],[175.067898,-41.425895],[174.650973,-41.281821],[175.22763,-40.459236],[174.900157,-39.908933],[173.824047,-39.508854],[173.852262,-39.146602],[174.574802,-38.797683],[174.743474,-38.027808],[174.697017,-37.381129],[174.292028,-36.711092],[174.319004,-36.534824],[173.840997,-36.121981],[173.054171,-35.237125],[172.636005,-34.529107],[173.007042,-34.450662],[173.551298,-35.006183],[174.32939,-35.265496],[174.612009,-36.156397]]]]}}]}"#
         );
 
         Ok(())
     }
 
+    #[test]
+    fn foreign_members() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "name": "places", "crs": {"type": "name"}, "features": [{"type": "Feature", "id": "a1", "vendor": {"x": 1}, "properties": {"n": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"name": "places",
+"crs": {"type":"name"},
+"features": [{"type": "Feature", "id": "a1", "vendor": {"x":1}, "properties": {"n": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_crs() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "crs": {"type": "name", "properties": {"name": "EPSG:2056"}}, "features": []}"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"crs": {"type": "name", "properties": {"name": "urn:ogc:def:crs:EPSG::2056"}},
+"features": []}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nested_properties() -> Result<()> {
+        let geojson = r#"{"type": "Feature", "properties": {"tags": ["a","b"], "meta": {"k":1,"v":[null]}}, "geometry": {"type": "Point", "coordinates": [1,2]}}"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"meta": {"k":1,"v":[null]}, "tags": ["a","b"]}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn null_geometry() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"n": 1}, "geometry": null}]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"n": 1}, "geometry": null}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pretty() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "id": 1, "properties": {"b": 1, "a": "x"}, "geometry": {"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [1,2]}, {"type": "LineString", "coordinates": [[1,2],[3,4]]}]}}, {"type": "Feature", "properties": {}, "geometry": null}]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let options = GeoJsonWriterOptions {
+            pretty: true,
+            ..Default::default()
+        };
+        let mut writer = GeoJsonWriter::with_options(&mut out, options);
+        assert!(read_geojson(geojson.as_bytes(), &mut writer).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {
+      "type": "Feature",
+      "id": 1,
+      "properties": {
+        "a": "x",
+        "b": 1
+      },
+      "geometry": {
+        "type": "GeometryCollection",
+        "geometries": [
+          {
+            "type": "Point",
+            "coordinates": [1,2]
+          },
+          {
+            "type": "LineString",
+            "coordinates": [[1,2],[3,4]]
+          }
+        ]
+      }
+    },
+    {
+      "type": "Feature",
+      "properties": {},
+      "geometry": null
+    }
+  ]
+}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sort_properties() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let options = GeoJsonWriterOptions {
+            sort_properties: true,
+            ..Default::default()
+        };
+        let mut writer = GeoJsonWriter::with_options(&mut out, options);
+        writer.feature_begin(0)?;
+        writer.properties_begin()?;
+        writer.property(0, "name", &ColumnValue::String("a"))?;
+        writer.property(1, "data", &ColumnValue::Binary(&[1]))?;
+        writer.property(2, "id", &ColumnValue::Int(7))?;
+        writer.properties_end()?;
+        writer.null_geometry()?;
+        writer.feature_end(0)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "Feature", "properties": {"id": 7, "name": "a"}, "geometry": null}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn coordinates_per_line() -> Result<()> {
+        let geojson = r#"{"type": "LineString", "coordinates": [[1,1],[2,2],[3,3],[4,4],[5,5]]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let options = GeoJsonWriterOptions {
+            pretty: true,
+            coordinates_per_line: 2,
+            ..Default::default()
+        };
+        let mut writer = GeoJsonWriter::with_options(&mut out, options);
+        assert!(read_geojson(geojson.as_bytes(), &mut writer).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+  "type": "LineString",
+  "coordinates": [[1,1],[2,2],
+    [3,3],[4,4],
+    [5,5]]
+}"#
+        );
+        Ok(())
+    }
+
     #[test]
     fn conversions() {
         let geom: geo_types::Geometry<f64> = geo_types::Point::new(10.0, 20.0).into();
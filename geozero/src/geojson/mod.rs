@@ -49,6 +49,10 @@ impl From<geojson::Error> for crate::error::GeozeroError {
     fn from(geojson_error: geojson::Error) -> Self {
         match geojson_error {
             geojson::Error::Io(io) => Self::IoError(io),
+            geojson::Error::MalformedJson(json) => {
+                let (line, column) = (json.line(), json.column());
+                Self::Geometry(json.to_string()).at_position(line, column)
+            }
             other => Self::Geometry(other.to_string()),
         }
     }
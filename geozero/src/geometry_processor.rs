@@ -1,7 +1,7 @@
 use crate::error::{GeozeroError, Result};
 
 /// Dimensions requested for processing
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct CoordDimensions {
     /// height
     pub z: bool,
@@ -46,10 +46,38 @@ impl CoordDimensions {
             tm: false,
         }
     }
+    /// Number of interleaved x, y, z and m values per vertex
+    pub(crate) fn xyzm_stride(&self) -> usize {
+        2 + self.z as usize + self.m as usize
+    }
+}
+
+/// Process interleaved coordinates vertex by vertex
+pub(crate) fn process_coords<P: GeomProcessor + ?Sized>(
+    processor: &mut P,
+    coords: &[f64],
+    dims: CoordDimensions,
+    idx: usize,
+) -> Result<()> {
+    let multi_dim = processor.multi_dim();
+    for (i, c) in coords.chunks_exact(dims.xyzm_stride()).enumerate() {
+        if multi_dim {
+            let z = if dims.z { Some(c[2]) } else { None };
+            let m = if dims.m { c.last().copied() } else { None };
+            processor.coordinate(c[0], c[1], z, m, None, None, idx + i)?;
+        } else {
+            processor.xy(c[0], c[1], idx + i)?;
+        }
+    }
+    Ok(())
 }
 
 /// Geometry processing trait
 ///
+/// Empty geometries are processed with `size` 0 and without any member or coordinate
+/// events between begin and end, e.g. `LINESTRING EMPTY` results in `linestring_begin(true, 0, idx)`
+/// followed by `linestring_end(true, idx)`. Empty points are processed with [empty_point](Self::empty_point).
+///
 /// # Usage example:
 ///
 /// ```rust
@@ -102,7 +130,18 @@ pub trait GeomProcessor {
         Ok(())
     }
 
+    /// Process a sequence of interleaved coordinates
+    ///
+    /// `coords` contains x, y and, if set in `dims`, z and m values of each vertex.
+    /// `idx` is the index of the first vertex. Column-oriented readers pass whole coordinate
+    /// arrays at once, the default implementation calls `xy` or `coordinate` for each vertex.
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        process_coords(self, coords, dims, idx)
+    }
+
     /// Process empty coordinates, like WKT's `POINT EMPTY`
+    ///
+    /// Called instead of `point_begin`/`point_end`.
     fn empty_point(&mut self, idx: usize) -> Result<()> {
         Err(GeozeroError::Geometry(
             "The input was an empty Point, but the output doesn't support empty Points".to_string(),
@@ -303,6 +342,39 @@ pub trait GeomProcessor {
     }
 }
 
+/// Verification of declared geometry sizes for writers encoding sizes before members.
+#[cfg(any(feature = "with-wkb", feature = "with-mvt"))]
+#[derive(Default, Debug)]
+pub(crate) struct SizeCheck {
+    /// (declared size, received members) of open geometries
+    open: Vec<(usize, usize)>,
+}
+
+#[cfg(any(feature = "with-wkb", feature = "with-mvt"))]
+impl SizeCheck {
+    /// Geometry begin with declared size
+    pub fn begin(&mut self, size: usize) {
+        self.member();
+        self.open.push((size, 0));
+    }
+    /// Coordinate or empty point
+    pub fn member(&mut self) {
+        if let Some((_, count)) = self.open.last_mut() {
+            *count += 1;
+        }
+    }
+    /// Geometry end. Fails if number of members doesn't match declared size.
+    pub fn end(&mut self, geometry_type: &str) -> Result<()> {
+        match self.open.pop() {
+            Some((size, count)) if size != count => Err(GeozeroError::Geometry(format!(
+                "{} declared with size {} but has {} members",
+                geometry_type, size, count
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[test]
 fn error_message() {
     use crate::error::GeozeroError;
@@ -321,3 +393,17 @@ fn error_message() {
         "processing geometry `test`".to_string()
     );
 }
+
+#[test]
+#[cfg(any(feature = "with-wkb", feature = "with-mvt"))]
+fn size_check() {
+    let mut check = SizeCheck::default();
+    check.begin(2);
+    check.begin(1);
+    check.member();
+    assert!(check.end("Point").is_ok());
+    assert_eq!(
+        check.end("MultiPoint").err().unwrap().to_string(),
+        "processing geometry `MultiPoint declared with size 2 but has 1 members`"
+    );
+}
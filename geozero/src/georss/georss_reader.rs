@@ -0,0 +1,289 @@
+use crate::error::{GeozeroError, Result};
+use crate::gml::{is_geometry, process_gml_element, AxisOrder};
+use crate::xml::{Element, XmlReader};
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    PropertyProcessor,
+};
+use std::io::{BufReader, Read};
+
+/// GeoRSS Reader.
+pub struct GeoRssReader<'a, R: Read>(pub &'a mut R);
+
+impl<'a, R: Read> GeozeroDatasource for GeoRssReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_georss(&mut self.0, processor)
+    }
+}
+
+/// GeoRSS String slice.
+///
+/// Geometries of multiple entries are processed as geometry collection.
+pub struct GeoRss<'a>(pub &'a str);
+
+impl GeozeroGeometry for GeoRss<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_georss_geom(&mut self.0.as_bytes(), processor)
+    }
+}
+
+impl GeozeroDatasource for GeoRss<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_georss(&mut self.0.as_bytes(), processor)
+    }
+}
+
+/// Read and process Atom entries and RSS items with GeoRSS geometries.
+///
+/// Supported are the GeoRSS-Simple elements `point`, `line`, `polygon` and `box`, and
+/// GML geometries in `where` elements. Coordinates are processed in longitude/latitude order.
+/// `title`, `summary` and `description` are processed as string properties.
+pub fn read_georss<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let mut reader = XmlReader::new(BufReader::new(reader));
+    processor.dataset_begin(None)?;
+    let mut idx = 0;
+    while let Some((element, empty)) = reader.next_start()? {
+        if !is_entry(&element.name) {
+            continue;
+        }
+        let entry = reader.read_element(element, empty)?;
+        process_entry(&entry, idx, processor)?;
+        idx += 1;
+    }
+    processor.dataset_end()
+}
+
+/// Read and process GeoRSS geometries of Atom entries and RSS items.
+pub fn read_georss_geom<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let mut reader = XmlReader::new(BufReader::new(reader));
+    let mut entries = Vec::new();
+    while let Some((element, empty)) = reader.next_start()? {
+        if !is_entry(&element.name) {
+            continue;
+        }
+        let entry = reader.read_element(element, empty)?;
+        if entry_geometry(&entry).is_some() {
+            entries.push(entry);
+        }
+    }
+    if entries.len() == 1 {
+        process_geometry(entry_geometry(&entries[0]).unwrap(), 0, processor)
+    } else {
+        processor.geometrycollection_begin(entries.len(), 0)?;
+        for (idx, entry) in entries.iter().enumerate() {
+            process_geometry(entry_geometry(entry).unwrap(), idx, processor)?;
+        }
+        processor.geometrycollection_end(0)
+    }
+}
+
+fn is_entry(name: &str) -> bool {
+    name == "entry" || name == "item"
+}
+
+fn process_entry<P: FeatureProcessor>(entry: &Element, idx: u64, processor: &mut P) -> Result<()> {
+    processor.feature_begin(idx)?;
+    processor.properties_begin()?;
+    process_properties(entry, processor)?;
+    processor.properties_end()?;
+    if let Some(geom) = entry_geometry(entry) {
+        processor.geometry_begin()?;
+        process_geometry(geom, 0, processor).map_err(|e| e.in_feature(idx))?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx)
+}
+
+fn process_properties<P: PropertyProcessor>(entry: &Element, processor: &mut P) -> Result<()> {
+    let mut i = 0;
+    for name in ["title", "summary", "description"] {
+        if let Some(value) = entry.child_text(name) {
+            if processor.property(i, name, &ColumnValue::String(value))? {
+                break;
+            }
+            i += 1;
+        }
+    }
+    Ok(())
+}
+
+/// First GeoRSS geometry element of entry
+fn entry_geometry(entry: &Element) -> Option<&Element> {
+    entry.children.iter().find(|child| {
+        matches!(
+            child.name.as_str(),
+            "point" | "line" | "polygon" | "box" | "where"
+        )
+    })
+}
+
+fn invalid(element: &Element) -> GeozeroError {
+    GeozeroError::Geometry(format!("Invalid GeoRSS `{}` element", element.name))
+}
+
+/// Parse "lat lon lat lon ..." into x/y pairs
+fn parse_coords(element: &Element) -> Result<Vec<(f64, f64)>> {
+    let values = element
+        .text
+        .split_whitespace()
+        .map(|v| v.parse::<f64>().map_err(|_| invalid(element)))
+        .collect::<Result<Vec<_>>>()?;
+    if values.is_empty() || !values.len().is_multiple_of(2) {
+        return Err(invalid(element));
+    }
+    Ok(values
+        .chunks(2)
+        .map(|lat_lon| (lat_lon[1], lat_lon[0]))
+        .collect())
+}
+
+fn process_geometry<P: GeomProcessor>(
+    element: &Element,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match element.name.as_str() {
+        "point" => {
+            let coords = parse_coords(element)?;
+            if coords.len() != 1 {
+                return Err(invalid(element));
+            }
+            processor.point_begin(idx)?;
+            process_coord(coords[0], 0, processor)?;
+            processor.point_end(idx)
+        }
+        "line" => {
+            let coords = parse_coords(element)?;
+            if coords.len() < 2 {
+                return Err(invalid(element));
+            }
+            process_linestring(&coords, true, idx, processor)
+        }
+        "polygon" => {
+            let coords = parse_coords(element)?;
+            if coords.len() < 4 {
+                return Err(invalid(element));
+            }
+            processor.polygon_begin(true, 1, idx)?;
+            process_linestring(&coords, false, 0, processor)?;
+            processor.polygon_end(true, idx)
+        }
+        "box" => {
+            let coords = parse_coords(element)?;
+            if coords.len() != 2 {
+                return Err(invalid(element));
+            }
+            let ((xmin, ymin), (xmax, ymax)) = (coords[0], coords[1]);
+            let ring = [
+                (xmin, ymin),
+                (xmax, ymin),
+                (xmax, ymax),
+                (xmin, ymax),
+                (xmin, ymin),
+            ];
+            processor.polygon_begin(true, 1, idx)?;
+            process_linestring(&ring, false, 0, processor)?;
+            processor.polygon_end(true, idx)
+        }
+        // GeoRSS GML uses WGS84 in latitude/longitude order
+        _ => {
+            let geometry = element
+                .children
+                .iter()
+                .find(|child| is_geometry(&child.name))
+                .ok_or_else(|| invalid(element))?;
+            process_gml_element(geometry, AxisOrder::YX, idx, processor)
+        }
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[(f64, f64)],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (idxc, coord) in coords.iter().enumerate() {
+        process_coord(*coord, idxc, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_coord<P: GeomProcessor>(coord: (f64, f64), idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(coord.0, coord.1, None, None, None, None, idx)
+    } else {
+        processor.xy(coord.0, coord.1, idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::ToWkt;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:georss="http://www.georss.org/georss"
+      xmlns:gml="http://www.opengis.net/gml">
+  <title>Earthquakes</title>
+  <entry>
+    <title>M 3.2 - Central California</title>
+    <summary>Depth 8 km</summary>
+    <georss:point>36.5 -121.1</georss:point>
+  </entry>
+  <entry>
+    <title>Flood warning</title>
+    <georss:box>42.943 -71.032 43.039 -69.856</georss:box>
+  </entry>
+  <entry>
+    <title>Route</title>
+    <georss:where>
+      <gml:LineString>
+        <gml:posList>45.256 -110.45 46.46 -109.48 43.84 -109.86</gml:posList>
+      </gml:LineString>
+    </georss:where>
+  </entry>
+  <entry>
+    <title>No location</title>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn geometries() -> Result<()> {
+        assert_eq!(
+            GeoRss(ATOM).to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(-121.1 36.5),POLYGON((-71.032 42.943,-69.856 42.943,-69.856 43.039,-71.032 43.039,-71.032 42.943)),LINESTRING(-110.45 45.256,-109.48 46.46,-109.86 43.84))"
+        );
+        let rss = r#"<rss version="2.0" xmlns:georss="http://www.georss.org/georss">
+          <channel><item><title>Area</title><description>Closed</description>
+            <georss:polygon>45 -110 46 -110 46 -109 45 -110</georss:polygon>
+          </item></channel></rss>"#;
+        assert_eq!(
+            GeoRss(rss).to_wkt()?,
+            "POLYGON((-110 45,-110 46,-109 46,-110 45))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        GeoRss(ATOM).process(&mut GeoJsonWriter::new(&mut out))?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(
+            r#""properties": {"title": "M 3.2 - Central California", "summary": "Depth 8 km"}, "geometry": {"type": "Point", "coordinates": [-121.1,36.5]}"#
+        ));
+        assert!(json.contains(r#""properties": {"title": "No location"}}"#));
+        assert_eq!(json.matches(r#""type": "Feature""#).count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_coordinates() {
+        let atom = r#"<feed><entry><georss:point>45.2</georss:point></entry></feed>"#;
+        assert!(GeoRss(atom).to_wkt().is_err());
+    }
+}
@@ -0,0 +1,4 @@
+//! GeoRSS conversions.
+mod georss_reader;
+
+pub use georss_reader::*;
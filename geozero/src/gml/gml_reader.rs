@@ -0,0 +1,587 @@
+use super::gml_writer::AxisOrder;
+use crate::error::{GeozeroError, Result};
+use crate::xml::{Element, XmlReader};
+use crate::{GeomProcessor, GeozeroGeometry};
+use std::io::{BufReader, Read};
+
+/// GML String slice.
+///
+/// Coordinates are processed in the axis order of the document.
+/// Multiple top-level geometries are processed as geometry collection.
+pub struct Gml<'a>(pub &'a str);
+
+impl GeozeroGeometry for Gml<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_gml(&mut self.0.as_bytes(), processor)
+    }
+}
+
+/// Read and process GML geometries.
+///
+/// Geometry elements can be embedded in other content, like WFS feature collections.
+/// The EPSG code of the first `srsName` attribute is passed as SRID.
+pub fn read_gml<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let (geometries, srs_name) = parse_gml(reader)?;
+    process_gml(&geometries, srs_name.as_deref(), processor)
+}
+
+/// Read and process GML geometries with coordinates in x/y (longitude/latitude) order.
+///
+/// Coordinates of documents in [AxisOrder::YX] are swapped. Without `axis_order`, it is
+/// derived from the first `srsName` attribute with [is_lat_lon_order](crate::transform::is_lat_lon_order).
+pub fn read_gml_xy<R: Read, P: GeomProcessor>(
+    reader: R,
+    axis_order: Option<AxisOrder>,
+    processor: &mut P,
+) -> Result<()> {
+    let (mut geometries, srs_name) = parse_gml(reader)?;
+    let axis_order = axis_order.unwrap_or_else(|| match srs_name.as_deref() {
+        Some(name) if crate::transform::is_lat_lon_order(name) => AxisOrder::YX,
+        _ => AxisOrder::XY,
+    });
+    if axis_order == AxisOrder::YX {
+        geometries.iter_mut().for_each(swap_axes);
+    }
+    process_gml(&geometries, srs_name.as_deref(), processor)
+}
+
+/// Parse geometries and first `srsName`.
+fn parse_gml<R: Read>(reader: R) -> Result<(Vec<GmlGeometry>, Option<String>)> {
+    let mut reader = XmlReader::new(BufReader::new(reader));
+    let mut geometries = Vec::new();
+    let mut srs_name = None;
+    while let Some((element, empty)) = reader.next_start()? {
+        if srs_name.is_none() {
+            srs_name = element.attribute("srsName").map(String::from);
+        }
+        if !is_geometry(&element.name) {
+            continue;
+        }
+        let element = reader.read_element(element, empty)?;
+        geometries.push(parse_geometry(&element, 2)?);
+    }
+    Ok((geometries, srs_name))
+}
+
+fn process_gml<P: GeomProcessor>(
+    geometries: &[GmlGeometry],
+    srs_name: Option<&str>,
+    processor: &mut P,
+) -> Result<()> {
+    let srid = srs_name.and_then(crate::epsg_code);
+    if srid.is_some() {
+        processor.srid(srid)?;
+    }
+    if geometries.len() == 1 {
+        process_geometry(&geometries[0], 0, processor)
+    } else {
+        processor.geometrycollection_begin(geometries.len(), 0)?;
+        for (idx, geom) in geometries.iter().enumerate() {
+            process_geometry(geom, idx, processor)?;
+        }
+        processor.geometrycollection_end(0)
+    }
+}
+
+/// Process a parsed GML geometry element, e.g. embedded in GeoRSS.
+#[cfg(feature = "with-georss")]
+pub(crate) fn process_gml_element<P: GeomProcessor>(
+    element: &Element,
+    axis_order: AxisOrder,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let mut geometry = parse_geometry(element, 2)?;
+    if axis_order == AxisOrder::YX {
+        swap_axes(&mut geometry);
+    }
+    process_geometry(&geometry, idx, processor)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+}
+
+type Ring = Vec<Coord>;
+
+#[derive(Debug)]
+enum GmlGeometry {
+    Point(Coord),
+    LineString(Vec<Coord>),
+    Polygon(Vec<Ring>),
+    MultiPoint(Vec<Coord>),
+    MultiLineString(Vec<Vec<Coord>>),
+    MultiPolygon(Vec<Vec<Ring>>),
+    GeometryCollection(Vec<GmlGeometry>),
+}
+
+pub(crate) fn is_geometry(name: &str) -> bool {
+    matches!(
+        name,
+        "Point"
+            | "LineString"
+            | "LinearRing"
+            | "Curve"
+            | "Polygon"
+            | "Surface"
+            | "MultiPoint"
+            | "MultiCurve"
+            | "MultiLineString"
+            | "MultiSurface"
+            | "MultiPolygon"
+            | "MultiGeometry"
+    )
+}
+
+fn parse_srs_dimension(element: &Element) -> Result<Option<usize>> {
+    match element.attribute("srsDimension") {
+        Some(dim) => match dim.trim().parse::<usize>() {
+            Ok(dim) if dim >= 2 => Ok(Some(dim)),
+            _ => Err(GeozeroError::Geometry(format!(
+                "Invalid srsDimension `{}`",
+                dim
+            ))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Member geometries of multi geometries, in `xxxMember` or `xxxMembers` elements
+fn members<'a>(
+    element: &'a Element,
+    member: &'a str,
+    members: &'a str,
+) -> impl Iterator<Item = &'a Element> {
+    element
+        .children
+        .iter()
+        .filter(move |child| child.name == member || child.name == members)
+        .flat_map(|child| child.children.iter())
+        .filter(|child| is_geometry(&child.name))
+}
+
+fn parse_geometry(element: &Element, srs_dimension: usize) -> Result<GmlGeometry> {
+    let dim = parse_srs_dimension(element)?.unwrap_or(srs_dimension);
+    let geom = match element.name.as_str() {
+        "Point" => {
+            let coords = parse_positions(element, dim)?;
+            match coords.first() {
+                Some(coord) => GmlGeometry::Point(*coord),
+                None => GmlGeometry::MultiPoint(Vec::new()),
+            }
+        }
+        "LineString" | "LinearRing" => GmlGeometry::LineString(parse_positions(element, dim)?),
+        "Curve" => GmlGeometry::LineString(parse_curve(element, dim)?),
+        "Polygon" => GmlGeometry::Polygon(parse_polygon(element, dim)?),
+        "Surface" => {
+            let mut polygons = Vec::new();
+            for patches in element.children("patches") {
+                for patch in patches.children("PolygonPatch") {
+                    polygons.push(parse_polygon(patch, dim)?);
+                }
+            }
+            if polygons.len() == 1 {
+                GmlGeometry::Polygon(polygons.remove(0))
+            } else {
+                GmlGeometry::MultiPolygon(polygons)
+            }
+        }
+        "MultiPoint" => {
+            let mut points = Vec::new();
+            for member in members(element, "pointMember", "pointMembers") {
+                if let GmlGeometry::Point(coord) = parse_geometry(member, dim)? {
+                    points.push(coord);
+                }
+            }
+            GmlGeometry::MultiPoint(points)
+        }
+        "MultiCurve" | "MultiLineString" => {
+            let mut lines = Vec::new();
+            for member in members(element, "curveMember", "curveMembers").chain(members(
+                element,
+                "lineStringMember",
+                "lineStringMembers",
+            )) {
+                match parse_geometry(member, dim)? {
+                    GmlGeometry::LineString(line) => lines.push(line),
+                    GmlGeometry::MultiLineString(mut parts) => lines.append(&mut parts),
+                    _ => return Err(invalid_member(element)),
+                }
+            }
+            GmlGeometry::MultiLineString(lines)
+        }
+        "MultiSurface" | "MultiPolygon" => {
+            let mut polygons = Vec::new();
+            for member in members(element, "surfaceMember", "surfaceMembers").chain(members(
+                element,
+                "polygonMember",
+                "polygonMembers",
+            )) {
+                match parse_geometry(member, dim)? {
+                    GmlGeometry::Polygon(polygon) => polygons.push(polygon),
+                    GmlGeometry::MultiPolygon(mut parts) => polygons.append(&mut parts),
+                    _ => return Err(invalid_member(element)),
+                }
+            }
+            GmlGeometry::MultiPolygon(polygons)
+        }
+        "MultiGeometry" => {
+            let geometries = members(element, "geometryMember", "geometryMembers")
+                .map(|member| parse_geometry(member, dim))
+                .collect::<Result<Vec<_>>>()?;
+            GmlGeometry::GeometryCollection(geometries)
+        }
+        _ => {
+            return Err(GeozeroError::Geometry(format!(
+                "Unsupported GML geometry `{}`",
+                element.name
+            )))
+        }
+    };
+    Ok(geom)
+}
+
+fn invalid_member(element: &Element) -> GeozeroError {
+    GeozeroError::Geometry(format!("Invalid member in GML `{}`", element.name))
+}
+
+/// Polygon or PolygonPatch with exterior and interior rings
+fn parse_polygon(element: &Element, dim: usize) -> Result<Vec<Ring>> {
+    let mut rings = Vec::new();
+    // `outerBoundaryIs` and `innerBoundaryIs` are used by GML 2
+    for boundary in element
+        .children("exterior")
+        .chain(element.children("outerBoundaryIs"))
+        .chain(element.children("interior"))
+        .chain(element.children("innerBoundaryIs"))
+    {
+        for ring in &boundary.children {
+            let ring_dim = parse_srs_dimension(ring)?.unwrap_or(dim);
+            match ring.name.as_str() {
+                "LinearRing" => rings.push(parse_positions(ring, ring_dim)?),
+                "Ring" => {
+                    let mut coords = Vec::new();
+                    for member in ring.children("curveMember") {
+                        for curve in &member.children {
+                            match parse_geometry(curve, ring_dim)? {
+                                GmlGeometry::LineString(mut line) => coords.append(&mut line),
+                                _ => return Err(invalid_member(ring)),
+                            }
+                        }
+                    }
+                    rings.push(coords);
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(rings)
+}
+
+/// Curve with LineStringSegments
+fn parse_curve(element: &Element, dim: usize) -> Result<Vec<Coord>> {
+    let mut coords: Vec<Coord> = Vec::new();
+    for segments in element.children("segments") {
+        for segment in segments.children("LineStringSegment") {
+            let segment_dim = parse_srs_dimension(segment)?.unwrap_or(dim);
+            let mut segment_coords = parse_positions(segment, segment_dim)?;
+            // consecutive segments share their end and start point
+            if let (Some(last), Some(first)) = (coords.last(), segment_coords.first()) {
+                if last.x == first.x && last.y == first.y {
+                    segment_coords.remove(0);
+                }
+            }
+            coords.append(&mut segment_coords);
+        }
+    }
+    Ok(coords)
+}
+
+/// Positions in `posList`, `pos` or GML 2 `coordinates` elements
+fn parse_positions(element: &Element, dim: usize) -> Result<Vec<Coord>> {
+    let mut coords = Vec::new();
+    for child in &element.children {
+        let child_dim = parse_srs_dimension(child)?.unwrap_or(dim);
+        match child.name.as_str() {
+            "posList" | "pos" => {
+                let values = parse_values(child.text.split_whitespace())?;
+                if !values.len().is_multiple_of(child_dim) {
+                    return Err(GeozeroError::Geometry(format!(
+                        "Number of values in `{}` not a multiple of {}",
+                        child.name, child_dim
+                    )));
+                }
+                coords.extend(values.chunks(child_dim).map(coord));
+            }
+            "coordinates" => {
+                for tuple in child.text.split_whitespace() {
+                    let values = parse_values(tuple.split(','))?;
+                    if values.len() < 2 {
+                        return Err(GeozeroError::Geometry(format!(
+                            "Invalid GML coordinates `{}`",
+                            tuple
+                        )));
+                    }
+                    coords.push(coord(&values));
+                }
+            }
+            "pointProperty" | "pointRep" => {
+                for point in child.children("Point") {
+                    coords.append(&mut parse_positions(point, child_dim)?);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(coords)
+}
+
+fn parse_values<'a>(values: impl Iterator<Item = &'a str>) -> Result<Vec<f64>> {
+    values
+        .map(|v| {
+            v.parse::<f64>()
+                .map_err(|_| GeozeroError::Geometry(format!("Invalid GML coordinate `{}`", v)))
+        })
+        .collect()
+}
+
+fn coord(values: &[f64]) -> Coord {
+    Coord {
+        x: values[0],
+        y: values[1],
+        z: values.get(2).copied(),
+    }
+}
+
+fn swap_axes(geom: &mut GmlGeometry) {
+    fn swap(coords: &mut [Coord]) {
+        for coord in coords {
+            std::mem::swap(&mut coord.x, &mut coord.y);
+        }
+    }
+    match geom {
+        GmlGeometry::Point(coord) => swap(std::slice::from_mut(coord)),
+        GmlGeometry::LineString(coords) | GmlGeometry::MultiPoint(coords) => swap(coords),
+        GmlGeometry::Polygon(rings) | GmlGeometry::MultiLineString(rings) => {
+            rings.iter_mut().for_each(|ring| swap(ring))
+        }
+        GmlGeometry::MultiPolygon(polygons) => {
+            polygons.iter_mut().flatten().for_each(|ring| swap(ring))
+        }
+        GmlGeometry::GeometryCollection(geometries) => geometries.iter_mut().for_each(swap_axes),
+    }
+}
+
+fn process_geometry<P: GeomProcessor>(
+    geom: &GmlGeometry,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match geom {
+        GmlGeometry::Point(coord) => {
+            processor.point_begin(idx)?;
+            process_coord(coord, 0, processor)?;
+            processor.point_end(idx)
+        }
+        GmlGeometry::LineString(coords) => process_linestring(coords, true, idx, processor),
+        GmlGeometry::Polygon(rings) => process_polygon(rings, true, idx, processor),
+        GmlGeometry::MultiPoint(coords) => {
+            processor.multipoint_begin(coords.len(), idx)?;
+            for (idxc, coord) in coords.iter().enumerate() {
+                process_coord(coord, idxc, processor)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        GmlGeometry::MultiLineString(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (idxl, line) in lines.iter().enumerate() {
+                process_linestring(line, false, idxl, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        GmlGeometry::MultiPolygon(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (idxp, rings) in polygons.iter().enumerate() {
+                process_polygon(rings, false, idxp, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        GmlGeometry::GeometryCollection(geometries) => {
+            processor.geometrycollection_begin(geometries.len(), idx)?;
+            for (idxg, geom) in geometries.iter().enumerate() {
+                process_geometry(geom, idxg, processor)?;
+            }
+            processor.geometrycollection_end(idx)
+        }
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (idxc, coord) in coords.iter().enumerate() {
+        process_coord(coord, idxc, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Ring],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (idxl, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, idxl, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+fn process_coord<P: GeomProcessor>(coord: &Coord, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        let z = if processor.dimensions().z {
+            coord.z
+        } else {
+            None
+        };
+        processor.coordinate(coord.x, coord.y, z, None, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use crate::{CoordDimensions, ToWkt};
+
+    #[test]
+    fn point() -> Result<()> {
+        let gml = r#"<gml:Point gml:id="p1" srsName="urn:ogc:def:crs:EPSG::4326" xmlns:gml="http://www.opengis.net/gml/3.2"><gml:pos>47.3769 8.5417</gml:pos></gml:Point>"#;
+        assert_eq!(Gml(gml).to_wkt()?, "POINT(47.3769 8.5417)");
+        Ok(())
+    }
+
+    #[test]
+    fn srs_name() -> Result<()> {
+        struct Srid(Option<i32>);
+        impl GeomProcessor for Srid {
+            fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+                self.0 = srid;
+                Ok(())
+            }
+        }
+        let gml = r#"<gml:Point gml:id="p1" srsName="urn:ogc:def:crs:EPSG::4326" xmlns:gml="http://www.opengis.net/gml/3.2"><gml:pos>47.3769 8.5417</gml:pos></gml:Point>"#;
+        let mut srid = Srid(None);
+        Gml(gml).process_geom(&mut srid)?;
+        assert_eq!(srid.0, Some(4326));
+        Ok(())
+    }
+
+    #[test]
+    fn axis_order() -> Result<()> {
+        let gml = r#"<gml:LineString srsName="urn:ogc:def:crs:EPSG::4326" xmlns:gml="http://www.opengis.net/gml/3.2"><gml:posList>47 8 48 9</gml:posList></gml:LineString>"#;
+        let mut wkt_data: Vec<u8> = Vec::new();
+        read_gml_xy(gml.as_bytes(), None, &mut WktWriter::new(&mut wkt_data))?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "LINESTRING(8 47,9 48)"
+        );
+
+        let mut wkt_data: Vec<u8> = Vec::new();
+        read_gml_xy(
+            gml.as_bytes(),
+            Some(AxisOrder::XY),
+            &mut WktWriter::new(&mut wkt_data),
+        )?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "LINESTRING(47 8,48 9)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn polygon() -> Result<()> {
+        let gml = r#"<gml:Polygon xmlns:gml="http://www.opengis.net/gml/3.2">
+            <gml:exterior><gml:LinearRing><gml:posList>0 0 10 0 10 10 0 10 0 0</gml:posList></gml:LinearRing></gml:exterior>
+            <gml:interior><gml:LinearRing><gml:posList>2 2 4 2 4 4 2 2</gml:posList></gml:LinearRing></gml:interior>
+        </gml:Polygon>"#;
+        assert_eq!(
+            Gml(gml).to_wkt()?,
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,4 2,4 4,2 2))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multi_geometries() -> Result<()> {
+        let gml = r#"<gml:MultiSurface xmlns:gml="http://www.opengis.net/gml/3.2">
+            <gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing>
+                <gml:posList>0 0 1 0 1 1 0 0</gml:posList>
+            </gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember>
+            <gml:surfaceMember><gml:Polygon><gml:exterior><gml:LinearRing>
+                <gml:posList>5 5 6 5 6 6 5 5</gml:posList>
+            </gml:LinearRing></gml:exterior></gml:Polygon></gml:surfaceMember>
+        </gml:MultiSurface>"#;
+        assert_eq!(
+            Gml(gml).to_wkt()?,
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))"
+        );
+
+        let gml = r#"<gml:MultiCurve xmlns:gml="http://www.opengis.net/gml/3.2">
+            <gml:curveMember><gml:LineString><gml:posList>1 2 3 4</gml:posList></gml:LineString></gml:curveMember>
+            <gml:curveMember><gml:Curve><gml:segments>
+                <gml:LineStringSegment><gml:posList>5 6 7 8</gml:posList></gml:LineStringSegment>
+                <gml:LineStringSegment><gml:posList>7 8 9 10</gml:posList></gml:LineStringSegment>
+            </gml:segments></gml:Curve></gml:curveMember>
+        </gml:MultiCurve>"#;
+        assert_eq!(
+            Gml(gml).to_wkt()?,
+            "MULTILINESTRING((1 2,3 4),(5 6,7 8,9 10))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn srs_dimension() -> Result<()> {
+        let gml = r#"<gml:LineString srsDimension="3" xmlns:gml="http://www.opengis.net/gml/3.2"><gml:posList>1 2 3 4 5 6</gml:posList></gml:LineString>"#;
+        assert_eq!(Gml(gml).to_wkt()?, "LINESTRING(1 2,4 5)");
+
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt_data);
+        writer.dims = CoordDimensions::xyz();
+        Gml(gml).process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "LINESTRING(1 2 3,4 5 6)"
+        );
+
+        let gml = r#"<gml:LineString xmlns:gml="http://www.opengis.net/gml/3.2"><gml:posList>1 2 3</gml:posList></gml:LineString>"#;
+        assert!(Gml(gml).to_wkt().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn wfs_collection() -> Result<()> {
+        let gml = r#"<wfs:FeatureCollection xmlns:wfs="http://www.opengis.net/wfs/2.0" xmlns:gml="http://www.opengis.net/gml/3.2" xmlns:app="http://example.com/app">
+            <wfs:member><app:Road gml:id="r1"><app:geom><gml:LineString><gml:posList>1 2 3 4</gml:posList></gml:LineString></app:geom></app:Road></wfs:member>
+            <wfs:member><app:Road gml:id="r2"><app:geom><gml:Point><gml:pos>5 6</gml:pos></gml:Point></app:geom></app:Road></wfs:member>
+        </wfs:FeatureCollection>"#;
+        assert_eq!(
+            Gml(gml).to_wkt()?,
+            "GEOMETRYCOLLECTION(LINESTRING(1 2,3 4),POINT(5 6))"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use crate::error::Result;
+use crate::{CoordDimensions, GeomProcessor};
+use quick_xml::escape::escape;
+use std::io::Write;
+
+/// Coordinate axis order of written positions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AxisOrder {
+    /// Easting/longitude first
+    #[default]
+    XY,
+    /// Northing/latitude first, as required by e.g. `urn:ogc:def:crs:EPSG::4326`
+    YX,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Element {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiCurve,
+    MultiSurface,
+    MultiGeometry,
+}
+
+/// GML 3.2 geometry writer.
+///
+/// Multi-geometries are written as `MultiPoint`, `MultiCurve`, `MultiSurface`
+/// and `MultiGeometry`.
+pub struct GmlWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// `srsName` attribute of top-level geometries
+    pub srs_name: Option<String>,
+    pub axis_order: AxisOrder,
+    out: &'a mut W,
+    stack: Vec<Element>,
+}
+
+impl<'a, W: Write> GmlWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> GmlWriter<'a, W> {
+        GmlWriter {
+            dims: CoordDimensions::default(),
+            srs_name: None,
+            axis_order: AxisOrder::default(),
+            out,
+            stack: Vec::new(),
+        }
+    }
+
+    fn parent(&self) -> Option<Element> {
+        self.stack.last().copied()
+    }
+
+    /// Parent of the current element
+    fn grandparent(&self) -> Option<Element> {
+        self.stack.iter().rev().nth(1).copied()
+    }
+
+    /// Start geometry element with namespace and SRS attributes on top-level geometries
+    fn begin(&mut self, element: Element, name: &str) -> Result<()> {
+        if self.parent() == Some(Element::MultiGeometry) {
+            self.out.write_all(b"<gml:geometryMember>")?;
+        }
+        write!(self.out, "<gml:{}", name)?;
+        if self.stack.is_empty() {
+            self.out
+                .write_all(br#" xmlns:gml="http://www.opengis.net/gml/3.2""#)?;
+            if let Some(srs_name) = &self.srs_name {
+                write!(self.out, r#" srsName="{}""#, escape(srs_name))?;
+            }
+            if self.dims.z {
+                self.out.write_all(br#" srsDimension="3""#)?;
+            }
+        }
+        self.out.write_all(b">")?;
+        self.stack.push(element);
+        Ok(())
+    }
+
+    fn end(&mut self, name: &str) -> Result<()> {
+        self.stack.pop();
+        write!(self.out, "</gml:{}>", name)?;
+        if self.parent() == Some(Element::MultiGeometry) {
+            self.out.write_all(b"</gml:geometryMember>")?;
+        }
+        Ok(())
+    }
+
+    fn write_coord(&mut self, x: f64, y: f64, z: Option<f64>, idx: usize) -> Result<()> {
+        let (a, b) = match self.axis_order {
+            AxisOrder::XY => (x, y),
+            AxisOrder::YX => (y, x),
+        };
+        let pos = match z {
+            Some(z) if self.dims.z => format!("{} {} {}", a, b, z),
+            _ => format!("{} {}", a, b),
+        };
+        if self.parent() == Some(Element::MultiPoint) {
+            write!(
+                self.out,
+                "<gml:pointMember><gml:Point><gml:pos>{}</gml:pos></gml:Point></gml:pointMember>",
+                pos
+            )?;
+        } else {
+            if idx > 0 {
+                self.out.write_all(b" ")?;
+            }
+            self.out.write_all(pos.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> GeomProcessor for GmlWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.write_coord(x, y, None, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.write_coord(x, y, z, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Element::Point, "Point")?;
+        self.out.write_all(b"<gml:pos/>")?;
+        self.end("Point")
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Element::Point, "Point")?;
+        self.out.write_all(b"<gml:pos>")?;
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"</gml:pos>")?;
+        self.end("Point")
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Element::MultiPoint, "MultiPoint")
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end("MultiPoint")
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, idx: usize) -> Result<()> {
+        match self.parent() {
+            Some(Element::Polygon) => {
+                let boundary = if idx == 0 { "exterior" } else { "interior" };
+                write!(self.out, "<gml:{}><gml:LinearRing>", boundary)?;
+                self.stack.push(Element::LineString);
+            }
+            Some(Element::MultiCurve) => {
+                self.out.write_all(b"<gml:curveMember><gml:LineString>")?;
+                self.stack.push(Element::LineString);
+            }
+            _ => self.begin(Element::LineString, "LineString")?,
+        }
+        self.out.write_all(b"<gml:posList>")?;
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.out.write_all(b"</gml:posList>")?;
+        match self.grandparent() {
+            Some(Element::Polygon) => {
+                self.stack.pop();
+                let boundary = if idx == 0 { "exterior" } else { "interior" };
+                write!(self.out, "</gml:LinearRing></gml:{}>", boundary)?;
+            }
+            Some(Element::MultiCurve) => {
+                self.stack.pop();
+                self.out.write_all(b"</gml:LineString></gml:curveMember>")?;
+            }
+            _ => self.end("LineString")?,
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Element::MultiCurve, "MultiCurve")
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end("MultiCurve")
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if self.parent() == Some(Element::MultiSurface) {
+            self.out.write_all(b"<gml:surfaceMember><gml:Polygon>")?;
+            self.stack.push(Element::Polygon);
+            Ok(())
+        } else {
+            self.begin(Element::Polygon, "Polygon")
+        }
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if self.grandparent() == Some(Element::MultiSurface) {
+            self.stack.pop();
+            self.out.write_all(b"</gml:Polygon></gml:surfaceMember>")?;
+            Ok(())
+        } else {
+            self.end("Polygon")
+        }
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Element::MultiSurface, "MultiSurface")
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end("MultiSurface")
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Element::MultiGeometry, "MultiGeometry")
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end("MultiGeometry")
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::gml::Gml;
+    use crate::wkt::WktStr;
+    use crate::{GeozeroGeometry, ToGml, ToWkt};
+
+    const NS: &str = r#"xmlns:gml="http://www.opengis.net/gml/3.2""#;
+
+    #[test]
+    fn geometries() -> Result<()> {
+        assert_eq!(
+            WktStr("POINT(1 2)").to_gml()?,
+            format!("<gml:Point {}><gml:pos>1 2</gml:pos></gml:Point>", NS)
+        );
+        assert_eq!(
+            WktStr("POLYGON((0 0,10 0,10 10,0 0),(2 2,4 2,4 4,2 2))").to_gml()?,
+            format!("<gml:Polygon {}><gml:exterior><gml:LinearRing><gml:posList>0 0 10 0 10 10 0 0</gml:posList></gml:LinearRing></gml:exterior><gml:interior><gml:LinearRing><gml:posList>2 2 4 2 4 4 2 2</gml:posList></gml:LinearRing></gml:interior></gml:Polygon>", NS)
+        );
+        assert_eq!(
+            WktStr("MULTILINESTRING((1 2,3 4),(5 6,7 8))").to_gml()?,
+            format!("<gml:MultiCurve {}><gml:curveMember><gml:LineString><gml:posList>1 2 3 4</gml:posList></gml:LineString></gml:curveMember><gml:curveMember><gml:LineString><gml:posList>5 6 7 8</gml:posList></gml:LineString></gml:curveMember></gml:MultiCurve>", NS)
+        );
+        assert_eq!(
+            WktStr("GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(3 4,5 6))").to_gml()?,
+            format!("<gml:MultiGeometry {}><gml:geometryMember><gml:Point><gml:pos>1 2</gml:pos></gml:Point></gml:geometryMember><gml:geometryMember><gml:LineString><gml:posList>3 4 5 6</gml:posList></gml:LineString></gml:geometryMember></gml:MultiGeometry>", NS)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn srs_and_axis_order() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GmlWriter::new(&mut out);
+        writer.srs_name = Some("urn:ogc:def:crs:EPSG::4326".to_string());
+        writer.axis_order = AxisOrder::YX;
+        writer.dims = CoordDimensions::xyz();
+        crate::geojson::GeoJson(
+            r#"{"type": "MultiPoint", "coordinates": [[8.5, 47.3, 408], [9, 47, 400]]}"#,
+        )
+        .process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            format!(
+                r#"<gml:MultiPoint {} srsName="urn:ogc:def:crs:EPSG::4326" srsDimension="3"><gml:pointMember><gml:Point><gml:pos>47.3 8.5 408</gml:pos></gml:Point></gml:pointMember><gml:pointMember><gml:Point><gml:pos>47 9 400</gml:pos></gml:Point></gml:pointMember></gml:MultiPoint>"#,
+                NS
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        let wkt = "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5),(5.2 5.2,5.4 5.2,5.4 5.4,5.2 5.2)))";
+        let gml = WktStr(wkt).to_gml()?;
+        assert_eq!(Gml(&gml).to_wkt()?, wkt);
+        Ok(())
+    }
+}
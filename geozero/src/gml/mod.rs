@@ -0,0 +1,32 @@
+//! GML conversions.
+mod gml_reader;
+mod gml_writer;
+
+pub use gml_reader::*;
+#[cfg(feature = "with-georss")]
+pub(crate) use gml_reader::{is_geometry, process_gml_element};
+pub use gml_writer::{AxisOrder, GmlWriter};
+
+pub(crate) mod conversion {
+    use super::gml_writer::*;
+    use crate::error::Result;
+    use crate::GeozeroGeometry;
+
+    /// Convert to GML.
+    pub trait ToGml {
+        /// Convert to GML 3.2 geometry fragment.
+        fn to_gml(&self) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToGml for T {
+        fn to_gml(&self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = GmlWriter::new(&mut out);
+            writer.dims = self.dims();
+            self.process_geom(&mut writer)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
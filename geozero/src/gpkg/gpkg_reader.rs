@@ -0,0 +1,266 @@
+use super::{quote_ident, sql_error};
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_wkb_slice, WkbDialect};
+use crate::{ColumnValue, DatasetMetadata, FeatureProcessor};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection, SqliteRow};
+use sqlx::{Column, ConnectOptions, Row, TypeInfo, ValueRef};
+use std::convert::TryFrom;
+
+/// GeoPackage feature table reader.
+///
+/// Besides reading all features, single features can be fetched by their feature id
+/// (`fid`) or by their position in the table without iterating from the start.
+/// The feature id column is not emitted as property. Title, description and CRS definition
+/// from the GeoPackage metadata tables are emitted as [DatasetMetadata].
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::gpkg::GpkgReader;
+/// use geozero::geojson::GeoJsonWriter;
+///
+/// # async fn read() -> geozero::error::Result<()> {
+/// let mut reader = GpkgReader::open("places.gpkg", "places").await?;
+/// let mut out: Vec<u8> = Vec::new();
+/// reader.select_by_id(&[3, 42], &mut GeoJsonWriter::new(&mut out)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgReader {
+    /// Name of feature table
+    pub table_name: String,
+    /// Name of geometry column
+    pub geometry_column: String,
+    /// Name of feature id column
+    pub fid_column: String,
+    /// SRID of geometries
+    pub srid: Option<i32>,
+    /// Title, description and CRS definition of feature table
+    pub metadata: DatasetMetadata,
+    conn: SqliteConnection,
+}
+
+impl GpkgReader {
+    /// Reader for feature table `table_name` on `conn`.
+    ///
+    /// Geometry column and SRID are read from `gpkg_geometry_columns`, the feature id
+    /// column is the integer primary key of the table. Metadata is read from
+    /// `gpkg_contents` and `gpkg_spatial_ref_sys`.
+    pub async fn new(mut conn: SqliteConnection, table_name: &str) -> Result<Self> {
+        let (geometry_column, srs_id): (String, i32) = sqlx::query_as(
+            "SELECT column_name, srs_id FROM gpkg_geometry_columns WHERE table_name = ?",
+        )
+        .bind(table_name)
+        .fetch_optional(&mut conn)
+        .await
+        .map_err(sql_error)?
+        .ok_or_else(|| GeozeroError::Dataset(format!("no feature table `{}`", table_name)))?;
+        let sql = format!(
+            "SELECT name FROM pragma_table_info({}) WHERE pk = 1",
+            quote_literal(table_name)
+        );
+        let fid_column: Option<(String,)> = sqlx::query_as(&sql)
+            .fetch_optional(&mut conn)
+            .await
+            .map_err(sql_error)?;
+        let contents: Option<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT c.identifier, c.description, s.definition FROM gpkg_contents c \
+             LEFT JOIN gpkg_spatial_ref_sys s ON s.srs_id = c.srs_id WHERE c.table_name = ?",
+        )
+        .bind(table_name)
+        .fetch_optional(&mut conn)
+        .await
+        .map_err(sql_error)?;
+        let (title, description, crs_wkt) = contents.unwrap_or_default();
+        let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty() && s != "undefined");
+        let metadata = DatasetMetadata {
+            title: non_empty(title),
+            description: non_empty(description),
+            crs_wkt: non_empty(crs_wkt),
+            ..Default::default()
+        };
+        Ok(GpkgReader {
+            table_name: table_name.to_string(),
+            geometry_column,
+            fid_column: fid_column.map_or_else(|| "fid".to_string(), |c| c.0),
+            srid: if srs_id > 0 { Some(srs_id) } else { None },
+            metadata,
+            conn,
+        })
+    }
+
+    /// Reader for feature table `table_name` in GeoPackage file `path`.
+    pub async fn open(path: &str, table_name: &str) -> Result<Self> {
+        let conn = SqliteConnectOptions::new()
+            .filename(path)
+            .read_only(true)
+            .connect()
+            .await
+            .map_err(sql_error)?;
+        GpkgReader::new(conn, table_name).await
+    }
+
+    /// Consume reader and return database connection.
+    pub fn into_inner(self) -> SqliteConnection {
+        self.conn
+    }
+
+    /// Number of features in table.
+    pub async fn feature_count(&mut self) -> Result<u64> {
+        let sql = format!("SELECT count(*) FROM {}", quote_ident(&self.table_name));
+        let count: (i64,) = sqlx::query_as(&sql)
+            .fetch_one(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        Ok(count.0 as u64)
+    }
+
+    /// Process all features ordered by feature id.
+    pub async fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY {}",
+            quote_ident(&self.table_name),
+            quote_ident(&self.fid_column)
+        );
+        let rows = sqlx::query(&sql)
+            .fetch_all(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        self.process_rows(&rows, processor)
+    }
+
+    /// Process features with feature ids `ids`.
+    ///
+    /// Features are processed in the order of `ids`, missing ids are skipped.
+    pub async fn select_by_id<P: FeatureProcessor>(
+        &mut self,
+        ids: &[i64],
+        processor: &mut P,
+    ) -> Result<()> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE {} = ?",
+            quote_ident(&self.table_name),
+            quote_ident(&self.fid_column)
+        );
+        let mut rows = Vec::with_capacity(ids.len());
+        for id in ids {
+            let row = sqlx::query(&sql)
+                .bind(id)
+                .fetch_optional(&mut self.conn)
+                .await
+                .map_err(sql_error)?;
+            rows.extend(row);
+        }
+        self.process_rows(&rows, processor)
+    }
+
+    /// Process the feature at position `feature_index` in feature id order.
+    ///
+    /// Returns `false` if the table has fewer features.
+    pub async fn seek<P: FeatureProcessor>(
+        &mut self,
+        feature_index: u64,
+        processor: &mut P,
+    ) -> Result<bool> {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY {} LIMIT 1 OFFSET ?",
+            quote_ident(&self.table_name),
+            quote_ident(&self.fid_column)
+        );
+        let offset = i64::try_from(feature_index)
+            .map_err(|_| GeozeroError::Dataset(format!("invalid index {}", feature_index)))?;
+        let row = sqlx::query(&sql)
+            .bind(offset)
+            .fetch_optional(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        let rows: Vec<SqliteRow> = row.into_iter().collect();
+        self.process_rows(&rows, processor)?;
+        Ok(!rows.is_empty())
+    }
+
+    fn process_rows<P: FeatureProcessor>(
+        &self,
+        rows: &[SqliteRow],
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.dataset_begin(Some(&self.table_name))?;
+        processor.dataset_srid(self.srid)?;
+        if self.metadata != DatasetMetadata::default() {
+            processor.dataset_metadata(&self.metadata)?;
+        }
+        for (idx, row) in rows.iter().enumerate() {
+            self.process_row(row, idx as u64, processor)?;
+        }
+        processor.dataset_end()
+    }
+
+    fn process_row<P: FeatureProcessor>(
+        &self,
+        row: &SqliteRow,
+        idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.feature_begin(idx)?;
+        processor.properties_begin()?;
+        let mut prop_idx = 0;
+        for column in row.columns() {
+            let name = column.name();
+            if name == self.geometry_column || name == self.fid_column {
+                continue;
+            }
+            let i = column.ordinal();
+            let value = row.try_get_raw(i).map_err(sql_error)?;
+            if value.is_null() {
+                continue;
+            }
+            let finish = match value.type_info().name() {
+                "INTEGER" if column.type_info().name() == "BOOLEAN" => {
+                    let v: bool = row.try_get_unchecked(i).map_err(sql_error)?;
+                    processor.property(prop_idx, name, &ColumnValue::Bool(v))?
+                }
+                "INTEGER" => {
+                    let v: i64 = row.try_get_unchecked(i).map_err(sql_error)?;
+                    processor.property(prop_idx, name, &ColumnValue::Long(v))?
+                }
+                "REAL" => {
+                    let v: f64 = row.try_get_unchecked(i).map_err(sql_error)?;
+                    processor.property(prop_idx, name, &ColumnValue::Double(v))?
+                }
+                "BLOB" => {
+                    let v: &[u8] = row.try_get_unchecked(i).map_err(sql_error)?;
+                    processor.property(prop_idx, name, &ColumnValue::Binary(v))?
+                }
+                _ => {
+                    let v: &str = row.try_get_unchecked(i).map_err(sql_error)?;
+                    let value = match column.type_info().name() {
+                        "DATE" | "DATETIME" => ColumnValue::DateTime(v),
+                        _ => ColumnValue::String(v),
+                    };
+                    processor.property(prop_idx, name, &value)?
+                }
+            };
+            prop_idx += 1;
+            if finish {
+                break;
+            }
+        }
+        processor.properties_end()?;
+        let geometry: Option<&[u8]> = row
+            .try_get_unchecked(self.geometry_column.as_str())
+            .map_err(sql_error)?;
+        if let Some(geometry) = geometry {
+            processor.geometry_begin()?;
+            process_wkb_slice(geometry, WkbDialect::Geopackage, processor)
+                .map_err(|e| e.in_feature(idx))?;
+            processor.geometry_end()?;
+        } else {
+            processor.null_geometry()?;
+        }
+        processor.feature_end(idx)
+    }
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
@@ -0,0 +1,404 @@
+use super::{quote_ident, sql_error};
+use crate::error::{GeozeroError, Result};
+use crate::processing::record::OwnedValue;
+use crate::processing::{Extent, ExtentProcessor};
+use crate::wkb::{gpkg_geometry_type, WkbDialect, WkbWriter};
+use crate::{
+    process_datasource_async, AsyncFeatureProcessor, ColumnValue, CoordDimensions, DatasetMetadata,
+    FeatureProcessor, GeomProcessor, GeozeroDatasource, PropertyProcessor, RecordedFeature,
+};
+use async_trait::async_trait;
+use sqlx::query::Query;
+use sqlx::sqlite::{SqliteArguments, SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Sqlite};
+use std::convert::TryFrom;
+
+const WGS84_DEFINITION: &str = r#"GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4326"]]"#;
+
+/// GeoPackage core tables and default spatial reference systems
+const GPKG_TABLES: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (srs_name TEXT NOT NULL, srs_id INTEGER PRIMARY KEY, organization TEXT NOT NULL, organization_coordsys_id INTEGER NOT NULL, definition TEXT NOT NULL, description TEXT)",
+    "CREATE TABLE IF NOT EXISTS gpkg_contents (table_name TEXT NOT NULL PRIMARY KEY, data_type TEXT NOT NULL, identifier TEXT UNIQUE, description TEXT DEFAULT '', last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')), min_x DOUBLE, min_y DOUBLE, max_x DOUBLE, max_y DOUBLE, srs_id INTEGER, CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id))",
+    "CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (table_name TEXT NOT NULL, column_name TEXT NOT NULL, geometry_type_name TEXT NOT NULL, srs_id INTEGER NOT NULL, z TINYINT NOT NULL, m TINYINT NOT NULL, CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name), CONSTRAINT uk_gc_table_name UNIQUE (table_name), CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name), CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id))",
+    "CREATE TABLE IF NOT EXISTS gpkg_extensions (table_name TEXT, column_name TEXT, extension_name TEXT NOT NULL, definition TEXT NOT NULL, scope TEXT NOT NULL, CONSTRAINT ge_tce UNIQUE (table_name, column_name, extension_name))",
+    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system')",
+    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system')",
+];
+
+/// RTree maintenance triggers of the `gpkg_rtree_index` extension
+const RTREE_TRIGGERS: &[&str] = &[
+    r#"CREATE TRIGGER "<i>_insert" AFTER INSERT ON "<t>" WHEN (new."<c>" NOT NULL AND NOT ST_IsEmpty(NEW."<c>")) BEGIN INSERT OR REPLACE INTO "<i>" VALUES (NEW."fid", ST_MinX(NEW."<c>"), ST_MaxX(NEW."<c>"), ST_MinY(NEW."<c>"), ST_MaxY(NEW."<c>")); END"#,
+    r#"CREATE TRIGGER "<i>_update1" AFTER UPDATE OF "<c>" ON "<t>" WHEN OLD."fid" = NEW."fid" AND (NEW."<c>" NOTNULL AND NOT ST_IsEmpty(NEW."<c>")) BEGIN INSERT OR REPLACE INTO "<i>" VALUES (NEW."fid", ST_MinX(NEW."<c>"), ST_MaxX(NEW."<c>"), ST_MinY(NEW."<c>"), ST_MaxY(NEW."<c>")); END"#,
+    r#"CREATE TRIGGER "<i>_update2" AFTER UPDATE OF "<c>" ON "<t>" WHEN OLD."fid" = NEW."fid" AND (NEW."<c>" ISNULL OR ST_IsEmpty(NEW."<c>")) BEGIN DELETE FROM "<i>" WHERE id = OLD."fid"; END"#,
+    r#"CREATE TRIGGER "<i>_update3" AFTER UPDATE ON "<t>" WHEN OLD."fid" != NEW."fid" AND (NEW."<c>" NOTNULL AND NOT ST_IsEmpty(NEW."<c>")) BEGIN DELETE FROM "<i>" WHERE id = OLD."fid"; INSERT OR REPLACE INTO "<i>" VALUES (NEW."fid", ST_MinX(NEW."<c>"), ST_MaxX(NEW."<c>"), ST_MinY(NEW."<c>"), ST_MaxY(NEW."<c>")); END"#,
+    r#"CREATE TRIGGER "<i>_update4" AFTER UPDATE ON "<t>" WHEN OLD."fid" != NEW."fid" AND (NEW."<c>" ISNULL OR ST_IsEmpty(NEW."<c>")) BEGIN DELETE FROM "<i>" WHERE id IN (OLD."fid", NEW."fid"); END"#,
+    r#"CREATE TRIGGER "<i>_delete" AFTER DELETE ON "<t>" WHEN old."<c>" NOT NULL BEGIN DELETE FROM "<i>" WHERE id = OLD."fid"; END"#,
+];
+
+/// GeoPackage dataset writer.
+///
+/// Creates the GeoPackage core tables, a feature table with columns added from the
+/// property types of the first feature containing them, and the `gpkg_contents` and
+/// `gpkg_geometry_columns` entries including extent and geometry type of the written features.
+/// With `spatial_index`, an RTree index with maintenance triggers is created.
+///
+/// Features are inserted in a transaction, which is committed at the end of each batch
+/// and at the end of the dataset.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJsonString;
+/// use geozero::gpkg::write_gpkg;
+///
+/// # async fn convert() -> geozero::error::Result<()> {
+/// let geojson = GeoJsonString(std::fs::read_to_string("places.json")?);
+/// write_gpkg(geojson, "places.gpkg", "places").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgWriter {
+    /// Name of feature table
+    pub table_name: String,
+    /// Name of geometry column (default: `geom`)
+    pub geometry_column: String,
+    pub dims: CoordDimensions,
+    /// SRID of geometries, defaults to the dataset SRID
+    pub srid: Option<i32>,
+    /// Create RTree spatial index
+    pub spatial_index: bool,
+    conn: SqliteConnection,
+    columns: Vec<String>,
+    geometry_type: Option<String>,
+    extent: Option<Extent>,
+    /// Dataset metadata of reader
+    metadata: DatasetMetadata,
+}
+
+impl GpkgWriter {
+    /// Writer for feature table `table_name` on `conn`.
+    pub fn new(conn: SqliteConnection, table_name: &str) -> Self {
+        GpkgWriter {
+            table_name: table_name.to_string(),
+            geometry_column: "geom".to_string(),
+            dims: CoordDimensions::default(),
+            srid: None,
+            spatial_index: true,
+            conn,
+            columns: Vec::new(),
+            geometry_type: None,
+            extent: None,
+            metadata: DatasetMetadata::default(),
+        }
+    }
+
+    /// Writer for feature table `table_name` in GeoPackage file `path`, which is created if missing.
+    pub async fn create(path: &str, table_name: &str) -> Result<Self> {
+        let conn = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .connect()
+            .await
+            .map_err(sql_error)?;
+        Ok(GpkgWriter::new(conn, table_name))
+    }
+
+    /// Consume writer and return database connection.
+    pub fn into_inner(self) -> SqliteConnection {
+        self.conn
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<()> {
+        sqlx::query(sql)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    fn rtree_name(&self) -> String {
+        format!("rtree_{}_{}", self.table_name, self.geometry_column)
+    }
+
+    fn srs_id(&self) -> i32 {
+        self.srid.unwrap_or(0)
+    }
+
+    async fn add_column(&mut self, name: &str, value: &ColumnValue<'_>) -> Result<()> {
+        let sql = format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            quote_ident(&self.table_name),
+            quote_ident(name),
+            sql_type(value)
+        );
+        self.execute(&sql).await?;
+        self.columns.push(name.to_string());
+        Ok(())
+    }
+
+    fn update_geometry_type(&mut self, gpkg: &[u8]) -> Result<()> {
+        let name = format!("{:?}", gpkg_geometry_type(gpkg)?).to_uppercase();
+        self.geometry_type = match self.geometry_type.take() {
+            None => Some(name),
+            Some(current) if current != name => Some("GEOMETRY".to_string()),
+            current => current,
+        };
+        Ok(())
+    }
+
+    /// Register SRS, feature table and spatial index in GeoPackage metadata tables
+    async fn write_metadata(&mut self) -> Result<()> {
+        let srs_id = self.srs_id();
+        if srs_id == 4326 {
+            sqlx::query("INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('WGS 84 geodetic', 4326, 'EPSG', 4326, ?, 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid')")
+                .bind(WGS84_DEFINITION)
+                .execute(&mut self.conn)
+                .await
+                .map_err(sql_error)?;
+        } else {
+            sqlx::query(
+                "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (?, ?, 'EPSG', ?, ?, NULL)",
+            )
+            .bind(format!("EPSG:{}", srs_id))
+            .bind(srs_id)
+            .bind(srs_id)
+            .bind(self.metadata.crs_wkt.as_deref().unwrap_or("undefined"))
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        }
+        let extent = self.extent.map(|e| e.bbox());
+        sqlx::query("INSERT OR REPLACE INTO gpkg_contents (table_name, data_type, identifier, description, min_x, min_y, max_x, max_y, srs_id) VALUES (?, 'features', ?, ?, ?, ?, ?, ?, ?)")
+            .bind(self.table_name.as_str())
+            .bind(self.metadata.title.as_deref().unwrap_or(&self.table_name))
+            .bind(self.metadata.description.as_deref().unwrap_or(""))
+            .bind(extent.map(|b| b[0]))
+            .bind(extent.map(|b| b[1]))
+            .bind(extent.map(|b| b[2]))
+            .bind(extent.map(|b| b[3]))
+            .bind(srs_id)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        sqlx::query("INSERT OR REPLACE INTO gpkg_geometry_columns VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(self.table_name.as_str())
+            .bind(self.geometry_column.as_str())
+            .bind(self.geometry_type.as_deref().unwrap_or("GEOMETRY"))
+            .bind(srs_id)
+            .bind(self.dims.z as i32)
+            .bind(self.dims.m as i32)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        if self.spatial_index {
+            sqlx::query("INSERT OR IGNORE INTO gpkg_extensions VALUES (?, ?, 'gpkg_rtree_index', 'http://www.geopackage.org/spec120/#extension_rtree', 'write-only')")
+                .bind(self.table_name.as_str())
+                .bind(self.geometry_column.as_str())
+                .execute(&mut self.conn)
+                .await
+                .map_err(sql_error)?;
+            // Index is filled while inserting, triggers keep it up to date afterwards
+            let rtree = self.rtree_name();
+            for trigger in RTREE_TRIGGERS {
+                let sql = trigger
+                    .replace("<i>", &rtree.replace('"', "\"\""))
+                    .replace("<t>", &self.table_name.replace('"', "\"\""))
+                    .replace("<c>", &self.geometry_column.replace('"', "\"\""));
+                self.execute(&sql).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GeoPackage column type of property value
+fn sql_type(value: &ColumnValue) -> &'static str {
+    match value {
+        ColumnValue::Bool(_) => "BOOLEAN",
+        ColumnValue::Byte(_) | ColumnValue::UByte(_) => "TINYINT",
+        ColumnValue::Short(_) | ColumnValue::UShort(_) => "SMALLINT",
+        ColumnValue::Int(_)
+        | ColumnValue::UInt(_)
+        | ColumnValue::Long(_)
+        | ColumnValue::ULong(_) => "INTEGER",
+        ColumnValue::Float(_) => "FLOAT",
+        ColumnValue::Double(_) => "DOUBLE",
+        ColumnValue::String(_) | ColumnValue::Json(_) => "TEXT",
+        ColumnValue::DateTime(_) => "DATETIME",
+        ColumnValue::Binary(_) => "BLOB",
+    }
+}
+
+fn bind_value<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    name: &str,
+    value: OwnedValue,
+) -> Result<Query<'q, Sqlite, SqliteArguments<'q>>> {
+    Ok(match value {
+        OwnedValue::Byte(v) => query.bind(i64::from(v)),
+        OwnedValue::UByte(v) => query.bind(i64::from(v)),
+        OwnedValue::Bool(v) => query.bind(v),
+        OwnedValue::Short(v) => query.bind(i64::from(v)),
+        OwnedValue::UShort(v) => query.bind(i64::from(v)),
+        OwnedValue::Int(v) => query.bind(i64::from(v)),
+        OwnedValue::UInt(v) => query.bind(i64::from(v)),
+        OwnedValue::Long(v) => query.bind(v),
+        OwnedValue::ULong(v) => {
+            query.bind(i64::try_from(v).map_err(|_| {
+                GeozeroError::Property(format!("`{}` value {} out of range", name, v))
+            })?)
+        }
+        OwnedValue::Float(v) => query.bind(f64::from(v)),
+        OwnedValue::Double(v) => query.bind(v),
+        OwnedValue::String(v) | OwnedValue::Json(v) | OwnedValue::DateTime(v) => query.bind(v),
+        OwnedValue::Binary(v) => query.bind(v),
+    })
+}
+
+/// Properties of a recorded feature
+#[derive(Default)]
+struct PropertyCollector(Vec<(String, OwnedValue)>);
+
+impl GeomProcessor for PropertyCollector {}
+
+impl PropertyProcessor for PropertyCollector {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.0.push((name.to_string(), value.into()));
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for PropertyCollector {}
+
+#[async_trait]
+impl AsyncFeatureProcessor for GpkgWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    async fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        // "GPKG" application id and version 1.3
+        self.execute("PRAGMA application_id = 1196444487").await?;
+        self.execute("PRAGMA user_version = 10300").await?;
+        self.execute("BEGIN").await?;
+        for sql in GPKG_TABLES {
+            self.execute(sql).await?;
+        }
+        let sql = format!(
+            "CREATE TABLE {} (fid INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, {} GEOMETRY)",
+            quote_ident(&self.table_name),
+            quote_ident(&self.geometry_column)
+        );
+        self.execute(&sql).await?;
+        if self.spatial_index {
+            let sql = format!(
+                "CREATE VIRTUAL TABLE {} USING rtree(id, minx, maxx, miny, maxy)",
+                quote_ident(&self.rtree_name())
+            );
+            self.execute(&sql).await?;
+        }
+        self.columns.clear();
+        self.geometry_type = None;
+        self.extent = None;
+        self.metadata = DatasetMetadata::default();
+        Ok(())
+    }
+    /// Use dataset SRID, if no SRID is set
+    async fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.srid.is_none() {
+            self.srid = srid;
+        }
+        Ok(())
+    }
+    /// Use title, description and CRS definition in GeoPackage metadata
+    async fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.metadata = metadata.clone();
+        Ok(())
+    }
+    async fn dataset_end(&mut self) -> Result<()> {
+        self.write_metadata().await?;
+        self.execute("COMMIT").await
+    }
+    async fn batch_end(&mut self) -> Result<()> {
+        self.execute("COMMIT").await?;
+        self.execute("BEGIN").await
+    }
+    async fn feature(&mut self, idx: u64, mut feature: RecordedFeature) -> Result<()> {
+        let mut properties = PropertyCollector::default();
+        feature.process(&mut properties)?;
+        let mut extent = ExtentProcessor::new();
+        feature.process(&mut extent)?;
+        let mut gpkg: Vec<u8> = Vec::new();
+        {
+            let mut writer = WkbWriter::new(&mut gpkg, WkbDialect::Geopackage);
+            writer.dims = self.dims;
+            writer.srid = Some(self.srs_id());
+            if let Some(e) = extent.extent() {
+                writer.envelope = vec![e.minx, e.maxx, e.miny, e.maxy];
+            }
+            feature
+                .process(&mut writer)
+                .map_err(|e| e.in_feature(idx))?;
+        }
+        let geometry = if gpkg.is_empty() {
+            None
+        } else {
+            self.update_geometry_type(&gpkg)?;
+            Some(gpkg)
+        };
+        if let Some(e) = extent.extent() {
+            match self.extent.as_mut() {
+                Some(extent) => extent.merge(&e),
+                None => self.extent = Some(e),
+            }
+        }
+
+        for (name, value) in &properties.0 {
+            if !self.columns.contains(name) {
+                self.add_column(name, &value.as_column_value()).await?;
+            }
+        }
+        let mut columns = vec![quote_ident(&self.geometry_column)];
+        columns.extend(properties.0.iter().map(|(name, _)| quote_ident(name)));
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote_ident(&self.table_name),
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", ")
+        );
+        let mut query = sqlx::query(&sql).bind(geometry);
+        for (name, value) in properties.0 {
+            query = bind_value(query, &name, value)?;
+        }
+        let fid = query
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?
+            .last_insert_rowid();
+
+        if let (true, Some(e)) = (self.spatial_index, extent.extent()) {
+            let sql = format!(
+                "INSERT INTO {} VALUES (?, ?, ?, ?, ?)",
+                quote_ident(&self.rtree_name())
+            );
+            sqlx::query(&sql)
+                .bind(fid)
+                .bind(e.minx)
+                .bind(e.maxx)
+                .bind(e.miny)
+                .bind(e.maxy)
+                .execute(&mut self.conn)
+                .await
+                .map_err(sql_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write all features of `datasource` into table `table_name` of GeoPackage file `path`.
+pub async fn write_gpkg<D>(datasource: D, path: &str, table_name: &str) -> Result<()>
+where
+    D: GeozeroDatasource + Send + 'static,
+{
+    let mut writer = GpkgWriter::create(path, table_name).await?;
+    process_datasource_async(datasource, &mut writer).await
+}
@@ -4,6 +4,9 @@
 //!
 //! Geometry types implementing [FromWkb](crate::wkb::FromWkb) can be decoded from Geopackage geometries using [wkb::Decode](crate::wkb::Decode).
 //!
+//! Complete GeoPackage datasets can be written with `GpkgWriter` (requires feature `with-tokio`).
+//! Feature tables are read with `GpkgReader`, which also fetches single features by id or position.
+//!
 //! # Usage example
 //!
 //! Select geo-types geometries from a Geopackage:
@@ -28,5 +31,21 @@
 //! ```
 
 mod geopackage;
+mod gpkg_reader;
+#[cfg(feature = "with-tokio")]
+mod gpkg_writer;
 
 pub use geopackage::*;
+pub use gpkg_reader::*;
+#[cfg(feature = "with-tokio")]
+pub use gpkg_writer::*;
+
+use crate::error::GeozeroError;
+
+fn sql_error(e: sqlx::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -1,5 +1,7 @@
 use crate::error::{GeozeroError, Result};
+use crate::timestamps::{decimal_year, unix_nanos};
 use std::io;
+use time::OffsetDateTime;
 
 /// GPX reader
 pub struct GpxReader<'a, R: io::Read>(pub &'a mut R);
@@ -136,15 +138,42 @@ fn process_waypoints_iter<'a, P: crate::GeomProcessor>(
     wrap_point: bool,
 ) -> crate::error::Result<()> {
     for waypoint in iter {
-        let point = waypoint.point();
         if wrap_point {
             processor.point_begin(*index)?;
-            processor.xy(point.x(), point.y(), 0)?;
+            process_waypoint(waypoint, processor, 0)?;
             processor.point_end(*index)?;
         } else {
-            processor.xy(point.x(), point.y(), *index)?;
+            process_waypoint(waypoint, processor, *index)?;
         }
         *index += 1;
     }
     Ok(())
 }
+
+/// Process waypoint coordinates with elevation as `z` and time as `t` (decimal year)
+/// and `tm` (nanoseconds since Unix epoch).
+fn process_waypoint<P: crate::GeomProcessor>(
+    waypoint: &gpx::Waypoint,
+    processor: &mut P,
+    idx: usize,
+) -> crate::error::Result<()> {
+    let point = waypoint.point();
+    if processor.multi_dim() {
+        let dims = processor.dimensions();
+        let z = if dims.z { waypoint.elevation } else { None };
+        let time = waypoint.time.map(OffsetDateTime::from);
+        let t = if dims.t {
+            time.and_then(decimal_year)
+        } else {
+            None
+        };
+        let tm = if dims.tm {
+            time.and_then(unix_nanos)
+        } else {
+            None
+        };
+        processor.coordinate(point.x(), point.y(), z, None, t, tm, idx)
+    } else {
+        processor.xy(point.x(), point.y(), idx)
+    }
+}
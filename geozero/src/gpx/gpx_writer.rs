@@ -0,0 +1,209 @@
+use crate::error::{GeozeroError, Result};
+use crate::timestamps::{from_decimal_year, from_unix_nanos};
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// GPX 1.1 writer.
+///
+/// Points are written as waypoints and (multi)linestrings as tracks. Elevation is taken
+/// from `z`, timestamps from `tm` (nanoseconds since Unix epoch) or `t` (decimal year).
+/// A `name` property is used as waypoint or track name.
+///
+/// The document is written at the end of the dataset.
+pub struct GpxWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    out: &'a mut W,
+    gpx: gpx::Gpx,
+    name: Option<String>,
+    track: Option<gpx::Track>,
+    line_is_track: bool,
+    in_points: bool,
+}
+
+impl<'a, W: Write> GpxWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> GpxWriter<'a, W> {
+        let gpx = gpx::Gpx {
+            version: gpx::GpxVersion::Gpx11,
+            creator: Some("geozero".to_string()),
+            ..Default::default()
+        };
+        GpxWriter {
+            dims: CoordDimensions {
+                z: true,
+                m: false,
+                t: true,
+                tm: true,
+            },
+            out,
+            gpx,
+            name: None,
+            track: None,
+            line_is_track: false,
+            in_points: false,
+        }
+    }
+
+    fn add_point(&mut self, wpt: gpx::Waypoint) -> Result<()> {
+        if self.in_points {
+            let mut wpt = wpt;
+            wpt.name = self.name.clone();
+            self.gpx.waypoints.push(wpt);
+        } else if let Some(segment) = self
+            .track
+            .as_mut()
+            .and_then(|track| track.segments.last_mut())
+        {
+            segment.points.push(wpt);
+        } else {
+            return Err(GeozeroError::Geometry(
+                "Coordinate outside of point or line".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn begin_track(&mut self) {
+        let mut track = gpx::Track::new();
+        track.name = self.name.clone();
+        self.track = Some(track);
+    }
+
+    fn end_track(&mut self) {
+        if let Some(track) = self.track.take() {
+            self.gpx.tracks.push(track);
+        }
+    }
+}
+
+impl<W: Write> GeomProcessor for GpxWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_point(gpx::Waypoint::new(geo_types::Point::new(x, y)))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        let mut wpt = gpx::Waypoint::new(geo_types::Point::new(x, y));
+        wpt.elevation = z;
+        let time = match (tm, t) {
+            (Some(tm), _) => from_unix_nanos(tm),
+            (None, Some(t)) => from_decimal_year(t),
+            (None, None) => None,
+        };
+        wpt.time = time.map(Into::into);
+        self.add_point(wpt)
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.in_points = true;
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_points = false;
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.in_points = true;
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_points = false;
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if self.track.is_none() {
+            self.begin_track();
+            self.line_is_track = true;
+        }
+        if let Some(track) = self.track.as_mut() {
+            track.segments.push(gpx::TrackSegment::new());
+        }
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if self.line_is_track {
+            self.line_is_track = false;
+            self.end_track();
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_track();
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_track();
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Err(GeozeroError::Geometry(
+            "Polygons are not supported by GPX".to_string(),
+        ))
+    }
+}
+
+impl<W: Write> PropertyProcessor for GpxWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if name == "name" {
+            self.name = Some(value.to_string());
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write> FeatureProcessor for GpxWriter<'_, W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        gpx::write(&self.gpx, &mut self.out).map_err(|e| GeozeroError::Dataset(e.to_string()))
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.name = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::gpx::Gpx;
+    use crate::wkt::WktStr;
+    use crate::GeozeroGeometry;
+
+    #[test]
+    fn write_track() {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GpxWriter::new(&mut out);
+        WktStr("MULTILINESTRING((1 2,3 4),(5 6,7 8))")
+            .process_geom(&mut writer)
+            .unwrap();
+        WktStr("POINT(10 20)").process_geom(&mut writer).unwrap();
+        writer.dataset_end().unwrap();
+
+        let gpx_str = std::str::from_utf8(&out).unwrap();
+        assert_eq!(
+            crate::ToWkt::to_wkt(&Gpx(gpx_str)).unwrap(),
+            "GEOMETRYCOLLECTION(POINT(10 20),MULTILINESTRING((1 2,3 4),(5 6,7 8)))"
+        );
+    }
+
+    #[test]
+    fn write_timestamps() {
+        let gpx_str = include_str!("../../tests/data/wikipedia_example.gpx");
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GpxWriter::new(&mut out);
+        Gpx(gpx_str).process_geom(&mut writer).unwrap();
+        writer.dataset_end().unwrap();
+
+        let written = std::str::from_utf8(&out).unwrap();
+        assert!(written.contains("<ele>4.94</ele>"));
+        assert!(written.contains("2009-10-17T18:37:31"));
+    }
+}
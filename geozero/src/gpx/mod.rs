@@ -1,4 +1,50 @@
+//! GPX conversions.
 mod gpx_reader;
+mod gpx_writer;
 
 pub use gpx_reader::read_gpx;
 pub use gpx_reader::{Gpx, GpxReader};
+pub use gpx_writer::GpxWriter;
+
+pub(crate) mod conversion {
+    use super::gpx_writer::*;
+    use crate::error::Result;
+    use crate::{FeatureProcessor, GeozeroDatasource, GeozeroGeometry};
+
+    /// Convert to GPX.
+    pub trait ToGpx {
+        /// Convert to GPX document.
+        fn to_gpx(&self) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToGpx for T {
+        fn to_gpx(&self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = GpxWriter::new(&mut out);
+            self.process_geom(&mut writer)?;
+            writer.dataset_end()?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+
+    /// Consume features as GPX.
+    pub trait ProcessToGpx {
+        /// Consume features as GPX String.
+        fn to_gpx(&mut self) -> Result<String>;
+    }
+
+    impl<T: GeozeroDatasource> ProcessToGpx for T {
+        fn to_gpx(&mut self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = GpxWriter::new(&mut out);
+                self.process(&mut writer)?;
+            }
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use crate::error::{GeozeroError, Result};
+use crate::timestamps::{decimal_year, parse_rfc3339, unix_nanos};
+use crate::xml::{Element, XmlReader};
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    PropertyProcessor,
+};
+use std::io::{BufReader, Read};
+use time::OffsetDateTime;
+
+/// KML Reader.
+pub struct KmlReader<'a, R: Read>(pub &'a mut R);
+
+impl<'a, R: Read> GeozeroDatasource for KmlReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_kml(&mut self.0, processor)
+    }
+}
+
+/// KML String slice.
+///
+/// Geometries of multiple Placemarks are processed as geometry collection.
+pub struct Kml<'a>(pub &'a str);
+
+impl GeozeroGeometry for Kml<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_kml_geom(&mut self.0.as_bytes(), processor)
+    }
+}
+
+impl GeozeroDatasource for Kml<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_kml(&mut self.0.as_bytes(), processor)
+    }
+}
+
+/// KMZ (zipped KML) Reader.
+///
+/// Reads `doc.kml` or the first `.kml` file in the archive.
+#[cfg(feature = "with-kmz")]
+pub struct KmzReader<'a, R: Read + std::io::Seek>(pub &'a mut R);
+
+#[cfg(feature = "with-kmz")]
+impl<'a, R: Read + std::io::Seek> GeozeroDatasource for KmzReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let mut archive = zip::ZipArchive::new(&mut self.0)?;
+        let name = if archive.file_names().any(|name| name == "doc.kml") {
+            "doc.kml".to_string()
+        } else {
+            archive
+                .file_names()
+                .find(|name| name.to_lowercase().ends_with(".kml"))
+                .ok_or_else(|| GeozeroError::Dataset("No KML file in KMZ archive".to_string()))?
+                .to_string()
+        };
+        let kml = archive.by_name(&name)?;
+        read_kml(kml, processor)
+    }
+}
+
+#[cfg(feature = "with-kmz")]
+impl From<zip::result::ZipError> for GeozeroError {
+    fn from(error: zip::result::ZipError) -> Self {
+        match error {
+            zip::result::ZipError::Io(io) => GeozeroError::IoError(io),
+            other => GeozeroError::Dataset(other.to_string()),
+        }
+    }
+}
+
+/// Read and process KML Placemarks.
+pub fn read_kml<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let mut reader = XmlReader::new(BufReader::new(reader));
+    processor.dataset_begin(None)?;
+    let mut idx = 0;
+    while let Some((element, empty)) = reader.next_start()? {
+        if element.name != "Placemark" {
+            continue;
+        }
+        let placemark = reader.read_element(element, empty)?;
+        process_placemark(&placemark, idx, processor)?;
+        idx += 1;
+    }
+    processor.dataset_end()
+}
+
+/// Read and process geometries of KML Placemarks.
+pub fn read_kml_geom<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let mut reader = XmlReader::new(BufReader::new(reader));
+    let mut geometries = Vec::new();
+    while let Some((element, empty)) = reader.next_start()? {
+        if element.name != "Placemark" {
+            continue;
+        }
+        let placemark = reader.read_element(element, empty)?;
+        if let Some(geom) = placemark_geometry(&placemark)? {
+            geometries.push(geom);
+        }
+    }
+    if geometries.len() == 1 {
+        process_geometry(&geometries[0], 0, processor)
+    } else {
+        processor.geometrycollection_begin(geometries.len(), 0)?;
+        for (idx, geom) in geometries.iter().enumerate() {
+            process_geometry(geom, idx, processor)?;
+        }
+        processor.geometrycollection_end(0)
+    }
+}
+
+fn process_placemark<P: FeatureProcessor>(
+    placemark: &Element,
+    idx: u64,
+    processor: &mut P,
+) -> Result<()> {
+    processor.feature_begin(idx)?;
+    processor.properties_begin()?;
+    process_properties(placemark, processor)?;
+    processor.properties_end()?;
+    if let Some(geom) = placemark_geometry(placemark)? {
+        processor.geometry_begin()?;
+        process_geometry(&geom, 0, processor)?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx)
+}
+
+/// Process `name`, `description` and `ExtendedData` values as string properties
+fn process_properties<P: PropertyProcessor>(placemark: &Element, processor: &mut P) -> Result<()> {
+    let mut i = 0;
+    let mut property = |name: &str, value: &str| -> Result<bool> {
+        let finish = processor.property(i, name, &ColumnValue::String(value))?;
+        i += 1;
+        Ok(finish)
+    };
+    for name in ["name", "description"] {
+        if let Some(value) = placemark.child_text(name) {
+            if property(name, value)? {
+                return Ok(());
+            }
+        }
+    }
+    if let Some(extended_data) = placemark.child("ExtendedData") {
+        for data in extended_data.children("Data") {
+            if let (Some(name), Some(value)) = (data.attribute("name"), data.child_text("value")) {
+                if property(name, value)? {
+                    return Ok(());
+                }
+            }
+        }
+        for schema_data in extended_data.children("SchemaData") {
+            for data in schema_data.children("SimpleData") {
+                if let Some(name) = data.attribute("name") {
+                    if property(name, &data.text)? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// KML coordinate tuple with optional timestamp of `gx:Track`
+#[derive(Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    time: Option<OffsetDateTime>,
+}
+
+#[derive(Debug)]
+enum KmlGeometry {
+    Point(Coord),
+    LineString(Vec<Coord>),
+    Polygon(Vec<Vec<Coord>>),
+    MultiGeometry(Vec<KmlGeometry>),
+}
+
+fn placemark_geometry(placemark: &Element) -> Result<Option<KmlGeometry>> {
+    for child in &placemark.children {
+        if let Some(geom) = parse_geometry(child)? {
+            return Ok(Some(geom));
+        }
+    }
+    Ok(None)
+}
+
+fn parse_geometry(element: &Element) -> Result<Option<KmlGeometry>> {
+    let geom = match element.name.as_str() {
+        "Point" => match parse_coordinates(element)?.into_iter().next() {
+            Some(coord) => KmlGeometry::Point(coord),
+            None => KmlGeometry::MultiGeometry(Vec::new()),
+        },
+        "LineString" | "LinearRing" => KmlGeometry::LineString(parse_coordinates(element)?),
+        "Polygon" => {
+            let mut rings = Vec::new();
+            for boundary in element.children("outerBoundaryIs") {
+                for ring in boundary.children("LinearRing") {
+                    rings.push(parse_coordinates(ring)?);
+                }
+            }
+            for boundary in element.children("innerBoundaryIs") {
+                for ring in boundary.children("LinearRing") {
+                    rings.push(parse_coordinates(ring)?);
+                }
+            }
+            KmlGeometry::Polygon(rings)
+        }
+        "Track" => KmlGeometry::LineString(parse_track(element)?),
+        "MultiGeometry" | "MultiTrack" => {
+            let mut geometries = Vec::new();
+            for child in &element.children {
+                if let Some(geom) = parse_geometry(child)? {
+                    geometries.push(geom);
+                }
+            }
+            KmlGeometry::MultiGeometry(geometries)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(geom))
+}
+
+/// Parse `lon,lat[,alt]` tuples of `coordinates` element
+fn parse_coordinates(element: &Element) -> Result<Vec<Coord>> {
+    match element.child_text("coordinates") {
+        Some(text) => text
+            .split_whitespace()
+            .map(|tuple| parse_coord(tuple.split(',')))
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Parse `gx:coord` positions (`lon lat alt`) with timestamps of `when` elements
+fn parse_track(element: &Element) -> Result<Vec<Coord>> {
+    let mut whens = element.children("when");
+    element
+        .children("coord")
+        .map(|coord| {
+            let mut coord = parse_coord(coord.text.split_whitespace())?;
+            coord.time = whens.next().and_then(|when| parse_rfc3339(&when.text));
+            Ok(coord)
+        })
+        .collect()
+}
+
+fn parse_coord<'a>(mut values: impl Iterator<Item = &'a str>) -> Result<Coord> {
+    let mut next_value = || -> Result<Option<f64>> {
+        values
+            .next()
+            .map(|v| {
+                v.trim()
+                    .parse::<f64>()
+                    .map_err(|_| GeozeroError::Geometry(format!("Invalid KML coordinate `{}`", v)))
+            })
+            .transpose()
+    };
+    let x = next_value()?;
+    let y = next_value()?;
+    let z = next_value()?;
+    match (x, y) {
+        (Some(x), Some(y)) => Ok(Coord {
+            x,
+            y,
+            z,
+            time: None,
+        }),
+        _ => Err(GeozeroError::Geometry(
+            "KML coordinate with less than two values".to_string(),
+        )),
+    }
+}
+
+fn process_geometry<P: GeomProcessor>(
+    geom: &KmlGeometry,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match geom {
+        KmlGeometry::Point(coord) => {
+            processor.point_begin(idx)?;
+            process_coord(coord, 0, processor)?;
+            processor.point_end(idx)
+        }
+        KmlGeometry::LineString(coords) => process_linestring(coords, true, idx, processor),
+        KmlGeometry::Polygon(rings) => process_polygon(rings, true, idx, processor),
+        KmlGeometry::MultiGeometry(geometries) => {
+            process_multi_geometry(geometries, idx, processor)
+        }
+    }
+}
+
+/// Process homogeneous MultiGeometry as multi-geometry type, otherwise as geometry collection
+fn process_multi_geometry<P: GeomProcessor>(
+    geometries: &[KmlGeometry],
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let all = |f: fn(&KmlGeometry) -> bool| !geometries.is_empty() && geometries.iter().all(f);
+    if all(|g| matches!(g, KmlGeometry::Point(_))) {
+        processor.multipoint_begin(geometries.len(), idx)?;
+        for (idxc, geom) in geometries.iter().enumerate() {
+            if let KmlGeometry::Point(coord) = geom {
+                process_coord(coord, idxc, processor)?;
+            }
+        }
+        processor.multipoint_end(idx)
+    } else if all(|g| matches!(g, KmlGeometry::LineString(_))) {
+        processor.multilinestring_begin(geometries.len(), idx)?;
+        for (idxc, geom) in geometries.iter().enumerate() {
+            if let KmlGeometry::LineString(coords) = geom {
+                process_linestring(coords, false, idxc, processor)?;
+            }
+        }
+        processor.multilinestring_end(idx)
+    } else if all(|g| matches!(g, KmlGeometry::Polygon(_))) {
+        processor.multipolygon_begin(geometries.len(), idx)?;
+        for (idxc, geom) in geometries.iter().enumerate() {
+            if let KmlGeometry::Polygon(rings) = geom {
+                process_polygon(rings, false, idxc, processor)?;
+            }
+        }
+        processor.multipolygon_end(idx)
+    } else {
+        processor.geometrycollection_begin(geometries.len(), idx)?;
+        for (idxg, geom) in geometries.iter().enumerate() {
+            process_geometry(geom, idxg, processor)?;
+        }
+        processor.geometrycollection_end(idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (idxc, coord) in coords.iter().enumerate() {
+        process_coord(coord, idxc, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Coord>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (idxl, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, idxl, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+/// Process coordinate with altitude as `z` and `gx:Track` timestamps as `t` (decimal year)
+/// and `tm` (nanoseconds since Unix epoch).
+fn process_coord<P: GeomProcessor>(coord: &Coord, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        let dims = processor.dimensions();
+        let z = if dims.z { coord.z } else { None };
+        let t = if dims.t {
+            coord.time.and_then(decimal_year)
+        } else {
+            None
+        };
+        let tm = if dims.tm {
+            coord.time.and_then(unix_nanos)
+        } else {
+            None
+        };
+        processor.coordinate(coord.x, coord.y, z, None, t, tm, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::wkt::WktWriter;
+    use crate::{CoordDimensions, ToWkt};
+
+    const PLACEMARKS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2" xmlns:gx="http://www.google.com/kml/ext/2.2">
+  <Document>
+    <name>Test</name>
+    <Folder>
+      <Placemark>
+        <name>Zürich</name>
+        <description><![CDATA[<b>City</b>]]></description>
+        <ExtendedData>
+          <Data name="population"><value>415367</value></Data>
+        </ExtendedData>
+        <Point><coordinates>8.5417,47.3769,408</coordinates></Point>
+      </Placemark>
+      <Placemark>
+        <name>Lake</name>
+        <ExtendedData>
+          <SchemaData schemaUrl="#lakes"><SimpleData name="kind">lake</SimpleData></SchemaData>
+        </ExtendedData>
+        <Polygon>
+          <outerBoundaryIs><LinearRing><coordinates>
+            0,0 10,0 10,10 0,10 0,0
+          </coordinates></LinearRing></outerBoundaryIs>
+          <innerBoundaryIs><LinearRing><coordinates>2,2 4,2 4,4 2,2</coordinates></LinearRing></innerBoundaryIs>
+        </Polygon>
+      </Placemark>
+    </Folder>
+  </Document>
+</kml>"##;
+
+    #[test]
+    fn placemarks() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        KmlReader(&mut PLACEMARKS.as_bytes()).process(&mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "Zürich", "description": "<b>City</b>", "population": "415367"}, "geometry": {"type": "Point", "coordinates": [8.5417,47.3769]}},
+{"type": "Feature", "properties": {"name": "Lake", "kind": "lake"}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[10,0],[10,10],[0,10],[0,0]],[[2,2],[4,2],[4,4],[2,2]]]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn geometries() -> Result<()> {
+        assert_eq!(
+            Kml(PLACEMARKS).to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(8.5417 47.3769),POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,4 2,4 4,2 2)))"
+        );
+
+        let kml = r#"<kml><Placemark><MultiGeometry>
+            <LineString><coordinates>1,2 3,4</coordinates></LineString>
+            <LineString><coordinates>5,6 7,8</coordinates></LineString>
+        </MultiGeometry></Placemark></kml>"#;
+        assert_eq!(Kml(kml).to_wkt()?, "MULTILINESTRING((1 2,3 4),(5 6,7 8))");
+
+        let kml = r#"<kml><Placemark><MultiGeometry>
+            <Point><coordinates>1,2</coordinates></Point>
+            <LineString><coordinates>5,6 7,8</coordinates></LineString>
+        </MultiGeometry></Placemark></kml>"#;
+        assert_eq!(
+            Kml(kml).to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(5 6,7 8))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn track() -> Result<()> {
+        let kml = r#"<kml xmlns:gx="http://www.google.com/kml/ext/2.2"><Placemark><gx:Track>
+            <when>2009-10-17T18:37:26Z</when>
+            <when>2009-10-17T18:37:31Z</when>
+            <gx:coord>-122.207881 37.371915 156.0</gx:coord>
+            <gx:coord>-122.205712 37.373288 152.0</gx:coord>
+        </gx:Track></Placemark></kml>"#;
+        assert_eq!(
+            Kml(kml).to_wkt()?,
+            "LINESTRING(-122.207881 37.371915,-122.205712 37.373288)"
+        );
+
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt_data);
+        writer.dims = CoordDimensions::xyz();
+        Kml(kml).process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "LINESTRING(-122.207881 37.371915 156,-122.205712 37.373288 152)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_coordinates() {
+        let kml = "<kml><Placemark><Point><coordinates>1,a</coordinates></Point></Placemark></kml>";
+        assert!(Kml(kml).to_wkt().is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use crate::error::Result;
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use quick_xml::escape::escape;
+use std::io::Write;
+
+/// KML writer.
+///
+/// Features are written as Placemarks. The properties configured as `name_column` and
+/// `description_column` become the Placemark name and description, all other properties
+/// are written as `ExtendedData`.
+///
+/// With `dims.z` set, Z values are written as altitude with `absolute` altitude mode.
+pub struct KmlWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// Property used as Placemark name
+    pub name_column: Option<String>,
+    /// Property used as Placemark description
+    pub description_column: Option<String>,
+    out: &'a mut W,
+    in_feature: bool,
+    geometry: Vec<u8>,
+    name: Option<String>,
+    description: Option<String>,
+    extended_data: Vec<(String, String)>,
+    in_multipoint: bool,
+    in_polygon: bool,
+}
+
+impl<'a, W: Write> KmlWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> KmlWriter<'a, W> {
+        KmlWriter {
+            dims: CoordDimensions::default(),
+            name_column: Some("name".to_string()),
+            description_column: Some("description".to_string()),
+            out,
+            in_feature: false,
+            geometry: Vec::new(),
+            name: None,
+            description: None,
+            extended_data: Vec::new(),
+            in_multipoint: false,
+            in_polygon: false,
+        }
+    }
+
+    /// Write geometry output, buffered until the end of a feature
+    fn write(&mut self, s: &str) -> Result<()> {
+        if self.in_feature {
+            self.geometry.extend_from_slice(s.as_bytes());
+        } else {
+            self.out.write_all(s.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn altitude_mode(&mut self) -> Result<()> {
+        if self.dims.z {
+            self.write("<altitudeMode>absolute</altitudeMode>")?;
+        }
+        Ok(())
+    }
+
+    fn write_coord(&mut self, x: f64, y: f64, z: Option<f64>, idx: usize) -> Result<()> {
+        let coord = match z {
+            Some(z) => format!("{},{},{}", x, y, z),
+            None => format!("{},{}", x, y),
+        };
+        if self.in_multipoint {
+            self.write("<Point>")?;
+            self.altitude_mode()?;
+            self.write(&format!("<coordinates>{}</coordinates></Point>", coord))
+        } else if idx > 0 {
+            self.write(&format!(" {}", coord))
+        } else {
+            self.write(&coord)
+        }
+    }
+}
+
+impl<W: Write> FeatureProcessor for KmlWriter<'_, W> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.out.write_all(
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>"#,
+        )?;
+        if let Some(name) = name {
+            write!(self.out, "\n<name>{}</name>", escape(name))?;
+        }
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.out.write_all(b"\n</Document>\n</kml>\n")?;
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.in_feature = true;
+        self.geometry.clear();
+        self.name = None;
+        self.description = None;
+        self.extended_data.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.in_feature = false;
+        self.out.write_all(b"\n<Placemark>")?;
+        if let Some(name) = &self.name {
+            write!(self.out, "<name>{}</name>", escape(name))?;
+        }
+        if let Some(description) = &self.description {
+            write!(
+                self.out,
+                "<description>{}</description>",
+                escape(description)
+            )?;
+        }
+        if !self.extended_data.is_empty() {
+            self.out.write_all(b"<ExtendedData>")?;
+            for (name, value) in &self.extended_data {
+                write!(
+                    self.out,
+                    r#"<Data name="{}"><value>{}</value></Data>"#,
+                    escape(name),
+                    escape(value)
+                )?;
+            }
+            self.out.write_all(b"</ExtendedData>")?;
+        }
+        self.out.write_all(&self.geometry)?;
+        self.out.write_all(b"</Placemark>")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for KmlWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let ColumnValue::Binary(_) = value {
+            return Ok(false);
+        }
+        if self.name_column.as_deref() == Some(name) {
+            self.name = Some(value.to_string());
+        } else if self.description_column.as_deref() == Some(name) {
+            self.description = Some(value.to_string());
+        } else {
+            self.extended_data
+                .push((name.to_string(), value.to_string()));
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write> GeomProcessor for KmlWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.write_coord(x, y, None, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.write_coord(x, y, z, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.write("<Point><coordinates></coordinates></Point>")
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.write("<Point>")?;
+        self.altitude_mode()?;
+        self.write("<coordinates>")
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.write("</coordinates></Point>")
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.in_multipoint = true;
+        self.write("<MultiGeometry>")
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_multipoint = false;
+        self.write("</MultiGeometry>")
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, idx: usize) -> Result<()> {
+        if self.in_polygon {
+            if idx == 0 {
+                self.write("<outerBoundaryIs>")?;
+            } else {
+                self.write("<innerBoundaryIs>")?;
+            }
+            self.write("<LinearRing><coordinates>")
+        } else {
+            self.write("<LineString>")?;
+            self.altitude_mode()?;
+            self.write("<coordinates>")
+        }
+    }
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        if self.in_polygon {
+            self.write("</coordinates></LinearRing>")?;
+            if idx == 0 {
+                self.write("</outerBoundaryIs>")
+            } else {
+                self.write("</innerBoundaryIs>")
+            }
+        } else {
+            self.write("</coordinates></LineString>")
+        }
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.write("<MultiGeometry>")
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.write("</MultiGeometry>")
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.write("<Polygon>")?;
+        self.altitude_mode()?;
+        self.in_polygon = true;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.in_polygon = false;
+        self.write("</Polygon>")
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.write("<MultiGeometry>")
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.write("</MultiGeometry>")
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.write("<MultiGeometry>")
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.write("</MultiGeometry>")
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::kml::Kml;
+    use crate::wkt::WktStr;
+    use crate::{GeozeroDatasource, GeozeroGeometry, ToKml, ToWkt};
+
+    #[test]
+    fn geometries() -> Result<()> {
+        assert_eq!(
+            WktStr("POINT(1 2)").to_kml()?,
+            "<Point><coordinates>1,2</coordinates></Point>"
+        );
+        assert_eq!(
+            WktStr("MULTIPOINT(1 2,3 4)").to_kml()?,
+            "<MultiGeometry><Point><coordinates>1,2</coordinates></Point><Point><coordinates>3,4</coordinates></Point></MultiGeometry>"
+        );
+        assert_eq!(
+            WktStr("POLYGON((0 0,10 0,10 10,0 0),(2 2,4 2,4 4,2 2))").to_kml()?,
+            "<Polygon><outerBoundaryIs><LinearRing><coordinates>0,0 10,0 10,10 0,0</coordinates></LinearRing></outerBoundaryIs><innerBoundaryIs><LinearRing><coordinates>2,2 4,2 4,4 2,2</coordinates></LinearRing></innerBoundaryIs></Polygon>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn altitude() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = KmlWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        GeoJson(r#"{"type": "LineString", "coordinates": [[1, 2, 3], [4, 5, 6]]}"#)
+            .process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "<LineString><altitudeMode>absolute</altitudeMode><coordinates>1,2,3 4,5,6</coordinates></LineString>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"title": "A & B", "id": 1, "note": "<none>"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+            ]}"#,
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = KmlWriter::new(&mut out);
+        writer.name_column = Some("title".to_string());
+        writer.description_column = Some("note".to_string());
+        geojson.process(&mut writer)?;
+        let kml = std::str::from_utf8(&out).unwrap();
+        assert_eq!(
+            kml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>
+<Placemark><name>A &amp; B</name><description>&lt;none&gt;</description><ExtendedData><Data name="id"><value>1</value></Data></ExtendedData><Point><coordinates>1,2</coordinates></Point></Placemark>
+</Document>
+</kml>
+"#
+        );
+        assert_eq!(Kml(kml).to_wkt()?, "POINT(1 2)");
+        Ok(())
+    }
+}
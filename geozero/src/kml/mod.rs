@@ -0,0 +1,49 @@
+//! KML conversions.
+mod kml_reader;
+mod kml_writer;
+
+pub use kml_reader::*;
+pub use kml_writer::KmlWriter;
+
+pub(crate) mod conversion {
+    use super::kml_writer::*;
+    use crate::error::Result;
+    use crate::{GeozeroDatasource, GeozeroGeometry};
+
+    /// Convert to KML.
+    pub trait ToKml {
+        /// Convert to KML geometry element.
+        fn to_kml(&self) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToKml for T {
+        fn to_kml(&self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = KmlWriter::new(&mut out);
+            writer.dims = self.dims();
+            self.process_geom(&mut writer)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+
+    /// Consume features as KML.
+    pub trait ProcessToKml {
+        /// Consume features as KML document.
+        fn to_kml(&mut self) -> Result<String>;
+    }
+
+    impl<T: GeozeroDatasource> ProcessToKml for T {
+        fn to_kml(&mut self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = KmlWriter::new(&mut out);
+                self.process(&mut writer)?;
+            }
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
@@ -17,36 +17,89 @@
 //!
 //! ## Format conversion overview
 //!
-//! |           |                          [GeozeroGeometry]                           | Dimensions |                         [GeozeroDatasource]                          | Geometry Conversion |             [GeomProcessor]             |
-//! |-----------|----------------------------------------------------------------------|------------|----------------------------------------------------------------------|---------------------|-----------------------------------------|
-//! | CSV       | [csv::Csv], [csv::CsvString]                                         | XY         | -                                                                    | [ProcessToCsv]      | [CsvWriter](csv::CsvWriter)             |
-//! | geo-types | `geo_types::Geometry<f64>`                                           | XY         | -                                                                    | [ToGeo]             | [GeoWriter](geo_types::GeoWriter)       |
-//! | GeoArrow  | `arrow2::array::BinaryArray`                                         | XY         | -                                                                    | -                   | -                                       |
-//! | GeoJSON   | [GeoJson](geojson::GeoJson), [GeoJsonString](geojson::GeoJsonString) | XYZ        | [GeoJsonReader](geojson::GeoJsonReader), [GeoJson](geojson::GeoJson) | [ToJson]            | [GeoJsonWriter](geojson::GeoJsonWriter) |
-//! | GDAL      | `gdal::vector::Geometry`                                             | XYZ        | -                                                                    | [ToGdal]            | [GdalWriter](gdal::GdalWriter)          |
-//! | GEOS      | `geos::Geometry`                                                     | XYZ        | -                                                                    | [ToGeos]            | [GeosWriter](geos::GeosWriter)          |
-//! | GPX       |                                                                      | XY         | [GpxReader](gpx::GpxReader)                                          |                     |                                         |
-//! | MVT       | [mvt::tile::Feature]                                                 | XY         | [mvt::tile::Layer]                                                   | [ToMvt]             | [MvtWriter](mvt::MvtWriter)             |
-//! | SVG       | -                                                                    | XY         | -                                                                    | [ToSvg]             | [SvgWriter](svg::SvgWriter)             |
-//! | WKB       | [Wkb](wkb::Wkb), [Ewkb](wkb::Ewkb), [GpkgWkb](wkb::GpkgWkb)          | XYZM       | -                                                                    | [ToWkb]             | [WkbWriter](wkb::WkbWriter)             |
-//! | WKT       | [wkt::WktStr], [wkt::WktString]                                      | XYZM       | [wkt::WktReader], [wkt::WktStr], [wkt::WktString]                    | [ToWkt]             | [WktWriter](wkt::WktWriter)             |
+//! |           |                          [GeozeroGeometry]                           | Dimensions |                         [GeozeroDatasource]                          | Geometry Conversion |               [GeomProcessor]               |
+//! |-----------|----------------------------------------------------------------------|------------|----------------------------------------------------------------------|---------------------|---------------------------------------------|
+//! | CityJSON  | [CityJson](cityjson::CityJson)                                       | XYZ        | [CityJsonReader](cityjson::CityJsonReader)                           | -                   | -                                           |
+//! | CSV       | [csv::Csv], [csv::CsvString]                                         | XY         | -                                                                    | [ProcessToCsv]      | [CsvWriter](csv::CsvWriter)                 |
+//! | geo-types | `geo_types::Geometry<f64>`                                           | XY         | -                                                                    | [ToGeo]             | [GeoWriter](geo_types::GeoWriter)           |
+//! | Geohash   | [Geohash](geohash::Geohash)                                          | XY         | -                                                                    | [ToGeohash]         | [GeohashWriter](geohash::GeohashWriter)     |
+//! | GeoArrow  | `arrow2::array::BinaryArray`                                         | XY         | [GeoArrowReader](arrow::GeoArrowReader)                              | -                   | -                                           |
+//! | GeoJSON   | [GeoJson](geojson::GeoJson), [GeoJsonString](geojson::GeoJsonString) | XYZ        | [GeoJsonReader](geojson::GeoJsonReader), [GeoJson](geojson::GeoJson) | [ToJson]            | [GeoJsonWriter](geojson::GeoJsonWriter)     |
+//! | GeoParquet| -                                                                    | XYZ        | -                                                                    | -                   | [GeoParquetWriter](arrow::GeoParquetWriter) |
+//! | GeoRSS    | [GeoRss](georss::GeoRss)                                             | XY         | [GeoRssReader](georss::GeoRssReader)                                 | -                   | -                                           |
+//! | GDAL      | `gdal::vector::Geometry`                                             | XYZ        | -                                                                    | [ToGdal]            | [GdalWriter](gdal::GdalWriter)              |
+//! | Geobuf    | [Geobuf](geobuf::Geobuf)                                             | XYZ        | [Geobuf](geobuf::Geobuf), [GeobufReader](geobuf::GeobufReader)       | [ToGeobuf]          | [GeobufWriter](geobuf::GeobufWriter)        |
+//! | GEOS      | `geos::Geometry`                                                     | XYZ        | -                                                                    | [ToGeos]            | [GeosWriter](geos::GeosWriter)              |
+//! | GML       | [Gml](gml::Gml)                                                      | XYZ        | -                                                                    | [ToGml]             | [GmlWriter](gml::GmlWriter)                 |
+//! | GPX       | [Gpx](gpx::Gpx)                                                      | XYZT       | [GpxReader](gpx::GpxReader)                                          | [ToGpx]             | [GpxWriter](gpx::GpxWriter)                 |
+//! | KML       | [Kml](kml::Kml)                                                      | XYZT       | [KmlReader](kml::KmlReader), [KmzReader](kml::KmzReader)             | [ToKml]             | [KmlWriter](kml::KmlWriter)                 |
+//! | MVT       | [mvt::tile::Feature]                                                 | XY         | [mvt::tile::Layer]                                                   | [ToMvt]             | [MvtWriter](mvt::MvtWriter)                 |
+//! | OBJ       | -                                                                    | XYZ        | -                                                                    | [ToObj]             | [ObjWriter](obj::ObjWriter)                 |
+//! | OSM       | -                                                                    | XY         | [OsmReader](osm::OsmReader)                                          | -                   | -                                           |
+//! | Polyline  | [Polyline](polyline::Polyline)                                       | XY         | -                                                                    | [ToPolyline]        | [PolylineWriter](polyline::PolylineWriter)  |
+//! | PostGIS   | -                                                                    | XYZM       | -                                                                    | -                   | [PostgisWriter](postgis::PostgisWriter)     |
+//! | SQL       | -                                                                    | XYZM       | -                                                                    | -                   | [SqlWriter](sql::SqlWriter)                 |
+//! | SVG       | -                                                                    | XY         | -                                                                    | [ToSvg]             | [SvgWriter](svg::SvgWriter)                 |
+//! | TopoJSON  | [TopoJson](topojson::TopoJson)                                       | XYZ        | [TopoJsonReader](topojson::TopoJsonReader)                           | -                   | -                                           |
+//! | WKB       | [Wkb](wkb::Wkb), [Ewkb](wkb::Ewkb), [GpkgWkb](wkb::GpkgWkb)          | XYZM       | -                                                                    | [ToWkb]             | [WkbWriter](wkb::WkbWriter)                 |
+//! | WKT       | [wkt::WktStr], [wkt::WktString]                                      | XYZM       | [wkt::WktReader], [wkt::WktStr], [wkt::WktString]                    | [ToWkt]             | [WktWriter](wkt::WktWriter)                 |
+//!
+//! ## Geometry conversion
+//!
+//! The conversion traits are implemented for all [GeozeroGeometry] types:
+//!
+//! ```
+//! use geozero::{geojson::GeoJson, wkt::WktStr, ToJson, ToSvg, ToWkt};
+//!
+//! let wkt = WktStr("POINT(10 -20)");
+//! assert_eq!(wkt.to_json().unwrap(), r#"{"type": "Point", "coordinates": [10,-20]}"#);
+//! assert_eq!(wkt.to_svg().unwrap(), r#"<path d="M 10 -20 Z"/>"#);
+//!
+//! let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#);
+//! assert_eq!(geojson.to_wkt().unwrap(), "LINESTRING(1 2,3 4)");
+//! ```
 
 mod api;
+#[cfg(feature = "with-tokio")]
+mod async_api;
+#[cfg(feature = "with-compression")]
+mod compression;
+mod convert;
+mod crs;
 pub mod error;
 mod feature_processor;
 mod geometry_processor;
 mod multiplex;
+#[cfg(feature = "with-rayon")]
+mod parallel;
 mod property_processor;
+mod schema;
+mod serde_properties;
+mod write_counter;
 
 pub use api::*;
+#[cfg(feature = "with-tokio")]
+pub use async_api::*;
+#[cfg(feature = "with-compression")]
+pub use compression::*;
+pub use convert::*;
+pub use crs::*;
 pub use feature_processor::*;
 pub use geometry_processor::*;
 pub use multiplex::*;
+#[cfg(feature = "with-rayon")]
+pub use parallel::*;
 pub use property_processor::*;
+pub use schema::*;
+pub use serde_properties::*;
+pub use write_counter::*;
 
 #[cfg(feature = "with-arrow")]
 pub mod arrow;
 
+#[cfg(feature = "with-cityjson")]
+pub mod cityjson;
+
 #[cfg(feature = "with-csv")]
 pub mod csv;
 #[cfg(feature = "with-csv")]
@@ -62,37 +115,92 @@ pub mod geo_types;
 #[cfg(feature = "with-geo")]
 pub use crate::geo_types::conversion::*;
 
+#[cfg(feature = "with-geohash")]
+pub mod geohash;
+#[cfg(feature = "with-geohash")]
+pub use crate::geohash::conversion::*;
+
 #[cfg(feature = "with-geojson")]
 pub mod geojson;
 #[cfg(feature = "with-geojson")]
 pub use crate::geojson::conversion::*;
 
+#[cfg(feature = "with-georss")]
+pub mod georss;
+
 #[cfg(feature = "with-geos")]
 pub mod geos;
 #[cfg(feature = "with-geos")]
 pub use crate::geos::conversion::*;
 
+#[cfg(feature = "with-geobuf")]
+pub mod geobuf;
+#[cfg(feature = "with-geobuf")]
+pub use crate::geobuf::conversion::*;
+
+#[cfg(feature = "with-gml")]
+pub mod gml;
+#[cfg(feature = "with-gml")]
+pub use crate::gml::conversion::*;
+
 #[cfg(feature = "with-gpkg")]
 pub mod gpkg;
 
 #[cfg(feature = "with-gpx")]
 pub mod gpx;
+#[cfg(feature = "with-gpx")]
+pub use crate::gpx::conversion::*;
+
+#[cfg(feature = "with-kml")]
+pub mod kml;
+#[cfg(feature = "with-kml")]
+pub use crate::kml::conversion::*;
+
+#[cfg(feature = "with-mbtiles")]
+pub mod mbtiles;
+
+#[cfg(feature = "with-obj")]
+pub mod obj;
+#[cfg(feature = "with-obj")]
+pub use crate::obj::conversion::*;
+
+#[cfg(feature = "with-osm")]
+pub mod osm;
+
+#[cfg(feature = "with-pmtiles")]
+pub mod pmtiles;
+
+#[cfg(feature = "with-polyline")]
+pub mod polyline;
+#[cfg(feature = "with-polyline")]
+pub use crate::polyline::conversion::*;
+
+pub mod processing;
 
 #[cfg(any(
+    feature = "with-postgis-copy",
     feature = "with-postgis-postgres",
     feature = "with-postgis-sqlx",
     feature = "with-postgis-diesel"
 ))]
 pub mod postgis;
 
+#[cfg(feature = "with-sql")]
+pub mod sql;
+
 #[cfg(feature = "with-svg")]
 pub mod svg;
 #[cfg(feature = "with-svg")]
 pub use crate::svg::conversion::*;
 
+pub mod transform;
+
 #[cfg(feature = "with-tesselator")]
 pub mod tessellator;
 
+#[cfg(feature = "with-topojson")]
+pub mod topojson;
+
 #[cfg(feature = "with-wkb")]
 pub mod wkb;
 #[cfg(feature = "with-wkb")]
@@ -107,6 +215,13 @@ pub use crate::wkt::conversion::*;
 pub mod mvt;
 #[cfg(feature = "with-mvt")]
 pub use crate::mvt::conversion::*;
+#[cfg(feature = "with-mvt")]
+pub mod tiling;
+
+#[cfg(any(feature = "with-gpx", feature = "with-kml"))]
+mod timestamps;
+#[cfg(any(feature = "with-gml", feature = "with-kml"))]
+mod xml;
 
 /// Empty processor implementation
 pub struct ProcessorSink;
@@ -0,0 +1,116 @@
+use super::{flip_y, sql_error};
+use crate::error::{GeozeroError, Result};
+use crate::mvt::{MvtLayer, Tile};
+use crate::{FeatureProcessor, GeozeroDatasource};
+use prost::Message;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::ConnectOptions;
+use std::collections::BTreeMap;
+
+/// MBTiles container reader.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::mbtiles::MbtilesReader;
+/// use geozero::geojson::GeoJsonWriter;
+///
+/// # async fn read() -> geozero::error::Result<()> {
+/// let mut reader = MbtilesReader::open("places.mbtiles").await?;
+/// println!("{:?}", reader.metadata().await?.get("name"));
+/// let mut out: Vec<u8> = Vec::new();
+/// reader.process_tile(3, 4, 2, &mut GeoJsonWriter::new(&mut out)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MbtilesReader {
+    conn: SqliteConnection,
+}
+
+impl MbtilesReader {
+    /// Reader for MBTiles database on `conn`.
+    pub fn new(conn: SqliteConnection) -> Self {
+        MbtilesReader { conn }
+    }
+
+    /// Reader for MBTiles file `path`.
+    pub async fn open(path: &str) -> Result<Self> {
+        let conn = SqliteConnectOptions::new()
+            .filename(path)
+            .read_only(true)
+            .connect()
+            .await
+            .map_err(sql_error)?;
+        Ok(MbtilesReader::new(conn))
+    }
+
+    /// Consume reader and return database connection.
+    pub fn into_inner(self) -> SqliteConnection {
+        self.conn
+    }
+
+    /// Entries of the `metadata` table.
+    pub async fn metadata(&mut self) -> Result<BTreeMap<String, String>> {
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT name, value FROM metadata")
+            .fetch_all(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Coordinates `(z, x, y)` of all tiles.
+    pub async fn tile_coords(&mut self) -> Result<Vec<(u8, u32, u32)>> {
+        let rows: Vec<(i64, i64, i64)> = sqlx::query_as(
+            "SELECT zoom_level, tile_column, tile_row FROM tiles ORDER BY zoom_level, tile_column, tile_row",
+        )
+        .fetch_all(&mut self.conn)
+        .await
+        .map_err(sql_error)?;
+        Ok(rows
+            .into_iter()
+            .map(|(z, x, y)| (z as u8, x as u32, flip_y(z as u8, y as u32)))
+            .collect())
+    }
+
+    /// Data of tile `z/x/y`. `None` if the tile is missing.
+    pub async fn tile(&mut self, z: u8, x: u32, y: u32) -> Result<Option<Vec<u8>>> {
+        let row: Option<(Vec<u8>,)> = sqlx::query_as(
+            "SELECT tile_data FROM tiles WHERE zoom_level = ? AND tile_column = ? AND tile_row = ?",
+        )
+        .bind(z)
+        .bind(x)
+        .bind(flip_y(z, y))
+        .fetch_optional(&mut self.conn)
+        .await
+        .map_err(sql_error)?;
+        Ok(row.map(|r| r.0))
+    }
+
+    /// Process all layers of MVT tile `z/x/y` with geographic coordinates.
+    ///
+    /// Each layer is processed as a dataset. Returns `false` if the tile is missing.
+    /// Gzip compressed tiles are not supported.
+    pub async fn process_tile<P: FeatureProcessor>(
+        &mut self,
+        z: u8,
+        x: u32,
+        y: u32,
+        processor: &mut P,
+    ) -> Result<bool> {
+        let data = match self.tile(z, x, y).await? {
+            Some(data) => data,
+            None => return Ok(false),
+        };
+        if data.starts_with(&[0x1f, 0x8b]) {
+            return Err(GeozeroError::Dataset(
+                "gzip compressed tiles are not supported".to_string(),
+            ));
+        }
+        let tile =
+            Tile::decode(data.as_slice()).map_err(|e| GeozeroError::Dataset(e.to_string()))?;
+        for layer in &tile.layers {
+            MvtLayer::geographic(layer, z, x, y).process(processor)?;
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,115 @@
+use super::{flip_y, sql_error};
+use crate::error::Result;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::ConnectOptions;
+
+const MBTILES_TABLES: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS metadata (name TEXT, value TEXT)",
+    "CREATE UNIQUE INDEX IF NOT EXISTS metadata_index ON metadata (name)",
+    "CREATE TABLE IF NOT EXISTS tiles (zoom_level INTEGER, tile_column INTEGER, tile_row INTEGER, tile_data BLOB)",
+    "CREATE UNIQUE INDEX IF NOT EXISTS tile_index ON tiles (zoom_level, tile_column, tile_row)",
+];
+
+/// MBTiles container writer.
+///
+/// Existing tiles and metadata entries are replaced. [finish](MbtilesWriter::finish) adds
+/// the `format`, `minzoom` and `maxzoom` metadata entries, unless already set.
+pub struct MbtilesWriter {
+    conn: SqliteConnection,
+    zoom_range: Option<(u8, u8)>,
+}
+
+impl MbtilesWriter {
+    /// Writer for MBTiles database on `conn`. Creates the MBTiles tables if missing.
+    pub async fn new(mut conn: SqliteConnection) -> Result<Self> {
+        for sql in MBTILES_TABLES {
+            sqlx::query(sql)
+                .execute(&mut conn)
+                .await
+                .map_err(sql_error)?;
+        }
+        Ok(MbtilesWriter {
+            conn,
+            zoom_range: None,
+        })
+    }
+
+    /// Writer for MBTiles file `path`, which is created if missing.
+    pub async fn create(path: &str) -> Result<Self> {
+        let conn = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .connect()
+            .await
+            .map_err(sql_error)?;
+        MbtilesWriter::new(conn).await
+    }
+
+    /// Consume writer and return database connection.
+    pub fn into_inner(self) -> SqliteConnection {
+        self.conn
+    }
+
+    /// Set metadata entry `name`.
+    pub async fn set_metadata(&mut self, name: &str, value: &str) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO metadata (name, value) VALUES (?, ?)")
+            .bind(name)
+            .bind(value)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    /// Write tile `z/x/y`.
+    pub async fn write_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO tiles (zoom_level, tile_column, tile_row, tile_data) VALUES (?, ?, ?, ?)")
+            .bind(z)
+            .bind(x)
+            .bind(flip_y(z, y))
+            .bind(data)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        self.zoom_range = Some(match self.zoom_range {
+            Some((min, max)) => (min.min(z), max.max(z)),
+            None => (z, z),
+        });
+        Ok(())
+    }
+
+    /// Write `tiles` in a single transaction.
+    pub async fn write_tiles(&mut self, tiles: &[(u8, u32, u32, Vec<u8>)]) -> Result<()> {
+        self.execute("BEGIN").await?;
+        for (z, x, y, data) in tiles {
+            self.write_tile(*z, *x, *y, data).await?;
+        }
+        self.execute("COMMIT").await
+    }
+
+    /// Add default metadata entries.
+    pub async fn finish(&mut self) -> Result<()> {
+        let mut entries = vec![("format", "pbf".to_string())];
+        if let Some((min, max)) = self.zoom_range {
+            entries.push(("minzoom", min.to_string()));
+            entries.push(("maxzoom", max.to_string()));
+        }
+        for (name, value) in entries {
+            sqlx::query("INSERT OR IGNORE INTO metadata (name, value) VALUES (?, ?)")
+                .bind(name)
+                .bind(value)
+                .execute(&mut self.conn)
+                .await
+                .map_err(sql_error)?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<()> {
+        sqlx::query(sql)
+            .execute(&mut self.conn)
+            .await
+            .map_err(sql_error)?;
+        Ok(())
+    }
+}
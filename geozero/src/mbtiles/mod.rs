@@ -0,0 +1,43 @@
+//! MBTiles tile containers.
+//!
+//! [MBTiles](https://github.com/mapbox/mbtiles-spec) are SQLite databases with a `tiles`
+//! table in the TMS tiling scheme and a `metadata` table. Tile coordinates of
+//! [MbtilesReader] and [MbtilesWriter] are in the XYZ tiling scheme, rows are flipped
+//! on access.
+//!
+//! # Usage example:
+//!
+//! Write a tile pyramid generated with [generate_tiles](crate::tiling::generate_tiles):
+//! ```
+//! use geozero::geojson::GeoJsonString;
+//! use geozero::mbtiles::MbtilesWriter;
+//! use geozero::tiling::{generate_tiles, TilingOptions};
+//!
+//! # async fn tile() -> geozero::error::Result<()> {
+//! let mut geojson = GeoJsonString(std::fs::read_to_string("places.json")?);
+//! let mut tiles = Vec::new();
+//! generate_tiles(&mut geojson, &TilingOptions::default(), &mut tiles)?;
+//! let mut writer = MbtilesWriter::create("places.mbtiles").await?;
+//! writer.set_metadata("name", "places").await?;
+//! writer.write_tiles(&tiles).await?;
+//! writer.finish().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod mbtiles_reader;
+mod mbtiles_writer;
+
+pub use mbtiles_reader::*;
+pub use mbtiles_writer::*;
+
+use crate::error::GeozeroError;
+
+fn sql_error(e: sqlx::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+/// Flip tile row between XYZ and TMS tiling scheme.
+fn flip_y(z: u8, y: u32) -> u32 {
+    (1u32 << z) - 1 - y
+}
@@ -1,7 +1,8 @@
 use crate::error::Result;
 use crate::feature_processor::FeatureProcessor;
-use crate::geometry_processor::GeomProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
 use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::schema::DatasetMetadata;
 
 #[doc(hidden)]
 pub struct Multiplexer<P1: FeatureProcessor, P2: FeatureProcessor> {
@@ -48,6 +49,41 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexe
         self.p1.geometry_end()?;
         self.p2.geometry_end()
     }
+    fn null_geometry(&mut self) -> Result<()> {
+        self.p1.null_geometry()?;
+        self.p2.null_geometry()
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.p1.multiple_geometries() && self.p2.multiple_geometries()
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.p1.geometry_column_begin(idx, name)?;
+        self.p2.geometry_column_begin(idx, name)
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.p1.geometry_column_end(idx, name)?;
+        self.p2.geometry_column_end(idx, name)
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        self.p1.foreign_member(name, value)?;
+        self.p2.foreign_member(name, value)
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.p1.dataset_srid(srid)?;
+        self.p2.dataset_srid(srid)
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.p1.dataset_metadata(metadata)?;
+        self.p2.dataset_metadata(metadata)
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.p1.batch_begin(size)?;
+        self.p2.batch_begin(size)
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.p1.batch_end()?;
+        self.p2.batch_end()
+    }
 }
 
 impl<P1: FeatureProcessor, P2: FeatureProcessor> GeomProcessor for Multiplexer<P1, P2> {
@@ -189,3 +225,288 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> PropertyProcessor for Multiplex
             .and(self.p2.property(i, colname, colval))
     }
 }
+
+/// Processor forwarding all events to multiple processors.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{ExtentProcessor, StatsProcessor};
+/// use geozero::{wkt::WktStr, GeozeroGeometry, Tee};
+///
+/// let mut extent = ExtentProcessor::new();
+/// let mut stats = StatsProcessor::new();
+/// let mut tee = Tee::default();
+/// tee.add(&mut extent);
+/// tee.add(&mut stats);
+/// WktStr("LINESTRING(1 2,3 4)").process_geom(&mut tee).unwrap();
+/// assert_eq!(extent.extent().unwrap().bbox(), [1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(stats.stats().vertices, 2);
+/// ```
+#[derive(Default)]
+pub struct Tee<'a> {
+    processors: Vec<&'a mut dyn FeatureProcessor>,
+}
+
+impl<'a> Tee<'a> {
+    pub fn new(processors: Vec<&'a mut dyn FeatureProcessor>) -> Self {
+        Tee { processors }
+    }
+    /// Add a processor.
+    pub fn add(&mut self, processor: &'a mut dyn FeatureProcessor) {
+        self.processors.push(processor);
+    }
+    fn each<F: FnMut(&mut dyn FeatureProcessor) -> Result<()>>(&mut self, mut f: F) -> Result<()> {
+        for processor in self.processors.iter_mut() {
+            f(&mut **processor)?;
+        }
+        Ok(())
+    }
+}
+
+impl GeomProcessor for Tee<'_> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processors
+            .iter()
+            .map(|p| p.dimensions())
+            .fold(CoordDimensions::xy(), |dims, d| CoordDimensions {
+                z: dims.z || d.z,
+                m: dims.m || d.m,
+                t: dims.t || d.t,
+                tm: dims.tm || d.tm,
+            })
+    }
+    fn multi_dim(&self) -> bool {
+        self.processors.iter().any(|p| p.multi_dim())
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.each(|p| p.srid(srid))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.each(|p| p.xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.each(|p| {
+            if p.multi_dim() {
+                // Only pass requested dimensions
+                let dims = p.dimensions();
+                p.coordinate(
+                    x,
+                    y,
+                    z.filter(|_| dims.z),
+                    m.filter(|_| dims.m),
+                    t.filter(|_| dims.t),
+                    tm.filter(|_| dims.tm),
+                    idx,
+                )
+            } else {
+                p.xy(x, y, idx)
+            }
+        })
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.empty_point(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_begin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_end(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_begin(size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_end(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_begin(tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_end(tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_begin(size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_end(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_begin(tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_end(tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_begin(size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_end(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_begin(size, idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_end(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_begin(size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_end(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_begin(size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_end(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_begin(size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_end(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_begin(size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_end(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_begin(size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_end(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_begin(tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_end(tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_begin(size, idx))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_end(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_begin(size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_end(idx))
+    }
+}
+
+impl PropertyProcessor for Tee<'_> {
+    /// Returns `true` (abort) only if all processors requested to abort.
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let mut abort = true;
+        for processor in self.processors.iter_mut() {
+            abort &= processor.property(idx, name, value)?;
+        }
+        Ok(abort)
+    }
+}
+
+impl FeatureProcessor for Tee<'_> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.each(|p| p.dataset_begin(name))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.each(|p| p.dataset_end())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_begin(idx))
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_end(idx))
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.each(|p| p.properties_begin())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.each(|p| p.properties_end())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_begin())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_end())
+    }
+    fn null_geometry(&mut self) -> Result<()> {
+        self.each(|p| p.null_geometry())
+    }
+    /// Returns `true` only if all processors handle additional geometries.
+    fn multiple_geometries(&self) -> bool {
+        self.processors.iter().all(|p| p.multiple_geometries())
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.each(|p| p.geometry_column_begin(idx, name))
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.each(|p| p.geometry_column_end(idx, name))
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        self.each(|p| p.foreign_member(name, value))
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.each(|p| p.dataset_srid(srid))
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.each(|p| p.dataset_metadata(metadata))
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.each(|p| p.batch_begin(size))
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.each(|p| p.batch_end())
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::processing::{ExtentProcessor, StatsProcessor};
+    use crate::wkt::WktWriter;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn tee_features() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [3, 4, 5]}}
+        ]}"#;
+        let mut wkt = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        let mut extent = ExtentProcessor::new();
+        let mut stats = StatsProcessor::new();
+        {
+            let processors: Vec<&mut dyn FeatureProcessor> = vec![&mut writer, &mut extent];
+            let mut tee = Tee::new(processors);
+            tee.add(&mut stats);
+            assert!(tee.dimensions().z);
+            GeoJson(geojson).process(&mut tee)?;
+        }
+        assert_eq!(stats.stats().features, 2);
+        assert_eq!(stats.stats().vertices_z, 2);
+        let extent = extent.extent().unwrap();
+        assert_eq!(extent.bbox(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!((extent.minz, extent.maxz), (Some(3.0), Some(5.0)));
+        // 2D writer gets no z values
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(1 2),POINT(3 4)");
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
 //! MVT conversions.
 mod mvt_commands;
+mod mvt_layer_writer;
 pub(crate) mod mvt_reader;
 pub(crate) mod mvt_writer;
 #[rustfmt::skip]
 mod vector_tile;
 
+pub use mvt_layer_writer::*;
 pub use mvt_reader::*;
 pub use mvt_writer::*;
 pub use vector_tile::*;
@@ -0,0 +1,232 @@
+use crate::error::Result;
+use crate::mvt::vector_tile::tile;
+use crate::mvt::MvtWriter;
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::HashMap;
+
+/// Hashable key of a [tile::Value]
+#[derive(PartialEq, Eq, Hash)]
+enum ValueKey {
+    String(String),
+    Float(u32),
+    Double(u64),
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+}
+
+/// Writer for an MVT layer with feature properties.
+///
+/// Geometry coordinates have to be in tile coordinates, e.g. produced by
+/// [TileClip](crate::processing::TileClip). Property keys and values are dictionary encoded,
+/// binary properties are skipped. Features without geometry are dropped.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::mvt::MvtLayerWriter;
+/// use geozero::{geojson::GeoJson, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [25, 17]}}"#;
+/// let mut writer = MvtLayerWriter::new("places", 4096);
+/// GeoJson(geojson).process(&mut writer).unwrap();
+/// let layer = writer.into_layer();
+/// assert_eq!(layer.features[0].geometry, [9, 50, 34]);
+/// assert_eq!(layer.keys, ["name"]);
+/// ```
+pub struct MvtLayerWriter {
+    layer: tile::Layer,
+    geom: MvtWriter,
+    /// Properties of current feature, added to the dictionaries when the feature is kept
+    properties: Vec<(String, ValueKey, tile::Value)>,
+    keys: HashMap<String, u32>,
+    values: HashMap<ValueKey, u32>,
+}
+
+impl MvtLayerWriter {
+    /// Writer for layer `name` with tile extent `extent`.
+    pub fn new(name: &str, extent: u32) -> Self {
+        MvtLayerWriter {
+            layer: tile::Layer {
+                version: 2,
+                name: name.to_string(),
+                extent: Some(extent),
+                ..Default::default()
+            },
+            geom: MvtWriter::new(),
+            properties: Vec::new(),
+            keys: HashMap::new(),
+            values: HashMap::new(),
+        }
+    }
+    /// Layer with features processed so far.
+    pub fn layer(&self) -> &tile::Layer {
+        &self.layer
+    }
+    /// Consume writer and return layer.
+    pub fn into_layer(self) -> tile::Layer {
+        self.layer
+    }
+
+    fn key_idx(&mut self, name: &str) -> u32 {
+        if let Some(idx) = self.keys.get(name) {
+            return *idx;
+        }
+        let idx = self.layer.keys.len() as u32;
+        self.layer.keys.push(name.to_string());
+        self.keys.insert(name.to_string(), idx);
+        idx
+    }
+
+    fn value_idx(&mut self, key: ValueKey, value: tile::Value) -> u32 {
+        let next = self.layer.values.len() as u32;
+        let idx = *self.values.entry(key).or_insert(next);
+        if idx == next {
+            self.layer.values.push(value);
+        }
+        idx
+    }
+}
+
+/// MVT value of a property. `None` for binary values.
+fn mvt_value(value: &ColumnValue) -> Option<(ValueKey, tile::Value)> {
+    let mut mvt = tile::Value::default();
+    let key = match *value {
+        ColumnValue::Byte(v) => int_value(&mut mvt, v.into()),
+        ColumnValue::Short(v) => int_value(&mut mvt, v.into()),
+        ColumnValue::Int(v) => int_value(&mut mvt, v.into()),
+        ColumnValue::Long(v) => int_value(&mut mvt, v),
+        ColumnValue::UByte(v) => uint_value(&mut mvt, v.into()),
+        ColumnValue::UShort(v) => uint_value(&mut mvt, v.into()),
+        ColumnValue::UInt(v) => uint_value(&mut mvt, v.into()),
+        ColumnValue::ULong(v) => uint_value(&mut mvt, v),
+        ColumnValue::Bool(v) => {
+            mvt.bool_value = Some(v);
+            ValueKey::Bool(v)
+        }
+        ColumnValue::Float(v) => {
+            mvt.float_value = Some(v);
+            ValueKey::Float(v.to_bits())
+        }
+        ColumnValue::Double(v) => {
+            mvt.double_value = Some(v);
+            ValueKey::Double(v.to_bits())
+        }
+        ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+            mvt.string_value = Some(v.to_string());
+            ValueKey::String(v.to_string())
+        }
+        ColumnValue::Binary(_) => return None,
+    };
+    Some((key, mvt))
+}
+
+fn int_value(mvt: &mut tile::Value, v: i64) -> ValueKey {
+    mvt.int_value = Some(v);
+    ValueKey::Int(v)
+}
+
+fn uint_value(mvt: &mut tile::Value, v: u64) -> ValueKey {
+    mvt.uint_value = Some(v);
+    ValueKey::UInt(v)
+}
+
+impl GeomProcessor for MvtLayerWriter {
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom.xy(x, y, idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipolygon_end(idx)
+    }
+}
+
+impl PropertyProcessor for MvtLayerWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let Some((key, value)) = mvt_value(value) {
+            self.properties.push((name.to_string(), key, value));
+        }
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for MvtLayerWriter {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.geom = MvtWriter::new();
+        self.properties.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let mut feature = std::mem::take(&mut self.geom.feature);
+        if !feature.geometry.is_empty() {
+            for (name, key, value) in std::mem::take(&mut self.properties) {
+                let key_idx = self.key_idx(&name);
+                let value_idx = self.value_idx(key, value);
+                feature.tags.push(key_idx);
+                feature.tags.push(value_idx);
+            }
+            self.layer.features.push(feature);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn properties() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"a_name": "a", "b_count": 2}, "geometry": {"type": "Point", "coordinates": [25, 17]}},
+            {"type": "Feature", "properties": {"b_count": 2, "c_area": 1.5}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+            {"type": "Feature", "properties": {"a_name": "b"}, "geometry": null}
+        ]}"#;
+        let mut writer = MvtLayerWriter::new("test", 4096);
+        GeoJson(geojson).process(&mut writer)?;
+        let layer = writer.into_layer();
+        assert_eq!(layer.name, "test");
+        assert_eq!(layer.features.len(), 2);
+        assert_eq!(layer.keys, ["a_name", "b_count", "c_area"]);
+        assert_eq!(layer.values.len(), 3);
+        assert_eq!(layer.features[0].tags, [0, 0, 1, 1]);
+        assert_eq!(layer.features[1].tags, [1, 1, 2, 2]);
+        assert_eq!(layer.values[1].int_value, Some(2));
+        Ok(())
+    }
+}
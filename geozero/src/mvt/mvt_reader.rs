@@ -10,8 +10,113 @@ impl GeozeroDatasource for tile::Layer {
     }
 }
 
+/// Coordinate space of decoded MVT geometries.
+///
+/// The `extent` of the variants is only used for layers without extent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TileCoords {
+    /// Integer tile coordinates as stored in the tile (`0..extent`).
+    #[default]
+    Raw,
+    /// Tile-local coordinates scaled to `0.0..1.0`, origin top left.
+    Local { extent: u32 },
+    /// WGS84 longitude/latitude of tile `z/x/y` (XYZ tiling scheme, Web Mercator).
+    Geographic { extent: u32, z: u8, x: u32, y: u32 },
+}
+
+impl TileCoords {
+    /// Geographic coordinates for tile `z/x/y` with default extent of 4096.
+    pub fn geographic(z: u8, x: u32, y: u32) -> Self {
+        TileCoords::Geographic {
+            extent: 4096,
+            z,
+            x,
+            y,
+        }
+    }
+
+    fn transform(&self, tx: i32, ty: i32) -> (f64, f64) {
+        match *self {
+            TileCoords::Raw => (tx as f64, ty as f64),
+            TileCoords::Local { extent } => (tx as f64 / extent as f64, ty as f64 / extent as f64),
+            TileCoords::Geographic { extent, z, x, y } => {
+                let n = f64::powi(2.0, z as i32);
+                let xt = x as f64 + tx as f64 / extent as f64;
+                let yt = y as f64 + ty as f64 / extent as f64;
+                let lon = xt / n * 360.0 - 180.0;
+                let lat = (std::f64::consts::PI * (1.0 - 2.0 * yt / n))
+                    .sinh()
+                    .atan()
+                    .to_degrees();
+                (lon, lat)
+            }
+        }
+    }
+
+    /// Use the extent of `layer`, if set, instead of the configured one.
+    fn with_layer_extent(self, layer: &tile::Layer) -> Self {
+        let layer_extent = match layer.extent {
+            Some(extent) => extent,
+            None => return self,
+        };
+        match self {
+            TileCoords::Raw => TileCoords::Raw,
+            TileCoords::Local { .. } => TileCoords::Local {
+                extent: layer_extent,
+            },
+            TileCoords::Geographic { z, x, y, .. } => TileCoords::Geographic {
+                extent: layer_extent,
+                z,
+                x,
+                y,
+            },
+        }
+    }
+}
+
+/// MVT layer with coordinates mapped to tile-local or geographic coordinates.
+pub struct MvtLayer<'a> {
+    layer: &'a tile::Layer,
+    coords: TileCoords,
+}
+
+impl<'a> MvtLayer<'a> {
+    /// Layer emitting coordinates in `0.0..1.0` tile space.
+    pub fn local(layer: &'a tile::Layer) -> Self {
+        MvtLayer {
+            layer,
+            coords: TileCoords::Local { extent: 4096 },
+        }
+    }
+    /// Layer of tile `z/x/y` emitting WGS84 coordinates.
+    pub fn geographic(layer: &'a tile::Layer, z: u8, x: u32, y: u32) -> Self {
+        MvtLayer {
+            layer,
+            coords: TileCoords::geographic(z, x, y),
+        }
+    }
+}
+
+impl GeozeroDatasource for MvtLayer<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        process_with_coords(self.layer, self.coords, processor)
+    }
+}
+
 /// Process MVT layer.
 pub fn process(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Result<()> {
+    process_with_coords(layer, TileCoords::Raw, processor)
+}
+
+/// Process MVT layer with coordinates mapped to `coords`.
+///
+/// The extent of `coords` is replaced by the layer extent, if the layer has one.
+pub fn process_with_coords(
+    layer: &tile::Layer,
+    coords: TileCoords,
+    processor: &mut impl FeatureProcessor,
+) -> Result<()> {
+    let coords = coords.with_layer_extent(layer);
     processor.dataset_begin(Some(&layer.name))?;
     for (idx, feature) in layer.features.iter().enumerate() {
         processor.feature_begin(idx as u64)?;
@@ -19,7 +124,7 @@ pub fn process(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Re
         process_properties(layer, feature, processor)?;
 
         processor.geometry_begin()?;
-        process_geom(feature, processor)?;
+        process_geom_n(feature, &coords, 0, processor)?;
         processor.geometry_end()?;
 
         processor.feature_end(idx as u64)?;
@@ -79,24 +184,34 @@ impl GeozeroGeometry for tile::Feature {
 
 /// Process MVT geometry.
 pub fn process_geom<P: GeomProcessor>(geom: &tile::Feature, processor: &mut P) -> Result<()> {
-    process_geom_n(geom, 0, processor)
+    process_geom_n(geom, &TileCoords::Raw, 0, processor)
+}
+
+/// Process MVT geometry with coordinates mapped to `coords`.
+pub fn process_geom_with_coords<P: GeomProcessor>(
+    geom: &tile::Feature,
+    coords: TileCoords,
+    processor: &mut P,
+) -> Result<()> {
+    process_geom_n(geom, &coords, 0, processor)
 }
 
 fn process_geom_n<P: GeomProcessor>(
     geom: &tile::Feature,
+    coords: &TileCoords,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
     let mut cursor: [i32; 2] = [0, 0];
     match geom.r#type {
         Some(r#type) if r#type == GeomType::Point as i32 => {
-            process_point(&mut cursor, &geom.geometry, idx, processor)?;
+            process_point(&mut cursor, coords, &geom.geometry, idx, processor)?;
         }
         Some(r#type) if r#type == GeomType::Linestring as i32 => {
-            process_linestrings(&mut cursor, geom, idx, processor)?;
+            process_linestrings(&mut cursor, coords, geom, idx, processor)?;
         }
         Some(r#type) if r#type == GeomType::Polygon as i32 => {
-            process_polygons(&mut cursor, geom, idx, processor)?;
+            process_polygons(&mut cursor, coords, geom, idx, processor)?;
         }
         _ => {}
     }
@@ -105,30 +220,25 @@ fn process_geom_n<P: GeomProcessor>(
 
 fn process_coord<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     coord: &[u32],
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
     cursor[0] += ParameterInteger(coord[0]).value();
     cursor[1] += ParameterInteger(coord[1]).value();
+    let (x, y) = coords.transform(cursor[0], cursor[1]);
     if processor.multi_dim() {
-        processor.coordinate(
-            cursor[0] as f64,
-            cursor[1] as f64,
-            None,
-            None,
-            None,
-            None,
-            idx,
-        )?;
+        processor.coordinate(x, y, None, None, None, None, idx)?;
     } else {
-        processor.xy(cursor[0] as f64, cursor[1] as f64, idx)?;
+        processor.xy(x, y, idx)?;
     }
     Ok(())
 }
 
 fn process_point<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     geom: &[u32],
     idx: usize,
     processor: &mut P,
@@ -137,12 +247,12 @@ fn process_point<P: GeomProcessor>(
     let count = command.count() as usize;
     if count == 1 {
         processor.point_begin(idx)?;
-        process_coord(cursor, &geom[1..3], 0, processor)?;
+        process_coord(cursor, coords, &geom[1..3], 0, processor)?;
         processor.point_end(idx)?;
     } else {
         processor.multipoint_begin(count, idx)?;
         for i in 0..count {
-            process_coord(cursor, &geom[1 + i * 2..3 + i * 2], i, processor)?;
+            process_coord(cursor, coords, &geom[1 + i * 2..3 + i * 2], i, processor)?;
         }
         processor.multipoint_end(idx)?;
     }
@@ -151,6 +261,7 @@ fn process_point<P: GeomProcessor>(
 
 fn process_linestring<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     geom: &[u32],
     tagged: bool,
     idx: usize,
@@ -164,9 +275,15 @@ fn process_linestring<P: GeomProcessor>(
         return Err(GeozeroError::GeometryFormat);
     }
     processor.linestring_begin(tagged, 1 + lineto.count() as usize, idx)?;
-    process_coord(cursor, &geom[1..3], 0, processor)?;
+    process_coord(cursor, coords, &geom[1..3], 0, processor)?;
     for i in 0..lineto.count() as usize {
-        process_coord(cursor, &geom[4 + i * 2..6 + i * 2], i + 1, processor)?;
+        process_coord(
+            cursor,
+            coords,
+            &geom[4 + i * 2..6 + i * 2],
+            i + 1,
+            processor,
+        )?;
     }
     processor.linestring_end(tagged, idx)?;
     Ok(())
@@ -174,6 +291,7 @@ fn process_linestring<P: GeomProcessor>(
 
 fn process_linestrings<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     geom: &tile::Feature,
     idx: usize,
     processor: &mut P,
@@ -192,11 +310,11 @@ fn process_linestrings<P: GeomProcessor>(
     if line_string_slices.len() > 1 {
         processor.multilinestring_begin(line_string_slices.len(), idx)?;
         for i in 0..line_string_slices.len() {
-            process_linestring(cursor, line_string_slices[i], false, i, processor)?;
+            process_linestring(cursor, coords, line_string_slices[i], false, i, processor)?;
         }
         processor.multilinestring_end(idx)?;
     } else {
-        process_linestring(cursor, line_string_slices[0], true, idx, processor)?;
+        process_linestring(cursor, coords, line_string_slices[0], true, idx, processor)?;
     }
 
     Ok(())
@@ -204,6 +322,7 @@ fn process_linestrings<P: GeomProcessor>(
 
 fn process_polygon<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     rings: &[&[u32]],
     tagged: bool,
     idx: usize,
@@ -224,12 +343,19 @@ fn process_polygon<P: GeomProcessor>(
         }
         processor.linestring_begin(false, 1 + lineto.count() as usize, i)?;
         let mut start_cursor = cursor.clone();
-        process_coord(cursor, &ring[1..3], 0, processor)?;
+        process_coord(cursor, coords, &ring[1..3], 0, processor)?;
         for i in 0..lineto.count() as usize {
-            process_coord(cursor, &ring[4 + i * 2..6 + i * 2], i + 1, processor)?;
+            process_coord(
+                cursor,
+                coords,
+                &ring[4 + i * 2..6 + i * 2],
+                i + 1,
+                processor,
+            )?;
         }
         process_coord(
             &mut start_cursor,
+            coords,
             &ring[1..3],
             1 + lineto.count() as usize,
             processor,
@@ -244,6 +370,7 @@ fn process_polygon<P: GeomProcessor>(
 
 fn process_polygons<P: GeomProcessor>(
     cursor: &mut [i32; 2],
+    coords: &TileCoords,
     geom: &tile::Feature,
     idx: usize,
     processor: &mut P,
@@ -275,11 +402,11 @@ fn process_polygons<P: GeomProcessor>(
     if polygon_slices.len() > 1 {
         processor.multipolygon_begin(polygon_slices.len(), idx)?;
         for i in 0..polygon_slices.len() {
-            process_polygon(cursor, &polygon_slices[i], false, i, processor)?;
+            process_polygon(cursor, coords, &polygon_slices[i], false, i, processor)?;
         }
         processor.multipolygon_end(idx)?;
     } else {
-        process_polygon(cursor, &polygon_slices[0], true, idx, processor)?;
+        process_polygon(cursor, coords, &polygon_slices[0], true, idx, processor)?;
     }
 
     Ok(())
@@ -480,4 +607,62 @@ mod test {
             r#"{"type": "MultiPolygon", "coordinates": [[[[0,0],[10,0],[10,10],[0,10],[0,0]]],[[[11,11],[20,11],[20,20],[11,20],[11,11]],[[13,13],[13,17],[17,17],[17,13],[13,13]]]]}"#
         );
     }
+
+    #[test]
+    fn dequantized_coords() {
+        let mut mvt_feature = tile::Feature::default();
+        mvt_feature.set_type(GeomType::Point);
+        mvt_feature.geometry = [9, 4096, 4096].to_vec();
+
+        let mut json: Vec<u8> = Vec::new();
+        let mut p = crate::geojson::GeoJsonWriter::new(&mut json);
+        process_geom_with_coords(&mvt_feature, TileCoords::Local { extent: 4096 }, &mut p).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{"type": "Point", "coordinates": [0.5,0.5]}"#
+        );
+
+        // center of tile 0/0/0
+        let mut json: Vec<u8> = Vec::new();
+        let mut p = crate::geojson::GeoJsonWriter::new(&mut json);
+        process_geom_with_coords(&mvt_feature, TileCoords::geographic(0, 0, 0), &mut p).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{"type": "Point", "coordinates": [0,0]}"#
+        );
+
+        // upper left corner of tile 1/1/0
+        mvt_feature.geometry = [9, 0, 0].to_vec();
+        let mut json: Vec<u8> = Vec::new();
+        let mut p = crate::geojson::GeoJsonWriter::new(&mut json);
+        process_geom_with_coords(&mvt_feature, TileCoords::geographic(1, 1, 0), &mut p).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{"type": "Point", "coordinates": [0,85.0511287798066]}"#
+        );
+    }
+
+    #[test]
+    fn geographic_layer() {
+        let mut mvt_layer = tile::Layer::default();
+        mvt_layer.version = 2;
+        mvt_layer.name = String::from("points");
+        mvt_layer.extent = Some(256);
+        let mut mvt_feature = tile::Feature::default();
+        mvt_feature.set_type(GeomType::Point);
+        mvt_feature.geometry = [9, 256, 256].to_vec();
+        mvt_layer.features.push(mvt_feature);
+
+        let geojson = MvtLayer::geographic(&mvt_layer, 0, 0, 0).to_json().unwrap();
+        assert!(geojson.contains(r#""coordinates": [0,0]"#));
+
+        // configured extent is used for layers without extent
+        mvt_layer.extent = None;
+        let mut json: Vec<u8> = Vec::new();
+        let mut p = crate::geojson::GeoJsonWriter::new(&mut json);
+        process_with_coords(&mvt_layer, TileCoords::Local { extent: 256 }, &mut p).unwrap();
+        assert!(std::str::from_utf8(&json)
+            .unwrap()
+            .contains(r#""coordinates": [0.5,0.5]"#));
+    }
 }
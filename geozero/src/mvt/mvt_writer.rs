@@ -2,17 +2,21 @@
 //! <https://github.com/mapbox/vector-tile-spec/tree/master/2.1>
 
 use crate::error::{GeozeroError, Result};
+use crate::geometry_processor::SizeCheck;
 use crate::mvt::mvt_commands::*;
 use crate::mvt::vector_tile::{tile, tile::GeomType};
 use crate::GeomProcessor;
 
 /// Generator for MVT geometry type.
 pub struct MvtWriter {
+    /// Fail if number of members differs from declared size
+    pub strict: bool,
     pub(crate) feature: tile::Feature,
     last_x: i32,
     last_y: i32,
     line_state: LineState,
     is_multiline: bool,
+    sizes: SizeCheck,
 }
 
 #[derive(PartialEq)]
@@ -26,16 +30,30 @@ enum LineState {
 impl MvtWriter {
     pub fn new() -> MvtWriter {
         MvtWriter {
+            strict: false,
             feature: tile::Feature::default(),
             last_x: 0,
             last_y: 0,
             line_state: LineState::None,
             is_multiline: false,
+            sizes: SizeCheck::default(),
         }
     }
     pub fn geometry(&self) -> &tile::Feature {
         &self.feature
     }
+    fn begin_sized(&mut self, size: usize) {
+        if self.strict {
+            self.sizes.begin(size);
+        }
+    }
+    fn end_sized(&mut self, geometry_type: &str) -> Result<()> {
+        if self.strict {
+            self.sizes.end(geometry_type)
+        } else {
+            Ok(())
+        }
+    }
     fn reserve(&mut self, capacity: usize) {
         let total = self.feature.geometry.len() + capacity;
         if total > self.feature.geometry.capacity() {
@@ -48,6 +66,9 @@ impl MvtWriter {
 
 impl GeomProcessor for MvtWriter {
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.strict {
+            self.sizes.member();
+        }
         // Omit last coord of ring (emit ClosePath instead)
         let last_ring_coord = if let LineState::Ring(size) = self.line_state {
             idx == size - 1
@@ -86,6 +107,7 @@ impl GeomProcessor for MvtWriter {
         Ok(())
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin_sized(1);
         self.feature.set_type(GeomType::Point);
         self.reserve(3);
         self.feature
@@ -93,7 +115,11 @@ impl GeomProcessor for MvtWriter {
             .push(CommandInteger::from(Command::MoveTo, 1));
         Ok(())
     }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("Point")
+    }
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.feature.set_type(GeomType::Point);
         self.reserve(1 + 2 * size);
         self.feature
@@ -101,7 +127,11 @@ impl GeomProcessor for MvtWriter {
             .push(CommandInteger::from(Command::MoveTo, size as u32));
         Ok(())
     }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiPoint")
+    }
     fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         if tagged {
             self.feature.set_type(GeomType::Linestring);
         }
@@ -124,27 +154,36 @@ impl GeomProcessor for MvtWriter {
                 .push(CommandInteger::from(Command::ClosePath, 1));
         }
         self.line_state = LineState::None;
-        Ok(())
+        self.end_sized("LineString")
     }
-    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+    fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.is_multiline = true;
         self.feature.set_type(GeomType::Linestring);
         Ok(())
     }
     fn multilinestring_end(&mut self, _size: usize) -> Result<()> {
         self.is_multiline = false;
-        Ok(())
+        self.end_sized("MultiLineString")
     }
-    fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+    fn polygon_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         if tagged {
             self.feature.set_type(GeomType::Polygon);
         }
         Ok(())
     }
-    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end_sized("Polygon")
+    }
+    fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.feature.set_type(GeomType::Polygon);
         Ok(())
     }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiPolygon")
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,27 @@
+//! Wavefront OBJ conversions.
+mod obj_writer;
+
+pub use obj_writer::*;
+
+pub(crate) mod conversion {
+    use super::obj_writer::*;
+    use crate::error::Result;
+    use crate::GeozeroGeometry;
+
+    /// Convert to Wavefront OBJ.
+    pub trait ToObj {
+        /// Convert to OBJ mesh.
+        fn to_obj(&self) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToObj for T {
+        fn to_obj(&self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = ObjWriter::new(&mut out);
+            self.process_geom(&mut writer)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
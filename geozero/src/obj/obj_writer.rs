@@ -0,0 +1,227 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::HashMap;
+use std::io::Write;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Element {
+    /// Polygon exterior ring
+    Face,
+    /// Polygon interior ring, not supported by OBJ
+    Hole,
+    Line,
+}
+
+/// Wavefront OBJ writer.
+///
+/// Polygons, Triangles, PolyhedralSurfaces and TINs are written as faces, LineStrings as lines
+/// and Points as point elements. Identical vertices are written once and shared between elements.
+/// Interior rings of polygons are omitted, missing Z values are written as 0.
+/// Each feature is written as named object.
+///
+/// Unbuffered outputs should be wrapped in a [WriteCounter](crate::WriteCounter).
+pub struct ObjWriter<'a, W: Write> {
+    out: &'a mut W,
+    vertices: HashMap<[u64; 3], usize>,
+    /// Nesting level of surfaces
+    surfaces: usize,
+    element: Option<(Element, Vec<usize>)>,
+}
+
+impl<'a, W: Write> ObjWriter<'a, W> {
+    pub fn new(out: &'a mut W) -> ObjWriter<'a, W> {
+        ObjWriter {
+            out,
+            vertices: HashMap::new(),
+            surfaces: 0,
+            element: None,
+        }
+    }
+    /// Number of distinct vertices written.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+    /// Index of vertex, written if not yet defined.
+    fn vertex(&mut self, x: f64, y: f64, z: Option<f64>) -> Result<usize> {
+        let z = z.unwrap_or(0.0);
+        // -0.0 and 0.0 are the same vertex
+        let key = [
+            (x + 0.0).to_bits(),
+            (y + 0.0).to_bits(),
+            (z + 0.0).to_bits(),
+        ];
+        if let Some(idx) = self.vertices.get(&key) {
+            return Ok(*idx);
+        }
+        writeln!(self.out, "v {} {} {}", x, y, z)?;
+        let idx = self.vertices.len() + 1;
+        self.vertices.insert(key, idx);
+        Ok(idx)
+    }
+    fn add_coord(&mut self, x: f64, y: f64, z: Option<f64>) -> Result<()> {
+        if let Some((Element::Hole, _)) = self.element {
+            return Ok(());
+        }
+        let idx = self.vertex(x, y, z)?;
+        match self.element.as_mut() {
+            Some((_, indices)) => indices.push(idx),
+            None => writeln!(self.out, "p {}", idx)?,
+        }
+        Ok(())
+    }
+    fn begin_ring(&mut self, tagged: bool, size: usize, idx: usize) {
+        let element = if self.surfaces == 0 {
+            Element::Line
+        } else if tagged || idx == 0 {
+            Element::Face
+        } else {
+            Element::Hole
+        };
+        self.element = Some((element, Vec::with_capacity(size)));
+    }
+    fn end_ring(&mut self) -> Result<()> {
+        match self.element.take() {
+            Some((Element::Face, mut indices)) => {
+                if indices.len() > 1 && indices.first() == indices.last() {
+                    indices.pop();
+                }
+                if indices.len() >= 3 {
+                    self.write_element("f", &indices)?;
+                }
+            }
+            Some((Element::Line, indices)) if indices.len() >= 2 => {
+                self.write_element("l", &indices)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn write_element(&mut self, tag: &str, indices: &[usize]) -> Result<()> {
+        self.out.write_all(tag.as_bytes())?;
+        for idx in indices {
+            write!(self.out, " {}", idx)?;
+        }
+        self.out.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> GeomProcessor for ObjWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_coord(x, y, None)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_coord(x, y, z)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin_ring(tagged, size, idx);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end_ring()
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin_ring(false, size, idx);
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_ring()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.surfaces += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.surfaces -= 1;
+        Ok(())
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.surfaces += 1;
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.surfaces -= 1;
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.surfaces += 1;
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.surfaces -= 1;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for ObjWriter<'_, W> {}
+
+impl<W: Write> FeatureProcessor for ObjWriter<'_, W> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        writeln!(self.out, "o feature_{}", idx)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tin() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = ObjWriter::new(&mut out);
+        let triangles = [
+            [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 1.0)],
+            [(1.0, 0.0, 0.0), (1.0, 1.0, 1.0), (0.0, 1.0, 1.0)],
+        ];
+        writer.tin_begin(2, 0)?;
+        for (idx, triangle) in triangles.iter().enumerate() {
+            writer.triangle_begin(false, 1, idx)?;
+            writer.linestring_begin(false, 4, 0)?;
+            for (idxc, (x, y, z)) in triangle.iter().chain(&triangle[..1]).enumerate() {
+                writer.coordinate(*x, *y, Some(*z), None, None, None, idxc)?;
+            }
+            writer.linestring_end(false, 0)?;
+            writer.triangle_end(false, idx)?;
+        }
+        writer.tin_end(0)?;
+        assert_eq!(writer.vertex_count(), 4);
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "v 0 0 0\nv 1 0 0\nv 0 1 1\nf 1 2 3\nv 1 1 1\nf 2 4 3\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn geometries() -> Result<()> {
+        use crate::wkt::WktStr;
+        use crate::ToObj;
+
+        assert_eq!(
+            WktStr("POLYGON((0 0,4 0,4 4,0 0),(1 1,2 1,2 2,1 1))").to_obj()?,
+            "v 0 0 0\nv 4 0 0\nv 4 4 0\nf 1 2 3\n"
+        );
+        assert_eq!(
+            WktStr("GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(1 2,3 4))").to_obj()?,
+            "v 1 2 0\np 1\nv 3 4 0\nl 1 2\n"
+        );
+        Ok(())
+    }
+}
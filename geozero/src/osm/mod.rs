@@ -0,0 +1,4 @@
+//! OpenStreetMap PBF conversions.
+mod osm_reader;
+
+pub use osm_reader::*;
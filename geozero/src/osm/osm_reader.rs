@@ -0,0 +1,216 @@
+use crate::error::{GeozeroError, Result};
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, PropertyProcessor};
+use osmpbf::{Element, ElementReader};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// OpenStreetMap PBF reader.
+///
+/// Tagged nodes are emitted as Points, tagged ways as LineStrings or, if closed
+/// and tagged as area, as Polygons. Ways are assembled from the node coordinates
+/// read before, as ordered in regular PBF files. Relations are not processed.
+pub struct OsmReader<R: Read + Send> {
+    reader: Option<R>,
+    /// Tags processed as properties (default: all)
+    pub tags: Option<Vec<String>>,
+}
+
+impl<R: Read + Send> OsmReader<R> {
+    pub fn new(reader: R) -> Self {
+        OsmReader {
+            reader: Some(reader),
+            tags: None,
+        }
+    }
+
+    /// Only process the given tags as properties.
+    pub fn with_tags(reader: R, tags: &[&str]) -> Self {
+        OsmReader {
+            reader: Some(reader),
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+        }
+    }
+}
+
+impl<R: Read + Send> GeozeroDatasource for OsmReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let reader = self
+            .reader
+            .take()
+            .ok_or_else(|| GeozeroError::Dataset("OSM data already processed".to_string()))?;
+        read_osm(reader, processor, self.tags.as_deref())
+    }
+}
+
+impl From<osmpbf::Error> for GeozeroError {
+    fn from(error: osmpbf::Error) -> Self {
+        GeozeroError::Dataset(error.to_string())
+    }
+}
+
+/// Read and process OpenStreetMap PBF data.
+pub fn read_osm<R: Read + Send, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+    tags: Option<&[String]>,
+) -> Result<()> {
+    let mut osm = OsmProcessor {
+        processor,
+        tags,
+        nodes: HashMap::new(),
+        feature_idx: 0,
+    };
+    let mut result = osm.processor.dataset_begin(None);
+    ElementReader::new(reader).for_each(|element| {
+        if result.is_ok() {
+            result = osm.process_element(element);
+        }
+    })?;
+    result?;
+    osm.processor.dataset_end()
+}
+
+struct OsmProcessor<'a, P: FeatureProcessor> {
+    processor: &'a mut P,
+    tags: Option<&'a [String]>,
+    /// Node coordinates for way assembly
+    nodes: HashMap<i64, (f64, f64)>,
+    feature_idx: u64,
+}
+
+impl<P: FeatureProcessor> OsmProcessor<'_, P> {
+    fn process_element(&mut self, element: Element) -> Result<()> {
+        match element {
+            Element::Node(node) => {
+                self.nodes.insert(node.id(), (node.lon(), node.lat()));
+                let tags: Vec<(&str, &str)> = node.tags().collect();
+                self.process_point(node.id(), node.lon(), node.lat(), &tags)
+            }
+            Element::DenseNode(node) => {
+                self.nodes.insert(node.id(), (node.lon(), node.lat()));
+                let tags: Vec<(&str, &str)> = node.tags().collect();
+                self.process_point(node.id(), node.lon(), node.lat(), &tags)
+            }
+            Element::Way(way) => {
+                let tags: Vec<(&str, &str)> = way.tags().collect();
+                let refs: Vec<i64> = way.refs().collect();
+                self.process_way(way.id(), &refs, &tags)
+            }
+            Element::Relation(_) => Ok(()),
+        }
+    }
+
+    fn process_point(&mut self, id: i64, x: f64, y: f64, tags: &[(&str, &str)]) -> Result<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        self.feature_begin(id, tags)?;
+        let processor = &mut *self.processor;
+        processor.point_begin(0)?;
+        process_coord(processor, x, y, 0)?;
+        processor.point_end(0)?;
+        self.feature_end()
+    }
+
+    fn process_way(&mut self, id: i64, refs: &[i64], tags: &[(&str, &str)]) -> Result<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        let coords = refs
+            .iter()
+            .map(|node| self.nodes.get(node).copied())
+            .collect::<Option<Vec<_>>>();
+        let coords = match coords {
+            Some(coords) if coords.len() >= 2 => coords,
+            _ => {
+                log::warn!("way {}: missing node coordinates", id);
+                return Ok(());
+            }
+        };
+        let polygon = refs.len() >= 4 && refs.first() == refs.last() && is_area(tags);
+        self.feature_begin(id, tags)?;
+        let processor = &mut *self.processor;
+        if polygon {
+            processor.polygon_begin(true, 1, 0)?;
+            processor.linestring_begin(false, coords.len(), 0)?;
+        } else {
+            processor.linestring_begin(true, coords.len(), 0)?;
+        }
+        for (idx, (x, y)) in coords.iter().enumerate() {
+            process_coord(processor, *x, *y, idx)?;
+        }
+        if polygon {
+            processor.linestring_end(false, 0)?;
+            processor.polygon_end(true, 0)?;
+        } else {
+            processor.linestring_end(true, 0)?;
+        }
+        self.feature_end()
+    }
+
+    fn feature_begin(&mut self, id: i64, tags: &[(&str, &str)]) -> Result<()> {
+        let processor = &mut *self.processor;
+        processor.feature_begin(self.feature_idx)?;
+        processor.properties_begin()?;
+        processor.property(0, "osm_id", &ColumnValue::Long(id))?;
+        let mut i = 1;
+        for (key, value) in tags {
+            let selected = self
+                .tags
+                .map(|selected| selected.iter().any(|tag| tag.as_str() == *key))
+                .unwrap_or(true);
+            if selected {
+                if processor.property(i, key, &ColumnValue::String(value))? {
+                    break;
+                }
+                i += 1;
+            }
+        }
+        processor.properties_end()?;
+        processor.geometry_begin()
+    }
+
+    fn feature_end(&mut self) -> Result<()> {
+        self.processor.geometry_end()?;
+        self.processor.feature_end(self.feature_idx)?;
+        self.feature_idx += 1;
+        Ok(())
+    }
+}
+
+fn process_coord<P: GeomProcessor>(processor: &mut P, x: f64, y: f64, idx: usize) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(x, y, None, None, None, None, idx)
+    } else {
+        processor.xy(x, y, idx)
+    }
+}
+
+/// Keys of closed ways which are areas by default
+const AREA_KEYS: [&str; 8] = [
+    "building", "landuse", "leisure", "amenity", "natural", "place", "water", "boundary",
+];
+
+/// Closed way area detection from tags
+fn is_area(tags: &[(&str, &str)]) -> bool {
+    if let Some((_, area)) = tags.iter().find(|(key, _)| *key == "area") {
+        return *area != "no";
+    }
+    tags.iter().any(|(key, value)| {
+        AREA_KEYS.contains(key) && !(*key == "natural" && *value == "coastline")
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn area_tags() {
+        assert!(is_area(&[("building", "yes")]));
+        assert!(is_area(&[("highway", "pedestrian"), ("area", "yes")]));
+        assert!(!is_area(&[("building", "yes"), ("area", "no")]));
+        assert!(!is_area(&[("highway", "residential")]));
+        assert!(!is_area(&[("natural", "coastline")]));
+    }
+}
@@ -0,0 +1,55 @@
+//! Parallel dataset processing.
+
+use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::FeatureProcessor;
+use rayon::prelude::*;
+
+/// Number of features decoded in parallel before forwarding them.
+const BATCH_SIZE: u64 = 1024;
+
+/// Datasource with features which can be decoded independently.
+///
+/// Currently only implemented by `GeoArrowReader` for Arrow record batches.
+/// Readers of streamed sources like the Shapefile reader don't support it.
+pub trait ParallelDatasource: Sync {
+    /// Number of features.
+    fn feature_count(&self) -> u64;
+    /// Process feature at position `idx`, including `feature_begin` and `feature_end`.
+    fn process_feature<P: FeatureProcessor>(&self, idx: u64, processor: &mut P) -> Result<()>;
+    /// Decode features on the rayon thread pool and process them in order.
+    fn par_process<P: FeatureProcessor>(&self, processor: &mut P) -> Result<()>
+    where
+        Self: Sized,
+    {
+        par_process(self, processor)
+    }
+}
+
+/// Decode features on the rayon thread pool and process them in order.
+///
+/// Features are decoded in batches, the output is identical to sequential processing.
+pub fn par_process<D: ParallelDatasource, P: FeatureProcessor>(
+    datasource: &D,
+    processor: &mut P,
+) -> Result<()> {
+    processor.dataset_begin(None)?;
+    let count = datasource.feature_count();
+    let mut start = 0;
+    while start < count {
+        let end = (start + BATCH_SIZE).min(count);
+        let features = (start..end)
+            .into_par_iter()
+            .map(|idx| {
+                let mut recorder = FeatureRecorder::default();
+                datasource.process_feature(idx, &mut recorder)?;
+                Ok(recorder)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for feature in &features {
+            feature.replay(processor)?;
+        }
+        start = end;
+    }
+    processor.dataset_end()
+}
@@ -0,0 +1,26 @@
+//! PMTiles tile archives.
+//!
+//! [PMTiles](https://github.com/protomaps/PMTiles) version 3 is a single-file archive of
+//! tiles addressed by Hilbert curve tile ids, which can be served from cloud storage with
+//! HTTP range requests.
+//!
+//! # Usage example:
+//!
+//! ```
+//! use geozero::geojson::GeoJsonString;
+//! use geozero::pmtiles::PmtilesWriter;
+//! use geozero::tiling::{generate_tiles, TilingOptions};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! # fn tile() -> geozero::error::Result<()> {
+//! let mut geojson = GeoJsonString(std::fs::read_to_string("places.json")?);
+//! let mut writer = PmtilesWriter::new(BufWriter::new(File::create("places.pmtiles")?));
+//! generate_tiles(&mut geojson, &TilingOptions::default(), &mut writer)?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod pmtiles_writer;
+
+pub use pmtiles_writer::*;
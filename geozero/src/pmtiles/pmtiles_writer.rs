@@ -0,0 +1,374 @@
+use crate::error::{GeozeroError, Result};
+use crate::tiling::TileSink;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
+use std::io::Write;
+
+const HEADER_LEN: usize = 127;
+/// Maximal size of header and root directory
+const ROOT_MAX_LEN: usize = 16384;
+
+const COMPRESSION_NONE: u8 = 1;
+const COMPRESSION_GZIP: u8 = 2;
+const TILE_TYPE_MVT: u8 = 1;
+
+/// Directory entry. Entries with `run_length` 0 point to leaf directories.
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    tile_id: u64,
+    offset: u64,
+    length: u64,
+    run_length: u32,
+}
+
+/// PMTiles v3 archive writer.
+///
+/// Tiles are buffered in memory and written with [finish](PmtilesWriter::finish), ordered
+/// by tile id (clustered). Identical tiles are stored once, consecutive identical tiles
+/// are run-length encoded. Directories are split into leaf directories if the root
+/// directory would exceed 16 KiB. Directories and metadata are gzip compressed.
+pub struct PmtilesWriter<W: Write> {
+    /// Gzip compress tiles (default: true)
+    pub compress_tiles: bool,
+    /// JSON metadata, e.g. `vector_layers`
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    out: W,
+    tiles: BTreeMap<u64, Vec<u8>>,
+    /// `(z, x, y)` of tiles at the highest zoom level
+    max_zoom_tiles: Option<(u8, [u32; 4])>,
+    min_zoom: Option<u8>,
+    finished: bool,
+}
+
+impl<W: Write> PmtilesWriter<W> {
+    /// Writer into `out`.
+    pub fn new(out: W) -> Self {
+        PmtilesWriter {
+            compress_tiles: true,
+            metadata: serde_json::Map::new(),
+            out,
+            tiles: BTreeMap::new(),
+            max_zoom_tiles: None,
+            min_zoom: None,
+            finished: false,
+        }
+    }
+
+    /// Consume writer and return output.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Add tile `z/x/y` (XYZ tiling scheme).
+    pub fn add_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()> {
+        if z > 31 || x >= 1 << z || y >= 1 << z {
+            return Err(GeozeroError::Dataset(format!(
+                "invalid tile {}/{}/{}",
+                z, x, y
+            )));
+        }
+        let data = if self.compress_tiles {
+            gzip(data)?
+        } else {
+            data.to_vec()
+        };
+        self.tiles.insert(tile_id(z, x, y), data);
+        self.min_zoom = Some(self.min_zoom.map_or(z, |min| min.min(z)));
+        self.max_zoom_tiles = match self.max_zoom_tiles {
+            Some((max, _)) if z < max => self.max_zoom_tiles,
+            Some((max, [minx, miny, maxx, maxy])) if z == max => {
+                Some((z, [minx.min(x), miny.min(y), maxx.max(x), maxy.max(y)]))
+            }
+            _ => Some((z, [x, y, x, y])),
+        };
+        Ok(())
+    }
+
+    /// Write archive. Further calls have no effect.
+    pub fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut contents: HashMap<&[u8], u64> = HashMap::new();
+        let mut tile_data: Vec<u8> = Vec::new();
+        for (tile_id, data) in &self.tiles {
+            let offset = match contents.get(data.as_slice()) {
+                Some(offset) => *offset,
+                None => {
+                    let offset = tile_data.len() as u64;
+                    tile_data.extend_from_slice(data);
+                    contents.insert(data.as_slice(), offset);
+                    offset
+                }
+            };
+            if let Some(last) = entries.last_mut() {
+                if last.offset == offset && last.tile_id + last.run_length as u64 == *tile_id {
+                    last.run_length += 1;
+                    continue;
+                }
+            }
+            entries.push(Entry {
+                tile_id: *tile_id,
+                offset,
+                length: data.len() as u64,
+                run_length: 1,
+            });
+        }
+
+        let (root_dir, leaf_dirs) = build_directories(&entries)?;
+        let metadata = gzip(&serde_json::to_vec(&self.metadata).map_err(json_error)?)?;
+
+        let (min_zoom, (max_zoom, [minx, miny, maxx, maxy])) =
+            match (self.min_zoom, self.max_zoom_tiles) {
+                (Some(min_zoom), Some(max_zoom_tiles)) => (min_zoom, max_zoom_tiles),
+                _ => (0, (0, [0, 0, 0, 0])),
+            };
+        let (min_lon, max_lat) = tile_lon_lat(max_zoom, minx, miny);
+        let (max_lon, min_lat) = tile_lon_lat(max_zoom, maxx + 1, maxy + 1);
+
+        let root_offset = HEADER_LEN as u64;
+        let metadata_offset = root_offset + root_dir.len() as u64;
+        let leaf_offset = metadata_offset + metadata.len() as u64;
+        let data_offset = leaf_offset + leaf_dirs.len() as u64;
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(b"PMTiles");
+        header.push(3);
+        for v in [
+            root_offset,
+            root_dir.len() as u64,
+            metadata_offset,
+            metadata.len() as u64,
+            leaf_offset,
+            leaf_dirs.len() as u64,
+            data_offset,
+            tile_data.len() as u64,
+            self.tiles.len() as u64,
+            entries.len() as u64,
+            contents.len() as u64,
+        ] {
+            header.extend_from_slice(&v.to_le_bytes());
+        }
+        header.push(1); // clustered
+        header.push(COMPRESSION_GZIP);
+        header.push(if self.compress_tiles {
+            COMPRESSION_GZIP
+        } else {
+            COMPRESSION_NONE
+        });
+        header.push(TILE_TYPE_MVT);
+        header.push(min_zoom);
+        header.push(max_zoom);
+        for v in [min_lon, min_lat, max_lon, max_lat] {
+            header.extend_from_slice(&e7(v).to_le_bytes());
+        }
+        header.push(min_zoom);
+        header.extend_from_slice(&e7((min_lon + max_lon) / 2.0).to_le_bytes());
+        header.extend_from_slice(&e7((min_lat + max_lat) / 2.0).to_le_bytes());
+        debug_assert_eq!(header.len(), HEADER_LEN);
+
+        self.out.write_all(&header)?;
+        self.out.write_all(&root_dir)?;
+        self.out.write_all(&metadata)?;
+        self.out.write_all(&leaf_dirs)?;
+        self.out.write_all(&tile_data)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> TileSink for PmtilesWriter<W> {
+    fn write_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()> {
+        self.add_tile(z, x, y, data)
+    }
+    fn finish(&mut self) -> Result<()> {
+        PmtilesWriter::finish(self)
+    }
+}
+
+/// Hilbert curve tile id of tile `z/x/y`.
+fn tile_id(z: u8, x: u32, y: u32) -> u64 {
+    let base = ((1u64 << (2 * z as u64)) - 1) / 3;
+    let n = 1u64 << z;
+    let (mut x, mut y) = (x as u64, y as u64);
+    let mut d = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = ((x & s) > 0) as u64;
+        let ry = ((y & s) > 0) as u64;
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    base + d
+}
+
+/// Longitude and latitude of the top left corner of tile `z/x/y`.
+fn tile_lon_lat(z: u8, x: u32, y: u32) -> (f64, f64) {
+    let n = f64::powi(2.0, z as i32);
+    let lon = x as f64 / n * 360.0 - 180.0;
+    let lat = (PI * (1.0 - 2.0 * y as f64 / n)).sinh().atan().to_degrees();
+    (lon, lat)
+}
+
+fn e7(v: f64) -> i32 {
+    (v * 10_000_000.0).round() as i32
+}
+
+/// Serialized and compressed root directory and leaf directories.
+fn build_directories(entries: &[Entry]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let max_root_len = ROOT_MAX_LEN - HEADER_LEN;
+    if entries.len() < max_root_len {
+        let root = gzip(&serialize_directory(entries))?;
+        if root.len() <= max_root_len {
+            return Ok((root, Vec::new()));
+        }
+    }
+    let mut leaf_size = (entries.len() / 3500).max(4096);
+    loop {
+        let mut root_entries = Vec::new();
+        let mut leaf_dirs = Vec::new();
+        for chunk in entries.chunks(leaf_size) {
+            let leaf = gzip(&serialize_directory(chunk))?;
+            root_entries.push(Entry {
+                tile_id: chunk[0].tile_id,
+                offset: leaf_dirs.len() as u64,
+                length: leaf.len() as u64,
+                run_length: 0,
+            });
+            leaf_dirs.extend_from_slice(&leaf);
+        }
+        let root = gzip(&serialize_directory(&root_entries))?;
+        if root.len() <= max_root_len {
+            return Ok((root, leaf_dirs));
+        }
+        leaf_size += leaf_size / 5;
+    }
+}
+
+/// Uncompressed directory with columns of tile id deltas, run lengths, lengths and offsets.
+fn serialize_directory(entries: &[Entry]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_varint(&mut buf, entries.len() as u64);
+    let mut last_id = 0;
+    for entry in entries {
+        write_varint(&mut buf, entry.tile_id - last_id);
+        last_id = entry.tile_id;
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.run_length as u64);
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.length);
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        // 0 marks an entry directly following the previous entry
+        if i > 0 && entry.offset == entries[i - 1].offset + entries[i - 1].length {
+            write_varint(&mut buf, 0);
+        } else {
+            write_varint(&mut buf, entry.offset + 1);
+        }
+    }
+    buf
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+fn json_error(e: serde_json::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::convert::TryInto;
+    use std::io::Read;
+
+    fn read_varint(buf: &mut &[u8]) -> u64 {
+        let mut v = 0;
+        let mut shift = 0;
+        loop {
+            let b = buf[0];
+            *buf = &buf[1..];
+            v |= ((b & 0x7f) as u64) << shift;
+            if b < 0x80 {
+                return v;
+            }
+            shift += 7;
+        }
+    }
+
+    fn u64_at(data: &[u8], pos: usize) -> u64 {
+        u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn tile_ids() {
+        assert_eq!(tile_id(0, 0, 0), 0);
+        assert_eq!(tile_id(1, 0, 0), 1);
+        assert_eq!(tile_id(1, 0, 1), 2);
+        assert_eq!(tile_id(1, 1, 1), 3);
+        assert_eq!(tile_id(1, 1, 0), 4);
+        assert_eq!(tile_id(2, 0, 0), 5);
+        assert_eq!(tile_id(3, 0, 0), 21);
+    }
+
+    #[test]
+    fn archive() -> Result<()> {
+        let mut writer = PmtilesWriter::new(Vec::new());
+        writer.compress_tiles = false;
+        writer
+            .metadata
+            .insert("name".to_string(), serde_json::json!("test"));
+        writer.write_tile(1, 1, 0, b"a")?;
+        writer.write_tile(0, 0, 0, b"b")?;
+        writer.write_tile(1, 0, 0, b"a")?;
+        writer.write_tile(1, 0, 1, b"a")?;
+        TileSink::finish(&mut writer)?;
+        let data = writer.into_inner();
+
+        assert_eq!(&data[0..8], b"PMTiles\x03");
+        assert_eq!(u64_at(&data, 72), 4); // addressed tiles
+        assert_eq!(u64_at(&data, 80), 3); // tile entries
+        assert_eq!(u64_at(&data, 88), 2); // tile contents
+        assert_eq!(&data[96..102], &[1, 2, 1, 1, 0, 1]);
+        assert_eq!(&data[102..106], &e7(-180.0).to_le_bytes());
+
+        let (root_offset, root_len) = (u64_at(&data, 8) as usize, u64_at(&data, 16) as usize);
+        let mut root = Vec::new();
+        GzDecoder::new(&data[root_offset..root_offset + root_len]).read_to_end(&mut root)?;
+        let mut buf = root.as_slice();
+        let values: Vec<u64> = (0..13).map(|_| read_varint(&mut buf)).collect();
+        // count, tile id deltas, run lengths, lengths, offsets
+        assert_eq!(values, [3, 0, 1, 3, 1, 2, 1, 1, 1, 1, 1, 0, 2]);
+        assert!(buf.is_empty());
+
+        let (data_offset, data_len) = (u64_at(&data, 56) as usize, u64_at(&data, 64) as usize);
+        assert_eq!(&data[data_offset..data_offset + data_len], b"ba");
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! Encoded polyline conversions.
+//!
+//! Polyline algorithm format as used by Google Maps and OSRM, with precision 5 (polyline5)
+//! or 6 (polyline6).
+mod polyline_reader;
+mod polyline_writer;
+
+pub use polyline_reader::*;
+pub use polyline_writer::*;
+
+pub(crate) mod conversion {
+    use super::polyline_writer::*;
+    use crate::error::Result;
+    use crate::GeozeroGeometry;
+
+    /// Convert to encoded polyline.
+    pub trait ToPolyline {
+        /// Convert LineString or Point to encoded polyline with given precision (5 or 6).
+        fn to_polyline(&self, precision: u32) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToPolyline for T {
+        fn to_polyline(&self, precision: u32) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = PolylineWriter::new(&mut out, precision);
+            self.process_geom(&mut writer)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::{GeozeroError, Result};
+use crate::{GeomProcessor, GeozeroGeometry};
+
+/// Encoded polyline.
+pub struct Polyline<'a> {
+    encoded: &'a str,
+    precision: u32,
+}
+
+impl<'a> Polyline<'a> {
+    /// Polyline with precision 5 (polyline5).
+    pub fn new(encoded: &'a str) -> Self {
+        Self::with_precision(encoded, 5)
+    }
+
+    /// Polyline with given precision, e.g. 6 for polyline6.
+    pub fn with_precision(encoded: &'a str, precision: u32) -> Self {
+        Polyline { encoded, precision }
+    }
+}
+
+impl GeozeroGeometry for Polyline<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_polyline(self.encoded, self.precision, processor)
+    }
+}
+
+/// Decode polyline and process it as LineString.
+pub fn read_polyline<P: GeomProcessor>(
+    encoded: &str,
+    precision: u32,
+    processor: &mut P,
+) -> Result<()> {
+    let coords = decode(encoded, precision)?;
+    processor.linestring_begin(true, coords.len(), 0)?;
+    for (idx, (x, y)) in coords.into_iter().enumerate() {
+        if processor.multi_dim() {
+            processor.coordinate(x, y, None, None, None, None, idx)?;
+        } else {
+            processor.xy(x, y, idx)?;
+        }
+    }
+    processor.linestring_end(true, 0)
+}
+
+/// Decode polyline into (lon, lat) pairs
+fn decode(encoded: &str, precision: u32) -> Result<Vec<(f64, f64)>> {
+    let factor = 10f64.powi(precision as i32);
+    let mut bytes = encoded.bytes();
+    let mut coords = Vec::new();
+    let (mut lat, mut lon) = (0i64, 0i64);
+    while let Some(dlat) = decode_value(&mut bytes)? {
+        let dlon = decode_value(&mut bytes)?.ok_or_else(invalid_encoding)?;
+        lat = lat.checked_add(dlat).ok_or_else(invalid_encoding)?;
+        lon = lon.checked_add(dlon).ok_or_else(invalid_encoding)?;
+        coords.push((lon as f64 / factor, lat as f64 / factor));
+    }
+    Ok(coords)
+}
+
+/// Decode a single value, `None` at end of input
+fn decode_value(bytes: &mut impl Iterator<Item = u8>) -> Result<Option<i64>> {
+    let mut result = 0i64;
+    let mut shift = 0;
+    loop {
+        let byte = match bytes.next() {
+            Some(byte) => byte,
+            None if shift == 0 => return Ok(None),
+            None => return Err(invalid_encoding()),
+        };
+        if !(63..=127).contains(&byte) || shift > 60 {
+            return Err(invalid_encoding());
+        }
+        let chunk = (byte - 63) as i64;
+        result |= (chunk & 0x1f) << shift;
+        shift += 5;
+        if chunk < 0x20 {
+            break;
+        }
+    }
+    let value = if result & 1 == 1 {
+        !(result >> 1)
+    } else {
+        result >> 1
+    };
+    Ok(Some(value))
+}
+
+fn invalid_encoding() -> GeozeroError {
+    GeozeroError::Geometry("Invalid polyline encoding".to_string())
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::ToWkt;
+
+    #[test]
+    fn google_example() -> Result<()> {
+        // https://developers.google.com/maps/documentation/utilities/polylinealgorithm
+        let polyline = Polyline::new("_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+        assert_eq!(
+            polyline.to_wkt()?,
+            "LINESTRING(-120.2 38.5,-120.95 40.7,-126.453 43.252)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn precision6() -> Result<()> {
+        let polyline = Polyline::with_precision("_izlhA~rlgdF_{geC~ywl@", 6);
+        assert_eq!(polyline.to_wkt()?, "LINESTRING(-120.2 38.5,-120.95 40.7)");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Polyline::new("_p~iF~ps|U_").to_wkt().is_err());
+        assert!(Polyline::new("_p~iF").to_wkt().is_err());
+        assert!(Polyline::new("_p~iF ").to_wkt().is_err());
+        // accumulated offsets overflowing i64
+        let max_delta = format!("}}{}F", "~".repeat(11));
+        assert!(Polyline::new(&max_delta.repeat(6)).to_wkt().is_err());
+    }
+}
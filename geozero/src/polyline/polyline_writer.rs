@@ -0,0 +1,110 @@
+use crate::error::{GeozeroError, Result};
+use crate::GeomProcessor;
+use std::io::Write;
+
+/// Encoded polyline writer.
+///
+/// Supports LineString and Point geometries.
+pub struct PolylineWriter<'a, W: Write> {
+    factor: f64,
+    prev: (i64, i64),
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> PolylineWriter<'a, W> {
+    /// Writer with given precision, e.g. 5 for polyline5 or 6 for polyline6.
+    pub fn new(out: &'a mut W, precision: u32) -> PolylineWriter<'a, W> {
+        PolylineWriter {
+            factor: 10f64.powi(precision as i32),
+            prev: (0, 0),
+            out,
+        }
+    }
+
+    fn write_value(&mut self, value: i64) -> Result<()> {
+        let mut v = if value < 0 { !(value << 1) } else { value << 1 };
+        let mut buf = Vec::with_capacity(4);
+        while v >= 0x20 {
+            buf.push(((0x20 | (v & 0x1f)) + 63) as u8);
+            v >>= 5;
+        }
+        buf.push((v + 63) as u8);
+        self.out.write_all(&buf)?;
+        Ok(())
+    }
+
+    fn unsupported() -> GeozeroError {
+        GeozeroError::Geometry("Polyline supports only LineString and Point".to_string())
+    }
+}
+
+impl<W: Write> GeomProcessor for PolylineWriter<'_, W> {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        let lat = (y * self.factor).round() as i64;
+        let lon = (x * self.factor).round() as i64;
+        self.write_value(lat - self.prev.0)?;
+        self.write_value(lon - self.prev.1)?;
+        self.prev = (lat, lon);
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.prev = (0, 0);
+        Ok(())
+    }
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if !tagged {
+            return Err(Self::unsupported());
+        }
+        self.prev = (0, 0);
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(Self::unsupported())
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use crate::geojson::GeoJson;
+    use crate::polyline::Polyline;
+    use crate::wkt::WktStr;
+    use crate::{ToJson, ToPolyline};
+
+    #[test]
+    fn linestring() {
+        let wkt = WktStr("LINESTRING(-120.2 38.5,-120.95 40.7,-126.453 43.252)");
+        assert_eq!(wkt.to_polyline(5).unwrap(), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+        let wkt = WktStr("LINESTRING(-120.2 38.5,-120.95 40.7)");
+        assert_eq!(wkt.to_polyline(6).unwrap(), "_izlhA~rlgdF_{geC~ywl@");
+        assert_eq!(
+            WktStr("POINT(-120.2 38.5)").to_polyline(5).unwrap(),
+            "_p~iF~ps|U"
+        );
+        assert!(WktStr("MULTIPOINT(1 2,3 4)").to_polyline(5).is_err());
+    }
+
+    #[test]
+    fn geojson_roundtrip() {
+        let geojson = GeoJson(
+            r#"{"type": "LineString", "coordinates": [[13.38886,52.517037],[13.397634,52.529407]]}"#,
+        );
+        let polyline = geojson.to_polyline(6).unwrap();
+        assert_eq!(
+            Polyline::with_precision(&polyline, 6).to_json().unwrap(),
+            r#"{"type": "LineString", "coordinates": [[13.38886,52.517037],[13.397634,52.529407]]}"#
+        );
+    }
+}
@@ -3,6 +3,11 @@
 //! All geometry types implementing [GeozeroGeometry](crate::GeozeroGeometry) can be encoded as PostGIS EWKB geometry using [wkb::Encode](crate::wkb::Encode).
 //!
 //! Geometry types implementing [FromWkb](crate::wkb::FromWkb) can be decoded from PostGIS geometries using [wkb::Decode](crate::wkb::Decode).
+//!
+//! Features can be bulk loaded with binary `COPY` using [PostgisWriter] and read from binary `COPY` output
+//! with [PostgisCopyReader].
+#[cfg(feature = "with-postgis-copy")]
+mod postgis_copy;
 #[cfg(feature = "with-postgis-diesel")]
 mod postgis_diesel;
 #[cfg(feature = "with-postgis-postgres")]
@@ -10,6 +15,9 @@ mod postgis_postgres;
 #[cfg(feature = "with-postgis-sqlx")]
 mod postgis_sqlx;
 
+#[cfg(feature = "with-postgis-copy")]
+pub use postgis_copy::*;
+
 /// PostGIS geometry type encoding/decoding for rust-postgres.
 ///
 /// # PostGIS usage example with rust-postgres
@@ -0,0 +1,660 @@
+use crate::error::{GeozeroError, Result};
+use crate::processing::record::FeatureRecorder;
+use crate::wkb::wkb_reader::process_wkb_sized;
+use crate::wkb::{WkbDialect, WkbWriter};
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource,
+    PropertyProcessor,
+};
+use scroll::IOread;
+use std::convert::TryFrom;
+use std::io::{Read, Write};
+
+/// Signature of PostgreSQL binary COPY data
+const COPY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// PostgreSQL type of a [PostgisWriter] column.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PgType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Text,
+    Json,
+    Jsonb,
+    Bytea,
+    /// PostGIS `geometry` column receiving the feature geometry as EWKB
+    Geometry,
+}
+
+/// Column of the COPY target table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PgColumn {
+    pub name: String,
+    pub pg_type: PgType,
+}
+
+impl PgColumn {
+    pub fn new(name: &str, pg_type: PgType) -> Self {
+        PgColumn {
+            name: name.to_string(),
+            pg_type,
+        }
+    }
+}
+
+/// PostGIS bulk loader writing PostgreSQL binary COPY data.
+///
+/// Each feature is written as one row with the given `columns`. Properties are matched
+/// by column name and converted to the column type, missing properties are written as NULL.
+/// The feature geometry is written as EWKB into the first [PgType::Geometry] column,
+/// additional geometries into the [PgType::Geometry] column with the same name.
+///
+/// The output is meant to be streamed into the statement returned by
+/// [copy_statement](PostgisWriter::copy_statement), e.g. with `Client::copy_in` of rust-postgres.
+/// Buffered output is flushed at the end of each batch (see [Batcher](crate::processing::Batcher)).
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::postgis::{PgColumn, PgType, PostgisWriter};
+/// use geozero::GeozeroDatasource;
+///
+/// let mut copy_data = Vec::new();
+/// let mut writer = PostgisWriter::new(
+///     &mut copy_data,
+///     vec![
+///         PgColumn::new("name", PgType::Text),
+///         PgColumn::new("geom", PgType::Geometry),
+///     ],
+/// );
+/// writer.srid = Some(4326);
+/// assert_eq!(
+///     writer.copy_statement("places"),
+///     r#"COPY "places" ("name", "geom") FROM STDIN (FORMAT binary)"#
+/// );
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "Zurich"}, "geometry": {"type": "Point", "coordinates": [8.5, 47.4]}}"#)
+///     .process(&mut writer)
+///     .unwrap();
+/// ```
+pub struct PostgisWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// SRID of geometries, defaults to the dataset SRID
+    pub srid: Option<i32>,
+    out: &'a mut W,
+    columns: Vec<PgColumn>,
+    /// Encoded values of current row
+    values: Vec<Option<Vec<u8>>>,
+    geometry: FeatureRecorder,
+    /// Current additional geometry
+    column_geometry: Option<FeatureRecorder>,
+    header_written: bool,
+}
+
+impl<'a, W: Write> PostgisWriter<'a, W> {
+    pub fn new(out: &'a mut W, columns: Vec<PgColumn>) -> Self {
+        let values = vec![None; columns.len()];
+        PostgisWriter {
+            dims: CoordDimensions::default(),
+            srid: None,
+            out,
+            columns,
+            values,
+            geometry: FeatureRecorder::default(),
+            column_geometry: None,
+            header_written: false,
+        }
+    }
+
+    /// COPY statement for loading the output into `table`.
+    pub fn copy_statement(&self, table: &str) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|col| quote_ident(&col.name))
+            .collect();
+        format!(
+            "COPY {} ({}) FROM STDIN (FORMAT binary)",
+            quote_ident(table),
+            columns.join(", ")
+        )
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if !self.header_written {
+            self.out.write_all(COPY_SIGNATURE)?;
+            // flags and header extension length
+            self.out.write_all(&0i32.to_be_bytes())?;
+            self.out.write_all(&0i32.to_be_bytes())?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+
+    fn recorder(&mut self) -> &mut FeatureRecorder {
+        match self.column_geometry.as_mut() {
+            Some(recorder) => recorder,
+            None => &mut self.geometry,
+        }
+    }
+
+    fn geometry_ewkb(&self, geometry: &FeatureRecorder) -> Result<Vec<u8>> {
+        let mut ewkb = Vec::new();
+        {
+            let mut writer = WkbWriter::new(&mut ewkb, WkbDialect::Ewkb);
+            writer.dims = self.dims;
+            writer.srid = self.srid;
+            geometry.replay(&mut writer)?;
+        }
+        Ok(ewkb)
+    }
+
+    fn write_row(&mut self) -> Result<()> {
+        self.out
+            .write_all(&(self.columns.len() as i16).to_be_bytes())?;
+        for value in &mut self.values {
+            match value.take() {
+                Some(bytes) => {
+                    self.out.write_all(&(bytes.len() as i32).to_be_bytes())?;
+                    self.out.write_all(&bytes)?;
+                }
+                None => self.out.write_all(&(-1i32).to_be_bytes())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn int_value(value: &ColumnValue) -> Option<i64> {
+    match *value {
+        ColumnValue::Byte(v) => Some(v.into()),
+        ColumnValue::UByte(v) => Some(v.into()),
+        ColumnValue::Short(v) => Some(v.into()),
+        ColumnValue::UShort(v) => Some(v.into()),
+        ColumnValue::Int(v) => Some(v.into()),
+        ColumnValue::UInt(v) => Some(v.into()),
+        ColumnValue::Long(v) => Some(v),
+        ColumnValue::ULong(v) => i64::try_from(v).ok(),
+        _ => None,
+    }
+}
+
+fn float_value(value: &ColumnValue) -> Option<f64> {
+    match *value {
+        ColumnValue::Float(v) => Some(v.into()),
+        ColumnValue::Double(v) => Some(v),
+        _ => int_value(value).map(|v| v as f64),
+    }
+}
+
+fn json_text(value: &ColumnValue) -> Option<String> {
+    match value {
+        ColumnValue::Json(v) => Some(v.to_string()),
+        ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+            Some(serde_json::Value::from(*v).to_string())
+        }
+        ColumnValue::Binary(_) => None,
+        _ => Some(value.to_string()),
+    }
+}
+
+/// Binary COPY encoding of `value` for column type `pg_type`.
+fn encode_value(pg_type: PgType, value: &ColumnValue) -> Option<Vec<u8>> {
+    match pg_type {
+        PgType::Bool => match value {
+            ColumnValue::Bool(v) => Some(vec![*v as u8]),
+            _ => None,
+        },
+        PgType::Int2 => int_value(value)
+            .and_then(|v| i16::try_from(v).ok())
+            .map(|v| v.to_be_bytes().to_vec()),
+        PgType::Int4 => int_value(value)
+            .and_then(|v| i32::try_from(v).ok())
+            .map(|v| v.to_be_bytes().to_vec()),
+        PgType::Int8 => int_value(value).map(|v| v.to_be_bytes().to_vec()),
+        PgType::Float4 => float_value(value).map(|v| (v as f32).to_be_bytes().to_vec()),
+        PgType::Float8 => float_value(value).map(|v| v.to_be_bytes().to_vec()),
+        PgType::Text => match value {
+            ColumnValue::Binary(_) => None,
+            _ => Some(value.to_string().into_bytes()),
+        },
+        PgType::Json => json_text(value).map(String::into_bytes),
+        PgType::Jsonb => json_text(value).map(|json| {
+            // jsonb binary format version
+            let mut bytes = vec![1];
+            bytes.extend_from_slice(json.as_bytes());
+            bytes
+        }),
+        PgType::Bytea => match value {
+            ColumnValue::Binary(v) => Some(v.to_vec()),
+            _ => None,
+        },
+        PgType::Geometry => None,
+    }
+}
+
+impl<W: Write> FeatureProcessor for PostgisWriter<'_, W> {
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        self.write_header()
+    }
+    /// Use dataset SRID, if no SRID is set
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.srid.is_none() {
+            self.srid = srid;
+        }
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.write_header()?;
+        // file trailer
+        self.out.write_all(&(-1i16).to_be_bytes())?;
+        self.header_written = false;
+        self.out.flush()?;
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.write_header()?;
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if !self.geometry.is_empty() {
+            if let Some(i) = self
+                .columns
+                .iter()
+                .position(|col| col.pg_type == PgType::Geometry)
+            {
+                let ewkb = self
+                    .geometry_ewkb(&self.geometry)
+                    .map_err(|e| e.in_feature(idx))?;
+                self.values[i] = Some(ewkb);
+            }
+        }
+        self.write_row()
+    }
+    fn multiple_geometries(&self) -> bool {
+        true
+    }
+    fn geometry_column_begin(&mut self, _idx: usize, _name: &str) -> Result<()> {
+        self.column_geometry = Some(FeatureRecorder::default());
+        Ok(())
+    }
+    /// Write additional geometry into the [PgType::Geometry] column with the same name
+    fn geometry_column_end(&mut self, _idx: usize, name: &str) -> Result<()> {
+        let geometry = self.column_geometry.take().unwrap_or_default();
+        if let Some(i) = self
+            .columns
+            .iter()
+            .position(|col| col.pg_type == PgType::Geometry && col.name == name)
+        {
+            if !geometry.is_empty() {
+                let ewkb = self.geometry_ewkb(&geometry)?;
+                self.values[i] = Some(ewkb);
+            }
+        }
+        Ok(())
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for PostgisWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let Some(i) = self.columns.iter().position(|col| col.name == name) {
+            let pg_type = self.columns[i].pg_type;
+            let bytes = encode_value(pg_type, value).ok_or_else(|| {
+                GeozeroError::ColumnType(format!("{:?}", pg_type), format!("{:?}", value))
+            })?;
+            self.values[i] = Some(bytes);
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write> GeomProcessor for PostgisWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.recorder().srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.recorder().xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.recorder().coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.recorder().empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.recorder().point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.recorder().linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.recorder().linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.recorder().polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.recorder().polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.recorder().triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.recorder().triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.recorder().tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.recorder().tin_end(idx)
+    }
+}
+
+/// Reader for geometries in PostgreSQL binary COPY data.
+///
+/// Reads the output of e.g. `COPY (SELECT geom FROM places) TO STDOUT (FORMAT binary)`
+/// from a stream, like `Client::copy_out` of rust-postgres. Each row is processed as
+/// feature with the EWKB geometry in field `column`, all other fields are skipped.
+pub struct PostgisCopyReader<R: Read> {
+    /// Index of the geometry field
+    pub column: usize,
+    reader: R,
+}
+
+impl<R: Read> PostgisCopyReader<R> {
+    pub fn new(reader: R) -> Self {
+        PostgisCopyReader { column: 0, reader }
+    }
+
+    fn read_header(&mut self) -> Result<()> {
+        let mut signature = [0; 11];
+        self.reader.read_exact(&mut signature)?;
+        if signature != COPY_SIGNATURE {
+            return Err(GeozeroError::Dataset(
+                "invalid binary COPY signature".to_string(),
+            ));
+        }
+        let _flags = self.reader.ioread_with::<i32>(scroll::BE)?;
+        let extension_len = self.reader.ioread_with::<u32>(scroll::BE)?;
+        self.skip(extension_len.into())
+    }
+
+    fn skip(&mut self, len: u64) -> Result<()> {
+        std::io::copy(&mut self.reader.by_ref().take(len), &mut std::io::sink())?;
+        Ok(())
+    }
+
+    fn process_row<P: FeatureProcessor>(
+        &mut self,
+        fields: usize,
+        idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.feature_begin(idx)?;
+        for i in 0..fields {
+            let len = self.reader.ioread_with::<i32>(scroll::BE)?;
+            if len < 0 {
+                if i == self.column {
+                    processor.null_geometry()?;
+                }
+            } else if i == self.column {
+                processor.geometry_begin()?;
+                process_wkb_sized(&mut self.reader, len as u64, WkbDialect::Ewkb, processor)?;
+                processor.geometry_end()?;
+            } else {
+                self.skip(len as u64)?;
+            }
+        }
+        processor.feature_end(idx)
+    }
+}
+
+impl<R: Read> GeozeroDatasource for PostgisCopyReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        self.read_header()?;
+        processor.dataset_begin(None)?;
+        let mut idx = 0;
+        loop {
+            let fields = self.reader.ioread_with::<i16>(scroll::BE)?;
+            if fields == -1 {
+                break;
+            }
+            let fields = usize::try_from(fields)
+                .map_err(|_| GeozeroError::Feature(format!("invalid field count {}", fields)))?;
+            self.process_row(fields, idx, processor)
+                .map_err(|e| e.in_feature(idx))?;
+            idx += 1;
+        }
+        processor.dataset_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    fn columns() -> Vec<PgColumn> {
+        vec![
+            PgColumn::new("id", PgType::Int4),
+            PgColumn::new("name", PgType::Text),
+            PgColumn::new("geom", PgType::Geometry),
+        ]
+    }
+
+    #[test]
+    fn copy_data() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 7, "name": "a", "other": true}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": null}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut writer = PostgisWriter::new(&mut out, columns());
+        writer.srid = Some(4326);
+        GeoJson(geojson).process(&mut writer)?;
+
+        let mut expected = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0".to_vec();
+        // row 1
+        expected.extend_from_slice(&[0, 3]);
+        expected.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 7]);
+        expected.extend_from_slice(&[0, 0, 0, 1, b'a']);
+        expected.extend_from_slice(&[0, 0, 0, 25]);
+        expected.extend_from_slice(&[1, 1, 0, 0, 32, 230, 16, 0, 0]);
+        expected.extend_from_slice(&1f64.to_le_bytes());
+        expected.extend_from_slice(&2f64.to_le_bytes());
+        // row 2
+        expected.extend_from_slice(&[0, 3]);
+        for _ in 0..3 {
+            expected.extend_from_slice(&(-1i32).to_be_bytes());
+        }
+        // trailer
+        expected.extend_from_slice(&[0xff, 0xff]);
+        assert_eq!(out, expected);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn geometry_columns() -> Result<()> {
+        use crate::wkt::WktStr;
+        use crate::GeozeroGeometry;
+
+        let mut out = Vec::new();
+        let mut writer = PostgisWriter::new(&mut out, columns());
+        writer.columns[0] = PgColumn::new("centroid", PgType::Geometry);
+        assert!(writer.multiple_geometries());
+        writer.dataset_begin(None)?;
+        writer.feature_begin(0)?;
+        writer.geometry_begin()?;
+        WktStr("POINT(1 2)").process_geom(&mut writer)?;
+        writer.geometry_end()?;
+        writer.geometry_column_begin(1, "geom")?;
+        WktStr("POINT(3 4)").process_geom(&mut writer)?;
+        writer.geometry_column_end(1, "geom")?;
+        writer.feature_end(0)?;
+
+        let mut expected = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0".to_vec();
+        expected.extend_from_slice(&[0, 3]);
+        // primary geometry in first geometry column `centroid`
+        expected.extend_from_slice(&[0, 0, 0, 21, 1, 1, 0, 0, 0]);
+        expected.extend_from_slice(&1f64.to_le_bytes());
+        expected.extend_from_slice(&2f64.to_le_bytes());
+        expected.extend_from_slice(&(-1i32).to_be_bytes());
+        expected.extend_from_slice(&[0, 0, 0, 21, 1, 1, 0, 0, 0]);
+        expected.extend_from_slice(&3f64.to_le_bytes());
+        expected.extend_from_slice(&4f64.to_le_bytes());
+        assert_eq!(out, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn read_copy_data() -> Result<()> {
+        use crate::geojson::GeoJsonWriter;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 7, "name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"id": 8}, "geometry": null},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 2], [3, 4]]}}
+        ]}"#;
+        let mut copy_data = Vec::new();
+        let mut writer = PostgisWriter::new(&mut copy_data, columns());
+        GeoJson(geojson).process(&mut writer)?;
+
+        let mut reader = PostgisCopyReader::new(copy_data.as_slice());
+        reader.column = 2;
+        let mut out: Vec<u8> = Vec::new();
+        reader.process(&mut GeoJsonWriter::new(&mut out))?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(r#""coordinates": [1,2]"#));
+        assert!(json.contains(r#""geometry": null"#));
+        assert!(json.contains(r#""coordinates": [[1,2],[3,4]]"#));
+
+        let mut reader = PostgisCopyReader::new(&b"PGCOPY\n"[..]);
+        assert!(reader.process(&mut GeoJsonWriter::new(&mut out)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn type_conversion() {
+        assert_eq!(
+            encode_value(PgType::Int8, &ColumnValue::UInt(3)),
+            Some(vec![0, 0, 0, 0, 0, 0, 0, 3])
+        );
+        assert_eq!(encode_value(PgType::Int2, &ColumnValue::Long(70000)), None);
+        assert_eq!(
+            encode_value(PgType::Float8, &ColumnValue::Int(1)),
+            Some(1f64.to_be_bytes().to_vec())
+        );
+        assert_eq!(
+            encode_value(PgType::Jsonb, &ColumnValue::String("x")),
+            Some(b"\x01\"x\"".to_vec())
+        );
+        assert_eq!(encode_value(PgType::Bool, &ColumnValue::Int(1)), None);
+
+        let mut out = Vec::new();
+        let mut writer = PostgisWriter::new(&mut out, columns());
+        assert!(writer
+            .property(0, "id", &ColumnValue::String("seven"))
+            .is_err());
+    }
+
+    #[test]
+    fn copy_statement() {
+        let mut out = Vec::new();
+        let writer = PostgisWriter::new(&mut out, columns());
+        assert_eq!(
+            writer.copy_statement("my \"table\""),
+            r#"COPY "my ""table""" ("id", "name", "geom") FROM STDIN (FORMAT binary)"#
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::wkb::{self, FromWkb};
-use crate::GeozeroGeometry;
+use crate::{GeozeroGeometry, ToWkb};
 use bytes::{BufMut, BytesMut};
 use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
 
@@ -30,11 +30,8 @@ impl<'a, T: GeozeroGeometry + Sized> ToSql for wkb::Encode<T> {
         _ty: &Type,
         out: &mut BytesMut,
     ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
-        let pgout = &mut out.writer();
-        let mut writer = wkb::WkbWriter::new(pgout, wkb::WkbDialect::Ewkb);
-        writer.dims = self.0.dims();
-        writer.srid = self.0.srid();
-        self.0.process_geom(&mut writer)?;
+        self.0
+            .write_ewkb(&mut out.writer(), self.0.dims(), self.0.srid())?;
         Ok(IsNull::No)
     }
 
@@ -91,14 +88,10 @@ macro_rules! impl_postgres_postgis_encode {
                 out: &mut bytes::BytesMut,
             ) -> std::result::Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
             {
-                use $crate::GeozeroGeometry;
+                use $crate::{GeozeroGeometry, ToWkb};
                 use bytes::BufMut;
 
-                let pgout = &mut out.writer();
-                let mut writer = $crate::wkb::WkbWriter::new(pgout, $crate::wkb::WkbDialect::Ewkb);
-                writer.dims = self.dims();
-                writer.srid = self.srid();
-                self.process_geom(&mut writer)?;
+                self.write_ewkb(&mut out.writer(), self.dims(), self.srid())?;
                 Ok(postgres_types::IsNull::No)
             }
 
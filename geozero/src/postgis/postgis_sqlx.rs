@@ -1,5 +1,5 @@
 use crate::wkb::{self, FromWkb};
-use crate::GeozeroGeometry;
+use crate::{GeozeroGeometry, ToWkb};
 use sqlx::decode::Decode;
 use sqlx::encode::{Encode, IsNull};
 use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
@@ -47,14 +47,9 @@ impl<T: GeozeroGeometry + Sized> PgHasArrayType for wkb::Encode<T> {
 
 impl<T: GeozeroGeometry + Sized> Encode<'_, Postgres> for wkb::Encode<T> {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
-        let mut wkb_out: Vec<u8> = Vec::new();
-        let mut writer = wkb::WkbWriter::new(&mut wkb_out, wkb::WkbDialect::Ewkb);
-        writer.dims = self.0.dims();
-        writer.srid = self.0.srid();
         self.0
-            .process_geom(&mut writer)
+            .write_ewkb(&mut **buf, self.0.dims(), self.0.srid())
             .expect("Failed to encode Geometry");
-        buf.extend(&wkb_out); // Is there a way to write directly into PgArgumentBuffer?
 
         IsNull::No
     }
@@ -119,15 +114,9 @@ macro_rules! impl_sqlx_postgis_encode {
                 &self,
                 buf: &mut sqlx::postgres::PgArgumentBuffer,
             ) -> sqlx::encode::IsNull {
-                use $crate::GeozeroGeometry;
-                let mut wkb_out: Vec<u8> = Vec::new();
-                let mut writer =
-                    $crate::wkb::WkbWriter::new(&mut wkb_out, $crate::wkb::WkbDialect::Ewkb);
-                writer.dims = self.dims();
-                writer.srid = self.srid();
-                self.process_geom(&mut writer)
+                use $crate::{GeozeroGeometry, ToWkb};
+                self.write_ewkb(&mut **buf, self.dims(), self.srid())
                     .expect("Failed to encode Geometry");
-                buf.extend(&wkb_out); // Is there a way to write directly into PgArgumentBuffer?
 
                 sqlx::encode::IsNull::No
             }
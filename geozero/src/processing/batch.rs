@@ -0,0 +1,244 @@
+use crate::error::Result;
+use crate::processing::ProcessorLayer;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Grouping of features into batches.
+///
+/// Calls `batch_begin` before the first feature of each batch and `batch_end` after
+/// `batch_size` features or at the end of the dataset. Database writers use these hints
+/// to wrap inserts into transactions and flush buffered rows.
+/// A `batch_size` of 0 puts all features into a single batch.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::Batcher;
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::GeozeroDatasource;
+///
+/// let mut json = Vec::new();
+/// let mut batcher = Batcher::new(1000, GeoJsonWriter::new(&mut json));
+/// let feature = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#;
+/// GeoJson(feature).process(&mut batcher).unwrap();
+/// ```
+pub struct Batcher<P> {
+    batch_size: usize,
+    processor: P,
+    /// Number of features in the current batch
+    count: usize,
+    in_batch: bool,
+}
+
+impl<P> Batcher<P> {
+    /// Group features in batches of `batch_size` and forward to `processor`.
+    pub fn new(batch_size: usize, processor: P) -> Self {
+        Batcher {
+            batch_size,
+            processor,
+            count: 0,
+            in_batch: false,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume batcher and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+impl<P: FeatureProcessor> Batcher<P> {
+    fn end_batch(&mut self) -> Result<()> {
+        if self.in_batch {
+            self.in_batch = false;
+            self.count = 0;
+            self.processor.batch_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for Batcher<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    forward_events!(processor;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Batcher<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Batcher<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.count = 0;
+        self.in_batch = false;
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.end_batch()?;
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        if !self.in_batch {
+            self.in_batch = true;
+            self.processor.batch_begin(self.batch_size)?;
+        }
+        self.processor.feature_begin(idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.processor.feature_end(idx)?;
+        self.count += 1;
+        if self.count == self.batch_size {
+            self.end_batch()?;
+        }
+        Ok(())
+    }
+    // Batches of upstream processors are replaced by our own
+    fn batch_begin(&mut self, _size: usize) -> Result<()> {
+        Ok(())
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+    );
+}
+
+/// [Batcher] stage for [Pipeline](crate::processing::Pipeline).
+pub struct BatcherLayer {
+    pub batch_size: usize,
+}
+
+impl<P> ProcessorLayer<P> for BatcherLayer {
+    type Processor = Batcher<P>;
+    fn wrap(self, processor: P) -> Batcher<P> {
+        Batcher::new(self.batch_size, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::processing::Pipeline;
+    use crate::GeozeroDatasource;
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl GeomProcessor for Events {}
+    impl PropertyProcessor for Events {}
+    impl FeatureProcessor for Events {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(format!("f{}", idx));
+            Ok(())
+        }
+        fn batch_begin(&mut self, size: usize) -> Result<()> {
+            self.0.push(format!("begin({})", size));
+            Ok(())
+        }
+        fn batch_end(&mut self) -> Result<()> {
+            self.0.push("end".to_string());
+            Ok(())
+        }
+    }
+
+    fn batches(batch_size: usize, count: usize) -> Result<String> {
+        let features = (0..count)
+            .map(|_| r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#)
+            .collect::<Vec<_>>()
+            .join(",");
+        let json = format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            features
+        );
+        let mut batcher = Pipeline::new()
+            .layer(BatcherLayer { batch_size })
+            .build(Events::default());
+        GeoJson(&json).process(&mut batcher)?;
+        Ok(batcher.into_inner().0.join(" "))
+    }
+
+    #[test]
+    fn batches_of_features() -> Result<()> {
+        assert_eq!(
+            batches(2, 5)?,
+            "begin(2) f0 f1 end begin(2) f2 f3 end begin(2) f4 end"
+        );
+        assert_eq!(batches(2, 4)?, "begin(2) f0 f1 end begin(2) f2 f3 end");
+        assert_eq!(batches(0, 3)?, "begin(0) f0 f1 f2 end");
+        assert_eq!(batches(2, 0)?, "");
+        Ok(())
+    }
+}
@@ -0,0 +1,368 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Kind of coordinate sequence being processed.
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    Point,
+    Line,
+    Ring,
+    /// Members of curve polygons
+    Skip,
+}
+
+/// Reduction of geometries to their centroid.
+///
+/// Each geometry is replaced by a Point at its planar centroid. The centroid is computed
+/// while streaming from the components of highest dimension, like in PostGIS:
+/// area-weighted for polygonal geometries, length-weighted for lineal geometries and
+/// the mean of all points otherwise. Curves are measured along their control points
+/// and CurvePolygons are not considered as polygonal. Empty geometries are forwarded as
+/// empty Point. Z and M values are dropped.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::Centroid;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut centroid = Centroid::new(WktWriter::new(&mut wkt));
+/// WktStr("POLYGON((0 0,4 0,4 2,0 2,0 0))").process_geom(&mut centroid).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(2 1)");
+/// ```
+pub struct Centroid<P> {
+    processor: P,
+    /// Nesting level of geometries
+    depth: usize,
+    part: Part,
+    /// Within Polygon or Triangle
+    polygon: bool,
+    /// Within CurvePolygon
+    curve_polygon: bool,
+    /// Index of current ring in polygon
+    ring: usize,
+    first: Option<(f64, f64)>,
+    prev: Option<(f64, f64)>,
+    /// Ring sums of twice the signed area and of the weighted vertex coordinates
+    ring_sums: [f64; 3],
+    /// Sums of twice the area and of the weighted vertex coordinates
+    area: [f64; 3],
+    /// Sums of the length and of the weighted segment midpoints
+    length: [f64; 3],
+    /// Number of points and sums of their coordinates
+    points: [f64; 3],
+}
+
+impl<P> Centroid<P> {
+    /// Forward centroids to `processor`.
+    pub fn new(processor: P) -> Self {
+        Centroid {
+            processor,
+            depth: 0,
+            part: Part::Point,
+            polygon: false,
+            curve_polygon: false,
+            ring: 0,
+            first: None,
+            prev: None,
+            ring_sums: [0.0; 3],
+            area: [0.0; 3],
+            length: [0.0; 3],
+            points: [0.0; 3],
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume centroid processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn add_point(&mut self, x: f64, y: f64) {
+        let p = (x, y);
+        match (self.part, self.prev) {
+            (Part::Point, _) => {
+                self.points[0] += 1.0;
+                self.points[1] += x;
+                self.points[2] += y;
+            }
+            (Part::Line, Some(prev)) => {
+                let len = (x - prev.0).hypot(y - prev.1);
+                self.length[0] += len;
+                self.length[1] += len * (prev.0 + x) / 2.0;
+                self.length[2] += len * (prev.1 + y) / 2.0;
+            }
+            (Part::Ring, Some(prev)) => self.add_edge(prev, p),
+            (Part::Ring, None) => self.first = Some(p),
+            _ => {}
+        }
+        self.prev = Some(p);
+    }
+    fn add_edge(&mut self, (x0, y0): (f64, f64), (x1, y1): (f64, f64)) {
+        let cross = x0 * y1 - x1 * y0;
+        self.ring_sums[0] += cross;
+        self.ring_sums[1] += (x0 + x1) * cross;
+        self.ring_sums[2] += (y0 + y1) * cross;
+    }
+    fn part_begin(&mut self, part: Part) {
+        self.part = part;
+        self.first = None;
+        self.prev = None;
+        self.ring_sums = [0.0; 3];
+    }
+    fn part_end(&mut self) {
+        if self.part == Part::Ring {
+            if let (Some(first), Some(last)) = (self.first, self.prev) {
+                self.add_edge(last, first);
+            }
+            // exterior rings count positive, holes negative, independent of orientation
+            let mut sign = self.ring_sums[0].signum();
+            if self.ring > 0 {
+                sign = -sign;
+            }
+            for (sum, ring_sum) in self.area.iter_mut().zip(&self.ring_sums) {
+                *sum += sign * ring_sum;
+            }
+            self.ring += 1;
+        }
+        self.part = Part::Point;
+    }
+    /// Centroid of the accumulated geometry.
+    fn centroid(&self) -> Option<(f64, f64)> {
+        if self.area[0] != 0.0 {
+            let a3 = 3.0 * self.area[0];
+            Some((self.area[1] / a3, self.area[2] / a3))
+        } else if self.length[0] > 0.0 {
+            Some((
+                self.length[1] / self.length[0],
+                self.length[2] / self.length[0],
+            ))
+        } else if self.points[0] > 0.0 {
+            Some((
+                self.points[1] / self.points[0],
+                self.points[2] / self.points[0],
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<P: GeomProcessor> Centroid<P> {
+    fn begin(&mut self) {
+        self.depth += 1;
+    }
+    /// Forward centroid after the end of the top-level geometry.
+    fn end(&mut self) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth > 0 {
+            return Ok(());
+        }
+        let centroid = self.centroid();
+        self.area = [0.0; 3];
+        self.length = [0.0; 3];
+        self.points = [0.0; 3];
+        match centroid {
+            Some((x, y)) => {
+                self.processor.point_begin(0)?;
+                Coord::xy(x, y).process(&mut self.processor, 0)?;
+                self.processor.point_end(0)
+            }
+            None => self.processor.empty_point(0),
+        }
+    }
+}
+
+/// Accumulate begin and end events of geometries without own measure.
+macro_rules! nesting_events {
+    ($($begin:ident($($arg:ident: $ty:ty),*), $end:ident($($end_arg:ident: $end_ty:ty),*);)*) => {
+        $(
+            fn $begin(&mut self, $(_: $ty),*) -> Result<()> {
+                self.begin();
+                Ok(())
+            }
+            fn $end(&mut self, $(_: $end_ty),*) -> Result<()> {
+                self.end()
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for Centroid<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xy()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_point(x, y);
+        if self.depth == 0 {
+            self.begin();
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.begin();
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.part_begin(if self.curve_polygon {
+            Part::Skip
+        } else if self.polygon {
+            Part::Ring
+        } else {
+            Part::Line
+        });
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.part_end();
+        self.end()
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.part_begin(if self.curve_polygon || self.polygon {
+            Part::Skip
+        } else {
+            Part::Line
+        });
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.part_end();
+        self.end()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.polygon = true;
+        self.ring = 0;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon = false;
+        self.end()
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.polygon = true;
+        self.ring = 0;
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon = false;
+        self.end()
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.curve_polygon = true;
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_polygon = false;
+        self.end()
+    }
+    nesting_events!(
+        point_begin(idx: usize), point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize), multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize), multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize), multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize), geometrycollection_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize), compoundcurve_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize), multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize), multisurface_end(idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize), polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize), tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Centroid<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Centroid<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [Centroid] stage for [Pipeline](crate::processing::Pipeline).
+pub struct CentroidLayer;
+
+impl<P> ProcessorLayer<P> for CentroidLayer {
+    type Processor = Centroid<P>;
+    fn wrap(self, processor: P) -> Centroid<P> {
+        Centroid::new(processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn centroid(wkt: &str) -> Result<String> {
+        let mut out = Vec::new();
+        let mut centroid = Pipeline::new()
+            .layer(CentroidLayer)
+            .build(WktWriter::new(&mut out));
+        WktStr(wkt).process_geom(&mut centroid)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn centroids() -> Result<()> {
+        assert_eq!(centroid("POINT(1 2)")?, "POINT(1 2)");
+        assert_eq!(centroid("MULTIPOINT(0 0,2 0,4 3)")?, "POINT(2 1)");
+        assert_eq!(
+            centroid("LINESTRING(0 0,4 0,4 2)")?,
+            "POINT(2.6666666666666665 0.3333333333333333)"
+        );
+        // clockwise exterior ring with hole
+        assert_eq!(
+            centroid("POLYGON((0 0,0 6,6 6,6 0,0 0),(0 0,2 0,2 2,0 2,0 0))")?,
+            "POINT(3.25 3.25)"
+        );
+        // polygons dominate lower dimensions
+        assert_eq!(
+            centroid("GEOMETRYCOLLECTION(POINT(100 100),LINESTRING(0 0,100 0),POLYGON((0 0,2 0,2 2,0 2,0 0)))")?,
+            "POINT(1 1)"
+        );
+        assert_eq!(centroid("LINESTRING EMPTY")?, "POINT EMPTY");
+        Ok(())
+    }
+}
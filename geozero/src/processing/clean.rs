@@ -0,0 +1,329 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+struct Line {
+    tagged: bool,
+    idx: usize,
+    coords: Vec<Coord>,
+}
+
+/// Removal of repeated and collinear vertices.
+///
+/// Consecutive vertices with identical coordinates in LineStrings and polygon rings are
+/// reduced to one. With `collinear`, interior vertices on a straight segment between their
+/// neighbours are removed as well (exact test in 2D, vertices reversing the direction are
+/// kept). Lines and rings are kept unchanged if they would collapse to less than 2 or 4
+/// coordinates. CircularStrings are not changed.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::CleanVertices;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut clean = CleanVertices::new(WktWriter::new(&mut wkt));
+/// clean.collinear = true;
+/// WktStr("LINESTRING(0 0,1 0,1 0,2 0,2 1)").process_geom(&mut clean).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(0 0,2 0,2 1)");
+/// ```
+pub struct CleanVertices<P> {
+    /// Remove collinear interior vertices
+    pub collinear: bool,
+    processor: P,
+    line: Option<Line>,
+    /// Nesting level of polygons
+    polygons: usize,
+}
+
+impl<P> CleanVertices<P> {
+    /// Remove repeated vertices and forward to `processor`.
+    pub fn new(processor: P) -> Self {
+        CleanVertices {
+            collinear: false,
+            processor,
+            line: None,
+            polygons: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume cleaner and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+/// `b` lies on the segment from `a` to `c`, with `a` != `b`.
+fn is_collinear(a: &Coord, b: &Coord, c: &Coord) -> bool {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    let dot = (b.x - a.x) * (c.x - b.x) + (b.y - a.y) * (c.y - b.y);
+    cross == 0.0 && dot > 0.0
+}
+
+/// Coordinates without repeated and optionally collinear vertices.
+fn clean_coords(coords: &[Coord], collinear: bool) -> Vec<Coord> {
+    let mut cleaned: Vec<Coord> = Vec::with_capacity(coords.len());
+    for c in coords {
+        if cleaned.last() == Some(c) {
+            continue;
+        }
+        if collinear && cleaned.len() >= 2 {
+            let n = cleaned.len();
+            if is_collinear(&cleaned[n - 2], &cleaned[n - 1], c) {
+                cleaned.pop();
+            }
+        }
+        cleaned.push(*c);
+    }
+    cleaned
+}
+
+impl<P: GeomProcessor> CleanVertices<P> {
+    fn emit_line(&mut self, line: Line) -> Result<()> {
+        let min_size = if self.polygons > 0 && !line.tagged {
+            4
+        } else {
+            2
+        };
+        let cleaned = clean_coords(&line.coords, self.collinear);
+        let coords = if cleaned.len() >= min_size {
+            &cleaned
+        } else {
+            &line.coords
+        };
+        self.processor
+            .linestring_begin(line.tagged, coords.len(), line.idx)?;
+        for (idx, c) in coords.iter().enumerate() {
+            c.process(&mut self.processor, idx)?;
+        }
+        self.processor.linestring_end(line.tagged, line.idx)
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for CleanVertices<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord::xy(x, y));
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord { x, y, z, m, t, tm });
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.line = Some(Line {
+            tagged,
+            idx,
+            coords: Vec::with_capacity(size),
+        });
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        match self.line.take() {
+            Some(line) => self.emit_line(line),
+            None => self.processor.linestring_end(tagged, idx),
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.curvepolygon_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.triangle_end(tagged, idx)
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for CleanVertices<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for CleanVertices<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [CleanVertices] stage for [Pipeline](crate::processing::Pipeline).
+pub struct CleanVerticesLayer {
+    /// Remove collinear interior vertices
+    pub collinear: bool,
+}
+
+impl<P> ProcessorLayer<P> for CleanVerticesLayer {
+    type Processor = CleanVertices<P>;
+    fn wrap(self, processor: P) -> CleanVertices<P> {
+        let mut clean = CleanVertices::new(processor);
+        clean.collinear = self.collinear;
+        clean
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn clean(wkt: &str, collinear: bool) -> Result<String> {
+        clean_geom(&WktStr(wkt), collinear)
+    }
+
+    fn clean_geom<G: GeozeroGeometry>(geom: &G, collinear: bool) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut clean = Pipeline::new()
+            .layer(CleanVerticesLayer { collinear })
+            .build(writer);
+        geom.process_geom(&mut clean)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn repeated() -> Result<()> {
+        assert_eq!(
+            clean("LINESTRING(0 0,0 0,1 0,1 0,1 0,2 0)", false)?,
+            "LINESTRING(0 0,1 0,2 0)"
+        );
+        assert_eq!(clean("LINESTRING(1 1,1 1)", false)?, "LINESTRING(1 1,1 1)");
+        assert_eq!(
+            clean("POLYGON((0 0,1 0,1 0,1 1,0 0))", false)?,
+            "POLYGON((0 0,1 0,1 1,0 0))"
+        );
+        assert_eq!(
+            clean("POLYGON((0 0,1 0,1 0,0 0))", false)?,
+            "POLYGON((0 0,1 0,1 0,0 0))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn repeated_z() -> Result<()> {
+        use crate::geojson::GeoJson;
+
+        // differing z values are kept
+        let geojson =
+            GeoJson(r#"{"type": "LineString", "coordinates": [[0, 0, 1], [0, 0, 1], [0, 0, 2]]}"#);
+        assert_eq!(clean_geom(&geojson, false)?, "LINESTRING(0 0 1,0 0 2)");
+        Ok(())
+    }
+
+    #[test]
+    fn collinear() -> Result<()> {
+        assert_eq!(
+            clean("LINESTRING(0 0,1 1,2 2,2 2,3 3,3 4)", true)?,
+            "LINESTRING(0 0,3 3,3 4)"
+        );
+        // reversing direction
+        assert_eq!(
+            clean("LINESTRING(0 0,2 0,1 0)", true)?,
+            "LINESTRING(0 0,2 0,1 0)"
+        );
+        assert_eq!(
+            clean("POLYGON((0 0,1 0,2 0,2 2,0 2,0 0))", true)?,
+            "POLYGON((0 0,2 0,2 2,0 2,0 0))"
+        );
+        assert_eq!(
+            clean("LINESTRING(0 0,1 1,2 2)", false)?,
+            "LINESTRING(0 0,1 1,2 2)"
+        );
+        Ok(())
+    }
+}
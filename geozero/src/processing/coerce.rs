@@ -0,0 +1,737 @@
+use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Forward nested geometry events to `self.$sink()`, tracking the nesting level.
+macro_rules! nested_events {
+    ($sink:ident; $($begin:ident($($barg:ident: $bty:ty),*), $end:ident($($earg:ident: $ety:ty),*);)*) => {
+        $(
+            fn $begin(&mut self, $($barg: $bty),*) -> Result<()> {
+                self.$sink().$begin($($barg),*)?;
+                self.depth += 1;
+                Ok(())
+            }
+            fn $end(&mut self, $($earg: $ety),*) -> Result<()> {
+                self.depth -= 1;
+                self.$sink().$end($($earg),*)
+            }
+        )*
+    };
+}
+
+/// Promotion of single geometries to their Multi* counterpart.
+///
+/// Points, LineStrings and Polygons are converted to MultiPoints, MultiLineStrings and
+/// MultiPolygons, CircularStrings and CompoundCurves to MultiCurves and CurvePolygons to
+/// MultiSurfaces. Members of geometry collections are not changed.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::PromoteToMulti;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut promote = PromoteToMulti::new(WktWriter::new(&mut wkt));
+/// WktStr("POLYGON((0 0,1 0,1 1,0 0))").process_geom(&mut promote).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))");
+/// ```
+pub struct PromoteToMulti<P> {
+    processor: P,
+    /// Nesting level of geometries
+    depth: usize,
+}
+
+impl<P> PromoteToMulti<P> {
+    pub fn new(processor: P) -> Self {
+        PromoteToMulti {
+            processor,
+            depth: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn sink(&mut self) -> &mut P {
+        &mut self.processor
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for PromoteToMulti<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.processor.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.processor.multipoint_begin(0, idx)?;
+            self.processor.multipoint_end(idx)
+        } else {
+            self.processor.empty_point(idx)
+        }
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.processor.multipoint_begin(1, idx)?;
+        } else {
+            self.processor.point_begin(idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.processor.multipoint_end(idx)
+        } else {
+            self.processor.point_end(idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.depth == 0 && tagged {
+            self.processor.multilinestring_begin(1, idx)?;
+            self.processor.linestring_begin(false, size, 0)?;
+        } else {
+            self.processor.linestring_begin(tagged, size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 && tagged {
+            self.processor.linestring_end(false, 0)?;
+            self.processor.multilinestring_end(idx)
+        } else {
+            self.processor.linestring_end(tagged, idx)
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.depth == 0 && tagged {
+            self.processor.multipolygon_begin(1, idx)?;
+            self.processor.polygon_begin(false, size, 0)?;
+        } else {
+            self.processor.polygon_begin(tagged, size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 && tagged {
+            self.processor.polygon_end(false, 0)?;
+            self.processor.multipolygon_end(idx)
+        } else {
+            self.processor.polygon_end(tagged, idx)
+        }
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.processor.multicurve_begin(1, idx)?;
+            self.processor.circularstring_begin(size, 0)?;
+        } else {
+            self.processor.circularstring_begin(size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.processor.circularstring_end(0)?;
+            self.processor.multicurve_end(idx)
+        } else {
+            self.processor.circularstring_end(idx)
+        }
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.processor.multicurve_begin(1, idx)?;
+            self.processor.compoundcurve_begin(size, 0)?;
+        } else {
+            self.processor.compoundcurve_begin(size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.processor.compoundcurve_end(0)?;
+            self.processor.multicurve_end(idx)
+        } else {
+            self.processor.compoundcurve_end(idx)
+        }
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.processor.multisurface_begin(1, idx)?;
+            self.processor.curvepolygon_begin(size, 0)?;
+        } else {
+            self.processor.curvepolygon_begin(size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.processor.curvepolygon_end(0)?;
+            self.processor.multisurface_end(idx)
+        } else {
+            self.processor.curvepolygon_end(idx)
+        }
+    }
+    nested_events!(sink;
+        multipoint_begin(size: usize, idx: usize), multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize), multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize), multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize), geometrycollection_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize), multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize), multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize), triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize), polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize), tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for PromoteToMulti<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for PromoteToMulti<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// Splitting of features with Multi* geometries into one feature per member.
+///
+/// Members of MultiPoints, MultiLineStrings, MultiPolygons, MultiCurves and MultiSurfaces
+/// are written as separate features with the properties of the original feature.
+/// Output features are numbered consecutively. Geometries processed outside of a feature
+/// are forwarded unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{ExplodeMulti, StatsProcessor};
+/// use geozero::{geojson::GeoJson, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {"id": 1},
+///     "geometry": {"type": "MultiPoint", "coordinates": [[1, 2], [3, 4]]}}"#;
+/// let mut explode = ExplodeMulti::new(StatsProcessor::new());
+/// GeoJson(geojson).process(&mut explode).unwrap();
+/// let stats = explode.into_inner().into_stats();
+/// assert_eq!(stats.features, 2);
+/// assert_eq!(stats.geometry_count("Point"), 2);
+/// ```
+pub struct ExplodeMulti<P> {
+    processor: P,
+    /// Nesting level of geometries
+    depth: usize,
+    in_feature: bool,
+    /// Inside of a top-level Multi* geometry
+    exploding: bool,
+    has_geometry: bool,
+    /// Index of next output feature
+    feature_idx: u64,
+    properties: FeatureRecorder,
+    /// Geometry events not belonging to a member
+    geometry: FeatureRecorder,
+    /// Geometry events of members
+    parts: Vec<FeatureRecorder>,
+    /// Additional geometry columns, added unchanged to each member
+    columns: FeatureRecorder,
+    in_column: bool,
+}
+
+impl<P> ExplodeMulti<P> {
+    pub fn new(processor: P) -> Self {
+        ExplodeMulti {
+            processor,
+            depth: 0,
+            in_feature: false,
+            exploding: false,
+            has_geometry: false,
+            feature_idx: 0,
+            properties: FeatureRecorder::default(),
+            geometry: FeatureRecorder::default(),
+            parts: Vec::new(),
+            columns: FeatureRecorder::default(),
+            in_column: false,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Start a new member geometry, if inside of a top-level Multi* geometry.
+    fn member_begin(&mut self) -> bool {
+        if self.exploding && self.depth == 1 {
+            self.parts.push(FeatureRecorder::default());
+            true
+        } else {
+            false
+        }
+    }
+    fn member_end(&self) -> bool {
+        self.exploding && self.depth == 1
+    }
+    fn recorder(&mut self) -> &mut FeatureRecorder {
+        if self.in_column {
+            &mut self.columns
+        } else if self.exploding && self.depth > 0 {
+            self.parts.last_mut().unwrap_or(&mut self.geometry)
+        } else {
+            &mut self.geometry
+        }
+    }
+}
+
+impl<P: GeomProcessor> ExplodeMulti<P> {
+    fn sink(&mut self) -> &mut dyn GeomProcessor {
+        if self.in_feature {
+            self.recorder()
+        } else {
+            &mut self.processor
+        }
+    }
+    fn multi_begin(&mut self) {
+        if self.in_feature && !self.in_column && self.depth == 0 {
+            self.exploding = true;
+        }
+    }
+    fn multi_end(&mut self) {
+        if self.depth == 0 {
+            self.exploding = false;
+        }
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for ExplodeMulti<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.sink().srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.member_begin() {
+            let part = self.recorder();
+            part.point_begin(0)?;
+            part.xy(x, y, 0)?;
+            part.point_end(0)
+        } else {
+            self.sink().xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.member_begin() {
+            let part = self.recorder();
+            part.point_begin(0)?;
+            part.coordinate(x, y, z, m, t, tm, 0)?;
+            part.point_end(0)
+        } else {
+            self.sink().coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.sink().empty_point(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_begin();
+        self.sink().multipoint_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.multi_end();
+        self.sink().multipoint_end(idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_begin();
+        self.sink().multilinestring_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.multi_end();
+        self.sink().multilinestring_end(idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_begin();
+        self.sink().multipolygon_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.multi_end();
+        self.sink().multipolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_begin();
+        self.sink().multicurve_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.multi_end();
+        self.sink().multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_begin();
+        self.sink().multisurface_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.multi_end();
+        self.sink().multisurface_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.member_begin() {
+            self.recorder().linestring_begin(true, size, 0)?;
+        } else {
+            self.sink().linestring_begin(tagged, size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.member_end() {
+            self.recorder().linestring_end(true, 0)
+        } else {
+            self.sink().linestring_end(tagged, idx)
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.member_begin() {
+            self.recorder().polygon_begin(true, size, 0)?;
+        } else {
+            self.sink().polygon_begin(tagged, size, idx)?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        if self.member_end() {
+            self.recorder().polygon_end(true, 0)
+        } else {
+            self.sink().polygon_end(tagged, idx)
+        }
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.member_begin();
+        self.sink().circularstring_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.member_begin();
+        self.sink().compoundcurve_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.member_begin();
+        self.sink().curvepolygon_begin(size, idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.sink().circularstring_end(idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.sink().compoundcurve_end(idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.sink().curvepolygon_end(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.sink().point_begin(idx)?;
+        self.depth += 1;
+        Ok(())
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.sink().point_end(idx)
+    }
+    nested_events!(sink;
+        geometrycollection_begin(size: usize, idx: usize), geometrycollection_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize), triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize), polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize), tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for ExplodeMulti<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.in_feature {
+            self.properties.property(idx, name, value)
+        } else {
+            self.processor.property(idx, name, value)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for ExplodeMulti<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.properties.clear();
+        self.geometry.clear();
+        self.parts.clear();
+        self.columns.clear();
+        self.has_geometry = false;
+        self.in_feature = true;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.in_feature = false;
+        let parts = if self.parts.is_empty() {
+            std::slice::from_ref(&self.geometry)
+        } else {
+            &self.parts[..]
+        };
+        for part in parts {
+            self.processor.feature_begin(self.feature_idx)?;
+            self.properties.replay(&mut self.processor)?;
+            if self.has_geometry {
+                self.processor.geometry_begin()?;
+                part.replay(&mut self.processor)?;
+                self.processor.geometry_end()?;
+            }
+            self.columns.replay(&mut self.processor)?;
+            self.processor.feature_end(self.feature_idx)?;
+            self.feature_idx += 1;
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if self.in_feature {
+            self.properties.properties_begin()
+        } else {
+            self.processor.properties_begin()
+        }
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if self.in_feature {
+            self.properties.properties_end()
+        } else {
+            self.processor.properties_end()
+        }
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        if self.in_feature {
+            self.has_geometry = true;
+            Ok(())
+        } else {
+            self.processor.geometry_begin()
+        }
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if self.in_feature {
+            Ok(())
+        } else {
+            self.processor.geometry_end()
+        }
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        if self.in_feature {
+            self.properties.foreign_member(name, value)
+        } else {
+            self.processor.foreign_member(name, value)
+        }
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        if self.in_feature {
+            self.in_column = true;
+            self.columns.geometry_column_begin(idx, name)
+        } else {
+            self.processor.geometry_column_begin(idx, name)
+        }
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        if self.in_feature {
+            self.in_column = false;
+            self.columns.geometry_column_end(idx, name)
+        } else {
+            self.processor.geometry_column_end(idx, name)
+        }
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.dataset_srid(srid)
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.processor.dataset_metadata(metadata)
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.processor.batch_begin(size)
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.processor.batch_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn promote(wkt: &str) -> Result<String> {
+        let mut out = Vec::new();
+        let mut promote = PromoteToMulti::new(WktWriter::new(&mut out));
+        WktStr(wkt).process_geom(&mut promote)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn promote_geometries() -> Result<()> {
+        assert_eq!(promote("POINT(1 2)")?, "MULTIPOINT(1 2)");
+        assert_eq!(
+            promote("LINESTRING(1 2,3 4)")?,
+            "MULTILINESTRING((1 2,3 4))"
+        );
+        assert_eq!(
+            promote("POLYGON((0 0,1 0,1 1,0 0),(0.1 0.1,0.5 0.1,0.5 0.5,0.1 0.1))")?,
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0),(0.1 0.1,0.5 0.1,0.5 0.5,0.1 0.1)))"
+        );
+        assert_eq!(promote("MULTIPOINT(1 2,3 4)")?, "MULTIPOINT(1 2,3 4)");
+        assert_eq!(
+            promote("GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(1 2,3 4))")?,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(1 2,3 4))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn promote_curves() -> Result<()> {
+        let mut out = Vec::new();
+        let mut promote = PromoteToMulti::new(WktWriter::new(&mut out));
+        promote.circularstring_begin(3, 0)?;
+        promote.xy(0.0, 0.0, 0)?;
+        promote.xy(1.0, 1.0, 1)?;
+        promote.xy(2.0, 0.0, 2)?;
+        promote.circularstring_end(0)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "MULTICURVE(CIRCULARSTRING(0 0,1 1,2 0))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn explode_features() -> Result<()> {
+        use crate::geojson::{GeoJson, GeoJsonWriter};
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "MultiLineString", "coordinates": [[[1, 2], [3, 4]], [[5, 6], [7, 8]]]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut explode = ExplodeMulti::new(GeoJsonWriter::new(&mut out));
+        GeoJson(geojson).process(&mut explode)?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert_eq!(json.matches(r#""properties": {"id": 1}"#).count(), 2);
+        assert_eq!(json.matches(r#""properties": {"id": 2}"#).count(), 1);
+        assert!(json.contains(r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#));
+        assert!(json.contains(r#"{"type": "LineString", "coordinates": [[5,6],[7,8]]}"#));
+        assert!(!json.contains("MultiLineString"));
+        Ok(())
+    }
+
+    #[test]
+    fn explode_geometries() -> Result<()> {
+        let mut out = Vec::new();
+        let mut explode = ExplodeMulti::new(WktWriter::new(&mut out));
+        WktStr("MULTIPOINT(1 2,3 4)").process_geom(&mut explode)?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), "MULTIPOINT(1 2,3 4)");
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+use crate::error::Result;
+use crate::schema::merge_types;
+use crate::{ColumnType, ColumnValue, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::BTreeSet;
+
+/// Statistics of a property column.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnSummary {
+    pub name: String,
+    /// Type of all values, widened like [ColumnSchema](crate::ColumnSchema) types
+    pub column_type: ColumnType,
+    /// Number of features with a value
+    pub count: u64,
+    /// Number of features without a value
+    pub null_count: u64,
+    /// Minimal numeric value
+    pub min: Option<f64>,
+    /// Maximal numeric value
+    pub max: Option<f64>,
+    /// Lexicographically smallest string or date-time value
+    pub min_text: Option<String>,
+    /// Lexicographically largest string or date-time value
+    pub max_text: Option<String>,
+    /// Distinct values as text. `None` if the number of distinct values exceeds the limit.
+    pub distinct: Option<BTreeSet<String>>,
+}
+
+impl ColumnSummary {
+    fn new(name: &str, column_type: ColumnType, null_count: u64) -> Self {
+        ColumnSummary {
+            name: name.to_string(),
+            column_type,
+            count: 0,
+            null_count,
+            min: None,
+            max: None,
+            min_text: None,
+            max_text: None,
+            distinct: Some(BTreeSet::new()),
+        }
+    }
+    /// Number of distinct values, if within the limit.
+    pub fn distinct_count(&self) -> Option<u64> {
+        self.distinct.as_ref().map(|values| values.len() as u64)
+    }
+    fn add_number(&mut self, v: f64) {
+        if v.is_nan() {
+            return;
+        }
+        self.min = Some(self.min.map_or(v, |min| min.min(v)));
+        self.max = Some(self.max.map_or(v, |max| max.max(v)));
+    }
+    fn add_text(&mut self, v: &str) {
+        if self.min_text.as_deref().is_none_or(|min| v < min) {
+            self.min_text = Some(v.to_string());
+        }
+        if self.max_text.as_deref().is_none_or(|max| v > max) {
+            self.max_text = Some(v.to_string());
+        }
+    }
+    fn add_distinct(&mut self, value: &ColumnValue, max_distinct: usize) {
+        if let Some(values) = &mut self.distinct {
+            let text = match value {
+                ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                    if values.contains(*v) {
+                        return;
+                    }
+                    v.to_string()
+                }
+                _ => value.to_string(),
+            };
+            values.insert(text);
+            if values.len() > max_distinct {
+                self.distinct = None;
+            }
+        }
+    }
+}
+
+/// Processor collecting per-column statistics of feature properties while streaming.
+///
+/// The collected min/max values and distinct values can drive automatic styling
+/// (e.g. class breaks or categories) and schema inference of writers.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::processing::ColumnStats;
+/// use geozero::GeozeroDatasource;
+///
+/// let json = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"name": "a", "pop": 10}, "geometry": null},
+///     {"type": "Feature", "properties": {"name": "b"}, "geometry": null}
+/// ]}"#;
+/// let mut stats = ColumnStats::new();
+/// GeoJson(json).process(&mut stats).unwrap();
+/// let pop = stats.column("pop").unwrap();
+/// assert_eq!((pop.min, pop.max, pop.null_count), (Some(10.0), Some(10.0), 1));
+/// assert_eq!(stats.column("name").unwrap().distinct_count(), Some(2));
+/// ```
+pub struct ColumnStats {
+    columns: Vec<ColumnSummary>,
+    max_distinct: usize,
+    features: u64,
+    /// Columns present in current feature
+    present: Vec<bool>,
+}
+
+impl Default for ColumnStats {
+    fn default() -> Self {
+        ColumnStats {
+            columns: Vec::new(),
+            max_distinct: 1000,
+            features: 0,
+            present: Vec::new(),
+        }
+    }
+}
+
+impl ColumnStats {
+    /// Collect statistics with up to 1000 distinct values per column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Collect statistics with up to `max_distinct` distinct values per column.
+    pub fn with_max_distinct(max_distinct: usize) -> Self {
+        ColumnStats {
+            max_distinct,
+            ..Default::default()
+        }
+    }
+    /// Statistics of all columns in order of first appearance.
+    pub fn columns(&self) -> &[ColumnSummary] {
+        &self.columns
+    }
+    /// Statistics of column `name`.
+    pub fn column(&self, name: &str) -> Option<&ColumnSummary> {
+        self.columns.iter().find(|col| col.name == name)
+    }
+    /// Consume processor and return column statistics.
+    pub fn into_columns(self) -> Vec<ColumnSummary> {
+        self.columns
+    }
+}
+
+impl GeomProcessor for ColumnStats {}
+
+impl PropertyProcessor for ColumnStats {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let column_type = value.column_type();
+        let i = match self.columns.iter().position(|col| col.name == name) {
+            Some(i) => {
+                let col = &mut self.columns[i];
+                col.column_type = merge_types(col.column_type, column_type);
+                i
+            }
+            None => {
+                // missing in previous features
+                self.columns
+                    .push(ColumnSummary::new(name, column_type, self.features));
+                self.present.push(false);
+                self.columns.len() - 1
+            }
+        };
+        if self.present[i] {
+            // duplicate property name
+            return Ok(false);
+        }
+        self.present[i] = true;
+        let col = &mut self.columns[i];
+        col.count += 1;
+        match *value {
+            ColumnValue::Byte(v) => col.add_number(v.into()),
+            ColumnValue::UByte(v) => col.add_number(v.into()),
+            ColumnValue::Short(v) => col.add_number(v.into()),
+            ColumnValue::UShort(v) => col.add_number(v.into()),
+            ColumnValue::Int(v) => col.add_number(v.into()),
+            ColumnValue::UInt(v) => col.add_number(v.into()),
+            ColumnValue::Long(v) => col.add_number(v as f64),
+            ColumnValue::ULong(v) => col.add_number(v as f64),
+            ColumnValue::Float(v) => col.add_number(v.into()),
+            ColumnValue::Double(v) => col.add_number(v),
+            ColumnValue::String(v) | ColumnValue::DateTime(v) => col.add_text(v),
+            ColumnValue::Bool(_) | ColumnValue::Json(_) | ColumnValue::Binary(_) => {}
+        }
+        if !matches!(value, ColumnValue::Binary(_)) {
+            col.add_distinct(value, self.max_distinct);
+        }
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for ColumnStats {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.present.iter_mut().for_each(|present| *present = false);
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        for (col, present) in self.columns.iter_mut().zip(&self.present) {
+            if !present {
+                col.null_count += 1;
+            }
+        }
+        self.features += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn column_stats() -> Result<()> {
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "b", "pop": 10}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "a", "pop": 2.5, "tags": [1]}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "c", "pop": 7}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": null}
+        ]}"#;
+        let mut stats = ColumnStats::with_max_distinct(2);
+        GeoJson(json).process(&mut stats)?;
+        let columns = stats.into_columns();
+        let names: Vec<_> = columns.iter().map(|col| col.name.as_str()).collect();
+        assert_eq!(names, ["name", "pop", "tags"]);
+
+        let name = &columns[0];
+        assert_eq!(name.column_type, ColumnType::String);
+        assert_eq!((name.count, name.null_count), (4, 0));
+        assert_eq!(name.min_text.as_deref(), Some("a"));
+        assert_eq!(name.max_text.as_deref(), Some("c"));
+        assert_eq!((name.min, name.max), (None, None));
+        assert_eq!(name.distinct_count(), None);
+
+        let pop = &columns[1];
+        assert_eq!(pop.column_type, ColumnType::Double);
+        assert_eq!((pop.count, pop.null_count), (3, 1));
+        assert_eq!((pop.min, pop.max), (Some(2.5), Some(10.0)));
+        assert_eq!(pop.distinct, None);
+
+        let tags = &columns[2];
+        assert_eq!(tags.column_type, ColumnType::Json);
+        assert_eq!((tags.count, tags.null_count), (1, 3));
+        assert_eq!(
+            tags.distinct.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            ["[1]"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+/// Processor calling a closure for each coordinate.
+///
+/// All geometry, property and feature events besides coordinates are ignored,
+/// which makes it a simple base for extents, hashes or drawing.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{processing::CoordSink, wkt::WktStr, GeozeroGeometry};
+///
+/// let mut sum = 0.0;
+/// let mut sink = CoordSink::new(|x, y, _z| sum += x + y);
+/// WktStr("POLYGON((0 0,2 0,2 2,0 0))").process_geom(&mut sink).unwrap();
+/// assert_eq!(sum, 6.0);
+/// ```
+pub struct CoordSink<F: FnMut(f64, f64, Option<f64>)> {
+    coord: F,
+}
+
+impl<F: FnMut(f64, f64, Option<f64>)> CoordSink<F> {
+    pub fn new(coord: F) -> Self {
+        CoordSink { coord }
+    }
+}
+
+impl<F: FnMut(f64, f64, Option<f64>)> GeomProcessor for CoordSink<F> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        (self.coord)(x, y, None);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        (self.coord)(x, y, z);
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<F: FnMut(f64, f64, Option<f64>)> PropertyProcessor for CoordSink<F> {}
+
+impl<F: FnMut(f64, f64, Option<f64>)> FeatureProcessor for CoordSink<F> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let mut coords = Vec::new();
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1,2,3]}}, {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[4,5],[6,7]]}}]}"#,
+        );
+        geojson.process(&mut CoordSink::new(|x, y, z| coords.push((x, y, z))))?;
+        assert_eq!(
+            coords,
+            vec![(1., 2., Some(3.)), (4., 5., None), (6., 7., None)]
+        );
+        Ok(())
+    }
+}
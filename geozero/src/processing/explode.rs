@@ -0,0 +1,404 @@
+use crate::error::Result;
+use crate::processing::record::OwnedValue;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Output of [ExplodeVertices].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExplodeMode {
+    /// Replace each geometry by a MultiPoint of its vertices
+    MultiPoint,
+    /// Replace each feature by one Point feature per vertex
+    Features,
+}
+
+struct Vertex {
+    coord: Coord,
+    /// Index of the point or coordinate sequence within the feature
+    part: usize,
+}
+
+/// Conversion of geometries into their vertices, e.g. for vertex-level QA or point cloud exports.
+///
+/// With [ExplodeMode::Features], each vertex of a feature is forwarded as Point feature with
+/// the properties of the source feature and the additional properties `feature` (index of
+/// the source feature), `part` (index of the point or coordinate sequence within the feature)
+/// and `vertex` (index of the vertex within the feature). Output features are numbered
+/// consecutively. Geometries processed outside of a feature are converted to MultiPoints.
+///
+/// Polygon rings keep their closing vertex. Curves are converted to their control points.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{ExplodeMode, ExplodeVertices};
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut explode = ExplodeVertices::new(ExplodeMode::MultiPoint, WktWriter::new(&mut wkt));
+/// WktStr("LINESTRING(0 0,1 1,2 0)").process_geom(&mut explode).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "MULTIPOINT(0 0,1 1,2 0)");
+/// ```
+pub struct ExplodeVertices<P> {
+    mode: ExplodeMode,
+    processor: P,
+    vertices: Vec<Vertex>,
+    /// Nesting level of geometries
+    depth: usize,
+    /// Within LineString or CircularString
+    in_line: bool,
+    part: usize,
+    next_part: usize,
+    /// Index of current source feature
+    feature: Option<u64>,
+    properties: Vec<(String, OwnedValue)>,
+    /// Number of forwarded features
+    count: u64,
+}
+
+impl<P> ExplodeVertices<P> {
+    /// Convert geometries according to `mode` and forward to `processor`.
+    pub fn new(mode: ExplodeMode, processor: P) -> Self {
+        ExplodeVertices {
+            mode,
+            processor,
+            vertices: Vec::new(),
+            depth: 0,
+            in_line: false,
+            part: 0,
+            next_part: 0,
+            feature: None,
+            properties: Vec::new(),
+            count: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Current feature is split into point features.
+    fn exploding_feature(&self) -> bool {
+        self.mode == ExplodeMode::Features && self.feature.is_some()
+    }
+    fn add_vertex(&mut self, coord: Coord) {
+        if !self.in_line {
+            self.part = self.next_part;
+            self.next_part += 1;
+        }
+        self.vertices.push(Vertex {
+            coord,
+            part: self.part,
+        });
+    }
+    fn begin(&mut self) {
+        self.depth += 1;
+    }
+}
+
+impl<P: GeomProcessor> ExplodeVertices<P> {
+    /// Forward MultiPoint after the end of the top-level geometry.
+    fn end(&mut self) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth > 0 || self.exploding_feature() {
+            return Ok(());
+        }
+        self.processor.multipoint_begin(self.vertices.len(), 0)?;
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            vertex.coord.process(&mut self.processor, idx)?;
+        }
+        self.vertices.clear();
+        self.next_part = 0;
+        self.processor.multipoint_end(0)
+    }
+}
+
+impl<P: FeatureProcessor> ExplodeVertices<P> {
+    /// Forward one Point feature per vertex of the current feature.
+    fn emit_features(&mut self, feature: u64) -> Result<()> {
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            self.processor.feature_begin(self.count)?;
+            self.processor.properties_begin()?;
+            let index_properties = [
+                ("feature", feature),
+                ("part", vertex.part as u64),
+                ("vertex", idx as u64),
+            ];
+            let properties = self
+                .properties
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_column_value()))
+                .chain(
+                    index_properties
+                        .iter()
+                        .map(|(name, value)| (*name, ColumnValue::ULong(*value))),
+                );
+            for (i, (name, value)) in properties.enumerate() {
+                if self.processor.property(i, name, &value)? {
+                    break;
+                }
+            }
+            self.processor.properties_end()?;
+            self.processor.geometry_begin()?;
+            self.processor.point_begin(0)?;
+            vertex.coord.process(&mut self.processor, 0)?;
+            self.processor.point_end(0)?;
+            self.processor.geometry_end()?;
+            self.processor.feature_end(self.count)?;
+            self.count += 1;
+        }
+        self.vertices.clear();
+        self.properties.clear();
+        self.next_part = 0;
+        Ok(())
+    }
+}
+
+/// Track nesting of geometries without own vertex handling.
+macro_rules! nesting_events {
+    ($($begin:ident($($arg:ident: $ty:ty),*), $end:ident($($end_arg:ident: $end_ty:ty),*);)*) => {
+        $(
+            fn $begin(&mut self, $(_: $ty),*) -> Result<()> {
+                self.begin();
+                Ok(())
+            }
+            fn $end(&mut self, $(_: $end_ty),*) -> Result<()> {
+                self.end()
+            }
+        )*
+    };
+}
+
+/// Forward event to the inner processor, unless the current feature is split into point features.
+macro_rules! feature_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.exploding_feature() {
+                    Ok(())
+                } else {
+                    self.processor.$name($($arg),*)
+                }
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for ExplodeVertices<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.exploding_feature() {
+            Ok(())
+        } else {
+            self.processor.srid(srid)
+        }
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_vertex(Coord::xy(x, y));
+        if self.depth == 0 {
+            self.begin();
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_vertex(Coord { x, y, z, m, t, tm });
+        if self.depth == 0 {
+            self.begin();
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.begin();
+            self.end()?;
+        }
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.in_line = true;
+        self.part = self.next_part;
+        self.next_part += 1;
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.in_line = false;
+        self.end()
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin();
+        self.in_line = true;
+        self.part = self.next_part;
+        self.next_part += 1;
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_line = false;
+        self.end()
+    }
+    nesting_events!(
+        point_begin(idx: usize), point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize), multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize), multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize), polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize), multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize), geometrycollection_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize), compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize), curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize), multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize), multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize), triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize), polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize), tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for ExplodeVertices<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.exploding_feature() {
+            self.properties.push((name.to_string(), value.into()));
+            Ok(false)
+        } else {
+            self.processor.property(idx, name, value)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for ExplodeVertices<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.count = 0;
+        self.processor.dataset_begin(name)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature = Some(idx);
+        if self.exploding_feature() {
+            Ok(())
+        } else {
+            self.processor.feature_begin(idx)
+        }
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if self.exploding_feature() {
+            self.feature = None;
+            self.emit_features(idx)
+        } else {
+            self.feature = None;
+            self.processor.feature_end(idx)
+        }
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        batch_begin(size: usize);
+        batch_end();
+    );
+    feature_events!(
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+/// [ExplodeVertices] stage for [Pipeline](crate::processing::Pipeline).
+pub struct ExplodeVerticesLayer(pub ExplodeMode);
+
+impl<P> ProcessorLayer<P> for ExplodeVerticesLayer {
+    type Processor = ExplodeVertices<P>;
+    fn wrap(self, processor: P) -> ExplodeVertices<P> {
+        ExplodeVertices::new(self.0, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::{GeozeroDatasource, GeozeroGeometry};
+
+    fn explode(wkt: &str) -> Result<String> {
+        explode_geom(&WktStr(wkt))
+    }
+
+    fn explode_geom<G: GeozeroGeometry>(geom: &G) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut explode = Pipeline::new()
+            .layer(ExplodeVerticesLayer(ExplodeMode::MultiPoint))
+            .build(writer);
+        geom.process_geom(&mut explode)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn multipoint() -> Result<()> {
+        assert_eq!(
+            explode_geom(&GeoJson(r#"{"type": "Point", "coordinates": [1, 2, 3]}"#))?,
+            "MULTIPOINT(1 2 3)"
+        );
+        assert_eq!(
+            explode("POLYGON((0 0,1 0,1 1,0 0),(0.2 0.1,0.8 0.1,0.8 0.7,0.2 0.1))")?,
+            "MULTIPOINT(0 0,1 0,1 1,0 0,0.2 0.1,0.8 0.1,0.8 0.7,0.2 0.1)"
+        );
+        assert_eq!(
+            explode("GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,3 4))")?,
+            "MULTIPOINT(1 2,0 0,3 4)"
+        );
+        assert_eq!(explode("LINESTRING EMPTY")?, "MULTIPOINT EMPTY");
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "MultiPoint", "coordinates": [[1, 2], [3, 4]]}},
+            {"type": "Feature", "properties": {"name": "b"}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "LineString", "coordinates": [[5, 6], [7, 8]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut explode = ExplodeVertices::new(ExplodeMode::Features, GeoJsonWriter::new(&mut out));
+        GeoJson(json).process(&mut explode)?;
+        let expected = r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a", "feature": 0, "part": 0, "vertex": 0}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+{"type": "Feature", "properties": {"name": "a", "feature": 0, "part": 1, "vertex": 1}, "geometry": {"type": "Point", "coordinates": [3,4]}},
+{"type": "Feature", "properties": {"name": "c", "feature": 2, "part": 0, "vertex": 0}, "geometry": {"type": "Point", "coordinates": [5,6]}},
+{"type": "Feature", "properties": {"name": "c", "feature": 2, "part": 0, "vertex": 1}, "geometry": {"type": "Point", "coordinates": [7,8]}}]}"#;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+/// Coordinate extent.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Extent {
+    pub minx: f64,
+    pub miny: f64,
+    pub maxx: f64,
+    pub maxy: f64,
+    pub minz: Option<f64>,
+    pub maxz: Option<f64>,
+    pub minm: Option<f64>,
+    pub maxm: Option<f64>,
+}
+
+fn min(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn max(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl Extent {
+    /// Extent of a single coordinate.
+    pub fn new(x: f64, y: f64, z: Option<f64>, m: Option<f64>) -> Self {
+        Extent {
+            minx: x,
+            miny: y,
+            maxx: x,
+            maxy: y,
+            minz: z,
+            maxz: z,
+            minm: m,
+            maxm: m,
+        }
+    }
+    /// Extent of 2D bounding box `[minx, miny, maxx, maxy]`.
+    pub fn from_bbox(bbox: [f64; 4]) -> Self {
+        Extent {
+            minx: bbox[0],
+            miny: bbox[1],
+            maxx: bbox[2],
+            maxy: bbox[3],
+            minz: None,
+            maxz: None,
+            minm: None,
+            maxm: None,
+        }
+    }
+    /// Extend with coordinate.
+    pub fn add(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) {
+        self.merge(&Extent::new(x, y, z, m));
+    }
+    /// Extend with other extent.
+    pub fn merge(&mut self, other: &Extent) {
+        self.minx = self.minx.min(other.minx);
+        self.miny = self.miny.min(other.miny);
+        self.maxx = self.maxx.max(other.maxx);
+        self.maxy = self.maxy.max(other.maxy);
+        self.minz = min(self.minz, other.minz);
+        self.maxz = max(self.maxz, other.maxz);
+        self.minm = min(self.minm, other.minm);
+        self.maxm = max(self.maxm, other.maxm);
+    }
+    /// 2D bounding box `[minx, miny, maxx, maxy]`.
+    pub fn bbox(&self) -> [f64; 4] {
+        [self.minx, self.miny, self.maxx, self.maxy]
+    }
+    /// Check for 2D intersection with other extent.
+    pub fn intersects(&self, other: &Extent) -> bool {
+        self.minx <= other.maxx
+            && self.maxx >= other.minx
+            && self.miny <= other.maxy
+            && self.maxy >= other.miny
+    }
+}
+
+/// Processor computing the extent of all processed geometries.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{processing::ExtentProcessor, wkt::WktStr, GeozeroGeometry};
+///
+/// let mut processor = ExtentProcessor::new();
+/// WktStr("LINESTRING(1 5,3 2)").process_geom(&mut processor).unwrap();
+/// assert_eq!(processor.extent().unwrap().bbox(), [1.0, 2.0, 3.0, 5.0]);
+/// ```
+#[derive(Default)]
+pub struct ExtentProcessor {
+    extent: Option<Extent>,
+}
+
+impl ExtentProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Extent of processed geometries. `None` if no coordinate was processed.
+    pub fn extent(&self) -> Option<Extent> {
+        self.extent
+    }
+    /// Clear the current extent.
+    pub fn reset(&mut self) {
+        self.extent = None;
+    }
+    fn add(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) {
+        match self.extent.as_mut() {
+            Some(extent) => extent.add(x, y, z, m),
+            None => self.extent = Some(Extent::new(x, y, z, m)),
+        }
+    }
+}
+
+impl GeomProcessor for ExtentProcessor {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add(x, y, None, None);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add(x, y, z, m);
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for ExtentProcessor {}
+
+impl FeatureProcessor for ExtentProcessor {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extent() {
+        let mut extent = Extent::new(1.0, 2.0, None, Some(10.0));
+        extent.add(-1.0, 5.0, Some(3.0), None);
+        extent.add(0.0, 0.0, Some(4.0), Some(5.0));
+        assert_eq!(extent.bbox(), [-1.0, 0.0, 1.0, 5.0]);
+        assert_eq!((extent.minz, extent.maxz), (Some(3.0), Some(4.0)));
+        assert_eq!((extent.minm, extent.maxm), (Some(5.0), Some(10.0)));
+
+        assert!(extent.intersects(&Extent::new(1.0, 5.0, None, None)));
+        assert!(!extent.intersects(&Extent::new(1.1, 5.0, None, None)));
+        assert_eq!(Extent::from_bbox(extent.bbox()).bbox(), extent.bbox());
+    }
+
+    #[test]
+    #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+    fn geometries() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::wkt::WktStr;
+        use crate::GeozeroGeometry;
+
+        let mut processor = ExtentProcessor::new();
+        assert_eq!(processor.extent(), None);
+        GeoJson(r#"{"type": "Point", "coordinates": [1, 2, 3]}"#).process_geom(&mut processor)?;
+        GeoJson(
+            r#"{"type": "MultiPolygon", "coordinates": [[[[0, 0, 1], [10, 0, 2], [10, 5, 6], [0, 0, 1]]]]}"#,
+        )
+        .process_geom(&mut processor)?;
+        let extent = processor.extent().unwrap();
+        assert_eq!(extent.bbox(), [0.0, 0.0, 10.0, 5.0]);
+        assert_eq!((extent.minz, extent.maxz), (Some(1.0), Some(6.0)));
+        assert_eq!(extent.minm, None);
+
+        processor.reset();
+        WktStr("POINT EMPTY").process_geom(&mut processor)?;
+        assert_eq!(processor.extent(), None);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "LineString", "coordinates": [[-3, 4], [5, -6]]}}
+        ]}"#;
+        let mut processor = ExtentProcessor::new();
+        GeoJson(geojson).process(&mut processor)?;
+        assert_eq!(processor.extent().unwrap().bbox(), [-3.0, -6.0, 5.0, 4.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+use crate::error::Result;
+use crate::processing::record::{FeatureRecorder, OwnedValue};
+use crate::processing::Extent;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Spatial feature filter.
+///
+/// Features are buffered until their end and only forwarded to the inner processor
+/// if their 2D extent intersects `bbox`. Features without coordinates are dropped.
+/// Geometries processed outside of a feature are forwarded unfiltered.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{BboxFilter, StatsProcessor};
+/// use geozero::{geojson::GeoJson, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+///     {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [10, 20]}}
+/// ]}"#;
+/// let mut filter = BboxFilter::new([0.0, 0.0, 5.0, 5.0], StatsProcessor::new());
+/// GeoJson(geojson).process(&mut filter).unwrap();
+/// assert_eq!(filter.into_inner().stats().features, 1);
+/// ```
+pub struct BboxFilter<P> {
+    bbox: Extent,
+    processor: P,
+    recorder: FeatureRecorder,
+    in_feature: bool,
+    extent: Option<Extent>,
+}
+
+impl<P> BboxFilter<P> {
+    /// Filter features intersecting `[minx, miny, maxx, maxy]`.
+    pub fn new(bbox: [f64; 4], processor: P) -> Self {
+        BboxFilter {
+            bbox: Extent::from_bbox(bbox),
+            processor,
+            recorder: FeatureRecorder::default(),
+            in_feature: false,
+            extent: None,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume filter and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn add(&mut self, x: f64, y: f64) {
+        match self.extent.as_mut() {
+            Some(extent) => extent.add(x, y, None, None),
+            None => self.extent = Some(Extent::new(x, y, None, None)),
+        }
+    }
+}
+
+/// Forward event to recorder within features and to the inner processor otherwise.
+macro_rules! record_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.in_feature {
+                    self.recorder.$name($($arg),*)
+                } else {
+                    self.processor.$name($($arg),*)
+                }
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for BboxFilter<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.in_feature {
+            self.add(x, y);
+            self.recorder.xy(x, y, idx)
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.in_feature {
+            self.add(x, y);
+            self.recorder.coordinate(x, y, z, m, t, tm, idx)
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    record_events!(
+        srid(srid: Option<i32>);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for BboxFilter<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.in_feature {
+            self.recorder.property(idx, name, value)
+        } else {
+            self.processor.property(idx, name, value)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for BboxFilter<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.recorder.clear();
+        self.extent = None;
+        self.in_feature = true;
+        self.recorder.feature_begin(idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.in_feature = false;
+        self.recorder.feature_end(idx)?;
+        if let Some(extent) = self.extent.take() {
+            if extent.intersects(&self.bbox) {
+                self.recorder.replay(&mut self.processor)?;
+            }
+        }
+        self.recorder.clear();
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    record_events!(
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// Feature properties passed to the [PropertyFilter] predicate.
+#[derive(Default)]
+pub struct Properties {
+    values: Vec<(String, OwnedValue)>,
+}
+
+impl Properties {
+    /// Value of property `name`.
+    pub fn get(&self, name: &str) -> Option<ColumnValue<'_>> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_column_value())
+    }
+    /// Iterate over property names and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ColumnValue<'_>)> {
+        self.values
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_column_value()))
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Attribute feature filter.
+///
+/// Features are buffered until their end and only forwarded to the inner processor
+/// if `predicate` returns `true` for their properties.
+/// Geometries processed outside of a feature are forwarded unfiltered.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{PropertyFilter, StatsProcessor};
+/// use geozero::{geojson::GeoJson, ColumnValue, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"pop": 500}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+///     {"type": "Feature", "properties": {"pop": 5000}, "geometry": {"type": "Point", "coordinates": [10, 20]}}
+/// ]}"#;
+/// let mut filter = PropertyFilter::new(
+///     |props| matches!(props.get("pop"), Some(ColumnValue::Long(pop)) if pop > 1000),
+///     StatsProcessor::new(),
+/// );
+/// GeoJson(geojson).process(&mut filter).unwrap();
+/// assert_eq!(filter.into_inner().stats().features, 1);
+/// ```
+pub struct PropertyFilter<F, P> {
+    predicate: F,
+    processor: P,
+    recorder: FeatureRecorder,
+    in_feature: bool,
+    properties: Properties,
+}
+
+impl<F: FnMut(&Properties) -> bool, P> PropertyFilter<F, P> {
+    /// Filter features with properties matching `predicate`.
+    pub fn new(predicate: F, processor: P) -> Self {
+        PropertyFilter {
+            predicate,
+            processor,
+            recorder: FeatureRecorder::default(),
+            in_feature: false,
+            properties: Properties::default(),
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume filter and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+impl<F, P: GeomProcessor> GeomProcessor for PropertyFilter<F, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.in_feature {
+            self.recorder.coordinate(x, y, z, m, t, tm, idx)
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    record_events!(
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<F, P: PropertyProcessor> PropertyProcessor for PropertyFilter<F, P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.in_feature {
+            self.properties
+                .values
+                .push((name.to_string(), value.into()));
+            self.recorder.property(idx, name, value)
+        } else {
+            self.processor.property(idx, name, value)
+        }
+    }
+}
+
+impl<F: FnMut(&Properties) -> bool, P: FeatureProcessor> FeatureProcessor for PropertyFilter<F, P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.recorder.clear();
+        self.properties.values.clear();
+        self.in_feature = true;
+        self.recorder.feature_begin(idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.in_feature = false;
+        self.recorder.feature_end(idx)?;
+        if (self.predicate)(&self.properties) {
+            self.recorder.replay(&mut self.processor)?;
+        }
+        self.recorder.clear();
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    record_events!(
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::wkt::WktWriter;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "LineString", "coordinates": [[-3, 4], [5, -6]]}},
+            {"type": "Feature", "properties": {"id": 3}, "geometry": {"type": "Polygon", "coordinates": [[[10, 10], [11, 10], [11, 11], [10, 10]]]}},
+            {"type": "Feature", "properties": {"id": 4}, "geometry": null}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut filter = BboxFilter::new([-1.0, -1.0, 2.0, 2.0], WktWriter::new(&mut out));
+        GeoJson(geojson).process(&mut filter)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "POINT(1 2),LINESTRING(-3 4,5 -6)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn properties() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1, "name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"id": 2, "name": "b"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+
+        struct Names(Vec<String>);
+        impl GeomProcessor for Names {}
+        impl PropertyProcessor for Names {
+            fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+                if name == "name" {
+                    self.0.push(value.to_string());
+                }
+                Ok(false)
+            }
+        }
+        impl FeatureProcessor for Names {}
+
+        let mut filter = BboxFilter::new([2.5, 2.5, 5.0, 5.0], Names(Vec::new()));
+        GeoJson(geojson).process(&mut filter)?;
+        assert_eq!(filter.into_inner().0, vec!["b".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+    fn property_filter() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::wkt::WktWriter;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a", "pop": 500}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "b", "pop": 5000}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+            {"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "Point", "coordinates": [5, 6]}}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut filter = PropertyFilter::new(
+            |props: &Properties| {
+                props.get("pop").is_none() || props.get("name") == Some(ColumnValue::String("a"))
+            },
+            WktWriter::new(&mut out),
+        );
+        GeoJson(geojson).process(&mut filter)?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), "POINT(1 2),POINT(5 6)");
+
+        let mut out = Vec::new();
+        let mut count = 0;
+        let mut filter = PropertyFilter::new(
+            |props: &Properties| {
+                count += props.len();
+                false
+            },
+            WktWriter::new(&mut out),
+        );
+        GeoJson(geojson).process(&mut filter)?;
+        assert!(out.is_empty());
+        assert_eq!(count, 5);
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+use crate::error::Result;
+use crate::processing::properties::float_value;
+use crate::processing::record::FeatureRecorder;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Source of Z values forwarded by [ForceDims].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ZValue {
+    /// Keep Z values of the input
+    Keep,
+    /// Remove Z values
+    Drop,
+    /// Set Z of all coordinates to a constant
+    Constant(f64),
+    /// Set Z of all coordinates to the numeric value of a feature property
+    Property(String),
+}
+
+/// Removal or synthesis of Z and M values.
+///
+/// Bridges between 3D sources and 2D-only sinks (force 2D), or adds Z values to 2D
+/// geometries from a constant or a feature property, e.g. building heights.
+/// Features without a numeric value of the Z property get coordinates without Z.
+/// With [ZValue::Property], features are buffered, since the property may follow
+/// the geometry.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::ForceDims;
+/// use geozero::{wkt::WktWriter, CoordDimensions, GeomProcessor};
+///
+/// let mut wkt = Vec::new();
+/// let mut writer = WktWriter::new(&mut wkt);
+/// writer.dims = CoordDimensions::xyzm();
+/// let mut force = ForceDims::force_2d(writer);
+/// force.linestring_begin(true, 2, 0).unwrap();
+/// force.coordinate(0.0, 0.0, Some(1.0), Some(2.0), None, None, 0).unwrap();
+/// force.coordinate(1.0, 1.0, Some(3.0), Some(4.0), None, None, 1).unwrap();
+/// force.linestring_end(true, 0).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(0 0,1 1)");
+/// ```
+pub struct ForceDims<P> {
+    /// Remove M values
+    pub drop_m: bool,
+    z: ZValue,
+    processor: P,
+    /// Z value of current feature
+    z_value: Option<f64>,
+    in_feature: bool,
+    recorder: FeatureRecorder,
+    replaying: bool,
+}
+
+impl<P> ForceDims<P> {
+    /// Forward coordinates with Z values from `z` to `processor`.
+    pub fn new(z: ZValue, processor: P) -> Self {
+        let z_value = match z {
+            ZValue::Constant(v) => Some(v),
+            _ => None,
+        };
+        ForceDims {
+            drop_m: false,
+            z,
+            processor,
+            z_value,
+            in_feature: false,
+            recorder: FeatureRecorder::default(),
+            replaying: false,
+        }
+    }
+    /// Forward coordinates without Z and M values to `processor`.
+    pub fn force_2d(processor: P) -> Self {
+        let mut force = ForceDims::new(ZValue::Drop, processor);
+        force.drop_m = true;
+        force
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Events of the current feature are buffered until the Z property is known.
+    fn recording(&self) -> bool {
+        self.in_feature && !self.replaying && matches!(self.z, ZValue::Property(_))
+    }
+}
+
+impl<P: GeomProcessor> ForceDims<P> {
+    fn emit(&mut self, c: Coord, idx: usize) -> Result<()> {
+        if !self.processor.multi_dim() {
+            return self.processor.xy(c.x, c.y, idx);
+        }
+        let z = match self.z {
+            ZValue::Keep => c.z,
+            ZValue::Drop => None,
+            ZValue::Constant(_) | ZValue::Property(_) => self.z_value,
+        };
+        let m = if self.drop_m { None } else { c.m };
+        self.processor.coordinate(c.x, c.y, z, m, c.t, c.tm, idx)
+    }
+}
+
+/// Forward event to the inner processor, or buffer it while recording a feature.
+macro_rules! dims_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.recording() {
+                    self.recorder.$name($($arg),*)
+                } else {
+                    self.processor.$name($($arg),*)
+                }
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for ForceDims<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        let mut dims = self.processor.dimensions();
+        dims.z = self.z == ZValue::Keep && dims.z;
+        dims.m = !self.drop_m && dims.m;
+        dims
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.recording() {
+            self.recorder.xy(x, y, idx)
+        } else {
+            self.emit(Coord::xy(x, y), idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.recording() {
+            self.recorder.coordinate(x, y, z, m, t, tm, idx)
+        } else {
+            self.emit(Coord { x, y, z, m, t, tm }, idx)
+        }
+    }
+    dims_events!(
+        srid(srid: Option<i32>);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for ForceDims<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.recording() {
+            if let ZValue::Property(z_name) = &self.z {
+                if name == z_name {
+                    self.z_value = float_value(value);
+                }
+            }
+            return self.recorder.property(idx, name, value);
+        }
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for ForceDims<P> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        if self.replaying {
+            return self.processor.feature_begin(idx);
+        }
+        self.in_feature = true;
+        if self.recording() {
+            self.z_value = None;
+            self.recorder.feature_begin(idx)
+        } else {
+            self.processor.feature_begin(idx)
+        }
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if self.replaying {
+            return self.processor.feature_end(idx);
+        }
+        if self.recording() {
+            self.recorder.feature_end(idx)?;
+            self.in_feature = false;
+            let mut recorder = std::mem::take(&mut self.recorder);
+            self.replaying = true;
+            let result = recorder.replay(self);
+            self.replaying = false;
+            recorder.clear();
+            self.recorder = recorder;
+            self.z_value = None;
+            result
+        } else {
+            self.in_feature = false;
+            self.processor.feature_end(idx)
+        }
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        batch_begin(size: usize);
+        batch_end();
+    );
+    dims_events!(
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+/// [ForceDims] stage for [Pipeline](crate::processing::Pipeline).
+pub struct ForceDimsLayer {
+    pub z: ZValue,
+    /// Remove M values
+    pub drop_m: bool,
+}
+
+impl<P> ProcessorLayer<P> for ForceDimsLayer {
+    type Processor = ForceDims<P>;
+    fn wrap(self, processor: P) -> ForceDims<P> {
+        let mut force = ForceDims::new(self.z, processor);
+        force.drop_m = self.drop_m;
+        force
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::{GeozeroDatasource, GeozeroGeometry};
+
+    /// `LINESTRING(0 0 1 2,1 1 3 4)`, which can't be parsed as WKT
+    struct LineStringZm;
+
+    impl GeozeroGeometry for LineStringZm {
+        fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+            processor.linestring_begin(true, 2, 0)?;
+            processor.coordinate(0.0, 0.0, Some(1.0), Some(2.0), None, None, 0)?;
+            processor.coordinate(1.0, 1.0, Some(3.0), Some(4.0), None, None, 1)?;
+            processor.linestring_end(true, 0)
+        }
+    }
+
+    fn force(wkt: &str, z: ZValue, drop_m: bool) -> Result<String> {
+        force_geom(&WktStr(wkt), z, drop_m)
+    }
+
+    fn force_geom<G: GeozeroGeometry>(geom: &G, z: ZValue, drop_m: bool) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyzm();
+        let mut force = Pipeline::new()
+            .layer(ForceDimsLayer { z, drop_m })
+            .build(writer);
+        geom.process_geom(&mut force)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn dimensions() -> Result<()> {
+        let line = LineStringZm;
+        assert_eq!(
+            force_geom(&line, ZValue::Keep, false)?,
+            "LINESTRING(0 0 1 2,1 1 3 4)"
+        );
+        assert_eq!(
+            force_geom(&line, ZValue::Keep, true)?,
+            "LINESTRING(0 0 1,1 1 3)"
+        );
+        assert_eq!(
+            force_geom(&line, ZValue::Drop, true)?,
+            "LINESTRING(0 0,1 1)"
+        );
+        assert_eq!(
+            force("POINT(1 2)", ZValue::Constant(5.0), false)?,
+            "POINT(1 2 5)"
+        );
+        assert_eq!(
+            force_geom(&line, ZValue::Constant(5.0), true)?,
+            "LINESTRING(0 0 5,1 1 5)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn z_property() -> Result<()> {
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"height": 12.5}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"height": "x"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        writer.dims.z = true;
+        let mut force = ForceDims::new(ZValue::Property("height".to_string()), writer);
+        GeoJson(json).process(&mut force)?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(r#""coordinates": [1,2,12.5]"#));
+        assert!(json.contains(r#""coordinates": [3,4]"#));
+        Ok(())
+    }
+}
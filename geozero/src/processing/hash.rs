@@ -0,0 +1,237 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+const FNV_OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+// Event tags
+const COORD: u8 = 0x40;
+const EMPTY_POINT: u8 = 0x41;
+const END: u8 = 0x80;
+
+/// Processor computing a stable hash of geometry structure and coordinates.
+///
+/// The hash is a 128-bit FNV-1a hash, independent of platform and Rust version.
+/// Geometry types, nesting and coordinate values including Z and M are hashed,
+/// member indices, declared sizes and SRID are not. `-0.0` and `0.0`, as well as
+/// all NaN values, are hashed equally.
+///
+/// For hashing features separately, call [GeomHasher::reset] before each geometry.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{processing::GeomHasher, wkt::WktStr, GeozeroGeometry};
+///
+/// let hash = |wkt| {
+///     let mut hasher = GeomHasher::new();
+///     WktStr(wkt).process_geom(&mut hasher).unwrap();
+///     hasher.hash64()
+/// };
+/// assert_eq!(hash("LINESTRING(1 5,3 2)"), hash("LINESTRING (1 5, 3 2)"));
+/// assert_ne!(hash("LINESTRING(1 5,3 2)"), hash("LINESTRING(3 2,1 5)"));
+/// ```
+pub struct GeomHasher {
+    state: u128,
+}
+
+impl Default for GeomHasher {
+    fn default() -> Self {
+        GeomHasher { state: FNV_OFFSET }
+    }
+}
+
+impl GeomHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 128-bit hash of processed geometries.
+    pub fn hash128(&self) -> u128 {
+        self.state
+    }
+    /// 64-bit hash of processed geometries.
+    pub fn hash64(&self) -> u64 {
+        (self.state >> 64) as u64 ^ self.state as u64
+    }
+    /// Restart hashing.
+    pub fn reset(&mut self) {
+        self.state = FNV_OFFSET;
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u128;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+    fn write_f64(&mut self, v: f64) {
+        let bits = if v.is_nan() {
+            f64::NAN.to_bits()
+        } else if v == 0.0 {
+            0
+        } else {
+            v.to_bits()
+        };
+        self.write(&bits.to_le_bytes());
+    }
+    fn coord(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) {
+        let flags = z.is_some() as u8 | (m.is_some() as u8) << 1;
+        self.write(&[COORD, flags]);
+        self.write_f64(x);
+        self.write_f64(y);
+        if let Some(z) = z {
+            self.write_f64(z);
+        }
+        if let Some(m) = m {
+            self.write_f64(m);
+        }
+    }
+}
+
+/// Hash geometry begin and end events.
+macro_rules! hash_events {
+    ($($begin:ident($($arg:ident: $ty:ty),*), $end:ident($($end_arg:ident: $end_ty:ty),*) => $tag:expr;)*) => {
+        $(
+            fn $begin(&mut self, $($arg: $ty),*) -> Result<()> {
+                self.write(&[$tag]);
+                Ok(())
+            }
+            fn $end(&mut self, $($end_arg: $end_ty),*) -> Result<()> {
+                self.write(&[END | $tag]);
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl GeomProcessor for GeomHasher {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.coord(x, y, None, None);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.coord(x, y, z, m);
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.write(&[EMPTY_POINT]);
+        Ok(())
+    }
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.write(&[3, tagged as u8]);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.write(&[END | 3]);
+        Ok(())
+    }
+    fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.write(&[5, tagged as u8]);
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.write(&[END | 5]);
+        Ok(())
+    }
+    fn triangle_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.write(&[14, tagged as u8]);
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.write(&[END | 14]);
+        Ok(())
+    }
+    hash_events!(
+        point_begin(_idx: usize), point_end(_idx: usize) => 1;
+        multipoint_begin(_size: usize, _idx: usize), multipoint_end(_idx: usize) => 2;
+        multilinestring_begin(_size: usize, _idx: usize), multilinestring_end(_idx: usize) => 4;
+        multipolygon_begin(_size: usize, _idx: usize), multipolygon_end(_idx: usize) => 6;
+        geometrycollection_begin(_size: usize, _idx: usize), geometrycollection_end(_idx: usize) => 7;
+        circularstring_begin(_size: usize, _idx: usize), circularstring_end(_idx: usize) => 8;
+        compoundcurve_begin(_size: usize, _idx: usize), compoundcurve_end(_idx: usize) => 9;
+        curvepolygon_begin(_size: usize, _idx: usize), curvepolygon_end(_idx: usize) => 10;
+        multicurve_begin(_size: usize, _idx: usize), multicurve_end(_idx: usize) => 11;
+        multisurface_begin(_size: usize, _idx: usize), multisurface_end(_idx: usize) => 12;
+        polyhedralsurface_begin(_size: usize, _idx: usize), polyhedralsurface_end(_idx: usize) => 15;
+        tin_begin(_size: usize, _idx: usize), tin_end(_idx: usize) => 16;
+    );
+}
+
+impl PropertyProcessor for GeomHasher {}
+
+impl FeatureProcessor for GeomHasher {}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktStr;
+    use crate::GeozeroGeometry;
+
+    fn hash(wkt: &str) -> u128 {
+        hash_geom(&WktStr(wkt))
+    }
+
+    fn hash_geom<G: GeozeroGeometry>(geom: &G) -> u128 {
+        let mut hasher = GeomHasher::new();
+        geom.process_geom(&mut hasher).unwrap();
+        hasher.hash128()
+    }
+
+    #[test]
+    fn stable() {
+        // Independent of platform and version
+        assert_eq!(hash("POINT(1 2)"), 0x772d9d45acae05ab5052be8a24979570);
+        assert_eq!(GeomHasher::new().hash128(), FNV_OFFSET);
+    }
+
+    #[test]
+    fn structure() {
+        assert_eq!(hash("POINT(1 2)"), hash("POINT(1.0 2.0)"));
+        assert_eq!(hash("POINT(0 2)"), hash("POINT(-0 2)"));
+        assert_ne!(hash("POINT(1 2)"), hash("POINT(2 1)"));
+        assert_ne!(hash("POINT(1 2)"), hash("MULTIPOINT(1 2)"));
+        assert_ne!(
+            hash("MULTILINESTRING((1 2,3 4),(5 6,7 8))"),
+            hash("MULTILINESTRING((1 2,3 4,5 6,7 8))")
+        );
+        assert_ne!(hash("POINT EMPTY"), hash("LINESTRING EMPTY"));
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn dimensions() {
+        use crate::geojson::GeoJson;
+
+        let point = |coords: &str| {
+            hash_geom(&GeoJson(&format!(
+                r#"{{"type": "Point", "coordinates": {}}}"#,
+                coords
+            )))
+        };
+        assert_eq!(hash("POINT(1 2)"), point("[1, 2]"));
+        assert_ne!(point("[1, 2]"), point("[1, 2, 0]"));
+        assert_ne!(point("[1, 2, 0]"), point("[1, 2, 1]"));
+    }
+
+    #[test]
+    fn reset() {
+        let mut hasher = GeomHasher::new();
+        WktStr("POINT(1 2)").process_geom(&mut hasher).unwrap();
+        let first = hasher.hash64();
+        hasher.reset();
+        WktStr("POINT(1 2)").process_geom(&mut hasher).unwrap();
+        assert_eq!(hasher.hash64(), first);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::error::{GeozeroError, Result};
+use crate::processing::ProcessorLayer;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Resource limits for processing untrusted input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    /// Maximal nesting depth of geometries (a polygon ring has depth 2)
+    pub max_depth: usize,
+    /// Maximal number of vertices per feature
+    pub max_vertices: usize,
+    /// Maximal feature size in bytes, counting 8 bytes per coordinate value and the
+    /// length of property names and values
+    pub max_feature_size: usize,
+}
+
+impl Default for Limits {
+    /// Nesting depth of 64 levels, unlimited vertices and feature size.
+    fn default() -> Self {
+        Limits {
+            max_depth: 64,
+            max_vertices: usize::MAX,
+            max_feature_size: usize::MAX,
+        }
+    }
+}
+
+/// Enforcement of [Limits] on processed input.
+///
+/// Returns [GeozeroError::LimitExceeded] as soon as a limit is exceeded. Size hints of
+/// geometries are checked before they are forwarded, so that downstream writers don't
+/// allocate memory for corrupt or malicious input.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::error::GeozeroError;
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::processing::{Limiter, Limits};
+/// use geozero::GeozeroDatasource;
+///
+/// let limits = Limits { max_vertices: 2, ..Default::default() };
+/// let mut json = Vec::new();
+/// let mut limiter = Limiter::new(limits, GeoJsonWriter::new(&mut json));
+/// let feature = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 1], [2, 2], [3, 3]]}}"#;
+/// let err = GeoJson(feature).process(&mut limiter).unwrap_err();
+/// assert!(matches!(err.kind(), GeozeroError::LimitExceeded(_)));
+/// ```
+pub struct Limiter<P> {
+    limits: Limits,
+    processor: P,
+    depth: usize,
+    vertices: usize,
+    size: usize,
+}
+
+impl<P> Limiter<P> {
+    /// Enforce `limits` before forwarding to `processor`.
+    pub fn new(limits: Limits, processor: P) -> Self {
+        Limiter {
+            limits,
+            processor,
+            depth: 0,
+            vertices: 0,
+            size: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume limiter and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+
+    fn begin(&mut self, size: usize) -> Result<()> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth {
+            return Err(GeozeroError::LimitExceeded(format!(
+                "geometry nesting depth exceeds {}",
+                self.limits.max_depth
+            )));
+        }
+        // every member has at least one vertex
+        if size > self.limits.max_vertices - self.vertices {
+            return Err(GeozeroError::LimitExceeded(format!(
+                "geometry size {} exceeds {} vertices",
+                size, self.limits.max_vertices
+            )));
+        }
+        Ok(())
+    }
+    fn end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+    fn vertex(&mut self, values: usize) -> Result<()> {
+        if self.vertices == self.limits.max_vertices {
+            return Err(GeozeroError::LimitExceeded(format!(
+                "feature has more than {} vertices",
+                self.limits.max_vertices
+            )));
+        }
+        self.vertices += 1;
+        self.add_size(values * 8)
+    }
+    fn add_size(&mut self, size: usize) -> Result<()> {
+        self.size = self.size.saturating_add(size);
+        if self.size > self.limits.max_feature_size {
+            return Err(GeozeroError::LimitExceeded(format!(
+                "feature size exceeds {} bytes",
+                self.limits.max_feature_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for Limiter<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.vertex(2)?;
+        self.processor.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let values = 2 + [z, m, t].iter().filter(|v| v.is_some()).count() + tm.iter().count();
+        self.vertex(values)?;
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.processor.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(1)?;
+        self.processor.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(size)?;
+        self.processor.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.processor.tin_end(idx)
+    }
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Limiter<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let value_size = match value {
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => v.len(),
+            ColumnValue::Binary(v) => v.len(),
+            _ => 8,
+        };
+        self.add_size(name.len() + value_size)?;
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Limiter<P> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.depth = 0;
+        self.vertices = 0;
+        self.size = 0;
+        self.processor.feature_begin(idx)
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [Limiter] stage for [Pipeline](crate::processing::Pipeline).
+pub struct LimiterLayer(pub Limits);
+
+impl<P> ProcessorLayer<P> for LimiterLayer {
+    type Processor = Limiter<P>;
+    fn wrap(self, processor: P) -> Limiter<P> {
+        Limiter::new(self.0, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn check(limits: Limits, wkt: &str) -> Result<String> {
+        let mut out = Vec::new();
+        let mut limiter = Limiter::new(limits, WktWriter::new(&mut out));
+        WktStr(wkt).process_geom(&mut limiter)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn limit_message(result: Result<String>) -> String {
+        match result.unwrap_err().kind() {
+            GeozeroError::LimitExceeded(msg) => msg.clone(),
+            e => panic!("unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    fn depth() {
+        let limits = Limits {
+            max_depth: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            check(limits, "POLYGON((0 0,1 0,1 1,0 0))").unwrap(),
+            "POLYGON((0 0,1 0,1 1,0 0))"
+        );
+        assert_eq!(
+            limit_message(check(limits, "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))")),
+            "geometry nesting depth exceeds 2"
+        );
+    }
+
+    #[test]
+    fn vertices() {
+        let limits = Limits {
+            max_vertices: 3,
+            ..Default::default()
+        };
+        assert!(check(limits, "LINESTRING(0 0,1 1,2 2)").is_ok());
+        assert_eq!(
+            limit_message(check(limits, "LINESTRING(0 0,1 1,2 2,3 3)")),
+            "geometry size 4 exceeds 3 vertices"
+        );
+        assert_eq!(
+            limit_message(check(limits, "MULTIPOINT(0 0,1 1,2 2,3 3)")),
+            "geometry size 4 exceeds 3 vertices"
+        );
+        assert_eq!(
+            limit_message(check(limits, "MULTILINESTRING((0 0,1 1),(2 2,3 3))")),
+            "geometry size 2 exceeds 3 vertices"
+        );
+    }
+
+    #[test]
+    fn feature_size() {
+        let limits = Limits {
+            max_feature_size: 32,
+            ..Default::default()
+        };
+        assert!(check(limits, "LINESTRING(0 0,1 1)").is_ok());
+        assert_eq!(
+            limit_message(check(limits, "LINESTRING(0 0,1 1,2 2)")),
+            "feature size exceeds 32 bytes"
+        );
+    }
+}
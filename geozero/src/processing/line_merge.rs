@@ -0,0 +1,343 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+struct MultiLine {
+    idx: usize,
+    lines: Vec<Vec<Coord>>,
+    /// LineString being read
+    line: Option<Vec<Coord>>,
+}
+
+/// Merging of touching LineStrings within MultiLineStrings.
+///
+/// LineStrings whose endpoints are within `tolerance` are stitched together, reversing
+/// members where required, e.g. for OSM ways or contour line fragments. Members are merged
+/// greedily in input order: at junctions of more than two lines, the first matching line
+/// is appended. Closed lines are not extended. The merged geometry stays a MultiLineString,
+/// the shared vertex of merged lines is taken from the line appended to.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::LineMerge;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut merge = LineMerge::new(0.0, WktWriter::new(&mut wkt));
+/// WktStr("MULTILINESTRING((0 0,1 0),(2 1,1 0),(5 5,6 6))").process_geom(&mut merge).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "MULTILINESTRING((0 0,1 0,2 1),(5 5,6 6))");
+/// ```
+pub struct LineMerge<P> {
+    tolerance: f64,
+    processor: P,
+    multi_line: Option<MultiLine>,
+}
+
+impl<P> LineMerge<P> {
+    /// Merge lines with endpoints within `tolerance` and forward to `processor`.
+    pub fn new(tolerance: f64, processor: P) -> Self {
+        LineMerge {
+            tolerance,
+            processor,
+            multi_line: None,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume line merger and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Buffer for coordinates of the current MultiLineString member.
+    fn line(&mut self) -> Option<&mut Vec<Coord>> {
+        self.multi_line
+            .as_mut()
+            .and_then(|multi| multi.line.as_mut())
+    }
+}
+
+/// Endpoint connection of two lines.
+enum Join {
+    /// Append other line
+    Append { reverse: bool },
+    /// Prepend other line
+    Prepend { reverse: bool },
+}
+
+/// Merge lines with matching endpoints.
+fn merge_lines(mut lines: Vec<Vec<Coord>>, tolerance: f64) -> Vec<Vec<Coord>> {
+    let tolerance2 = tolerance * tolerance;
+    let touches = |a: &Coord, b: &Coord| (a.x - b.x).powi(2) + (a.y - b.y).powi(2) <= tolerance2;
+    let mut merged = Vec::with_capacity(lines.len());
+    while !lines.is_empty() {
+        let mut line = lines.remove(0);
+        while let (Some(first), Some(last)) = (line.first().copied(), line.last().copied()) {
+            if line.len() > 2 && touches(&first, &last) {
+                // closed
+                break;
+            }
+            let join = lines.iter().enumerate().find_map(|(i, other)| {
+                let (start, end) = (other.first()?, other.last()?);
+                if touches(&last, start) {
+                    Some((i, Join::Append { reverse: false }))
+                } else if touches(&last, end) {
+                    Some((i, Join::Append { reverse: true }))
+                } else if touches(&first, end) {
+                    Some((i, Join::Prepend { reverse: false }))
+                } else if touches(&first, start) {
+                    Some((i, Join::Prepend { reverse: true }))
+                } else {
+                    None
+                }
+            });
+            let (i, join) = match join {
+                Some(join) => join,
+                None => break,
+            };
+            let mut other = lines.remove(i);
+            match join {
+                Join::Append { reverse } => {
+                    if reverse {
+                        other.reverse();
+                    }
+                    line.extend_from_slice(&other[1..]);
+                }
+                Join::Prepend { reverse } => {
+                    if reverse {
+                        other.reverse();
+                    }
+                    other.pop();
+                    other.extend_from_slice(&line);
+                    line = other;
+                }
+            }
+        }
+        merged.push(line);
+    }
+    merged
+}
+
+impl<P: GeomProcessor> GeomProcessor for LineMerge<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if let Some(line) = self.line() {
+            line.push(Coord::xy(x, y));
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(line) = self.line() {
+            line.push(Coord { x, y, z, m, t, tm });
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.multi_line = Some(MultiLine {
+            idx,
+            lines: Vec::with_capacity(size),
+            line: None,
+        });
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        let multi = match self.multi_line.take() {
+            Some(multi) => multi,
+            None => return self.processor.multilinestring_end(idx),
+        };
+        let lines = merge_lines(multi.lines, self.tolerance);
+        self.processor
+            .multilinestring_begin(lines.len(), multi.idx)?;
+        for (i, line) in lines.iter().enumerate() {
+            self.processor.linestring_begin(false, line.len(), i)?;
+            for (idx, coord) in line.iter().enumerate() {
+                coord.process(&mut self.processor, idx)?;
+            }
+            self.processor.linestring_end(false, i)?;
+        }
+        self.processor.multilinestring_end(multi.idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        match self.multi_line.as_mut() {
+            Some(multi) => {
+                multi.line = Some(Vec::with_capacity(size));
+                Ok(())
+            }
+            None => self.processor.linestring_begin(tagged, size, idx),
+        }
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        match self.multi_line.as_mut() {
+            Some(multi) => {
+                if let Some(line) = multi.line.take() {
+                    multi.lines.push(line);
+                }
+                Ok(())
+            }
+            None => self.processor.linestring_end(tagged, idx),
+        }
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for LineMerge<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for LineMerge<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [LineMerge] stage for [Pipeline](crate::processing::Pipeline).
+pub struct LineMergeLayer(pub f64);
+
+impl<P> ProcessorLayer<P> for LineMergeLayer {
+    type Processor = LineMerge<P>;
+    fn wrap(self, processor: P) -> LineMerge<P> {
+        LineMerge::new(self.0, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn merge(wkt: &str, tolerance: f64) -> Result<String> {
+        merge_geom(&WktStr(wkt), tolerance)
+    }
+
+    fn merge_geom<G: GeozeroGeometry>(geom: &G, tolerance: f64) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut merge = Pipeline::new()
+            .layer(LineMergeLayer(tolerance))
+            .build(writer);
+        geom.process_geom(&mut merge)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn multilinestrings() -> Result<()> {
+        // prepend reversed, append reversed
+        assert_eq!(
+            merge(
+                "MULTILINESTRING((2 0,3 0),(2 0,1 0),(4 0,3 0),(9 9,8 8))",
+                0.0
+            )?,
+            "MULTILINESTRING((1 0,2 0,3 0,4 0),(9 9,8 8))"
+        );
+        assert_eq!(
+            merge("MULTILINESTRING((0 0,1 0),(1.05 0,2 0))", 0.0)?,
+            "MULTILINESTRING((0 0,1 0),(1.05 0,2 0))"
+        );
+        // closed lines are kept
+        assert_eq!(
+            merge("MULTILINESTRING((0 0,1 0,1 1,0 0),(1 1,2 2))", 0.0)?,
+            "MULTILINESTRING((0 0,1 0,1 1,0 0),(1 1,2 2))"
+        );
+        assert_eq!(
+            merge(
+                "GEOMETRYCOLLECTION(LINESTRING(0 0,1 0),MULTILINESTRING((1 1,2 2),(2 2,3 3)))",
+                0.0
+            )?,
+            "GEOMETRYCOLLECTION(LINESTRING(0 0,1 0),MULTILINESTRING((1 1,2 2,3 3)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn multilinestrings_z() -> Result<()> {
+        use crate::geojson::GeoJson;
+
+        let geojson = GeoJson(
+            r#"{"type": "MultiLineString", "coordinates": [[[0, 0, 1], [1, 0, 2]], [[1.05, 0, 3], [2, 0, 4]]]}"#,
+        );
+        assert_eq!(
+            merge_geom(&geojson, 0.1)?,
+            "MULTILINESTRING((0 0 1,1 0 2,2 0 4))"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,554 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::f64::consts::PI;
+
+#[derive(PartialEq)]
+enum Context {
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    Other,
+}
+
+struct Line {
+    tagged: bool,
+    idx: usize,
+    coords: Vec<Coord>,
+}
+
+/// Conversion of curves into linear geometries.
+///
+/// CircularStrings and CompoundCurves are converted to LineStrings, CurvePolygons to
+/// Polygons, MultiCurves to MultiLineStrings and MultiSurfaces to MultiPolygons.
+/// Arcs are approximated by segments spanning at most `max_angle` degrees and,
+/// if set, `max_segment_length`. Z and M values are interpolated.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{Linearize, StatsProcessor};
+/// use geozero::GeomProcessor;
+///
+/// let mut linearize = Linearize::new(StatsProcessor::new());
+/// linearize.circularstring_begin(3, 0).unwrap();
+/// linearize.xy(-1.0, 0.0, 0).unwrap();
+/// linearize.xy(0.0, 1.0, 1).unwrap();
+/// linearize.xy(1.0, 0.0, 2).unwrap();
+/// linearize.circularstring_end(0).unwrap();
+/// let stats = linearize.into_inner().into_stats();
+/// assert_eq!(stats.geometry_count("LineString"), 1);
+/// assert_eq!(stats.vertices, 37);
+/// ```
+pub struct Linearize<P> {
+    /// Maximal angle in degrees of an arc segment (default: 5)
+    pub max_angle: f64,
+    /// Maximal length of an arc segment
+    pub max_segment_length: Option<f64>,
+    processor: P,
+    context: Vec<Context>,
+    /// Linearized curve
+    line: Option<Line>,
+    /// Current compound curve segment
+    segment: Option<Vec<Coord>>,
+}
+
+impl<P> Linearize<P> {
+    pub fn new(processor: P) -> Self {
+        Linearize {
+            max_angle: 5.0,
+            max_segment_length: None,
+            processor,
+            context: Vec::new(),
+            line: None,
+            segment: None,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume linearizer and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Curves are members of a polygon or collection
+    fn member(&self) -> bool {
+        matches!(
+            self.context.last(),
+            Some(Context::CurvePolygon) | Some(Context::MultiCurve)
+        )
+    }
+    fn in_compound(&self) -> bool {
+        self.context.last() == Some(&Context::CompoundCurve)
+    }
+    fn push(&mut self, coord: Coord) -> bool {
+        if let Some(segment) = self.segment.as_mut() {
+            segment.push(coord);
+            true
+        } else {
+            false
+        }
+    }
+    /// Append linearized segment to current line
+    fn end_segment(&mut self, arc: bool) {
+        let segment = self.segment.take().unwrap_or_default();
+        let coords = if arc {
+            linearize_arcs(&segment, self.max_angle, self.max_segment_length)
+        } else {
+            segment
+        };
+        if let Some(line) = self.line.as_mut() {
+            let skip = match (line.coords.last(), coords.first()) {
+                (Some(last), Some(first)) => last.x == first.x && last.y == first.y,
+                _ => false,
+            };
+            line.coords.extend(coords.into_iter().skip(skip as usize));
+        }
+    }
+}
+
+impl<P: GeomProcessor> Linearize<P> {
+    fn emit_line(&mut self) -> Result<()> {
+        if let Some(line) = self.line.take() {
+            self.processor
+                .linestring_begin(line.tagged, line.coords.len(), line.idx)?;
+            for (idx, coord) in line.coords.iter().enumerate() {
+                coord.process(&mut self.processor, idx)?;
+            }
+            self.processor.linestring_end(line.tagged, line.idx)?;
+        }
+        Ok(())
+    }
+}
+
+fn lerp(a: Option<f64>, b: Option<f64>, f: f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + (b - a) * f),
+        _ => None,
+    }
+}
+
+/// Angle difference in range (0, 2π] for counter-clockwise and [-2π, 0) for clockwise direction.
+fn sweep(from: f64, to: f64, ccw: bool) -> f64 {
+    let mut angle = to - from;
+    if ccw {
+        while angle <= 0.0 {
+            angle += 2.0 * PI;
+        }
+    } else {
+        while angle >= 0.0 {
+            angle -= 2.0 * PI;
+        }
+    }
+    angle
+}
+
+/// Linearize circular arcs defined by a sequence of start, intermediate and end points.
+fn linearize_arcs(coords: &[Coord], max_angle: f64, max_segment_length: Option<f64>) -> Vec<Coord> {
+    let mut line = Vec::with_capacity(coords.len());
+    if let Some(first) = coords.first() {
+        line.push(*first);
+    }
+    for arc in coords.windows(3).step_by(2) {
+        linearize_arc(
+            &arc[0],
+            &arc[1],
+            &arc[2],
+            max_angle,
+            max_segment_length,
+            &mut line,
+        );
+    }
+    line
+}
+
+/// Add points of arc `p0`, `p1`, `p2` to `line`, excluding `p0`.
+fn linearize_arc(
+    p0: &Coord,
+    p1: &Coord,
+    p2: &Coord,
+    max_angle: f64,
+    max_segment_length: Option<f64>,
+    line: &mut Vec<Coord>,
+) {
+    let closed = p0.x == p2.x && p0.y == p2.y;
+    let (cx, cy) = if closed {
+        // Full circle with p1 opposite of p0
+        ((p0.x + p1.x) / 2.0, (p0.y + p1.y) / 2.0)
+    } else {
+        let d = 2.0 * (p0.x * (p1.y - p2.y) + p1.x * (p2.y - p0.y) + p2.x * (p0.y - p1.y));
+        if d.abs() < f64::EPSILON {
+            // Collinear points
+            line.push(*p1);
+            line.push(*p2);
+            return;
+        }
+        let (s0, s1, s2) = (
+            p0.x * p0.x + p0.y * p0.y,
+            p1.x * p1.x + p1.y * p1.y,
+            p2.x * p2.x + p2.y * p2.y,
+        );
+        (
+            (s0 * (p1.y - p2.y) + s1 * (p2.y - p0.y) + s2 * (p0.y - p1.y)) / d,
+            (s0 * (p2.x - p1.x) + s1 * (p0.x - p2.x) + s2 * (p1.x - p0.x)) / d,
+        )
+    };
+    let radius = (p0.x - cx).hypot(p0.y - cy);
+    let a0 = (p0.y - cy).atan2(p0.x - cx);
+    let a1 = (p1.y - cy).atan2(p1.x - cx);
+    let a2 = (p2.y - cy).atan2(p2.x - cx);
+    let ccw = closed || (p1.x - p0.x) * (p2.y - p0.y) - (p1.y - p0.y) * (p2.x - p0.x) > 0.0;
+    let total = if closed { 2.0 * PI } else { sweep(a0, a2, ccw) };
+    let f1 = sweep(a0, a1, ccw) / total;
+
+    let mut n = (total.abs() / max_angle.to_radians()).ceil();
+    if let Some(max_length) = max_segment_length {
+        n = n.max((total.abs() * radius / max_length).ceil());
+    }
+    let n = n.max(1.0) as usize;
+    for i in 1..n {
+        let f = i as f64 / n as f64;
+        let angle = a0 + total * f;
+        let (z, m) = if f <= f1 {
+            (lerp(p0.z, p1.z, f / f1), lerp(p0.m, p1.m, f / f1))
+        } else {
+            let f = (f - f1) / (1.0 - f1);
+            (lerp(p1.z, p2.z, f), lerp(p1.m, p2.m, f))
+        };
+        line.push(Coord {
+            x: cx + radius * angle.cos(),
+            y: cy + radius * angle.sin(),
+            z,
+            m,
+            t: None,
+            tm: None,
+        });
+    }
+    line.push(*p2);
+}
+
+impl<P: GeomProcessor> GeomProcessor for Linearize<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.push(Coord::xy(x, y)) {
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.push(Coord { x, y, z, m, t, tm }) {
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.in_compound() {
+            self.segment = Some(Vec::with_capacity(size));
+            Ok(())
+        } else {
+            self.processor.linestring_begin(tagged, size, idx)
+        }
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        if self.in_compound() {
+            self.end_segment(false);
+            Ok(())
+        } else {
+            self.processor.linestring_end(tagged, idx)
+        }
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        if !self.in_compound() {
+            self.line = Some(Line {
+                tagged: !self.member(),
+                idx,
+                coords: Vec::new(),
+            });
+        }
+        self.segment = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_segment(true);
+        if self.in_compound() {
+            Ok(())
+        } else {
+            self.emit_line()
+        }
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
+        self.line = Some(Line {
+            tagged: !self.member(),
+            idx,
+            coords: Vec::new(),
+        });
+        self.context.push(Context::CompoundCurve);
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.context.pop();
+        self.emit_line()
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let tagged = self.context.last() != Some(&Context::MultiSurface);
+        self.context.push(Context::CurvePolygon);
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.context.pop();
+        let tagged = self.context.last() != Some(&Context::MultiSurface);
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.context.push(Context::MultiCurve);
+        self.processor.multilinestring_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.context.pop();
+        self.processor.multilinestring_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.context.push(Context::MultiSurface);
+        self.processor.multipolygon_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.context.pop();
+        self.processor.multipolygon_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.context.push(Context::Other);
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.context.pop();
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.context.push(Context::Other);
+        self.processor.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.context.pop();
+        self.processor.geometrycollection_end(idx)
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Linearize<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Linearize<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [Linearize] stage for [Pipeline](crate::processing::Pipeline) with maximal arc segment angle.
+pub struct LinearizeLayer(pub f64);
+
+impl<P> ProcessorLayer<P> for LinearizeLayer {
+    type Processor = Linearize<P>;
+    fn wrap(self, processor: P) -> Linearize<P> {
+        let mut linearize = Linearize::new(processor);
+        linearize.max_angle = self.0;
+        linearize
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::WktWriter;
+
+    fn arc<P: GeomProcessor + ?Sized>(p: &mut P, coords: &[(f64, f64)], idx: usize) -> Result<()> {
+        p.circularstring_begin(coords.len(), idx)?;
+        for (i, (x, y)) in coords.iter().enumerate() {
+            p.xy(*x, *y, i)?;
+        }
+        p.circularstring_end(idx)
+    }
+
+    fn linestring<P: GeomProcessor + ?Sized>(
+        p: &mut P,
+        coords: &[(f64, f64)],
+        idx: usize,
+    ) -> Result<()> {
+        p.linestring_begin(false, coords.len(), idx)?;
+        for (i, (x, y)) in coords.iter().enumerate() {
+            p.xy(*x, *y, i)?;
+        }
+        p.linestring_end(false, idx)
+    }
+
+    /// Linearize and write WKT with coordinates rounded to 6 decimals.
+    fn linearize<F>(max_angle: f64, max_segment_length: Option<f64>, process: F) -> Result<String>
+    where
+        F: FnOnce(&mut dyn GeomProcessor) -> Result<()>,
+    {
+        let mut out = Vec::new();
+        let round = |v: f64| (v * 1e6).round() / 1e6 + 0.0;
+        let mut linearize = Pipeline::new()
+            .layer(LinearizeLayer(max_angle))
+            .transform(|x: f64, y: f64, z: Option<f64>| -> Result<_> {
+                Ok((round(x), round(y), z.map(round)))
+            })
+            .build(WktWriter::new(&mut out));
+        linearize.max_segment_length = max_segment_length;
+        process(&mut linearize)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn circularstring() -> Result<()> {
+        assert_eq!(
+            linearize(45.0, None, |p| arc(
+                p,
+                &[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)],
+                0
+            ))?,
+            "LINESTRING(0 0,0.292893 0.707107,1 1,1.707107 0.707107,2 0)"
+        );
+        // Two arcs, counter-clockwise
+        assert_eq!(
+            linearize(90.0, None, |p| arc(
+                p,
+                &[(0.0, 0.0), (1.0, -1.0), (2.0, 0.0), (3.0, 1.0), (4.0, 0.0)],
+                0
+            ))?,
+            "LINESTRING(0 0,1 -1,2 0,3 1,4 0)"
+        );
+        // Collinear
+        assert_eq!(
+            linearize(45.0, None, |p| arc(
+                p,
+                &[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)],
+                0
+            ))?,
+            "LINESTRING(0 0,1 1,2 2)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn segment_length() -> Result<()> {
+        assert_eq!(
+            linearize(180.0, Some(1.6), |p| arc(
+                p,
+                &[(-1.0, 0.0), (0.0, 1.0), (1.0, 0.0)],
+                0
+            ))?,
+            "LINESTRING(-1 0,0 1,1 0)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn compoundcurve() -> Result<()> {
+        assert_eq!(
+            linearize(90.0, None, |p| {
+                p.compoundcurve_begin(2, 0)?;
+                arc(p, &[(-1.0, 0.0), (0.0, 1.0), (1.0, 0.0)], 0)?;
+                linestring(p, &[(1.0, 0.0), (1.0, -1.0)], 1)?;
+                p.compoundcurve_end(0)
+            })?,
+            "LINESTRING(-1 0,0 1,1 0,1 -1)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn curvepolygon() -> Result<()> {
+        assert_eq!(
+            linearize(90.0, None, |p| {
+                p.multisurface_begin(2, 0)?;
+                p.curvepolygon_begin(2, 0)?;
+                arc(p, &[(-1.0, 0.0), (1.0, 0.0), (-1.0, 0.0)], 0)?;
+                linestring(p, &[(-0.5, 0.0), (0.0, 0.5), (0.5, 0.0), (-0.5, 0.0)], 1)?;
+                p.curvepolygon_end(0)?;
+                p.polygon_begin(false, 1, 1)?;
+                linestring(p, &[(5.0, 5.0), (6.0, 5.0), (6.0, 6.0), (5.0, 5.0)], 0)?;
+                p.polygon_end(false, 1)?;
+                p.multisurface_end(0)
+            })?,
+            "MULTIPOLYGON(((-1 0,0 -1,1 0,0 1,-1 0),(-0.5 0,0 0.5,0.5 0,-0.5 0)),((5 5,6 5,6 6,5 5)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multicurve() -> Result<()> {
+        assert_eq!(
+            linearize(90.0, None, |p| {
+                p.multicurve_begin(2, 0)?;
+                arc(p, &[(-1.0, 0.0), (0.0, 1.0), (1.0, 0.0)], 0)?;
+                linestring(p, &[(5.0, 5.0), (6.0, 6.0)], 1)?;
+                p.multicurve_end(0)
+            })?,
+            "MULTILINESTRING((-1 0,0 1,1 0),(5 5,6 6))"
+        );
+        Ok(())
+    }
+}
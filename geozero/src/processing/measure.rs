@@ -0,0 +1,552 @@
+use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::processing::ProcessorLayer;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Mean earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Computation of line lengths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LengthMetric {
+    /// Euclidean distance in coordinate units
+    Planar,
+    /// Great-circle distance in meters of longitude/latitude coordinates in degrees
+    Haversine,
+}
+
+impl LengthMetric {
+    fn distance(&self, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+        match self {
+            LengthMetric::Planar => (x2 - x1).hypot(y2 - y1),
+            LengthMetric::Haversine => {
+                let (lat1, lat2) = (y1.to_radians(), y2.to_radians());
+                let dlat = lat2 - lat1;
+                let dlon = (x2 - x1).to_radians();
+                let a = (dlat / 2.0).sin().powi(2)
+                    + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+                2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+            }
+        }
+    }
+}
+
+/// Kind of coordinate sequence being measured.
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    None,
+    Line,
+    Ring,
+}
+
+/// Streaming measurement of line lengths and polygon areas.
+///
+/// Length is the sum of all (Multi)LineString and CircularString lengths, computed with
+/// the configured [LengthMetric]. Curves are measured along their control points.
+/// Area is the planar area of (Multi)Polygons, Triangles, PolyhedralSurfaces and TINs
+/// in squared coordinate units, with holes subtracted. Polygon boundaries do not count
+/// as length.
+///
+/// Measured values are available per feature and as totals. When property names are set,
+/// the values of each feature are added as properties after the feature's own properties.
+/// Features are buffered in this case, since readers usually emit properties before
+/// the geometry.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::processing::{LengthMetric, Measure};
+/// use geozero::GeozeroDatasource;
+///
+/// let geojson = r#"{"type": "Feature", "properties": {"name": "A"},
+///     "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 2], [0, 0]]]}}"#;
+/// let mut out = Vec::new();
+/// let mut measure = Measure::new(LengthMetric::Planar, GeoJsonWriter::new(&mut out))
+///     .with_area_property("area");
+/// GeoJson(geojson).process(&mut measure).unwrap();
+/// assert_eq!(measure.total_area(), 4.0);
+/// let json = std::str::from_utf8(&out).unwrap();
+/// assert!(json.contains(r#""properties": {"name": "A", "area": 4}"#));
+/// ```
+pub struct Measure<P> {
+    metric: LengthMetric,
+    processor: P,
+    length_property: Option<String>,
+    area_property: Option<String>,
+    length: f64,
+    area: f64,
+    total_length: f64,
+    total_area: f64,
+    part: Part,
+    /// Within Polygon or Triangle
+    polygon: bool,
+    /// Within CurvePolygon
+    curve_polygon: bool,
+    /// Index of current ring in polygon
+    ring: usize,
+    first: Option<(f64, f64)>,
+    prev: Option<(f64, f64)>,
+    /// Twice the signed area of the current ring
+    ring_sum: f64,
+    in_feature: bool,
+    recorder: FeatureRecorder,
+    replaying: bool,
+    /// Number of properties forwarded during replay
+    properties: usize,
+    properties_abort: bool,
+    injected: bool,
+}
+
+impl<P> Measure<P> {
+    /// Measure lengths with `metric` and forward events to `processor`.
+    pub fn new(metric: LengthMetric, processor: P) -> Self {
+        Measure {
+            metric,
+            processor,
+            length_property: None,
+            area_property: None,
+            length: 0.0,
+            area: 0.0,
+            total_length: 0.0,
+            total_area: 0.0,
+            part: Part::None,
+            polygon: false,
+            curve_polygon: false,
+            ring: 0,
+            first: None,
+            prev: None,
+            ring_sum: 0.0,
+            in_feature: false,
+            recorder: FeatureRecorder::default(),
+            replaying: false,
+            properties: 0,
+            properties_abort: false,
+            injected: false,
+        }
+    }
+    /// Add the length of each feature as property `name`.
+    pub fn with_length_property(mut self, name: &str) -> Self {
+        self.length_property = Some(name.to_string());
+        self
+    }
+    /// Add the area of each feature as property `name`.
+    pub fn with_area_property(mut self, name: &str) -> Self {
+        self.area_property = Some(name.to_string());
+        self
+    }
+    /// Length of the current or last feature.
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+    /// Area of the current or last feature.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+    /// Length of all processed geometries.
+    pub fn total_length(&self) -> f64 {
+        self.total_length
+    }
+    /// Area of all processed geometries.
+    pub fn total_area(&self) -> f64 {
+        self.total_area
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume measure processor and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    /// Events of the current feature are buffered for injecting properties.
+    fn recording(&self) -> bool {
+        self.in_feature
+            && !self.replaying
+            && (self.length_property.is_some() || self.area_property.is_some())
+    }
+    fn add_point(&mut self, x: f64, y: f64) {
+        if self.replaying {
+            return;
+        }
+        let p = (x, y);
+        match (self.part, self.prev) {
+            (Part::Line, Some(prev)) => {
+                let d = self.metric.distance(prev, p);
+                self.length += d;
+                self.total_length += d;
+            }
+            (Part::Ring, Some(prev)) => self.ring_sum += prev.0 * p.1 - p.0 * prev.1,
+            (Part::Ring, None) => self.first = Some(p),
+            _ => {}
+        }
+        self.prev = Some(p);
+    }
+    fn part_begin(&mut self, part: Part) {
+        self.part = part;
+        self.first = None;
+        self.prev = None;
+        self.ring_sum = 0.0;
+    }
+    fn part_end(&mut self) {
+        if self.part == Part::Ring && !self.replaying {
+            if let (Some(first), Some(last)) = (self.first, self.prev) {
+                self.ring_sum += last.0 * first.1 - first.0 * last.1;
+            }
+            let mut area = self.ring_sum.abs() / 2.0;
+            if self.ring > 0 {
+                // hole
+                area = -area;
+            }
+            self.area += area;
+            self.total_area += area;
+            self.ring += 1;
+        }
+        self.part = Part::None;
+    }
+}
+
+impl<P: PropertyProcessor> Measure<P> {
+    fn inject_properties(&mut self) -> Result<()> {
+        self.injected = true;
+        if self.properties_abort {
+            return Ok(());
+        }
+        let values = [
+            (self.length_property.as_deref(), self.length),
+            (self.area_property.as_deref(), self.area),
+        ];
+        for (name, value) in &values {
+            if let Some(name) = name {
+                if self
+                    .processor
+                    .property(self.properties, name, &ColumnValue::Double(*value))?
+                {
+                    break;
+                }
+                self.properties += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Forward event to the inner processor, or buffer it while recording a feature.
+macro_rules! measure_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.recording() {
+                    self.recorder.$name($($arg),*)
+                } else {
+                    self.processor.$name($($arg),*)
+                }
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for Measure<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.add_point(x, y);
+        if self.recording() {
+            self.recorder.xy(x, y, idx)
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.add_point(x, y);
+        if self.recording() {
+            self.recorder.coordinate(x, y, z, m, t, tm, idx)
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.part_begin(if self.curve_polygon {
+            Part::None
+        } else if self.polygon {
+            Part::Ring
+        } else {
+            Part::Line
+        });
+        if self.recording() {
+            self.recorder.linestring_begin(tagged, size, idx)
+        } else {
+            self.processor.linestring_begin(tagged, size, idx)
+        }
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.part_end();
+        if self.recording() {
+            self.recorder.linestring_end(tagged, idx)
+        } else {
+            self.processor.linestring_end(tagged, idx)
+        }
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.part_begin(if self.curve_polygon || self.polygon {
+            Part::None
+        } else {
+            Part::Line
+        });
+        if self.recording() {
+            self.recorder.circularstring_begin(size, idx)
+        } else {
+            self.processor.circularstring_begin(size, idx)
+        }
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.part_end();
+        if self.recording() {
+            self.recorder.circularstring_end(idx)
+        } else {
+            self.processor.circularstring_end(idx)
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon = true;
+        self.ring = 0;
+        if self.recording() {
+            self.recorder.polygon_begin(tagged, size, idx)
+        } else {
+            self.processor.polygon_begin(tagged, size, idx)
+        }
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon = false;
+        if self.recording() {
+            self.recorder.polygon_end(tagged, idx)
+        } else {
+            self.processor.polygon_end(tagged, idx)
+        }
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon = true;
+        self.ring = 0;
+        if self.recording() {
+            self.recorder.triangle_begin(tagged, size, idx)
+        } else {
+            self.processor.triangle_begin(tagged, size, idx)
+        }
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon = false;
+        if self.recording() {
+            self.recorder.triangle_end(tagged, idx)
+        } else {
+            self.processor.triangle_end(tagged, idx)
+        }
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.curve_polygon = true;
+        if self.recording() {
+            self.recorder.curvepolygon_begin(size, idx)
+        } else {
+            self.processor.curvepolygon_begin(size, idx)
+        }
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.curve_polygon = false;
+        if self.recording() {
+            self.recorder.curvepolygon_end(idx)
+        } else {
+            self.processor.curvepolygon_end(idx)
+        }
+    }
+    measure_events!(
+        srid(srid: Option<i32>);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Measure<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.recording() {
+            return self.recorder.property(idx, name, value);
+        }
+        if self.replaying {
+            self.properties = self.properties.max(idx + 1);
+        }
+        let abort = self.processor.property(idx, name, value)?;
+        self.properties_abort |= abort;
+        Ok(abort)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Measure<P> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        if self.replaying {
+            self.properties = 0;
+            self.properties_abort = false;
+            self.injected = false;
+            return self.processor.feature_begin(idx);
+        }
+        self.length = 0.0;
+        self.area = 0.0;
+        self.in_feature = true;
+        if self.recording() {
+            self.recorder.feature_begin(idx)
+        } else {
+            self.processor.feature_begin(idx)
+        }
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if self.replaying {
+            if !self.injected {
+                self.processor.properties_begin()?;
+                self.inject_properties()?;
+                self.processor.properties_end()?;
+            }
+            return self.processor.feature_end(idx);
+        }
+        if self.recording() {
+            self.recorder.feature_end(idx)?;
+            self.in_feature = false;
+            let mut recorder = std::mem::take(&mut self.recorder);
+            self.replaying = true;
+            let result = recorder.replay(self);
+            self.replaying = false;
+            recorder.clear();
+            self.recorder = recorder;
+            result
+        } else {
+            self.in_feature = false;
+            self.processor.feature_end(idx)
+        }
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if self.recording() {
+            return self.recorder.properties_end();
+        }
+        if self.replaying && !self.injected {
+            self.inject_properties()?;
+        }
+        self.processor.properties_end()
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        batch_begin(size: usize);
+        batch_end();
+    );
+    measure_events!(
+        properties_begin();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+/// [Measure] stage for [Pipeline](crate::processing::Pipeline).
+pub struct MeasureLayer {
+    pub metric: LengthMetric,
+    /// Property name for the length of each feature
+    pub length_property: Option<String>,
+    /// Property name for the area of each feature
+    pub area_property: Option<String>,
+}
+
+impl<P> ProcessorLayer<P> for MeasureLayer {
+    type Processor = Measure<P>;
+    fn wrap(self, processor: P) -> Measure<P> {
+        let mut measure = Measure::new(self.metric, processor);
+        measure.length_property = self.length_property;
+        measure.area_property = self.area_property;
+        measure
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::processing::Pipeline;
+    use crate::{GeozeroDatasource, ProcessorSink};
+
+    #[test]
+    fn haversine() {
+        // one degree of latitude
+        let d = LengthMetric::Haversine.distance((8.0, 47.0), (8.0, 48.0));
+        assert!((d - 111_195.0).abs() < 1.0);
+        assert_eq!(LengthMetric::Planar.distance((0.0, 0.0), (3.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn measure() -> Result<()> {
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "MultiLineString", "coordinates": [[[0, 0], [3, 4]], [[0, 0], [0, 1], [1, 1]]]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]], [[1, 1], [2, 1], [2, 2], [1, 2], [1, 1]]]}},
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 1]}}
+        ]}"#;
+        let mut measure = Measure::new(LengthMetric::Planar, ProcessorSink);
+        GeoJson(json).process(&mut measure)?;
+        assert_eq!(measure.total_length(), 7.0);
+        assert_eq!(measure.total_area(), 15.0);
+        assert_eq!((measure.length(), measure.area()), (0.0, 0.0));
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut measure = Pipeline::new()
+            .layer(MeasureLayer {
+                metric: LengthMetric::Planar,
+                length_property: Some("length".to_string()),
+                area_property: Some("area".to_string()),
+            })
+            .build(GeoJsonWriter::new(&mut out));
+        GeoJson(json).process(&mut measure)?;
+        assert_eq!(measure.total_length(), 7.0);
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(r#""properties": {"id": 1, "length": 7, "area": 0}"#));
+        assert!(json.contains(r#""properties": {"id": 2, "length": 0, "area": 15}"#));
+        assert!(json.contains(r#""properties": {"length": 0, "area": 0}"#));
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnMetadata, ColumnValue, CoordDimensions, DatasetMetadata, DatasetSchema, FeatureProcessor,
+    GeomProcessor, PropertyProcessor, RewindableDatasource,
+};
+
+/// Concatenate the features of `inputs` into one dataset.
+///
+/// The schemas of all inputs are merged with [DatasetSchema::merge] before processing,
+/// which fails for incompatible geometry types or SRIDs. The united columns are emitted
+/// with `dataset_metadata`, property indices refer to these columns and features missing
+/// a column have no value for it. Feature indices are numbered across all inputs.
+///
+/// Inputs are scanned for their schema and rewound before processing.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::processing::merge;
+///
+/// let tile1 = r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#;
+/// let tile2 = r#"{"type": "Feature", "properties": {"pop": 5}, "geometry": {"type": "Point", "coordinates": [2, 2]}}"#;
+/// let mut json = Vec::new();
+/// merge(vec![GeoJson(tile1), GeoJson(tile2)], &mut GeoJsonWriter::new(&mut json)).unwrap();
+/// ```
+pub fn merge<D: RewindableDatasource, P: FeatureProcessor>(
+    mut inputs: Vec<D>,
+    processor: &mut P,
+) -> Result<()> {
+    let mut schema = DatasetSchema::default();
+    for (i, input) in inputs.iter_mut().enumerate() {
+        schema
+            .merge(&input.schema()?)
+            .map_err(|e| GeozeroError::Dataset(format!("merging input {}: {}", i, e)))?;
+    }
+    processor.dataset_begin(schema.name.as_deref())?;
+    if schema.srid.is_some() {
+        processor.dataset_srid(schema.srid)?;
+    }
+    let columns = schema
+        .columns
+        .iter()
+        .map(|col| ColumnMetadata {
+            name: col.name.clone(),
+            column_type: Some(col.column_type),
+            nullable: col.nullable,
+            ..Default::default()
+        })
+        .collect();
+    processor.dataset_metadata(&DatasetMetadata {
+        columns,
+        ..Default::default()
+    })?;
+    let mut merger = MergeProcessor {
+        processor,
+        schema: &schema,
+        count: 0,
+    };
+    for input in &mut inputs {
+        input.rewind()?;
+        input.process(&mut merger)?;
+    }
+    processor.dataset_end()
+}
+
+/// Processor forwarding features of one input with merged feature and property indices.
+struct MergeProcessor<'a, P> {
+    processor: &'a mut P,
+    schema: &'a DatasetSchema,
+    /// Number of features of previous inputs and current input
+    count: u64,
+}
+
+impl<P: GeomProcessor> GeomProcessor for MergeProcessor<'_, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    forward_events!(processor;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        coordinate(x: f64, y: f64, z: Option<f64>, m: Option<f64>, t: Option<f64>, tm: Option<u64>, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for MergeProcessor<'_, P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let idx = self
+            .schema
+            .columns
+            .iter()
+            .position(|col| col.name == name)
+            .unwrap_or(idx);
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for MergeProcessor<'_, P> {
+    /// Dataset events of inputs are replaced by the merged dataset
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+    fn dataset_srid(&mut self, _srid: Option<i32>) -> Result<()> {
+        Ok(())
+    }
+    fn dataset_metadata(&mut self, _metadata: &DatasetMetadata) -> Result<()> {
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.processor.feature_begin(self.count)
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.processor.feature_end(self.count)?;
+        self.count += 1;
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonReader, GeoJsonWriter};
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl GeomProcessor for Events {}
+    impl PropertyProcessor for Events {
+        fn property(&mut self, idx: usize, name: &str, _value: &ColumnValue) -> Result<bool> {
+            self.0.push(format!("{}:{}", idx, name));
+            Ok(false)
+        }
+    }
+    impl FeatureProcessor for Events {
+        fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+            let columns = metadata
+                .columns
+                .iter()
+                .map(|col| col.name.as_str())
+                .collect::<Vec<_>>();
+            self.0.push(format!("[{}]", columns.join(",")));
+            Ok(())
+        }
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(format!("f{}", idx));
+            Ok(())
+        }
+    }
+
+    const TILE1: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+    ]}"#;
+    const TILE2: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"pop": 5, "name": "c"}, "geometry": {"type": "MultiPoint", "coordinates": [[3, 3]]}}
+    ]}"#;
+
+    #[test]
+    fn merge_tiles() -> Result<()> {
+        let mut events = Events::default();
+        merge(vec![GeoJson(TILE1), GeoJson(TILE2)], &mut events)?;
+        assert_eq!(
+            events.0.join(" "),
+            "[name,pop] f0 0:name f1 0:name f2 0:name 1:pop"
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        merge(
+            vec![GeoJson(TILE1), GeoJson(TILE2)],
+            &mut GeoJsonWriter::new(&mut out),
+        )?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert_eq!(json.matches(r#""type": "Feature""#).count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_readers() -> Result<()> {
+        let mut tile1 = std::io::Cursor::new(TILE1);
+        let mut tile2 = std::io::Cursor::new(TILE2);
+        let mut events = Events::default();
+        merge(
+            vec![GeoJsonReader(&mut tile1), GeoJsonReader(&mut tile2)],
+            &mut events,
+        )?;
+        assert_eq!(
+            events.0.join(" "),
+            "[name,pop] f0 0:name f1 0:name f2 0:name 1:pop"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_geometry_types() {
+        let lines = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 1], [2, 2]]}}"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(merge(
+            vec![GeoJson(TILE1), GeoJson(lines)],
+            &mut GeoJsonWriter::new(&mut out)
+        )
+        .is_err());
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+//! Processors for analysing, filtering and modifying features and geometries.
+
+/// Implement processor methods forwarding events unchanged to `self.$inner`.
+///
+/// With `$inner if $active`, events are only forwarded while `self.$active` is true.
+macro_rules! forward_events {
+    ($inner:ident; $($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                self.$inner.$name($($arg),*)
+            }
+        )*
+    };
+    ($inner:ident if $active:ident; $($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.$active {
+                    self.$inner.$name($($arg),*)
+                } else {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+mod batch;
+mod centroid;
+mod clean;
+mod coerce;
+mod column_stats;
+mod coord_sink;
+mod explode;
+mod extent;
+mod filter;
+mod force_dims;
+mod hash;
+mod limit;
+mod line_merge;
+mod linearize;
+mod measure;
+mod merge;
+mod orient;
+mod page;
+mod pipeline;
+mod progress;
+mod properties;
+pub(crate) mod record;
+mod sample;
+mod simplify;
+mod snap;
+mod split;
+mod stats;
+mod summary;
+mod tile_clip;
+mod validate;
+
+pub use batch::*;
+pub use centroid::*;
+pub use clean::*;
+pub use coerce::*;
+pub use column_stats::*;
+pub use coord_sink::*;
+pub use explode::*;
+pub use extent::*;
+pub use filter::*;
+pub use force_dims::*;
+pub use hash::*;
+pub use limit::*;
+pub use line_merge::*;
+pub use linearize::*;
+pub use measure::*;
+pub use merge::*;
+pub use orient::*;
+pub(crate) use page::FeaturePage;
+pub use pipeline::*;
+pub use progress::*;
+pub use properties::*;
+pub use sample::*;
+pub use simplify::*;
+pub use snap::*;
+pub use split::*;
+pub use stats::*;
+pub use summary::*;
+pub use tile_clip::*;
+pub use validate::*;
+
+/// Buffered coordinate.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub(crate) struct Coord {
+    pub x: f64,
+    pub y: f64,
+    pub z: Option<f64>,
+    pub m: Option<f64>,
+    pub t: Option<f64>,
+    pub tm: Option<u64>,
+}
+
+impl Coord {
+    pub fn xy(x: f64, y: f64) -> Self {
+        Coord {
+            x,
+            y,
+            ..Default::default()
+        }
+    }
+    /// Process coordinate with the dimensions requested by `processor`.
+    pub fn process<P: crate::GeomProcessor>(
+        &self,
+        processor: &mut P,
+        idx: usize,
+    ) -> crate::error::Result<()> {
+        if processor.multi_dim() {
+            processor.coordinate(self.x, self.y, self.z, self.m, self.t, self.tm, idx)
+        } else {
+            processor.xy(self.x, self.y, idx)
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Ring orientation convention.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RingOrientation {
+    /// Counter-clockwise exterior rings and clockwise holes (OGC, GeoJSON RFC 7946)
+    Ogc,
+    /// Clockwise exterior rings and counter-clockwise holes (Shapefile)
+    Shapefile,
+}
+
+struct Ring {
+    idx: usize,
+    coords: Vec<Coord>,
+}
+
+/// Normalization of polygon ring orientation.
+///
+/// Rings of Polygons and Triangles are buffered one at a time and reversed if their
+/// orientation does not match the configured convention. The first ring of a polygon
+/// is the exterior ring, all following rings are holes. Rings of CurvePolygons and
+/// rings without area are forwarded unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{OrientRings, RingOrientation};
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut orient = OrientRings::new(RingOrientation::Ogc, WktWriter::new(&mut wkt));
+/// WktStr("POLYGON((0 0,0 1,1 1,0 0))").process_geom(&mut orient).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POLYGON((0 0,1 1,0 1,0 0))");
+/// ```
+pub struct OrientRings<P> {
+    orientation: RingOrientation,
+    processor: P,
+    ring: Option<Ring>,
+    /// Within Polygon or Triangle
+    polygon: bool,
+    /// Index of next ring in polygon
+    ring_idx: usize,
+}
+
+impl<P> OrientRings<P> {
+    /// Orient rings according to `orientation` and forward to `processor`.
+    pub fn new(orientation: RingOrientation, processor: P) -> Self {
+        OrientRings {
+            orientation,
+            processor,
+            ring: None,
+            polygon: false,
+            ring_idx: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume ring orienter and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+/// Twice the signed area of a ring, positive for counter-clockwise orientation.
+fn signed_area2(coords: &[Coord]) -> f64 {
+    let mut sum = 0.0;
+    for (i, a) in coords.iter().enumerate() {
+        let b = &coords[(i + 1) % coords.len()];
+        sum += a.x * b.y - b.x * a.y;
+    }
+    sum
+}
+
+impl<P: GeomProcessor> OrientRings<P> {
+    fn emit_ring(&mut self, mut ring: Ring, exterior: bool) -> Result<()> {
+        let ccw = exterior == (self.orientation == RingOrientation::Ogc);
+        let area = signed_area2(&ring.coords);
+        if (ccw && area < 0.0) || (!ccw && area > 0.0) {
+            ring.coords.reverse();
+        }
+        self.processor
+            .linestring_begin(false, ring.coords.len(), ring.idx)?;
+        for (idx, c) in ring.coords.iter().enumerate() {
+            c.process(&mut self.processor, idx)?;
+        }
+        self.processor.linestring_end(false, ring.idx)
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for OrientRings<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if let Some(ring) = self.ring.as_mut() {
+            ring.coords.push(Coord::xy(x, y));
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(ring) = self.ring.as_mut() {
+            ring.coords.push(Coord { x, y, z, m, t, tm });
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if !self.polygon || tagged {
+            return self.processor.linestring_begin(tagged, size, idx);
+        }
+        self.ring = Some(Ring {
+            idx,
+            coords: Vec::with_capacity(size),
+        });
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        match self.ring.take() {
+            Some(ring) => {
+                let exterior = self.ring_idx == 0;
+                self.ring_idx += 1;
+                self.emit_ring(ring, exterior)
+            }
+            None => self.processor.linestring_end(tagged, idx),
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon = true;
+        self.ring_idx = 0;
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon = false;
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon = true;
+        self.ring_idx = 0;
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon = false;
+        self.processor.triangle_end(tagged, idx)
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for OrientRings<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for OrientRings<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [OrientRings] stage for [Pipeline](crate::processing::Pipeline).
+pub struct OrientRingsLayer(pub RingOrientation);
+
+impl<P> ProcessorLayer<P> for OrientRingsLayer {
+    type Processor = OrientRings<P>;
+    fn wrap(self, processor: P) -> OrientRings<P> {
+        OrientRings::new(self.0, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn orient(wkt: &str, orientation: RingOrientation) -> Result<String> {
+        orient_geom(&WktStr(wkt), orientation)
+    }
+
+    fn orient_geom<G: GeozeroGeometry>(geom: &G, orientation: RingOrientation) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut orient = Pipeline::new()
+            .layer(OrientRingsLayer(orientation))
+            .build(writer);
+        geom.process_geom(&mut orient)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn orientation() -> Result<()> {
+        let ogc = "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,1 2,2 2,2 1,1 1))";
+        let shp = "POLYGON((0 0,0 4,4 4,4 0,0 0),(1 1,2 1,2 2,1 2,1 1))";
+        assert_eq!(orient(ogc, RingOrientation::Ogc)?, ogc);
+        assert_eq!(orient(shp, RingOrientation::Ogc)?, ogc);
+        assert_eq!(orient(ogc, RingOrientation::Shapefile)?, shp);
+        assert_eq!(
+            orient(
+                "MULTIPOLYGON(((0 0,0 1,1 0,0 0)),((5 5,6 5,5 6,5 5)))",
+                RingOrientation::Shapefile
+            )?,
+            "MULTIPOLYGON(((0 0,0 1,1 0,0 0)),((5 5,5 6,6 5,5 5)))"
+        );
+        // lines are unchanged
+        assert_eq!(
+            orient("LINESTRING(0 0,0 1,1 1,0 0)", RingOrientation::Ogc)?,
+            "LINESTRING(0 0,0 1,1 1,0 0)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn orientation_z() -> Result<()> {
+        use crate::geojson::GeoJson;
+
+        let shp = GeoJson(
+            r#"{"type": "Polygon", "coordinates": [
+                [[0, 0, 1], [0, 4, 4], [4, 4, 3], [4, 0, 2], [0, 0, 1]],
+                [[1, 1, 0], [2, 1, 0], [2, 2, 0], [1, 2, 0], [1, 1, 0]]
+            ]}"#,
+        );
+        assert_eq!(
+            orient_geom(&shp, RingOrientation::Ogc)?,
+            "POLYGON((0 0 1,4 0 2,4 4 3,0 4 4,0 0 1),(1 1 0,1 2 0,2 2 0,2 1 0,1 1 0))"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Processor forwarding the features of a range with renumbered feature indices.
+pub(crate) struct FeaturePage<'a, P: FeatureProcessor> {
+    processor: &'a mut P,
+    skip: u64,
+    take: Option<u64>,
+    /// Number of features started
+    count: u64,
+    /// Current feature is within range or no feature is processed
+    active: bool,
+}
+
+impl<'a, P: FeatureProcessor> FeaturePage<'a, P> {
+    pub fn new(skip: u64, take: Option<u64>, processor: &'a mut P) -> Self {
+        FeaturePage {
+            processor,
+            skip,
+            take,
+            count: 0,
+            active: true,
+        }
+    }
+    fn end(&self) -> Option<u64> {
+        self.take.map(|take| self.skip.saturating_add(take))
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for FeaturePage<'_, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    forward_events!(processor if active;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        coordinate(x: f64, y: f64, z: Option<f64>, m: Option<f64>, t: Option<f64>, tm: Option<u64>, idx: usize);
+        coords(coords: &[f64], dims: CoordDimensions, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for FeaturePage<'_, P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.active {
+            self.processor.property(idx, name, value)
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for FeaturePage<'_, P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.count = 0;
+        self.active = true;
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.active = true;
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        if Some(self.count) == self.end() {
+            self.active = true;
+            return Err(GeozeroError::Stop);
+        }
+        self.count += 1;
+        self.active = self.count > self.skip;
+        if self.active {
+            self.processor.feature_begin(self.count - self.skip - 1)
+        } else {
+            Ok(())
+        }
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if !self.active {
+            self.active = true;
+            return Ok(());
+        }
+        self.processor.feature_end(self.count - self.skip - 1)?;
+        if Some(self.count) == self.end() {
+            return Err(GeozeroError::Stop);
+        }
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor if active;
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
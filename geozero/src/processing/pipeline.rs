@@ -0,0 +1,152 @@
+use crate::transform::{CoordTransform, TransformProcessor};
+
+/// Wrapping of a processor into another processor.
+pub trait ProcessorLayer<P> {
+    /// Resulting processor
+    type Processor;
+    /// Wrap `processor`.
+    fn wrap(self, processor: P) -> Self::Processor;
+}
+
+/// Layer without processing.
+pub struct Identity;
+
+impl<P> ProcessorLayer<P> for Identity {
+    type Processor = P;
+    fn wrap(self, processor: P) -> P {
+        processor
+    }
+}
+
+/// Two layers, `outer` processing events before `inner`.
+pub struct Stack<Outer, Inner> {
+    outer: Outer,
+    inner: Inner,
+}
+
+impl<P, Outer, Inner> ProcessorLayer<P> for Stack<Outer, Inner>
+where
+    Inner: ProcessorLayer<P>,
+    Outer: ProcessorLayer<Inner::Processor>,
+{
+    type Processor = Outer::Processor;
+    fn wrap(self, processor: P) -> Self::Processor {
+        self.outer.wrap(self.inner.wrap(processor))
+    }
+}
+
+/// Layer applying a coordinate transformation.
+pub struct TransformLayer<T: CoordTransform>(pub T);
+
+impl<T: CoordTransform, P> ProcessorLayer<P> for TransformLayer<T> {
+    type Processor = TransformProcessor<T, P>;
+    fn wrap(self, processor: P) -> Self::Processor {
+        TransformProcessor::with_transform(self.0, processor)
+    }
+}
+
+/// Builder for chained processors.
+///
+/// Stages are applied in the order they are added, the processor passed to
+/// [build](Pipeline::build) receives the events last.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::Pipeline;
+/// use geozero::transform::AffineTransform;
+/// use geozero::{error::Result, wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut pipeline = Pipeline::new()
+///     .transform(AffineTransform::scale(1.5, 1.5, 1.0))
+///     .transform(|x: f64, y: f64, z: Option<f64>| -> Result<_> { Ok((x.round(), y.round(), z)) })
+///     .build(WktWriter::new(&mut wkt));
+/// WktStr("POINT(1 2)").process_geom(&mut pipeline).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(2 3)");
+/// ```
+pub struct Pipeline<L> {
+    layer: L,
+}
+
+impl Pipeline<Identity> {
+    pub fn new() -> Self {
+        Pipeline { layer: Identity }
+    }
+}
+
+impl Default for Pipeline<Identity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L> Pipeline<L> {
+    /// Add a processing stage.
+    pub fn layer<N>(self, layer: N) -> Pipeline<Stack<L, N>> {
+        Pipeline {
+            layer: Stack {
+                outer: self.layer,
+                inner: layer,
+            },
+        }
+    }
+    /// Add a coordinate transformation stage.
+    pub fn transform<T: CoordTransform>(
+        self,
+        transform: T,
+    ) -> Pipeline<Stack<L, TransformLayer<T>>> {
+        self.layer(TransformLayer(transform))
+    }
+    /// Build processor chain ending with `processor`.
+    pub fn build<P>(self, processor: P) -> L::Processor
+    where
+        L: ProcessorLayer<P>,
+    {
+        self.layer.wrap(processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::error::Result;
+    use crate::processing::StatsProcessor;
+    use crate::transform::AffineTransform;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    #[test]
+    fn transform_order() -> Result<()> {
+        let mut wkt = Vec::new();
+        let mut pipeline = Pipeline::new()
+            .transform(AffineTransform::translate(1.0, 1.0, 0.0))
+            .transform(AffineTransform::scale(10.0, 10.0, 1.0))
+            .build(WktWriter::new(&mut wkt));
+        WktStr("LINESTRING(0 0,1 2)").process_geom(&mut pipeline)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt).unwrap(),
+            "LINESTRING(10 10,20 30)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_pipeline() -> Result<()> {
+        let mut stats = Pipeline::new().build(StatsProcessor::new());
+        WktStr("POINT(1 2)").process_geom(&mut stats)?;
+        assert_eq!(stats.stats().vertices, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn inner_processor() -> Result<()> {
+        let mut pipeline = Pipeline::default()
+            .transform(AffineTransform::translate(1.0, 1.0, 0.0))
+            .build(StatsProcessor::new());
+        WktStr("POINT(1 2)").process_geom(&mut pipeline)?;
+        assert_eq!(pipeline.into_inner().stats().vertices, 1);
+        Ok(())
+    }
+}
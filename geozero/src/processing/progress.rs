@@ -0,0 +1,307 @@
+use crate::error::{GeozeroError, Result};
+use crate::processing::ProcessorLayer;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Shared count of bytes read by a [ProgressReader].
+#[derive(Clone, Default, Debug)]
+pub struct ByteCounter(Arc<AtomicU64>);
+
+impl ByteCounter {
+    /// Number of bytes read.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+    fn add(&self, n: usize) {
+        self.0.fetch_add(n as u64, Ordering::Relaxed);
+    }
+}
+
+/// Reader counting the bytes consumed by a dataset reader.
+pub struct ProgressReader<R> {
+    inner: R,
+    counter: ByteCounter,
+}
+
+impl<R> ProgressReader<R> {
+    pub fn new(inner: R) -> Self {
+        ProgressReader {
+            inner,
+            counter: ByteCounter::default(),
+        }
+    }
+    /// Handle for reading the number of consumed bytes, e.g. by [Progress].
+    pub fn counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+    /// Underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.counter.add(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for ProgressReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        self.counter.add(amt);
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: Seek> Seek for ProgressReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// Progress reporting after each processed feature.
+///
+/// `callback` is called with the number of processed features, the number of bytes read
+/// (if `bytes_read` is set) and `total`. Processing is cancelled with
+/// [GeozeroError::Cancelled], when the callback returns `ControlFlow::Break`.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::error::GeozeroError;
+/// use geozero::geojson::GeoJsonReader;
+/// use geozero::processing::{Progress, ProgressReader, StatsProcessor};
+/// use geozero::GeozeroDatasource;
+/// use std::ops::ControlFlow;
+///
+/// let geojson = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+/// ]}"#;
+/// let mut reader = ProgressReader::new(geojson.as_bytes());
+/// let mut progress = Progress::new(StatsProcessor::new(), |features, bytes, total| {
+///     println!("{} features, {} of {:?} bytes", features, bytes, total);
+///     if features < 1 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+/// });
+/// progress.bytes_read = Some(reader.counter());
+/// progress.total = Some(geojson.len() as u64);
+/// let err = GeoJsonReader(&mut reader).process(&mut progress).unwrap_err();
+/// assert!(matches!(err.kind(), GeozeroError::Cancelled));
+/// ```
+pub struct Progress<P, F> {
+    processor: P,
+    callback: F,
+    /// Total number of features or bytes passed to the callback
+    pub total: Option<u64>,
+    /// Bytes read by a [ProgressReader]
+    pub bytes_read: Option<ByteCounter>,
+    features: u64,
+}
+
+impl<P, F: FnMut(u64, u64, Option<u64>) -> ControlFlow<()>> Progress<P, F> {
+    /// Report progress of `processor` to `callback`.
+    pub fn new(processor: P, callback: F) -> Self {
+        Progress {
+            processor,
+            callback,
+            total: None,
+            bytes_read: None,
+            features: 0,
+        }
+    }
+    /// Number of processed features.
+    pub fn features(&self) -> u64 {
+        self.features
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume progress reporter and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn report(&mut self) -> Result<()> {
+        let bytes = self.bytes_read.as_ref().map(|c| c.get()).unwrap_or(0);
+        match (self.callback)(self.features, bytes, self.total) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(GeozeroError::Cancelled),
+        }
+    }
+}
+
+impl<P: GeomProcessor, F> GeomProcessor for Progress<P, F> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.processor.coords(coords, dims, idx)
+    }
+    forward_events!(processor;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor, F> PropertyProcessor for Progress<P, F> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(u64, u64, Option<u64>) -> ControlFlow<()>> FeatureProcessor
+    for Progress<P, F>
+{
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.features = 0;
+        self.processor.dataset_begin(name)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.processor.feature_end(idx)?;
+        self.features += 1;
+        self.report()
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        feature_begin(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [Progress] stage for [Pipeline](crate::processing::Pipeline).
+pub struct ProgressLayer<F> {
+    pub callback: F,
+    pub total: Option<u64>,
+    pub bytes_read: Option<ByteCounter>,
+}
+
+impl<P, F: FnMut(u64, u64, Option<u64>) -> ControlFlow<()>> ProcessorLayer<P> for ProgressLayer<F> {
+    type Processor = Progress<P, F>;
+    fn wrap(self, processor: P) -> Progress<P, F> {
+        let mut progress = Progress::new(processor, self.callback);
+        progress.total = self.total;
+        progress.bytes_read = self.bytes_read;
+        progress
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonReader;
+    use crate::processing::StatsProcessor;
+    use crate::GeozeroDatasource;
+
+    const GEOJSON: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"n": 1}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"n": 2}, "geometry": {"type": "Point", "coordinates": [2, 2]}},
+        {"type": "Feature", "properties": {"n": 3}, "geometry": {"type": "Point", "coordinates": [3, 3]}}
+    ]}"#;
+
+    #[test]
+    fn report_progress() -> Result<()> {
+        let mut reader = ProgressReader::new(GEOJSON.as_bytes());
+        let mut reports = Vec::new();
+        let mut progress = Progress::new(StatsProcessor::new(), |features, bytes, total| {
+            reports.push((features, bytes, total));
+            ControlFlow::Continue(())
+        });
+        progress.bytes_read = Some(reader.counter());
+        progress.total = Some(3);
+        GeoJsonReader(&mut reader).process(&mut progress)?;
+        assert_eq!(progress.into_inner().stats().features, 3);
+        let features: Vec<_> = reports.iter().map(|r| (r.0, r.2)).collect();
+        assert_eq!(features, [(1, Some(3)), (2, Some(3)), (3, Some(3))]);
+        assert_eq!(reports[2].1, GEOJSON.len() as u64);
+        Ok(())
+    }
+
+    #[test]
+    fn cancel() {
+        let mut progress = Progress::new(StatsProcessor::new(), |features, _, _| {
+            if features < 2 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        let mut input = GEOJSON.as_bytes();
+        let err = GeoJsonReader(&mut input)
+            .process(&mut progress)
+            .unwrap_err();
+        assert!(matches!(err.kind(), GeozeroError::Cancelled));
+        assert_eq!(progress.features(), 2);
+    }
+}
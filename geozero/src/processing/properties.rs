@@ -0,0 +1,360 @@
+use crate::error::{GeozeroError, Result};
+use crate::processing::record::OwnedValue;
+use crate::{
+    ColumnType, ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::convert::TryFrom;
+
+struct Column {
+    name: String,
+    rename: Option<String>,
+    cast: Option<ColumnType>,
+}
+
+/// Selection, renaming and type conversion of feature properties.
+///
+/// Only configured properties are forwarded, in input order and with consecutive
+/// property indices.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::PropertyMapper;
+/// use geozero::{geojson::{GeoJson, GeoJsonWriter}, ColumnType, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {"id": "7", "name": "A", "pop": 500},
+///     "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+/// let mut out = Vec::new();
+/// let mut mapper = PropertyMapper::new(GeoJsonWriter::new(&mut out))
+///     .cast("id", ColumnType::Int)
+///     .rename("name", "title");
+/// GeoJson(geojson).process(&mut mapper).unwrap();
+/// let json = std::str::from_utf8(&out).unwrap();
+/// assert!(json.contains(r#""properties": {"id": 7, "title": "A"}"#));
+/// ```
+pub struct PropertyMapper<P> {
+    processor: P,
+    columns: Vec<Column>,
+    /// Number of forwarded properties of current feature
+    count: usize,
+}
+
+impl<P> PropertyMapper<P> {
+    /// Property mapper without any properties to forward.
+    pub fn new(processor: P) -> Self {
+        PropertyMapper {
+            processor,
+            columns: Vec::new(),
+            count: 0,
+        }
+    }
+    fn column(&mut self, name: &str) -> &mut Column {
+        let pos = match self.columns.iter().position(|c| c.name == name) {
+            Some(pos) => pos,
+            None => {
+                self.columns.push(Column {
+                    name: name.to_string(),
+                    rename: None,
+                    cast: None,
+                });
+                self.columns.len() - 1
+            }
+        };
+        &mut self.columns[pos]
+    }
+    /// Forward property `name`.
+    pub fn keep(mut self, name: &str) -> Self {
+        self.column(name);
+        self
+    }
+    /// Forward property `name` as `new_name`.
+    pub fn rename(mut self, name: &str, new_name: &str) -> Self {
+        self.column(name).rename = Some(new_name.to_string());
+        self
+    }
+    /// Forward property `name` converted to `column_type`.
+    pub fn cast(mut self, name: &str, column_type: ColumnType) -> Self {
+        self.column(name).cast = Some(column_type);
+        self
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume mapper and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+fn int_value(value: &ColumnValue) -> Option<i128> {
+    match *value {
+        ColumnValue::Byte(v) => Some(v.into()),
+        ColumnValue::UByte(v) => Some(v.into()),
+        ColumnValue::Bool(v) => Some(v.into()),
+        ColumnValue::Short(v) => Some(v.into()),
+        ColumnValue::UShort(v) => Some(v.into()),
+        ColumnValue::Int(v) => Some(v.into()),
+        ColumnValue::UInt(v) => Some(v.into()),
+        ColumnValue::Long(v) => Some(v.into()),
+        ColumnValue::ULong(v) => Some(v.into()),
+        ColumnValue::Float(_) | ColumnValue::Double(_) => float_value(value)
+            .filter(|v| v.fract() == 0.0)
+            .map(|v| v as i128),
+        ColumnValue::String(v) => v.trim().parse().ok(),
+        ColumnValue::Json(_) | ColumnValue::DateTime(_) | ColumnValue::Binary(_) => None,
+    }
+}
+
+pub(crate) fn float_value(value: &ColumnValue) -> Option<f64> {
+    match *value {
+        ColumnValue::Float(v) => Some(v.into()),
+        ColumnValue::Double(v) => Some(v),
+        ColumnValue::String(v) => v.trim().parse().ok(),
+        ColumnValue::Json(_) | ColumnValue::DateTime(_) | ColumnValue::Binary(_) => None,
+        _ => int_value(value).map(|v| v as f64),
+    }
+}
+
+/// Convert `value` to `column_type`.
+fn cast(value: &ColumnValue, column_type: ColumnType) -> Result<OwnedValue> {
+    let int = || int_value(value);
+    let converted = match column_type {
+        ColumnType::Byte => int()
+            .and_then(|v| i8::try_from(v).ok())
+            .map(OwnedValue::Byte),
+        ColumnType::UByte => int()
+            .and_then(|v| u8::try_from(v).ok())
+            .map(OwnedValue::UByte),
+        ColumnType::Bool => match *value {
+            ColumnValue::String(v) => v.trim().parse().ok(),
+            _ => int().map(|v| v != 0),
+        }
+        .map(OwnedValue::Bool),
+        ColumnType::Short => int()
+            .and_then(|v| i16::try_from(v).ok())
+            .map(OwnedValue::Short),
+        ColumnType::UShort => int()
+            .and_then(|v| u16::try_from(v).ok())
+            .map(OwnedValue::UShort),
+        ColumnType::Int => int()
+            .and_then(|v| i32::try_from(v).ok())
+            .map(OwnedValue::Int),
+        ColumnType::UInt => int()
+            .and_then(|v| u32::try_from(v).ok())
+            .map(OwnedValue::UInt),
+        ColumnType::Long => int()
+            .and_then(|v| i64::try_from(v).ok())
+            .map(OwnedValue::Long),
+        ColumnType::ULong => int()
+            .and_then(|v| u64::try_from(v).ok())
+            .map(OwnedValue::ULong),
+        ColumnType::Float => float_value(value).map(|v| OwnedValue::Float(v as f32)),
+        ColumnType::Double => float_value(value).map(OwnedValue::Double),
+        ColumnType::String => match value {
+            ColumnValue::Binary(_) => None,
+            _ => Some(OwnedValue::String(value.to_string())),
+        },
+        ColumnType::Json => match value {
+            ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+                Some(OwnedValue::Json(format!("{:?}", v)))
+            }
+            ColumnValue::Binary(_) => None,
+            _ => Some(OwnedValue::Json(value.to_string())),
+        },
+        ColumnType::DateTime => match value {
+            ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+                Some(OwnedValue::DateTime(v.to_string()))
+            }
+            _ => None,
+        },
+        ColumnType::Binary => match value {
+            ColumnValue::Binary(v) => Some(OwnedValue::Binary(v.to_vec())),
+            ColumnValue::String(v) => Some(OwnedValue::Binary(v.as_bytes().to_vec())),
+            _ => None,
+        },
+    };
+    converted.ok_or_else(|| {
+        GeozeroError::ColumnType(format!("{:?}", column_type), format!("{:?}", value))
+    })
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for PropertyMapper<P> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let column = match self.columns.iter().find(|c| c.name == name) {
+            Some(column) => column,
+            None => return Ok(false),
+        };
+        let idx = self.count;
+        self.count += 1;
+        let name = column.rename.as_deref().unwrap_or(name);
+        match column.cast {
+            Some(column_type) if column_type != value.column_type() => {
+                let value = cast(value, column_type)?;
+                self.processor.property(idx, name, &value.as_column_value())
+            }
+            _ => self.processor.property(idx, name, value),
+        }
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for PropertyMapper<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    forward_events!(processor;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for PropertyMapper<P> {
+    fn properties_begin(&mut self) -> Result<()> {
+        self.count = 0;
+        self.processor.properties_begin()
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cast_values() -> Result<()> {
+        assert_eq!(
+            cast(&ColumnValue::String(" 42 "), ColumnType::Short)?,
+            OwnedValue::Short(42)
+        );
+        assert_eq!(
+            cast(&ColumnValue::Double(3.0), ColumnType::ULong)?,
+            OwnedValue::ULong(3)
+        );
+        assert_eq!(
+            cast(&ColumnValue::Int(3), ColumnType::Double)?,
+            OwnedValue::Double(3.0)
+        );
+        assert_eq!(
+            cast(&ColumnValue::Long(-1), ColumnType::Bool)?,
+            OwnedValue::Bool(true)
+        );
+        assert_eq!(
+            cast(&ColumnValue::Float(1.5), ColumnType::String)?,
+            OwnedValue::String("1.5".to_string())
+        );
+        assert_eq!(
+            cast(&ColumnValue::String("a"), ColumnType::Json)?,
+            OwnedValue::Json("\"a\"".to_string())
+        );
+        assert_eq!(
+            cast(&ColumnValue::Int(300), ColumnType::UByte)
+                .unwrap_err()
+                .to_string(),
+            "expected a `UByte` value but found `Int(300)`"
+        );
+        assert!(cast(&ColumnValue::Double(1.5), ColumnType::Int).is_err());
+        assert!(cast(&ColumnValue::String("x"), ColumnType::Double).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn mapping() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        #[derive(Default)]
+        struct Props(Vec<String>);
+        impl GeomProcessor for Props {}
+        impl PropertyProcessor for Props {
+            fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+                self.0.push(format!("{} {}={:?}", idx, name, value));
+                Ok(false)
+            }
+        }
+        impl FeatureProcessor for Props {}
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a", "pop": 500, "area": 1.5}, "geometry": null},
+            {"type": "Feature", "properties": {"pop": "50"}, "geometry": null}
+        ]}"#;
+        let mut mapper = PropertyMapper::new(Props::default())
+            .rename("name", "title")
+            .cast("pop", ColumnType::Int);
+        GeoJson(geojson).process(&mut mapper)?;
+        assert_eq!(
+            mapper.into_inner().0,
+            vec![r#"0 title=String("a")"#, "1 pop=Int(500)", "0 pop=Int(50)"]
+        );
+
+        let mut mapper = PropertyMapper::new(Props::default()).cast("name", ColumnType::Int);
+        assert!(GeoJson(geojson).process(&mut mapper).is_err());
+        Ok(())
+    }
+}
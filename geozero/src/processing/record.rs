@@ -0,0 +1,506 @@
+use crate::error::Result;
+use crate::{ColumnValue, DatasetMetadata, FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+/// Owned copy of a [ColumnValue].
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum OwnedValue {
+    Byte(i8),
+    UByte(u8),
+    Bool(bool),
+    Short(i16),
+    UShort(u16),
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Json(String),
+    DateTime(String),
+    Binary(Vec<u8>),
+}
+
+impl From<&ColumnValue<'_>> for OwnedValue {
+    fn from(value: &ColumnValue) -> Self {
+        match *value {
+            ColumnValue::Byte(v) => OwnedValue::Byte(v),
+            ColumnValue::UByte(v) => OwnedValue::UByte(v),
+            ColumnValue::Bool(v) => OwnedValue::Bool(v),
+            ColumnValue::Short(v) => OwnedValue::Short(v),
+            ColumnValue::UShort(v) => OwnedValue::UShort(v),
+            ColumnValue::Int(v) => OwnedValue::Int(v),
+            ColumnValue::UInt(v) => OwnedValue::UInt(v),
+            ColumnValue::Long(v) => OwnedValue::Long(v),
+            ColumnValue::ULong(v) => OwnedValue::ULong(v),
+            ColumnValue::Float(v) => OwnedValue::Float(v),
+            ColumnValue::Double(v) => OwnedValue::Double(v),
+            ColumnValue::String(v) => OwnedValue::String(v.to_string()),
+            ColumnValue::Json(v) => OwnedValue::Json(v.to_string()),
+            ColumnValue::DateTime(v) => OwnedValue::DateTime(v.to_string()),
+            ColumnValue::Binary(v) => OwnedValue::Binary(v.to_vec()),
+        }
+    }
+}
+
+impl OwnedValue {
+    pub fn as_column_value(&self) -> ColumnValue<'_> {
+        match self {
+            OwnedValue::Byte(v) => ColumnValue::Byte(*v),
+            OwnedValue::UByte(v) => ColumnValue::UByte(*v),
+            OwnedValue::Bool(v) => ColumnValue::Bool(*v),
+            OwnedValue::Short(v) => ColumnValue::Short(*v),
+            OwnedValue::UShort(v) => ColumnValue::UShort(*v),
+            OwnedValue::Int(v) => ColumnValue::Int(*v),
+            OwnedValue::UInt(v) => ColumnValue::UInt(*v),
+            OwnedValue::Long(v) => ColumnValue::Long(*v),
+            OwnedValue::ULong(v) => ColumnValue::ULong(*v),
+            OwnedValue::Float(v) => ColumnValue::Float(*v),
+            OwnedValue::Double(v) => ColumnValue::Double(*v),
+            OwnedValue::String(v) => ColumnValue::String(v),
+            OwnedValue::Json(v) => ColumnValue::Json(v),
+            OwnedValue::DateTime(v) => ColumnValue::DateTime(v),
+            OwnedValue::Binary(v) => ColumnValue::Binary(v),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum GeomType {
+    Point,
+    MultiPoint,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+    GeometryCollection,
+    CircularString,
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    Triangle,
+    PolyhedralSurface,
+    Tin,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Event {
+    FeatureBegin(u64),
+    FeatureEnd(u64),
+    PropertiesBegin,
+    PropertiesEnd,
+    Property(usize, String, OwnedValue),
+    GeometryBegin,
+    GeometryEnd,
+    NullGeometry,
+    GeometryColumnBegin(usize, String),
+    GeometryColumnEnd(usize, String),
+    ForeignMember(String, String),
+    DatasetSrid(Option<i32>),
+    DatasetMetadata(Box<DatasetMetadata>),
+    BatchBegin(usize),
+    BatchEnd,
+    Srid(Option<i32>),
+    Xy(f64, f64, usize),
+    Coordinate {
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    },
+    EmptyPoint(usize),
+    Begin {
+        geom_type: GeomType,
+        tagged: bool,
+        size: usize,
+        idx: usize,
+    },
+    End {
+        geom_type: GeomType,
+        tagged: bool,
+        idx: usize,
+    },
+}
+
+/// Recorder of feature events for later replay.
+#[derive(Default)]
+pub(crate) struct FeatureRecorder {
+    events: Vec<Event>,
+}
+
+impl FeatureRecorder {
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+    #[cfg(any(
+        feature = "with-csv",
+        feature = "with-sql",
+        feature = "with-postgis-copy"
+    ))]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+    /// Split recorded dataset into one recorder per feature.
+    ///
+    /// Events outside of features are dropped.
+    #[cfg(feature = "with-mvt")]
+    pub fn into_features(self) -> Vec<FeatureRecorder> {
+        let mut features = Vec::new();
+        let mut current: Option<Vec<Event>> = None;
+        for event in self.events {
+            match event {
+                Event::FeatureBegin(_) => current = Some(vec![event]),
+                Event::FeatureEnd(_) => {
+                    if let Some(mut events) = current.take() {
+                        events.push(event);
+                        features.push(FeatureRecorder { events });
+                    }
+                }
+                event => {
+                    if let Some(events) = current.as_mut() {
+                        events.push(event);
+                    }
+                }
+            }
+        }
+        features
+    }
+    fn begin(&mut self, geom_type: GeomType, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.events.push(Event::Begin {
+            geom_type,
+            tagged,
+            size,
+            idx,
+        });
+        Ok(())
+    }
+    fn end(&mut self, geom_type: GeomType, tagged: bool, idx: usize) -> Result<()> {
+        self.events.push(Event::End {
+            geom_type,
+            tagged,
+            idx,
+        });
+        Ok(())
+    }
+    /// Forward recorded events to `processor`.
+    ///
+    /// Additional geometry columns are skipped for processors without support
+    /// for multiple geometries.
+    pub fn replay<P: FeatureProcessor>(&self, processor: &mut P) -> Result<()> {
+        let mut abort = false;
+        let multiple_geometries = processor.multiple_geometries();
+        let mut skip = false;
+        for event in &self.events {
+            if !multiple_geometries {
+                match event {
+                    Event::GeometryColumnBegin(..) => {
+                        skip = true;
+                        continue;
+                    }
+                    Event::GeometryColumnEnd(..) => {
+                        skip = false;
+                        continue;
+                    }
+                    _ if skip => continue,
+                    _ => {}
+                }
+            }
+            match event {
+                Event::FeatureBegin(idx) => processor.feature_begin(*idx)?,
+                Event::FeatureEnd(idx) => processor.feature_end(*idx)?,
+                Event::PropertiesBegin => {
+                    abort = false;
+                    processor.properties_begin()?
+                }
+                Event::PropertiesEnd => processor.properties_end()?,
+                Event::Property(idx, name, value) => {
+                    if !abort {
+                        abort = processor.property(*idx, name, &value.as_column_value())?;
+                    }
+                }
+                Event::GeometryBegin => processor.geometry_begin()?,
+                Event::GeometryEnd => processor.geometry_end()?,
+                Event::NullGeometry => processor.null_geometry()?,
+                Event::GeometryColumnBegin(idx, name) => {
+                    processor.geometry_column_begin(*idx, name)?
+                }
+                Event::GeometryColumnEnd(idx, name) => processor.geometry_column_end(*idx, name)?,
+                Event::ForeignMember(name, value) => processor.foreign_member(name, value)?,
+                Event::DatasetSrid(srid) => processor.dataset_srid(*srid)?,
+                Event::DatasetMetadata(metadata) => processor.dataset_metadata(metadata)?,
+                Event::BatchBegin(size) => processor.batch_begin(*size)?,
+                Event::BatchEnd => processor.batch_end()?,
+                Event::Srid(srid) => processor.srid(*srid)?,
+                Event::Xy(x, y, idx) => processor.xy(*x, *y, *idx)?,
+                Event::Coordinate {
+                    x,
+                    y,
+                    z,
+                    m,
+                    t,
+                    tm,
+                    idx,
+                } => processor.coordinate(*x, *y, *z, *m, *t, *tm, *idx)?,
+                Event::EmptyPoint(idx) => processor.empty_point(*idx)?,
+                Event::Begin {
+                    geom_type,
+                    tagged,
+                    size,
+                    idx,
+                } => {
+                    let (tagged, size, idx) = (*tagged, *size, *idx);
+                    match geom_type {
+                        GeomType::Point => processor.point_begin(idx)?,
+                        GeomType::MultiPoint => processor.multipoint_begin(size, idx)?,
+                        GeomType::LineString => processor.linestring_begin(tagged, size, idx)?,
+                        GeomType::MultiLineString => processor.multilinestring_begin(size, idx)?,
+                        GeomType::Polygon => processor.polygon_begin(tagged, size, idx)?,
+                        GeomType::MultiPolygon => processor.multipolygon_begin(size, idx)?,
+                        GeomType::GeometryCollection => {
+                            processor.geometrycollection_begin(size, idx)?
+                        }
+                        GeomType::CircularString => processor.circularstring_begin(size, idx)?,
+                        GeomType::CompoundCurve => processor.compoundcurve_begin(size, idx)?,
+                        GeomType::CurvePolygon => processor.curvepolygon_begin(size, idx)?,
+                        GeomType::MultiCurve => processor.multicurve_begin(size, idx)?,
+                        GeomType::MultiSurface => processor.multisurface_begin(size, idx)?,
+                        GeomType::Triangle => processor.triangle_begin(tagged, size, idx)?,
+                        GeomType::PolyhedralSurface => {
+                            processor.polyhedralsurface_begin(size, idx)?
+                        }
+                        GeomType::Tin => processor.tin_begin(size, idx)?,
+                    }
+                }
+                Event::End {
+                    geom_type,
+                    tagged,
+                    idx,
+                } => {
+                    let (tagged, idx) = (*tagged, *idx);
+                    match geom_type {
+                        GeomType::Point => processor.point_end(idx)?,
+                        GeomType::MultiPoint => processor.multipoint_end(idx)?,
+                        GeomType::LineString => processor.linestring_end(tagged, idx)?,
+                        GeomType::MultiLineString => processor.multilinestring_end(idx)?,
+                        GeomType::Polygon => processor.polygon_end(tagged, idx)?,
+                        GeomType::MultiPolygon => processor.multipolygon_end(idx)?,
+                        GeomType::GeometryCollection => processor.geometrycollection_end(idx)?,
+                        GeomType::CircularString => processor.circularstring_end(idx)?,
+                        GeomType::CompoundCurve => processor.compoundcurve_end(idx)?,
+                        GeomType::CurvePolygon => processor.curvepolygon_end(idx)?,
+                        GeomType::MultiCurve => processor.multicurve_end(idx)?,
+                        GeomType::MultiSurface => processor.multisurface_end(idx)?,
+                        GeomType::Triangle => processor.triangle_end(tagged, idx)?,
+                        GeomType::PolyhedralSurface => processor.polyhedralsurface_end(idx)?,
+                        GeomType::Tin => processor.tin_end(idx)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GeomProcessor for FeatureRecorder {
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.events.push(Event::Srid(srid));
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.events.push(Event::Xy(x, y, idx));
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.events.push(Event::Coordinate {
+            x,
+            y,
+            z,
+            m,
+            t,
+            tm,
+            idx,
+        });
+        Ok(())
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.events.push(Event::EmptyPoint(idx));
+        Ok(())
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(GeomType::Point, true, 1, idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::Point, true, idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::MultiPoint, true, size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::MultiPoint, true, idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::LineString, tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(GeomType::LineString, tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::MultiLineString, true, size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::MultiLineString, true, idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::Polygon, tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(GeomType::Polygon, tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::MultiPolygon, true, size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::MultiPolygon, true, idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::GeometryCollection, true, size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::GeometryCollection, true, idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::CircularString, true, size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::CircularString, true, idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::CompoundCurve, true, size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::CompoundCurve, true, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::CurvePolygon, true, size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::CurvePolygon, true, idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::MultiCurve, true, size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::MultiCurve, true, idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::MultiSurface, true, size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::MultiSurface, true, idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::Triangle, tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(GeomType::Triangle, tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::PolyhedralSurface, true, size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::PolyhedralSurface, true, idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(GeomType::Tin, true, size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end(GeomType::Tin, true, idx)
+    }
+}
+
+impl PropertyProcessor for FeatureRecorder {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.events
+            .push(Event::Property(idx, name.to_string(), value.into()));
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for FeatureRecorder {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.events.push(Event::FeatureBegin(idx));
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.events.push(Event::FeatureEnd(idx));
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.events.push(Event::PropertiesBegin);
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.events.push(Event::PropertiesEnd);
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.events.push(Event::GeometryBegin);
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.events.push(Event::GeometryEnd);
+        Ok(())
+    }
+    fn null_geometry(&mut self) -> Result<()> {
+        self.events.push(Event::NullGeometry);
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        true
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.events
+            .push(Event::GeometryColumnBegin(idx, name.to_string()));
+        Ok(())
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.events
+            .push(Event::GeometryColumnEnd(idx, name.to_string()));
+        Ok(())
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        self.events
+            .push(Event::ForeignMember(name.to_string(), value.to_string()));
+        Ok(())
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.events.push(Event::DatasetSrid(srid));
+        Ok(())
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.events
+            .push(Event::DatasetMetadata(Box::new(metadata.clone())));
+        Ok(())
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.events.push(Event::BatchBegin(size));
+        Ok(())
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.events.push(Event::BatchEnd);
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+use crate::error::Result;
+use crate::processing::ProcessorLayer;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Selection of sampled features.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sampling {
+    /// Random fraction of features between 0.0 and 1.0
+    Fraction(f64),
+    /// Every nth feature, starting with the first one
+    EveryNth(u64),
+}
+
+/// Forwarding of a sample of features, e.g. for previewing huge datasets.
+///
+/// Events of features not in the sample are dropped and the indices of sampled
+/// features are renumbered from 0. Random sampling is reproducible for the same seed.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{Sample, StatsProcessor};
+/// use geozero::{geojson::GeoJson, GeozeroDatasource};
+///
+/// let geojson = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [2, 2]}},
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 3]}}
+/// ]}"#;
+/// let mut sample = Sample::every_nth(2, StatsProcessor::new());
+/// GeoJson(geojson).process(&mut sample).unwrap();
+/// assert_eq!(sample.into_inner().stats().features, 2);
+/// ```
+pub struct Sample<P> {
+    sampling: Sampling,
+    processor: P,
+    seed: u64,
+    rng: u64,
+    /// Number of features read
+    count: u64,
+    /// Number of features forwarded
+    sampled: u64,
+    /// Current feature is sampled or no feature is processed
+    active: bool,
+}
+
+impl<P> Sample<P> {
+    /// Forward features selected by `sampling` to `processor`.
+    pub fn new(sampling: Sampling, processor: P) -> Self {
+        Sample {
+            sampling,
+            processor,
+            seed: 0,
+            rng: 0,
+            count: 0,
+            sampled: 0,
+            active: true,
+        }
+    }
+    /// Forward a random fraction `p` of features.
+    pub fn fraction(p: f64, processor: P) -> Self {
+        Sample::new(Sampling::Fraction(p), processor)
+    }
+    /// Forward every `n`th feature.
+    pub fn every_nth(n: u64, processor: P) -> Self {
+        Sample::new(Sampling::EveryNth(n), processor)
+    }
+    /// Seed for random sampling (default: 0).
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = seed;
+        self
+    }
+    /// Number of features forwarded.
+    pub fn sampled(&self) -> u64 {
+        self.sampled
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume sampler and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    // SplitMix64
+    fn next_random(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+    fn select(&mut self) -> bool {
+        match self.sampling {
+            Sampling::Fraction(p) => self.next_random() < p,
+            Sampling::EveryNth(n) => n > 0 && self.count.is_multiple_of(n),
+        }
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for Sample<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    forward_events!(processor if active;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        coordinate(x: f64, y: f64, z: Option<f64>, m: Option<f64>, t: Option<f64>, tm: Option<u64>, idx: usize);
+        coords(coords: &[f64], dims: CoordDimensions, idx: usize);
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize);
+        linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize);
+        polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize);
+        curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize);
+        triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize);
+        polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize);
+        tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Sample<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.active {
+            self.processor.property(idx, name, value)
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Sample<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.rng = self.seed;
+        self.count = 0;
+        self.sampled = 0;
+        self.active = true;
+        self.processor.dataset_begin(name)
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.active = self.select();
+        self.count += 1;
+        if self.active {
+            self.processor.feature_begin(self.sampled)
+        } else {
+            Ok(())
+        }
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if self.active {
+            self.processor.feature_end(self.sampled)?;
+            self.sampled += 1;
+        }
+        self.active = true;
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        batch_begin(size: usize);
+        batch_end();
+    );
+    forward_events!(processor if active;
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+/// [Sample] stage for [Pipeline](crate::processing::Pipeline).
+pub struct SampleLayer {
+    pub sampling: Sampling,
+    pub seed: u64,
+}
+
+impl<P> ProcessorLayer<P> for SampleLayer {
+    type Processor = Sample<P>;
+    fn wrap(self, processor: P) -> Sample<P> {
+        Sample::new(self.sampling, processor).with_seed(self.seed)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::processing::Pipeline;
+    use crate::GeozeroDatasource;
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl GeomProcessor for Events {
+        fn xy(&mut self, x: f64, _y: f64, _idx: usize) -> Result<()> {
+            self.0.push(format!("x={}", x));
+            Ok(())
+        }
+    }
+    impl PropertyProcessor for Events {}
+    impl FeatureProcessor for Events {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(format!("f{}", idx));
+            Ok(())
+        }
+    }
+
+    fn points(count: usize) -> String {
+        let features = (0..count)
+            .map(|i| format!(r#"{{"type": "Feature", "properties": {{}}, "geometry": {{"type": "Point", "coordinates": [{}, 1]}}}}"#, i))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            features
+        )
+    }
+
+    #[test]
+    fn every_nth() -> Result<()> {
+        let mut sample = Sample::every_nth(3, Events::default());
+        GeoJson(&points(7)).process(&mut sample)?;
+        assert_eq!(sample.sampled(), 3);
+        assert_eq!(sample.into_inner().0.join(" "), "f0 x=0 f1 x=3 f2 x=6");
+        Ok(())
+    }
+
+    #[test]
+    fn fraction() -> Result<()> {
+        let json = points(1000);
+        let mut sample = Pipeline::new()
+            .layer(SampleLayer {
+                sampling: Sampling::Fraction(0.1),
+                seed: 42,
+            })
+            .build(Events::default());
+        GeoJson(&json).process(&mut sample)?;
+        let sampled = sample.sampled();
+        assert!(sampled > 50 && sampled < 150);
+        let events = sample.into_inner().0;
+        assert_eq!(events.len() as u64, 2 * sampled);
+
+        // reproducible with the same seed
+        let mut again = Sample::fraction(0.1, Events::default()).with_seed(42);
+        GeoJson(&json).process(&mut again)?;
+        assert_eq!(again.into_inner().0, events);
+
+        let mut all = Sample::fraction(1.0, Events::default());
+        GeoJson(&json).process(&mut all)?;
+        assert_eq!(all.sampled(), 1000);
+        Ok(())
+    }
+}
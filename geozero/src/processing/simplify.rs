@@ -0,0 +1,359 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+struct Line {
+    tagged: bool,
+    idx: usize,
+    coords: Vec<Coord>,
+}
+
+/// Line simplification with the Douglas-Peucker algorithm.
+///
+/// LineStrings and polygon rings are simplified before forwarding to the inner processor.
+/// Rings are kept unchanged if they would collapse to less than 4 coordinates.
+/// Triangles, PolyhedralSurfaces and TINs are not simplified.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::Simplify;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut simplify = Simplify::new(1.0, WktWriter::new(&mut wkt));
+/// WktStr("LINESTRING(0 0,1 0.1,2 0)").process_geom(&mut simplify).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(0 0,2 0)");
+/// ```
+pub struct Simplify<P> {
+    tolerance: f64,
+    processor: P,
+    line: Option<Line>,
+    /// Nesting level of polygons
+    polygons: usize,
+    /// Nesting level of geometries which are not simplified
+    unchanged: usize,
+}
+
+impl<P> Simplify<P> {
+    /// Simplify with maximal distance `tolerance` and forward to `processor`.
+    pub fn new(tolerance: f64, processor: P) -> Self {
+        Simplify {
+            tolerance,
+            processor,
+            line: None,
+            polygons: 0,
+            unchanged: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume simplifier and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+impl<P: GeomProcessor> Simplify<P> {
+    fn emit_line(&mut self, line: Line) -> Result<()> {
+        let min_size = if self.polygons > 0 && !line.tagged {
+            4
+        } else {
+            2
+        };
+        let keep = douglas_peucker(&line.coords, self.tolerance);
+        let count = keep.iter().filter(|k| **k).count();
+        let coords: Vec<&Coord> = if count >= min_size {
+            line.coords
+                .iter()
+                .zip(keep)
+                .filter_map(|(c, k)| if k { Some(c) } else { None })
+                .collect()
+        } else {
+            line.coords.iter().collect()
+        };
+        self.processor
+            .linestring_begin(line.tagged, coords.len(), line.idx)?;
+        for (idx, c) in coords.into_iter().enumerate() {
+            c.process(&mut self.processor, idx)?;
+        }
+        self.processor.linestring_end(line.tagged, line.idx)
+    }
+}
+
+/// Squared distance of `p` to segment `a`-`b`.
+fn segment_distance2(p: &Coord, a: &Coord, b: &Coord) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let (x, y) = if dx == 0.0 && dy == 0.0 {
+        (a.x, a.y)
+    } else {
+        let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+        (a.x + t * dx, a.y + t * dy)
+    };
+    (p.x - x).powi(2) + (p.y - y).powi(2)
+}
+
+/// Douglas-Peucker simplification returning the coordinates to keep.
+fn douglas_peucker(coords: &[Coord], tolerance: f64) -> Vec<bool> {
+    let n = coords.len();
+    if n < 3 {
+        return vec![true; n];
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+    let tolerance2 = tolerance * tolerance;
+    let mut stack = vec![(0, n - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max = 0.0;
+        let mut max_idx = start;
+        for (i, coord) in coords.iter().enumerate().take(end).skip(start + 1) {
+            let dist = segment_distance2(coord, &coords[start], &coords[end]);
+            if dist > max {
+                max = dist;
+                max_idx = i;
+            }
+        }
+        if max > tolerance2 {
+            keep[max_idx] = true;
+            stack.push((start, max_idx));
+            stack.push((max_idx, end));
+        }
+    }
+    keep
+}
+
+impl<P: GeomProcessor> GeomProcessor for Simplify<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord::xy(x, y));
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord { x, y, z, m, t, tm });
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.unchanged > 0 {
+            return self.processor.linestring_begin(tagged, size, idx);
+        }
+        self.line = Some(Line {
+            tagged,
+            idx,
+            coords: Vec::with_capacity(size),
+        });
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        match self.line.take() {
+            Some(line) => self.emit_line(line),
+            None => self.processor.linestring_end(tagged, idx),
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.curvepolygon_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.tin_end(idx)
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Simplify<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Simplify<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [Simplify] stage for [Pipeline](crate::processing::Pipeline).
+pub struct SimplifyLayer(pub f64);
+
+impl<P> ProcessorLayer<P> for SimplifyLayer {
+    type Processor = Simplify<P>;
+    fn wrap(self, processor: P) -> Simplify<P> {
+        Simplify::new(self.0, processor)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn simplify(wkt: &str, tolerance: f64) -> Result<String> {
+        simplify_geom(&WktStr(wkt), tolerance)
+    }
+
+    fn simplify_geom<G: GeozeroGeometry>(geom: &G, tolerance: f64) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut simplify = Pipeline::new()
+            .layer(SimplifyLayer(tolerance))
+            .build(writer);
+        geom.process_geom(&mut simplify)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn linestring() -> Result<()> {
+        assert_eq!(
+            simplify("LINESTRING(0 0,1 0.1,2 -0.1,3 5,4 6,5 7)", 1.0)?,
+            "LINESTRING(0 0,2 -0.1,3 5,5 7)"
+        );
+        assert_eq!(
+            simplify("LINESTRING(0 0,1 0.1,2 -0.1,3 5,4 6,5 7)", 0.0)?,
+            "LINESTRING(0 0,1 0.1,2 -0.1,3 5,5 7)"
+        );
+        assert_eq!(
+            simplify("MULTILINESTRING((0 0,1 0.1,2 0),(5 5,6 6))", 1.0)?,
+            "MULTILINESTRING((0 0,2 0),(5 5,6 6))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn linestring_z() -> Result<()> {
+        use crate::geojson::GeoJson;
+
+        let geojson = GeoJson(
+            r#"{"type": "LineString", "coordinates": [[0, 0, 1], [1, 0.1, 2], [2, -0.1, 3], [3, 5, 4], [4, 6, 5], [5, 7, 6]]}"#,
+        );
+        assert_eq!(
+            simplify_geom(&geojson, 1.0)?,
+            "LINESTRING(0 0 1,2 -0.1 3,3 5 4,5 7 6)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn polygon() -> Result<()> {
+        assert_eq!(
+            simplify(
+                "POLYGON((0 0,5 0.2,10 0,10 10,5 9.9,0 10,0 0),(0 0,1 0.1,2 0,1 -0.1,0 0))",
+                1.0
+            )?,
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(0 0,1 0.1,2 0,1 -0.1,0 0))"
+        );
+        assert_eq!(
+            simplify(
+                "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 0.1,2 0))",
+                1.0
+            )?,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,2 0))"
+        );
+        Ok(())
+    }
+}
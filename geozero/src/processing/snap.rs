@@ -0,0 +1,343 @@
+use crate::error::Result;
+use crate::processing::{Coord, ProcessorLayer};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+struct Line {
+    tagged: bool,
+    idx: usize,
+    coords: Vec<Coord>,
+}
+
+/// Quantization of x and y coordinates to a regular grid.
+///
+/// Coordinates are rounded to the nearest multiple of the grid resolution.
+/// With `remove_duplicates`, consecutive coordinates of LineStrings and polygon rings
+/// snapping to the same grid cell are merged. Lines are kept with duplicates if they would
+/// collapse to less than 2 coordinates, rings to less than 4 coordinates.
+/// Triangles, PolyhedralSurfaces and TINs are snapped without removing duplicates.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::SnapToGrid;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut snap = SnapToGrid::new(1.0, WktWriter::new(&mut wkt));
+/// snap.remove_duplicates = true;
+/// WktStr("LINESTRING(0.1 0.2,0.3 -0.1,2.6 1.4)").process_geom(&mut snap).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(0 0,3 1)");
+/// ```
+pub struct SnapToGrid<P> {
+    resolution: f64,
+    /// Merge consecutive coordinates snapping to the same grid cell
+    pub remove_duplicates: bool,
+    processor: P,
+    line: Option<Line>,
+    /// Nesting level of polygons
+    polygons: usize,
+    /// Nesting level of geometries without duplicate removal
+    unchanged: usize,
+}
+
+impl<P> SnapToGrid<P> {
+    /// Snap to grid with cell size `resolution` and forward to `processor`.
+    pub fn new(resolution: f64, processor: P) -> Self {
+        SnapToGrid {
+            resolution,
+            remove_duplicates: false,
+            processor,
+            line: None,
+            polygons: 0,
+            unchanged: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume snapper and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn snap(&self, v: f64) -> f64 {
+        if self.resolution > 0.0 {
+            // adding 0.0 avoids negative zero
+            (v / self.resolution).round() * self.resolution + 0.0
+        } else {
+            v
+        }
+    }
+}
+
+impl<P: GeomProcessor> SnapToGrid<P> {
+    fn emit_line(&mut self, line: Line) -> Result<()> {
+        let min_size = if self.polygons > 0 && !line.tagged {
+            4
+        } else {
+            2
+        };
+        let mut coords: Vec<&Coord> = Vec::with_capacity(line.coords.len());
+        for c in &line.coords {
+            if coords
+                .last()
+                .is_none_or(|last| (last.x, last.y) != (c.x, c.y))
+            {
+                coords.push(c);
+            }
+        }
+        if coords.len() < min_size {
+            coords = line.coords.iter().collect();
+        }
+        self.processor
+            .linestring_begin(line.tagged, coords.len(), line.idx)?;
+        for (idx, c) in coords.into_iter().enumerate() {
+            c.process(&mut self.processor, idx)?;
+        }
+        self.processor.linestring_end(line.tagged, line.idx)
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for SnapToGrid<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        let (x, y) = (self.snap(x), self.snap(y));
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord::xy(x, y));
+            Ok(())
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let (x, y) = (self.snap(x), self.snap(y));
+        if let Some(line) = self.line.as_mut() {
+            line.coords.push(Coord { x, y, z, m, t, tm });
+            Ok(())
+        } else {
+            self.processor.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if !self.remove_duplicates || self.unchanged > 0 {
+            return self.processor.linestring_begin(tagged, size, idx);
+        }
+        self.line = Some(Line {
+            tagged,
+            idx,
+            coords: Vec::with_capacity(size),
+        });
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        match self.line.take() {
+            Some(line) => self.emit_line(line),
+            None => self.processor.linestring_end(tagged, idx),
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.polygons += 1;
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.polygons -= 1;
+        self.processor.curvepolygon_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.unchanged += 1;
+        self.processor.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.unchanged -= 1;
+        self.processor.tin_end(idx)
+    }
+    forward_events!(processor;
+        empty_point(idx: usize);
+        point_begin(idx: usize);
+        point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize);
+        multipoint_end(idx: usize);
+        multilinestring_begin(size: usize, idx: usize);
+        multilinestring_end(idx: usize);
+        multipolygon_begin(size: usize, idx: usize);
+        multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize);
+        geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize);
+        circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize);
+        compoundcurve_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize);
+        multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize);
+        multisurface_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for SnapToGrid<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for SnapToGrid<P> {
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        feature_begin(idx: u64);
+        feature_end(idx: u64);
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [SnapToGrid] stage for [Pipeline](crate::processing::Pipeline).
+pub struct SnapToGridLayer {
+    pub resolution: f64,
+    pub remove_duplicates: bool,
+}
+
+impl<P> ProcessorLayer<P> for SnapToGridLayer {
+    type Processor = SnapToGrid<P>;
+    fn wrap(self, processor: P) -> SnapToGrid<P> {
+        let mut snap = SnapToGrid::new(self.resolution, processor);
+        snap.remove_duplicates = self.remove_duplicates;
+        snap
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::Pipeline;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn snap(wkt: &str, resolution: f64, remove_duplicates: bool) -> Result<String> {
+        snap_geom(&WktStr(wkt), resolution, remove_duplicates)
+    }
+
+    fn snap_geom<G: GeozeroGeometry>(
+        geom: &G,
+        resolution: f64,
+        remove_duplicates: bool,
+    ) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.dims = CoordDimensions::xyz();
+        let mut snap = Pipeline::new()
+            .layer(SnapToGridLayer {
+                resolution,
+                remove_duplicates,
+            })
+            .build(writer);
+        geom.process_geom(&mut snap)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn coordinates() -> Result<()> {
+        assert_eq!(
+            snap("LINESTRING(0 0,0.1 0.1,2 2)", 1.0, false)?,
+            "LINESTRING(0 0,0 0,2 2)"
+        );
+        assert_eq!(
+            snap("MULTIPOINT(0 0,0.1 0.1)", 1.0, true)?,
+            "MULTIPOINT(0 0,0 0)"
+        );
+        assert_eq!(snap("POINT(1.26 3.74)", 0.0, false)?, "POINT(1.26 3.74)");
+        assert_eq!(snap("POINT(-0.1 0.1)", 1.0, false)?, "POINT(0 0)");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn coordinates_z() -> Result<()> {
+        use crate::geojson::GeoJson;
+
+        let geojson = GeoJson(r#"{"type": "Point", "coordinates": [1.26, -3.74, 1.26]}"#);
+        assert_eq!(snap_geom(&geojson, 0.5, false)?, "POINT(1.5 -3.5 1.26)");
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates() -> Result<()> {
+        assert_eq!(
+            snap("LINESTRING(0 0,0.1 0.1,2 2,2.2 1.9)", 1.0, true)?,
+            "LINESTRING(0 0,2 2)"
+        );
+        // collapsed line
+        assert_eq!(
+            snap("LINESTRING(0 0,0.1 0.1)", 1.0, true)?,
+            "LINESTRING(0 0,0 0)"
+        );
+        assert_eq!(
+            snap("POLYGON((0 0,10 0,10.1 0.1,10 10,0 10,0 0))", 1.0, true)?,
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))"
+        );
+        // collapsed ring
+        assert_eq!(
+            snap("POLYGON((0 0,0.2 0,0.2 0.2,0 0))", 1.0, true)?,
+            "POLYGON((0 0,0 0,0 0,0 0))"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,357 @@
+use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor, GeometryType,
+    GeozeroDatasource, PropertyProcessor,
+};
+use std::collections::BTreeMap;
+
+/// Criterion for splitting a dataset.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SplitBy {
+    /// Value of a property, formatted as string
+    Property(String),
+    /// Geometry type, e.g. `Point` or `MultiPolygon`
+    GeometryType,
+}
+
+/// Features of a split dataset with the same key.
+///
+/// Feature indices are numbered from 0 and the dataset is named by the key.
+pub struct SplitDataset {
+    key: String,
+    srid: Option<i32>,
+    metadata: Option<DatasetMetadata>,
+    features: FeatureRecorder,
+    count: u64,
+}
+
+impl SplitDataset {
+    /// Key of all features.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+    /// Number of features.
+    pub fn feature_count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl GeozeroDatasource for SplitDataset {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        processor.dataset_begin(Some(&self.key))?;
+        if self.srid.is_some() {
+            processor.dataset_srid(self.srid)?;
+        }
+        if let Some(metadata) = &self.metadata {
+            processor.dataset_metadata(metadata)?;
+        }
+        self.features.replay(processor)?;
+        processor.dataset_end()
+    }
+}
+
+/// Demultiplexing of features into datasets by a property value or geometry type.
+///
+/// Features are buffered per key. At the end of the dataset, `write` is called for each
+/// key in sorted order with a [SplitDataset], which creates an output and a writer,
+/// processes the dataset and finishes the output before the next one is opened.
+/// Features without the split property or without geometry get an empty key.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::processing::{SplitBy, Splitter};
+/// use geozero::GeozeroDatasource;
+///
+/// let geojson = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"region": "north"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {"region": "south"}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+/// ]}"#;
+/// let mut splitter = Splitter::new(SplitBy::Property("region".to_string()), |key, dataset| {
+///     let mut file = std::io::sink(); // e.g. File::create(format!("{}.geojson", key))?
+///     dataset.process(&mut GeoJsonWriter::new(&mut file))
+/// });
+/// GeoJson(geojson).process(&mut splitter).unwrap();
+/// ```
+pub struct Splitter<F> {
+    split_by: SplitBy,
+    write: F,
+    dims: CoordDimensions,
+    srid: Option<i32>,
+    metadata: Option<DatasetMetadata>,
+    /// Events of the current feature
+    feature: FeatureRecorder,
+    /// Key of the current feature
+    value: Option<String>,
+    geometry_type: Option<GeometryType>,
+    /// Geometry nesting level
+    depth: usize,
+    datasets: BTreeMap<String, SplitDataset>,
+}
+
+impl<F> Splitter<F>
+where
+    F: FnMut(&str, &mut SplitDataset) -> Result<()>,
+{
+    /// Split features by `split_by` and pass the datasets to `write`.
+    pub fn new(split_by: SplitBy, write: F) -> Self {
+        Splitter {
+            split_by,
+            write,
+            dims: CoordDimensions::xy(),
+            srid: None,
+            metadata: None,
+            feature: FeatureRecorder::default(),
+            value: None,
+            geometry_type: None,
+            depth: 0,
+            datasets: BTreeMap::new(),
+        }
+    }
+    /// Request coordinate dimensions from the reader (default: XY).
+    pub fn with_dimensions(mut self, dims: CoordDimensions) -> Self {
+        self.dims = dims;
+        self
+    }
+    fn key(&mut self) -> String {
+        match self.split_by {
+            SplitBy::Property(_) => self.value.take().unwrap_or_default(),
+            SplitBy::GeometryType => self
+                .geometry_type
+                .take()
+                .map(|t| format!("{:?}", t))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Record geometry begin event and remember the type of the outermost geometry.
+///
+/// The outermost geometry is recorded with index 0, since readers may pass the
+/// feature index, which doesn't match the renumbered features of the split datasets.
+macro_rules! begin_events {
+    ($($name:ident($($arg:ident: $ty:ty),*) => $geometry_type:ident;)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty,)* idx: usize) -> Result<()> {
+                self.geometry_type.get_or_insert(GeometryType::$geometry_type);
+                let idx = if self.depth == 0 { 0 } else { idx };
+                self.depth += 1;
+                self.feature.$name($($arg,)* idx)
+            }
+        )*
+    };
+}
+
+/// Record geometry end event with the index of the corresponding begin event.
+macro_rules! end_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty,)* idx: usize) -> Result<()> {
+                self.depth = self.depth.saturating_sub(1);
+                let idx = if self.depth == 0 { 0 } else { idx };
+                self.feature.$name($($arg,)* idx)
+            }
+        )*
+    };
+}
+
+impl<F> GeomProcessor for Splitter<F>
+where
+    F: FnMut(&str, &mut SplitDataset) -> Result<()>,
+{
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geometry_type.get_or_insert(GeometryType::Point);
+        let idx = if self.depth == 0 { 0 } else { idx };
+        self.feature.empty_point(idx)
+    }
+    begin_events!(
+        point_begin() => Point;
+        multipoint_begin(size: usize) => MultiPoint;
+        linestring_begin(tagged: bool, size: usize) => LineString;
+        multilinestring_begin(size: usize) => MultiLineString;
+        polygon_begin(tagged: bool, size: usize) => Polygon;
+        multipolygon_begin(size: usize) => MultiPolygon;
+        geometrycollection_begin(size: usize) => GeometryCollection;
+        circularstring_begin(size: usize) => CircularString;
+        compoundcurve_begin(size: usize) => CompoundCurve;
+        curvepolygon_begin(size: usize) => CurvePolygon;
+        multicurve_begin(size: usize) => MultiCurve;
+        multisurface_begin(size: usize) => MultiSurface;
+        triangle_begin(tagged: bool, size: usize) => Triangle;
+        polyhedralsurface_begin(size: usize) => PolyhedralSurface;
+        tin_begin(size: usize) => Tin;
+    );
+    end_events!(
+        point_end();
+        multipoint_end();
+        linestring_end(tagged: bool);
+        multilinestring_end();
+        polygon_end(tagged: bool);
+        multipolygon_end();
+        geometrycollection_end();
+        circularstring_end();
+        compoundcurve_end();
+        curvepolygon_end();
+        multicurve_end();
+        multisurface_end();
+        triangle_end(tagged: bool);
+        polyhedralsurface_end();
+        tin_end();
+    );
+    forward_events!(feature;
+        srid(srid: Option<i32>);
+        xy(x: f64, y: f64, idx: usize);
+        coordinate(x: f64, y: f64, z: Option<f64>, m: Option<f64>, t: Option<f64>, tm: Option<u64>, idx: usize);
+    );
+}
+
+impl<F> PropertyProcessor for Splitter<F>
+where
+    F: FnMut(&str, &mut SplitDataset) -> Result<()>,
+{
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let SplitBy::Property(column) = &self.split_by {
+            if column == name {
+                self.value = Some(value.to_string());
+            }
+        }
+        self.feature.property(idx, name, value)
+    }
+}
+
+impl<F> FeatureProcessor for Splitter<F>
+where
+    F: FnMut(&str, &mut SplitDataset) -> Result<()>,
+{
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        self.srid = None;
+        self.metadata = None;
+        self.datasets.clear();
+        Ok(())
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.srid = srid;
+        Ok(())
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.metadata = Some(metadata.clone());
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        let datasets = std::mem::take(&mut self.datasets);
+        for (key, mut dataset) in datasets {
+            (self.write)(&key, &mut dataset)?;
+        }
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature.clear();
+        self.value = None;
+        self.geometry_type = None;
+        self.depth = 0;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let key = self.key();
+        let srid = self.srid;
+        let metadata = &self.metadata;
+        let dataset = self
+            .datasets
+            .entry(key.clone())
+            .or_insert_with(|| SplitDataset {
+                key,
+                srid,
+                metadata: metadata.clone(),
+                features: FeatureRecorder::default(),
+                count: 0,
+            });
+        dataset.features.feature_begin(dataset.count)?;
+        self.feature.replay(&mut dataset.features)?;
+        dataset.features.feature_end(dataset.count)?;
+        dataset.count += 1;
+        Ok(())
+    }
+    // Additional geometry columns are dropped on replay for writers without support
+    fn multiple_geometries(&self) -> bool {
+        true
+    }
+    // Batches are not meaningful for the split datasets
+    fn batch_begin(&mut self, _size: usize) -> Result<()> {
+        Ok(())
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    forward_events!(feature;
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+    );
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+
+    const REGIONS: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"region": "north"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"region": "south"}, "geometry": {"type": "MultiPoint", "coordinates": [[2, 2]]}},
+        {"type": "Feature", "properties": {"region": "north"}, "geometry": {"type": "Point", "coordinates": [3, 3]}},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [4, 4]}}
+    ]}"#;
+
+    fn split(split_by: SplitBy) -> Result<Vec<(String, u64, String)>> {
+        let mut outputs = Vec::new();
+        let mut splitter = Splitter::new(split_by, |key, dataset| {
+            let mut out: Vec<u8> = Vec::new();
+            dataset.process(&mut GeoJsonWriter::new(&mut out))?;
+            outputs.push((
+                key.to_string(),
+                dataset.feature_count(),
+                String::from_utf8(out).unwrap(),
+            ));
+            Ok(())
+        });
+        GeoJson(REGIONS).process(&mut splitter)?;
+        Ok(outputs)
+    }
+
+    #[test]
+    fn split_by_property() -> Result<()> {
+        let outputs = split(SplitBy::Property("region".to_string()))?;
+        let keys = outputs
+            .iter()
+            .map(|(key, count, _)| format!("{}:{}", key, count))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![":1", "north:2", "south:1"]);
+        let north = &outputs[1].2;
+        assert!(north.contains(r#""name": "north""#));
+        assert!(north.contains("[1,1]") && north.contains("[3,3]"));
+        assert!(!north.contains("[2,2]"));
+        let _: serde_json::Value = serde_json::from_str(north).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn split_by_geometry_type() -> Result<()> {
+        let outputs = split(SplitBy::GeometryType)?;
+        let keys = outputs
+            .iter()
+            .map(|(key, count, _)| format!("{}:{}", key, count))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["MultiPoint:1", "Point:3"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::BTreeMap;
+
+/// Dataset statistics.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Stats {
+    /// Number of features
+    pub features: u64,
+    /// Number of geometries by OGC type name, including members of collections
+    pub geometry_types: BTreeMap<&'static str, u64>,
+    /// Number of polygon, triangle and curve polygon rings
+    pub rings: u64,
+    /// Number of coordinates
+    pub vertices: u64,
+    /// Number of coordinates with Z value
+    pub vertices_z: u64,
+    /// Number of coordinates with M value
+    pub vertices_m: u64,
+}
+
+impl Stats {
+    /// Total number of geometries.
+    pub fn geometries(&self) -> u64 {
+        self.geometry_types.values().sum()
+    }
+    /// Number of geometries of type `name`, e.g. `Polygon`.
+    pub fn geometry_count(&self, name: &str) -> u64 {
+        self.geometry_types.get(name).copied().unwrap_or(0)
+    }
+    /// Dimensions used by at least one coordinate.
+    pub fn dims(&self) -> CoordDimensions {
+        CoordDimensions {
+            z: self.vertices_z > 0,
+            m: self.vertices_m > 0,
+            t: false,
+            tm: false,
+        }
+    }
+}
+
+/// Processor counting features, geometries and vertices.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{processing::StatsProcessor, wkt::WktStr, GeozeroGeometry};
+///
+/// let mut processor = StatsProcessor::new();
+/// WktStr("MULTIPOINT(1 2,3 4)").process_geom(&mut processor).unwrap();
+/// assert_eq!(processor.stats().geometry_count("MultiPoint"), 1);
+/// assert_eq!(processor.stats().vertices, 2);
+/// ```
+#[derive(Default)]
+pub struct StatsProcessor {
+    stats: Stats,
+}
+
+impl StatsProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Collected statistics.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+    /// Consume processor and return statistics.
+    pub fn into_stats(self) -> Stats {
+        self.stats
+    }
+    fn geometry(&mut self, name: &'static str) {
+        *self.stats.geometry_types.entry(name).or_insert(0) += 1;
+    }
+}
+
+impl GeomProcessor for StatsProcessor {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<()> {
+        self.stats.vertices += 1;
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        _x: f64,
+        _y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.stats.vertices += 1;
+        if z.is_some() {
+            self.stats.vertices_z += 1;
+        }
+        if m.is_some() {
+            self.stats.vertices_m += 1;
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.geometry("Point");
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.geometry("Point");
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("MultiPoint");
+        Ok(())
+    }
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geometry("LineString");
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("MultiLineString");
+        Ok(())
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geometry("Polygon");
+        }
+        self.stats.rings += size as u64;
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("MultiPolygon");
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("GeometryCollection");
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("CircularString");
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("CompoundCurve");
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.geometry("CurvePolygon");
+        self.stats.rings += size as u64;
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("MultiCurve");
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("MultiSurface");
+        Ok(())
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geometry("Triangle");
+        }
+        self.stats.rings += size as u64;
+        Ok(())
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("PolyhedralSurface");
+        Ok(())
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.geometry("Tin");
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for StatsProcessor {}
+
+impl FeatureProcessor for StatsProcessor {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.stats.features += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+    fn geometries() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::wkt::WktStr;
+        use crate::GeozeroGeometry;
+
+        let mut processor = StatsProcessor::new();
+        WktStr("POLYGON((0 0,1 0,1 1,0 0),(0.1 0.1,0.2 0.1,0.2 0.2,0.1 0.1))")
+            .process_geom(&mut processor)?;
+        GeoJson(
+            r#"{"type": "GeometryCollection", "geometries": [
+                {"type": "Point", "coordinates": [1, 2, 3]},
+                {"type": "LineString", "coordinates": [[1, 2], [3, 4]]},
+                {"type": "MultiPolygon", "coordinates": [[[[0, 0], [1, 0], [1, 1], [0, 0]]]]}
+            ]}"#,
+        )
+        .process_geom(&mut processor)?;
+        let stats = processor.into_stats();
+        assert_eq!(stats.features, 0);
+        assert_eq!(stats.geometries(), 5);
+        assert_eq!(stats.geometry_count("Polygon"), 1);
+        assert_eq!(stats.geometry_count("MultiPolygon"), 1);
+        assert_eq!(stats.geometry_count("GeometryCollection"), 1);
+        assert_eq!(stats.geometry_count("Triangle"), 0);
+        assert_eq!(stats.rings, 3);
+        assert_eq!(stats.vertices, 15);
+        assert_eq!(stats.vertices_z, 1);
+        assert!(stats.dims().z);
+        assert!(!stats.dims().m);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+            {"type": "Feature", "properties": {"id": 3}, "geometry": {"type": "LineString", "coordinates": [[-3, 4], [5, -6]]}}
+        ]}"#;
+        let mut processor = StatsProcessor::new();
+        GeoJson(geojson).process(&mut processor)?;
+        let stats = processor.stats();
+        assert_eq!(stats.features, 3);
+        assert_eq!(stats.geometry_count("Point"), 2);
+        assert_eq!(stats.geometry_count("LineString"), 1);
+        assert_eq!(stats.vertices, 4);
+        assert_eq!(stats.vertices_z, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::error::Result;
+use crate::processing::{Extent, ExtentProcessor, Stats, StatsProcessor};
+use crate::schema::{DatasetSchema, SchemaProcessor};
+use crate::{FeatureProcessor, RewindableDatasource, Tee};
+
+/// Schema, extent and statistics of a dataset.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DatasetSummary {
+    pub schema: DatasetSchema,
+    /// Extent of all geometries. `None` for datasets without coordinates.
+    pub extent: Option<Extent>,
+    pub stats: Stats,
+}
+
+impl DatasetSummary {
+    /// Scan all features of `datasource`.
+    pub fn scan<D: RewindableDatasource>(datasource: &mut D) -> Result<Self> {
+        let mut schema = SchemaProcessor::new();
+        let mut extent = ExtentProcessor::new();
+        let mut stats = StatsProcessor::new();
+        {
+            let mut tee = Tee::default();
+            tee.add(&mut schema);
+            tee.add(&mut extent);
+            tee.add(&mut stats);
+            datasource.process(&mut tee)?;
+        }
+        Ok(DatasetSummary {
+            schema: schema.into_schema(),
+            extent: extent.extent(),
+            stats: stats.into_stats(),
+        })
+    }
+}
+
+/// Convert a datasource into a writer requiring the schema or extent up front.
+///
+/// A first pass collects the [DatasetSummary], which is passed to `writer` for creating
+/// the output processor (e.g. a Shapefile or FlatGeobuf writer). The datasource is then
+/// rewound and all features are processed with the created writer in a second pass.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::processing::{convert_with_schema, StatsProcessor};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+/// let stats = convert_with_schema(&mut GeoJson(geojson), |summary| {
+///     assert_eq!(summary.schema.columns[0].name, "name");
+///     assert_eq!(summary.extent.unwrap().bbox(), [1.0, 2.0, 1.0, 2.0]);
+///     Ok(StatsProcessor::new())
+/// })
+/// .unwrap();
+/// assert_eq!(stats.stats().features, 1);
+/// ```
+pub fn convert_with_schema<D, W, F>(datasource: &mut D, writer: F) -> Result<W>
+where
+    D: RewindableDatasource,
+    W: FeatureProcessor,
+    F: FnOnce(&DatasetSummary) -> Result<W>,
+{
+    let summary = DatasetSummary::scan(datasource)?;
+    datasource.rewind()?;
+    let mut writer = writer(&summary)?;
+    datasource.process(&mut writer)?;
+    Ok(writer)
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJsonReader, GeoJsonWriter};
+    use crate::{ColumnType, CoordDimensions};
+    use std::io::Cursor;
+
+    #[test]
+    fn two_pass() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+            {"type": "Feature", "properties": {"id": 2.5, "name": "b"}, "geometry": {"type": "Point", "coordinates": [-3, 4, 5]}}
+        ]}"#;
+        let mut reader = Cursor::new(geojson.as_bytes());
+        let mut out: Vec<u8> = Vec::new();
+        convert_with_schema(&mut GeoJsonReader(&mut reader), |summary| {
+            let schema = &summary.schema;
+            assert_eq!(schema.feature_count, 2);
+            assert_eq!(schema.column("id").unwrap().column_type, ColumnType::Double);
+            assert!(schema.column("name").unwrap().nullable);
+            assert_eq!(schema.dims, CoordDimensions::xyz());
+            assert_eq!(summary.extent.unwrap().bbox(), [-3.0, 2.0, 1.0, 4.0]);
+            assert_eq!(summary.stats.vertices_z, 2);
+            Ok(GeoJsonWriter::new(&mut out))
+        })?;
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(r#""name": "b""#));
+        assert_eq!(out.matches(r#""type": "Feature""#).count(), 2);
+        Ok(())
+    }
+}
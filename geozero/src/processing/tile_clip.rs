@@ -0,0 +1,780 @@
+use crate::error::{GeozeroError, Result};
+use crate::processing::record::FeatureRecorder;
+use crate::processing::ProcessorLayer;
+use crate::{ColumnValue, DatasetMetadata, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::f64::consts::PI;
+
+/// Maximal latitude of Web Mercator tiles
+const MAX_LAT: f64 = 85.051_128_779_806_59;
+
+type Point = [f64; 2];
+type Line = Vec<Point>;
+
+/// Buffered geometry in tile coordinates.
+#[derive(Debug)]
+enum Geom {
+    Point(Option<Point>),
+    MultiPoint(Line),
+    LineString(Line),
+    MultiLineString(Vec<Line>),
+    Polygon(Vec<Line>),
+    MultiPolygon(Vec<Vec<Line>>),
+    GeometryCollection(Vec<Geom>),
+}
+
+/// Clipped geometry with `tagged` flag of its begin event.
+type Clipped = (Geom, bool);
+
+/// Clip box `[minx, miny, maxx, maxy]`
+type Bounds = [f64; 4];
+
+/// Clipping of geometries to a Web Mercator tile.
+///
+/// Geographic WGS84 input coordinates are transformed to tile coordinates
+/// (`0..extent`, origin top left) of the XYZ tile `z/x/y` and clipped to the tile
+/// extended by `buffer` on each side, so that the output can be passed to an
+/// [MvtWriter](crate::mvt::MvtWriter). LineStrings are clipped with the Cohen-Sutherland
+/// algorithm, polygon rings with the Sutherland-Hodgman algorithm.
+///
+/// Clipped LineStrings consisting of multiple parts are emitted as MultiLineStrings.
+/// Geometries outside of the tile and features without remaining geometry are dropped.
+/// Z and M values are not processed, curves are not supported.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::TileClip;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut clip = TileClip::new(0, 0, 0, WktWriter::new(&mut wkt));
+/// WktStr("LINESTRING(-270 0,0 0)").process_geom(&mut clip).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(-64 2048,2048 2048)");
+/// ```
+pub struct TileClip<P> {
+    /// Tile extent in tile coordinates (default: 4096)
+    pub extent: u32,
+    /// Buffer around the tile in tile coordinates (default: 64)
+    pub buffer: u32,
+    z: u8,
+    x: u32,
+    y: u32,
+    processor: P,
+    /// Geometries under construction
+    stack: Vec<Geom>,
+    /// Clipped geometries of current feature
+    geometries: Vec<Clipped>,
+    /// Clipped geometries of additional geometry columns of current feature
+    columns: Vec<(usize, String, Vec<Clipped>)>,
+    in_column: bool,
+    /// Properties of current feature
+    recorder: FeatureRecorder,
+    in_feature: bool,
+    /// Index of next forwarded feature
+    feature_idx: u64,
+}
+
+impl<P> TileClip<P> {
+    /// Clip to tile `z/x/y` and forward to `processor`.
+    pub fn new(z: u8, x: u32, y: u32, processor: P) -> Self {
+        TileClip {
+            extent: 4096,
+            buffer: 64,
+            z,
+            x,
+            y,
+            processor,
+            stack: Vec::new(),
+            geometries: Vec::new(),
+            recorder: FeatureRecorder::default(),
+            columns: Vec::new(),
+            in_column: false,
+            in_feature: false,
+            feature_idx: 0,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume clipper and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+
+    /// Transform longitude/latitude to tile coordinates.
+    fn tile_coords(&self, lon: f64, lat: f64) -> Point {
+        let n = f64::powi(2.0, self.z as i32);
+        let extent = self.extent as f64;
+        let lat = lat.clamp(-MAX_LAT, MAX_LAT).to_radians();
+        let xt = (lon + 180.0) / 360.0 * n;
+        let yt = (1.0 - lat.tan().asinh() / PI) / 2.0 * n;
+        [(xt - self.x as f64) * extent, (yt - self.y as f64) * extent]
+    }
+
+    fn bounds(&self) -> Bounds {
+        let min = -(self.buffer as f64);
+        let max = self.extent as f64 + self.buffer as f64;
+        [min, min, max, max]
+    }
+
+    fn begin(&mut self, geom: Geom) -> Result<()> {
+        self.stack.push(geom);
+        Ok(())
+    }
+
+    fn add_point(&mut self, x: f64, y: f64) -> Result<()> {
+        let p = self.tile_coords(x, y);
+        match self.stack.last_mut() {
+            Some(Geom::Point(point)) => *point = Some(p),
+            Some(Geom::MultiPoint(line)) | Some(Geom::LineString(line)) => line.push(p),
+            _ => return Err(GeozeroError::Geometry("unexpected coordinate".to_string())),
+        }
+        Ok(())
+    }
+
+    /// Add finished geometry to its parent or clip it, if complete.
+    fn end(&mut self, tagged: bool) -> Result<()> {
+        let bounds = self.bounds();
+        let geom = self
+            .stack
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("unbalanced geometry".to_string()))?;
+        match (self.stack.last_mut(), geom) {
+            (None, geom) => {
+                if let Some(geom) = clip(geom, &bounds) {
+                    match self.columns.last_mut() {
+                        Some((_, _, geometries)) if self.in_column => {
+                            geometries.push((geom, tagged))
+                        }
+                        _ => self.geometries.push((geom, tagged)),
+                    }
+                }
+                Ok(())
+            }
+            (Some(Geom::MultiLineString(lines)), Geom::LineString(line))
+            | (Some(Geom::Polygon(lines)), Geom::LineString(line)) => {
+                lines.push(line);
+                Ok(())
+            }
+            (Some(Geom::MultiPolygon(polygons)), Geom::Polygon(rings)) => {
+                polygons.push(rings);
+                Ok(())
+            }
+            (Some(Geom::GeometryCollection(geoms)), geom) => {
+                geoms.push(geom);
+                Ok(())
+            }
+            (Some(_), _) => Err(GeozeroError::Geometry(
+                "unexpected geometry member".to_string(),
+            )),
+        }
+    }
+
+    fn unsupported(&self, geometry_type: &str) -> Result<()> {
+        Err(GeozeroError::Geometry(format!(
+            "{} not supported by TileClip",
+            geometry_type
+        )))
+    }
+}
+
+/// Cohen-Sutherland outcode
+fn outcode(p: &Point, b: &Bounds) -> u8 {
+    let mut code = 0;
+    if p[0] < b[0] {
+        code |= 1;
+    } else if p[0] > b[2] {
+        code |= 2;
+    }
+    if p[1] < b[1] {
+        code |= 4;
+    } else if p[1] > b[3] {
+        code |= 8;
+    }
+    code
+}
+
+/// Clip segment `a`-`b` with the Cohen-Sutherland algorithm.
+///
+/// Returns the clipped segment and whether its start or end point was moved.
+fn clip_segment(mut a: Point, mut b: Point, bounds: &Bounds) -> Option<(Point, Point, bool, bool)> {
+    let (mut code_a, mut code_b) = (outcode(&a, bounds), outcode(&b, bounds));
+    let (mut moved_a, mut moved_b) = (false, false);
+    loop {
+        if code_a | code_b == 0 {
+            return Some((a, b, moved_a, moved_b));
+        }
+        if code_a & code_b != 0 {
+            return None;
+        }
+        let code = if code_a != 0 { code_a } else { code_b };
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let p = if code & 8 != 0 {
+            [a[0] + dx * (bounds[3] - a[1]) / dy, bounds[3]]
+        } else if code & 4 != 0 {
+            [a[0] + dx * (bounds[1] - a[1]) / dy, bounds[1]]
+        } else if code & 2 != 0 {
+            [bounds[2], a[1] + dy * (bounds[2] - a[0]) / dx]
+        } else {
+            [bounds[0], a[1] + dy * (bounds[0] - a[0]) / dx]
+        };
+        if code == code_a {
+            a = p;
+            code_a = outcode(&a, bounds);
+            moved_a = true;
+        } else {
+            b = p;
+            code_b = outcode(&b, bounds);
+            moved_b = true;
+        }
+    }
+}
+
+/// Clip LineString into parts inside of `bounds`.
+fn clip_line(line: &[Point], bounds: &Bounds) -> Vec<Line> {
+    if line.len() < 2 {
+        return line
+            .iter()
+            .filter(|p| outcode(p, bounds) == 0)
+            .map(|p| vec![*p])
+            .collect();
+    }
+    let mut parts = Vec::new();
+    let mut part: Line = Vec::new();
+    for segment in line.windows(2) {
+        match clip_segment(segment[0], segment[1], bounds) {
+            Some((a, b, moved_a, moved_b)) => {
+                if part.is_empty() || moved_a {
+                    if part.len() > 1 {
+                        parts.push(std::mem::take(&mut part));
+                    }
+                    part = vec![a];
+                }
+                part.push(b);
+                if moved_b {
+                    parts.push(std::mem::take(&mut part));
+                }
+            }
+            None => {
+                if part.len() > 1 {
+                    parts.push(std::mem::take(&mut part));
+                }
+                part.clear();
+            }
+        }
+    }
+    if part.len() > 1 {
+        parts.push(part);
+    }
+    parts
+}
+
+/// Clip ring with the Sutherland-Hodgman algorithm.
+///
+/// Returns a closed ring or `None` if less than three vertices remain.
+fn clip_ring(ring: &[Point], bounds: &Bounds) -> Option<Line> {
+    let mut points: Line = ring.to_vec();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    // Edges as (axis, value, keep greater values)
+    let edges = [
+        (0, bounds[0], true),
+        (0, bounds[2], false),
+        (1, bounds[1], true),
+        (1, bounds[3], false),
+    ];
+    for &(axis, value, greater) in &edges {
+        if points.is_empty() {
+            break;
+        }
+        let inside = |p: &Point| {
+            if greater {
+                p[axis] >= value
+            } else {
+                p[axis] <= value
+            }
+        };
+        let intersect = |a: &Point, b: &Point| {
+            let t = (value - a[axis]) / (b[axis] - a[axis]);
+            let mut p = [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])];
+            p[axis] = value;
+            p
+        };
+        let input = std::mem::take(&mut points);
+        let mut prev = input[input.len() - 1];
+        for p in input {
+            match (inside(&prev), inside(&p)) {
+                (true, true) => points.push(p),
+                (true, false) => points.push(intersect(&prev, &p)),
+                (false, true) => {
+                    points.push(intersect(&prev, &p));
+                    points.push(p);
+                }
+                (false, false) => {}
+            }
+            prev = p;
+        }
+    }
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 {
+        return None;
+    }
+    points.push(points[0]);
+    Some(points)
+}
+
+fn clip_polygon(rings: &[Line], bounds: &Bounds) -> Option<Vec<Line>> {
+    let mut rings = rings.iter();
+    let exterior = clip_ring(rings.next()?, bounds)?;
+    let mut clipped = vec![exterior];
+    clipped.extend(rings.filter_map(|ring| clip_ring(ring, bounds)));
+    Some(clipped)
+}
+
+/// Clip geometry. Returns `None` for geometries outside of `bounds`.
+fn clip(geom: Geom, bounds: &Bounds) -> Option<Geom> {
+    let inside = |p: &Point| outcode(p, bounds) == 0;
+    match geom {
+        Geom::Point(p) => p.filter(inside).map(|p| Geom::Point(Some(p))),
+        Geom::MultiPoint(points) => {
+            let points: Line = points.into_iter().filter(inside).collect();
+            Some(points)
+                .filter(|points| !points.is_empty())
+                .map(Geom::MultiPoint)
+        }
+        Geom::LineString(line) => {
+            let mut parts = clip_line(&line, bounds);
+            match parts.len() {
+                0 => None,
+                1 => parts.pop().map(Geom::LineString),
+                _ => Some(Geom::MultiLineString(parts)),
+            }
+        }
+        Geom::MultiLineString(lines) => {
+            let parts: Vec<Line> = lines
+                .iter()
+                .flat_map(|line| clip_line(line, bounds))
+                .collect();
+            Some(parts)
+                .filter(|parts| !parts.is_empty())
+                .map(Geom::MultiLineString)
+        }
+        Geom::Polygon(rings) => clip_polygon(&rings, bounds).map(Geom::Polygon),
+        Geom::MultiPolygon(polygons) => {
+            let polygons: Vec<Vec<Line>> = polygons
+                .iter()
+                .filter_map(|rings| clip_polygon(rings, bounds))
+                .collect();
+            Some(polygons)
+                .filter(|polygons| !polygons.is_empty())
+                .map(Geom::MultiPolygon)
+        }
+        Geom::GeometryCollection(geoms) => {
+            let geoms: Vec<Geom> = geoms
+                .into_iter()
+                .filter_map(|geom| clip(geom, bounds))
+                .collect();
+            Some(geoms)
+                .filter(|geoms| !geoms.is_empty())
+                .map(Geom::GeometryCollection)
+        }
+    }
+}
+
+fn emit_line<P: GeomProcessor>(
+    line: &[Point],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, line.len(), idx)?;
+    for (i, p) in line.iter().enumerate() {
+        processor.xy(p[0], p[1], i)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn emit_polygon<P: GeomProcessor>(
+    rings: &[Line],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (i, ring) in rings.iter().enumerate() {
+        emit_line(ring, false, i, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+fn emit<P: GeomProcessor>(geom: &Geom, tagged: bool, idx: usize, processor: &mut P) -> Result<()> {
+    match geom {
+        Geom::Point(None) => processor.empty_point(idx),
+        Geom::Point(Some(p)) => {
+            processor.point_begin(idx)?;
+            processor.xy(p[0], p[1], 0)?;
+            processor.point_end(idx)
+        }
+        Geom::MultiPoint(points) => {
+            processor.multipoint_begin(points.len(), idx)?;
+            for (i, p) in points.iter().enumerate() {
+                processor.xy(p[0], p[1], i)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        Geom::LineString(line) => emit_line(line, tagged, idx, processor),
+        Geom::MultiLineString(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (i, line) in lines.iter().enumerate() {
+                emit_line(line, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        Geom::Polygon(rings) => emit_polygon(rings, tagged, idx, processor),
+        Geom::MultiPolygon(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (i, rings) in polygons.iter().enumerate() {
+                emit_polygon(rings, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        Geom::GeometryCollection(geoms) => {
+            processor.geometrycollection_begin(geoms.len(), idx)?;
+            for (i, geom) in geoms.iter().enumerate() {
+                emit(geom, true, i, processor)?;
+            }
+            processor.geometrycollection_end(idx)
+        }
+    }
+}
+
+impl<P: GeomProcessor> TileClip<P> {
+    /// End geometry and forward it, if it is complete and outside of a feature.
+    fn finish(&mut self, tagged: bool) -> Result<()> {
+        self.end(tagged)?;
+        if !self.in_feature && self.stack.is_empty() {
+            for (geom, tagged) in self.geometries.drain(..) {
+                emit(&geom, tagged, 0, &mut self.processor)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for TileClip<P> {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_point(x, y)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        _z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_point(x, y)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Geom::Point(None))?;
+        self.finish(false)
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Geom::Point(None))
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::MultiPoint(Vec::with_capacity(size)))
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::LineString(Vec::with_capacity(size)))
+    }
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.finish(tagged)
+    }
+    fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::MultiLineString(Vec::with_capacity(size)))
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::Polygon(Vec::with_capacity(size)))
+    }
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.finish(tagged)
+    }
+    fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::MultiPolygon(Vec::with_capacity(size)))
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::GeometryCollection(Vec::with_capacity(size)))
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn triangle_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::Polygon(Vec::with_capacity(size)))
+    }
+    fn triangle_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.finish(tagged)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::MultiPolygon(Vec::with_capacity(size)))
+    }
+    fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn tin_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin(Geom::MultiPolygon(Vec::with_capacity(size)))
+    }
+    fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        self.finish(false)
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.unsupported("CircularString")
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.unsupported("CompoundCurve")
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.unsupported("CurvePolygon")
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.unsupported("MultiCurve")
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.unsupported("MultiSurface")
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for TileClip<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.in_feature {
+            self.recorder.property(idx, name, value)
+        } else {
+            self.processor.property(idx, name, value)
+        }
+    }
+}
+
+/// Forward event to recorder within features and to the inner processor otherwise.
+macro_rules! record_events {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name(&mut self, $($arg: $ty),*) -> Result<()> {
+                if self.in_feature {
+                    self.recorder.$name($($arg),*)
+                } else {
+                    self.processor.$name($($arg),*)
+                }
+            }
+        )*
+    };
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for TileClip<P> {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.recorder.clear();
+        self.geometries.clear();
+        self.columns.clear();
+        self.stack.clear();
+        self.in_feature = true;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.in_feature = false;
+        if !self.geometries.is_empty() {
+            let idx = self.feature_idx;
+            self.feature_idx += 1;
+            self.processor.feature_begin(idx)?;
+            self.recorder.replay(&mut self.processor)?;
+            self.processor.geometry_begin()?;
+            for (geom, tagged) in self.geometries.drain(..) {
+                emit(&geom, tagged, 0, &mut self.processor)?;
+            }
+            self.processor.geometry_end()?;
+            for (column_idx, name, geometries) in self.columns.drain(..) {
+                self.processor.geometry_column_begin(column_idx, &name)?;
+                for (geom, tagged) in geometries {
+                    emit(&geom, tagged, 0, &mut self.processor)?;
+                }
+                self.processor.geometry_column_end(column_idx, &name)?;
+            }
+            self.processor.feature_end(idx)?;
+        }
+        self.recorder.clear();
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        if self.in_feature {
+            self.columns.push((idx, name.to_string(), Vec::new()));
+            self.in_column = true;
+            Ok(())
+        } else {
+            self.processor.geometry_column_begin(idx, name)
+        }
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        if self.in_feature {
+            self.in_column = false;
+            Ok(())
+        } else {
+            self.processor.geometry_column_end(idx, name)
+        }
+    }
+    /// Output coordinates are tile coordinates without SRID
+    fn dataset_srid(&mut self, _srid: Option<i32>) -> Result<()> {
+        Ok(())
+    }
+    record_events!(
+        properties_begin();
+        properties_end();
+        foreign_member(name: &str, value: &str);
+    );
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        dataset_end();
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+/// [TileClip] stage for [Pipeline](crate::processing::Pipeline).
+pub struct TileClipLayer {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+    /// Tile extent in tile coordinates
+    pub extent: u32,
+    /// Buffer around the tile in tile coordinates
+    pub buffer: u32,
+}
+
+impl<P> ProcessorLayer<P> for TileClipLayer {
+    type Processor = TileClip<P>;
+    fn wrap(self, processor: P) -> TileClip<P> {
+        let mut clip = TileClip::new(self.z, self.x, self.y, processor);
+        clip.extent = self.extent;
+        clip.buffer = self.buffer;
+        clip
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::processing::{Pipeline, SnapToGridLayer};
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn clip(wkt: &str, z: u8, x: u32, y: u32) -> Result<String> {
+        let mut out = Vec::new();
+        let mut clip = Pipeline::new()
+            .layer(TileClipLayer {
+                z,
+                x,
+                y,
+                extent: 4096,
+                buffer: 64,
+            })
+            .layer(SnapToGridLayer {
+                resolution: 1.0,
+                remove_duplicates: false,
+            })
+            .build(WktWriter::new(&mut out));
+        WktStr(wkt).process_geom(&mut clip)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn points() -> Result<()> {
+        assert_eq!(clip("POINT(0 0)", 0, 0, 0)?, "POINT(2048 2048)");
+        assert_eq!(clip("POINT(-90 0)", 1, 0, 0)?, "POINT(2048 4096)");
+        assert_eq!(clip("POINT(200 0)", 0, 0, 0)?, "");
+        assert_eq!(
+            clip("MULTIPOINT(0 0,200 0)", 0, 0, 0)?,
+            "MULTIPOINT(2048 2048)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn linestrings() -> Result<()> {
+        assert_eq!(
+            clip("LINESTRING(-270 0,0 0)", 0, 0, 0)?,
+            "LINESTRING(-64 2048,2048 2048)"
+        );
+        assert_eq!(
+            clip("LINESTRING(0 0,270 0,270 -80,-180 -80)", 0, 0, 0)?,
+            "MULTILINESTRING((2048 2048,4160 2048),(4160 3636,0 3636))"
+        );
+        assert_eq!(clip("LINESTRING(200 0,270 0)", 0, 0, 0)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn polygons() -> Result<()> {
+        assert_eq!(
+            clip("POLYGON((-270 -60,90 -60,90 60,-270 60,-270 -60))", 0, 0, 0)?,
+            "POLYGON((-64 2907,3072 2907,3072 1189,-64 1189,-64 2907))"
+        );
+        assert_eq!(
+            clip(
+                "MULTIPOLYGON(((200 0,210 0,210 10,200 0)),((-90 0,90 0,90 60,-90 0),(200 0,210 0,210 10,200 0)))",
+                0,
+                0,
+                0
+            )?,
+            "MULTIPOLYGON(((1024 2048,3072 2048,3072 1189,1024 2048)))"
+        );
+        assert_eq!(
+            clip("GEOMETRYCOLLECTION(POINT(200 0),POINT(0 0))", 0, 0, 0)?,
+            "GEOMETRYCOLLECTION(POINT(2048 2048))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::processing::StatsProcessor;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [200, 0]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [0, 0]}}
+        ]}"#;
+        let mut clip = TileClip::new(0, 0, 0, StatsProcessor::new());
+        GeoJson(geojson).process(&mut clip)?;
+        assert_eq!(clip.into_inner().stats().features, 1);
+        Ok(())
+    }
+}
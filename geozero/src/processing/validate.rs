@@ -0,0 +1,410 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::fmt;
+
+/// Geometry validation issue.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Issue {
+    /// First and last coordinate of a ring differ
+    UnclosedRing,
+    /// Ring with less than 4 coordinates
+    TooFewRingPoints(usize),
+    /// NaN or infinite coordinate value
+    InvalidCoordinate,
+    /// Coordinates with and without Z or M values in the same geometry
+    InconsistentDimensions,
+    /// Number of members or coordinates differs from declared size
+    SizeMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::UnclosedRing => write!(f, "ring is not closed"),
+            Issue::TooFewRingPoints(n) => write!(f, "ring has {} points, at least 4 expected", n),
+            Issue::InvalidCoordinate => write!(f, "invalid coordinate value"),
+            Issue::InconsistentDimensions => write!(f, "inconsistent coordinate dimensions"),
+            Issue::SizeMismatch { expected, actual } => {
+                write!(f, "declared size {} but got {} elements", expected, actual)
+            }
+        }
+    }
+}
+
+/// Validation issue with location.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ValidationIssue {
+    /// Index of feature, `None` for geometries processed outside of a feature
+    pub feature: Option<u64>,
+    /// OGC type name of the geometry containing the issue
+    pub geometry_type: &'static str,
+    pub issue: Issue,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(feature) = self.feature {
+            write!(f, "feature {}: ", feature)?;
+        }
+        write!(f, "{}: {}", self.geometry_type, self.issue)
+    }
+}
+
+struct OpenGeometry {
+    geometry_type: &'static str,
+    size: usize,
+    count: usize,
+    ring: bool,
+    first: Option<(f64, f64)>,
+    last: Option<(f64, f64)>,
+}
+
+/// Streaming geometry validation.
+///
+/// Checks geometries for unclosed rings, rings with less than 4 points, NaN or infinite
+/// coordinates, inconsistent dimensions and declared sizes not matching the number of
+/// members or coordinates. Events are forwarded to the inner processor.
+/// With `fail_fast`, processing is aborted with an error at the first issue.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::processing::{Issue, Validator};
+/// use geozero::{wkt::WktStr, GeozeroGeometry, ProcessorSink};
+///
+/// let mut validator = Validator::new(ProcessorSink);
+/// WktStr("POLYGON((0 0,1 0,1 1))").process_geom(&mut validator).unwrap();
+/// let issues = validator.issues();
+/// assert_eq!(issues[0].issue, Issue::TooFewRingPoints(3));
+/// assert_eq!(issues[1].issue, Issue::UnclosedRing);
+/// ```
+pub struct Validator<P> {
+    /// Abort processing at first issue
+    pub fail_fast: bool,
+    processor: P,
+    issues: Vec<ValidationIssue>,
+    feature: Option<u64>,
+    stack: Vec<OpenGeometry>,
+    /// Dimensions (z, m) of first coordinate in current geometry
+    dims: Option<(bool, bool)>,
+}
+
+impl<P> Validator<P> {
+    pub fn new(processor: P) -> Self {
+        Validator {
+            fail_fast: false,
+            processor,
+            issues: Vec::new(),
+            feature: None,
+            stack: Vec::new(),
+            dims: None,
+        }
+    }
+    /// Issues found so far.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+    /// No issues found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume validator and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+    fn report(&mut self, geometry_type: &'static str, issue: Issue) -> Result<()> {
+        let issue = ValidationIssue {
+            feature: self.feature,
+            geometry_type,
+            issue,
+        };
+        if self.fail_fast {
+            return Err(GeozeroError::Geometry(issue.to_string()));
+        }
+        self.issues.push(issue);
+        Ok(())
+    }
+    fn begin(&mut self, geometry_type: &'static str, tagged: bool, size: usize) {
+        let ring = match self.stack.last_mut() {
+            Some(parent) => {
+                parent.count += 1;
+                !tagged
+                    && geometry_type == "LineString"
+                    && matches!(
+                        parent.geometry_type,
+                        "Polygon" | "Triangle" | "CurvePolygon"
+                    )
+            }
+            None => {
+                self.dims = None;
+                false
+            }
+        };
+        self.stack.push(OpenGeometry {
+            geometry_type,
+            size,
+            count: 0,
+            ring,
+            first: None,
+            last: None,
+        });
+    }
+    fn end(&mut self) -> Result<()> {
+        let geom = match self.stack.pop() {
+            Some(geom) => geom,
+            None => return Ok(()),
+        };
+        if geom.count != geom.size {
+            self.report(
+                geom.geometry_type,
+                Issue::SizeMismatch {
+                    expected: geom.size,
+                    actual: geom.count,
+                },
+            )?;
+        }
+        if geom.ring {
+            if geom.count < 4 {
+                self.report(geom.geometry_type, Issue::TooFewRingPoints(geom.count))?;
+            }
+            if geom.first != geom.last {
+                self.report(geom.geometry_type, Issue::UnclosedRing)?;
+            }
+        }
+        Ok(())
+    }
+    fn check_coord(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) -> Result<()> {
+        let geometry_type = match self.stack.last_mut() {
+            Some(geom) => {
+                geom.count += 1;
+                if geom.first.is_none() {
+                    geom.first = Some((x, y));
+                }
+                geom.last = Some((x, y));
+                geom.geometry_type
+            }
+            None => "Point",
+        };
+        let finite = x.is_finite()
+            && y.is_finite()
+            && z.is_none_or(f64::is_finite)
+            && m.is_none_or(f64::is_finite);
+        if !finite {
+            self.report(geometry_type, Issue::InvalidCoordinate)?;
+        }
+        let dims = (z.is_some(), m.is_some());
+        match self.dims {
+            None => self.dims = Some(dims),
+            Some(first) if first != dims => {
+                // report only once per geometry
+                self.dims = Some(dims);
+                self.report(geometry_type, Issue::InconsistentDimensions)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Validate geometry event before forwarding.
+macro_rules! validate_events {
+    ($($begin:ident($($barg:ident: $bty:ty),*) => ($name:expr, $tagged:expr, $size:expr), $end:ident($($earg:ident: $ety:ty),*);)*) => {
+        $(
+            fn $begin(&mut self, $($barg: $bty),*) -> Result<()> {
+                self.begin($name, $tagged, $size);
+                self.processor.$begin($($barg),*)
+            }
+            fn $end(&mut self, $($earg: $ety),*) -> Result<()> {
+                self.end()?;
+                self.processor.$end($($earg),*)
+            }
+        )*
+    };
+}
+
+impl<P: GeomProcessor> GeomProcessor for Validator<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.check_coord(x, y, None, None)?;
+        self.processor.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.check_coord(x, y, z, m)?;
+        if self.processor.multi_dim() {
+            // Only pass requested dimensions
+            let dims = self.processor.dimensions();
+            self.processor.coordinate(
+                x,
+                y,
+                z.filter(|_| dims.z),
+                m.filter(|_| dims.m),
+                t.filter(|_| dims.t),
+                tm.filter(|_| dims.tm),
+                idx,
+            )
+        } else {
+            self.processor.xy(x, y, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        if let Some(parent) = self.stack.last_mut() {
+            parent.count += 1;
+        }
+        self.processor.empty_point(idx)
+    }
+    validate_events!(
+        point_begin(idx: usize) => ("Point", true, 1), point_end(idx: usize);
+        multipoint_begin(size: usize, idx: usize) => ("MultiPoint", true, size), multipoint_end(idx: usize);
+        linestring_begin(tagged: bool, size: usize, idx: usize) => ("LineString", tagged, size), linestring_end(tagged: bool, idx: usize);
+        multilinestring_begin(size: usize, idx: usize) => ("MultiLineString", true, size), multilinestring_end(idx: usize);
+        polygon_begin(tagged: bool, size: usize, idx: usize) => ("Polygon", tagged, size), polygon_end(tagged: bool, idx: usize);
+        multipolygon_begin(size: usize, idx: usize) => ("MultiPolygon", true, size), multipolygon_end(idx: usize);
+        geometrycollection_begin(size: usize, idx: usize) => ("GeometryCollection", true, size), geometrycollection_end(idx: usize);
+        circularstring_begin(size: usize, idx: usize) => ("CircularString", true, size), circularstring_end(idx: usize);
+        compoundcurve_begin(size: usize, idx: usize) => ("CompoundCurve", true, size), compoundcurve_end(idx: usize);
+        curvepolygon_begin(size: usize, idx: usize) => ("CurvePolygon", true, size), curvepolygon_end(idx: usize);
+        multicurve_begin(size: usize, idx: usize) => ("MultiCurve", true, size), multicurve_end(idx: usize);
+        multisurface_begin(size: usize, idx: usize) => ("MultiSurface", true, size), multisurface_end(idx: usize);
+        triangle_begin(tagged: bool, size: usize, idx: usize) => ("Triangle", tagged, size), triangle_end(tagged: bool, idx: usize);
+        polyhedralsurface_begin(size: usize, idx: usize) => ("PolyhedralSurface", true, size), polyhedralsurface_end(idx: usize);
+        tin_begin(size: usize, idx: usize) => ("Tin", true, size), tin_end(idx: usize);
+    );
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for Validator<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Validator<P> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature = Some(idx);
+        self.processor.feature_begin(idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.feature = None;
+        self.processor.feature_end(idx)
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    forward_events!(processor;
+        dataset_begin(name: Option<&str>);
+        dataset_end();
+        properties_begin();
+        properties_end();
+        geometry_begin();
+        geometry_end();
+        null_geometry();
+        geometry_column_begin(idx: usize, name: &str);
+        geometry_column_end(idx: usize, name: &str);
+        foreign_member(name: &str, value: &str);
+        dataset_srid(srid: Option<i32>);
+        dataset_metadata(metadata: &DatasetMetadata);
+        batch_begin(size: usize);
+        batch_end();
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProcessorSink;
+
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn geometries() -> Result<()> {
+        use crate::wkt::WktStr;
+        use crate::GeozeroGeometry;
+
+        let validate = |wkt: &str| -> Result<Vec<Issue>> {
+            let mut validator = Validator::new(ProcessorSink);
+            WktStr(wkt).process_geom(&mut validator)?;
+            Ok(validator.issues().iter().map(|i| i.issue.clone()).collect())
+        };
+        assert_eq!(validate("POLYGON((0 0,1 0,1 1,0 0))")?, vec![]);
+        assert_eq!(validate("LINESTRING(0 0,1 0)")?, vec![]);
+        assert_eq!(
+            validate("MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((0 0,1 0,1 1,0 1)))")?,
+            vec![Issue::UnclosedRing]
+        );
+        assert_eq!(
+            validate("POLYGON((0 0,1 0,0 0))")?,
+            vec![Issue::TooFewRingPoints(3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn events() -> Result<()> {
+        let mut validator = Validator::new(ProcessorSink);
+        validator.feature_begin(7)?;
+        validator.linestring_begin(true, 3, 0)?;
+        validator.xy(0.0, 0.0, 0)?;
+        validator.xy(f64::NAN, 1.0, 1)?;
+        validator.linestring_end(true, 0)?;
+        validator.feature_end(7)?;
+        assert_eq!(
+            validator.issues(),
+            &[
+                ValidationIssue {
+                    feature: Some(7),
+                    geometry_type: "LineString",
+                    issue: Issue::InvalidCoordinate
+                },
+                ValidationIssue {
+                    feature: Some(7),
+                    geometry_type: "LineString",
+                    issue: Issue::SizeMismatch {
+                        expected: 3,
+                        actual: 2
+                    }
+                }
+            ]
+        );
+        assert_eq!(
+            validator.issues()[1].to_string(),
+            "feature 7: LineString: declared size 3 but got 2 elements"
+        );
+
+        let mut validator = Validator::new(ProcessorSink);
+        validator.multipoint_begin(2, 0)?;
+        validator.xy(0.0, 0.0, 0)?;
+        validator.coordinate(1.0, 1.0, Some(1.0), None, None, None, 1)?;
+        validator.multipoint_end(0)?;
+        assert_eq!(validator.issues()[0].issue, Issue::InconsistentDimensions);
+        assert_eq!(validator.issues()[0].feature, None);
+
+        let mut validator = Validator::new(ProcessorSink);
+        validator.fail_fast = true;
+        validator.multipoint_begin(2, 0)?;
+        validator.xy(0.0, 0.0, 0)?;
+        assert_eq!(
+            validator.multipoint_end(0).unwrap_err().to_string(),
+            "processing geometry `MultiPoint: declared size 2 but got 1 elements`"
+        );
+        Ok(())
+    }
+}
@@ -22,6 +22,49 @@ pub enum ColumnValue<'a> {
     Binary(&'a [u8]),
 }
 
+/// Type of a [ColumnValue].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnType {
+    Byte,
+    UByte,
+    Bool,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+    Json,
+    DateTime,
+    Binary,
+}
+
+impl ColumnValue<'_> {
+    /// Type of value.
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            ColumnValue::Byte(_) => ColumnType::Byte,
+            ColumnValue::UByte(_) => ColumnType::UByte,
+            ColumnValue::Bool(_) => ColumnType::Bool,
+            ColumnValue::Short(_) => ColumnType::Short,
+            ColumnValue::UShort(_) => ColumnType::UShort,
+            ColumnValue::Int(_) => ColumnType::Int,
+            ColumnValue::UInt(_) => ColumnType::UInt,
+            ColumnValue::Long(_) => ColumnType::Long,
+            ColumnValue::ULong(_) => ColumnType::ULong,
+            ColumnValue::Float(_) => ColumnType::Float,
+            ColumnValue::Double(_) => ColumnType::Double,
+            ColumnValue::String(_) => ColumnType::String,
+            ColumnValue::Json(_) => ColumnType::Json,
+            ColumnValue::DateTime(_) => ColumnType::DateTime,
+            ColumnValue::Binary(_) => ColumnType::Binary,
+        }
+    }
+}
+
 /// Feature property processing trait.
 ///
 /// # Usage example:
@@ -46,6 +89,47 @@ pub trait PropertyProcessor {
     }
 }
 
+/// Process a JSON property value.
+///
+/// Scalar values are passed without allocation, nested arrays and objects are
+/// written as JSON text into `json`, which is reused across calls.
+#[cfg(any(
+    feature = "with-geojson",
+    feature = "with-topojson",
+    feature = "with-cityjson"
+))]
+pub(crate) fn process_json_property<P: PropertyProcessor>(
+    processor: &mut P,
+    idx: usize,
+    name: &str,
+    value: &serde_json::Value,
+    json: &mut String,
+) -> Result<bool> {
+    use serde_json::Value as JsonValue;
+    use std::fmt::Write;
+
+    match value {
+        JsonValue::String(v) => processor.property(idx, name, &ColumnValue::String(v)),
+        JsonValue::Number(v) if v.is_f64() => {
+            processor.property(idx, name, &ColumnValue::Double(v.as_f64().unwrap()))
+        }
+        JsonValue::Number(v) if v.is_i64() => {
+            processor.property(idx, name, &ColumnValue::Long(v.as_i64().unwrap()))
+        }
+        JsonValue::Number(v) if v.is_u64() => {
+            processor.property(idx, name, &ColumnValue::ULong(v.as_u64().unwrap()))
+        }
+        JsonValue::Bool(v) => processor.property(idx, name, &ColumnValue::Bool(*v)),
+        JsonValue::Array(_) | JsonValue::Object(_) => {
+            json.clear();
+            write!(json, "{}", value).map_err(|e| GeozeroError::Property(e.to_string()))?;
+            processor.property(idx, name, &ColumnValue::Json(json))
+        }
+        // Null
+        _ => processor.property(idx, name, &ColumnValue::String("null")),
+    }
+}
+
 impl fmt::Display for ColumnValue<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -154,6 +238,58 @@ impl PropertyReadType for String {
     }
 }
 
+/// Get Rust type as property value.
+pub trait PropertyWriteType {
+    /// Property value, `None` for null values.
+    fn column_value(&self) -> Option<ColumnValue<'_>>;
+}
+
+macro_rules! impl_scalar_property_writer {
+    ( $t:ty, $e:path ) => {
+        impl PropertyWriteType for $t {
+            fn column_value(&self) -> Option<ColumnValue<'_>> {
+                Some($e(*self))
+            }
+        }
+    };
+}
+
+impl_scalar_property_writer!(i8, ColumnValue::Byte);
+impl_scalar_property_writer!(u8, ColumnValue::UByte);
+impl_scalar_property_writer!(bool, ColumnValue::Bool);
+impl_scalar_property_writer!(i16, ColumnValue::Short);
+impl_scalar_property_writer!(u16, ColumnValue::UShort);
+impl_scalar_property_writer!(i32, ColumnValue::Int);
+impl_scalar_property_writer!(u32, ColumnValue::UInt);
+impl_scalar_property_writer!(i64, ColumnValue::Long);
+impl_scalar_property_writer!(u64, ColumnValue::ULong);
+impl_scalar_property_writer!(f32, ColumnValue::Float);
+impl_scalar_property_writer!(f64, ColumnValue::Double);
+
+impl PropertyWriteType for String {
+    fn column_value(&self) -> Option<ColumnValue<'_>> {
+        Some(ColumnValue::String(self))
+    }
+}
+
+impl PropertyWriteType for &str {
+    fn column_value(&self) -> Option<ColumnValue<'_>> {
+        Some(ColumnValue::String(self))
+    }
+}
+
+impl PropertyWriteType for Vec<u8> {
+    fn column_value(&self) -> Option<ColumnValue<'_>> {
+        Some(ColumnValue::Binary(self))
+    }
+}
+
+impl<T: PropertyWriteType> PropertyWriteType for Option<T> {
+    fn column_value(&self) -> Option<ColumnValue<'_>> {
+        self.as_ref().and_then(|v| v.column_value())
+    }
+}
+
 impl PropertyProcessor for HashMap<String, String> {
     fn property(&mut self, _idx: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
         self.insert(colname.to_string(), colval.to_string());
@@ -178,3 +314,14 @@ fn convert_column_value() {
         "expected a `ColumnValue::Int` value but found `String(\"Yes\")`"
     );
 }
+
+#[test]
+fn write_column_value() {
+    assert_eq!(42i32.column_value(), Some(ColumnValue::Int(42)));
+    assert_eq!(
+        "Yes".to_string().column_value(),
+        Some(ColumnValue::String("Yes"))
+    );
+    assert_eq!(Some(1.5f64).column_value(), Some(ColumnValue::Double(1.5)));
+    assert_eq!(None::<u8>.column_value(), None);
+}
@@ -0,0 +1,521 @@
+use crate::error::{GeozeroError, Result};
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnType, ColumnValue, PropertyProcessor};
+
+/// Simple Features geometry type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeometryType {
+    /// Mixed geometry types
+    Unknown,
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    CircularString,
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    PolyhedralSurface,
+    Tin,
+    Triangle,
+}
+
+/// Property column of a dataset.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnSchema {
+    pub name: String,
+    /// Type of all values. Mixed integer types are widened to `Long`, mixed numeric
+    /// types to `Double` and other mixed types to `String`.
+    pub column_type: ColumnType,
+    /// Column is missing in some features
+    pub nullable: bool,
+}
+
+/// Column names and types, geometry type and dimensions of a dataset.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DatasetSchema {
+    pub name: Option<String>,
+    /// Columns in order of first appearance
+    pub columns: Vec<ColumnSchema>,
+    /// Type of top-level geometries, `GeometryType::Unknown` for mixed types and
+    /// `None` when the dataset contains no geometries
+    pub geometry_type: Option<GeometryType>,
+    pub dims: CoordDimensions,
+    pub srid: Option<i32>,
+    pub feature_count: u64,
+}
+
+impl DatasetSchema {
+    /// Column with name `name`.
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|col| col.name == name)
+    }
+    /// Combine with the schema of another dataset, e.g. for appending its features.
+    ///
+    /// Columns are united, columns missing in one of the datasets become nullable.
+    /// Geometry types must be equal, single and multi types of the same kind are combined
+    /// to the multi type. Datasets with different SRIDs are rejected.
+    pub fn merge(&mut self, other: &DatasetSchema) -> Result<()> {
+        self.geometry_type = merge_geometry_types(self.geometry_type, other.geometry_type)
+            .ok_or_else(|| {
+                GeozeroError::Dataset(format!(
+                    "incompatible geometry types {:?} and {:?}",
+                    self.geometry_type, other.geometry_type
+                ))
+            })?;
+        self.srid = match (self.srid, other.srid) {
+            (Some(a), Some(b)) if a != b => {
+                return Err(GeozeroError::Dataset(format!(
+                    "different SRIDs {} and {}",
+                    a, b
+                )))
+            }
+            (a, b) => a.or(b),
+        };
+        for col in &mut self.columns {
+            if other.column(&col.name).is_none() && other.feature_count > 0 {
+                col.nullable = true;
+            }
+        }
+        for other_col in &other.columns {
+            match self
+                .columns
+                .iter_mut()
+                .find(|col| col.name == other_col.name)
+            {
+                Some(col) => {
+                    col.column_type = merge_types(col.column_type, other_col.column_type);
+                    col.nullable |= other_col.nullable;
+                }
+                None => self.columns.push(ColumnSchema {
+                    nullable: other_col.nullable || self.feature_count > 0,
+                    ..other_col.clone()
+                }),
+            }
+        }
+        if self.name.is_none() {
+            self.name = other.name.clone();
+        }
+        self.dims.z |= other.dims.z;
+        self.dims.m |= other.dims.m;
+        self.feature_count += other.feature_count;
+        Ok(())
+    }
+}
+
+/// Common type of geometry types `a` and `b`, `None` if they are incompatible.
+fn merge_geometry_types(
+    a: Option<GeometryType>,
+    b: Option<GeometryType>,
+) -> Option<Option<GeometryType>> {
+    use GeometryType::*;
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => return Some(a.or(b)),
+    };
+    let merged = match (a, b) {
+        _ if a == b => a,
+        (Unknown, _) | (_, Unknown) => Unknown,
+        (Point, MultiPoint) | (MultiPoint, Point) => MultiPoint,
+        (LineString, MultiLineString) | (MultiLineString, LineString) => MultiLineString,
+        (Polygon, MultiPolygon) | (MultiPolygon, Polygon) => MultiPolygon,
+        _ => return None,
+    };
+    Some(Some(merged))
+}
+
+/// Descriptive metadata of a property column, e.g. from a FlatGeobuf header.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ColumnMetadata {
+    pub name: String,
+    pub column_type: Option<ColumnType>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Maximal length of values
+    pub width: Option<i32>,
+    /// Number of significant digits
+    pub precision: Option<i32>,
+    /// Number of digits after the decimal point
+    pub scale: Option<i32>,
+    pub nullable: bool,
+    pub unique: bool,
+    pub primary_key: bool,
+    /// Format specific metadata, e.g. JSON text
+    pub metadata: Option<String>,
+}
+
+/// Descriptive metadata of a dataset.
+///
+/// Emitted with [FeatureProcessor::dataset_metadata] by readers of formats with dataset
+/// headers (e.g. GeoPackage), so that writers can preserve them in conversions.
+/// FlatGeobuf headers are only passed through when the reader of the `flatgeobuf` crate
+/// emits this event.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DatasetMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// CRS definition as WKT
+    pub crs_wkt: Option<String>,
+    /// Node size of spatial index
+    pub index_node_size: Option<u16>,
+    pub columns: Vec<ColumnMetadata>,
+    /// Format specific metadata, e.g. JSON text
+    pub metadata: Option<String>,
+}
+
+impl DatasetMetadata {
+    /// Metadata of column with name `name`.
+    pub fn column(&self, name: &str) -> Option<&ColumnMetadata> {
+        self.columns.iter().find(|col| col.name == name)
+    }
+}
+
+fn is_integer(t: ColumnType) -> bool {
+    use ColumnType::*;
+    matches!(t, Byte | UByte | Short | UShort | Int | UInt | Long | ULong)
+}
+
+pub(crate) fn merge_types(a: ColumnType, b: ColumnType) -> ColumnType {
+    let is_float = |t| matches!(t, ColumnType::Float | ColumnType::Double);
+    if a == b {
+        a
+    } else if is_integer(a) && is_integer(b) {
+        ColumnType::Long
+    } else if (is_integer(a) || is_float(a)) && (is_integer(b) || is_float(b)) {
+        ColumnType::Double
+    } else {
+        ColumnType::String
+    }
+}
+
+/// Processor collecting the [DatasetSchema] of processed features.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::{ColumnType, GeometryType, GeozeroDatasource, SchemaProcessor};
+///
+/// let json = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {"name": "b", "pop": 10}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+/// ]}"#;
+/// let mut processor = SchemaProcessor::new();
+/// GeoJson(json).process(&mut processor).unwrap();
+/// let schema = processor.schema();
+/// assert_eq!(schema.geometry_type, Some(GeometryType::Point));
+/// assert_eq!(schema.column("pop").unwrap().column_type, ColumnType::Long);
+/// assert!(schema.column("pop").unwrap().nullable);
+/// ```
+#[derive(Default)]
+pub struct SchemaProcessor {
+    schema: DatasetSchema,
+    /// Nesting level of current geometry
+    depth: usize,
+    /// Columns present in current feature
+    present: Vec<bool>,
+}
+
+impl SchemaProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Schema of features processed so far.
+    pub fn schema(&self) -> DatasetSchema {
+        self.schema.clone()
+    }
+    /// Consume processor and return schema.
+    pub fn into_schema(self) -> DatasetSchema {
+        self.schema
+    }
+
+    fn begin(&mut self, geometry_type: GeometryType) {
+        if self.depth == 0 {
+            self.schema.geometry_type = match self.schema.geometry_type {
+                None => Some(geometry_type),
+                Some(t) if t == geometry_type => Some(t),
+                Some(_) => Some(GeometryType::Unknown),
+            };
+        }
+        self.depth += 1;
+    }
+    fn end(&mut self) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        Ok(())
+    }
+    fn coord(&mut self, z: Option<f64>, m: Option<f64>) -> Result<()> {
+        self.schema.dims.z |= z.is_some();
+        self.schema.dims.m |= m.is_some();
+        Ok(())
+    }
+}
+
+impl GeomProcessor for SchemaProcessor {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn multi_dim(&self) -> bool {
+        true
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if srid.is_some() {
+            self.schema.srid = srid;
+        }
+        Ok(())
+    }
+    fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        _x: f64,
+        _y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.coord(z, m)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Point);
+        self.end()
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Point);
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::MultiPoint);
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::LineString);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::MultiLineString);
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Polygon);
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::MultiPolygon);
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::GeometryCollection);
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::CircularString);
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::CompoundCurve);
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::CurvePolygon);
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::MultiCurve);
+        Ok(())
+    }
+    fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::MultiSurface);
+        Ok(())
+    }
+    fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Triangle);
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::PolyhedralSurface);
+        Ok(())
+    }
+    fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Tin);
+        Ok(())
+    }
+    fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+}
+
+impl PropertyProcessor for SchemaProcessor {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let column_type = value.column_type();
+        match self.schema.columns.iter().position(|col| col.name == name) {
+            Some(i) => {
+                let col = &mut self.schema.columns[i];
+                col.column_type = merge_types(col.column_type, column_type);
+                self.present[i] = true;
+            }
+            None => {
+                self.schema.columns.push(ColumnSchema {
+                    name: name.to_string(),
+                    column_type,
+                    // missing in previous features
+                    nullable: self.schema.feature_count > 0,
+                });
+                self.present.push(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for SchemaProcessor {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.schema.name = name.map(ToString::to_string);
+        Ok(())
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.srid(srid)
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.depth = 0;
+        self.present.iter_mut().for_each(|present| *present = false);
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        for (col, present) in self.schema.columns.iter_mut().zip(&self.present) {
+            col.nullable |= !present;
+        }
+        self.schema.feature_count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn geojson_schema() -> Result<()> {
+        let json = r#"{"type": "FeatureCollection", "name": "places", "features": [
+            {"type": "Feature", "properties": {"name": "a", "pop": 10}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+            {"type": "Feature", "properties": {"name": "b", "pop": 1.5, "capital": true}, "geometry": {"type": "Point", "coordinates": [2, 2, 5]}},
+            {"type": "Feature", "properties": {"name": "c", "pop": "many"}, "geometry": {"type": "LineString", "coordinates": [[1, 1], [2, 2]]}}
+        ]}"#;
+        let schema = GeoJson(json).schema()?;
+        assert_eq!(schema.feature_count, 3);
+        assert_eq!(schema.geometry_type, Some(GeometryType::Unknown));
+        assert!(schema.dims.z && !schema.dims.m);
+        let columns: Vec<_> = schema
+            .columns
+            .iter()
+            .map(|col| (col.name.as_str(), col.column_type, col.nullable))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                ("name", ColumnType::String, false),
+                ("pop", ColumnType::String, false),
+                ("capital", ColumnType::Bool, true),
+            ]
+        );
+
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"pop": 10}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+            {"type": "Feature", "properties": {"pop": 1.5}, "geometry": null}
+        ]}"#;
+        let schema = GeoJson(json).schema()?;
+        assert_eq!(schema.geometry_type, Some(GeometryType::Point));
+        assert_eq!(
+            schema.column("pop").unwrap().column_type,
+            ColumnType::Double
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge_schemas() -> Result<()> {
+        let mut schema = GeoJson(
+            r#"{"type": "Feature", "properties": {"name": "a", "pop": 10}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#,
+        )
+        .schema()?;
+        let other = GeoJson(
+            r#"{"type": "Feature", "properties": {"pop": 1.5, "area": 3}, "geometry": {"type": "MultiPoint", "coordinates": [[1, 1]]}}"#,
+        )
+        .schema()?;
+        schema.merge(&other)?;
+        assert_eq!(schema.feature_count, 2);
+        assert_eq!(schema.geometry_type, Some(GeometryType::MultiPoint));
+        let columns: Vec<_> = schema
+            .columns
+            .iter()
+            .map(|col| (col.name.as_str(), col.column_type, col.nullable))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                ("name", ColumnType::String, true),
+                ("pop", ColumnType::Double, false),
+                ("area", ColumnType::Long, true),
+            ]
+        );
+
+        let lines = GeoJson(
+            r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 1], [2, 2]]}}"#,
+        )
+        .schema()?;
+        assert!(schema.merge(&lines).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+//! Conversion of feature properties from and to serde types.
+
+use crate::error::{GeozeroError, Result};
+use crate::{ColumnValue, PropertyProcessor};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// Property processor collecting properties for deserialization with serde.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{geojson::GeoJson, FeatureProcessor, GeomProcessor, GeozeroDatasource};
+/// use geozero::{ColumnValue, PropertyProcessor, SerdeProperties, error::Result};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, PartialEq, Debug)]
+/// struct City {
+///     name: String,
+///     population: u64,
+/// }
+///
+/// #[derive(Default)]
+/// struct Cities {
+///     props: SerdeProperties,
+///     cities: Vec<City>,
+/// }
+///
+/// impl GeomProcessor for Cities {}
+/// impl PropertyProcessor for Cities {
+///     fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+///         self.props.property(idx, name, value)
+///     }
+/// }
+/// impl FeatureProcessor for Cities {
+///     fn properties_begin(&mut self) -> Result<()> {
+///         self.props.clear();
+///         Ok(())
+///     }
+///     fn properties_end(&mut self) -> Result<()> {
+///         self.cities.push(self.props.deserialize()?);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut cities = Cities::default();
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "Bern", "population": 134794}, "geometry": null}"#)
+///     .process(&mut cities)
+///     .unwrap();
+/// assert_eq!(cities.cities, vec![City { name: "Bern".to_string(), population: 134794 }]);
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct SerdeProperties {
+    map: Map<String, Value>,
+}
+
+impl SerdeProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Remove collected properties.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+    /// Collected properties as JSON object.
+    pub fn to_value(&self) -> Value {
+        Value::Object(self.map.clone())
+    }
+    /// Deserialize collected properties.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(&Value::Object(self.map.clone()))
+            .map_err(|e| GeozeroError::Properties(e.to_string()))
+    }
+}
+
+impl PropertyProcessor for SerdeProperties {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.map.insert(name.to_string(), json_value(value));
+        Ok(false)
+    }
+}
+
+/// Convert property value to JSON value.
+fn json_value(value: &ColumnValue) -> Value {
+    match *value {
+        ColumnValue::Byte(v) => Value::from(v),
+        ColumnValue::UByte(v) => Value::from(v),
+        ColumnValue::Bool(v) => Value::from(v),
+        ColumnValue::Short(v) => Value::from(v),
+        ColumnValue::UShort(v) => Value::from(v),
+        ColumnValue::Int(v) => Value::from(v),
+        ColumnValue::UInt(v) => Value::from(v),
+        ColumnValue::Long(v) => Value::from(v),
+        ColumnValue::ULong(v) => Value::from(v),
+        ColumnValue::Float(v) => Number::from_f64(v as f64).map_or(Value::Null, Value::Number),
+        ColumnValue::Double(v) => Number::from_f64(v).map_or(Value::Null, Value::Number),
+        ColumnValue::String(v) | ColumnValue::DateTime(v) => Value::from(v),
+        ColumnValue::Json(v) => serde_json::from_str(v).unwrap_or_else(|_| Value::from(v)),
+        ColumnValue::Binary(v) => Value::from(v),
+    }
+}
+
+/// Process fields of a serializable struct or map as properties.
+///
+/// Null values are omitted, arrays and objects are passed as JSON values.
+pub fn process_serde_properties<T: Serialize, P: PropertyProcessor>(
+    properties: &T,
+    processor: &mut P,
+) -> Result<bool> {
+    let map = match serde_json::to_value(properties) {
+        Ok(Value::Object(map)) => map,
+        Ok(_) => {
+            return Err(GeozeroError::Properties(
+                "struct or map expected".to_string(),
+            ))
+        }
+        Err(e) => return Err(GeozeroError::Properties(e.to_string())),
+    };
+    let mut idx = 0;
+    for (name, value) in &map {
+        let json;
+        let value = match value {
+            Value::Null => continue,
+            Value::Bool(v) => ColumnValue::Bool(*v),
+            Value::Number(v) if v.is_i64() => ColumnValue::Long(v.as_i64().unwrap()),
+            Value::Number(v) if v.is_u64() => ColumnValue::ULong(v.as_u64().unwrap()),
+            Value::Number(v) => ColumnValue::Double(v.as_f64().unwrap_or(f64::NAN)),
+            Value::String(v) => ColumnValue::String(v),
+            Value::Array(_) | Value::Object(_) => {
+                json = value.to_string();
+                ColumnValue::Json(&json)
+            }
+        };
+        if processor.property(idx, name, &value)? {
+            return Ok(true);
+        }
+        idx += 1;
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Props {
+        name: String,
+        count: i32,
+        ratio: f64,
+        tags: Vec<String>,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        let props = Props {
+            name: "a".to_string(),
+            count: -3,
+            ratio: 0.5,
+            tags: vec!["x".to_string(), "y".to_string()],
+            note: None,
+        };
+        let mut collected = SerdeProperties::new();
+        process_serde_properties(&props, &mut collected)?;
+        assert_eq!(
+            collected.to_value().to_string(),
+            r#"{"count":-3,"name":"a","ratio":0.5,"tags":["x","y"]}"#
+        );
+        assert_eq!(collected.deserialize::<Props>()?, props);
+        Ok(())
+    }
+
+    #[test]
+    fn type_error() {
+        let mut collected = SerdeProperties::new();
+        collected
+            .property(0, "count", &ColumnValue::String("many"))
+            .unwrap();
+        assert!(collected.deserialize::<Props>().is_err());
+        assert!(process_serde_properties(&42, &mut collected).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+//! SQL dump conversions.
+mod sql_writer;
+
+pub use sql_writer::*;
@@ -0,0 +1,449 @@
+use crate::error::Result;
+use crate::processing::record::FeatureRecorder;
+use crate::wkb::{WkbDialect, WkbWriter};
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// SQL dialect of [SqlWriter] output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// PostgreSQL with PostGIS, geometries as hex encoded EWKB
+    Postgis,
+    /// SQLite with SpatiaLite, geometries created with `GeomFromWKB`
+    Spatialite,
+    /// MySQL, geometries created with `ST_GeomFromWKB`
+    Mysql,
+}
+
+/// SQL dump writer.
+///
+/// Writes a `CREATE TABLE` statement with the property columns of the first feature and
+/// an `INSERT` statement for each feature. Properties first appearing in later features
+/// are added with `ALTER TABLE`. Batches of features are wrapped into transactions.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::sql::{SqlDialect, SqlWriter};
+/// use geozero::GeozeroDatasource;
+///
+/// let mut sql = Vec::new();
+/// let mut writer = SqlWriter::new(&mut sql, SqlDialect::Postgis, "places");
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "Zurich"}, "geometry": {"type": "Point", "coordinates": [8.5, 47.4]}}"#)
+///     .process(&mut writer)
+///     .unwrap();
+/// let sql = String::from_utf8(sql).unwrap();
+/// assert!(sql.starts_with(r#"CREATE TABLE "places" ("geom" geometry, "name" TEXT);"#));
+/// ```
+pub struct SqlWriter<'a, W: Write> {
+    pub dims: CoordDimensions,
+    /// SRID of geometries, defaults to the dataset SRID
+    pub srid: Option<i32>,
+    /// Name of geometry column (default: `geom`)
+    pub geometry_column: String,
+    dialect: SqlDialect,
+    table_name: String,
+    out: &'a mut W,
+    /// Columns of created table, `None` before table creation
+    columns: Option<Vec<String>>,
+    properties: Vec<(String, String, &'static str)>,
+    geometry: FeatureRecorder,
+}
+
+impl<'a, W: Write> SqlWriter<'a, W> {
+    pub fn new(out: &'a mut W, dialect: SqlDialect, table_name: &str) -> Self {
+        SqlWriter {
+            dims: CoordDimensions::default(),
+            srid: None,
+            geometry_column: "geom".to_string(),
+            dialect,
+            table_name: table_name.to_string(),
+            out,
+            columns: None,
+            properties: Vec::new(),
+            geometry: FeatureRecorder::default(),
+        }
+    }
+
+    fn quote_ident(&self, name: &str) -> String {
+        match self.dialect {
+            SqlDialect::Mysql => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        let value = value.replace('\'', "''");
+        match self.dialect {
+            SqlDialect::Mysql => format!("'{}'", value.replace('\\', "\\\\")),
+            _ => format!("'{}'", value),
+        }
+    }
+
+    fn hex_literal(&self, bytes: &[u8]) -> String {
+        let hex: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+        match self.dialect {
+            SqlDialect::Postgis => format!("decode('{}', 'hex')", hex),
+            _ => format!("X'{}'", hex),
+        }
+    }
+
+    /// Column type and SQL literal of property value
+    fn property_sql(&self, value: &ColumnValue) -> (String, &'static str) {
+        let (pg, sqlite, mysql) = match value {
+            ColumnValue::Bool(_) => ("BOOLEAN", "INTEGER", "BOOLEAN"),
+            ColumnValue::Byte(_) | ColumnValue::UByte(_) | ColumnValue::Short(_) => {
+                ("SMALLINT", "INTEGER", "SMALLINT")
+            }
+            ColumnValue::UShort(_) | ColumnValue::Int(_) => ("INTEGER", "INTEGER", "INT"),
+            ColumnValue::UInt(_) | ColumnValue::Long(_) => ("BIGINT", "INTEGER", "BIGINT"),
+            ColumnValue::ULong(_) => ("NUMERIC", "INTEGER", "BIGINT UNSIGNED"),
+            ColumnValue::Float(_) => ("REAL", "REAL", "FLOAT"),
+            ColumnValue::Double(_) => ("DOUBLE PRECISION", "REAL", "DOUBLE"),
+            ColumnValue::String(_) => ("TEXT", "TEXT", "TEXT"),
+            ColumnValue::Json(_) => ("JSONB", "TEXT", "JSON"),
+            ColumnValue::DateTime(_) => ("TIMESTAMPTZ", "TEXT", "DATETIME"),
+            ColumnValue::Binary(_) => ("BYTEA", "BLOB", "BLOB"),
+        };
+        let sql_type = match self.dialect {
+            SqlDialect::Postgis => pg,
+            SqlDialect::Spatialite => sqlite,
+            SqlDialect::Mysql => mysql,
+        };
+        let literal = match value {
+            ColumnValue::Bool(v) => match self.dialect {
+                SqlDialect::Spatialite => (*v as u8).to_string(),
+                _ => v.to_string().to_uppercase(),
+            },
+            ColumnValue::Float(v) if !v.is_finite() => "NULL".to_string(),
+            ColumnValue::Double(v) if !v.is_finite() => "NULL".to_string(),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                self.quote_string(v)
+            }
+            ColumnValue::Binary(v) => self.hex_literal(v),
+            _ => value.to_string(),
+        };
+        (literal, sql_type)
+    }
+
+    /// SQL expression of recorded geometry
+    fn geometry_sql(&mut self) -> Result<String> {
+        if self.geometry.is_empty() {
+            return Ok("NULL".to_string());
+        }
+        let wkb_dialect = match self.dialect {
+            SqlDialect::Postgis => WkbDialect::Ewkb,
+            _ => WkbDialect::Wkb,
+        };
+        let mut wkb = Vec::new();
+        {
+            let mut writer = WkbWriter::new(&mut wkb, wkb_dialect);
+            writer.dims = self.dims;
+            writer.srid = self.srid;
+            self.geometry.replay(&mut writer)?;
+        }
+        let hex: String = wkb.iter().map(|b| format!("{:02X}", b)).collect();
+        let srid = self.srid.unwrap_or(0);
+        Ok(match self.dialect {
+            SqlDialect::Postgis => format!("'{}'::geometry", hex),
+            SqlDialect::Spatialite => format!("GeomFromWKB(X'{}', {})", hex, srid),
+            SqlDialect::Mysql => format!("ST_GeomFromWKB(X'{}', {})", hex, srid),
+        })
+    }
+
+    fn create_table(&mut self) -> Result<()> {
+        let table = self.quote_ident(&self.table_name);
+        let geom = self.quote_ident(&self.geometry_column);
+        let mut columns = Vec::new();
+        match self.dialect {
+            SqlDialect::Postgis => match self.srid {
+                Some(srid) => columns.push(format!("{} geometry(Geometry, {})", geom, srid)),
+                None => columns.push(format!("{} geometry", geom)),
+            },
+            SqlDialect::Mysql => columns.push(format!("{} GEOMETRY", geom)),
+            // added with AddGeometryColumn
+            SqlDialect::Spatialite => {}
+        }
+        for (name, _, sql_type) in &self.properties {
+            columns.push(format!("{} {}", self.quote_ident(name), sql_type));
+        }
+        writeln!(self.out, "CREATE TABLE {} ({});", table, columns.join(", "))?;
+        if self.dialect == SqlDialect::Spatialite {
+            let dims = match (self.dims.z, self.dims.m) {
+                (false, false) => "XY",
+                (true, false) => "XYZ",
+                (false, true) => "XYM",
+                (true, true) => "XYZM",
+            };
+            let table = self.quote_string(&self.table_name);
+            let geom = self.quote_string(&self.geometry_column);
+            writeln!(
+                self.out,
+                "SELECT AddGeometryColumn({}, {}, {}, 'GEOMETRY', '{}');",
+                table,
+                geom,
+                self.srid.unwrap_or(0),
+                dims
+            )?;
+        }
+        self.columns = Some(self.properties.iter().map(|p| p.0.clone()).collect());
+        Ok(())
+    }
+}
+
+impl<W: Write> FeatureProcessor for SqlWriter<'_, W> {
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        self.columns = None;
+        Ok(())
+    }
+    /// Use dataset SRID, if no SRID is set
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.srid.is_none() {
+            self.srid = srid;
+        }
+        Ok(())
+    }
+    fn batch_begin(&mut self, _size: usize) -> Result<()> {
+        writeln!(self.out, "BEGIN;")?;
+        Ok(())
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        writeln!(self.out, "COMMIT;")?;
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.properties.clear();
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if self.columns.is_none() {
+            self.create_table()?;
+        }
+        let table = self.quote_ident(&self.table_name);
+        let new_columns: Vec<(String, &'static str)> = self
+            .properties
+            .iter()
+            .filter(|(name, _, _)| !self.columns.as_ref().unwrap().contains(name))
+            .map(|(name, _, sql_type)| (name.clone(), *sql_type))
+            .collect();
+        for (name, sql_type) in new_columns {
+            let column = self.quote_ident(&name);
+            writeln!(
+                self.out,
+                "ALTER TABLE {} ADD COLUMN {} {};",
+                table, column, sql_type
+            )?;
+            self.columns.as_mut().unwrap().push(name);
+        }
+        let mut columns = vec![self.quote_ident(&self.geometry_column)];
+        let mut values = vec![self.geometry_sql().map_err(|e| e.in_feature(idx))?];
+        for (name, literal, _) in &self.properties {
+            columns.push(self.quote_ident(name));
+            values.push(literal.clone());
+        }
+        writeln!(
+            self.out,
+            "INSERT INTO {} ({}) VALUES ({});",
+            table,
+            columns.join(", "),
+            values.join(", ")
+        )?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for SqlWriter<'_, W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let (literal, sql_type) = self.property_sql(value);
+        self.properties.push((name.to_string(), literal, sql_type));
+        Ok(false)
+    }
+}
+
+impl<W: Write> GeomProcessor for SqlWriter<'_, W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geometry.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geometry.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geometry.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geometry.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geometry.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geometry.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geometry.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geometry.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.tin_end(idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    const FEATURES: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "O'Hare", "rank": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"name": "B", "open": true}, "geometry": null}
+    ]}"#;
+
+    fn sql(dialect: SqlDialect) -> Result<String> {
+        let mut out = Vec::new();
+        let mut writer = SqlWriter::new(&mut out, dialect, "airports");
+        writer.srid = Some(4326);
+        GeoJson(FEATURES).process(&mut writer)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn postgis() -> Result<()> {
+        assert_eq!(
+            sql(SqlDialect::Postgis)?,
+            r#"CREATE TABLE "airports" ("geom" geometry(Geometry, 4326), "name" TEXT, "rank" BIGINT);
+INSERT INTO "airports" ("geom", "name", "rank") VALUES ('0101000020E6100000000000000000F03F0000000000000040'::geometry, 'O''Hare', 1);
+ALTER TABLE "airports" ADD COLUMN "open" BOOLEAN;
+INSERT INTO "airports" ("geom", "name", "open") VALUES (NULL, 'B', TRUE);
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn spatialite() -> Result<()> {
+        assert_eq!(
+            sql(SqlDialect::Spatialite)?,
+            r#"CREATE TABLE "airports" ("name" TEXT, "rank" INTEGER);
+SELECT AddGeometryColumn('airports', 'geom', 4326, 'GEOMETRY', 'XY');
+INSERT INTO "airports" ("geom", "name", "rank") VALUES (GeomFromWKB(X'0101000000000000000000F03F0000000000000040', 4326), 'O''Hare', 1);
+ALTER TABLE "airports" ADD COLUMN "open" INTEGER;
+INSERT INTO "airports" ("geom", "name", "open") VALUES (NULL, 'B', 1);
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mysql() -> Result<()> {
+        let mut out = Vec::new();
+        let mut writer = SqlWriter::new(&mut out, SqlDialect::Mysql, "t");
+        writer.batch_begin(10)?;
+        writer.feature_begin(0)?;
+        writer.property(0, "path", &ColumnValue::String(r"C:\tmp"))?;
+        writer.property(1, "data", &ColumnValue::Binary(&[1, 255]))?;
+        writer.feature_end(0)?;
+        writer.batch_end()?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"BEGIN;
+CREATE TABLE `t` (`geom` GEOMETRY, `path` TEXT, `data` BLOB);
+INSERT INTO `t` (`geom`, `path`, `data`) VALUES (NULL, 'C:\\tmp', X'01FF');
+COMMIT;
+"#
+        );
+        Ok(())
+    }
+}
@@ -22,6 +22,11 @@ impl<'a, W: Write> SvgWriter<'a, W> {
             size: None,
         }
     }
+    /// Flush and return the output.
+    pub fn into_inner(self) -> Result<&'a mut W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
     pub fn set_dimensions(
         &mut self,
         xmin: f64,
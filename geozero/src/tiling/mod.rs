@@ -0,0 +1,29 @@
+//! Vector tile pyramid generation.
+//!
+//! Features of any [GeozeroDatasource](crate::GeozeroDatasource) with geographic WGS84
+//! coordinates are clipped to Web Mercator tiles of a zoom range, simplified and encoded
+//! as Mapbox Vector Tiles, which are written into a [TileSink].
+//!
+//! # Usage example:
+//!
+//! ```
+//! use geozero::geojson::GeoJsonString;
+//! use geozero::tiling::{generate_tiles, TileDirectory, TilingOptions};
+//!
+//! # fn tile() -> geozero::error::Result<()> {
+//! let mut geojson = GeoJsonString(std::fs::read_to_string("places.json")?);
+//! let options = TilingOptions {
+//!     max_zoom: 8,
+//!     layer_name: "places".to_string(),
+//!     ..Default::default()
+//! };
+//! generate_tiles(&mut geojson, &options, &mut TileDirectory::new("tiles"))?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod sink;
+mod tiler;
+
+pub use sink::*;
+pub use tiler::*;
@@ -0,0 +1,46 @@
+use crate::error::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// Destination of encoded tiles.
+pub trait TileSink {
+    /// Write encoded tile `z/x/y` (XYZ tiling scheme).
+    fn write_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()>;
+    /// Called after all tiles are written.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Tile directory with files `{z}/{x}/{y}.{extension}`.
+pub struct TileDirectory {
+    path: PathBuf,
+    /// File extension (default: `pbf`)
+    pub extension: String,
+}
+
+impl TileDirectory {
+    /// Tile directory `path`, which is created if missing.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        TileDirectory {
+            path: path.into(),
+            extension: "pbf".to_string(),
+        }
+    }
+}
+
+impl TileSink for TileDirectory {
+    fn write_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()> {
+        let dir = self.path.join(z.to_string()).join(x.to_string());
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(format!("{}.{}", y, self.extension)), data)?;
+        Ok(())
+    }
+}
+
+impl TileSink for Vec<(u8, u32, u32, Vec<u8>)> {
+    fn write_tile(&mut self, z: u8, x: u32, y: u32, data: &[u8]) -> Result<()> {
+        self.push((z, x, y, data.to_vec()));
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error::{GeozeroError, Result};
+use crate::mvt::{MvtLayerWriter, Tile};
+use crate::processing::record::FeatureRecorder;
+use crate::processing::{ExtentProcessor, Simplify, TileClip};
+use crate::tiling::TileSink;
+use crate::GeozeroDatasource;
+use prost::Message;
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+
+/// Maximal latitude of Web Mercator tiles
+const MAX_LAT: f64 = 85.051_128_779_806_59;
+
+/// Options for [generate_tiles].
+#[derive(Clone, Debug)]
+pub struct TilingOptions {
+    /// Lowest generated zoom level (default: 0)
+    pub min_zoom: u8,
+    /// Highest generated zoom level (default: 14)
+    pub max_zoom: u8,
+    /// Name of the MVT layer (default: `layer`)
+    pub layer_name: String,
+    /// Tile extent in tile coordinates (default: 4096)
+    pub extent: u32,
+    /// Buffer around tiles in tile coordinates (default: 64)
+    pub buffer: u32,
+    /// Simplification tolerance in tile coordinates below `max_zoom` (default: 1.0)
+    pub simplify: f64,
+}
+
+impl Default for TilingOptions {
+    fn default() -> Self {
+        TilingOptions {
+            min_zoom: 0,
+            max_zoom: 14,
+            layer_name: "layer".to_string(),
+            extent: 4096,
+            buffer: 64,
+            simplify: 1.0,
+        }
+    }
+}
+
+/// Generate a tile pyramid from `datasource` and write it into `sink`.
+///
+/// All features are buffered in memory. For each zoom level, every feature is assigned
+/// to the tiles intersecting its extent including the tile buffer. Features are then
+/// clipped with [TileClip], simplified with [Simplify] (except on `max_zoom`) and encoded
+/// with an [MvtLayerWriter]. Tiles without features are not written.
+///
+/// Returns the number of written tiles.
+pub fn generate_tiles<D: GeozeroDatasource, S: TileSink>(
+    datasource: &mut D,
+    options: &TilingOptions,
+    sink: &mut S,
+) -> Result<u64> {
+    if options.min_zoom > options.max_zoom || options.max_zoom > 30 {
+        return Err(GeozeroError::Dataset(format!(
+            "invalid zoom range {}-{}",
+            options.min_zoom, options.max_zoom
+        )));
+    }
+    let mut recorder = FeatureRecorder::default();
+    datasource.process(&mut recorder)?;
+    let mut features = Vec::new();
+    for feature in recorder.into_features() {
+        let mut extent = ExtentProcessor::new();
+        feature.replay(&mut extent)?;
+        if let Some(extent) = extent.extent() {
+            features.push((extent.bbox(), feature));
+        }
+    }
+
+    let mut count = 0;
+    for z in options.min_zoom..=options.max_zoom {
+        let mut tiles: BTreeMap<(u32, u32), Vec<usize>> = BTreeMap::new();
+        for (i, (bbox, _)) in features.iter().enumerate() {
+            let [minx, miny, maxx, maxy] = tile_range(z, bbox, options);
+            for x in minx..=maxx {
+                for y in miny..=maxy {
+                    tiles.entry((x, y)).or_default().push(i);
+                }
+            }
+        }
+        let tolerance = if z < options.max_zoom {
+            options.simplify
+        } else {
+            0.0
+        };
+        for ((x, y), indices) in tiles {
+            let writer = MvtLayerWriter::new(&options.layer_name, options.extent);
+            let mut clip = TileClip::new(z, x, y, Simplify::new(tolerance, writer));
+            clip.extent = options.extent;
+            clip.buffer = options.buffer;
+            for i in indices {
+                features[i].1.replay(&mut clip)?;
+            }
+            let layer = clip.into_inner().into_inner().into_layer();
+            if layer.features.is_empty() {
+                continue;
+            }
+            let tile = Tile {
+                layers: vec![layer],
+            };
+            sink.write_tile(z, x, y, &tile.encode_to_vec())?;
+            count += 1;
+        }
+    }
+    sink.finish()?;
+    Ok(count)
+}
+
+/// Range `[minx, miny, maxx, maxy]` of tiles on zoom level `z` intersecting `bbox`.
+fn tile_range(z: u8, bbox: &[f64; 4], options: &TilingOptions) -> [u32; 4] {
+    let n = f64::powi(2.0, z as i32);
+    let buffer = options.buffer as f64 / options.extent as f64;
+    let tile = |v: f64| (v.floor().max(0.0) as u32).min(n as u32 - 1);
+    let x = |lon: f64| (lon + 180.0) / 360.0 * n;
+    let y = |lat: f64| {
+        let lat = lat.clamp(-MAX_LAT, MAX_LAT).to_radians();
+        (1.0 - lat.tan().asinh() / PI) / 2.0 * n
+    };
+    [
+        tile(x(bbox[0]) - buffer),
+        tile(y(bbox[3]) - buffer),
+        tile(x(bbox[2]) + buffer),
+        tile(y(bbox[1]) + buffer),
+    ]
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+
+    #[test]
+    fn pyramid() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [8.5, 47.3]}},
+            {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "LineString", "coordinates": [[-10, 10], [10, -10]]}}
+        ]}"#;
+        let options = TilingOptions {
+            max_zoom: 2,
+            layer_name: "test".to_string(),
+            ..Default::default()
+        };
+        let mut tiles: Vec<(u8, u32, u32, Vec<u8>)> = Vec::new();
+        let count = generate_tiles(&mut GeoJson(geojson), &options, &mut tiles)?;
+        assert_eq!(count, tiles.len() as u64);
+        let zxy: Vec<_> = tiles.iter().map(|(z, x, y, _)| (*z, *x, *y)).collect();
+        assert_eq!(
+            zxy,
+            [
+                (0, 0, 0),
+                (1, 0, 0),
+                (1, 0, 1),
+                (1, 1, 0),
+                (1, 1, 1),
+                (2, 1, 1),
+                (2, 1, 2),
+                (2, 2, 1),
+                (2, 2, 2)
+            ]
+        );
+        let tile = Tile::decode(tiles[0].3.as_slice()).unwrap();
+        assert_eq!(tile.layers[0].name, "test");
+        assert_eq!(tile.layers[0].features.len(), 2);
+        // Point only in tile 2/2/1
+        let tile = Tile::decode(tiles[7].3.as_slice()).unwrap();
+        assert_eq!(tile.layers[0].features.len(), 2);
+        let tile = Tile::decode(tiles[8].3.as_slice()).unwrap();
+        assert_eq!(tile.layers[0].features.len(), 1);
+        Ok(())
+    }
+}
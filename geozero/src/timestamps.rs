@@ -0,0 +1,84 @@
+//! Conversions between timestamps and the `t`/`tm` coordinate channels.
+#[cfg(feature = "with-kml")]
+use time::format_description::well_known::Rfc3339;
+#[cfg(feature = "with-gpx")]
+use time::Duration;
+use time::{Date, OffsetDateTime, UtcOffset};
+
+/// Nanoseconds since Unix epoch (`tm` channel)
+pub(crate) fn unix_nanos(dt: OffsetDateTime) -> Option<u64> {
+    let nanos = dt.unix_timestamp_nanos();
+    if nanos >= 0 && nanos <= u64::MAX as i128 {
+        Some(nanos as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "with-gpx")]
+pub(crate) fn from_unix_nanos(tm: u64) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(tm as i128).ok()
+}
+
+/// Parse RFC 3339 timestamp, like `2009-10-17T18:37:26Z`
+#[cfg(feature = "with-kml")]
+pub(crate) fn parse_rfc3339(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s.trim(), &Rfc3339).ok()
+}
+
+fn year_start(year: i32) -> Option<OffsetDateTime> {
+    Some(
+        Date::from_ordinal_date(year, 1)
+            .ok()?
+            .midnight()
+            .assume_utc(),
+    )
+}
+
+/// Decimal year (`t` channel)
+pub(crate) fn decimal_year(dt: OffsetDateTime) -> Option<f64> {
+    let dt = dt.to_offset(UtcOffset::UTC);
+    let year = dt.year();
+    let start = year_start(year)?;
+    let end = year_start(year + 1)?;
+    Some(year as f64 + (dt - start).as_seconds_f64() / (end - start).as_seconds_f64())
+}
+
+#[cfg(feature = "with-gpx")]
+pub(crate) fn from_decimal_year(t: f64) -> Option<OffsetDateTime> {
+    if !t.is_finite() {
+        return None;
+    }
+    let year = t.floor() as i32;
+    let start = year_start(year)?;
+    let end = year_start(year + 1)?;
+    let offset = (end - start).as_seconds_f64() * t.fract();
+    Some(start + Duration::seconds_f64(offset))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "with-gpx")]
+    fn conversions() {
+        let dt = OffsetDateTime::from_unix_timestamp(1255804646).unwrap(); // 2009-10-17T18:37:26Z
+        assert_eq!(unix_nanos(dt), Some(1255804646000000000));
+        assert_eq!(from_unix_nanos(1255804646000000000), Some(dt));
+
+        let t = decimal_year(dt).unwrap();
+        assert!((t - 2009.7939).abs() < 0.0001);
+        let roundtrip = from_decimal_year(t).unwrap();
+        assert!((roundtrip - dt).whole_seconds().abs() < 1);
+    }
+
+    #[test]
+    #[cfg(feature = "with-kml")]
+    fn rfc3339() {
+        let dt = OffsetDateTime::from_unix_timestamp(1255804646).unwrap();
+        assert_eq!(parse_rfc3339("2009-10-17T18:37:26Z"), Some(dt));
+        assert_eq!(parse_rfc3339("2009-10-17T20:37:26+02:00"), Some(dt));
+        assert_eq!(parse_rfc3339("2009-10-17"), None);
+    }
+}
@@ -0,0 +1,4 @@
+//! TopoJSON conversions.
+mod topojson_reader;
+
+pub use topojson_reader::*;
@@ -0,0 +1,469 @@
+use crate::error::{GeozeroError, Result};
+use crate::property_processor::process_json_property;
+use crate::{
+    FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry, PropertyProcessor,
+};
+use serde_json::map::Map;
+use serde_json::value::Value as JsonValue;
+use std::io::Read;
+
+/// TopoJSON String slice.
+pub struct TopoJson<'a>(pub &'a str);
+
+impl GeozeroGeometry for TopoJson<'_> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_topojson_geom(self.0.as_bytes(), processor)
+    }
+}
+
+impl GeozeroDatasource for TopoJson<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_topojson(self.0.as_bytes(), processor)
+    }
+}
+
+/// TopoJSON Reader.
+pub struct TopoJsonReader<'a, R: Read>(pub &'a mut R);
+
+impl<'a, R: Read> GeozeroDatasource for TopoJsonReader<'a, R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_topojson(&mut self.0, processor)
+    }
+}
+
+/// Read and process TopoJSON.
+///
+/// The geometries of all objects are processed as features.
+pub fn read_topojson<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let topology = Topology::from_reader(reader)?;
+    processor.dataset_begin(None)?;
+    let mut idx = 0;
+    for object in topology.objects.values() {
+        for geometry in feature_geometries(object) {
+            processor.feature_begin(idx)?;
+            if let Some(JsonValue::Object(properties)) = geometry.get("properties") {
+                processor.properties_begin()?;
+                process_properties(properties, processor)?;
+                processor.properties_end()?;
+            }
+            if has_geometry(geometry) {
+                processor.geometry_begin()?;
+                topology.process_geometry(geometry, 0, processor)?;
+                processor.geometry_end()?;
+            }
+            processor.feature_end(idx)?;
+            idx += 1;
+        }
+    }
+    processor.dataset_end()
+}
+
+/// Read and process TopoJSON geometries as geometry collection.
+pub fn read_topojson_geom<R: Read, P: GeomProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let topology = Topology::from_reader(reader)?;
+    let geometries: Vec<&JsonValue> = topology
+        .objects
+        .values()
+        .flat_map(feature_geometries)
+        .filter(|geometry| has_geometry(geometry))
+        .collect();
+    processor.geometrycollection_begin(geometries.len(), 0)?;
+    for (idx, geometry) in geometries.into_iter().enumerate() {
+        topology.process_geometry(geometry, idx, processor)?;
+    }
+    processor.geometrycollection_end(0)
+}
+
+/// Top-level GeometryCollection members are processed as separate features
+fn feature_geometries(object: &JsonValue) -> Vec<&JsonValue> {
+    match (object.get("type"), object.get("geometries")) {
+        (Some(JsonValue::String(t)), Some(JsonValue::Array(geometries)))
+            if t == "GeometryCollection" =>
+        {
+            geometries.iter().collect()
+        }
+        _ => vec![object],
+    }
+}
+
+fn has_geometry(geometry: &JsonValue) -> bool {
+    !matches!(geometry.get("type"), None | Some(JsonValue::Null))
+}
+
+fn json_error(e: serde_json::Error) -> GeozeroError {
+    GeozeroError::Dataset(format!("Invalid TopoJSON: {}", e))
+}
+
+fn invalid(msg: &str) -> GeozeroError {
+    GeozeroError::Geometry(format!("Invalid TopoJSON: {}", msg))
+}
+
+type Position = Vec<f64>;
+
+struct Transform {
+    scale: [f64; 2],
+    translate: [f64; 2],
+}
+
+impl Transform {
+    fn apply(&self, pos: &mut Position) {
+        pos[0] = pos[0] * self.scale[0] + self.translate[0];
+        pos[1] = pos[1] * self.scale[1] + self.translate[1];
+    }
+}
+
+/// Topology with decoded arcs
+struct Topology {
+    transform: Option<Transform>,
+    arcs: Vec<Vec<Position>>,
+    objects: Map<String, JsonValue>,
+}
+
+impl Topology {
+    fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let json: JsonValue = serde_json::from_reader(reader).map_err(json_error)?;
+        let mut json = match json {
+            JsonValue::Object(json) => json,
+            _ => return Err(invalid("expected Topology object")),
+        };
+        if json.get("type").and_then(|t| t.as_str()) != Some("Topology") {
+            return Err(invalid("expected Topology object"));
+        }
+        let transform = match json.get("transform") {
+            Some(transform) => {
+                let pair = |key: &str| -> Result<[f64; 2]> {
+                    let values = parse_position(transform.get(key))?;
+                    Ok([values[0], values[1]])
+                };
+                Some(Transform {
+                    scale: pair("scale")?,
+                    translate: pair("translate")?,
+                })
+            }
+            None => None,
+        };
+        let arcs = match json.get("arcs") {
+            Some(JsonValue::Array(arcs)) => arcs
+                .iter()
+                .map(|arc| decode_arc(arc, transform.as_ref()))
+                .collect::<Result<Vec<_>>>()?,
+            None => Vec::new(),
+            _ => return Err(invalid("arcs")),
+        };
+        let objects = match json.remove("objects") {
+            Some(JsonValue::Object(objects)) => objects,
+            _ => return Err(invalid("missing objects")),
+        };
+        Ok(Topology {
+            transform,
+            arcs,
+            objects,
+        })
+    }
+
+    /// Quantized position of Point and MultiPoint
+    fn position(&self, value: Option<&JsonValue>) -> Result<Position> {
+        let mut pos = parse_position(value)?;
+        if let Some(transform) = &self.transform {
+            transform.apply(&mut pos);
+        }
+        Ok(pos)
+    }
+
+    /// Concatenate arcs, reversing arcs with negative (one's complement) index
+    fn line(&self, arcs: &JsonValue) -> Result<Vec<Position>> {
+        let indices = arcs.as_array().ok_or_else(|| invalid("arc indices"))?;
+        let mut coords: Vec<Position> = Vec::new();
+        for index in indices {
+            let index = index.as_i64().ok_or_else(|| invalid("arc index"))?;
+            let (arc_idx, reverse) = if index < 0 {
+                (!index as usize, true)
+            } else {
+                (index as usize, false)
+            };
+            let arc = self
+                .arcs
+                .get(arc_idx)
+                .ok_or_else(|| invalid("arc index out of range"))?;
+            // consecutive arcs share their end and start position
+            let skip = if coords.is_empty() { 0 } else { 1 };
+            if reverse {
+                coords.extend(arc.iter().rev().skip(skip).cloned());
+            } else {
+                coords.extend(arc.iter().skip(skip).cloned());
+            }
+        }
+        Ok(coords)
+    }
+
+    fn lines(&self, arcs: &JsonValue) -> Result<Vec<Vec<Position>>> {
+        arcs.as_array()
+            .ok_or_else(|| invalid("arc indices"))?
+            .iter()
+            .map(|arcs| self.line(arcs))
+            .collect()
+    }
+
+    fn process_geometry<P: GeomProcessor>(
+        &self,
+        geometry: &JsonValue,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geom_type = geometry
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| invalid("geometry type"))?;
+        let arcs = || geometry.get("arcs").ok_or_else(|| invalid("missing arcs"));
+        let multi_dim = processor.multi_dim();
+        match geom_type {
+            "Point" => {
+                let pos = self.position(geometry.get("coordinates"))?;
+                processor.point_begin(idx)?;
+                process_coord(&pos, multi_dim, 0, processor)?;
+                processor.point_end(idx)
+            }
+            "MultiPoint" => {
+                let points = geometry
+                    .get("coordinates")
+                    .and_then(|c| c.as_array())
+                    .ok_or_else(|| invalid("coordinates"))?;
+                processor.multipoint_begin(points.len(), idx)?;
+                for (idxc, point) in points.iter().enumerate() {
+                    let pos = self.position(Some(point))?;
+                    process_coord(&pos, multi_dim, idxc, processor)?;
+                }
+                processor.multipoint_end(idx)
+            }
+            "LineString" => {
+                let line = self.line(arcs()?)?;
+                process_linestring(&line, true, idx, processor)
+            }
+            "MultiLineString" => {
+                let lines = self.lines(arcs()?)?;
+                processor.multilinestring_begin(lines.len(), idx)?;
+                for (idxl, line) in lines.iter().enumerate() {
+                    process_linestring(line, false, idxl, processor)?;
+                }
+                processor.multilinestring_end(idx)
+            }
+            "Polygon" => {
+                let rings = self.lines(arcs()?)?;
+                process_polygon(&rings, true, idx, processor)
+            }
+            "MultiPolygon" => {
+                let polygons = arcs()?
+                    .as_array()
+                    .ok_or_else(|| invalid("arc indices"))?
+                    .iter()
+                    .map(|polygon| self.lines(polygon))
+                    .collect::<Result<Vec<_>>>()?;
+                processor.multipolygon_begin(polygons.len(), idx)?;
+                for (idxp, rings) in polygons.iter().enumerate() {
+                    process_polygon(rings, false, idxp, processor)?;
+                }
+                processor.multipolygon_end(idx)
+            }
+            "GeometryCollection" => {
+                let geometries = geometry
+                    .get("geometries")
+                    .and_then(|g| g.as_array())
+                    .ok_or_else(|| invalid("geometries"))?;
+                processor.geometrycollection_begin(geometries.len(), idx)?;
+                for (idxg, geometry) in geometries.iter().enumerate() {
+                    self.process_geometry(geometry, idxg, processor)?;
+                }
+                processor.geometrycollection_end(idx)
+            }
+            other => Err(GeozeroError::Geometry(format!(
+                "Unsupported TopoJSON geometry type `{}`",
+                other
+            ))),
+        }
+    }
+}
+
+fn parse_position(value: Option<&JsonValue>) -> Result<Position> {
+    let pos = value
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| invalid("position"))?
+        .iter()
+        .map(|v| v.as_f64().ok_or_else(|| invalid("position")))
+        .collect::<Result<Position>>()?;
+    if pos.len() < 2 {
+        return Err(invalid("position"));
+    }
+    Ok(pos)
+}
+
+/// Delta-decode and transform quantized arc positions
+fn decode_arc(arc: &JsonValue, transform: Option<&Transform>) -> Result<Vec<Position>> {
+    let positions = arc.as_array().ok_or_else(|| invalid("arc"))?;
+    let (mut x, mut y) = (0.0, 0.0);
+    positions
+        .iter()
+        .map(|pos| {
+            let mut pos = parse_position(Some(pos))?;
+            if let Some(transform) = transform {
+                x += pos[0];
+                y += pos[1];
+                pos[0] = x;
+                pos[1] = y;
+                transform.apply(&mut pos);
+            }
+            Ok(pos)
+        })
+        .collect()
+}
+
+fn process_coord<P: GeomProcessor>(
+    pos: &Position,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    if multi_dim {
+        processor.coordinate(pos[0], pos[1], pos.get(2).copied(), None, None, None, idx)
+    } else {
+        processor.xy(pos[0], pos[1], idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    line: &[Position],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let multi_dim = processor.multi_dim();
+    processor.linestring_begin(tagged, line.len(), idx)?;
+    for (idxc, pos) in line.iter().enumerate() {
+        process_coord(pos, multi_dim, idxc, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Position>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (idxl, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, idxl, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+/// Process TopoJSON properties
+fn process_properties<P: PropertyProcessor>(
+    properties: &Map<String, JsonValue>,
+    processor: &mut P,
+) -> Result<()> {
+    let mut json = String::new();
+    for (i, (key, value)) in properties.iter().enumerate() {
+        let finish = process_json_property(processor, i, key, value, &mut json)?;
+        if finish {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::ToWkt;
+
+    struct Coords(Vec<(f64, f64)>);
+
+    impl GeomProcessor for Coords {
+        fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+            self.0.push((x, y));
+            Ok(())
+        }
+    }
+
+    // Example from the TopoJSON specification
+    const EXAMPLE: &str = r#"{
+      "type": "Topology",
+      "transform": {"scale": [0.0005000500050005, 0.00010001000100010001], "translate": [100, 0]},
+      "objects": {
+        "example": {
+          "type": "GeometryCollection",
+          "geometries": [
+            {"type": "Point", "properties": {"prop0": "value0"}, "coordinates": [4000, 5000]},
+            {"type": "LineString", "properties": {"prop0": "value0", "prop1": 0}, "arcs": [0]},
+            {"type": "Polygon", "properties": {"prop0": "value0", "prop1": {"this": "that"}}, "arcs": [[-2]]}
+          ]
+        }
+      },
+      "arcs": [
+        [[4000, 0], [1999, 9999], [2000, -9999], [2000, 9999]],
+        [[0, 0], [0, 9999], [2000, 0], [0, -9999], [-2000, 0]]
+      ]
+    }"#;
+
+    #[test]
+    fn quantized_example() -> Result<()> {
+        let wkt = TopoJson(EXAMPLE).to_wkt()?;
+        assert!(wkt.starts_with("GEOMETRYCOLLECTION(POINT("));
+        assert!(wkt.contains(",LINESTRING(") && wkt.contains(",POLYGON(("));
+
+        let mut coords = Coords(Vec::new());
+        TopoJson(EXAMPLE).process_geom(&mut coords)?;
+        let expected = [
+            (102.000200020002, 0.5000500050005),
+            (102.000200020002, 0.0),
+            (102.999799979998, 1.0),
+            (103.999899989999, 0.0),
+            (105.0, 1.0),
+            (100.0, 0.0),
+            (101.000100010001, 0.0),
+            (101.000100010001, 1.0),
+            (100.0, 1.0),
+            (100.0, 0.0),
+        ];
+        assert_eq!(coords.0.len(), expected.len());
+        for ((x, y), (ex, ey)) in coords.0.into_iter().zip(expected) {
+            assert!((x - ex).abs() < 1e-9 && (y - ey).abs() < 1e-9);
+        }
+
+        let mut out: Vec<u8> = Vec::new();
+        TopoJson(EXAMPLE).process(&mut GeoJsonWriter::new(&mut out))?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(r#""properties": {"prop0": "value0", "prop1": 0}"#));
+        assert!(json.contains(r#""properties": {"prop0": "value0", "prop1": {"this":"that"}}"#));
+        assert_eq!(json.matches(r#""type": "Feature""#).count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn arcs() -> Result<()> {
+        let topojson = r#"{
+          "type": "Topology",
+          "objects": {
+            "lines": {"type": "MultiLineString", "arcs": [[0, 1], [-2]]},
+            "polygon": {"type": "Polygon", "arcs": [[0, 1, 2]]}
+          },
+          "arcs": [[[0, 0], [1, 0]], [[1, 0], [1, 1]], [[1, 1], [0, 0]]]
+        }"#;
+        assert_eq!(
+            TopoJson(topojson).to_wkt()?,
+            "GEOMETRYCOLLECTION(MULTILINESTRING((0 0,1 0,1 1),(1 1,1 0)),POLYGON((0 0,1 0,1 1,0 0)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_topology() {
+        assert!(TopoJson(r#"{"type": "FeatureCollection", "features": []}"#)
+            .to_wkt()
+            .is_err());
+        let topojson = r#"{"type": "Topology", "objects": {"l": {"type": "LineString", "arcs": [3]}}, "arcs": []}"#;
+        assert!(TopoJson(topojson).to_wkt().is_err());
+    }
+}
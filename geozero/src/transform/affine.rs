@@ -0,0 +1,161 @@
+use crate::error::Result;
+use crate::transform::{CoordTransform, TransformProcessor};
+
+/// Affine coordinate transformation.
+///
+/// Transformation matrix `[a, b, c, d, e, f, g, h, i, xoff, yoff, zoff]`:
+/// ```text
+/// x' = a * x + b * y + c * z + xoff
+/// y' = d * x + e * y + f * z + yoff
+/// z' = g * x + h * y + i * z + zoff
+/// ```
+/// Missing z values are treated as 0 and are not added to the output.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AffineTransform(pub [f64; 12]);
+
+impl AffineTransform {
+    /// Identity transformation.
+    pub fn identity() -> Self {
+        AffineTransform([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0])
+    }
+    /// 2D transformation with matrix `[a, b, d, e, xoff, yoff]`.
+    pub fn from_2d(a: f64, b: f64, d: f64, e: f64, xoff: f64, yoff: f64) -> Self {
+        AffineTransform([a, b, 0.0, d, e, 0.0, 0.0, 0.0, 1.0, xoff, yoff, 0.0])
+    }
+    /// Translation.
+    pub fn translate(dx: f64, dy: f64, dz: f64) -> Self {
+        AffineTransform([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, dx, dy, dz])
+    }
+    /// Scaling relative to origin.
+    pub fn scale(sx: f64, sy: f64, sz: f64) -> Self {
+        AffineTransform([sx, 0.0, 0.0, 0.0, sy, 0.0, 0.0, 0.0, sz, 0.0, 0.0, 0.0])
+    }
+    /// Counter-clockwise rotation in degrees around the z axis.
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        AffineTransform([cos, -sin, 0.0, sin, cos, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0])
+    }
+    /// Combined transformation applying `self` followed by `next`.
+    pub fn then(&self, next: &AffineTransform) -> Self {
+        let [a1, b1, c1, d1, e1, f1, g1, h1, i1, x1, y1, z1] = self.0;
+        let [a2, b2, c2, d2, e2, f2, g2, h2, i2, x2, y2, z2] = next.0;
+        AffineTransform([
+            a2 * a1 + b2 * d1 + c2 * g1,
+            a2 * b1 + b2 * e1 + c2 * h1,
+            a2 * c1 + b2 * f1 + c2 * i1,
+            d2 * a1 + e2 * d1 + f2 * g1,
+            d2 * b1 + e2 * e1 + f2 * h1,
+            d2 * c1 + e2 * f1 + f2 * i1,
+            g2 * a1 + h2 * d1 + i2 * g1,
+            g2 * b1 + h2 * e1 + i2 * h1,
+            g2 * c1 + h2 * f1 + i2 * i1,
+            a2 * x1 + b2 * y1 + c2 * z1 + x2,
+            d2 * x1 + e2 * y1 + f2 * z1 + y2,
+            g2 * x1 + h2 * y1 + i2 * z1 + z2,
+        ])
+    }
+    /// Apply transformation to a coordinate.
+    pub fn apply(&self, x: f64, y: f64, z: Option<f64>) -> (f64, f64, Option<f64>) {
+        let [a, b, c, d, e, f, g, h, i, xoff, yoff, zoff] = self.0;
+        let z0 = z.unwrap_or(0.0);
+        (
+            a * x + b * y + c * z0 + xoff,
+            d * x + e * y + f * z0 + yoff,
+            z.map(|z| g * x + h * y + i * z + zoff),
+        )
+    }
+}
+
+impl Default for AffineTransform {
+    fn default() -> Self {
+        AffineTransform::identity()
+    }
+}
+
+impl CoordTransform for AffineTransform {
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)> {
+        Ok(self.apply(x, y, z))
+    }
+}
+
+/// Processor applying an affine transformation before forwarding to an inner processor.
+pub type AffineTransformer<P> = TransformProcessor<AffineTransform, P>;
+
+impl<P> TransformProcessor<AffineTransform, P> {
+    /// Apply `transform` and forward to `processor`.
+    pub fn new(transform: AffineTransform, processor: P) -> Self {
+        Self::with_transform(transform, processor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_coord(actual: (f64, f64, Option<f64>), expected: (f64, f64, Option<f64>)) {
+        assert!((actual.0 - expected.0).abs() < 1e-9, "{:?}", actual);
+        assert!((actual.1 - expected.1).abs() < 1e-9, "{:?}", actual);
+        match (actual.2, expected.2) {
+            (Some(z1), Some(z2)) => assert!((z1 - z2).abs() < 1e-9, "{:?}", actual),
+            (z1, z2) => assert_eq!(z1, z2),
+        }
+    }
+
+    #[test]
+    fn transformations() {
+        assert_coord(
+            AffineTransform::identity().apply(1.0, 2.0, None),
+            (1.0, 2.0, None),
+        );
+        assert_coord(
+            AffineTransform::translate(1.0, 2.0, 3.0).apply(1.0, 2.0, Some(3.0)),
+            (2.0, 4.0, Some(6.0)),
+        );
+        assert_coord(
+            AffineTransform::scale(2.0, 3.0, 4.0).apply(1.0, 2.0, Some(3.0)),
+            (2.0, 6.0, Some(12.0)),
+        );
+        assert_coord(
+            AffineTransform::rotate(90.0).apply(1.0, 0.0, None),
+            (0.0, 1.0, None),
+        );
+        assert_coord(
+            AffineTransform::from_2d(1.0, 2.0, 3.0, 4.0, 5.0, 6.0).apply(1.0, 1.0, None),
+            (8.0, 13.0, None),
+        );
+    }
+
+    #[test]
+    fn composition() {
+        let transform = AffineTransform::scale(2.0, 2.0, 1.0)
+            .then(&AffineTransform::translate(10.0, 20.0, 0.0));
+        assert_coord(transform.apply(1.0, 2.0, None), (12.0, 24.0, None));
+        let transform = AffineTransform::rotate(90.0).then(&AffineTransform::scale(1.0, 3.0, 1.0));
+        assert_coord(transform.apply(1.0, 0.0, Some(5.0)), (0.0, 3.0, Some(5.0)));
+    }
+
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn processor() -> Result<()> {
+        use crate::wkt::{WktStr, WktWriter};
+        use crate::GeozeroGeometry;
+
+        let mut wkt = Vec::new();
+        let mut transformer = AffineTransformer::new(
+            AffineTransform::translate(-1.0, -2.0, 0.0)
+                .then(&AffineTransform::scale(10.0, 10.0, 1.0)),
+            WktWriter::new(&mut wkt),
+        );
+        WktStr("POLYGON((1 2,2 2,2 3,1 2))").process_geom(&mut transformer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt).unwrap(),
+            "POLYGON((0 0,10 0,10 10,0 0))"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+//! Coordinate transformations.
+//!
+//! A [TransformProcessor] applies a [CoordTransform] to each coordinate and forwards
+//! all processing events to an inner processor.
+//!
+//! # Usage example:
+//!
+//! Reproject a GeoJSON geometry from WGS84 to Web Mercator:
+//!
+//! ```ignore
+//! use geozero::transform::ProjTransformer;
+//! use geozero::{geojson::GeoJson, wkt::WktWriter, GeozeroGeometry};
+//!
+//! let mut wkt = Vec::new();
+//! let mut transformer = ProjTransformer::new("EPSG:4326", "EPSG:3857", WktWriter::new(&mut wkt))?;
+//! GeoJson(r#"{"type": "Point", "coordinates": [8.5, 47.4]}"#).process_geom(&mut transformer)?;
+//! ```
+
+mod affine;
+#[cfg(feature = "with-proj")]
+mod proj_transformer;
+mod swap;
+
+pub use affine::*;
+#[cfg(feature = "with-proj")]
+pub use proj_transformer::*;
+pub use swap::*;
+
+use crate::error::Result;
+use crate::{
+    ColumnValue, CoordDimensions, DatasetMetadata, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Coordinate transformation.
+pub trait CoordTransform {
+    /// Transform x, y and optional z coordinate.
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)>;
+    /// Set SRID of the following dataset and return the SRID of transformed coordinates.
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<Option<i32>> {
+        Ok(srid)
+    }
+    /// Set SRID of the following geometry and return the SRID of transformed coordinates.
+    ///
+    /// Geometry SRIDs override the dataset SRID, e.g. for tables with mixed SRIDs.
+    fn geometry_srid(&mut self, srid: Option<i32>) -> Result<Option<i32>> {
+        Ok(srid)
+    }
+}
+
+impl<F: FnMut(f64, f64, Option<f64>) -> Result<(f64, f64, Option<f64>)>> CoordTransform for F {
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)> {
+        self(x, y, z)
+    }
+}
+
+/// Processor applying a coordinate transformation before forwarding to an inner processor.
+pub struct TransformProcessor<T: CoordTransform, P> {
+    transform: T,
+    processor: P,
+}
+
+impl<T: CoordTransform, P> TransformProcessor<T, P> {
+    pub fn with_transform(transform: T, processor: P) -> Self {
+        TransformProcessor {
+            transform,
+            processor,
+        }
+    }
+    /// Inner processor.
+    pub fn processor(&mut self) -> &mut P {
+        &mut self.processor
+    }
+    /// Consume transformer and return inner processor.
+    pub fn into_inner(self) -> P {
+        self.processor
+    }
+}
+
+impl<T: CoordTransform, P: GeomProcessor> GeomProcessor for TransformProcessor<T, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        let srid = self.transform.geometry_srid(srid)?;
+        self.processor.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        let (x, y, _) = self.transform.transform_coord(x, y, None)?;
+        self.processor.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let (x, y, z) = self.transform.transform_coord(x, y, z)?;
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.processor.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.processor.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.processor.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.processor.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.processor.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.processor.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.processor.tin_end(idx)
+    }
+}
+
+impl<T: CoordTransform, P: PropertyProcessor> PropertyProcessor for TransformProcessor<T, P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.processor.property(idx, name, value)
+    }
+}
+
+impl<T: CoordTransform, P: FeatureProcessor> FeatureProcessor for TransformProcessor<T, P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.processor.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.processor.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.processor.feature_begin(idx)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.processor.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.processor.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.processor.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.processor.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.processor.geometry_end()
+    }
+    fn null_geometry(&mut self) -> Result<()> {
+        self.processor.null_geometry()
+    }
+    fn multiple_geometries(&self) -> bool {
+        self.processor.multiple_geometries()
+    }
+    fn geometry_column_begin(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.processor.geometry_column_begin(idx, name)
+    }
+    fn geometry_column_end(&mut self, idx: usize, name: &str) -> Result<()> {
+        self.processor.geometry_column_end(idx, name)
+    }
+    fn foreign_member(&mut self, name: &str, value: &str) -> Result<()> {
+        self.processor.foreign_member(name, value)
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        let srid = self.transform.dataset_srid(srid)?;
+        self.processor.dataset_srid(srid)
+    }
+    fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> Result<()> {
+        self.processor.dataset_metadata(metadata)
+    }
+    fn batch_begin(&mut self, size: usize) -> Result<()> {
+        self.processor.batch_begin(size)
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        self.processor.batch_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    #[test]
+    fn closure_transform() -> Result<()> {
+        let mut wkt = Vec::new();
+        let mut transformer = TransformProcessor::with_transform(
+            |x: f64, y: f64, z: Option<f64>| -> Result<_> { Ok((x + 1.0, y * 2.0, z)) },
+            WktWriter::new(&mut wkt),
+        );
+        WktStr("LINESTRING(1 2,3 4)").process_geom(&mut transformer)?;
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "LINESTRING(2 4,4 8)");
+        Ok(())
+    }
+
+    #[test]
+    fn dimensions() -> Result<()> {
+        let mut wkt = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        writer.dims = CoordDimensions::xyzm();
+        let mut transformer = TransformProcessor::with_transform(
+            |x: f64, y: f64, z: Option<f64>| -> Result<_> { Ok((x, y, z.map(|z| z * 10.0))) },
+            writer,
+        );
+        // XYZM can't be parsed as WKT
+        transformer.point_begin(0)?;
+        transformer.coordinate(1.0, 2.0, Some(3.0), Some(4.0), None, None, 0)?;
+        transformer.point_end(0)?;
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(1 2 30 4)");
+        Ok(())
+    }
+}
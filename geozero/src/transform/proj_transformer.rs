@@ -0,0 +1,173 @@
+use crate::error::{GeozeroError, Result};
+use crate::transform::{CoordTransform, TransformProcessor};
+use proj::Proj;
+use std::collections::HashMap;
+
+/// Coordinate reprojection with PROJ.
+///
+/// Coordinates are expected in the axis order of the "visualization" convention,
+/// i.e. longitude before latitude for geographic CRS. Z values are passed unchanged.
+pub struct ProjTransform(pub Proj);
+
+impl ProjTransform {
+    /// Reprojection between two CRS definitions like `EPSG:4326` or PROJ strings.
+    pub fn new(from: &str, to: &str) -> Result<Self> {
+        Ok(ProjTransform(Proj::new_known_crs(from, to, None)?))
+    }
+}
+
+impl CoordTransform for ProjTransform {
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)> {
+        let (x, y) = self.0.convert((x, y))?;
+        Ok((x, y, z))
+    }
+}
+
+/// Processor reprojecting coordinates before forwarding to an inner processor.
+pub type ProjTransformer<P> = TransformProcessor<ProjTransform, P>;
+
+impl<P> TransformProcessor<ProjTransform, P> {
+    /// Reproject from CRS `from` to `to` and forward to `processor`.
+    pub fn new(from: &str, to: &str, processor: P) -> Result<Self> {
+        Ok(Self::with_transform(
+            ProjTransform::new(from, to)?,
+            processor,
+        ))
+    }
+}
+
+/// Reprojection of datasets with mixed SRIDs to a common target SRID.
+///
+/// The source CRS is taken from the SRID of each geometry, falling back to the dataset SRID.
+/// Coordinates with unknown SRID or already in the target SRID are passed unchanged.
+/// Transformations are created on first use of a source SRID and cached.
+pub struct ProjSridTransform {
+    target: i32,
+    dataset_srid: Option<i32>,
+    geometry_srid: Option<i32>,
+    projs: HashMap<i32, Proj>,
+}
+
+impl ProjSridTransform {
+    /// Reprojection to EPSG code `target`.
+    pub fn new(target: i32) -> Self {
+        ProjSridTransform {
+            target,
+            dataset_srid: None,
+            geometry_srid: None,
+            projs: HashMap::new(),
+        }
+    }
+    /// Output SRID for coordinates with SRID `srid`.
+    fn target_srid(&self, srid: Option<i32>) -> Option<i32> {
+        srid.map(|_| self.target)
+    }
+}
+
+impl CoordTransform for ProjSridTransform {
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)> {
+        let source = match self.geometry_srid.or(self.dataset_srid) {
+            Some(srid) if srid != self.target => srid,
+            _ => return Ok((x, y, z)),
+        };
+        if !self.projs.contains_key(&source) {
+            let proj = Proj::new_known_crs(
+                &format!("EPSG:{}", source),
+                &format!("EPSG:{}", self.target),
+                None,
+            )?;
+            self.projs.insert(source, proj);
+        }
+        let (x, y) = self.projs[&source].convert((x, y))?;
+        Ok((x, y, z))
+    }
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<Option<i32>> {
+        self.dataset_srid = srid;
+        Ok(self.target_srid(srid))
+    }
+    fn geometry_srid(&mut self, srid: Option<i32>) -> Result<Option<i32>> {
+        self.geometry_srid = srid;
+        Ok(self.target_srid(srid.or(self.dataset_srid)))
+    }
+}
+
+/// Processor reprojecting geometries with mixed SRIDs to a common target SRID.
+pub type ProjSridTransformer<P> = TransformProcessor<ProjSridTransform, P>;
+
+impl<P> TransformProcessor<ProjSridTransform, P> {
+    /// Reproject to EPSG code `target` and forward to `processor`.
+    pub fn to_srid(target: i32, processor: P) -> Self {
+        Self::with_transform(ProjSridTransform::new(target), processor)
+    }
+}
+
+impl From<proj::ProjCreateError> for GeozeroError {
+    fn from(error: proj::ProjCreateError) -> Self {
+        GeozeroError::Geometry(error.to_string())
+    }
+}
+
+impl From<proj::ProjError> for GeozeroError {
+    fn from(error: proj::ProjError) -> Self {
+        GeozeroError::Geometry(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::GeomProcessor;
+
+    struct CoordCollector(Vec<(f64, f64)>);
+
+    impl GeomProcessor for CoordCollector {
+        fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+            self.0.push((x, y));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn web_mercator() -> Result<()> {
+        let mut transformer =
+            ProjTransformer::new("EPSG:4326", "EPSG:3857", CoordCollector(Vec::new()))?;
+        transformer.xy(8.5, 47.4, 0)?;
+        transformer.xy(0.0, 0.0, 1)?;
+        let coords = transformer.into_inner().0;
+        assert!((coords[0].0 - 946215.6717428254).abs() < 0.01);
+        assert!((coords[0].1 - 6007610.414387713).abs() < 0.01);
+        assert!(coords[1].0.abs() < 0.01 && coords[1].1.abs() < 0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn mixed_srids() -> Result<()> {
+        let mut transformer = ProjSridTransformer::to_srid(3857, CoordCollector(Vec::new()));
+        transformer.srid(Some(4326))?;
+        transformer.xy(8.5, 47.4, 0)?;
+        transformer.srid(Some(3857))?;
+        transformer.xy(1.0, 2.0, 0)?;
+        transformer.srid(None)?;
+        transformer.xy(3.0, 4.0, 0)?;
+        let coords = transformer.into_inner().0;
+        assert!((coords[0].0 - 946215.6717428254).abs() < 0.01);
+        assert!((coords[0].1 - 6007610.414387713).abs() < 0.01);
+        assert_eq!(coords[1..], [(1.0, 2.0), (3.0, 4.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_crs() {
+        assert!(ProjTransformer::new("EPSG:4326", "EPSG:xxx", CoordCollector(Vec::new())).is_err());
+    }
+}
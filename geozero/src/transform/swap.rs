@@ -0,0 +1,94 @@
+use crate::error::Result;
+use crate::transform::{CoordTransform, TransformProcessor};
+
+/// Transformation swapping x and y coordinates.
+///
+/// Fixes latitude/longitude order, e.g. of GML documents or WFS responses
+/// in `urn:ogc:def:crs:EPSG::4326`.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct SwapXY;
+
+impl CoordTransform for SwapXY {
+    fn transform_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+    ) -> Result<(f64, f64, Option<f64>)> {
+        Ok((y, x, z))
+    }
+}
+
+/// Processor swapping x and y coordinates before forwarding to an inner processor.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::transform::AxisSwapper;
+/// use geozero::{wkt::{WktStr, WktWriter}, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut swapper = AxisSwapper::new(WktWriter::new(&mut wkt));
+/// WktStr("POINT(47.4 8.5)").process_geom(&mut swapper).unwrap();
+/// assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(8.5 47.4)");
+/// ```
+pub type AxisSwapper<P> = TransformProcessor<SwapXY, P>;
+
+impl<P> TransformProcessor<SwapXY, P> {
+    /// Swap axes and forward to `processor`.
+    pub fn new(processor: P) -> Self {
+        Self::with_transform(SwapXY, processor)
+    }
+}
+
+/// Check whether a CRS name implies latitude/longitude axis order.
+///
+/// True for geographic EPSG codes in URN or URL notation (e.g. `urn:ogc:def:crs:EPSG::4326`),
+/// which require latitude first. The notation `EPSG:4326` is commonly used with
+/// longitude first and is treated as x/y order.
+pub fn is_lat_lon_order(crs_name: &str) -> bool {
+    let name = crs_name.trim();
+    if name
+        .get(..5)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("EPSG:"))
+    {
+        return false;
+    }
+    matches!(crate::epsg_code(name), Some(4000..=4999))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lat_lon_order() {
+        assert!(is_lat_lon_order("urn:ogc:def:crs:EPSG::4326"));
+        assert!(is_lat_lon_order(
+            "http://www.opengis.net/def/crs/EPSG/0/4258"
+        ));
+        assert!(!is_lat_lon_order("EPSG:4326"));
+        assert!(!is_lat_lon_order("urn:ogc:def:crs:EPSG::3857"));
+        assert!(!is_lat_lon_order("urn:ogc:def:crs:OGC:1.3:CRS84"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+    fn processor() -> Result<()> {
+        use crate::geojson::GeoJson;
+        use crate::wkt::WktWriter;
+        use crate::{CoordDimensions, GeozeroGeometry};
+
+        let mut wkt = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        writer.dims = CoordDimensions::xyz();
+        let mut swapper = AxisSwapper::new(writer);
+        GeoJson(r#"{"type": "LineString", "coordinates": [[1, 2, 3], [4, 5, 6]]}"#)
+            .process_geom(&mut swapper)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt).unwrap(),
+            "LINESTRING(2 1 3,5 4 6)"
+        );
+        Ok(())
+    }
+}
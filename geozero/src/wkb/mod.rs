@@ -11,10 +11,12 @@
 //! assert_eq!(wkb.to_wkt().unwrap(), "POINT(10 -20)");
 //! ```
 pub(crate) mod wkb_common;
+pub(crate) mod wkb_hex;
 pub(crate) mod wkb_reader;
 pub(crate) mod wkb_writer;
 
 pub use wkb_common::*;
+pub use wkb_hex::HexReader;
 pub use wkb_reader::*;
 pub use wkb_writer::*;
 
@@ -23,6 +25,7 @@ pub(crate) mod conversion {
     use crate::error::Result;
     use crate::wkb::WkbDialect;
     use crate::{CoordDimensions, GeozeroGeometry};
+    use std::io::Write;
 
     /// Convert to WKB.
     ///
@@ -45,7 +48,31 @@ pub(crate) mod conversion {
             dims: CoordDimensions,
             srid: Option<i32>,
             envelope: Vec<f64>,
-        ) -> Result<Vec<u8>>;
+        ) -> Result<Vec<u8>> {
+            let mut wkb: Vec<u8> = Vec::new();
+            self.write_wkb_dialect(&mut wkb, dialect, dims, srid, envelope)?;
+            Ok(wkb)
+        }
+        /// Write WKB dialect into `out` without intermediate buffer.
+        ///
+        /// Network buffers implementing `bytes::BufMut` can be written with `BufMut::writer`.
+        fn write_wkb_dialect<W: Write>(
+            &self,
+            out: &mut W,
+            dialect: WkbDialect,
+            dims: CoordDimensions,
+            srid: Option<i32>,
+            envelope: Vec<f64>,
+        ) -> Result<()>;
+        /// Write EWKB into `out` without intermediate buffer.
+        fn write_ewkb<W: Write>(
+            &self,
+            out: &mut W,
+            dims: CoordDimensions,
+            srid: Option<i32>,
+        ) -> Result<()> {
+            self.write_wkb_dialect(out, WkbDialect::Ewkb, dims, srid, Vec::new())
+        }
         /// Convert to OGC WKB.
         fn to_wkb(&self, dims: CoordDimensions) -> Result<Vec<u8>> {
             self.to_wkb_dialect(WkbDialect::Wkb, dims, None, Vec::new())
@@ -66,20 +93,19 @@ pub(crate) mod conversion {
     }
 
     impl<T: GeozeroGeometry> ToWkb for T {
-        fn to_wkb_dialect(
+        fn write_wkb_dialect<W: Write>(
             &self,
+            out: &mut W,
             dialect: WkbDialect,
             dims: CoordDimensions,
             srid: Option<i32>,
             envelope: Vec<f64>,
-        ) -> Result<Vec<u8>> {
-            let mut wkb: Vec<u8> = Vec::new();
-            let mut writer = WkbWriter::new(&mut wkb, dialect);
+        ) -> Result<()> {
+            let mut writer = WkbWriter::new(out, dialect);
             writer.dims = dims;
             writer.srid = srid;
             writer.envelope = envelope;
-            self.process_geom(&mut writer)?;
-            Ok(wkb)
+            self.process_geom(&mut writer)
         }
     }
 }
@@ -37,7 +37,7 @@ pub trait FromWkb {
 }
 
 /// WKB dialect.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum WkbDialect {
     Wkb,
     Ewkb,
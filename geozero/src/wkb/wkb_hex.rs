@@ -0,0 +1,118 @@
+use std::io::{self, Read, Write};
+
+/// Reader decoding hex text (e.g. PostGIS EWKB in text mode) into bytes.
+///
+/// Upper and lower case digits are accepted.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::wkb::HexReader;
+/// use std::io::Read;
+///
+/// let mut bytes = Vec::new();
+/// HexReader::new("0101ff".as_bytes()).read_to_end(&mut bytes).unwrap();
+/// assert_eq!(bytes, [1, 1, 255]);
+/// ```
+pub struct HexReader<R> {
+    inner: R,
+    buf: [u8; 512],
+}
+
+impl<R: Read> HexReader<R> {
+    pub fn new(inner: R) -> Self {
+        HexReader {
+            inner,
+            buf: [0; 512],
+        }
+    }
+    /// Underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+fn hex_digit(c: u8) -> io::Result<u8> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid hex digit {:?}", c as char),
+        )),
+    }
+}
+
+impl<R: Read> Read for HexReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let len = (out.len() * 2).min(self.buf.len());
+        if len == 0 {
+            return Ok(0);
+        }
+        let read = self.inner.read(&mut self.buf[..len])?;
+        if read == 0 {
+            return Ok(0);
+        }
+        if read % 2 == 1 {
+            // complete the last digit pair
+            self.inner.read_exact(&mut self.buf[read..read + 1])?;
+        }
+        let pairs = read.div_ceil(2);
+        for (byte, pair) in out.iter_mut().zip(self.buf[..pairs * 2].chunks_exact(2)) {
+            *byte = (hex_digit(pair[0])? << 4) | hex_digit(pair[1])?;
+        }
+        Ok(pairs)
+    }
+}
+
+/// Write `bytes` as uppercase hex text.
+pub(crate) fn write_hex<W: Write + ?Sized>(out: &mut W, bytes: &[u8]) -> io::Result<()> {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut buf = [0; 512];
+    for chunk in bytes.chunks(buf.len() / 2) {
+        for (i, b) in chunk.iter().enumerate() {
+            buf[2 * i] = DIGITS[(b >> 4) as usize];
+            buf[2 * i + 1] = DIGITS[(b & 0xf) as usize];
+        }
+        out.write_all(&buf[..chunk.len() * 2])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roundtrip() -> io::Result<()> {
+        let bytes: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let mut hex = Vec::new();
+        write_hex(&mut hex, &bytes)?;
+        assert_eq!(&hex[..6], b"000102");
+        assert_eq!(&hex[hex.len() - 4..], b"FEFF");
+
+        // small reads split digit pairs of the input
+        let mut reader = HexReader::new(io::BufReader::with_capacity(3, hex.as_slice()));
+        let mut decoded = Vec::new();
+        let mut buf = [0; 5];
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            decoded.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(decoded, bytes);
+
+        let err = HexReader::new("0g".as_bytes())
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = HexReader::new("010".as_bytes())
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        Ok(())
+    }
+}
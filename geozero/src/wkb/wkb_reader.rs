@@ -1,7 +1,8 @@
 use crate::error::{GeozeroError, Result};
-use crate::wkb::{WKBByteOrder, WKBGeometryType, WkbDialect};
-use crate::{GeomProcessor, GeozeroGeometry};
+use crate::wkb::{HexReader, WKBByteOrder, WKBGeometryType, WkbDialect};
+use crate::{CoordDimensions, GeomProcessor, GeozeroGeometry};
 use scroll::IOread;
+use std::convert::TryFrom;
 use std::io::Read;
 
 #[cfg(feature = "with-postgis-diesel")]
@@ -14,7 +15,12 @@ pub struct Wkb(pub Vec<u8>);
 
 impl GeozeroGeometry for Wkb {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_wkb_geom(&mut self.0.as_slice(), processor)
+        process_wkb_slice(&self.0, WkbDialect::Wkb, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        scan_dims(read_wkb_header(&mut self.0.as_slice()), |scanner| {
+            process_wkb_slice(&self.0, WkbDialect::Wkb, scanner)
+        })
     }
 }
 
@@ -29,7 +35,17 @@ pub struct Ewkb(pub Vec<u8>);
 
 impl GeozeroGeometry for Ewkb {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_ewkb_geom(&mut self.0.as_slice(), processor)
+        process_wkb_slice(&self.0, WkbDialect::Ewkb, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        scan_dims(read_ewkb_header(&mut self.0.as_slice()), |scanner| {
+            process_wkb_slice(&self.0, WkbDialect::Ewkb, scanner)
+        })
+    }
+    fn srid(&self) -> Option<i32> {
+        read_ewkb_header(&mut self.0.as_slice())
+            .ok()
+            .and_then(|info| info.srid)
     }
 }
 
@@ -38,26 +54,215 @@ pub struct GpkgWkb(pub Vec<u8>);
 
 impl GeozeroGeometry for GpkgWkb {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_gpkg_geom(&mut self.0.as_slice(), processor)
+        process_wkb_slice(&self.0, WkbDialect::Geopackage, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        scan_dims(read_gpkg_header(&mut self.0.as_slice()), |scanner| {
+            process_wkb_slice(&self.0, WkbDialect::Geopackage, scanner)
+        })
+    }
+    fn srid(&self) -> Option<i32> {
+        read_gpkg_header(&mut self.0.as_slice())
+            .ok()
+            .and_then(|info| info.srid)
+    }
+}
+
+/// Processor collecting the coordinate dimensions of all geometry members.
+#[derive(Default)]
+struct DimensionScanner(CoordDimensions);
+
+impl GeomProcessor for DimensionScanner {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn coordinate(
+        &mut self,
+        _x: f64,
+        _y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.0.z |= z.is_some();
+        self.0.m |= m.is_some();
+        Ok(())
     }
 }
 
+/// Dimensions of the geometry header combined with the dimensions of all members,
+/// which can differ in mixed-dimension geometries.
+fn scan_dims(
+    header: Result<WkbInfo>,
+    process: impl FnOnce(&mut DimensionScanner) -> Result<()>,
+) -> CoordDimensions {
+    let mut scanner = DimensionScanner(header.map(|info| info.dims()).unwrap_or_default());
+    // keep dimensions found before an error
+    let _ = process(&mut scanner);
+    scanner.0
+}
+
 /// Process WKB geometry.
 pub fn process_wkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_wkb_header(raw)?;
-    process_wkb_geom_n(raw, &info, read_wkb_header, 0, processor)
+    with_offset(raw, None, |raw| {
+        let info = read_wkb_header(raw)?;
+        process_wkb_geom_n(raw, &info, read_wkb_header, 0, processor)
+            .map_err(|e| e.in_geometry(&format!("{:?}", info.base_type)))
+    })
 }
 
 /// Process EWKB geometry.
+///
+/// The SRID of the geometry is emitted with [GeomProcessor::srid], allowing datasets with
+/// mixed SRIDs to be handled per geometry.
 pub fn process_ewkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_ewkb_header(raw)?;
-    process_wkb_geom_n(raw, &info, read_ewkb_header, 0, processor)
+    with_offset(raw, None, |raw| {
+        let info = read_ewkb_header(raw)?;
+        processor.srid(info.srid)?;
+        process_wkb_geom_n(raw, &info, read_ewkb_header, 0, processor)
+            .map_err(|e| e.in_geometry(&format!("{:?}", info.base_type)))
+    })
+}
+
+/// Process hex encoded EWKB geometry, e.g. PostGIS results in text mode.
+///
+/// An optional `\x` prefix of `bytea` output is skipped.
+/// The geometry is decoded while reading, without allocating the binary buffer.
+pub fn process_hex_ewkb_geom<P: GeomProcessor>(hex: &str, processor: &mut P) -> Result<()> {
+    let hex = hex.strip_prefix("\\x").unwrap_or(hex);
+    let len = (hex.len() / 2) as u64;
+    with_offset(&mut HexReader::new(hex.as_bytes()), Some(len), |raw| {
+        let info = read_ewkb_header(raw)?;
+        processor.srid(info.srid)?;
+        process_wkb_geom_n(raw, &info, read_ewkb_header, 0, processor)
+            .map_err(|e| e.in_geometry(&format!("{:?}", info.base_type)))
+    })
 }
 
 /// Process GPKG geometry.
 pub fn process_gpkg_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_gpkg_header(raw)?;
+    with_offset(raw, None, |raw| {
+        let info = read_gpkg_header(raw)?;
+        process_wkb_geom_n(raw, &info, read_wkb_header, 0, processor)
+            .map_err(|e| e.in_geometry(&format!("{:?}", info.base_type)))
+    })
+}
+
+/// Process WKB type geometry from a byte buffer.
+///
+/// In contrast to reading from a stream, element counts are checked against the
+/// buffer length before they are passed to the processor, so that corrupt input
+/// fails early instead of causing huge allocations in downstream writers.
+pub fn process_wkb_slice<P: GeomProcessor>(
+    buf: &[u8],
+    dialect: WkbDialect,
+    processor: &mut P,
+) -> Result<()> {
+    with_offset(&mut &*buf, Some(buf.len() as u64), |raw| {
+        process_dialect_geom(raw, &dialect, processor)
+    })
+}
+
+/// Process a length-prefixed WKB type geometry record from a stream.
+///
+/// Records start with their byte length as 32 bit big-endian integer, like the fields of
+/// PostgreSQL binary COPY data. NULL records with length -1 are not processed and return
+/// `Ok(false)`. Each record is consumed completely, so that consecutive records can be read
+/// from non-seekable streams like pipes or sockets.
+pub fn process_wkb_record<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    dialect: WkbDialect,
+    processor: &mut P,
+) -> Result<bool> {
+    let len = raw.ioread_with::<i32>(scroll::BE)?;
+    if len == -1 {
+        return Ok(false);
+    }
+    let len = u64::try_from(len)
+        .map_err(|_| GeozeroError::Geometry(format!("invalid record length {}", len)))?;
+    process_wkb_sized(raw, len, dialect, processor)?;
+    Ok(true)
+}
+
+/// Process WKB type geometry of `len` bytes from a stream and skip unread bytes.
+pub(crate) fn process_wkb_sized<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    len: u64,
+    dialect: WkbDialect,
+    processor: &mut P,
+) -> Result<()> {
+    let mut record = raw.by_ref().take(len);
+    with_offset(&mut record, Some(len), |raw| {
+        process_dialect_geom(raw, &dialect, processor)
+    })?;
+    std::io::copy(&mut record, &mut std::io::sink())?;
+    Ok(())
+}
+
+fn process_dialect_geom<R: WkbRead, P: GeomProcessor>(
+    raw: &mut R,
+    dialect: &WkbDialect,
+    processor: &mut P,
+) -> Result<()> {
+    let info = match dialect {
+        WkbDialect::Wkb | WkbDialect::Ewkb => read_wkb_header(raw)?,
+        WkbDialect::Geopackage => read_gpkg_header(raw)?,
+    };
+    if *dialect == WkbDialect::Ewkb {
+        processor.srid(info.srid)?;
+    }
     process_wkb_geom_n(raw, &info, read_wkb_header, 0, processor)
+        .map_err(|e| e.in_geometry(&format!("{:?}", info.base_type)))
+}
+
+/// Reader with optional knowledge of the remaining input length.
+pub(crate) trait WkbRead: Read {
+    /// Number of unread bytes, if known.
+    fn remaining(&self) -> Option<u64>;
+}
+
+impl WkbRead for &[u8] {
+    fn remaining(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+
+/// Reader counting consumed bytes.
+struct OffsetReader<R> {
+    inner: R,
+    offset: u64,
+    /// Input length, if known
+    len: Option<u64>,
+}
+
+impl<R: Read> Read for OffsetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read> WkbRead for OffsetReader<R> {
+    fn remaining(&self) -> Option<u64> {
+        self.len.map(|len| len.saturating_sub(self.offset))
+    }
+}
+
+/// Add byte offset to errors.
+fn with_offset<R: Read>(
+    raw: &mut R,
+    len: Option<u64>,
+    read: impl FnOnce(&mut OffsetReader<&mut R>) -> Result<()>,
+) -> Result<()> {
+    let mut reader = OffsetReader {
+        inner: raw,
+        offset: 0,
+        len,
+    };
+    read(&mut reader).map_err(|e| e.at_offset(reader.offset))
 }
 
 /// Process WKB type geometry..
@@ -79,52 +284,36 @@ pub(crate) struct WkbInfo {
     base_type: WKBGeometryType,
     has_z: bool,
     has_m: bool,
-    #[allow(dead_code)]
     srid: Option<i32>,
     #[allow(dead_code)]
     envelope: Vec<f64>,
 }
 
-/// OGC WKB header.
-pub(crate) fn read_wkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
-    let byte_order = raw.ioread::<u8>()?;
-    let endian = if byte_order == WKBByteOrder::XDR as u8 {
-        scroll::BE
-    } else {
-        scroll::LE
-    };
-    let type_id = raw.ioread_with::<u32>(endian)?;
-    let base_type = WKBGeometryType::from_u32(type_id % 1000);
-    let type_id_dim = type_id / 1000;
-    let has_z = type_id_dim == 1 || type_id_dim == 3;
-    let has_m = type_id_dim == 2 || type_id_dim == 3;
-
-    let info = WkbInfo {
-        endian,
-        base_type,
-        has_z,
-        has_m,
-        srid: None,
-        envelope: Vec::new(),
-    };
-    Ok(info)
+impl WkbInfo {
+    fn dims(&self) -> CoordDimensions {
+        CoordDimensions {
+            z: self.has_z,
+            m: self.has_m,
+            t: false,
+            tm: false,
+        }
+    }
 }
 
-/// EWKB header according to https://git.osgeo.org/gitea/postgis/postgis/src/branch/master/doc/ZMSgeoms.txt
-fn read_ewkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
+/// OGC WKB header.
+///
+/// Dimensions are detected from ISO type codes as well as from EWKB flags.
+pub(crate) fn read_wkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     let byte_order = raw.ioread::<u8>()?;
     let endian = if byte_order == WKBByteOrder::XDR as u8 {
         scroll::BE
     } else {
         scroll::LE
     };
-
     let type_id = raw.ioread_with::<u32>(endian)?;
-    let base_type = WKBGeometryType::from_u32(type_id & 0xFF);
-    let has_z = type_id & 0x80000000 == 0x80000000;
-    let has_m = type_id & 0x40000000 == 0x40000000;
+    let (base_type, has_z, has_m, has_srid) = decode_type_id(type_id);
 
-    let srid = if type_id & 0x20000000 == 0x20000000 {
+    let srid = if has_srid {
         Some(raw.ioread_with::<i32>(endian)?)
     } else {
         None
@@ -141,6 +330,31 @@ fn read_ewkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     Ok(info)
 }
 
+/// EWKB header according to https://git.osgeo.org/gitea/postgis/postgis/src/branch/master/doc/ZMSgeoms.txt
+///
+/// ISO type codes are accepted as well.
+fn read_ewkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
+    read_wkb_header(raw)
+}
+
+/// Base type, Z, M and SRID presence of ISO (`1000`/`2000`/`3000` ranges) and EWKB (flags) type codes
+fn decode_type_id(type_id: u32) -> (WKBGeometryType, bool, bool, bool) {
+    let mut has_z = type_id & 0x80000000 == 0x80000000;
+    let mut has_m = type_id & 0x40000000 == 0x40000000;
+    let has_srid = type_id & 0x20000000 == 0x20000000;
+    let iso_type = type_id & 0x0FFFFFFF;
+    let base_type = match iso_type / 1000 {
+        0 => WKBGeometryType::from_u32(iso_type),
+        dim @ 1..=3 => {
+            has_z |= dim != 2;
+            has_m |= dim != 1;
+            WKBGeometryType::from_u32(iso_type % 1000)
+        }
+        _ => WKBGeometryType::Unknown,
+    };
+    (base_type, has_z, has_m, has_srid)
+}
+
 /// GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
 fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     let magic = [raw.ioread::<u8>()?, raw.ioread::<u8>()?];
@@ -186,28 +400,87 @@ fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     Ok(info)
 }
 
+/// Geometry type of GPKG geometry blob
+#[cfg(all(feature = "with-gpkg", feature = "with-tokio"))]
+pub(crate) fn gpkg_geometry_type(blob: &[u8]) -> Result<WKBGeometryType> {
+    Ok(read_gpkg_header(&mut &blob[..])?.base_type)
+}
+
 // TODO: Spatialite https://www.gaia-gis.it/gaia-sins/BLOB-Geometry.html
 
-pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
+pub(crate) fn process_wkb_geom_n<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     read_header: fn(&mut R) -> Result<WkbInfo>,
     idx: usize,
     processor: &mut P,
+) -> Result<()> {
+    process_wkb_geom_nested(raw, info, read_header, idx, 0, processor)
+}
+
+/// Maximal nesting depth of geometry collections.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// Minimal size of a WKB member header (byte order and type).
+const HEADER_SIZE: u64 = 5;
+
+/// Minimal size of a coordinate.
+fn coord_size(info: &WkbInfo) -> u64 {
+    8 * (2 + info.has_z as u64 + info.has_m as u64)
+}
+
+/// Read element count and check that the remaining input can hold `count` elements
+/// of at least `min_size` bytes.
+fn read_count<R: WkbRead>(raw: &mut R, info: &WkbInfo, min_size: u64) -> Result<usize> {
+    let count = raw.ioread_with::<u32>(info.endian)?;
+    if let Some(remaining) = raw.remaining() {
+        if u64::from(count) * min_size > remaining {
+            return Err(GeozeroError::Geometry(format!(
+                "{} elements exceed remaining {} bytes",
+                count, remaining
+            )));
+        }
+    }
+    Ok(count as usize)
+}
+
+fn process_wkb_geom_nested<R: WkbRead, P: GeomProcessor>(
+    raw: &mut R,
+    info: &WkbInfo,
+    read_header: fn(&mut R) -> Result<WkbInfo>,
+    idx: usize,
+    depth: usize,
+    processor: &mut P,
 ) -> Result<()> {
     match info.base_type {
         WKBGeometryType::Point => {
-            processor.point_begin(idx)?;
-            process_coord(raw, &info, processor.multi_dim(), 0, processor)?;
-            processor.point_end(idx)?;
+            let coord = read_coord(raw, info)?;
+            // Empty points are encoded with NaN coordinates
+            if coord.0.is_nan() && coord.1.is_nan() {
+                processor.empty_point(idx)?;
+            } else {
+                processor.point_begin(idx)?;
+                emit_coord(coord, processor.multi_dim(), 0, processor)?;
+                processor.point_end(idx)?;
+            }
         }
         WKBGeometryType::MultiPoint => {
-            let n_pts = raw.ioread_with::<u32>(info.endian)? as usize;
-            processor.multipoint_begin(n_pts, idx)?;
-            let multi = processor.multi_dim();
-            for i in 0..n_pts {
+            let n_pts = read_count(raw, info, HEADER_SIZE + 16)?;
+            // skip empty points, which can't be represented as MultiPoint member
+            // counts read from streams are unchecked
+            let capacity = if raw.remaining().is_some() { n_pts } else { 0 };
+            let mut coords = Vec::with_capacity(capacity);
+            for _ in 0..n_pts {
                 let info = read_header(raw)?;
-                process_coord(raw, &info, multi, i, processor)?;
+                let coord = read_coord(raw, &info)?;
+                if !(coord.0.is_nan() && coord.1.is_nan()) {
+                    coords.push(coord);
+                }
+            }
+            processor.multipoint_begin(coords.len(), idx)?;
+            let multi = processor.multi_dim();
+            for (i, coord) in coords.into_iter().enumerate() {
+                emit_coord(coord, multi, i, processor)?;
             }
             processor.multipoint_end(idx)?;
         }
@@ -221,7 +494,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             process_compoundcurve(raw, &info, read_header, idx, processor)?;
         }
         WKBGeometryType::MultiLineString => {
-            let n_lines = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_lines = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.multilinestring_begin(n_lines, idx)?;
             for i in 0..n_lines {
                 let info = read_header(raw)?;
@@ -230,7 +503,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             processor.multilinestring_end(idx)?;
         }
         WKBGeometryType::MultiCurve => {
-            let n_curves = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_curves = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.multicurve_begin(n_curves, idx)?;
             for i in 0..n_curves {
                 process_curve(raw, read_header, i, processor)?;
@@ -247,7 +520,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             process_curvepolygon(raw, &info, read_header, idx, processor)?;
         }
         WKBGeometryType::MultiPolygon => {
-            let n_polys = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_polys = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.multipolygon_begin(n_polys, idx)?;
             for i in 0..n_polys {
                 let info = read_header(raw)?;
@@ -256,7 +529,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             processor.multipolygon_end(idx)?;
         }
         WKBGeometryType::PolyhedralSurface => {
-            let n_polys = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_polys = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.polyhedralsurface_begin(n_polys, idx)?;
             for i in 0..n_polys {
                 let info = read_header(raw)?;
@@ -265,7 +538,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             processor.polyhedralsurface_end(idx)?;
         }
         WKBGeometryType::Tin => {
-            let n_triangles = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_triangles = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.tin_begin(n_triangles, idx)?;
             for i in 0..n_triangles {
                 let info = read_header(raw)?;
@@ -274,7 +547,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             processor.tin_end(idx)?;
         }
         WKBGeometryType::MultiSurface => {
-            let n_polys = raw.ioread_with::<u32>(info.endian)? as usize;
+            let n_polys = read_count(raw, info, HEADER_SIZE + 4)?;
             processor.multisurface_begin(n_polys, idx)?;
             for i in 0..n_polys {
                 let info = read_header(raw)?;
@@ -292,11 +565,17 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
         }
 
         WKBGeometryType::GeometryCollection => {
-            let n_geoms = raw.ioread_with::<u32>(info.endian)? as usize;
+            if depth >= MAX_NESTING_DEPTH {
+                return Err(GeozeroError::LimitExceeded(format!(
+                    "geometry collections nested deeper than {} levels",
+                    MAX_NESTING_DEPTH
+                )));
+            }
+            let n_geoms = read_count(raw, info, HEADER_SIZE)?;
             processor.geometrycollection_begin(n_geoms, idx)?;
             for i in 0..n_geoms {
                 let info = read_header(raw)?;
-                process_wkb_geom_n(raw, &info, read_header, i, processor)?;
+                process_wkb_geom_nested(raw, &info, read_header, i, depth + 1, processor)?;
             }
             processor.geometrycollection_end(idx)?;
         }
@@ -305,13 +584,9 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
     Ok(())
 }
 
-fn process_coord<R: Read, P: GeomProcessor>(
-    raw: &mut R,
-    info: &WkbInfo,
-    multi_dim: bool,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
+type Coord = (f64, f64, Option<f64>, Option<f64>);
+
+fn read_coord<R: Read>(raw: &mut R, info: &WkbInfo) -> Result<Coord> {
     let x = raw.ioread_with::<f64>(info.endian)?;
     let y = raw.ioread_with::<f64>(info.endian)?;
     let z = if info.has_z {
@@ -324,22 +599,41 @@ fn process_coord<R: Read, P: GeomProcessor>(
     } else {
         None
     };
+    Ok((x, y, z, m))
+}
+
+fn emit_coord<P: GeomProcessor>(
+    (x, y, z, m): Coord,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
     if multi_dim {
-        processor.coordinate(x, y, z, m, None, None, idx)?;
+        processor.coordinate(x, y, z, m, None, None, idx)
     } else {
-        processor.xy(x, y, idx)?;
+        processor.xy(x, y, idx)
     }
-    Ok(())
 }
 
-fn process_linestring<R: Read, P: GeomProcessor>(
+fn process_coord<R: WkbRead, P: GeomProcessor>(
+    raw: &mut R,
+    info: &WkbInfo,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let coord = read_coord(raw, info)?;
+    emit_coord(coord, multi_dim, idx, processor)
+}
+
+fn process_linestring<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     tagged: bool,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let length = raw.ioread_with::<u32>(info.endian)? as usize;
+    let length = read_count(raw, info, coord_size(info))?;
     processor.linestring_begin(tagged, length, idx)?;
     let multi = processor.multi_dim();
     for i in 0..length {
@@ -348,13 +642,13 @@ fn process_linestring<R: Read, P: GeomProcessor>(
     processor.linestring_end(tagged, idx)
 }
 
-fn process_circularstring<R: Read, P: GeomProcessor>(
+fn process_circularstring<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let length = raw.ioread_with::<u32>(info.endian)? as usize;
+    let length = read_count(raw, info, coord_size(info))?;
     processor.circularstring_begin(length, idx)?;
     let multi = processor.multi_dim();
     for i in 0..length {
@@ -363,14 +657,14 @@ fn process_circularstring<R: Read, P: GeomProcessor>(
     processor.circularstring_end(idx)
 }
 
-fn process_polygon<R: Read, P: GeomProcessor>(
+fn process_polygon<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     tagged: bool,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let ring_count = raw.ioread_with::<u32>(info.endian)? as usize;
+    let ring_count = read_count(raw, info, 4)?;
     processor.polygon_begin(tagged, ring_count, idx)?;
     for i in 0..ring_count {
         process_linestring(raw, info, false, i, processor)?;
@@ -378,14 +672,14 @@ fn process_polygon<R: Read, P: GeomProcessor>(
     processor.polygon_end(tagged, idx)
 }
 
-fn process_triangle<R: Read, P: GeomProcessor>(
+fn process_triangle<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     tagged: bool,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let ring_count = raw.ioread_with::<u32>(info.endian)? as usize;
+    let ring_count = read_count(raw, info, 4)?;
     processor.triangle_begin(tagged, ring_count, idx)?;
     for i in 0..ring_count {
         process_linestring(raw, info, false, i, processor)?;
@@ -393,14 +687,14 @@ fn process_triangle<R: Read, P: GeomProcessor>(
     processor.triangle_end(tagged, idx)
 }
 
-fn process_compoundcurve<R: Read, P: GeomProcessor>(
+fn process_compoundcurve<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     read_header: fn(&mut R) -> Result<WkbInfo>,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let n_strings = raw.ioread_with::<u32>(info.endian)? as usize;
+    let n_strings = read_count(raw, info, HEADER_SIZE + 4)?;
     processor.compoundcurve_begin(n_strings, idx)?;
     for i in 0..n_strings {
         let info = read_header(raw)?;
@@ -417,7 +711,7 @@ fn process_compoundcurve<R: Read, P: GeomProcessor>(
     processor.compoundcurve_end(idx)
 }
 
-fn process_curve<R: Read, P: GeomProcessor>(
+fn process_curve<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     read_header: fn(&mut R) -> Result<WkbInfo>,
     idx: usize,
@@ -439,14 +733,14 @@ fn process_curve<R: Read, P: GeomProcessor>(
     Ok(())
 }
 
-fn process_curvepolygon<R: Read, P: GeomProcessor>(
+fn process_curvepolygon<R: WkbRead, P: GeomProcessor>(
     raw: &mut R,
     info: &WkbInfo,
     read_header: fn(&mut R) -> Result<WkbInfo>,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let ring_count = raw.ioread_with::<u32>(info.endian)? as usize;
+    let ring_count = read_count(raw, info, HEADER_SIZE + 4)?;
     processor.curvepolygon_begin(ring_count, idx)?;
     for i in 0..ring_count {
         process_curve(raw, read_header, i, processor)?;
@@ -511,6 +805,209 @@ mod test {
         );
     }
 
+    #[test]
+    fn hex_ewkb() -> Result<()> {
+        let hex = "01040000A0E6100000020000000101000080000000000000244000000000000034C0000000000000594001010000800000000000000000000000000000E0BF0000000000405940";
+        for input in &[hex.to_string(), hex.to_lowercase(), format!("\\x{}", hex)] {
+            let mut wkt_data: Vec<u8> = Vec::new();
+            let mut writer = WktWriter::new(&mut wkt_data);
+            writer.dims.z = true;
+            process_hex_ewkb_geom(input, &mut writer)?;
+            assert_eq!(
+                std::str::from_utf8(&wkt_data).unwrap(),
+                "MULTIPOINT(10 -20 100,0 -0.5 101)"
+            );
+        }
+
+        let mut wkt_data: Vec<u8> = Vec::new();
+        assert!(process_hex_ewkb_geom("0101000000XX", &mut WktWriter::new(&mut wkt_data)).is_err());
+        assert!(process_hex_ewkb_geom(&hex[..40], &mut WktWriter::new(&mut wkt_data)).is_err());
+        // Counts are checked against the hex length
+        let err = process_hex_ewkb_geom("0104000000FFFFFFFF", &mut WktWriter::new(&mut wkt_data))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "processing geometry `4294967295 elements exceed remaining 0 bytes` (at byte 9, in MultiPoint)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn srid_event() -> Result<()> {
+        struct SridCollector(Vec<Option<i32>>);
+        impl GeomProcessor for SridCollector {
+            fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+                self.0.push(srid);
+                Ok(())
+            }
+        }
+
+        let mut collector = SridCollector(Vec::new());
+        // SELECT 'SRID=4326;POINT(10 -20 100)'::geometry
+        let ewkb =
+            hex::decode("01010000A0E6100000000000000000244000000000000034C00000000000005940")
+                .unwrap();
+        process_ewkb_geom(&mut ewkb.as_slice(), &mut collector)?;
+        // SELECT 'POINT(10 -20)'::geometry
+        let ewkb = hex::decode("0101000000000000000000244000000000000034C0").unwrap();
+        process_ewkb_geom(&mut ewkb.as_slice(), &mut collector)?;
+        Ewkb(ewkb).process_geom(&mut collector)?;
+        assert_eq!(collector.0, vec![Some(4326), None, None]);
+        Ok(())
+    }
+
+    /// Non-seekable reader returning one byte per read, like a slow socket.
+    struct ByteStream<'a>(&'a [u8]);
+
+    impl Read for ByteStream<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn stream_records() -> Result<()> {
+        // SELECT 'SRID=4326;POINT(10 -20 100)'::geometry
+        let point_z =
+            hex::decode("01010000A0E6100000000000000000244000000000000034C00000000000005940")
+                .unwrap();
+        // SELECT 'LINESTRING(0 0,1 1)'::geometry
+        let line = hex::decode(
+            "01020000000200000000000000000000000000000000000000000000000000F03F000000000000F03F",
+        )
+        .unwrap();
+        let mut data = Vec::new();
+        for record in &[&point_z, &line] {
+            data.extend_from_slice(&(record.len() as i32).to_be_bytes());
+            data.extend_from_slice(record);
+        }
+        data.extend_from_slice(&(-1i32).to_be_bytes());
+        // record with trailing padding
+        data.extend_from_slice(&(line.len() as i32 + 2).to_be_bytes());
+        data.extend_from_slice(&line);
+        data.extend_from_slice(&[0, 0]);
+
+        let mut stream = ByteStream(&data);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt_data);
+        writer.dims.z = true;
+        let mut processed = Vec::new();
+        while !stream.0.is_empty() {
+            processed.push(process_wkb_record(
+                &mut stream,
+                WkbDialect::Ewkb,
+                &mut writer,
+            )?);
+        }
+        assert_eq!(processed, vec![true, true, false, true]);
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "POINT(10 -20 100)LINESTRING(0 0,1 1)LINESTRING(0 0,1 1)"
+        );
+
+        // geometries without framing
+        let mut data = point_z.clone();
+        data.extend_from_slice(&line);
+        let mut stream = ByteStream(&data);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        process_ewkb_geom(&mut stream, &mut WktWriter::new(&mut wkt_data))?;
+        process_ewkb_geom(&mut stream, &mut WktWriter::new(&mut wkt_data))?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "POINT(10 -20)LINESTRING(0 0,1 1)"
+        );
+
+        // truncated record
+        let mut data = (point_z.len() as i32).to_be_bytes().to_vec();
+        data.extend_from_slice(&point_z[..10]);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        assert!(process_wkb_record(
+            &mut ByteStream(&data),
+            WkbDialect::Ewkb,
+            &mut WktWriter::new(&mut wkt_data)
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn geometry_dims_srid() {
+        use crate::ToWkb;
+
+        // SELECT 'SRID=4326;POINT(10 -20 100)'::geometry
+        let ewkb = Ewkb(
+            hex::decode("01010000A0E6100000000000000000244000000000000034C00000000000005940")
+                .unwrap(),
+        );
+        assert!(ewkb.dims().z);
+        assert!(!ewkb.dims().m);
+        assert_eq!(ewkb.srid(), Some(4326));
+        assert_eq!(ewkb.to_wkt_ndim(ewkb.dims()).unwrap(), "POINT(10 -20 100)");
+
+        let wkb = Wkb(ewkb.to_wkb(ewkb.dims()).unwrap());
+        assert!(wkb.dims().z);
+        assert_eq!(wkb.srid(), None);
+
+        assert!(!Ewkb(Vec::new()).dims().z);
+        assert_eq!(Ewkb(Vec::new()).srid(), None);
+    }
+
+    #[test]
+    fn type_codes() {
+        // ISO POINT Z (10 -20 100) read as EWKB
+        let iso =
+            hex::decode("01E9030000000000000000244000000000000034C00000000000005940").unwrap();
+        let info = read_ewkb_header(&mut iso.as_slice()).unwrap();
+        assert_eq!(info.base_type, WKBGeometryType::Point);
+        assert!(info.has_z);
+        assert!(!info.has_m);
+        assert_eq!(
+            Ewkb(iso).to_wkt_ndim(CoordDimensions::xyz()).unwrap(),
+            "POINT(10 -20 100)"
+        );
+
+        // EWKB SRID=4326;POINT(10 -20 100) read as WKB
+        let ewkb =
+            hex::decode("01010000A0E6100000000000000000244000000000000034C00000000000005940")
+                .unwrap();
+        let info = read_wkb_header(&mut ewkb.as_slice()).unwrap();
+        assert!(info.has_z);
+        assert_eq!(info.srid, Some(4326));
+        assert_eq!(
+            Wkb(ewkb).to_wkt_ndim(CoordDimensions::xyz()).unwrap(),
+            "POINT(10 -20 100)"
+        );
+
+        // ISO POINT ZM in the 3000 range
+        let info = read_wkb_header(&mut hex::decode("01B90B0000").unwrap().as_slice()).unwrap();
+        assert_eq!(info.base_type, WKBGeometryType::Point);
+        assert!(info.has_z && info.has_m);
+
+        // Unknown dimension range
+        let info = read_wkb_header(&mut hex::decode("01A10F0000").unwrap().as_slice()).unwrap();
+        assert_eq!(info.base_type, WKBGeometryType::Unknown);
+    }
+
+    #[test]
+    fn mixed_dims() {
+        // 2D GEOMETRYCOLLECTION header with EWKB POINT Z member
+        let wkb = Wkb(hex::decode(
+            "0107000000010000000101000080000000000000244000000000000034C00000000000005940",
+        )
+        .unwrap());
+        assert!(wkb.dims().z);
+        assert!(!wkb.dims().m);
+        assert_eq!(
+            wkb.to_wkt_ndim(wkb.dims()).unwrap(),
+            "GEOMETRYCOLLECTION(POINT(10 -20 100))"
+        );
+    }
+
     #[test]
     fn ewkb_geometries() {
         // SELECT 'POINT(10 -20)'::geometry
@@ -665,6 +1162,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn error_context() {
+        // Truncated LINESTRING(10 -20,0 -0.5)
+        let wkb = hex::decode("0102000000020000000000000000002440").unwrap();
+        let err = process_wkb_geom(&mut wkb.as_slice(), &mut WktWriter::new(&mut Vec::new()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "I/O error (at byte 17, in LineString)");
+        assert!(matches!(err.kind(), GeozeroError::IoError(_)));
+    }
+
+    #[test]
+    fn corrupt_counts() {
+        // LINESTRING with 2^32-1 points and no coordinates
+        let wkb = Wkb(hex::decode("0102000000FFFFFFFF").unwrap());
+        let err = wkb.to_wkt().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "processing geometry `4294967295 elements exceed remaining 0 bytes` (at byte 9, in LineString)"
+        );
+        // Counts are not checked when reading from a stream
+        let err = process_wkb_geom(&mut wkb.0.as_slice(), &mut WktWriter::new(&mut Vec::new()))
+            .unwrap_err();
+        assert!(matches!(err.kind(), GeozeroError::IoError(_)));
+        // MULTIPOINT with 2^32-1 points from a stream
+        let mut stream = std::io::Cursor::new(hex::decode("0104000000FFFFFFFF").unwrap());
+        let err = process_ewkb_geom(&mut stream, &mut WktWriter::new(&mut Vec::new())).unwrap_err();
+        assert!(matches!(err.kind(), GeozeroError::IoError(_)));
+    }
+
+    #[test]
+    fn nesting_depth() {
+        let mut wkb = Vec::new();
+        for _ in 0..100 {
+            // GEOMETRYCOLLECTION with 1 member
+            wkb.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        wkb.extend(hex::decode("0101000000000000000000244000000000000034C0").unwrap());
+        let err = Wkb(wkb).to_wkt().unwrap_err();
+        assert!(matches!(err.kind(), GeozeroError::LimitExceeded(_)));
+        assert!(err
+            .to_string()
+            .contains("geometry collections nested deeper than 64 levels"));
+    }
+
     #[test]
     fn scroll_error() {
         let err = read_ewkb_header(&mut std::io::Cursor::new(b"")).unwrap_err();
@@ -1,10 +1,15 @@
 use crate::error::Result;
+use crate::geometry_processor::{process_coords, SizeCheck};
+use crate::wkb::wkb_hex::write_hex;
 use crate::wkb::{WKBByteOrder, WKBGeometryType, WkbDialect};
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
-use scroll::IOwrite;
 use std::io::Write;
 
 /// WKB writer.
+///
+/// Unbuffered outputs should be wrapped in a [WriteCounter](crate::WriteCounter).
+/// Network buffers implementing `bytes::BufMut`, e.g. of database drivers, can be
+/// written directly with `BufMut::writer`.
 pub struct WkbWriter<'a, W: Write> {
     pub dims: CoordDimensions,
     pub srid: Option<i32>,
@@ -16,13 +21,33 @@ pub struct WkbWriter<'a, W: Write> {
     pub extended_gpkg: bool,
     /// Empty geometry flag (GPKG)
     pub empty: bool,
+    /// Fail if number of members differs from declared size
+    pub strict: bool,
+    /// Detect Z and M presence from the first coordinate instead of using `dims`
+    pub auto_dims: bool,
+    /// Write uppercase hex text instead of binary output (e.g. for PostGIS text mode)
+    pub hex: bool,
+    sizes: SizeCheck,
+    /// Geometry nesting level
+    depth: usize,
+    /// Output buffered until dimensions are detected
+    pending: Option<PendingGeometry>,
     endian: scroll::Endian,
     dialect: WkbDialect,
+    /// Next header is the main header of a geometry
     first_header: bool,
     geom_state: GeomState,
     out: &'a mut W,
 }
 
+/// Buffered geometry output with positions of type codes to be completed
+struct PendingGeometry {
+    buf: Vec<u8>,
+    type_id_offsets: Vec<usize>,
+    /// End positions of empty points written with XY coordinates
+    empty_point_ends: Vec<usize>,
+}
+
 #[derive(PartialEq, Debug)]
 enum GeomState {
     Normal,
@@ -39,6 +64,12 @@ impl<'a, W: Write> WkbWriter<'a, W> {
             envelope_dims: CoordDimensions::default(),
             extended_gpkg: false,
             empty: false,
+            strict: false,
+            auto_dims: false,
+            hex: false,
+            sizes: SizeCheck::default(),
+            depth: 0,
+            pending: None,
             endian: scroll::LE,
             dialect,
             first_header: true,
@@ -47,8 +78,165 @@ impl<'a, W: Write> WkbWriter<'a, W> {
         }
     }
 
+    /// Flush and return the output.
+    pub fn into_inner(self) -> Result<&'a mut W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Reset geometry state after an incomplete or failed geometry.
+    ///
+    /// Buffered output of an unfinished geometry is discarded. The next geometry is
+    /// written with a main header (EWKB SRID or GPKG header).
+    pub fn reset(&mut self) {
+        self.sizes = SizeCheck::default();
+        self.depth = 0;
+        self.pending = None;
+        self.first_header = true;
+        self.geom_state = GeomState::Normal;
+    }
+
+    fn begin_sized(&mut self, size: usize) {
+        self.depth += 1;
+        if self.strict {
+            self.sizes.begin(size);
+        }
+    }
+    fn end_sized(&mut self, geometry_type: &str) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            if self.pending.is_some() {
+                // geometry without coordinates
+                self.detect_dims(false, false)?;
+            }
+            // next geometry gets its own main header
+            self.first_header = true;
+        }
+        if self.strict {
+            self.sizes.end(geometry_type)
+        } else {
+            Ok(())
+        }
+    }
+    fn member(&mut self) {
+        if self.strict {
+            self.sizes.member();
+        }
+    }
+
+    fn write_out(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.hex {
+            write_hex(self.out, bytes)?;
+        } else {
+            self.out.write_all(bytes)?;
+        }
+        Ok(())
+    }
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        match self.pending.as_mut() {
+            Some(pending) => pending.buf.extend_from_slice(bytes),
+            None => self.write_out(bytes)?,
+        }
+        Ok(())
+    }
+    fn write_u8(&mut self, v: u8) -> Result<()> {
+        self.write_bytes(&[v])
+    }
+    fn write_u32(&mut self, v: u32) -> Result<()> {
+        if self.endian == scroll::BE {
+            self.write_bytes(&v.to_be_bytes())
+        } else {
+            self.write_bytes(&v.to_le_bytes())
+        }
+    }
+    fn write_i32(&mut self, v: i32) -> Result<()> {
+        if self.endian == scroll::BE {
+            self.write_bytes(&v.to_be_bytes())
+        } else {
+            self.write_bytes(&v.to_le_bytes())
+        }
+    }
+    fn write_f64(&mut self, v: f64) -> Result<()> {
+        if self.endian == scroll::BE {
+            self.write_bytes(&v.to_be_bytes())
+        } else {
+            self.write_bytes(&v.to_le_bytes())
+        }
+    }
+
+    /// Type code with dimension information of selected format
+    fn dims_type_id(&self, type_id: u32) -> u32 {
+        match self.dialect {
+            WkbDialect::Ewkb => {
+                let mut type_id = type_id;
+                if self.dims.z {
+                    type_id |= 0x80000000;
+                }
+                if self.dims.m {
+                    type_id |= 0x40000000;
+                }
+                type_id
+            }
+            WkbDialect::Wkb | WkbDialect::Geopackage => {
+                type_id + 1000 * self.dims.z as u32 + 2000 * self.dims.m as u32
+            }
+        }
+    }
+
+    /// Set detected dimensions and write buffered output
+    fn detect_dims(&mut self, z: bool, m: bool) -> Result<()> {
+        if let Some(mut pending) = self.pending.take() {
+            self.dims.z = z;
+            self.dims.m = m;
+            for offset in pending.type_id_offsets {
+                let bytes = &mut pending.buf[offset..offset + 4];
+                let mut raw = [0; 4];
+                raw.copy_from_slice(bytes);
+                let raw = if self.endian == scroll::BE {
+                    self.dims_type_id(u32::from_be_bytes(raw)).to_be_bytes()
+                } else {
+                    self.dims_type_id(u32::from_le_bytes(raw)).to_le_bytes()
+                };
+                bytes.copy_from_slice(&raw);
+            }
+            let extra = z as usize + m as usize;
+            if extra == 0 || pending.empty_point_ends.is_empty() {
+                self.write_out(&pending.buf)?;
+            } else {
+                // complete the NaN coordinates of empty points
+                let nan = if self.endian == scroll::BE {
+                    f64::NAN.to_be_bytes()
+                } else {
+                    f64::NAN.to_le_bytes()
+                };
+                let mut buf = Vec::with_capacity(
+                    pending.buf.len() + pending.empty_point_ends.len() * extra * 8,
+                );
+                let mut start = 0;
+                for end in pending.empty_point_ends {
+                    buf.extend_from_slice(&pending.buf[start..end]);
+                    (0..extra).for_each(|_| buf.extend_from_slice(&nan));
+                    start = end;
+                }
+                buf.extend_from_slice(&pending.buf[start..]);
+                self.write_out(&buf)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Write header in selected format
     fn write_header(&mut self, wkb_type: WKBGeometryType) -> Result<()> {
+        let top_level = self.depth <= 1 && self.geom_state == GeomState::Normal;
+        if self.auto_dims && top_level && self.pending.is_none() {
+            // buffer until the first coordinate
+            self.dims = CoordDimensions::default();
+            self.pending = Some(PendingGeometry {
+                buf: Vec::new(),
+                type_id_offsets: Vec::new(),
+                empty_point_ends: Vec::new(),
+            });
+        }
         match self.dialect {
             WkbDialect::Wkb => self.write_wkb_header(wkb_type)?,
             WkbDialect::Ewkb => self.write_ewkb_header(wkb_type)?,
@@ -69,15 +257,10 @@ impl<'a, W: Write> WkbWriter<'a, W> {
         } else {
             WKBByteOrder::NDR
         };
-        self.out.iowrite(byte_order as u8)?;
-        let mut type_id = wkb_type as u32;
-        if self.dims.z {
-            type_id += 1000;
-        }
-        if self.dims.m {
-            type_id += 2000;
-        }
-        self.out.iowrite_with(type_id, self.endian)?;
+        self.write_u8(byte_order as u8)?;
+        let type_id = self.dims_type_id(wkb_type as u32);
+        self.mark_type_id();
+        self.write_u32(type_id)?;
         Ok(())
     }
 
@@ -88,24 +271,19 @@ impl<'a, W: Write> WkbWriter<'a, W> {
         } else {
             WKBByteOrder::NDR
         };
-        self.out.iowrite(byte_order as u8)?;
+        self.write_u8(byte_order as u8)?;
 
-        let mut type_id = wkb_type as u32;
-        if self.dims.z {
-            type_id |= 0x80000000;
-        }
-        if self.dims.m {
-            type_id |= 0x40000000;
-        }
+        let mut type_id = self.dims_type_id(wkb_type as u32);
         if self.srid.is_some() && self.first_header {
             type_id |= 0x20000000;
         }
-        self.out.iowrite_with(type_id, self.endian)?;
+        self.mark_type_id();
+        self.write_u32(type_id)?;
 
         if self.first_header {
             // write SRID in main header only
             if let Some(srid) = self.srid {
-                self.out.iowrite_with(srid, self.endian)?;
+                self.write_i32(srid)?;
             }
             self.first_header = false;
         }
@@ -113,12 +291,19 @@ impl<'a, W: Write> WkbWriter<'a, W> {
         Ok(())
     }
 
+    /// Remember position of type code written with undetected dimensions
+    fn mark_type_id(&mut self) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.type_id_offsets.push(pending.buf.len());
+        }
+    }
+
     /// GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
     fn write_gpkg_header(&mut self) -> Result<()> {
         let magic = b"GP";
-        self.out.write_all(magic)?;
+        self.write_bytes(magic)?;
         let version: u8 = 0;
-        self.out.iowrite(version)?;
+        self.write_u8(version)?;
 
         let mut flags: u8 = 0;
         if self.extended_gpkg {
@@ -142,15 +327,15 @@ impl<'a, W: Write> WkbWriter<'a, W> {
             flags |= 0b0000_0001;
         }
         // println!("flags: {:#010b}", flags);
-        self.out.iowrite(flags)?;
+        self.write_u8(flags)?;
 
         // srs_id
         // 0: undefined geographic coordinate reference systems
         // -1: undefined Cartesian coordinate reference systems
-        self.out.iowrite_with(self.srid.unwrap_or(0), self.endian)?;
+        self.write_i32(self.srid.unwrap_or(0))?;
 
-        for val in &self.envelope {
-            self.out.iowrite_with(*val, self.endian)?;
+        for val in self.envelope.clone() {
+            self.write_f64(val)?;
         }
 
         Ok(())
@@ -159,15 +344,14 @@ impl<'a, W: Write> WkbWriter<'a, W> {
 
 impl<W: Write> GeomProcessor for WkbWriter<'_, W> {
     fn dimensions(&self) -> CoordDimensions {
-        self.dims
-    }
-    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
-        if self.geom_state == GeomState::MultiPointGeom {
-            self.write_header(WKBGeometryType::Point)?;
+        if self.auto_dims {
+            CoordDimensions::xyzm()
+        } else {
+            self.dims
         }
-        self.out.iowrite_with(x, self.endian)?;
-        self.out.iowrite_with(y, self.endian)?;
-        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.coordinate(x, y, None, None, None, None, idx)
     }
     fn coordinate(
         &mut self,
@@ -179,121 +363,231 @@ impl<W: Write> GeomProcessor for WkbWriter<'_, W> {
         _tm: Option<u64>,
         _idx: usize,
     ) -> Result<()> {
+        self.member();
         if self.geom_state == GeomState::MultiPointGeom {
             self.write_header(WKBGeometryType::Point)?;
         }
-        self.out.iowrite_with(x, self.endian)?;
-        self.out.iowrite_with(y, self.endian)?;
-        if let Some(z) = z {
-            self.out.iowrite_with(z, self.endian)?;
+        if self.pending.is_some() {
+            self.detect_dims(z.is_some(), m.is_some())?;
+        }
+        self.write_f64(x)?;
+        self.write_f64(y)?;
+        if self.dims.z {
+            self.write_f64(z.unwrap_or(f64::NAN))?;
+        }
+        if self.dims.m {
+            self.write_f64(m.unwrap_or(f64::NAN))?;
+        }
+        Ok(())
+    }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        let bulk = self.pending.is_none()
+            && self.geom_state != GeomState::MultiPointGeom
+            && (dims.z, dims.m) == (self.dims.z, self.dims.m);
+        if !bulk {
+            return process_coords(self, coords, dims, idx);
+        }
+        let stride = dims.xyzm_stride();
+        let count = coords.len() / stride;
+        if self.strict {
+            (0..count).for_each(|_| self.member());
+        }
+        // write all coordinates with a single call
+        let mut buf = Vec::with_capacity(count * stride * 8);
+        for v in &coords[..count * stride] {
+            if self.endian == scroll::BE {
+                buf.extend_from_slice(&v.to_be_bytes());
+            } else {
+                buf.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        self.write_out(&buf)?;
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.member();
+        // Empty points are encoded with NaN coordinates
+        self.write_header(WKBGeometryType::Point)?;
+        let ndims = 2 + self.dims.z as usize + self.dims.m as usize;
+        for _ in 0..ndims {
+            self.write_f64(f64::NAN)?;
         }
-        if let Some(m) = m {
-            self.out.iowrite_with(m, self.endian)?;
+        if let Some(pending) = self.pending.as_mut() {
+            pending.empty_point_ends.push(pending.buf.len());
+        }
+        if self.depth == 0 {
+            if self.pending.is_some() {
+                self.detect_dims(false, false)?;
+            }
+            self.first_header = true;
         }
         Ok(())
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin_sized(1);
         self.write_header(WKBGeometryType::Point)
     }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("Point")
+    }
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::MultiPoint)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         self.geom_state = GeomState::MultiPointGeom;
         Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_state = GeomState::Normal;
-        Ok(())
+        self.end_sized("MultiPoint")
     }
     fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         if self.geom_state != GeomState::RingGeom {
             self.write_header(WKBGeometryType::LineString)?;
         }
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end_sized("LineString")
+    }
     fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::MultiLineString)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiLineString")
+    }
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::Polygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         self.geom_state = GeomState::RingGeom;
         Ok(())
     }
     fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
         self.geom_state = GeomState::Normal;
-        Ok(())
+        self.end_sized("Polygon")
     }
     fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::MultiPolygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiPolygon")
+    }
     fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::GeometryCollection)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("GeometryCollection")
+    }
 
     fn circularstring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::CircularString)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("CircularString")
+    }
     fn compoundcurve_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::CompoundCurve)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("CompoundCurve")
+    }
     fn curvepolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::CurvePolygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("CurvePolygon")
+    }
     fn multicurve_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::MultiCurve)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiCurve")
+    }
     fn multisurface_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::MultiSurface)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("MultiSurface")
+    }
 
     fn triangle_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::Triangle)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         self.geom_state = GeomState::RingGeom;
         Ok(())
     }
     fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
         self.geom_state = GeomState::Normal;
-        Ok(())
+        self.end_sized("Triangle")
     }
     fn polyhedralsurface_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::PolyhedralSurface)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("PolyhedralSurface")
+    }
     fn tin_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.begin_sized(size);
         self.write_header(WKBGeometryType::Tin)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.write_u32(size as u32)?;
         Ok(())
     }
+    fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_sized("Tin")
+    }
 }
 
 impl<W: Write> PropertyProcessor for WkbWriter<'_, W> {}
 
-impl<W: Write> FeatureProcessor for WkbWriter<'_, W> {}
+impl<W: Write> FeatureProcessor for WkbWriter<'_, W> {
+    /// Use dataset SRID, if no SRID is set
+    fn dataset_srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.srid.is_none() {
+            self.srid = srid;
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.reset();
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::wkb::{process_ewkb_geom, process_gpkg_geom};
+    use crate::wkb::{process_ewkb_geom, process_gpkg_geom, process_wkb_geom};
     use crate::ToWkb;
 
     fn ewkb_roundtrip(ewkbstr: &str, with_z: bool, srid: Option<i32>) -> bool {
@@ -338,6 +632,298 @@ mod test {
         assert!(ewkb_roundtrip("01070000000300000001010000000000000000002440000000000000244001010000000000000000003E400000000000003E400102000000020000000000000000002E400000000000002E4000000000000034400000000000003440", false, None));
     }
 
+    #[test]
+    fn ewkb_empty() {
+        // SELECT 'POINT EMPTY'::geometry
+        assert!(ewkb_roundtrip(
+            "0101000000000000000000F87F000000000000F87F",
+            false,
+            None
+        ));
+
+        // SELECT 'LINESTRING EMPTY'::geometry
+        assert!(ewkb_roundtrip("010200000000000000", false, None));
+
+        // SELECT 'GEOMETRYCOLLECTION(POINT EMPTY,POLYGON EMPTY)'::geometry
+        assert!(ewkb_roundtrip(
+            "0107000000020000000101000000000000000000F87F000000000000F87F010300000000000000",
+            false,
+            None
+        ));
+    }
+
+    #[test]
+    fn ewkb_collection_srid() -> Result<()> {
+        // SELECT 'SRID=4326;GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(POINT(1 2)),MULTIPOINT(3 4))'::geometry
+        assert!(ewkb_roundtrip("0107000020E6100000020000000107000000010000000101000000000000000000F03F0000000000000040010400000001000000010100000000000000000008400000000000001040", false, Some(4326)));
+
+        // OGC WKB never contains an SRID
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.srid = Some(4326);
+        writer.geometrycollection_begin(1, 0)?;
+        writer.geometrycollection_begin(1, 0)?;
+        writer.point_begin(0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.point_end(0)?;
+        writer.geometrycollection_end(0)?;
+        writer.geometrycollection_end(0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "0107000000010000000107000000010000000101000000000000000000F03F0000000000000040"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_geometries() -> Result<()> {
+        let point = "0101000020E6100000000000000000F03F0000000000000040";
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Ewkb);
+        writer.srid = Some(4326);
+        for _ in 0..2 {
+            writer.point_begin(0)?;
+            writer.xy(1.0, 2.0, 0)?;
+            writer.point_end(0)?;
+        }
+        assert_eq!(hex::encode_upper(&wkb_out), format!("{}{}", point, point));
+
+        // incomplete geometry
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Ewkb);
+        writer.srid = Some(4326);
+        writer.geometrycollection_begin(1, 0)?;
+        writer.geometry_begin()?;
+        writer.point_begin(0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.point_end(0)?;
+        assert!(hex::encode_upper(writer.into_inner()?).ends_with(point));
+
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Geopackage);
+        writer.srid = Some(4326);
+        for _ in 0..2 {
+            writer.empty_point(0)?;
+        }
+        let gpkg_point = "47500001E61000000101000000000000000000F87F000000000000F87F";
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            format!("{}{}", gpkg_point, gpkg_point)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multipoint_with_empty() -> Result<()> {
+        // MULTIPOINT (EMPTY, 1 2)
+        let wkb = hex::decode("0104000000020000000101000000000000000000F87F000000000000F87F0101000000000000000000F03F0000000000000040").unwrap();
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        crate::wkb::process_wkb_geom(&mut wkb.as_slice(), &mut writer)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "0104000000010000000101000000000000000000F03F0000000000000040"
+        );
+
+        // output can be read again
+        let mut wkb_again: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_again, WkbDialect::Wkb);
+        crate::wkb::process_wkb_geom(&mut wkb_out.as_slice(), &mut writer)?;
+        assert_eq!(wkb_again, wkb_out);
+        Ok(())
+    }
+
+    #[test]
+    fn dataset_srid() -> Result<()> {
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Ewkb);
+        writer.dataset_srid(Some(4326))?;
+        writer.point_begin(0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.point_end(0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "0101000020E6100000000000000000F03F0000000000000040"
+        );
+
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Ewkb);
+        writer.srid = Some(2056);
+        writer.dataset_srid(Some(4326))?;
+        assert_eq!(writer.srid, Some(2056));
+        Ok(())
+    }
+
+    #[test]
+    fn auto_dims() -> Result<()> {
+        // SELECT 'SRID=4326;MULTIPOINT (10 -20 100, 0 -0.5 101)'::geometry
+        let ewkb = hex::decode("01040000A0E6100000020000000101000080000000000000244000000000000034C0000000000000594001010000800000000000000000000000000000E0BF0000000000405940").unwrap();
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Ewkb);
+        writer.auto_dims = true;
+        writer.srid = Some(4326);
+        process_ewkb_geom(&mut ewkb.as_slice(), &mut writer)?;
+        assert_eq!(wkb_out, ewkb);
+
+        // LINESTRING Z (1 2 3, 4 5 6)
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.auto_dims = true;
+        writer.linestring_begin(true, 2, 0)?;
+        writer.coordinate(1.0, 2.0, Some(3.0), None, None, None, 0)?;
+        writer.coordinate(4.0, 5.0, Some(6.0), None, None, None, 1)?;
+        writer.linestring_end(true, 0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "01EA03000002000000000000000000F03F00000000000000400000000000000840000000000000104000000000000014400000000000001840"
+        );
+
+        // POINT (1 2)
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.auto_dims = true;
+        writer.point_begin(0)?;
+        writer.coordinate(1.0, 2.0, None, None, None, None, 0)?;
+        writer.point_end(0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "0101000000000000000000F03F0000000000000040"
+        );
+
+        // LINESTRING EMPTY
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.auto_dims = true;
+        writer.linestring_begin(true, 0, 0)?;
+        writer.linestring_end(true, 0)?;
+        assert_eq!(hex::encode_upper(&wkb_out), "010200000000000000");
+
+        // GEOMETRYCOLLECTION Z (POINT Z EMPTY, POINT Z (1 2 3))
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.auto_dims = true;
+        writer.geometrycollection_begin(2, 0)?;
+        writer.empty_point(0)?;
+        writer.point_begin(1)?;
+        writer.coordinate(1.0, 2.0, Some(3.0), None, None, None, 0)?;
+        writer.point_end(1)?;
+        writer.geometrycollection_end(0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "01EF0300000200000001E9030000000000000000F87F000000000000F87F000000000000F87F01E9030000000000000000F03F00000000000000400000000000000840"
+        );
+        // read back
+        let mut roundtrip: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut roundtrip, WkbDialect::Wkb);
+        writer.dims = CoordDimensions::xyz();
+        process_wkb_geom(&mut wkb_out.as_slice(), &mut writer)?;
+        assert_eq!(roundtrip, wkb_out);
+        Ok(())
+    }
+
+    #[test]
+    fn coords() -> Result<()> {
+        let coords = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        for dims in &[CoordDimensions::xy(), CoordDimensions::xyz()] {
+            let mut bulk: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::new(&mut bulk, WkbDialect::Ewkb);
+            writer.dims = *dims;
+            let size = coords.len() / dims.xyzm_stride();
+            writer.linestring_begin(true, size, 0)?;
+            writer.coords(&coords, *dims, 0)?;
+            writer.linestring_end(true, 0)?;
+
+            let mut single: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::new(&mut single, WkbDialect::Ewkb);
+            writer.dims = *dims;
+            writer.linestring_begin(true, size, 0)?;
+            process_coords(&mut writer, &coords, *dims, 0)?;
+            writer.linestring_end(true, 0)?;
+            assert_eq!(bulk, single);
+        }
+
+        // MultiPoint members with headers
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.multipoint_begin(2, 0)?;
+        writer.coords(&coords[..4], CoordDimensions::xy(), 0)?;
+        writer.multipoint_end(0)?;
+        assert_eq!(
+            hex::encode_upper(&wkb_out),
+            "0104000000020000000101000000000000000000F03F0000000000000040010100000000000000000008400000000000001040"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hex_output() -> Result<()> {
+        let ewkb = hex::decode("01040000A0E6100000020000000101000080000000000000244000000000000034C0000000000000594001010000800000000000000000000000000000E0BF0000000000405940").unwrap();
+        for dialect in &[WkbDialect::Ewkb, WkbDialect::Geopackage] {
+            let mut binary: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::new(&mut binary, *dialect);
+            writer.auto_dims = true;
+            process_ewkb_geom(&mut ewkb.as_slice(), &mut writer)?;
+
+            let mut text: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::new(&mut text, *dialect);
+            writer.auto_dims = true;
+            writer.hex = true;
+            process_ewkb_geom(&mut ewkb.as_slice(), &mut writer)?;
+            assert_eq!(String::from_utf8(text).unwrap(), hex::encode_upper(&binary));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_into_buffer() -> Result<()> {
+        use crate::wkb::Ewkb;
+
+        let ewkb =
+            hex::decode("01010000A0E6100000000000000000244000000000000034C00000000000005940")
+                .unwrap();
+        let geom = Ewkb(ewkb.clone());
+        // append to buffer with length prefix
+        let mut buf = vec![0, 0, 0, 29];
+        geom.write_ewkb(&mut buf, CoordDimensions::xyz(), Some(4326))?;
+        assert_eq!(&buf[..4], &[0, 0, 0, 29]);
+        assert_eq!(&buf[4..], ewkb.as_slice());
+
+        #[cfg(feature = "with-postgis-postgres")]
+        {
+            use bytes::BufMut;
+            let mut buf = bytes::BytesMut::new();
+            geom.write_ewkb(&mut (&mut buf).writer(), CoordDimensions::xyz(), Some(4326))?;
+            assert_eq!(&buf[..], ewkb.as_slice());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn strict_sizes() -> Result<()> {
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.strict = true;
+        writer.linestring_begin(true, 3, 0)?;
+        writer.xy(0.0, 0.0, 0)?;
+        writer.xy(1.0, 1.0, 1)?;
+        assert_eq!(
+            writer.linestring_end(true, 0).unwrap_err().to_string(),
+            "processing geometry `LineString declared with size 3 but has 2 members`"
+        );
+
+        let mut writer = WkbWriter::new(&mut wkb_out, WkbDialect::Wkb);
+        writer.strict = true;
+        writer.polygon_begin(true, 1, 0)?;
+        writer.linestring_begin(false, 1, 0)?;
+        writer.xy(0.0, 0.0, 0)?;
+        writer.linestring_end(false, 0)?;
+        writer.linestring_begin(false, 1, 1)?;
+        writer.xy(0.0, 0.0, 0)?;
+        writer.linestring_end(false, 1)?;
+        assert!(writer.polygon_end(true, 0).is_err());
+        Ok(())
+    }
+
     #[test]
     fn ewkb_curves() {
         // SELECT 'CIRCULARSTRING(0 0,1 1,2 0)'::geometry
@@ -0,0 +1,158 @@
+use crate::error::Result;
+use crate::{wkt::WktWriter, CoordDimensions, GeomProcessor};
+
+// Writing Wkt is delegated to the WktWriter, and it's output buffered to Vec, to be eventually
+// incorporated in the output of the GeoParquet writer. Is there a better way?
+#[derive(Default)]
+pub(crate) struct BufferingWktWriter {
+    buffer: Vec<u8>,
+    pub(crate) dims: CoordDimensions,
+    /// Opening parenthesis state of the WktWriter, kept between events
+    pending: bool,
+    pending_space: bool,
+}
+
+impl BufferingWktWriter {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.buffer.clear();
+        self.pending = false;
+        self.pending_space = false;
+    }
+
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Forward an event to a WktWriter continuing the current geometry.
+    fn with_writer<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut WktWriter<'_, Vec<u8>>) -> Result<()>,
+    {
+        let mut writer = WktWriter::new(&mut self.buffer);
+        writer.dims = self.dims;
+        writer.pending = self.pending;
+        writer.pending_space = self.pending_space;
+        let result = f(&mut writer);
+        self.pending = writer.pending;
+        self.pending_space = writer.pending_space;
+        result
+    }
+}
+
+impl GeomProcessor for BufferingWktWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.with_writer(|w| w.coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.point_begin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.point_end(idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.empty_point(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multipoint_begin(size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multipoint_end(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.linestring_begin(tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.linestring_end(tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multilinestring_begin(size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multilinestring_end(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.polygon_begin(tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.polygon_end(tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multipolygon_begin(size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multipolygon_end(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.geometrycollection_begin(size, idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.geometrycollection_end(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.circularstring_begin(size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.circularstring_end(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.compoundcurve_begin(size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.compoundcurve_end(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.curvepolygon_begin(size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.curvepolygon_end(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multicurve_begin(size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multicurve_end(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multisurface_begin(size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.multisurface_end(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.triangle_begin(tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.triangle_end(tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.polyhedralsurface_begin(size, idx))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.polyhedralsurface_end(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.tin_begin(size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.with_writer(|w| w.tin_end(idx))
+    }
+}
@@ -1,6 +1,8 @@
 //! Well-Known Text (WKT) conversions.
 //!
 //! OpenGIS Simple Features Specification For SQL Revision 1.1, Chapter 3.2.5
+#[cfg(feature = "with-geoparquet")]
+pub(crate) mod buffering_wkt_writer;
 pub(crate) mod wkt_reader;
 pub(crate) mod wkt_writer;
 
@@ -1,7 +1,9 @@
 use crate::error::{GeozeroError, Result};
-use crate::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use crate::{
+    FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry, RewindableDatasource,
+};
 
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 use wkt::types::{
     Coord, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Point,
     Polygon,
@@ -33,6 +35,12 @@ impl GeozeroDatasource for WktStr<'_> {
     }
 }
 
+impl RewindableDatasource for WktStr<'_> {
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// Wkt Reader.
 pub struct WktReader<'a, R: Read>(pub &'a mut R);
 
@@ -42,6 +50,13 @@ impl<'a, R: Read> GeozeroDatasource for WktReader<'a, R> {
     }
 }
 
+impl<'a, R: Read + Seek> RewindableDatasource for WktReader<'a, R> {
+    fn rewind(&mut self) -> Result<()> {
+        self.0.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+}
+
 /// Read and process WKT geometry.
 pub fn read_wkt<R: Read, P: GeomProcessor>(reader: &mut R, processor: &mut P) -> Result<()> {
     use std::str::FromStr;
@@ -351,6 +366,21 @@ mod test {
             assert_eq!("POINT EMPTY", &actual);
         }
 
+        #[test]
+        fn empty_roundtrip() {
+            for wkt in [
+                "LINESTRING EMPTY",
+                "POLYGON EMPTY",
+                "MULTIPOINT EMPTY",
+                "MULTILINESTRING EMPTY",
+                "MULTIPOLYGON EMPTY",
+                "GEOMETRYCOLLECTION EMPTY",
+                "GEOMETRYCOLLECTION(POINT EMPTY,LINESTRING EMPTY,POINT(1 2))",
+            ] {
+                assert_eq!(wkt, &WktStr(wkt).to_wkt().unwrap());
+            }
+        }
+
         #[test]
         fn geometry_collection_with_empty_point() {
             let str = "GEOMETRYCOLLECTION(POINT(40 10),LINESTRING(10 10,20 20,10 40),POINT EMPTY)";
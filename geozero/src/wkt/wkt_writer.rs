@@ -2,39 +2,212 @@ use crate::error::Result;
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use std::io::Write;
 
+/// Output style of [WktWriter].
+///
+/// The default is the compact PostGIS style, e.g. `POINT(1 2 3)` or `LINESTRING(1 2,3 4)`.
+#[derive(Clone, Default, Debug)]
+pub struct WktWriterOptions {
+    /// Write keywords in lowercase, e.g. `point(1 2)` (default: false)
+    pub lowercase: bool,
+    /// Write a space after commas, e.g. `LINESTRING(1 2, 3 4)` (default: false)
+    pub space_after_comma: bool,
+    /// ISO style with Z/M dimension tags and a space before parentheses,
+    /// e.g. `POINT Z (1 2 3)` (default: false)
+    ///
+    /// The dimension tags follow the ordinates of the written coordinates.
+    pub iso: bool,
+}
+
+impl WktWriterOptions {
+    /// Compact PostGIS style without optional whitespace.
+    pub fn compact() -> Self {
+        Self::default()
+    }
+    /// ISO style with spaces, e.g. `LINESTRING Z (1 2 3, 4 5 6)`.
+    pub fn iso() -> Self {
+        WktWriterOptions {
+            lowercase: false,
+            space_after_comma: true,
+            iso: true,
+        }
+    }
+}
+
 /// WKT Writer.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::wkt::{WktWriter, WktWriterOptions};
+/// use geozero::{CoordDimensions, GeozeroGeometry};
+///
+/// let mut wkt = Vec::new();
+/// let mut writer = WktWriter::with_options(&mut wkt, WktWriterOptions::iso());
+/// writer.dims = CoordDimensions::xyz();
+/// let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,2,3],[4,5,6]]}"#);
+/// geojson.process_geom(&mut writer).unwrap();
+/// assert_eq!(wkt, b"LINESTRING Z (1 2 3, 4 5 6)");
+/// ```
 pub struct WktWriter<'a, W: Write> {
     pub dims: CoordDimensions,
+    pub options: WktWriterOptions,
+    /// Opening parenthesis of current geometry not written yet
+    pub(super) pending: bool,
+    /// Space before pending parenthesis
+    pub(super) pending_space: bool,
+    /// Output of ISO tagged geometries waiting for their first coordinate
+    /// to determine the dimension tag, with nesting level
+    deferred: Vec<(usize, Vec<u8>)>,
+    /// Nesting level of current geometry
+    level: usize,
+    /// Dimensions (z, m) of the first coordinate of the current geometry
+    coord_dims: Option<(bool, bool)>,
     out: &'a mut W,
 }
 
 impl<'a, W: Write> WktWriter<'a, W> {
     pub fn new(out: &'a mut W) -> WktWriter<'a, W> {
+        Self::with_options(out, WktWriterOptions::default())
+    }
+    /// Writer with custom output style.
+    pub fn with_options(out: &'a mut W, options: WktWriterOptions) -> WktWriter<'a, W> {
         WktWriter {
             dims: CoordDimensions::default(),
+            options,
+            pending: false,
+            pending_space: false,
+            deferred: Vec::new(),
+            level: 0,
+            coord_dims: None,
             out,
         }
     }
+    /// Flush and return the output.
+    pub fn into_inner(mut self) -> Result<&'a mut W> {
+        self.flush_deferred()?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        if let Some((_, buf)) = self.deferred.last_mut() {
+            buf.extend_from_slice(data);
+        } else {
+            self.out.write_all(data)?;
+        }
+        Ok(())
+    }
+    /// Write output of incomplete geometries without dimension tags.
+    fn flush_deferred(&mut self) -> Result<()> {
+        for (_, buf) in std::mem::take(&mut self.deferred) {
+            self.out.write_all(&buf)?;
+        }
+        Ok(())
+    }
+    fn dimension_tag(&mut self, z: bool, m: bool) -> Result<()> {
+        match (z, m) {
+            (true, true) => self.keyword(b" ZM"),
+            (true, false) => self.keyword(b" Z"),
+            (false, true) => self.keyword(b" M"),
+            (false, false) => Ok(()),
+        }
+    }
+    /// Complete deferred geometries with the dimensions of their first coordinate.
+    fn resolve_deferred(&mut self, z: bool, m: bool) -> Result<()> {
+        self.coord_dims.get_or_insert((z, m));
+        for (_, buf) in std::mem::take(&mut self.deferred) {
+            self.dimension_tag(z, m)?;
+            self.out.write_all(&buf)?;
+        }
+        Ok(())
+    }
+    /// Write opening parenthesis of current geometry, if pending.
+    fn open(&mut self) -> Result<()> {
+        if self.pending {
+            if self.pending_space {
+                self.write(b" ")?;
+            }
+            self.write(b"(")?;
+            self.pending = false;
+        }
+        Ok(())
+    }
+    fn comma(&mut self) -> Result<()> {
+        if self.options.space_after_comma {
+            self.write(b", ")?;
+        } else {
+            self.write(b",")?;
+        }
+        Ok(())
+    }
+    fn keyword(&mut self, keyword: &[u8]) -> Result<()> {
+        if self.options.lowercase {
+            self.write(&keyword.to_ascii_lowercase())?;
+        } else {
+            self.write(keyword)?;
+        }
+        Ok(())
+    }
     fn geom_begin(&mut self, idx: usize, tag: &[u8]) -> Result<()> {
+        self.open()?;
         if idx > 0 {
-            self.out.write_all(b",")?;
+            self.comma()?;
+        }
+        self.keyword(tag)?;
+        let tagged = !tag.is_empty();
+        if self.level == 0 {
+            self.coord_dims = None;
         }
-        self.out.write_all(tag)?;
+        self.level += 1;
+        if tagged && self.options.iso {
+            // dimension tag is written with the first coordinate
+            self.deferred.push((self.level, Vec::new()));
+        }
+        self.pending = true;
+        self.pending_space = tagged && self.options.iso;
         Ok(())
     }
     fn tagged_geom_begin(&mut self, tagged: bool, idx: usize, tag: &[u8]) -> Result<()> {
-        if idx > 0 {
-            self.out.write_all(b",")?;
-        }
         if tagged {
-            self.out.write_all(tag)?;
+            self.geom_begin(idx, tag)
         } else {
-            self.out.write_all(b"(")?;
+            self.geom_begin(idx, b"")
         }
-        Ok(())
     }
     fn geom_end(&mut self) -> Result<()> {
-        self.out.write_all(b")")?;
+        if matches!(self.deferred.last(), Some((level, _)) if *level == self.level) {
+            // geometry without coordinates
+            let (_, buf) = self.deferred.pop().unwrap_or_default();
+            let (z, m) = self.coord_dims.unwrap_or((self.dims.z, self.dims.m));
+            self.dimension_tag(z, m)?;
+            self.write(&buf)?;
+        }
+        self.level = self.level.saturating_sub(1);
+        if self.pending {
+            // geometry without members
+            self.keyword(b" EMPTY")?;
+            self.pending = false;
+        } else {
+            self.write(b")")?;
+        }
+        Ok(())
+    }
+    fn tagged_geom_end(&mut self, tagged: bool) -> Result<()> {
+        if !tagged && self.pending {
+            self.keyword(b"EMPTY")?;
+            self.pending = false;
+            self.level = self.level.saturating_sub(1);
+            Ok(())
+        } else {
+            self.geom_end()
+        }
+    }
+    fn position(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.open()?;
+        if idx > 0 {
+            self.comma()?;
+        }
+        self.write(format!("{} {}", x, y).as_bytes())?;
         Ok(())
     }
 }
@@ -44,12 +217,8 @@ impl<W: Write> GeomProcessor for WktWriter<'_, W> {
         self.dims
     }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        if idx == 0 {
-            self.out.write_all(&format!("{} {}", x, y).as_bytes())?;
-        } else {
-            self.out.write_all(&format!(",{} {}", x, y).as_bytes())?;
-        }
-        Ok(())
+        self.resolve_deferred(false, false)?;
+        self.position(x, y, idx)
     }
     fn coordinate(
         &mut self,
@@ -61,115 +230,112 @@ impl<W: Write> GeomProcessor for WktWriter<'_, W> {
         _tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        if idx == 0 {
-            self.out.write_all(&format!("{} {}", x, y).as_bytes())?;
-        } else {
-            self.out.write_all(&format!(",{} {}", x, y).as_bytes())?;
-        }
+        self.resolve_deferred(z.is_some(), m.is_some())?;
+        self.position(x, y, idx)?;
         if let Some(z) = z {
-            self.out.write_all(&format!(" {}", z).as_bytes())?;
+            self.write(format!(" {}", z).as_bytes())?;
         }
         if let Some(m) = m {
-            self.out.write_all(&format!(" {}", m).as_bytes())?;
+            self.write(format!(" {}", m).as_bytes())?;
         }
         Ok(())
     }
 
     fn point_begin(&mut self, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"POINT(")
+        self.geom_begin(idx, b"POINT")
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
 
     fn empty_point(&mut self, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"POINT EMPTY")
-        // we intentionally omit calling geom_end(), because POINT EMPTY has no closing paren
+        self.geom_begin(idx, b"POINT")?;
+        // no point_end() call for empty points
+        self.geom_end()
     }
 
     fn multipoint_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"MULTIPOINT(")
+        self.geom_begin(idx, b"MULTIPOINT")
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn linestring_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
-        self.tagged_geom_begin(tagged, idx, b"LINESTRING(")
+        self.tagged_geom_begin(tagged, idx, b"LINESTRING")
     }
-    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
-        self.geom_end()
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.tagged_geom_end(tagged)
     }
     fn multilinestring_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"MULTILINESTRING(")
+        self.geom_begin(idx, b"MULTILINESTRING")
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn polygon_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
-        self.tagged_geom_begin(tagged, idx, b"POLYGON(")
+        self.tagged_geom_begin(tagged, idx, b"POLYGON")
     }
-    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
-        self.geom_end()
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.tagged_geom_end(tagged)
     }
     fn multipolygon_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"MULTIPOLYGON(")
+        self.geom_begin(idx, b"MULTIPOLYGON")
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
-    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
-        self.out.write_all(b"GEOMETRYCOLLECTION(")?;
-        Ok(())
+    fn geometrycollection_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(idx, b"GEOMETRYCOLLECTION")
     }
     fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn circularstring_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"CIRCULARSTRING(")
+        self.geom_begin(idx, b"CIRCULARSTRING")
     }
     fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn compoundcurve_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"COMPOUNDCURVE(")
+        self.geom_begin(idx, b"COMPOUNDCURVE")
     }
 
     fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn curvepolygon_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"CURVEPOLYGON(")
+        self.geom_begin(idx, b"CURVEPOLYGON")
     }
     fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn multicurve_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"MULTICURVE(")
+        self.geom_begin(idx, b"MULTICURVE")
     }
     fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn multisurface_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"MULTISURFACE(")
+        self.geom_begin(idx, b"MULTISURFACE")
     }
     fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
 
     fn triangle_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
-        self.tagged_geom_begin(tagged, idx, b"TRIANGLE(")
+        self.tagged_geom_begin(tagged, idx, b"TRIANGLE")
     }
-    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
-        self.geom_end()
+    fn triangle_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.tagged_geom_end(tagged)
     }
     fn polyhedralsurface_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"POLYHEDRALSURFACE(")
+        self.geom_begin(idx, b"POLYHEDRALSURFACE")
     }
     fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
     }
     fn tin_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
-        self.geom_begin(idx, b"TIN(")
+        self.geom_begin(idx, b"TIN")
     }
     fn tin_end(&mut self, _idx: usize) -> Result<()> {
         self.geom_end()
@@ -178,11 +344,121 @@ impl<W: Write> GeomProcessor for WktWriter<'_, W> {
 
 impl<W: Write> PropertyProcessor for WktWriter<'_, W> {}
 
-impl<W: Write> FeatureProcessor for WktWriter<'_, W> {}
+impl<W: Write> FeatureProcessor for WktWriter<'_, W> {
+    fn geometry_begin(&mut self) -> Result<()> {
+        // discard state of incomplete geometries
+        self.pending = false;
+        self.level = 0;
+        self.flush_deferred()
+    }
+}
 
 #[cfg(test)]
 mod test {
-    use crate::ToWkt;
+    use super::*;
+    use crate::{GeozeroGeometry, ToWkt};
+
+    #[test]
+    fn reuse() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.geometry_begin()?;
+        writer.linestring_begin(true, 0, 0)?;
+        writer.geometry_begin()?;
+        writer.point_begin(0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.point_end(0)?;
+        assert_eq!(writer.into_inner()?, b"LINESTRINGPOINT(1 2)");
+        Ok(())
+    }
+
+    fn write_wkt(options: WktWriterOptions, dims: CoordDimensions, wkt: &str) -> Result<String> {
+        write_geom(options, dims, &crate::wkt::WktStr(wkt))
+    }
+
+    fn write_geom<G: GeozeroGeometry>(
+        options: WktWriterOptions,
+        dims: CoordDimensions,
+        geom: &G,
+    ) -> Result<String> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::with_options(&mut out, options);
+        writer.dims = dims;
+        geom.process_geom(&mut writer)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn output_style() -> Result<()> {
+        let geojson = crate::geojson::GeoJson(
+            r#"{"type": "GeometryCollection", "geometries": [
+                {"type": "Point", "coordinates": [1, 2, 3]},
+                {"type": "LineString", "coordinates": []},
+                {"type": "Polygon", "coordinates": [[[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 0, 1]]]}
+            ]}"#,
+        );
+        let xyz = CoordDimensions::xyz();
+        assert_eq!(
+            write_geom(WktWriterOptions::compact(), xyz, &geojson)?,
+            "GEOMETRYCOLLECTION(POINT(1 2 3),LINESTRING EMPTY,POLYGON((0 0 1,1 0 1,1 1 1,0 0 1)))"
+        );
+        assert_eq!(
+            write_geom(WktWriterOptions::iso(), xyz, &geojson)?,
+            "GEOMETRYCOLLECTION Z (POINT Z (1 2 3), LINESTRING Z EMPTY, POLYGON Z ((0 0 1, 1 0 1, 1 1 1, 0 0 1)))"
+        );
+        let options = WktWriterOptions {
+            lowercase: true,
+            ..WktWriterOptions::iso()
+        };
+        assert_eq!(
+            write_wkt(options, CoordDimensions::xy(), "MULTIPOINT(1 2,3 4)")?,
+            "multipoint (1 2, 3 4)"
+        );
+        let options = WktWriterOptions {
+            space_after_comma: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            write_wkt(options, CoordDimensions::xy(), "LINESTRING(1 2,3 4)")?,
+            "LINESTRING(1 2, 3 4)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn iso_tag_from_coordinates() -> Result<()> {
+        // 2D input with 3D output dimensions
+        assert_eq!(
+            write_wkt(
+                WktWriterOptions::iso(),
+                CoordDimensions::xyz(),
+                "LINESTRING(1 2,4 5)"
+            )?,
+            "LINESTRING (1 2, 4 5)"
+        );
+
+        // input with M but without Z
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::with_options(&mut out, WktWriterOptions::iso());
+        writer.dims = CoordDimensions::xyzm();
+        writer.multipoint_begin(2, 0)?;
+        writer.coordinate(1.0, 2.0, None, Some(3.0), None, None, 0)?;
+        writer.coordinate(4.0, 5.0, None, Some(6.0), None, None, 1)?;
+        writer.multipoint_end(0)?;
+        writer.geometrycollection_begin(2, 0)?;
+        writer.point_begin(0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.point_end(0)?;
+        writer.linestring_begin(true, 0, 1)?;
+        writer.linestring_end(true, 1)?;
+        writer.geometrycollection_end(0)?;
+        assert_eq!(
+            std::str::from_utf8(writer.into_inner()?).unwrap(),
+            "MULTIPOINT M (1 2 3, 4 5 6)GEOMETRYCOLLECTION (POINT (1 2), LINESTRING EMPTY)"
+        );
+        Ok(())
+    }
 
     #[test]
     #[cfg(feature = "with-geo")]
@@ -0,0 +1,62 @@
+use crate::error::{GeozeroError, Result};
+use std::io::{BufWriter, Write};
+
+/// Buffered writer counting written bytes.
+///
+/// Writers like `WkbWriter` or `GeoJsonWriter` emit many small writes,
+/// which are expensive on unbuffered outputs like files or sockets.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::{geojson::GeoJsonWriter, wkt::WktStr, GeozeroGeometry, WriteCounter};
+///
+/// let mut out = WriteCounter::new(Vec::new());
+/// WktStr("POINT(1 2)").process_geom(&mut GeoJsonWriter::new(&mut out)).unwrap();
+/// assert_eq!(out.bytes_written(), 39);
+/// assert_eq!(out.into_inner().unwrap().len(), 39);
+/// ```
+pub struct WriteCounter<W: Write> {
+    inner: BufWriter<W>,
+    count: u64,
+}
+
+impl<W: Write> WriteCounter<W> {
+    pub fn new(inner: W) -> Self {
+        WriteCounter {
+            inner: BufWriter::new(inner),
+            count: 0,
+        }
+    }
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        WriteCounter {
+            inner: BufWriter::with_capacity(capacity, inner),
+            count: 0,
+        }
+    }
+    /// Number of bytes written, including buffered bytes.
+    pub fn bytes_written(&self) -> u64 {
+        self.count
+    }
+    /// Underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+    /// Flush buffer and return underlying writer.
+    pub fn into_inner(self) -> Result<W> {
+        self.inner
+            .into_inner()
+            .map_err(|e| GeozeroError::IoError(e.into()))
+    }
+}
+
+impl<W: Write> Write for WriteCounter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
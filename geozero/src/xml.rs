@@ -0,0 +1,163 @@
+//! Minimal XML element tree on top of a streaming parser.
+use crate::error::{GeozeroError, Result};
+use quick_xml::events::{BytesStart, Event};
+use std::io::BufRead;
+
+/// Maximal nesting depth of elements read into a tree.
+const MAX_NESTING_DEPTH: usize = 256;
+
+/// XML element with namespace prefixes stripped from element and attribute names.
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: String,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    fn from_start(start: &BytesStart) -> Result<Self> {
+        let name = String::from_utf8_lossy(start.local_name().as_ref()).to_string();
+        let mut attributes = Vec::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+            attributes.push((key, attr.unescape_value()?.to_string()));
+        }
+        Ok(Element {
+            name,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First child element with given name
+    #[cfg(any(feature = "with-georss", feature = "with-kml"))]
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Child elements with given name
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Text of first child element with given name
+    #[cfg(any(feature = "with-georss", feature = "with-kml"))]
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.as_str())
+    }
+}
+
+/// Streaming XML reader returning start tags and complete subtrees on demand.
+pub(crate) struct XmlReader<R: BufRead> {
+    reader: quick_xml::Reader<R>,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> XmlReader<R> {
+    pub fn new(reader: R) -> Self {
+        let mut reader = quick_xml::Reader::from_reader(reader);
+        reader.trim_text(true);
+        XmlReader {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Next start tag, skipping all other events. Returns `None` at end of input.
+    ///
+    /// The returned flag is true for empty elements (`<tag/>`).
+    pub fn next_start(&mut self) -> Result<Option<(Element, bool)>> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => return Ok(Some((Element::from_start(&e)?, false))),
+                Event::Empty(e) => return Ok(Some((Element::from_start(&e)?, true))),
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Read text and children of an element returned by `next_start` up to its end tag.
+    pub fn read_element(&mut self, element: Element, empty: bool) -> Result<Element> {
+        if empty {
+            return Ok(element);
+        }
+        self.read_subtree(element, 0)
+    }
+
+    fn read_subtree(&mut self, mut element: Element, depth: usize) -> Result<Element> {
+        if depth >= MAX_NESTING_DEPTH {
+            return Err(GeozeroError::LimitExceeded(format!(
+                "XML elements nested deeper than {} levels",
+                MAX_NESTING_DEPTH
+            )));
+        }
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => {
+                    let child = Element::from_start(&e)?;
+                    let child = self.read_subtree(child, depth + 1)?;
+                    element.children.push(child);
+                }
+                Event::Empty(e) => element.children.push(Element::from_start(&e)?),
+                Event::Text(e) => element.text.push_str(&e.unescape()?),
+                Event::CData(e) => element
+                    .text
+                    .push_str(&String::from_utf8_lossy(&e.into_inner())),
+                Event::End(_) => return Ok(element),
+                Event::Eof => {
+                    return Err(GeozeroError::Dataset(format!(
+                        "Unexpected end of XML in element `{}`",
+                        element.name
+                    )))
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl From<quick_xml::Error> for GeozeroError {
+    fn from(error: quick_xml::Error) -> Self {
+        match error {
+            quick_xml::Error::Io(io) => match std::sync::Arc::try_unwrap(io) {
+                Ok(io) => GeozeroError::IoError(io),
+                Err(io) => GeozeroError::Dataset(io.to_string()),
+            },
+            other => GeozeroError::Dataset(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nesting_depth() -> Result<()> {
+        let xml = format!("{}{}", "<a>".repeat(300), "</a>".repeat(300));
+        let mut reader = XmlReader::new(xml.as_bytes());
+        let (root, empty) = reader.next_start()?.unwrap();
+        assert!(matches!(
+            reader.read_element(root, empty),
+            Err(GeozeroError::LimitExceeded(_))
+        ));
+
+        let xml = format!("{}{}", "<a>".repeat(10), "</a>".repeat(10));
+        let mut reader = XmlReader::new(xml.as_bytes());
+        let (root, empty) = reader.next_start()?.unwrap();
+        assert_eq!(reader.read_element(root, empty)?.children.len(), 1);
+        Ok(())
+    }
+}
@@ -99,3 +99,182 @@ async fn geos_query() -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+#[cfg(all(feature = "with-tokio", feature = "with-geojson"))]
+#[tokio::test]
+async fn gpkg_writer() -> geozero::error::Result<()> {
+    use geozero::geojson::GeoJsonString;
+    use geozero::gpkg::GpkgWriter;
+    use geozero::{process_datasource_async, ToWkt};
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::{ConnectOptions, Connection};
+    use std::str::FromStr;
+
+    let geojson = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "A", "population": 10}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"name": "B", "area": 2.5}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+        {"type": "Feature", "properties": {"name": "C"}, "geometry": null}
+    ]}"#;
+    let conn = SqliteConnectOptions::from_str("sqlite::memory:")
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut writer = GpkgWriter::new(conn, "places");
+    writer.srid = Some(4326);
+    process_datasource_async(GeoJsonString(geojson.to_string()), &mut writer).await?;
+    let mut conn = writer.into_inner();
+
+    let row: (String, String, i32) = sqlx::query_as(
+        "SELECT column_name, geometry_type_name, srs_id FROM gpkg_geometry_columns WHERE table_name = 'places'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert_eq!(row, ("geom".to_string(), "POINT".to_string(), 4326));
+
+    let row: (f64, f64, f64, f64) =
+        sqlx::query_as("SELECT min_x, min_y, max_x, max_y FROM gpkg_contents")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(row, (1.0, 2.0, 3.0, 4.0));
+
+    type PlaceRow = (String, Option<i64>, Option<f64>, Option<Vec<u8>>);
+    let rows: Vec<PlaceRow> =
+        sqlx::query_as("SELECT name, population, area, geom FROM places ORDER BY fid")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!((rows[0].1, rows[1].2), (Some(10), Some(2.5)));
+    let geom = wkb::GpkgWkb(rows[1].3.clone().unwrap());
+    assert_eq!(geom.to_wkt()?, "POINT(3 4)");
+    assert!(rows[2].3.is_none());
+
+    let count: (i64,) = sqlx::query_as("SELECT count(*) FROM rtree_places_geom WHERE minx >= 2")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(count.0, 1);
+    conn.close().await.unwrap();
+
+    Ok(())
+}
+
+#[cfg(feature = "with-tokio")]
+#[tokio::test]
+async fn gpkg_writer_metadata() -> geozero::error::Result<()> {
+    use geozero::gpkg::{GpkgReader, GpkgWriter};
+    use geozero::{
+        AsyncFeatureProcessor, DatasetMetadata, FeatureProcessor, GeomProcessor, PropertyProcessor,
+    };
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+
+    let conn = SqliteConnectOptions::from_str("sqlite::memory:")
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut writer = GpkgWriter::new(conn, "places");
+    writer.dataset_begin(None).await?;
+    writer.dataset_srid(Some(2056)).await?;
+    writer
+        .dataset_metadata(&DatasetMetadata {
+            title: Some("Places".to_string()),
+            description: Some("Populated places".to_string()),
+            crs_wkt: Some("PROJCS[\"CH1903+ / LV95\"]".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    writer.dataset_end().await?;
+    let mut conn = writer.into_inner();
+
+    let row: (String, String) = sqlx::query_as(
+        "SELECT identifier, description FROM gpkg_contents WHERE table_name = 'places'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert_eq!(row, ("Places".to_string(), "Populated places".to_string()));
+    let row: (String,) =
+        sqlx::query_as("SELECT definition FROM gpkg_spatial_ref_sys WHERE srs_id = 2056")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(row.0, "PROJCS[\"CH1903+ / LV95\"]");
+
+    // metadata is passed to processors of GpkgReader
+    struct Metadata(Option<DatasetMetadata>);
+    impl GeomProcessor for Metadata {}
+    impl PropertyProcessor for Metadata {}
+    impl FeatureProcessor for Metadata {
+        fn dataset_metadata(&mut self, metadata: &DatasetMetadata) -> geozero::error::Result<()> {
+            self.0 = Some(metadata.clone());
+            Ok(())
+        }
+    }
+    let mut reader = GpkgReader::new(conn, "places").await?;
+    let mut processor = Metadata(None);
+    reader.process(&mut processor).await?;
+    assert_eq!(
+        processor.0,
+        Some(DatasetMetadata {
+            title: Some("Places".to_string()),
+            description: Some("Populated places".to_string()),
+            crs_wkt: Some("PROJCS[\"CH1903+ / LV95\"]".to_string()),
+            ..Default::default()
+        })
+    );
+
+    Ok(())
+}
+
+#[cfg(all(feature = "with-tokio", feature = "with-geojson"))]
+#[tokio::test]
+async fn gpkg_reader() -> geozero::error::Result<()> {
+    use geozero::geojson::{GeoJsonString, GeoJsonWriter};
+    use geozero::gpkg::{GpkgReader, GpkgWriter};
+    use geozero::process_datasource_async;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+
+    let geojson = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "A", "population": 10}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"name": "B", "area": 2.5}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+        {"type": "Feature", "properties": {"name": "C"}, "geometry": null}
+    ]}"#;
+    let conn = SqliteConnectOptions::from_str("sqlite::memory:")
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut writer = GpkgWriter::new(conn, "places");
+    process_datasource_async(GeoJsonString(geojson.to_string()), &mut writer).await?;
+    let mut reader = GpkgReader::new(writer.into_inner(), "places").await?;
+    assert_eq!(reader.geometry_column, "geom");
+    assert_eq!(reader.fid_column, "fid");
+    assert_eq!(reader.feature_count().await?, 3);
+
+    let mut out: Vec<u8> = Vec::new();
+    reader
+        .select_by_id(&[2, 7, 1], &mut GeoJsonWriter::new(&mut out))
+        .await?;
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(r#""properties": {"name": "B", "area": 2.5}"#));
+    assert!(out.find(r#""name": "B""#) < out.find(r#""name": "A""#));
+    assert!(!out.contains(r#""name": "C""#));
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(reader.seek(2, &mut GeoJsonWriter::new(&mut out)).await?);
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(r#""properties": {"name": "C"}, "geometry": null"#));
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(!reader.seek(3, &mut GeoJsonWriter::new(&mut out)).await?);
+
+    Ok(())
+}
@@ -84,6 +84,53 @@ fn test_wikipedia_example() {
     );
 }
 
+#[test]
+fn test_wikipedia_example_timestamps() {
+    let gpx_str = include_str!("data/wikipedia_example.gpx");
+    let mut cursor = io::Cursor::new(gpx_str);
+    let mut writer = TestWriter::default();
+    writer.1.z = true;
+    writer.1.tm = true;
+
+    geozero::gpx::read_gpx(&mut cursor, &mut writer).unwrap();
+
+    #[rustfmt::skip]
+    assert_eq!(
+        writer.0,
+        vec![
+            Cmd::GeometryCollectionBegin { idx: 0, size: 1 },
+                Cmd::MultiLineStringBegin { idx: 0 },
+                    Cmd::LineStringBegin { idx: 0 },
+                        Cmd::Coordinate { idx: 0, x: -122.326897, y: 47.644548, z: Some(4.46), m: None, t: None, tm: Some(1255804646000000000) },
+                        Cmd::Coordinate { idx: 1, x: -122.326897, y: 47.644548, z: Some(4.94), m: None, t: None, tm: Some(1255804651000000000) },
+                        Cmd::Coordinate { idx: 2, x: -122.326897, y: 47.644548, z: Some(6.87), m: None, t: None, tm: Some(1255804654000000000) },
+                    Cmd::LineStringEnd { idx: 0 },
+                Cmd::MultiLineStringEnd { idx: 0 },
+            Cmd::GeometryCollectionEnd { idx: 0 },
+        ]
+    );
+}
+
+#[test]
+fn test_gpx_roundtrip() {
+    use geozero::ToGpx;
+
+    let gpx_str = include_str!("data/wikipedia_example.gpx");
+    let written = Gpx(gpx_str).to_gpx().unwrap();
+    let mut writer = TestWriter::default();
+    writer.1.tm = true;
+    geozero::gpx::read_gpx(&mut written.as_bytes(), &mut writer).unwrap();
+    assert!(writer.0.contains(&Cmd::Coordinate {
+        idx: 2,
+        x: -122.326897,
+        y: 47.644548,
+        z: None,
+        m: None,
+        t: None,
+        tm: Some(1255804654000000000)
+    }));
+}
+
 mod wikipedia_example_conversions {
     use super::*;
 
@@ -0,0 +1,57 @@
+use geozero::error::Result;
+use geozero::geojson::{GeoJson, GeoJsonWriter};
+use geozero::mbtiles::{MbtilesReader, MbtilesWriter};
+use geozero::tiling::{generate_tiles, TilingOptions};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::ConnectOptions;
+use std::str::FromStr;
+
+#[tokio::test]
+async fn write_and_read() -> Result<()> {
+    let geojson = r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [8.5, 47.3]}}"#;
+    let options = TilingOptions {
+        max_zoom: 3,
+        layer_name: "places".to_string(),
+        ..Default::default()
+    };
+    let mut tiles = Vec::new();
+    generate_tiles(&mut GeoJson(geojson), &options, &mut tiles)?;
+    assert_eq!(tiles.len(), 4);
+
+    let conn = SqliteConnectOptions::from_str("sqlite::memory:")
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut writer = MbtilesWriter::new(conn).await?;
+    writer.set_metadata("name", "places").await?;
+    writer.write_tiles(&tiles).await?;
+    writer.finish().await?;
+
+    let mut reader = MbtilesReader::new(writer.into_inner());
+    let metadata = reader.metadata().await?;
+    assert_eq!(metadata["name"], "places");
+    assert_eq!(metadata["format"], "pbf");
+    assert_eq!(metadata["minzoom"], "0");
+    assert_eq!(metadata["maxzoom"], "3");
+    assert_eq!(
+        reader.tile_coords().await?,
+        [(0, 0, 0), (1, 1, 0), (2, 2, 1), (3, 4, 2)]
+    );
+    assert_eq!(reader.tile(3, 4, 2).await?.as_ref(), Some(&tiles[3].3));
+    assert_eq!(reader.tile(3, 4, 5).await?, None);
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(
+        reader
+            .process_tile(3, 4, 2, &mut GeoJsonWriter::new(&mut out))
+            .await?
+    );
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(r#""name": "a""#));
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut writer = GeoJsonWriter::new(&mut out);
+    assert!(!reader.process_tile(3, 0, 0, &mut writer).await?);
+    Ok(())
+}
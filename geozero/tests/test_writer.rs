@@ -1,4 +1,5 @@
 use geozero::error::Result;
+use geozero::CoordDimensions;
 
 #[derive(Debug, PartialEq)]
 pub enum Cmd {
@@ -64,10 +65,15 @@ pub enum Cmd {
     },
 }
 
+/// Recorded commands and requested dimensions
 #[derive(Default)]
-pub struct TestWriter(pub Vec<Cmd>);
+pub struct TestWriter(pub Vec<Cmd>, pub CoordDimensions);
 
 impl geozero::GeomProcessor for TestWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.1
+    }
+
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         self.0.push(Cmd::Xy { idx, x, y });
         Ok(())